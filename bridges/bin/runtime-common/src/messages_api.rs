@@ -17,7 +17,8 @@
 //! Helpers for implementing various message-related runtime API mthods.
 
 use bp_messages::{
-	InboundMessageDetails, LaneId, MessageNonce, MessagePayload, OutboundMessageDetails,
+	InboundMessageDetails, LaneId, LanePriority, MessageNonce, MessagePayload,
+	OutboundMessageDetails,
 };
 use sp_std::vec::Vec;
 
@@ -46,6 +47,31 @@ where
 		.collect()
 }
 
+/// Implementation of the `To*OutboundLaneApi::messages_pending_confirmation`.
+pub fn outbound_messages_pending_confirmation<Runtime, MessagesPalletInstance>(
+	lane: LaneId,
+) -> MessageNonce
+where
+	Runtime: pallet_bridge_messages::Config<MessagesPalletInstance>,
+	MessagesPalletInstance: 'static,
+{
+	pallet_bridge_messages::Pallet::<Runtime, MessagesPalletInstance>::outbound_lane_data(lane)
+		.messages_pending_confirmation()
+}
+
+/// Implementation of the `To*OutboundLaneApi::lanes_by_priority`.
+pub fn outbound_lanes_by_priority<Runtime, MessagesPalletInstance>(
+	lanes: Vec<LaneId>,
+) -> Vec<(LaneId, LanePriority)>
+where
+	Runtime: pallet_bridge_messages::Config<MessagesPalletInstance>,
+	MessagesPalletInstance: 'static,
+{
+	pallet_bridge_messages::Pallet::<Runtime, MessagesPalletInstance>::outbound_lanes_by_priority(
+		lanes,
+	)
+}
+
 /// Implementation of the `To*InboundLaneApi::message_details`.
 pub fn inbound_message_details<Runtime, MessagesPalletInstance>(
 	lane: LaneId,
@@ -30,7 +30,7 @@ use crate::messages::{
 use bp_header_chain::{ChainWithGrandpa, HeaderChain};
 use bp_messages::{
 	target_chain::{DispatchMessage, MessageDispatch},
-	LaneId, MessageNonce,
+	ChainWithMessages, LaneId, MessageNonce,
 };
 use bp_parachains::SingleParaStoredHeaderDataBuilder;
 use bp_relayers::PayRewardFromAccount;
@@ -138,6 +138,7 @@ parameter_types! {
 	pub MaximumMultiplier: Multiplier = sp_runtime::traits::Bounded::max_value();
 	pub const MaxUnrewardedRelayerEntriesAtInboundLane: MessageNonce = 16;
 	pub const MaxUnconfirmedMessagesAtInboundLane: MessageNonce = 1_000;
+	pub const MaxMessagesPerRelayerPerBlock: MessageNonce = MessageNonce::MAX;
 	pub const ReserveId: [u8; 8] = *b"brdgrlrs";
 }
 
@@ -205,6 +206,8 @@ impl pallet_bridge_messages::Config for TestRuntime {
 	type ActiveOutboundLanes = ActiveOutboundLanes;
 	type MaxUnrewardedRelayerEntriesAtInboundLane = MaxUnrewardedRelayerEntriesAtInboundLane;
 	type MaxUnconfirmedMessagesAtInboundLane = MaxUnconfirmedMessagesAtInboundLane;
+	type ThisChain = ThisUnderlyingChain;
+	type UpgradePeriod = ConstU32<10>;
 
 	type MaximalOutboundPayloadSize = FromThisChainMaximalOutboundPayloadSize<OnThisChainBridge>;
 	type OutboundPayload = FromThisChainMessagePayload;
@@ -224,6 +227,10 @@ impl pallet_bridge_messages::Config for TestRuntime {
 	type SourceHeaderChain = SourceHeaderChainAdapter<OnThisChainBridge>;
 	type MessageDispatch = DummyMessageDispatch;
 	type BridgedChainId = BridgedChainId;
+
+	type FeeEscrowCurrency = Balances;
+	type FeeEscrowPeriod = ConstU32<10>;
+	type MaxFeeEscrowsPerBlock = ConstU32<4>;
 }
 
 impl pallet_bridge_relayers::Config for TestRuntime {
@@ -335,6 +342,13 @@ impl Chain for ThisUnderlyingChain {
 	}
 }
 
+impl ChainWithMessages for ThisUnderlyingChain {
+	const WITH_CHAIN_MESSAGES_PALLET_NAME: &'static str = "BridgeMessages";
+	const MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX: MessageNonce = 16;
+	const MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX: MessageNonce = 1_000;
+	type MaxMessagesPerRelayerPerBlock = MaxMessagesPerRelayerPerBlock;
+}
+
 /// The chain where we are in tests.
 pub struct ThisChain;
 
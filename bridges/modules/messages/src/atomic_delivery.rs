@@ -0,0 +1,607 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Atomic delivery of messages over two inbound lanes.
+//!
+//! Some protocol actions (e.g. opening a bidirectional channel) only make sense if messages of
+//! both lanes are delivered. The [`CrossLaneDeliveryCoordinator`] either delivers all expected
+//! messages of both lanes, or none of them.
+//!
+//! Messages of both lanes are delivered in a single storage transaction, which is committed if
+//! all messages are delivered and rolled back otherwise.
+
+use crate::{
+	ensure_not_paused, ensure_relayer_may_deliver, gap_detection_weight, inbound_lane,
+	lane_state_weight, note_relayer_delivery, relayer_delivery_weight,
+	verify_and_decode_messages_proof, Config, Error, Event, MessagesProofOf, Pallet, WeightInfoExt,
+	LOG_TARGET,
+};
+
+use bp_messages::{
+	target_chain::{DeliveryPayments, DispatchMessage, MessageDispatch, ProvedLaneMessages},
+	LaneId, MessageNonce, ReceivalResult, ReceivedMessages,
+};
+use bp_runtime::{OwnedBridgeModule, RangeInclusiveExt};
+use frame_support::{
+	ensure,
+	storage::{with_transaction, TransactionOutcome},
+	traits::Get,
+	weights::Weight,
+};
+use frame_system::{ensure_signed, pallet_prelude::OriginFor};
+use sp_runtime::{DispatchError, DispatchResult};
+use sp_std::{marker::PhantomData, ops::RangeInclusive, prelude::*};
+
+/// Received messages of a single lane, along with their dispatch results.
+type ReceivedLaneMessages<T, I> =
+	ReceivedMessages<<<T as Config<I>>::MessageDispatch as MessageDispatch>::DispatchLevelResult>;
+
+/// Atomic delivery, started by the [`CrossLaneDeliveryCoordinator::begin_atomic_delivery`].
+///
+/// The token holds the messages ranges, expected by both lanes. It must be either committed or
+/// aborted.
+#[derive(Debug, PartialEq, Eq)]
+#[must_use]
+pub struct DeliveryToken {
+	lane_a: (LaneId, RangeInclusive<MessageNonce>),
+	lane_b: (LaneId, RangeInclusive<MessageNonce>),
+}
+
+/// Coordinator of the message delivery over two inbound lanes.
+pub struct CrossLaneDeliveryCoordinator<T, I = ()>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> CrossLaneDeliveryCoordinator<T, I> {
+	/// Returns weight of the atomic delivery of messages from the `proof`.
+	///
+	/// The benchmarked delivery weight only covers a single inbound lane, so the weight of
+	/// updating the other lane is added here.
+	pub fn delivery_weight(
+		proof: &MessagesProofOf<T, I>,
+		messages_count: u32,
+		dispatch_weight: Weight,
+	) -> Weight {
+		T::WeightInfo::receive_messages_proof_weight(proof, messages_count, dispatch_weight)
+			.saturating_add(relayer_delivery_weight::<T, I>())
+			.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+			.saturating_add(gap_detection_weight::<T, I>().saturating_mul(2))
+			.saturating_add(lane_state_weight::<T, I>().saturating_mul(2))
+	}
+
+	/// Start delivery of the given messages range over both lanes.
+	///
+	/// Fails if any of lanes is closed or if the range doesn't start right after the latest
+	/// message, delivered over the lane. Nothing is changed until the delivery is committed.
+	pub fn begin_atomic_delivery(
+		lane_a: (LaneId, RangeInclusive<MessageNonce>),
+		lane_b: (LaneId, RangeInclusive<MessageNonce>),
+	) -> Result<DeliveryToken, DispatchError> {
+		ensure!(lane_a.0 != lane_b.0, Error::<T, I>::AtomicDeliveryNoncesMismatch);
+		Self::ensure_lane_expects(&lane_a)?;
+		Self::ensure_lane_expects(&lane_b)?;
+
+		Ok(DeliveryToken { lane_a, lane_b })
+	}
+
+	/// Deliver messages of both lanes of the `token`.
+	///
+	/// The messages `proof` is handled the same way as by the `receive_messages_proof` call: the
+	/// `origin` must be signed by the relayer, the pallet must be operating normally, the proof
+	/// is verified, the `dispatch_weight` must cover dispatch of all messages, the messages are
+	/// counted towards the per-block limit of the relayer and the relayer is rewarded for the
+	/// delivery.
+	///
+	/// The proof must contain exactly the expected messages of both lanes. If any of those
+	/// messages is not accepted by its lane, all changes made by the delivery are rolled back.
+	/// Returns the actual weight of the delivery.
+	pub fn commit_atomic_delivery(
+		origin: OriginFor<T>,
+		token: DeliveryToken,
+		relayer_id_at_bridged_chain: &T::InboundRelayer,
+		proof: MessagesProofOf<T, I>,
+		messages_count: u32,
+		dispatch_weight: Weight,
+	) -> Result<Weight, DispatchError> {
+		with_transaction(|| {
+			let result = Self::deliver(
+				origin,
+				token.lane_a,
+				token.lane_b,
+				relayer_id_at_bridged_chain,
+				proof,
+				messages_count,
+				dispatch_weight,
+			);
+			match result {
+				Ok(_) => TransactionOutcome::Commit(result),
+				Err(_) => TransactionOutcome::Rollback(result),
+			}
+		})
+	}
+
+	/// Abort the atomic delivery.
+	///
+	/// No messages are delivered over any of lanes.
+	pub fn abort_atomic_delivery(token: DeliveryToken) {
+		log::trace!(
+			target: LOG_TARGET,
+			"Aborted atomic delivery of messages {:?} at lane {:?} and {:?} at lane {:?}",
+			token.lane_a.1,
+			token.lane_a.0,
+			token.lane_b.1,
+			token.lane_b.0,
+		);
+	}
+
+	/// Ensure that the lane is waiting for the given messages range.
+	fn ensure_lane_expects(
+		(lane_id, nonces): &(LaneId, RangeInclusive<MessageNonce>),
+	) -> DispatchResult {
+		ensure!(!Pallet::<T, I>::lane_state(*lane_id).is_closed(), Error::<T, I>::LaneClosed);
+
+		let next_nonce = Pallet::<T, I>::inbound_lane_data(*lane_id)
+			.last_delivered_nonce()
+			.checked_add(1);
+		ensure!(
+			!nonces.is_empty() && Some(*nonces.start()) == next_nonce,
+			Error::<T, I>::AtomicDeliveryNoncesMismatch
+		);
+		Ok(())
+	}
+
+	/// Verify the messages proof and deliver all expected messages of both lanes.
+	fn deliver(
+		origin: OriginFor<T>,
+		lane_a: (LaneId, RangeInclusive<MessageNonce>),
+		lane_b: (LaneId, RangeInclusive<MessageNonce>),
+		relayer_id_at_bridged_chain: &T::InboundRelayer,
+		proof: MessagesProofOf<T, I>,
+		messages_count: u32,
+		dispatch_weight: Weight,
+	) -> Result<Weight, DispatchError> {
+		Pallet::<T, I>::ensure_not_halted().map_err(Error::<T, I>::BridgeModule)?;
+		ensure_not_paused::<T, I>()?;
+		let relayer_id_at_this_chain = ensure_signed(origin)?;
+		ensure!(
+			MessageNonce::from(messages_count) <= T::MaxUnconfirmedMessagesAtInboundLane::get(),
+			Error::<T, I>::TooManyMessagesInTheProof
+		);
+		ensure!(
+			MessageNonce::from(messages_count) ==
+				lane_a.1.saturating_len().saturating_add(lane_b.1.saturating_len()),
+			Error::<T, I>::AtomicDeliveryNoncesMismatch
+		);
+		ensure_relayer_may_deliver::<T, I>(&relayer_id_at_this_chain, messages_count.into())?;
+		ensure!(T::MessageDispatch::is_active(), Error::<T, I>::MessageDispatchInactive);
+
+		let mut actual_weight = Self::delivery_weight(&proof, messages_count, dispatch_weight);
+		let mut messages = verify_and_decode_messages_proof::<
+			T::SourceHeaderChain,
+			T::InboundPayload,
+		>(proof, messages_count)
+		.map_err(|err| {
+			log::trace!(target: LOG_TARGET, "Rejecting invalid atomic delivery proof: {:?}", err);
+
+			Error::<T, I>::InvalidMessagesProof
+		})?;
+
+		let mut dispatch_weight_left = dispatch_weight;
+		let mut messages_received_status = Vec::with_capacity(2);
+		for lane in [lane_a, lane_b] {
+			let lane_data = messages.remove(&lane.0).unwrap_or_default();
+			messages_received_status.push(Self::deliver_lane_messages(
+				lane,
+				relayer_id_at_bridged_chain,
+				lane_data,
+				&mut dispatch_weight_left,
+				&mut actual_weight,
+			)?);
+		}
+		// the proof must not bring messages of other lanes
+		ensure!(messages.is_empty(), Error::<T, I>::AtomicDeliveryNoncesMismatch);
+
+		// all messages have been dispatched, so they are counted towards the relayer limit
+		note_relayer_delivery::<T, I>(&relayer_id_at_this_chain, messages_count.into());
+
+		T::DeliveryPayments::pay_reward(
+			relayer_id_at_this_chain,
+			messages_count.into(),
+			messages_count.into(),
+			actual_weight,
+		);
+		Pallet::<T, I>::deposit_event(Event::MessagesReceived(messages_received_status));
+
+		Ok(actual_weight)
+	}
+
+	/// Deliver all expected messages over the lane.
+	fn deliver_lane_messages(
+		(lane_id, nonces): (LaneId, RangeInclusive<MessageNonce>),
+		relayer_id_at_bridged_chain: &T::InboundRelayer,
+		lane_data: ProvedLaneMessages<DispatchMessage<T::InboundPayload>>,
+		dispatch_weight_left: &mut Weight,
+		actual_weight: &mut Weight,
+	) -> Result<ReceivedLaneMessages<T, I>, DispatchError> {
+		ensure!(
+			lane_data.messages.iter().map(|message| message.key.nonce).eq(nonces),
+			Error::<T, I>::AtomicDeliveryNoncesMismatch
+		);
+
+		let mut lane = inbound_lane::<T, I>(lane_id);
+
+		// there may be less unrewarded relayers than the maximal configured value
+		let lane_extra_proof_size_bytes = lane.storage_mut().extra_proof_size_bytes();
+		*actual_weight = actual_weight
+			.set_proof_size(actual_weight.proof_size().saturating_sub(lane_extra_proof_size_bytes));
+
+		if let Some(lane_state) = lane_data.lane_state {
+			lane.receive_state_update(lane_state);
+		}
+
+		let mut lane_messages_received_status =
+			ReceivedMessages::new(lane_id, Vec::with_capacity(lane_data.messages.len()));
+		for mut message in lane_data.messages {
+			let nonce = message.key.nonce;
+			let message_dispatch_weight = T::MessageDispatch::dispatch_weight(&mut message);
+			ensure!(
+				!message_dispatch_weight.any_gt(*dispatch_weight_left),
+				Error::<T, I>::InsufficientDispatchWeight
+			);
+
+			let receival_result = lane.receive_message::<T::MessageDispatch>(
+				relayer_id_at_bridged_chain,
+				nonce,
+				message.data,
+			);
+			let unspent_weight = match receival_result {
+				ReceivalResult::Dispatched(ref dispatch_result) =>
+					dispatch_result.unspent_weight.min(message_dispatch_weight),
+				_ => {
+					log::trace!(
+						target: LOG_TARGET,
+						"Inbound lane {:?} has rejected atomically delivered message {}: {:?}",
+						lane_id,
+						nonce,
+						receival_result,
+					);
+					return Err(Error::<T, I>::AtomicDeliveryFailed.into())
+				},
+			};
+			*dispatch_weight_left -= message_dispatch_weight - unspent_weight;
+			*actual_weight = actual_weight.saturating_sub(unspent_weight);
+
+			lane_messages_received_status.push(nonce, receival_result);
+		}
+
+		if lane.storage_mut().get_or_init_data().has_gap() {
+			log::error!(
+				target: LOG_TARGET,
+				"Detected a gap between atomically delivered messages at inbound lane {:?}",
+				lane_id,
+			);
+			Pallet::<T, I>::deposit_event(Event::GapDetected { lane_id });
+		}
+
+		Ok(lane_messages_received_status)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		mock::{
+			run_test, MaxMessagesPerRelayerPerBlock, MaxUnrewardedRelayerEntriesAtInboundLane,
+			RuntimeOrigin, TestMessagesProof, TestRuntime, REGULAR_PAYLOAD, TEST_LANE_ID,
+			TEST_LANE_ID_2, TEST_RELAYER_A, TEST_RELAYER_B,
+		},
+		InboundLanes, PalletOperatingMode,
+	};
+
+	use bp_messages::{
+		DeliveredMessages, InboundLaneData, Message, MessageKey, MessagesOperatingMode,
+		UnrewardedRelayer,
+	};
+	use bp_runtime::BasicOperatingMode;
+	use codec::Encode;
+	use frame_support::{assert_noop, assert_ok};
+
+	type Coordinator = CrossLaneDeliveryCoordinator<TestRuntime, ()>;
+
+	fn lane_messages(lane_id: LaneId, nonces: RangeInclusive<MessageNonce>) -> Vec<Message> {
+		nonces
+			.map(|nonce| Message {
+				key: MessageKey { lane_id, nonce },
+				payload: REGULAR_PAYLOAD.encode(),
+			})
+			.collect()
+	}
+
+	fn commit(
+		token: DeliveryToken,
+		relayer: u64,
+		messages: Vec<Message>,
+	) -> Result<Weight, DispatchError> {
+		let messages_count = messages.len() as u32;
+		Coordinator::commit_atomic_delivery(
+			RuntimeOrigin::signed(relayer),
+			token,
+			&TEST_RELAYER_A,
+			TestMessagesProof::from(Ok(messages)),
+			messages_count,
+			REGULAR_PAYLOAD.declared_weight * messages_count as u64,
+		)
+	}
+
+	fn last_delivered_nonce(lane_id: LaneId) -> MessageNonce {
+		Pallet::<TestRuntime, ()>::inbound_lane_data(lane_id).last_delivered_nonce()
+	}
+
+	#[test]
+	fn atomic_delivery_over_two_lanes_works() {
+		run_test(|| {
+			let token =
+				Coordinator::begin_atomic_delivery((TEST_LANE_ID, 1..=2), (TEST_LANE_ID_2, 1..=1))
+					.unwrap();
+			assert_ok!(commit(
+				token,
+				1,
+				[lane_messages(TEST_LANE_ID, 1..=2), lane_messages(TEST_LANE_ID_2, 1..=1)].concat(),
+			));
+
+			assert_eq!(last_delivered_nonce(TEST_LANE_ID), 2);
+			assert_eq!(last_delivered_nonce(TEST_LANE_ID_2), 1);
+			assert_eq!(Pallet::<TestRuntime, ()>::relayer_deliveries_this_block(&1), 3);
+		});
+	}
+
+	#[test]
+	fn atomic_delivery_is_rolled_back_if_second_lane_fails() {
+		run_test(|| {
+			// the second lane won't accept messages from one more relayer
+			let max_entries: MessageNonce = MaxUnrewardedRelayerEntriesAtInboundLane::get();
+			InboundLanes::<TestRuntime, ()>::insert(
+				TEST_LANE_ID_2,
+				crate::StoredInboundLaneData(InboundLaneData {
+					relayers: (1..=max_entries)
+						.map(|nonce| UnrewardedRelayer {
+							relayer: if nonce % 2 == 0 { TEST_RELAYER_B } else { TEST_RELAYER_A },
+							messages: DeliveredMessages::new(nonce),
+						})
+						.collect(),
+					last_confirmed_nonce: 0,
+				}),
+			);
+
+			let token = Coordinator::begin_atomic_delivery(
+				(TEST_LANE_ID, 1..=1),
+				(TEST_LANE_ID_2, max_entries + 1..=max_entries + 1),
+			)
+			.unwrap();
+			assert_noop!(
+				commit(
+					token,
+					1,
+					[
+						lane_messages(TEST_LANE_ID, 1..=1),
+						lane_messages(TEST_LANE_ID_2, max_entries + 1..=max_entries + 1),
+					]
+					.concat(),
+				),
+				Error::<TestRuntime, ()>::AtomicDeliveryFailed,
+			);
+
+			assert_eq!(last_delivered_nonce(TEST_LANE_ID), 0);
+			assert_eq!(last_delivered_nonce(TEST_LANE_ID_2), max_entries);
+		});
+	}
+
+	#[test]
+	fn atomic_delivery_is_aborted_if_nonces_do_not_align() {
+		run_test(|| {
+			assert_noop!(
+				Coordinator::begin_atomic_delivery((TEST_LANE_ID, 1..=1), (TEST_LANE_ID_2, 2..=2)),
+				Error::<TestRuntime, ()>::AtomicDeliveryNoncesMismatch,
+			);
+
+			// proof doesn't contain all expected messages
+			let token =
+				Coordinator::begin_atomic_delivery((TEST_LANE_ID, 1..=2), (TEST_LANE_ID_2, 1..=1))
+					.unwrap();
+			assert_noop!(
+				commit(
+					token,
+					1,
+					[lane_messages(TEST_LANE_ID, 1..=1), lane_messages(TEST_LANE_ID_2, 1..=1)]
+						.concat(),
+				),
+				Error::<TestRuntime, ()>::AtomicDeliveryNoncesMismatch,
+			);
+
+			let token =
+				Coordinator::begin_atomic_delivery((TEST_LANE_ID, 1..=1), (TEST_LANE_ID_2, 1..=1))
+					.unwrap();
+			Coordinator::abort_atomic_delivery(token);
+			assert_eq!(last_delivered_nonce(TEST_LANE_ID), 0);
+			assert_eq!(last_delivered_nonce(TEST_LANE_ID_2), 0);
+		});
+	}
+
+	#[test]
+	fn receive_messages_proof_atomically_works() {
+		run_test(|| {
+			let messages =
+				[lane_messages(TEST_LANE_ID, 1..=2), lane_messages(TEST_LANE_ID_2, 1..=1)].concat();
+			let proof = TestMessagesProof::from(Ok(messages));
+			let dispatch_weight = REGULAR_PAYLOAD.declared_weight * 3;
+			let declared_weight = Coordinator::delivery_weight(&proof, 3, dispatch_weight);
+
+			let post_info = Pallet::<TestRuntime, ()>::receive_messages_proof_atomically(
+				RuntimeOrigin::signed(1),
+				TEST_RELAYER_A,
+				(TEST_LANE_ID, 1..=2),
+				(TEST_LANE_ID_2, 1..=1),
+				proof,
+				3,
+				dispatch_weight,
+			)
+			.unwrap();
+
+			assert!(post_info.actual_weight.unwrap().all_lte(declared_weight));
+			assert_eq!(last_delivered_nonce(TEST_LANE_ID), 2);
+			assert_eq!(last_delivered_nonce(TEST_LANE_ID_2), 1);
+			assert_eq!(Pallet::<TestRuntime, ()>::relayer_deliveries_this_block(&1), 3);
+		});
+	}
+
+	#[test]
+	fn receive_messages_proof_atomically_rejects_unsigned_origin() {
+		run_test(|| {
+			assert_noop!(
+				Pallet::<TestRuntime, ()>::receive_messages_proof_atomically(
+					RuntimeOrigin::root(),
+					TEST_RELAYER_A,
+					(TEST_LANE_ID, 1..=1),
+					(TEST_LANE_ID_2, 1..=1),
+					TestMessagesProof::from(Ok([
+						lane_messages(TEST_LANE_ID, 1..=1),
+						lane_messages(TEST_LANE_ID_2, 1..=1),
+					]
+					.concat())),
+					2,
+					REGULAR_PAYLOAD.declared_weight * 2,
+				),
+				DispatchError::BadOrigin,
+			);
+		});
+	}
+
+	#[test]
+	fn delivery_weight_includes_lane_state_and_gap_detection_weights() {
+		let proof = TestMessagesProof::from(Ok(lane_messages(TEST_LANE_ID, 1..=1)));
+		let dispatch_weight = REGULAR_PAYLOAD.declared_weight;
+		let single_lane_weight =
+			<TestRuntime as Config>::WeightInfo::receive_messages_proof_weight(
+				&proof,
+				1,
+				dispatch_weight,
+			)
+			.saturating_add(relayer_delivery_weight::<TestRuntime, ()>());
+
+		assert_eq!(
+			Coordinator::delivery_weight(&proof, 1, dispatch_weight),
+			single_lane_weight
+				.saturating_add(
+					<TestRuntime as frame_system::Config>::DbWeight::get().reads_writes(1, 1)
+				)
+				.saturating_add(gap_detection_weight::<TestRuntime, ()>() * 2)
+				.saturating_add(lane_state_weight::<TestRuntime, ()>() * 2),
+		);
+	}
+
+	#[test]
+	fn atomic_delivery_rejects_invalid_proof() {
+		run_test(|| {
+			let token =
+				Coordinator::begin_atomic_delivery((TEST_LANE_ID, 1..=1), (TEST_LANE_ID_2, 1..=1))
+					.unwrap();
+			assert_noop!(
+				Coordinator::commit_atomic_delivery(
+					RuntimeOrigin::signed(1),
+					token,
+					&TEST_RELAYER_A,
+					TestMessagesProof::from(Err(())),
+					2,
+					REGULAR_PAYLOAD.declared_weight * 2,
+				),
+				Error::<TestRuntime, ()>::InvalidMessagesProof,
+			);
+		});
+	}
+
+	#[test]
+	fn atomic_delivery_is_rejected_if_pallet_is_not_operating_normally() {
+		run_test(|| {
+			let messages =
+				[lane_messages(TEST_LANE_ID, 1..=1), lane_messages(TEST_LANE_ID_2, 1..=1)].concat();
+
+			PalletOperatingMode::<TestRuntime, ()>::put(MessagesOperatingMode::Basic(
+				BasicOperatingMode::Halted,
+			));
+			let token =
+				Coordinator::begin_atomic_delivery((TEST_LANE_ID, 1..=1), (TEST_LANE_ID_2, 1..=1))
+					.unwrap();
+			assert_noop!(
+				commit(token, 1, messages.clone()),
+				Error::<TestRuntime, ()>::BridgeModule(bp_runtime::OwnedBridgeModuleError::Halted),
+			);
+
+			PalletOperatingMode::<TestRuntime, ()>::put(MessagesOperatingMode::Paused);
+			let token =
+				Coordinator::begin_atomic_delivery((TEST_LANE_ID, 1..=1), (TEST_LANE_ID_2, 1..=1))
+					.unwrap();
+			assert_noop!(
+				commit(token, 1, messages),
+				Error::<TestRuntime, ()>::NotOperatingNormally
+			);
+		});
+	}
+
+	#[test]
+	fn atomic_delivery_is_rejected_if_dispatch_weight_is_not_enough() {
+		run_test(|| {
+			let token =
+				Coordinator::begin_atomic_delivery((TEST_LANE_ID, 1..=1), (TEST_LANE_ID_2, 1..=1))
+					.unwrap();
+			let mut declared_weight = REGULAR_PAYLOAD.declared_weight * 2;
+			*declared_weight.ref_time_mut() -= 1;
+			assert_noop!(
+				Coordinator::commit_atomic_delivery(
+					RuntimeOrigin::signed(1),
+					token,
+					&TEST_RELAYER_A,
+					TestMessagesProof::from(Ok([
+						lane_messages(TEST_LANE_ID, 1..=1),
+						lane_messages(TEST_LANE_ID_2, 1..=1),
+					]
+					.concat())),
+					2,
+					declared_weight,
+				),
+				Error::<TestRuntime, ()>::InsufficientDispatchWeight,
+			);
+		});
+	}
+
+	#[test]
+	fn atomic_delivery_counts_towards_relayer_cap() {
+		run_test(|| {
+			MaxMessagesPerRelayerPerBlock::set(2);
+
+			let token =
+				Coordinator::begin_atomic_delivery((TEST_LANE_ID, 1..=2), (TEST_LANE_ID_2, 1..=1))
+					.unwrap();
+			assert_noop!(
+				commit(
+					token,
+					1,
+					[lane_messages(TEST_LANE_ID, 1..=2), lane_messages(TEST_LANE_ID_2, 1..=1)]
+						.concat(),
+				),
+				Error::<TestRuntime, ()>::RelayerCapExceeded,
+			);
+		});
+	}
+}
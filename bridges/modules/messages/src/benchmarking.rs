@@ -18,21 +18,30 @@
 
 use crate::{
 	inbound_lane::InboundLaneStorage, outbound_lane, weights_ext::EXPECTED_DEFAULT_MESSAGE_LENGTH,
-	Call, OutboundLanes, RuntimeInboundLaneStorage,
+	Call, FeeBalanceOf, OutboundLanes, RuntimeInboundLaneStorage,
 };
 
 use bp_messages::{
 	source_chain::TargetHeaderChain, target_chain::SourceHeaderChain, DeliveredMessages,
-	InboundLaneData, LaneId, MessageNonce, OutboundLaneData, UnrewardedRelayer,
-	UnrewardedRelayersState,
+	InboundLaneData, LaneId, LanePriority, LaneState, MessageKey, MessageNonce, OutboundLaneData,
+	UnrewardedRelayer, UnrewardedRelayersState,
 };
 use bp_runtime::StorageProofSize;
 use codec::Decode;
 use frame_benchmarking::{account, benchmarks_instance_pallet};
-use frame_support::weights::Weight;
-use frame_system::RawOrigin;
-use sp_runtime::{traits::TrailingZeroInput, BoundedVec};
-use sp_std::{ops::RangeInclusive, prelude::*};
+use frame_support::{
+	traits::{
+		fungible::{Inspect, Mutate},
+		Get,
+	},
+	weights::Weight,
+};
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
+use sp_runtime::{
+	traits::{Saturating, TrailingZeroInput},
+	BoundedVec,
+};
+use sp_std::{collections::vec_deque::VecDeque, ops::RangeInclusive, prelude::*};
 
 const SEED: u32 = 0;
 
@@ -334,7 +343,9 @@ benchmarks_instance_pallet! {
 			last_delivered_nonce: 2,
 		};
 		let mut delivered_messages = DeliveredMessages::new(1);
-		delivered_messages.note_dispatched_message();
+		delivered_messages
+			.note_dispatched_message()
+			.expect("nonce 2 doesn't overflow MessageNonce");
 		let proof = T::prepare_message_delivery_proof(MessageDeliveryProofParams {
 			lane: T::bench_lane_id(),
 			inbound_lane_data: InboundLaneData {
@@ -438,6 +449,103 @@ benchmarks_instance_pallet! {
 		assert!(T::is_message_successfully_dispatched(21));
 	}
 
+	// Benchmark `set_lane_priority` extrinsic, called by the pallet owner.
+	set_lane_priority {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		crate::PalletOwner::<T, I>::put(&owner);
+	}: set_lane_priority(RawOrigin::Signed(owner), T::bench_lane_id(), LanePriority(255))
+	verify {
+		assert_eq!(
+			crate::OutboundLanePriorities::<T, I>::get(T::bench_lane_id()),
+			LanePriority(255),
+		);
+	}
+
+	// Benchmark `escrow_message_fee` extrinsic, called by the sender of the message.
+	escrow_message_fee {
+		let sender: T::AccountId = account("sender", 0, SEED);
+		let fee = endow_fee_payer::<T, I>(&sender);
+		send_regular_message::<T, I>();
+		let key = MessageKey { lane_id: T::bench_lane_id(), nonce: 1 };
+		crate::OutboundMessageSenders::<T, I>::insert(&key, &sender);
+	}: escrow_message_fee(RawOrigin::Signed(sender), T::bench_lane_id(), 1, fee)
+	verify {
+		assert!(crate::EscrowedFees::<T, I>::contains_key(&key));
+	}
+
+	// Benchmark closing escrows of `n` confirmed messages, delivered by the same relayer. Every
+	// message has the escrowed fee, which is paid to the relayer.
+	close_fee_escrows {
+		let n in 1 .. T::MaxFeeEscrowsPerBlock::get();
+
+		escrow_fees::<T, I>(n);
+		let relayer: T::AccountId = account("relayer", 0, SEED);
+		T::endow_account(&relayer);
+		let relayers: VecDeque<_> = vec![UnrewardedRelayer {
+			relayer,
+			messages: DeliveredMessages { begin: 1, end: n as MessageNonce },
+		}]
+		.into();
+	}: {
+		crate::Pallet::<T, I>::close_fee_escrows(
+			T::bench_lane_id(),
+			&relayers,
+			&(1..=n as MessageNonce),
+		);
+	}
+	verify {
+		assert_eq!(crate::LaneFeeEscrows::<T, I>::get(T::bench_lane_id()), 0);
+	}
+
+	// Benchmark refunding `n` fee escrows, which expire at the same block.
+	refund_expired_fee_escrows {
+		let n in 0 .. T::MaxFeeEscrowsPerBlock::get();
+
+		let expires_at = escrow_fees::<T, I>(n);
+	}: {
+		crate::Pallet::<T, I>::refund_expired_fee_escrows(expires_at);
+	}
+	verify {
+		assert_eq!(crate::LaneFeeEscrows::<T, I>::get(T::bench_lane_id()), 0);
+	}
+
+	// Benchmark `start_lane_upgrade` extrinsic, called by the pallet owner.
+	start_lane_upgrade {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		crate::PalletOwner::<T, I>::put(&owner);
+	}: start_lane_upgrade(RawOrigin::Signed(owner), T::bench_lane_id(), 1)
+	verify {
+		assert!(matches!(
+			crate::LaneStates::<T, I>::get(T::bench_lane_id()),
+			LaneState::Upgrading { new_version: 1, .. },
+		));
+	}
+
+	// Benchmark `complete_lane_upgrade` extrinsic, called by the pallet owner.
+	complete_lane_upgrade {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		crate::PalletOwner::<T, I>::put(&owner);
+		crate::LaneStates::<T, I>::insert(
+			T::bench_lane_id(),
+			LaneState::Upgrading { since: frame_system::Pallet::<T>::block_number(), new_version: 1 },
+		);
+	}: complete_lane_upgrade(RawOrigin::Signed(owner), T::bench_lane_id())
+	verify {
+		assert_eq!(crate::LaneVersions::<T, I>::get(T::bench_lane_id()), 1);
+	}
+
+	// Benchmark `set_lane_closed` extrinsic, called by the pallet owner to reopen the closed lane.
+	//
+	// Reopening the lane is more expensive than closing it, because the lane state is read.
+	set_lane_closed {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		crate::PalletOwner::<T, I>::put(&owner);
+		crate::LaneStates::<T, I>::insert(T::bench_lane_id(), LaneState::Closed);
+	}: set_lane_closed(RawOrigin::Signed(owner), T::bench_lane_id(), false)
+	verify {
+		assert!(crate::Pallet::<T, I>::lane_state(T::bench_lane_id()).is_open());
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::TestRuntime)
 }
 
@@ -446,6 +554,27 @@ fn send_regular_message<T: Config<I>, I: 'static>() {
 	outbound_lane.send_message(BoundedVec::try_from(vec![]).expect("We craft valid messages"));
 }
 
+fn endow_fee_payer<T: Config<I>, I: 'static>(payer: &T::AccountId) -> FeeBalanceOf<T, I> {
+	let fee = T::FeeEscrowCurrency::minimum_balance().saturating_mul(10u32.into());
+	T::FeeEscrowCurrency::set_balance(payer, fee.saturating_mul(1_000u32.into()));
+	fee
+}
+
+fn escrow_fees<T: Config<I>, I: 'static>(n: u32) -> BlockNumberFor<T> {
+	let payer: T::AccountId = account("payer", 0, SEED);
+	let fee = endow_fee_payer::<T, I>(&payer);
+	for nonce in 1..=n as MessageNonce {
+		send_regular_message::<T, I>();
+		crate::Pallet::<T, I>::escrow_fee(
+			MessageKey { lane_id: T::bench_lane_id(), nonce },
+			&payer,
+			fee,
+		)
+		.expect("fee payer is endowed");
+	}
+	frame_system::Pallet::<T>::block_number().saturating_add(T::FeeEscrowPeriod::get())
+}
+
 fn receive_messages<T: Config<I>, I: 'static>(nonce: MessageNonce) {
 	let mut inbound_lane_storage =
 		RuntimeInboundLaneStorage::<T, I>::from_lane_id(T::bench_lane_id());
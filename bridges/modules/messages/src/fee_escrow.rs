@@ -0,0 +1,394 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Escrow of the message fees.
+//!
+//! The sender of the message may pay the fee for delivering it using the `escrow_message_fee`
+//! call. The fee is moved to the escrow account of the lane and stays there until the delivery of
+//! the message is confirmed. It is then paid to the relayer that has delivered the message or, if
+//! that fails, refunded to the payer. If the delivery is not confirmed within the
+//! `FeeEscrowPeriod`, the fee is refunded to the payer by the `on_initialize` hook.
+
+use crate::{
+	Config, Error, EscrowedFeeOf, EscrowedFees, FeeBalanceOf, FeeEscrowExpirations, LaneFeeEscrows,
+	Pallet, WeightInfo, LOG_TARGET,
+};
+
+use bp_messages::{EscrowedFee, LaneId, MessageKey, MessageNonce, UnrewardedRelayer};
+use frame_support::{
+	ensure,
+	traits::{
+		fungible::{Inspect, Mutate},
+		tokens::{Fortitude, Preservation},
+		Get,
+	},
+	weights::Weight,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_runtime::{
+	traits::{AccountIdConversion, Saturating},
+	DispatchResult,
+};
+use sp_std::{collections::vec_deque::VecDeque, ops::RangeInclusive};
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Account that holds the escrowed fees of messages, sent over the given lane.
+	pub fn fee_escrow_account(lane_id: LaneId) -> T::AccountId {
+		lane_id.into_sub_account_truncating((T::BridgedChainId::get(), b"fee-escrow"))
+	}
+
+	/// Escrow the fee of the message with given key until its delivery is confirmed or the
+	/// escrow expires.
+	///
+	/// The `amount` is moved from the `payer` account to the escrow account of the lane.
+	pub(crate) fn escrow_fee(
+		key: MessageKey,
+		payer: &T::AccountId,
+		amount: FeeBalanceOf<T, I>,
+	) -> DispatchResult {
+		ensure!(!EscrowedFees::<T, I>::contains_key(&key), Error::<T, I>::FeeAlreadyEscrowed);
+		ensure!(
+			T::FeeEscrowCurrency::reducible_balance(
+				payer,
+				Preservation::Preserve,
+				Fortitude::Polite
+			) >= amount,
+			Error::<T, I>::InsufficientFeeBalance
+		);
+
+		let expires_at =
+			frame_system::Pallet::<T>::block_number().saturating_add(T::FeeEscrowPeriod::get());
+		FeeEscrowExpirations::<T, I>::try_append(expires_at, key.clone())
+			.map_err(|_| Error::<T, I>::TooManyFeeEscrows)?;
+
+		T::FeeEscrowCurrency::transfer(
+			payer,
+			&Self::fee_escrow_account(key.lane_id),
+			amount,
+			Preservation::Preserve,
+		)?;
+
+		LaneFeeEscrows::<T, I>::mutate(key.lane_id, |escrows| *escrows = escrows.saturating_add(1));
+		EscrowedFees::<T, I>::insert(
+			&key,
+			EscrowedFee { payer: payer.clone(), amount, expires_at },
+		);
+		Ok(())
+	}
+
+	/// Close escrows of the `confirmed` messages.
+	///
+	/// The escrowed fee is paid to the relayer that has delivered the message. If that fails, it is
+	/// refunded to the payer. Returns the consumed weight, which never exceeds the
+	/// `T::WeightInfo::close_fee_escrows(confirmed.saturating_len())`.
+	pub(crate) fn close_fee_escrows(
+		lane_id: LaneId,
+		relayers: &VecDeque<UnrewardedRelayer<T::AccountId>>,
+		confirmed: &RangeInclusive<MessageNonce>,
+	) -> Weight {
+		// if there are no escrows at the lane, we don't need to look for them
+		if LaneFeeEscrows::<T, I>::get(lane_id) == 0 {
+			return T::WeightInfo::close_fee_escrows(0)
+		}
+
+		let mut messages = 0u32;
+		for nonce in confirmed.clone() {
+			messages = messages.saturating_add(1);
+
+			let key = MessageKey { lane_id, nonce };
+			let Some(fee) = EscrowedFees::<T, I>::get(&key) else { continue };
+
+			let relayer = relayers
+				.iter()
+				.find(|entry| entry.messages.contains_message(nonce))
+				.map(|entry| &entry.relayer);
+			let is_released = match relayer {
+				Some(relayer) => Self::close_escrow(&key, &fee, relayer)
+					.map_err(|e| {
+						log::debug!(
+							target: LOG_TARGET,
+							"Failed to release fee of message {:?} to relayer {:?}: {:?}",
+							key,
+							relayer,
+							e,
+						)
+					})
+					.is_ok(),
+				None => false,
+			};
+			if is_released {
+				continue
+			}
+
+			if let Err(e) = Self::close_escrow(&key, &fee, &fee.payer) {
+				log::error!(
+					target: LOG_TARGET,
+					"Failed to refund fee of message {:?}: {:?}",
+					key,
+					e,
+				);
+			}
+		}
+		T::WeightInfo::close_fee_escrows(messages)
+	}
+
+	/// Refund fees of messages, escrows of which expire at the block `now`.
+	///
+	/// Returns the consumed weight.
+	pub(crate) fn refund_expired_fee_escrows(now: BlockNumberFor<T>) -> Weight {
+		let expired = FeeEscrowExpirations::<T, I>::take(now);
+		let weight = T::WeightInfo::refund_expired_fee_escrows(expired.len() as u32);
+		for key in expired {
+			// the escrow may already be closed by the delivery confirmation
+			let Some(fee) = EscrowedFees::<T, I>::get(&key) else { continue };
+			if let Err(e) = Self::close_escrow(&key, &fee, &fee.payer) {
+				log::error!(
+					target: LOG_TARGET,
+					"Failed to refund expired fee of message {:?}: {:?}",
+					key,
+					e,
+				);
+			}
+		}
+		weight
+	}
+
+	/// Pay the escrowed fee to the `beneficiary` and remove the escrow.
+	fn close_escrow(
+		key: &MessageKey,
+		fee: &EscrowedFeeOf<T, I>,
+		beneficiary: &T::AccountId,
+	) -> DispatchResult {
+		T::FeeEscrowCurrency::transfer(
+			&Self::fee_escrow_account(key.lane_id),
+			beneficiary,
+			fee.amount,
+			Preservation::Expendable,
+		)?;
+
+		EscrowedFees::<T, I>::remove(key);
+		LaneFeeEscrows::<T, I>::mutate(key.lane_id, |escrows| *escrows = escrows.saturating_sub(1));
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{
+		run_test, unrewarded_relayer, Balances, FeeEscrowPeriod, MaxFeeEscrowsPerBlock,
+		TestRuntime, ENDOWED_ACCOUNT, TEST_LANE_ID, TEST_LANE_ID_2, TEST_RELAYER_A, TEST_RELAYER_B,
+	};
+	use frame_support::{assert_noop, assert_ok};
+
+	type Messages = Pallet<TestRuntime, ()>;
+
+	fn key(nonce: u64) -> MessageKey {
+		MessageKey { lane_id: TEST_LANE_ID, nonce }
+	}
+
+	fn escrow_balance() -> u64 {
+		Balances::balance(&Messages::fee_escrow_account(TEST_LANE_ID))
+	}
+
+	fn lane_escrows() -> u32 {
+		LaneFeeEscrows::<TestRuntime, ()>::get(TEST_LANE_ID)
+	}
+
+	fn close_fee_escrow(nonce: u64, relayer: u64) -> Weight {
+		Messages::close_fee_escrows(
+			TEST_LANE_ID,
+			&vec![unrewarded_relayer(nonce, nonce, relayer)].into(),
+			&(nonce..=nonce),
+		)
+	}
+
+	#[test]
+	fn fee_escrow_accounts_are_different_for_different_lanes() {
+		assert_ne!(
+			Messages::fee_escrow_account(TEST_LANE_ID),
+			Messages::fee_escrow_account(TEST_LANE_ID_2),
+		);
+	}
+
+	#[test]
+	fn escrow_fee_works() {
+		run_test(|| {
+			frame_system::Pallet::<TestRuntime>::set_block_number(5);
+			assert_ok!(Messages::escrow_fee(key(1), &ENDOWED_ACCOUNT, 100));
+
+			let expires_at = 5 + FeeEscrowPeriod::get();
+			assert_eq!(escrow_balance(), 100);
+			assert_eq!(Balances::balance(&ENDOWED_ACCOUNT), 1_000_000 - 100);
+			assert_eq!(
+				EscrowedFees::<TestRuntime, ()>::get(key(1)),
+				Some(EscrowedFee { payer: ENDOWED_ACCOUNT, amount: 100, expires_at }),
+			);
+			assert_eq!(
+				FeeEscrowExpirations::<TestRuntime, ()>::get(expires_at).to_vec(),
+				vec![key(1)]
+			);
+			assert_eq!(lane_escrows(), 1);
+		});
+	}
+
+	#[test]
+	fn escrow_fee_rejects_second_escrow_of_the_same_message() {
+		run_test(|| {
+			assert_ok!(Messages::escrow_fee(key(1), &ENDOWED_ACCOUNT, 100));
+			assert_noop!(
+				Messages::escrow_fee(key(1), &ENDOWED_ACCOUNT, 100),
+				Error::<TestRuntime, ()>::FeeAlreadyEscrowed,
+			);
+		});
+	}
+
+	#[test]
+	fn escrow_fee_fails_if_payer_cant_afford_it() {
+		run_test(|| {
+			assert_noop!(
+				Messages::escrow_fee(key(1), &TEST_RELAYER_A, 100),
+				Error::<TestRuntime, ()>::InsufficientFeeBalance,
+			);
+		});
+	}
+
+	#[test]
+	fn escrow_fee_fails_if_too_many_escrows_expire_at_the_same_block() {
+		run_test(|| {
+			for nonce in 1..=MaxFeeEscrowsPerBlock::get() as u64 {
+				assert_ok!(Messages::escrow_fee(key(nonce), &ENDOWED_ACCOUNT, 100));
+			}
+			assert_noop!(
+				Messages::escrow_fee(key(100), &ENDOWED_ACCOUNT, 100),
+				Error::<TestRuntime, ()>::TooManyFeeEscrows,
+			);
+		});
+	}
+
+	#[test]
+	fn close_fee_escrows_pays_relayer_once() {
+		run_test(|| {
+			assert_ok!(Messages::escrow_fee(key(1), &ENDOWED_ACCOUNT, 100));
+			assert_ok!(Messages::escrow_fee(key(2), &ENDOWED_ACCOUNT, 50));
+
+			assert_eq!(
+				close_fee_escrow(1, TEST_RELAYER_A),
+				<() as WeightInfo>::close_fee_escrows(1)
+			);
+			assert_eq!(Balances::balance(&TEST_RELAYER_A), 100);
+			assert_eq!(escrow_balance(), 50);
+			assert!(!EscrowedFees::<TestRuntime, ()>::contains_key(key(1)));
+			assert_eq!(lane_escrows(), 1);
+
+			// the same fee can't be paid twice
+			close_fee_escrow(1, TEST_RELAYER_A);
+			assert_eq!(Balances::balance(&TEST_RELAYER_A), 100);
+			assert_eq!(lane_escrows(), 1);
+		});
+	}
+
+	#[test]
+	fn close_fee_escrows_pays_every_relayer_for_its_messages() {
+		run_test(|| {
+			assert_ok!(Messages::escrow_fee(key(1), &ENDOWED_ACCOUNT, 100));
+			assert_ok!(Messages::escrow_fee(key(2), &ENDOWED_ACCOUNT, 50));
+			assert_ok!(Messages::escrow_fee(key(3), &ENDOWED_ACCOUNT, 10));
+
+			// messages 1 and 2 are delivered by the relayer A and message 3 by the relayer B,
+			// but only messages 2 and 3 are confirmed
+			let weight = Messages::close_fee_escrows(
+				TEST_LANE_ID,
+				&vec![
+					unrewarded_relayer(1, 2, TEST_RELAYER_A),
+					unrewarded_relayer(3, 3, TEST_RELAYER_B),
+				]
+				.into(),
+				&(2..=3),
+			);
+
+			assert_eq!(weight, <() as WeightInfo>::close_fee_escrows(2));
+			assert_eq!(Balances::balance(&TEST_RELAYER_A), 50);
+			assert_eq!(Balances::balance(&TEST_RELAYER_B), 10);
+			assert_eq!(escrow_balance(), 100);
+			assert!(EscrowedFees::<TestRuntime, ()>::contains_key(key(1)));
+			assert_eq!(lane_escrows(), 1);
+		});
+	}
+
+	#[test]
+	fn close_fee_escrows_refunds_fee_if_it_cant_be_paid_to_relayer() {
+		run_test(|| {
+			assert_ok!(Messages::escrow_fee(key(1), &ENDOWED_ACCOUNT, 100));
+
+			// the message is confirmed, but there's no relayer that has delivered it
+			Messages::close_fee_escrows(TEST_LANE_ID, &vec![].into(), &(1..=1));
+
+			assert_eq!(Balances::balance(&ENDOWED_ACCOUNT), 1_000_000);
+			assert_eq!(escrow_balance(), 0);
+			assert!(!EscrowedFees::<TestRuntime, ()>::contains_key(key(1)));
+			assert_eq!(lane_escrows(), 0);
+		});
+	}
+
+	#[test]
+	fn close_fee_escrows_does_not_look_for_escrows_at_lane_without_escrows() {
+		run_test(|| {
+			// the escrow at other lane doesn't matter
+			assert_ok!(Messages::escrow_fee(
+				MessageKey { lane_id: TEST_LANE_ID_2, nonce: 1 },
+				&ENDOWED_ACCOUNT,
+				100
+			));
+
+			assert_eq!(
+				Messages::close_fee_escrows(TEST_LANE_ID, &vec![].into(), &(1..=100)),
+				<() as WeightInfo>::close_fee_escrows(0),
+			);
+		});
+	}
+
+	#[test]
+	fn refund_expired_fee_escrows_works() {
+		run_test(|| {
+			assert_ok!(Messages::escrow_fee(key(1), &ENDOWED_ACCOUNT, 100));
+			assert_ok!(Messages::escrow_fee(key(2), &ENDOWED_ACCOUNT, 50));
+			let expires_at = EscrowedFees::<TestRuntime, ()>::get(key(1)).unwrap().expires_at;
+
+			// escrow of the message 1 is closed by the delivery confirmation
+			close_fee_escrow(1, TEST_RELAYER_A);
+
+			// nothing expires before the `expires_at`
+			assert_eq!(
+				Messages::refund_expired_fee_escrows(expires_at - 1),
+				<() as WeightInfo>::refund_expired_fee_escrows(0),
+			);
+			assert_eq!(Balances::balance(&ENDOWED_ACCOUNT), 1_000_000 - 150);
+
+			// and the fee of the message 2 is refunded at the `expires_at`
+			assert_eq!(
+				Messages::refund_expired_fee_escrows(expires_at),
+				<() as WeightInfo>::refund_expired_fee_escrows(2),
+			);
+			assert_eq!(Balances::balance(&ENDOWED_ACCOUNT), 1_000_000 - 100);
+			assert_eq!(Balances::balance(&TEST_RELAYER_A), 100);
+			assert_eq!(escrow_balance(), 0);
+			assert_eq!(EscrowedFees::<TestRuntime, ()>::iter().count(), 0);
+			assert_eq!(FeeEscrowExpirations::<TestRuntime, ()>::iter().count(), 0);
+			assert_eq!(lane_escrows(), 0);
+		});
+	}
+}
@@ -139,26 +139,9 @@ impl<S: InboundLaneStorage> InboundLane<S> {
 			return None
 		}
 
-		let new_confirmed_nonce = outbound_lane_data.latest_received_nonce;
-		data.last_confirmed_nonce = new_confirmed_nonce;
-		// Firstly, remove all of the records where higher nonce <= new confirmed nonce
-		while data
-			.relayers
-			.front()
-			.map(|entry| entry.messages.end <= new_confirmed_nonce)
-			.unwrap_or(false)
-		{
-			data.relayers.pop_front();
-		}
-		// Secondly, update the next record with lower nonce equal to new confirmed nonce if needed.
-		// Note: There will be max. 1 record to update as we don't allow messages from relayers to
-		// overlap.
-		match data.relayers.front_mut() {
-			Some(entry) if entry.messages.begin <= new_confirmed_nonce => {
-				entry.messages.begin = new_confirmed_nonce + 1;
-			},
-			_ => {},
-		}
+		// Remove all of the records where higher nonce <= new confirmed nonce and update the
+		// next record with lower nonce equal to new confirmed nonce if needed
+		data.drain_confirmed(outbound_lane_data.latest_received_nonce);
 
 		self.storage.set_data(data);
 		Some(outbound_lane_data.latest_received_nonce)
@@ -196,7 +179,13 @@ impl<S: InboundLaneStorage> InboundLane<S> {
 		// now let's update inbound lane storage
 		match data.relayers.back_mut() {
 			Some(entry) if entry.relayer == *relayer_at_bridged_chain => {
-				entry.messages.note_dispatched_message();
+				// we have checked that `nonce` is the `last_delivered_nonce + 1` above, so it
+				// can't overflow here
+				let note_result = entry.messages.note_dispatched_message();
+				debug_assert!(
+					note_result.is_ok(),
+					"Nonce of the dispatched message {nonce} has overflowed the delivered messages range",
+				);
 			},
 			_ => {
 				data.relayers.push_back(UnrewardedRelayer {
@@ -36,6 +36,7 @@
 #![warn(missing_docs)]
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub use atomic_delivery::{CrossLaneDeliveryCoordinator, DeliveryToken};
 pub use inbound_lane::StoredInboundLaneData;
 pub use outbound_lane::StoredMessagePayload;
 pub use weights::WeightInfo;
@@ -58,22 +59,32 @@ use bp_messages::{
 		DeliveryPayments, DispatchMessage, MessageDispatch, ProvedLaneMessages, ProvedMessages,
 		SourceHeaderChain,
 	},
-	DeliveredMessages, InboundLaneData, InboundMessageDetails, LaneId, MessageKey, MessageNonce,
-	MessagePayload, MessagesOperatingMode, OutboundLaneData, OutboundMessageDetails,
-	UnrewardedRelayersState, VerificationError,
+	ChainWithMessages, DeliveredMessages, EscrowedFee, InboundLaneData, InboundMessageDetails,
+	LaneId, LanePriority, LaneState, MessageKey, MessageNonce, MessagePayload,
+	MessagesOperatingMode, OutboundLaneData, OutboundMessageDetails, UnrewardedRelayersState,
+	VerificationError,
 };
 use bp_runtime::{
-	BasicOperatingMode, ChainId, OwnedBridgeModule, PreComputedSize, RangeInclusiveExt, Size,
+	BasicOperatingMode, ChainId, OperatingMode, OwnedBridgeModule, PreComputedSize,
+	RangeInclusiveExt, Size,
 };
 use codec::{Decode, Encode, MaxEncodedLen};
-use frame_support::{dispatch::PostDispatchInfo, ensure, fail, traits::Get, DefaultNoBound};
-use sp_runtime::traits::UniqueSaturatedFrom;
-use sp_std::{marker::PhantomData, prelude::*};
+use frame_support::{
+	dispatch::PostDispatchInfo,
+	ensure, fail,
+	traits::{fungible, Get},
+	DefaultNoBound,
+};
+use sp_runtime::traits::{Saturating, UniqueSaturatedFrom};
+use sp_std::{marker::PhantomData, ops::RangeInclusive, prelude::*};
 
+mod atomic_delivery;
+mod fee_escrow;
 mod inbound_lane;
 mod outbound_lane;
 mod weights_ext;
 
+pub mod migration;
 pub mod weights;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -134,6 +145,19 @@ pub mod pallet {
 		/// Transaction that is declaring more messages than this value, will be rejected. Even if
 		/// these messages are from different lanes.
 		type MaxUnconfirmedMessagesAtInboundLane: Get<MessageNonce>;
+		/// This chain, to which the messages are delivered.
+		///
+		/// Its `MaxMessagesPerRelayerPerBlock` limits the number of messages that a single
+		/// relayer may deliver in a single block. Only messages that have been verified and
+		/// dispatched are counted. Delivery transactions that would bring the number of messages,
+		/// delivered by the relayer in the current block, over this value, are rejected.
+		type ThisChain: ChainWithMessages;
+		/// Number of blocks after which the lane upgrade is completed automatically.
+		///
+		/// Once the upgrade period is over, the lane is opened with the new protocol version, even
+		/// if the upgrade has not been completed using the `complete_lane_upgrade` call.
+		#[pallet::constant]
+		type UpgradePeriod: Get<BlockNumberFor<Self>>;
 
 		/// Maximal encoded size of the outbound payload.
 		#[pallet::constant]
@@ -164,8 +188,27 @@ pub mod pallet {
 		type SourceHeaderChain: SourceHeaderChain;
 		/// Message dispatch.
 		type MessageDispatch: MessageDispatch<DispatchPayload = Self::InboundPayload>;
+
+		// Types that are used by the message fee escrow.
+
+		/// Currency of the escrowed message fees.
+		type FeeEscrowCurrency: fungible::Mutate<Self::AccountId>;
+		/// Number of blocks, after which the escrowed fee is refunded to its payer, unless the
+		/// delivery of the message is confirmed before.
+		#[pallet::constant]
+		type FeeEscrowPeriod: Get<BlockNumberFor<Self>>;
+		/// Maximal number of fee escrows that may expire at the same block.
+		#[pallet::constant]
+		type MaxFeeEscrowsPerBlock: Get<u32>;
 	}
 
+	/// Shortcut to the balance type of the escrowed message fees.
+	pub type FeeBalanceOf<T, I> = <<T as Config<I>>::FeeEscrowCurrency as fungible::Inspect<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
+	/// Shortcut to the escrowed message fee type for Config.
+	pub type EscrowedFeeOf<T, I> =
+		EscrowedFee<<T as frame_system::Config>::AccountId, FeeBalanceOf<T, I>, BlockNumberFor<T>>;
 	/// Shortcut to messages proof type for Config.
 	pub type MessagesProofOf<T, I> =
 		<<T as Config<I>>::SourceHeaderChain as SourceHeaderChain>::MessagesProof;
@@ -176,7 +219,11 @@ pub mod pallet {
 			<T as frame_system::Config>::AccountId,
 		>>::MessagesDeliveryProof;
 
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
 	impl<T: Config<I>, I: 'static> OwnedBridgeModule<T> for Pallet<T, I> {
@@ -191,10 +238,18 @@ pub mod pallet {
 	where
 		u32: TryFrom<BlockNumberFor<T>>,
 	{
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			// deliveries of the previous block don't count towards the relayer cap
+			Self::clear_relayer_deliveries()
+				// refund fees of messages, which delivery has not been confirmed in time
+				.saturating_add(Self::refund_expired_fee_escrows(now))
+		}
+
 		fn on_idle(_block: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
-			// we'll need at least to read outbound lane state, kill a message and update lane state
+			// we'll need at least to read outbound lane state, kill a message with its sender and
+			// update lane state
 			let db_weight = T::DbWeight::get();
-			if !remaining_weight.all_gte(db_weight.reads_writes(1, 2)) {
+			if !remaining_weight.all_gte(db_weight.reads_writes(1, 3)) {
 				return Weight::zero()
 			}
 
@@ -218,6 +273,11 @@ pub mod pallet {
 			// we already checked we have enough `remaining_weight` to cover this `used_weight`
 			used_weight
 		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+			Self::do_try_state()
+		}
 	}
 
 	#[pallet::call]
@@ -243,6 +303,137 @@ pub mod pallet {
 			<Self as OwnedBridgeModule<_>>::set_operating_mode(origin, operating_mode)
 		}
 
+		/// Start upgrade of the lane protocol to the `new_version`.
+		///
+		/// While the lane is upgrading, new messages may not be sent over it, but already sent
+		/// messages are still delivered. The upgrade is completed either by the
+		/// `complete_lane_upgrade` call, or automatically after `UpgradePeriod` blocks.
+		///
+		/// May only be called either by root, or by `PalletOwner`.
+		#[pallet::call_index(4)]
+		#[pallet::weight((T::WeightInfo::start_lane_upgrade(), DispatchClass::Operational))]
+		pub fn start_lane_upgrade(
+			origin: OriginFor<T>,
+			lane_id: LaneId,
+			new_version: u32,
+		) -> DispatchResult {
+			Self::ensure_owner_or_root(origin)?;
+			ensure!(Self::lane_state(lane_id).is_open(), Error::<T, I>::LaneNotOpened);
+			ensure!(new_version > Self::lane_version(lane_id), Error::<T, I>::InvalidLaneVersion);
+
+			let state = LaneState::Upgrading {
+				since: frame_system::Pallet::<T>::block_number(),
+				new_version,
+			};
+			LaneStates::<T, I>::insert(lane_id, state);
+			Self::deposit_event(Event::LaneStateChanged { lane_id, state });
+			Ok(())
+		}
+
+		/// Complete upgrade of the lane protocol before the `UpgradePeriod` is over.
+		///
+		/// May only be called either by root, or by `PalletOwner`.
+		#[pallet::call_index(5)]
+		#[pallet::weight((T::WeightInfo::complete_lane_upgrade(), DispatchClass::Operational))]
+		pub fn complete_lane_upgrade(origin: OriginFor<T>, lane_id: LaneId) -> DispatchResult {
+			Self::ensure_owner_or_root(origin)?;
+			match LaneStates::<T, I>::get(lane_id) {
+				LaneState::Upgrading { new_version, .. } => {
+					LaneStates::<T, I>::remove(lane_id);
+					LaneVersions::<T, I>::insert(lane_id, new_version);
+					Self::deposit_event(Event::LaneStateChanged {
+						lane_id,
+						state: LaneState::Opened,
+					});
+					Self::deposit_event(Event::LaneUpgraded { lane_id, version: new_version });
+					Ok(())
+				},
+				_ => Err(Error::<T, I>::LaneNotUpgrading.into()),
+			}
+		}
+
+		/// Close or reopen the lane.
+		///
+		/// No messages may be sent or delivered over the closed lane. Closing the lane aborts its
+		/// pending upgrade.
+		///
+		/// May only be called either by root, or by `PalletOwner`.
+		#[pallet::call_index(6)]
+		#[pallet::weight((T::WeightInfo::set_lane_closed(), DispatchClass::Operational))]
+		pub fn set_lane_closed(
+			origin: OriginFor<T>,
+			lane_id: LaneId,
+			closed: bool,
+		) -> DispatchResult {
+			Self::ensure_owner_or_root(origin)?;
+			let state = if closed {
+				LaneStates::<T, I>::insert(lane_id, LaneState::Closed);
+				LaneState::Closed
+			} else {
+				ensure!(LaneStates::<T, I>::get(lane_id).is_closed(), Error::<T, I>::LaneNotClosed);
+				LaneStates::<T, I>::remove(lane_id);
+				LaneState::Opened
+			};
+			Self::deposit_event(Event::LaneStateChanged { lane_id, state });
+			Ok(())
+		}
+
+		/// Change priority of the outbound lane.
+		///
+		/// Relayers are serving lanes with higher priority first.
+		///
+		/// May only be called either by root, or by `PalletOwner`.
+		#[pallet::call_index(7)]
+		#[pallet::weight((T::WeightInfo::set_lane_priority(), DispatchClass::Operational))]
+		pub fn set_lane_priority(
+			origin: OriginFor<T>,
+			lane_id: LaneId,
+			priority: LanePriority,
+		) -> DispatchResult {
+			Self::ensure_owner_or_root(origin)?;
+			OutboundLanePriorities::<T, I>::insert(lane_id, priority);
+			Self::deposit_event(Event::LanePriorityChanged { lane_id, priority });
+			Ok(())
+		}
+
+		/// Escrow the `amount` fee for delivering the message with given `nonce`, sent over the
+		/// `lane_id`.
+		///
+		/// The message must be sent by the caller (see `SendMessageArgs::with_sender`), but its
+		/// delivery must not be confirmed yet. The fee is moved from the caller account to the
+		/// escrow account of the lane. Once the delivery of the message is confirmed, it is paid
+		/// to the relayer that has delivered the message, or refunded to the caller if that fails.
+		/// If the delivery is not confirmed within `FeeEscrowPeriod` blocks, the fee is refunded
+		/// to the caller.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::escrow_message_fee())]
+		pub fn escrow_message_fee(
+			origin: OriginFor<T>,
+			lane_id: LaneId,
+			nonce: MessageNonce,
+			amount: FeeBalanceOf<T, I>,
+		) -> DispatchResult {
+			ensure_normal_operating_mode::<T, I>()?;
+			let payer = ensure_signed(origin)?;
+
+			let lane_data = OutboundLanes::<T, I>::get(lane_id);
+			ensure!(
+				nonce > lane_data.latest_received_nonce &&
+					nonce <= lane_data.latest_generated_nonce,
+				Error::<T, I>::MessageIsNotQueued
+			);
+
+			let key = MessageKey { lane_id, nonce };
+			ensure!(
+				OutboundMessageSenders::<T, I>::get(&key).as_ref() == Some(&payer),
+				Error::<T, I>::NotMessageSender
+			);
+
+			Self::escrow_fee(key.clone(), &payer, amount)?;
+			Self::deposit_event(Event::MessageFeeEscrowed { key, payer, amount });
+			Ok(())
+		}
+
 		/// Receive messages proof from bridged chain.
 		///
 		/// The weight of the call assumes that the transaction always brings outbound lane
@@ -257,6 +448,8 @@ pub mod pallet {
 		///
 		/// - there are too many messages in the proof;
 		///
+		/// - one of the lanes in the proof is closed;
+		///
 		/// - the proof verification procedure returns an error - e.g. because header used to craft
 		///   proof is not imported by the associated finality pallet;
 		///
@@ -265,7 +458,10 @@ pub mod pallet {
 		/// The call may succeed, but some messages may not be delivered e.g. if they are not fit
 		/// into the unrewarded relayers vector.
 		#[pallet::call_index(2)]
-		#[pallet::weight(T::WeightInfo::receive_messages_proof_weight(proof, *messages_count, *dispatch_weight))]
+		#[pallet::weight(T::WeightInfo::receive_messages_proof_weight(proof, *messages_count, *dispatch_weight)
+			.saturating_add(relayer_delivery_weight::<T, I>())
+			.saturating_add(gap_detection_weight::<T, I>())
+			.saturating_add(lane_state_weight::<T, I>()))]
 		pub fn receive_messages_proof(
 			origin: OriginFor<T>,
 			relayer_id_at_bridged_chain: T::InboundRelayer,
@@ -274,6 +470,7 @@ pub mod pallet {
 			dispatch_weight: Weight,
 		) -> DispatchResultWithPostInfo {
 			Self::ensure_not_halted().map_err(Error::<T, I>::BridgeModule)?;
+			ensure_not_paused::<T, I>()?;
 			let relayer_id_at_this_chain = ensure_signed(origin)?;
 
 			// reject transactions that are declaring too many messages
@@ -282,6 +479,10 @@ pub mod pallet {
 				Error::<T, I>::TooManyMessagesInTheProof
 			);
 
+			// reject transactions that would bring the relayer over its per-block limit, before
+			// dispatching anything
+			ensure_relayer_may_deliver::<T, I>(&relayer_id_at_this_chain, messages_count.into())?;
+
 			// if message dispatcher is currently inactive, we won't accept any messages
 			ensure!(T::MessageDispatch::is_active(), Error::<T, I>::MessageDispatchInactive);
 
@@ -299,7 +500,10 @@ pub mod pallet {
 				&proof,
 				messages_count,
 				dispatch_weight,
-			);
+			)
+			.saturating_add(relayer_delivery_weight::<T, I>())
+			.saturating_add(gap_detection_weight::<T, I>())
+			.saturating_add(lane_state_weight::<T, I>());
 			let mut actual_weight = declared_weight;
 
 			// verify messages proof && convert proof into messages
@@ -319,6 +523,9 @@ pub mod pallet {
 			let mut messages_received_status = Vec::with_capacity(messages.len());
 			let mut dispatch_weight_left = dispatch_weight;
 			for (lane_id, lane_data) in messages {
+				// messages may still be delivered over the upgrading lane, but not over the closed
+				ensure!(!Self::lane_state(lane_id).is_closed(), Error::<T, I>::LaneClosed);
+
 				let mut lane = inbound_lane::<T, I>(lane_id);
 
 				// subtract extra storage proof bytes from the actual PoV size - there may be
@@ -343,6 +550,7 @@ pub mod pallet {
 
 				let mut lane_messages_received_status =
 					ReceivedMessages::new(lane_id, Vec::with_capacity(lane_data.messages.len()));
+				let mut is_lane_fatal_error_reported = false;
 				for mut message in lane_data.messages {
 					debug_assert_eq!(message.key.lane_id, lane_id);
 					total_messages += 1;
@@ -384,6 +592,25 @@ pub mod pallet {
 						ReceivalResult::TooManyUnrewardedRelayers |
 						ReceivalResult::TooManyUnconfirmedMessages => message_dispatch_weight,
 					};
+
+					// the lane won't accept any more messages until it receives delivery
+					// confirmations, so let operators know once per lane
+					if receival_result.is_fatal() && !is_lane_fatal_error_reported {
+						log::error!(
+							target: LOG_TARGET,
+							"Inbound lane {:?} has rejected message {}: {:?}",
+							lane_id,
+							message.key.nonce,
+							receival_result,
+						);
+						Self::deposit_event(Event::LaneFatalError {
+							lane_id,
+							nonce: message.key.nonce,
+							result: receival_result.clone(),
+						});
+						is_lane_fatal_error_reported = true;
+					}
+
 					lane_messages_received_status.push(message.key.nonce, receival_result);
 
 					let unspent_weight = unspent_weight.min(message_dispatch_weight);
@@ -391,9 +618,23 @@ pub mod pallet {
 					actual_weight = actual_weight.saturating_sub(unspent_weight);
 				}
 
+				// messages are only accepted in order, so this should never happen, but we want
+				// to know if some messages have been lost anyway
+				if lane.storage_mut().get_or_init_data().has_gap() {
+					log::error!(
+						target: LOG_TARGET,
+						"Detected a gap between delivered messages at inbound lane {:?}",
+						lane_id,
+					);
+					Self::deposit_event(Event::GapDetected { lane_id });
+				}
+
 				messages_received_status.push(lane_messages_received_status);
 			}
 
+			// count dispatched messages towards the relayer limit
+			note_relayer_delivery::<T, I>(&relayer_id_at_this_chain, valid_messages);
+
 			// let's now deal with relayer payments
 			T::DeliveryPayments::pay_reward(
 				relayer_id_at_this_chain,
@@ -416,12 +657,49 @@ pub mod pallet {
 			Ok(PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee: Pays::Yes })
 		}
 
+		/// Receive messages proof from bridged chain and deliver messages of two lanes atomically.
+		///
+		/// The proof must contain exactly the `lane_a` and `lane_b` messages. Either all of them
+		/// are delivered, or the call fails and nothing is changed. Apart from that, the call
+		/// fails for the same reasons as the `receive_messages_proof` call. See the
+		/// [`CrossLaneDeliveryCoordinator`] for details.
+		#[pallet::call_index(9)]
+		#[pallet::weight(CrossLaneDeliveryCoordinator::<T, I>::delivery_weight(
+			proof,
+			*messages_count,
+			*dispatch_weight,
+		))]
+		pub fn receive_messages_proof_atomically(
+			origin: OriginFor<T>,
+			relayer_id_at_bridged_chain: T::InboundRelayer,
+			lane_a: (LaneId, RangeInclusive<MessageNonce>),
+			lane_b: (LaneId, RangeInclusive<MessageNonce>),
+			proof: MessagesProofOf<T, I>,
+			messages_count: u32,
+			dispatch_weight: Weight,
+		) -> DispatchResultWithPostInfo {
+			let token =
+				CrossLaneDeliveryCoordinator::<T, I>::begin_atomic_delivery(lane_a, lane_b)?;
+			let actual_weight = CrossLaneDeliveryCoordinator::<T, I>::commit_atomic_delivery(
+				origin,
+				token,
+				&relayer_id_at_bridged_chain,
+				proof,
+				messages_count,
+				dispatch_weight,
+			)?;
+
+			Ok(PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee: Pays::Yes })
+		}
+
 		/// Receive messages delivery proof from bridged chain.
 		#[pallet::call_index(3)]
 		#[pallet::weight(T::WeightInfo::receive_messages_delivery_proof_weight(
 			proof,
 			relayers_state,
-		))]
+		).saturating_add(T::WeightInfo::close_fee_escrows(
+			u32::try_from(relayers_state.total_messages).unwrap_or(u32::MAX),
+		)))]
 		pub fn receive_messages_delivery_proof(
 			origin: OriginFor<T>,
 			proof: MessagesDeliveryProofOf<T, I>,
@@ -449,6 +727,7 @@ pub mod pallet {
 			// mark messages as delivered
 			let mut lane = outbound_lane::<T, I>(lane_id);
 			let last_delivered_nonce = lane_data.last_delivered_nonce();
+			let mut close_fee_escrows_weight = Weight::zero();
 			let confirmed_messages = lane
 				.confirm_delivery(
 					relayers_state.total_messages,
@@ -465,6 +744,10 @@ pub mod pallet {
 					messages: confirmed_messages,
 				});
 
+				// pay escrowed fees of confirmed messages to relayers that have delivered them
+				close_fee_escrows_weight =
+					Self::close_fee_escrows(lane_id, &lane_data.relayers, &received_range);
+
 				// if some new messages have been confirmed, reward relayers
 				let actually_rewarded_relayers = T::DeliveryConfirmationPayments::pay_reward(
 					lane_id,
@@ -503,7 +786,8 @@ pub mod pallet {
 			let actual_weight = T::WeightInfo::receive_messages_delivery_proof_weight(
 				&PreComputedSize(proof_size as usize),
 				&relayers_state,
-			);
+			)
+			.saturating_add(close_fee_escrows_weight);
 
 			Ok(PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee: Pays::Yes })
 		}
@@ -531,6 +815,51 @@ pub mod pallet {
 			/// Delivered messages.
 			messages: DeliveredMessages,
 		},
+		/// The inbound lane has rejected a message and won't accept any more messages until
+		/// the delivery confirmations reach it.
+		LaneFatalError {
+			/// Lane, which has rejected the message.
+			lane_id: LaneId,
+			/// Nonce of the first rejected message.
+			nonce: MessageNonce,
+			/// Reason of the rejection.
+			result: ReceivalResult<<T::MessageDispatch as MessageDispatch>::DispatchLevelResult>,
+		},
+		/// Some messages are missing between the unrewarded relayer entries of the inbound lane.
+		GapDetected {
+			/// Lane, which has lost the messages.
+			lane_id: LaneId,
+		},
+		/// The state of the lane has been changed.
+		LaneStateChanged {
+			/// Lane, which state has been changed.
+			lane_id: LaneId,
+			/// New state of the lane.
+			state: LaneState<BlockNumberFor<T>>,
+		},
+		/// The lane protocol upgrade has been completed.
+		LaneUpgraded {
+			/// Lane, which has been upgraded.
+			lane_id: LaneId,
+			/// Version of the lane protocol after the upgrade.
+			version: u32,
+		},
+		/// The priority of the outbound lane has been changed.
+		LanePriorityChanged {
+			/// Lane, which priority has been changed.
+			lane_id: LaneId,
+			/// New priority of the lane.
+			priority: LanePriority,
+		},
+		/// The fee for delivering the message has been escrowed.
+		MessageFeeEscrowed {
+			/// Key of the message.
+			key: MessageKey,
+			/// Account that has paid the fee.
+			payer: T::AccountId,
+			/// Amount of the escrowed fee.
+			amount: FeeBalanceOf<T, I>,
+		},
 	}
 
 	#[pallet::error]
@@ -566,6 +895,34 @@ pub mod pallet {
 		ReceivalConfirmation(ReceivalConfirmationError),
 		/// Error generated by the `OwnedBridgeModule` trait.
 		BridgeModule(bp_runtime::OwnedBridgeModuleError),
+		/// The lane is not opened.
+		LaneNotOpened,
+		/// The lane is closed.
+		LaneClosed,
+		/// The lane is not closed.
+		LaneNotClosed,
+		/// The lane protocol is not being upgraded.
+		LaneNotUpgrading,
+		/// The new lane protocol version must be greater than the current one.
+		InvalidLaneVersion,
+		/// The fee of the message is already escrowed.
+		FeeAlreadyEscrowed,
+		/// There is no escrowed fee of the message.
+		FeeNotEscrowed,
+		/// The relayer would deliver more than the maximal number of messages in this block.
+		RelayerCapExceeded,
+		/// Messages of the atomic delivery do not match messages, expected by the lanes.
+		AtomicDeliveryNoncesMismatch,
+		/// Some message of the atomic delivery has been rejected by its lane.
+		AtomicDeliveryFailed,
+		/// The payer of the message fee can't afford it.
+		InsufficientFeeBalance,
+		/// The message is not sent yet or its delivery is already confirmed.
+		MessageIsNotQueued,
+		/// Only the sender of the message may escrow its fee.
+		NotMessageSender,
+		/// Too many fee escrows expire at the same block.
+		TooManyFeeEscrows,
 	}
 
 	/// Optional pallet owner.
@@ -621,11 +978,80 @@ pub mod pallet {
 		MaxValues = MaybeOutboundLanesCount<T, I>,
 	>;
 
+	/// Map of lane id => outbound lane priority.
+	///
+	/// The priority is kept outside of the `OutboundLaneData`, because the latter is proved to the
+	/// bridged chain and its encoding must not change.
+	#[pallet::storage]
+	pub type OutboundLanePriorities<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, LaneId, LanePriority, ValueQuery>;
+
+	/// Map of lane id => lane state. Lanes are opened by default.
+	///
+	/// The expired upgrade is kept here until the lane state is changed again, or until the
+	/// `migration::CompleteExpiredLaneUpgrades` migration is executed. Use the
+	/// `Pallet::lane_state` to get the actual lane state.
+	#[pallet::storage]
+	pub type LaneStates<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, LaneId, LaneState<BlockNumberFor<T>>, ValueQuery>;
+
+	/// Map of lane id => lane protocol version.
+	///
+	/// Use the `Pallet::lane_version` to get the actual lane protocol version.
+	#[pallet::storage]
+	pub type LaneVersions<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, LaneId, u32, ValueQuery>;
+
 	/// All queued outbound messages.
 	#[pallet::storage]
 	pub type OutboundMessages<T: Config<I>, I: 'static = ()> =
 		StorageMap<_, Blake2_128Concat, MessageKey, StoredMessagePayload<T, I>>;
 
+	/// Number of the block, where the latest message has been sent over the outbound lane.
+	///
+	/// Relayers may use it to detect stuck lanes with `OutboundLaneData::is_stuck`.
+	#[pallet::storage]
+	pub type LastMessageBlock<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, LaneId, BlockNumberFor<T>>;
+
+	/// Senders of outbound messages, which may escrow fees for delivering them.
+	///
+	/// Only messages, sent with `SendMessageArgs::with_sender`, have an entry here. The entry is
+	/// removed when the message is pruned.
+	#[pallet::storage]
+	pub type OutboundMessageSenders<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, MessageKey, T::AccountId>;
+
+	/// Message fees, escrowed until the delivery of messages is confirmed.
+	#[pallet::storage]
+	pub type EscrowedFees<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, MessageKey, EscrowedFeeOf<T, I>>;
+
+	/// Number of escrowed fees of messages, sent over the lane.
+	///
+	/// Delivery confirmations of lanes without escrowed fees don't need to look for them.
+	#[pallet::storage]
+	pub type LaneFeeEscrows<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, LaneId, u32, ValueQuery>;
+
+	/// Keys of messages, escrowed fees of which expire at the block.
+	#[pallet::storage]
+	pub type FeeEscrowExpirations<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<MessageKey, T::MaxFeeEscrowsPerBlock>,
+		ValueQuery,
+	>;
+
+	/// Number of messages, delivered by every relayer in the current block.
+	///
+	/// The map is killed at the beginning of every block. Nothing is tracked if
+	/// `MaxMessagesPerRelayerPerBlock` of `ThisChain` is `MessageNonce::MAX`.
+	#[pallet::storage]
+	pub type RelayerDeliveryThisBlock<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, MessageNonce, ValueQuery>;
+
 	#[pallet::genesis_config]
 	#[derive(DefaultNoBound)]
 	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
@@ -677,6 +1103,90 @@ pub mod pallet {
 		pub fn inbound_lane_data(lane: LaneId) -> InboundLaneData<T::InboundRelayer> {
 			InboundLanes::<T, I>::get(lane).0
 		}
+
+		/// Return given outbound lanes along with their priorities, ordered by decreasing
+		/// priority.
+		///
+		/// Lanes with the same priority keep their relative order.
+		pub fn outbound_lanes_by_priority(lanes: Vec<LaneId>) -> Vec<(LaneId, LanePriority)> {
+			let mut lanes = lanes
+				.into_iter()
+				.map(|lane| (lane, OutboundLanePriorities::<T, I>::get(lane)))
+				.collect::<Vec<_>>();
+			lanes.sort_by(|(_, a), (_, b)| b.cmp(a));
+			lanes
+		}
+
+		/// Return the lane state, taking expired upgrades into account.
+		pub fn lane_state(lane: LaneId) -> LaneState<BlockNumberFor<T>> {
+			match LaneStates::<T, I>::get(lane) {
+				LaneState::Upgrading { since, .. } if Self::is_upgrade_expired(since) =>
+					LaneState::Opened,
+				state => state,
+			}
+		}
+
+		/// Return the lane protocol version, taking expired upgrades into account.
+		pub fn lane_version(lane: LaneId) -> u32 {
+			match LaneStates::<T, I>::get(lane) {
+				LaneState::Upgrading { since, new_version } if Self::is_upgrade_expired(since) =>
+					new_version,
+				_ => LaneVersions::<T, I>::get(lane),
+			}
+		}
+
+		/// Returns true if the lane upgrade, started at the block `since`, is over.
+		pub(crate) fn is_upgrade_expired(since: BlockNumberFor<T>) -> bool {
+			frame_system::Pallet::<T>::block_number() >=
+				since.saturating_add(T::UpgradePeriod::get())
+		}
+
+		/// Return number of messages, delivered by the `relayer` in the current block.
+		pub fn relayer_deliveries_this_block(relayer: &T::AccountId) -> MessageNonce {
+			RelayerDeliveryThisBlock::<T, I>::get(relayer)
+		}
+
+		/// Forget the number of messages, delivered by relayers in the previous block.
+		///
+		/// There's at most one entry per delivery transaction of the previous block. Returns the
+		/// consumed weight.
+		fn clear_relayer_deliveries() -> Weight {
+			if max_messages_per_relayer_per_block::<T, I>() == MessageNonce::MAX {
+				return Weight::zero()
+			}
+
+			let removed = RelayerDeliveryThisBlock::<T, I>::clear(u32::MAX, None);
+			T::DbWeight::get().reads_writes(removed.loops.into(), removed.unique.into())
+		}
+
+		/// Ensure the correctness of the state of this pallet.
+		///
+		/// Checks that unrewarded relayer entries of every inbound lane are ordered by message
+		/// nonce, do not overlap, have no gaps between them and only contain messages that are not
+		/// yet confirmed.
+		#[cfg(any(feature = "try-runtime", test))]
+		pub fn do_try_state() -> Result<(), sp_runtime::TryRuntimeError> {
+			for (_, lane_data) in InboundLanes::<T, I>::iter() {
+				let mut last_nonce = lane_data.last_confirmed_nonce;
+				for entry in &lane_data.relayers {
+					ensure!(
+						entry.messages.begin > last_nonce,
+						"Unrewarded relayer entries are not ordered by message nonce"
+					);
+					ensure!(
+						entry.messages.begin <= entry.messages.end,
+						"Unrewarded relayer entry has invalid messages range"
+					);
+					last_nonce = entry.messages.end;
+				}
+				ensure!(
+					!lane_data.has_gap(),
+					"Unrewarded relayer entries have a gap between message nonces"
+				);
+			}
+
+			Ok(())
+		}
 	}
 
 	/// Get-parameter that returns number of active outbound lanes that the pallet maintains.
@@ -695,6 +1205,16 @@ pub mod pallet {
 pub struct SendMessageArgs<T: Config<I>, I: 'static> {
 	lane_id: LaneId,
 	payload: StoredMessagePayload<T, I>,
+	sender: Option<T::AccountId>,
+}
+
+impl<T: Config<I>, I: 'static> SendMessageArgs<T, I> {
+	/// Remember the account that sends the message, so that it may escrow the fee for delivering
+	/// the message using the `escrow_message_fee` call.
+	pub fn with_sender(mut self, sender: T::AccountId) -> Self {
+		self.sender = Some(sender);
+		self
+	}
 }
 
 impl<T, I> bp_messages::source_chain::MessagesBridge<T::OutboundPayload> for Pallet<T, I>
@@ -714,6 +1234,9 @@ where
 		// let's check if outbound lane is active
 		ensure!(T::ActiveOutboundLanes::get().contains(&lane), Error::<T, I>::InactiveOutboundLane);
 
+		// new messages may not be sent over closed or upgrading lanes
+		ensure!(Pallet::<T, I>::lane_state(lane).is_open(), Error::<T, I>::LaneNotOpened);
+
 		// let's first check if message can be delivered to target chain
 		T::TargetHeaderChain::verify_message(message).map_err(|err| {
 			log::trace!(
@@ -731,6 +1254,7 @@ where
 			payload: StoredMessagePayload::<T, I>::try_from(message.encode()).map_err(|_| {
 				Error::<T, I>::MessageRejectedByPallet(VerificationError::MessageTooLarge)
 			})?,
+			sender: None,
 		})
 	}
 
@@ -739,6 +1263,13 @@ where
 		let mut lane = outbound_lane::<T, I>(args.lane_id);
 		let message_len = args.payload.len();
 		let nonce = lane.send_message(args.payload);
+		if let Some(sender) = args.sender {
+			OutboundMessageSenders::<T, I>::insert(
+				MessageKey { lane_id: args.lane_id, nonce },
+				sender,
+			);
+		}
+		LastMessageBlock::<T, I>::insert(args.lane_id, frame_system::Pallet::<T>::block_number());
 
 		// return number of messages in the queue to let sender know about its state
 		let enqueued_messages = lane.data().queued_messages().saturating_len();
@@ -768,6 +1299,75 @@ fn ensure_normal_operating_mode<T: Config<I>, I: 'static>() -> Result<(), Error<
 	Err(Error::<T, I>::NotOperatingNormally)
 }
 
+/// Ensure that the pallet is not paused.
+fn ensure_not_paused<T: Config<I>, I: 'static>() -> Result<(), Error<T, I>> {
+	if PalletOperatingMode::<T, I>::get().is_paused() {
+		return Err(Error::<T, I>::NotOperatingNormally)
+	}
+
+	Ok(())
+}
+
+/// Returns the maximal number of messages that a single relayer may deliver in a single block.
+fn max_messages_per_relayer_per_block<T: Config<I>, I: 'static>() -> MessageNonce {
+	<T::ThisChain as ChainWithMessages>::MaxMessagesPerRelayerPerBlock::get()
+}
+
+/// Ensure that the `relayer` may deliver `messages` more messages in the current block without
+/// exceeding its per-block limit.
+fn ensure_relayer_may_deliver<T: Config<I>, I: 'static>(
+	relayer: &T::AccountId,
+	messages: MessageNonce,
+) -> Result<(), Error<T, I>> {
+	let max_messages_per_block = max_messages_per_relayer_per_block::<T, I>();
+	if max_messages_per_block == MessageNonce::MAX {
+		return Ok(())
+	}
+
+	ensure!(
+		Pallet::<T, I>::relayer_deliveries_this_block(relayer).saturating_add(messages) <=
+			max_messages_per_block,
+		Error::<T, I>::RelayerCapExceeded
+	);
+	Ok(())
+}
+
+/// Count `dispatched_messages` messages, delivered and dispatched by the `relayer` in the current
+/// block, towards its per-block limit.
+///
+/// The limit itself is checked by the `ensure_relayer_may_deliver` before messages are dispatched.
+fn note_relayer_delivery<T: Config<I>, I: 'static>(
+	relayer: &T::AccountId,
+	dispatched_messages: MessageNonce,
+) {
+	if max_messages_per_relayer_per_block::<T, I>() == MessageNonce::MAX {
+		return
+	}
+
+	RelayerDeliveryThisBlock::<T, I>::mutate(relayer, |delivered| {
+		delivered.saturating_accrue(dispatched_messages)
+	});
+}
+
+/// Returns weight of the `ensure_relayer_may_deliver` and `note_relayer_delivery` calls.
+fn relayer_delivery_weight<T: Config<I>, I: 'static>() -> Weight {
+	if max_messages_per_relayer_per_block::<T, I>() == MessageNonce::MAX {
+		return Weight::zero()
+	}
+
+	T::DbWeight::get().reads_writes(1, 1)
+}
+
+/// Returns weight of reading the inbound lane data to detect gaps between the delivered messages.
+fn gap_detection_weight<T: Config<I>, I: 'static>() -> Weight {
+	T::DbWeight::get().reads(1)
+}
+
+/// Returns weight of reading the lane state to check that messages may be delivered over the lane.
+fn lane_state_weight<T: Config<I>, I: 'static>() -> Weight {
+	T::DbWeight::get().reads(1)
+}
+
 /// Creates new inbound lane object, backed by runtime storage.
 fn inbound_lane<T: Config<I>, I: 'static>(
 	lane_id: LaneId,
@@ -882,7 +1482,9 @@ impl<T: Config<I>, I: 'static> OutboundLaneStorage for RuntimeOutboundLaneStorag
 	}
 
 	fn remove_message(&mut self, nonce: &MessageNonce) {
-		OutboundMessages::<T, I>::remove(MessageKey { lane_id: self.lane_id, nonce: *nonce });
+		let key = MessageKey { lane_id: self.lane_id, nonce: *nonce };
+		OutboundMessageSenders::<T, I>::remove(&key);
+		OutboundMessages::<T, I>::remove(key);
 	}
 }
 
@@ -916,23 +1518,25 @@ mod tests {
 	use crate::{
 		mock::{
 			inbound_unrewarded_relayers_state, message, message_payload, run_test,
-			unrewarded_relayer, AccountId, DbWeight, RuntimeEvent as TestEvent, RuntimeOrigin,
-			TestDeliveryConfirmationPayments, TestDeliveryPayments, TestMessageDispatch,
-			TestMessagesDeliveryProof, TestMessagesProof, TestOnMessagesDelivered, TestRelayer,
-			TestRuntime, TestWeightInfo, MAX_OUTBOUND_PAYLOAD_SIZE,
+			unrewarded_relayer, AccountId, Balances, DbWeight, FeeEscrowPeriod,
+			MaxMessagesPerRelayerPerBlock, MaxUnrewardedRelayerEntriesAtInboundLane,
+			RuntimeEvent as TestEvent, RuntimeOrigin, TestDeliveryConfirmationPayments,
+			TestDeliveryPayments, TestMessageDispatch, TestMessagesDeliveryProof,
+			TestMessagesProof, TestOnMessagesDelivered, TestRelayer, TestRuntime, TestWeightInfo,
+			UpgradePeriod, ENDOWED_ACCOUNT, MAX_OUTBOUND_PAYLOAD_SIZE,
 			PAYLOAD_REJECTED_BY_TARGET_CHAIN, REGULAR_PAYLOAD, TEST_LANE_ID, TEST_LANE_ID_2,
-			TEST_LANE_ID_3, TEST_RELAYER_A, TEST_RELAYER_B,
+			TEST_LANE_ID_3, TEST_RELAYER_A, TEST_RELAYER_B, TEST_RELAYER_C,
 		},
 		outbound_lane::ReceivalConfirmationError,
 	};
 	use bp_messages::{
-		source_chain::MessagesBridge, BridgeMessagesCall, UnrewardedRelayer,
+		source_chain::MessagesBridge, BridgeMessagesCall, ReceivalResult, UnrewardedRelayer,
 		UnrewardedRelayersState,
 	};
 	use bp_test_utils::generate_owned_bridge_module_tests;
 	use frame_support::{
 		assert_noop, assert_ok,
-		dispatch::Pays,
+		dispatch::{DispatchResultWithPostInfo, GetDispatchInfo, Pays},
 		storage::generator::{StorageMap, StorageValue},
 		traits::Hooks,
 		weights::Weight,
@@ -970,6 +1574,15 @@ mod tests {
 		);
 	}
 
+	fn send_regular_message_from(lane_id: LaneId, sender: AccountId) {
+		get_ready_for_events();
+
+		let valid_message = Pallet::<TestRuntime, ()>::validate_message(lane_id, &REGULAR_PAYLOAD)
+			.expect("validate_message has failed")
+			.with_sender(sender);
+		Pallet::<TestRuntime, ()>::send_message(valid_message);
+	}
+
 	fn receive_messages_delivery_proof() {
 		System::<TestRuntime>::set_block_number(1);
 		System::<TestRuntime>::reset_events();
@@ -1104,63 +1717,271 @@ mod tests {
 	}
 
 	#[test]
-	fn send_message_works() {
+	fn pallet_rejects_new_and_inbound_messages_in_paused_operating_mode() {
 		run_test(|| {
+			// send message first to be able to check that delivery_proof succeeds later
 			send_regular_message(TEST_LANE_ID);
-		});
-	}
 
-	#[test]
-	fn send_message_rejects_too_large_message() {
-		run_test(|| {
-			let mut message_payload = message_payload(1, 0);
-			// the payload isn't simply extra, so it'll definitely overflow
-			// `MAX_OUTBOUND_PAYLOAD_SIZE` if we add `MAX_OUTBOUND_PAYLOAD_SIZE` bytes to extra
-			message_payload
-				.extra
-				.extend_from_slice(&[0u8; MAX_OUTBOUND_PAYLOAD_SIZE as usize]);
+			PalletOperatingMode::<TestRuntime, ()>::put(MessagesOperatingMode::Paused);
+			assert!(!Pallet::<TestRuntime, ()>::is_halted());
+
 			assert_noop!(
-				Pallet::<TestRuntime, ()>::validate_message(TEST_LANE_ID, &message_payload.clone(),),
-				Error::<TestRuntime, ()>::MessageRejectedByPallet(
-					VerificationError::MessageTooLarge
-				),
+				Pallet::<TestRuntime, ()>::validate_message(TEST_LANE_ID, &REGULAR_PAYLOAD),
+				Error::<TestRuntime, ()>::NotOperatingNormally,
 			);
 
-			// let's check that we're able to send `MAX_OUTBOUND_PAYLOAD_SIZE` messages
-			while message_payload.encoded_size() as u32 > MAX_OUTBOUND_PAYLOAD_SIZE {
-				message_payload.extra.pop();
-			}
-			assert_eq!(message_payload.encoded_size() as u32, MAX_OUTBOUND_PAYLOAD_SIZE);
-
-			let valid_message =
-				Pallet::<TestRuntime, ()>::validate_message(TEST_LANE_ID, &message_payload)
-					.expect("validate_message has failed");
-			Pallet::<TestRuntime, ()>::send_message(valid_message);
-		})
-	}
-
-	#[test]
-	fn chain_verifier_rejects_invalid_message_in_send_message() {
-		run_test(|| {
-			// messages with this payload are rejected by target chain verifier
 			assert_noop!(
-				Pallet::<TestRuntime, ()>::validate_message(
-					TEST_LANE_ID,
-					&PAYLOAD_REJECTED_BY_TARGET_CHAIN,
+				Pallet::<TestRuntime>::receive_messages_proof(
+					RuntimeOrigin::signed(1),
+					TEST_RELAYER_A,
+					Ok(vec![message(1, REGULAR_PAYLOAD)]).into(),
+					1,
+					REGULAR_PAYLOAD.declared_weight,
 				),
-				Error::<TestRuntime, ()>::MessageRejectedByChainVerifier(VerificationError::Other(
-					mock::TEST_ERROR
-				)),
+				Error::<TestRuntime, ()>::NotOperatingNormally,
 			);
-		});
-	}
 
-	#[test]
-	fn receive_messages_proof_works() {
-		run_test(|| {
-			assert_ok!(Pallet::<TestRuntime>::receive_messages_proof(
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_delivery_proof(
 				RuntimeOrigin::signed(1),
-				TEST_RELAYER_A,
+				TestMessagesDeliveryProof(Ok((
+					TEST_LANE_ID,
+					InboundLaneData {
+						last_confirmed_nonce: 1,
+						relayers: vec![unrewarded_relayer(1, 1, TEST_RELAYER_A)]
+							.into_iter()
+							.collect(),
+					},
+				))),
+				UnrewardedRelayersState {
+					unrewarded_relayer_entries: 1,
+					messages_in_oldest_entry: 1,
+					total_messages: 1,
+					last_delivered_nonce: 1,
+				},
+			));
+		});
+	}
+
+	#[test]
+	fn do_try_state_checks_inbound_lane_relayers_order() {
+		run_test(|| {
+			InboundLanes::<TestRuntime>::insert(
+				TEST_LANE_ID,
+				InboundLaneData {
+					last_confirmed_nonce: 1,
+					relayers: vec![
+						unrewarded_relayer(2, 3, TEST_RELAYER_A),
+						unrewarded_relayer(4, 4, TEST_RELAYER_B),
+					]
+					.into_iter()
+					.collect(),
+				},
+			);
+			assert_ok!(Pallet::<TestRuntime>::do_try_state());
+
+			InboundLanes::<TestRuntime>::insert(
+				TEST_LANE_ID,
+				InboundLaneData {
+					last_confirmed_nonce: 1,
+					relayers: vec![
+						unrewarded_relayer(4, 4, TEST_RELAYER_B),
+						unrewarded_relayer(2, 3, TEST_RELAYER_A),
+					]
+					.into_iter()
+					.collect(),
+				},
+			);
+			assert!(Pallet::<TestRuntime>::do_try_state().is_err());
+		});
+	}
+
+	#[test]
+	fn do_try_state_checks_inbound_lane_relayers_gaps() {
+		run_test(|| {
+			InboundLanes::<TestRuntime>::insert(
+				TEST_LANE_ID,
+				InboundLaneData {
+					last_confirmed_nonce: 1,
+					relayers: vec![
+						unrewarded_relayer(2, 3, TEST_RELAYER_A),
+						unrewarded_relayer(5, 5, TEST_RELAYER_B),
+					]
+					.into_iter()
+					.collect(),
+				},
+			);
+			assert!(Pallet::<TestRuntime>::do_try_state().is_err());
+
+			// message `2` has been lost right after the last confirmed one
+			InboundLanes::<TestRuntime>::insert(
+				TEST_LANE_ID,
+				InboundLaneData {
+					last_confirmed_nonce: 1,
+					relayers: vec![unrewarded_relayer(3, 3, TEST_RELAYER_A)].into_iter().collect(),
+				},
+			);
+			assert!(Pallet::<TestRuntime>::do_try_state().is_err());
+		});
+	}
+
+	#[test]
+	fn receive_messages_proof_detects_gaps() {
+		run_test(|| {
+			get_ready_for_events();
+
+			// no event if messages are delivered in order
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_proof(
+				RuntimeOrigin::signed(1),
+				TEST_RELAYER_A,
+				Ok(vec![message(1, REGULAR_PAYLOAD)]).into(),
+				1,
+				REGULAR_PAYLOAD.declared_weight,
+			));
+			assert!(!System::<TestRuntime>::events().iter().any(|record| matches!(
+				record.event,
+				TestEvent::Messages(Event::GapDetected { .. })
+			)));
+
+			// message `2` has been lost
+			InboundLanes::<TestRuntime>::insert(
+				TEST_LANE_ID,
+				InboundLaneData {
+					last_confirmed_nonce: 0,
+					relayers: vec![
+						unrewarded_relayer(1, 1, TEST_RELAYER_A),
+						unrewarded_relayer(3, 3, TEST_RELAYER_B),
+					]
+					.into_iter()
+					.collect(),
+				},
+			);
+			System::<TestRuntime>::reset_events();
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_proof(
+				RuntimeOrigin::signed(1),
+				TEST_RELAYER_A,
+				Ok(vec![message(4, REGULAR_PAYLOAD)]).into(),
+				1,
+				REGULAR_PAYLOAD.declared_weight,
+			));
+			assert!(System::<TestRuntime>::events().contains(&EventRecord {
+				phase: Phase::Initialization,
+				event: TestEvent::Messages(Event::GapDetected { lane_id: TEST_LANE_ID }),
+				topics: vec![],
+			}));
+			assert!(Pallet::<TestRuntime>::do_try_state().is_err());
+		});
+	}
+
+	#[test]
+	fn receive_messages_proof_reports_fatal_lane_errors() {
+		run_test(|| {
+			get_ready_for_events();
+
+			// all unrewarded relayer entries are occupied by other relayers
+			InboundLanes::<TestRuntime>::insert(
+				TEST_LANE_ID,
+				InboundLaneData {
+					last_confirmed_nonce: 0,
+					relayers: (1..=MaxUnrewardedRelayerEntriesAtInboundLane::get())
+						.map(|nonce| {
+							let relayer =
+								if nonce % 2 == 0 { TEST_RELAYER_B } else { TEST_RELAYER_C };
+							unrewarded_relayer(nonce, nonce, relayer)
+						})
+						.collect(),
+				},
+			);
+
+			let first_nonce = MaxUnrewardedRelayerEntriesAtInboundLane::get() + 1;
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_proof(
+				RuntimeOrigin::signed(1),
+				TEST_RELAYER_A,
+				Ok(vec![
+					message(first_nonce, REGULAR_PAYLOAD),
+					message(first_nonce + 1, REGULAR_PAYLOAD),
+				])
+				.into(),
+				2,
+				REGULAR_PAYLOAD.declared_weight * 2,
+			));
+
+			// the event is only deposited once per lane
+			let fatal_errors = System::<TestRuntime>::events()
+				.into_iter()
+				.filter_map(|record| match record.event {
+					TestEvent::Messages(event @ Event::LaneFatalError { .. }) => Some(event),
+					_ => None,
+				})
+				.collect::<Vec<_>>();
+			assert_eq!(
+				fatal_errors,
+				vec![Event::LaneFatalError {
+					lane_id: TEST_LANE_ID,
+					nonce: first_nonce,
+					result: ReceivalResult::TooManyUnrewardedRelayers,
+				}],
+			);
+		});
+	}
+
+	#[test]
+	fn send_message_works() {
+		run_test(|| {
+			send_regular_message(TEST_LANE_ID);
+		});
+	}
+
+	#[test]
+	fn send_message_rejects_too_large_message() {
+		run_test(|| {
+			let mut message_payload = message_payload(1, 0);
+			// the payload isn't simply extra, so it'll definitely overflow
+			// `MAX_OUTBOUND_PAYLOAD_SIZE` if we add `MAX_OUTBOUND_PAYLOAD_SIZE` bytes to extra
+			message_payload
+				.extra
+				.extend_from_slice(&[0u8; MAX_OUTBOUND_PAYLOAD_SIZE as usize]);
+			assert_noop!(
+				Pallet::<TestRuntime, ()>::validate_message(TEST_LANE_ID, &message_payload.clone(),),
+				Error::<TestRuntime, ()>::MessageRejectedByPallet(
+					VerificationError::MessageTooLarge
+				),
+			);
+
+			// let's check that we're able to send `MAX_OUTBOUND_PAYLOAD_SIZE` messages
+			while message_payload.encoded_size() as u32 > MAX_OUTBOUND_PAYLOAD_SIZE {
+				message_payload.extra.pop();
+			}
+			assert_eq!(message_payload.encoded_size() as u32, MAX_OUTBOUND_PAYLOAD_SIZE);
+
+			let valid_message =
+				Pallet::<TestRuntime, ()>::validate_message(TEST_LANE_ID, &message_payload)
+					.expect("validate_message has failed");
+			Pallet::<TestRuntime, ()>::send_message(valid_message);
+		})
+	}
+
+	#[test]
+	fn chain_verifier_rejects_invalid_message_in_send_message() {
+		run_test(|| {
+			// messages with this payload are rejected by target chain verifier
+			assert_noop!(
+				Pallet::<TestRuntime, ()>::validate_message(
+					TEST_LANE_ID,
+					&PAYLOAD_REJECTED_BY_TARGET_CHAIN,
+				),
+				Error::<TestRuntime, ()>::MessageRejectedByChainVerifier(VerificationError::Other(
+					mock::TEST_ERROR
+				)),
+			);
+		});
+	}
+
+	#[test]
+	fn receive_messages_proof_works() {
+		run_test(|| {
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_proof(
+				RuntimeOrigin::signed(1),
+				TEST_RELAYER_A,
 				Ok(vec![message(1, REGULAR_PAYLOAD)]).into(),
 				1,
 				REGULAR_PAYLOAD.declared_weight,
@@ -1172,6 +1993,204 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn upgrading_lane_rejects_new_messages_but_accepts_delivery() {
+		run_test(|| {
+			assert_ok!(Pallet::<TestRuntime>::start_lane_upgrade(
+				RuntimeOrigin::root(),
+				TEST_LANE_ID,
+				1,
+			));
+			assert_eq!(
+				Pallet::<TestRuntime>::lane_state(TEST_LANE_ID),
+				LaneState::Upgrading { since: 0, new_version: 1 },
+			);
+
+			assert_noop!(
+				Pallet::<TestRuntime, ()>::validate_message(TEST_LANE_ID, &REGULAR_PAYLOAD),
+				Error::<TestRuntime, ()>::LaneNotOpened,
+			);
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_proof(
+				RuntimeOrigin::signed(1),
+				TEST_RELAYER_A,
+				Ok(vec![message(1, REGULAR_PAYLOAD)]).into(),
+				1,
+				REGULAR_PAYLOAD.declared_weight,
+			));
+			assert_eq!(InboundLanes::<TestRuntime>::get(TEST_LANE_ID).0.last_delivered_nonce(), 1);
+		});
+	}
+
+	#[test]
+	fn lane_upgrade_is_completed_after_upgrade_period() {
+		run_test(|| {
+			System::<TestRuntime>::set_block_number(1);
+			assert_ok!(Pallet::<TestRuntime>::start_lane_upgrade(
+				RuntimeOrigin::root(),
+				TEST_LANE_ID,
+				1,
+			));
+
+			System::<TestRuntime>::set_block_number(UpgradePeriod::get());
+			assert!(!Pallet::<TestRuntime>::lane_state(TEST_LANE_ID).is_open());
+			assert_eq!(Pallet::<TestRuntime>::lane_version(TEST_LANE_ID), 0);
+
+			System::<TestRuntime>::set_block_number(1 + UpgradePeriod::get());
+			assert_eq!(Pallet::<TestRuntime>::lane_state(TEST_LANE_ID), LaneState::Opened);
+			assert_eq!(Pallet::<TestRuntime>::lane_version(TEST_LANE_ID), 1);
+			assert_ok!(Pallet::<TestRuntime, ()>::validate_message(TEST_LANE_ID, &REGULAR_PAYLOAD));
+
+			// the next upgrade may be started right away
+			assert_ok!(Pallet::<TestRuntime>::start_lane_upgrade(
+				RuntimeOrigin::root(),
+				TEST_LANE_ID,
+				2,
+			));
+			assert_eq!(Pallet::<TestRuntime>::lane_version(TEST_LANE_ID), 1);
+		});
+	}
+
+	#[test]
+	fn lane_upgrade_may_be_completed_manually() {
+		run_test(|| {
+			get_ready_for_events();
+			assert_noop!(
+				Pallet::<TestRuntime>::complete_lane_upgrade(RuntimeOrigin::root(), TEST_LANE_ID),
+				Error::<TestRuntime, ()>::LaneNotUpgrading,
+			);
+			assert_ok!(Pallet::<TestRuntime>::start_lane_upgrade(
+				RuntimeOrigin::root(),
+				TEST_LANE_ID,
+				3,
+			));
+			assert_noop!(
+				Pallet::<TestRuntime>::complete_lane_upgrade(
+					RuntimeOrigin::signed(1),
+					TEST_LANE_ID
+				),
+				DispatchError::BadOrigin,
+			);
+
+			assert_ok!(Pallet::<TestRuntime>::complete_lane_upgrade(
+				RuntimeOrigin::root(),
+				TEST_LANE_ID,
+			));
+			assert_eq!(LaneStates::<TestRuntime>::get(TEST_LANE_ID), LaneState::Opened);
+			assert_eq!(LaneVersions::<TestRuntime>::get(TEST_LANE_ID), 3);
+			assert_eq!(
+				System::<TestRuntime>::events().last().map(|record| record.event.clone()),
+				Some(TestEvent::Messages(Event::LaneUpgraded {
+					lane_id: TEST_LANE_ID,
+					version: 3
+				})),
+			);
+
+			// upgrade to the same or older version is not allowed
+			assert_noop!(
+				Pallet::<TestRuntime>::start_lane_upgrade(RuntimeOrigin::root(), TEST_LANE_ID, 3),
+				Error::<TestRuntime, ()>::InvalidLaneVersion,
+			);
+		});
+	}
+
+	#[test]
+	fn closed_lane_rejects_new_messages_and_delivery() {
+		run_test(|| {
+			assert_ok!(Pallet::<TestRuntime>::start_lane_upgrade(
+				RuntimeOrigin::root(),
+				TEST_LANE_ID,
+				1,
+			));
+			assert_ok!(Pallet::<TestRuntime>::set_lane_closed(
+				RuntimeOrigin::root(),
+				TEST_LANE_ID,
+				true,
+			));
+
+			assert_noop!(
+				Pallet::<TestRuntime, ()>::validate_message(TEST_LANE_ID, &REGULAR_PAYLOAD),
+				Error::<TestRuntime, ()>::LaneNotOpened,
+			);
+			assert_noop!(
+				Pallet::<TestRuntime>::receive_messages_proof(
+					RuntimeOrigin::signed(1),
+					TEST_RELAYER_A,
+					Ok(vec![message(1, REGULAR_PAYLOAD)]).into(),
+					1,
+					REGULAR_PAYLOAD.declared_weight,
+				),
+				Error::<TestRuntime, ()>::LaneClosed,
+			);
+			assert_noop!(
+				Pallet::<TestRuntime>::start_lane_upgrade(RuntimeOrigin::root(), TEST_LANE_ID, 1),
+				Error::<TestRuntime, ()>::LaneNotOpened,
+			);
+
+			// reopening the lane does not resume the aborted upgrade
+			assert_ok!(Pallet::<TestRuntime>::set_lane_closed(
+				RuntimeOrigin::root(),
+				TEST_LANE_ID,
+				false,
+			));
+			assert_eq!(Pallet::<TestRuntime>::lane_state(TEST_LANE_ID), LaneState::Opened);
+			assert_eq!(Pallet::<TestRuntime>::lane_version(TEST_LANE_ID), 0);
+			assert_noop!(
+				Pallet::<TestRuntime>::set_lane_closed(RuntimeOrigin::root(), TEST_LANE_ID, false),
+				Error::<TestRuntime, ()>::LaneNotClosed,
+			);
+		});
+	}
+
+	#[test]
+	fn set_lane_priority_works() {
+		run_test(|| {
+			assert_eq!(
+				OutboundLanePriorities::<TestRuntime>::get(TEST_LANE_ID),
+				LanePriority::DEFAULT
+			);
+			assert_noop!(
+				Pallet::<TestRuntime>::set_lane_priority(
+					RuntimeOrigin::signed(1),
+					TEST_LANE_ID,
+					LanePriority(255),
+				),
+				DispatchError::BadOrigin,
+			);
+
+			let lane_data = OutboundLanes::<TestRuntime>::get(TEST_LANE_ID);
+			assert_ok!(Pallet::<TestRuntime>::set_lane_priority(
+				RuntimeOrigin::root(),
+				TEST_LANE_ID,
+				LanePriority(255),
+			));
+			assert_eq!(OutboundLanePriorities::<TestRuntime>::get(TEST_LANE_ID), LanePriority(255));
+			assert_eq!(OutboundLanes::<TestRuntime>::get(TEST_LANE_ID), lane_data);
+		});
+	}
+
+	#[test]
+	fn outbound_lanes_are_ordered_by_decreasing_priority() {
+		run_test(|| {
+			OutboundLanePriorities::<TestRuntime>::insert(TEST_LANE_ID_2, LanePriority(255));
+			OutboundLanePriorities::<TestRuntime>::insert(TEST_LANE_ID_3, LanePriority(0));
+
+			assert_eq!(
+				Pallet::<TestRuntime>::outbound_lanes_by_priority(vec![
+					TEST_LANE_ID_3,
+					TEST_LANE_ID,
+					TEST_LANE_ID_2,
+					LaneId([0, 0, 0, 4]),
+				]),
+				vec![
+					(TEST_LANE_ID_2, LanePriority(255)),
+					(TEST_LANE_ID, LanePriority::DEFAULT),
+					(LaneId([0, 0, 0, 4]), LanePriority::DEFAULT),
+					(TEST_LANE_ID_3, LanePriority(0)),
+				],
+			);
+		});
+	}
+
 	#[test]
 	fn receive_messages_proof_updates_confirmed_message_nonce() {
 		run_test(|| {
@@ -1304,6 +2323,147 @@ mod tests {
 		});
 	}
 
+	fn receive_messages(
+		relayer: AccountId,
+		nonces: RangeInclusive<MessageNonce>,
+	) -> DispatchResultWithPostInfo {
+		let messages_count = nonces.checked_len().unwrap_or(0) as u32;
+		Pallet::<TestRuntime, ()>::receive_messages_proof(
+			RuntimeOrigin::signed(relayer),
+			TEST_RELAYER_A,
+			Ok(nonces.map(|nonce| message(nonce, REGULAR_PAYLOAD)).collect::<Vec<_>>()).into(),
+			messages_count,
+			REGULAR_PAYLOAD.declared_weight * messages_count as u64,
+		)
+	}
+
+	#[test]
+	fn receive_messages_proof_rejects_messages_over_relayer_cap() {
+		run_test(|| {
+			MaxMessagesPerRelayerPerBlock::set(3);
+
+			assert_ok!(receive_messages(1, 1..=2));
+			assert_noop!(receive_messages(1, 3..=4), Error::<TestRuntime, ()>::RelayerCapExceeded);
+			assert_ok!(receive_messages(1, 3..=3));
+			assert_noop!(receive_messages(1, 4..=4), Error::<TestRuntime, ()>::RelayerCapExceeded);
+
+			assert_eq!(InboundLanes::<TestRuntime>::get(TEST_LANE_ID).0.last_delivered_nonce(), 3);
+			assert_eq!(Pallet::<TestRuntime, ()>::relayer_deliveries_this_block(&1), 3);
+		});
+	}
+
+	#[test]
+	fn receive_messages_proof_checks_relayer_cap_before_verifying_proof() {
+		run_test(|| {
+			MaxMessagesPerRelayerPerBlock::set(2);
+
+			assert_ok!(receive_messages(1, 1..=2));
+			assert_noop!(
+				Pallet::<TestRuntime, ()>::receive_messages_proof(
+					RuntimeOrigin::signed(1),
+					TEST_RELAYER_A,
+					Err(()).into(),
+					1,
+					Weight::zero(),
+				),
+				Error::<TestRuntime, ()>::RelayerCapExceeded,
+			);
+		});
+	}
+
+	#[test]
+	fn relayer_cap_is_tracked_separately_for_every_relayer() {
+		run_test(|| {
+			MaxMessagesPerRelayerPerBlock::set(2);
+
+			assert_ok!(receive_messages(1, 1..=2));
+			assert_noop!(receive_messages(1, 3..=3), Error::<TestRuntime, ()>::RelayerCapExceeded);
+			assert_ok!(receive_messages(2, 3..=4));
+
+			assert_eq!(InboundLanes::<TestRuntime>::get(TEST_LANE_ID).0.last_delivered_nonce(), 4);
+			assert_eq!(Pallet::<TestRuntime, ()>::relayer_deliveries_this_block(&1), 2);
+			assert_eq!(Pallet::<TestRuntime, ()>::relayer_deliveries_this_block(&2), 2);
+		});
+	}
+
+	#[test]
+	fn relayer_cap_only_counts_dispatched_messages() {
+		run_test(|| {
+			MaxMessagesPerRelayerPerBlock::set(2);
+
+			assert_ok!(receive_messages(1, 1..=2));
+			// messages 1 and 2 are already delivered, so they are not dispatched again
+			assert_ok!(receive_messages(2, 1..=2));
+			assert_eq!(Pallet::<TestRuntime, ()>::relayer_deliveries_this_block(&2), 0);
+
+			assert_ok!(receive_messages(2, 3..=4));
+			assert_eq!(Pallet::<TestRuntime, ()>::relayer_deliveries_this_block(&2), 2);
+		});
+	}
+
+	#[test]
+	fn relayer_cap_is_reset_at_next_block() {
+		run_test(|| {
+			MaxMessagesPerRelayerPerBlock::set(2);
+
+			assert_ok!(receive_messages(1, 1..=2));
+			assert_noop!(receive_messages(1, 3..=3), Error::<TestRuntime, ()>::RelayerCapExceeded);
+
+			System::<TestRuntime>::set_block_number(1);
+			Pallet::<TestRuntime, ()>::on_initialize(1);
+			assert_eq!(Pallet::<TestRuntime, ()>::relayer_deliveries_this_block(&1), 0);
+			assert_ok!(receive_messages(1, 3..=4));
+		});
+	}
+
+	#[test]
+	fn relayer_deliveries_are_cleared_on_initialize() {
+		run_test(|| {
+			MaxMessagesPerRelayerPerBlock::set(2);
+
+			assert_ok!(receive_messages(1, 1..=1));
+			assert_ok!(receive_messages(2, 2..=2));
+			assert_eq!(RelayerDeliveryThisBlock::<TestRuntime, ()>::iter().count(), 2);
+
+			System::<TestRuntime>::set_block_number(1);
+			assert_eq!(
+				Pallet::<TestRuntime, ()>::on_initialize(1),
+				DbWeight::get().reads_writes(2, 2) +
+					<TestRuntime as Config>::WeightInfo::refund_expired_fee_escrows(0),
+			);
+			assert_eq!(RelayerDeliveryThisBlock::<TestRuntime, ()>::iter().count(), 0);
+		});
+	}
+
+	#[test]
+	fn relayer_deliveries_are_not_tracked_without_cap() {
+		run_test(|| {
+			assert_ok!(receive_messages(1, 1..=2));
+			assert_eq!(RelayerDeliveryThisBlock::<TestRuntime, ()>::iter().count(), 0);
+		});
+	}
+
+	#[test]
+	fn relayer_delivery_tracking_is_included_in_weight() {
+		run_test(|| {
+			let proof = TestMessagesProof::from(Ok(vec![message(1, REGULAR_PAYLOAD)]));
+			let weight = |proof: &TestMessagesProof| {
+				Call::<TestRuntime, ()>::receive_messages_proof {
+					relayer_id_at_bridged_chain: TEST_RELAYER_A,
+					proof: proof.clone(),
+					messages_count: 1,
+					dispatch_weight: REGULAR_PAYLOAD.declared_weight,
+				}
+				.get_dispatch_info()
+				.weight
+			};
+
+			let weight_without_cap = weight(&proof);
+			MaxMessagesPerRelayerPerBlock::set(2);
+			assert_eq!(weight(&proof), weight_without_cap + DbWeight::get().reads_writes(1, 1));
+		});
+	}
+
 	#[test]
 	fn receive_messages_delivery_proof_works() {
 		run_test(|| {
@@ -1317,6 +2477,45 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn last_message_block_is_updated_on_every_send() {
+		run_test(|| {
+			assert_eq!(LastMessageBlock::<TestRuntime, ()>::get(TEST_LANE_ID), None);
+
+			send_regular_message(TEST_LANE_ID);
+			assert_eq!(LastMessageBlock::<TestRuntime, ()>::get(TEST_LANE_ID), Some(1));
+
+			System::<TestRuntime>::set_block_number(5);
+			let valid_message =
+				Pallet::<TestRuntime, ()>::validate_message(TEST_LANE_ID, &REGULAR_PAYLOAD)
+					.unwrap();
+			Pallet::<TestRuntime, ()>::send_message(valid_message);
+			assert_eq!(LastMessageBlock::<TestRuntime, ()>::get(TEST_LANE_ID), Some(5));
+			assert_eq!(LastMessageBlock::<TestRuntime, ()>::get(TEST_LANE_ID_2), None);
+		});
+	}
+
+	#[test]
+	fn lane_is_stuck_until_messages_are_confirmed() {
+		run_test(|| {
+			send_regular_message(TEST_LANE_ID);
+			let last_activity_block =
+				LastMessageBlock::<TestRuntime, ()>::get(TEST_LANE_ID).unwrap();
+			assert!(OutboundLanes::<TestRuntime, ()>::get(TEST_LANE_ID).is_stuck(
+				12,
+				10,
+				last_activity_block
+			));
+
+			receive_messages_delivery_proof();
+			assert!(!OutboundLanes::<TestRuntime, ()>::get(TEST_LANE_ID).is_stuck(
+				12,
+				10,
+				last_activity_block
+			));
+		});
+	}
+
 	#[test]
 	fn receive_messages_delivery_proof_rewards_relayers() {
 		run_test(|| {
@@ -1353,6 +2552,7 @@ mod tests {
 						..Default::default()
 					},
 				)
+				.saturating_add(DbWeight::get().reads(1))
 			);
 			assert!(TestDeliveryConfirmationPayments::is_reward_paid(TEST_RELAYER_A, 1));
 			assert!(!TestDeliveryConfirmationPayments::is_reward_paid(TEST_RELAYER_B, 1));
@@ -1396,6 +2596,7 @@ mod tests {
 						..Default::default()
 					},
 				)
+				.saturating_add(DbWeight::get().reads(1))
 			);
 			assert!(!TestDeliveryConfirmationPayments::is_reward_paid(TEST_RELAYER_A, 1));
 			assert!(TestDeliveryConfirmationPayments::is_reward_paid(TEST_RELAYER_B, 1));
@@ -1403,6 +2604,200 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn escrowed_message_fee_is_paid_to_relayer_on_delivery_confirmation() {
+		run_test(|| {
+			send_regular_message_from(TEST_LANE_ID, ENDOWED_ACCOUNT);
+			assert_ok!(Pallet::<TestRuntime>::escrow_message_fee(
+				RuntimeOrigin::signed(ENDOWED_ACCOUNT),
+				TEST_LANE_ID,
+				1,
+				100,
+			));
+			let key = MessageKey { lane_id: TEST_LANE_ID, nonce: 1 };
+			assert!(EscrowedFees::<TestRuntime, ()>::contains_key(&key));
+			assert_eq!(Balances::free_balance(ENDOWED_ACCOUNT), 1_000_000 - 100);
+			assert_eq!(
+				System::<TestRuntime>::events().last().map(|record| record.event.clone()),
+				Some(TestEvent::Messages(Event::MessageFeeEscrowed {
+					key,
+					payer: ENDOWED_ACCOUNT,
+					amount: 100,
+				})),
+			);
+
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_delivery_proof(
+				RuntimeOrigin::signed(1),
+				TestMessagesDeliveryProof(Ok((
+					TEST_LANE_ID,
+					InboundLaneData {
+						relayers: vec![unrewarded_relayer(1, 1, TEST_RELAYER_A)]
+							.into_iter()
+							.collect(),
+						..Default::default()
+					},
+				))),
+				UnrewardedRelayersState {
+					unrewarded_relayer_entries: 1,
+					messages_in_oldest_entry: 1,
+					total_messages: 1,
+					last_delivered_nonce: 1,
+				},
+			));
+			assert_eq!(Balances::free_balance(TEST_RELAYER_A), 100);
+			assert_eq!(EscrowedFees::<TestRuntime, ()>::iter().count(), 0);
+		});
+	}
+
+	#[test]
+	fn escrow_message_fee_fails_if_message_is_not_queued() {
+		run_test(|| {
+			// the message is not sent yet
+			assert_noop!(
+				Pallet::<TestRuntime>::escrow_message_fee(
+					RuntimeOrigin::signed(ENDOWED_ACCOUNT),
+					TEST_LANE_ID,
+					1,
+					100,
+				),
+				Error::<TestRuntime, ()>::MessageIsNotQueued,
+			);
+
+			// the delivery of the message is already confirmed
+			send_regular_message_from(TEST_LANE_ID, ENDOWED_ACCOUNT);
+			OutboundLanes::<TestRuntime, ()>::mutate(TEST_LANE_ID, |lane| {
+				lane.latest_received_nonce = 1
+			});
+			assert_noop!(
+				Pallet::<TestRuntime>::escrow_message_fee(
+					RuntimeOrigin::signed(ENDOWED_ACCOUNT),
+					TEST_LANE_ID,
+					1,
+					100,
+				),
+				Error::<TestRuntime, ()>::MessageIsNotQueued,
+			);
+		});
+	}
+
+	#[test]
+	fn escrow_message_fee_fails_if_payer_cant_afford_it() {
+		run_test(|| {
+			send_regular_message_from(TEST_LANE_ID, TEST_RELAYER_A);
+			assert_noop!(
+				Pallet::<TestRuntime>::escrow_message_fee(
+					RuntimeOrigin::signed(TEST_RELAYER_A),
+					TEST_LANE_ID,
+					1,
+					100,
+				),
+				Error::<TestRuntime, ()>::InsufficientFeeBalance,
+			);
+		});
+	}
+
+	#[test]
+	fn escrow_message_fee_fails_if_caller_is_not_message_sender() {
+		run_test(|| {
+			// the sender of the message is unknown
+			send_regular_message(TEST_LANE_ID);
+			assert_noop!(
+				Pallet::<TestRuntime>::escrow_message_fee(
+					RuntimeOrigin::signed(ENDOWED_ACCOUNT),
+					TEST_LANE_ID,
+					1,
+					100,
+				),
+				Error::<TestRuntime, ()>::NotMessageSender,
+			);
+
+			// the message is sent by other account
+			send_regular_message_from(TEST_LANE_ID, TEST_RELAYER_A);
+			assert_noop!(
+				Pallet::<TestRuntime>::escrow_message_fee(
+					RuntimeOrigin::signed(ENDOWED_ACCOUNT),
+					TEST_LANE_ID,
+					2,
+					100,
+				),
+				Error::<TestRuntime, ()>::NotMessageSender,
+			);
+		});
+	}
+
+	#[test]
+	fn escrowed_message_fee_is_refunded_if_delivery_is_not_confirmed_in_time() {
+		run_test(|| {
+			send_regular_message_from(TEST_LANE_ID, ENDOWED_ACCOUNT);
+			assert_ok!(Pallet::<TestRuntime>::escrow_message_fee(
+				RuntimeOrigin::signed(ENDOWED_ACCOUNT),
+				TEST_LANE_ID,
+				1,
+				100,
+			));
+			let expires_at = System::<TestRuntime>::block_number() + FeeEscrowPeriod::get();
+
+			Pallet::<TestRuntime>::on_initialize(expires_at - 1);
+			assert_eq!(Balances::free_balance(ENDOWED_ACCOUNT), 1_000_000 - 100);
+
+			Pallet::<TestRuntime>::on_initialize(expires_at);
+			assert_eq!(Balances::free_balance(ENDOWED_ACCOUNT), 1_000_000);
+			assert_eq!(EscrowedFees::<TestRuntime, ()>::iter().count(), 0);
+		});
+	}
+
+	#[test]
+	fn delivery_confirmation_does_not_pay_for_fee_escrows_at_lane_without_escrows() {
+		run_test(|| {
+			send_regular_message(TEST_LANE_ID);
+
+			let proof = TestMessagesDeliveryProof(Ok((
+				TEST_LANE_ID,
+				InboundLaneData {
+					relayers: vec![unrewarded_relayer(1, 1, TEST_RELAYER_A)].into_iter().collect(),
+					..Default::default()
+				},
+			)));
+			let relayers_state = UnrewardedRelayersState {
+				unrewarded_relayer_entries: 1,
+				messages_in_oldest_entry: 1,
+				total_messages: 1,
+				last_delivered_nonce: 1,
+			};
+			let result = Pallet::<TestRuntime>::receive_messages_delivery_proof(
+				RuntimeOrigin::signed(1),
+				proof.clone(),
+				relayers_state.clone(),
+			)
+			.unwrap();
+
+			assert_eq!(
+				result.actual_weight,
+				Some(
+					TestWeightInfo::receive_messages_delivery_proof_weight(
+						&PreComputedSize(proof.size() as usize),
+						&relayers_state,
+					)
+					.saturating_add(TestWeightInfo::close_fee_escrows(0))
+				),
+			);
+		});
+	}
+
+	#[test]
+	fn message_sender_is_removed_when_message_is_pruned() {
+		run_test(|| {
+			send_regular_message_from(TEST_LANE_ID, ENDOWED_ACCOUNT);
+			let key = MessageKey { lane_id: TEST_LANE_ID, nonce: 1 };
+			assert_eq!(OutboundMessageSenders::<TestRuntime, ()>::get(&key), Some(ENDOWED_ACCOUNT));
+
+			receive_messages_delivery_proof();
+			System::<TestRuntime>::set_block_number(2);
+			Pallet::<TestRuntime, ()>::on_idle(0, DbWeight::get().reads_writes(100, 100));
+			assert_eq!(OutboundMessageSenders::<TestRuntime, ()>::get(&key), None);
+		});
+	}
+
 	#[test]
 	fn receive_messages_delivery_proof_rejects_invalid_proof() {
 		run_test(|| {
@@ -1578,7 +2973,9 @@ mod tests {
 						&proof,
 						messages_count,
 						REGULAR_PAYLOAD.declared_weight,
-					);
+					)
+					.saturating_add(gap_detection_weight::<TestRuntime, ()>())
+					.saturating_add(lane_state_weight::<TestRuntime, ()>());
 				let result = Pallet::<TestRuntime>::receive_messages_proof(
 					RuntimeOrigin::signed(1),
 					TEST_RELAYER_A,
@@ -1714,7 +3111,7 @@ mod tests {
 			// messages 1+2 are confirmed in 1 tx, message 3 in a separate tx
 			// dispatch of message 2 has failed
 			let mut delivered_messages_1_and_2 = DeliveredMessages::new(1);
-			delivered_messages_1_and_2.note_dispatched_message();
+			delivered_messages_1_and_2.note_dispatched_message().unwrap();
 			let messages_1_and_2_proof = Ok((
 				TEST_LANE_ID,
 				InboundLaneData {
@@ -1883,8 +3280,8 @@ mod tests {
 
 			// if passed wight is enough to prune single message
 			assert_eq!(
-				Pallet::<TestRuntime, ()>::on_idle(0, dbw.reads_writes(1, 2)),
-				dbw.reads_writes(1, 2),
+				Pallet::<TestRuntime, ()>::on_idle(0, dbw.reads_writes(1, 3)),
+				dbw.reads_writes(1, 3),
 			);
 			assert_eq!(
 				outbound_lane::<TestRuntime, ()>(TEST_LANE_ID).data().oldest_unpruned_nonce,
@@ -1893,8 +3290,8 @@ mod tests {
 
 			// if passed wight is enough to prune two more messages
 			assert_eq!(
-				Pallet::<TestRuntime, ()>::on_idle(0, dbw.reads_writes(1, 3)),
-				dbw.reads_writes(1, 3),
+				Pallet::<TestRuntime, ()>::on_idle(0, dbw.reads_writes(1, 5)),
+				dbw.reads_writes(1, 5),
 			);
 			assert_eq!(
 				outbound_lane::<TestRuntime, ()>(TEST_LANE_ID).data().oldest_unpruned_nonce,
@@ -1904,7 +3301,7 @@ mod tests {
 			// if passed wight is enough to prune many messages
 			assert_eq!(
 				Pallet::<TestRuntime, ()>::on_idle(0, dbw.reads_writes(100, 100)),
-				dbw.reads_writes(1, 2),
+				dbw.reads_writes(1, 3),
 			);
 			assert_eq!(
 				outbound_lane::<TestRuntime, ()>(TEST_LANE_ID).data().oldest_unpruned_nonce,
@@ -1963,7 +3360,7 @@ mod tests {
 			System::<TestRuntime>::set_block_number(2);
 			assert_eq!(
 				Pallet::<TestRuntime, ()>::on_idle(0, dbw.reads_writes(100, 100)),
-				dbw.reads_writes(1, 2),
+				dbw.reads_writes(1, 3),
 			);
 			assert_eq!(
 				outbound_lane::<TestRuntime, ()>(TEST_LANE_ID).data().oldest_unpruned_nonce,
@@ -1979,7 +3376,7 @@ mod tests {
 
 			assert_eq!(
 				Pallet::<TestRuntime, ()>::on_idle(0, dbw.reads_writes(100, 100)),
-				dbw.reads_writes(1, 2),
+				dbw.reads_writes(1, 3),
 			);
 			assert_eq!(
 				outbound_lane::<TestRuntime, ()>(TEST_LANE_ID).data().oldest_unpruned_nonce,
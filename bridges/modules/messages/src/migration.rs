@@ -0,0 +1,183 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Storage migrations of the messages pallet.
+
+use crate::{Config, Event, LaneStates, LaneVersions, Pallet, LOG_TARGET};
+
+use bp_messages::LaneState;
+use frame_support::{
+	migrations::VersionedMigration,
+	traits::{Get, OnRuntimeUpgrade},
+	weights::Weight,
+};
+use sp_std::marker::PhantomData;
+
+#[cfg(feature = "try-runtime")]
+use bp_messages::LaneId;
+#[cfg(feature = "try-runtime")]
+use codec::{Decode, Encode};
+#[cfg(feature = "try-runtime")]
+use frame_system::pallet_prelude::BlockNumberFor;
+#[cfg(feature = "try-runtime")]
+use sp_runtime::TryRuntimeError;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+/// Migration that completes expired upgrades of active outbound lanes and sets the storage
+/// version of the pallet to `1`.
+///
+/// The lane upgrade that has started more than `UpgradePeriod` blocks ago is treated as
+/// completed by the `Pallet::lane_state` and the `Pallet::lane_version`, but its `Upgrading` state
+/// is kept in the storage until the lane state is changed again. The migration opens lanes from
+/// the `Config::ActiveOutboundLanes` list that are in this state and stores their new protocol
+/// versions, so the storage matches the actual lane states. The migration is only executed if
+/// the on-chain storage version of the pallet is `0`.
+pub type CompleteExpiredLaneUpgrades<T, I> = VersionedMigration<
+	0,
+	1,
+	version_unchecked::CompleteExpiredLaneUpgrades<T, I>,
+	Pallet<T, I>,
+	<T as frame_system::Config>::DbWeight,
+>;
+
+/// Migration logic that is not checking the storage version of the pallet.
+///
+/// Should only be used by the [`CompleteExpiredLaneUpgrades`].
+mod version_unchecked {
+	use super::*;
+
+	pub struct CompleteExpiredLaneUpgrades<T, I>(PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for CompleteExpiredLaneUpgrades<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			// we only look at the bounded set of active lanes, so the migration fits into a block
+			let lanes = T::ActiveOutboundLanes::get();
+			let mut count = 0u64;
+			for lane_id in lanes {
+				let new_version = match LaneStates::<T, I>::get(lane_id) {
+					LaneState::Upgrading { since, new_version }
+						if Pallet::<T, I>::is_upgrade_expired(since) =>
+						new_version,
+					_ => continue,
+				};
+
+				LaneStates::<T, I>::remove(lane_id);
+				LaneVersions::<T, I>::insert(lane_id, new_version);
+				Pallet::<T, I>::deposit_event(Event::LaneStateChanged {
+					lane_id: *lane_id,
+					state: LaneState::Opened,
+				});
+				Pallet::<T, I>::deposit_event(Event::LaneUpgraded {
+					lane_id: *lane_id,
+					version: new_version,
+				});
+				count += 1;
+			}
+
+			log::info!(
+				target: LOG_TARGET,
+				"Completed {} expired lane upgrades",
+				count,
+			);
+
+			T::DbWeight::get().reads_writes(lanes.len() as u64, count * 2)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			let lanes = T::ActiveOutboundLanes::get()
+				.iter()
+				.map(|lane_id| {
+					(
+						*lane_id,
+						Pallet::<T, I>::lane_state(*lane_id),
+						Pallet::<T, I>::lane_version(*lane_id),
+					)
+				})
+				.collect::<Vec<_>>();
+			Ok(lanes.encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let lanes: Vec<(LaneId, LaneState<BlockNumberFor<T>>, u32)> =
+				Decode::decode(&mut &state[..])
+					.map_err(|_| "Failed to decode lane states, stored before the upgrade")?;
+			for (lane_id, state, version) in lanes {
+				frame_support::ensure!(
+					LaneStates::<T, I>::get(lane_id) == state,
+					"Expired lane upgrade has not been completed"
+				);
+				frame_support::ensure!(
+					LaneVersions::<T, I>::get(lane_id) == version,
+					"Lane protocol version has been changed by the migration"
+				);
+			}
+
+			Ok(())
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{
+		run_test, TestRuntime, UpgradePeriod, TEST_LANE_ID, TEST_LANE_ID_2, TEST_LANE_ID_3,
+	};
+	use frame_support::traits::{GetStorageVersion, StorageVersion};
+
+	#[test]
+	fn complete_expired_lane_upgrades_opens_active_lanes_with_expired_upgrades() {
+		run_test(|| {
+			let upgrading = LaneState::Upgrading { since: 5, new_version: 2 };
+			let expired = LaneState::Upgrading { since: 1, new_version: 1 };
+			LaneStates::<TestRuntime, ()>::insert(TEST_LANE_ID, expired);
+			LaneStates::<TestRuntime, ()>::insert(TEST_LANE_ID_2, upgrading);
+			// the `TEST_LANE_ID_3` is not active, so it is not migrated
+			LaneStates::<TestRuntime, ()>::insert(TEST_LANE_ID_3, expired);
+			frame_system::Pallet::<TestRuntime>::set_block_number(1 + UpgradePeriod::get());
+			StorageVersion::new(0).put::<Pallet<TestRuntime, ()>>();
+
+			CompleteExpiredLaneUpgrades::<TestRuntime, ()>::on_runtime_upgrade();
+
+			assert!(!LaneStates::<TestRuntime, ()>::contains_key(TEST_LANE_ID));
+			assert_eq!(LaneVersions::<TestRuntime, ()>::get(TEST_LANE_ID), 1);
+			assert_eq!(LaneStates::<TestRuntime, ()>::get(TEST_LANE_ID_2), upgrading);
+			assert_eq!(LaneVersions::<TestRuntime, ()>::get(TEST_LANE_ID_2), 0);
+			assert_eq!(LaneStates::<TestRuntime, ()>::get(TEST_LANE_ID_3), expired);
+			assert_eq!(Pallet::<TestRuntime, ()>::on_chain_storage_version(), 1);
+		});
+	}
+
+	#[test]
+	fn complete_expired_lane_upgrades_is_executed_once() {
+		run_test(|| {
+			StorageVersion::new(1).put::<Pallet<TestRuntime, ()>>();
+			LaneStates::<TestRuntime, ()>::insert(
+				TEST_LANE_ID,
+				LaneState::Upgrading { since: 1, new_version: 1 },
+			);
+			frame_system::Pallet::<TestRuntime>::set_block_number(1 + UpgradePeriod::get());
+
+			CompleteExpiredLaneUpgrades::<TestRuntime, ()>::on_runtime_upgrade();
+
+			assert!(LaneStates::<TestRuntime, ()>::contains_key(TEST_LANE_ID));
+			assert_eq!(LaneVersions::<TestRuntime, ()>::get(TEST_LANE_ID), 0);
+		});
+	}
+}
@@ -26,17 +26,21 @@ use bp_messages::{
 		DeliveryPayments, DispatchMessage, DispatchMessageData, MessageDispatch,
 		ProvedLaneMessages, ProvedMessages, SourceHeaderChain,
 	},
-	DeliveredMessages, InboundLaneData, LaneId, Message, MessageKey, MessageNonce,
-	UnrewardedRelayer, UnrewardedRelayersState, VerificationError,
+	ChainWithMessages, DeliveredMessages, InboundLaneData, LaneId, Message, MessageKey,
+	MessageNonce, UnrewardedRelayer, UnrewardedRelayersState, VerificationError,
 };
-use bp_runtime::{messages::MessageDispatchResult, Size};
+use bp_runtime::{messages::MessageDispatchResult, Chain, ChainId, Size};
 use codec::{Decode, Encode};
 use frame_support::{
 	derive_impl, parameter_types,
 	weights::{constants::RocksDbWeight, Weight},
 };
 use scale_info::TypeInfo;
-use sp_runtime::BuildStorage;
+use sp_runtime::{
+	testing::{Header, H256},
+	traits::BlakeTwo256,
+	BuildStorage, MultiSignature,
+};
 use std::{
 	collections::{BTreeMap, VecDeque},
 	ops::RangeInclusive,
@@ -94,10 +98,44 @@ parameter_types! {
 	pub const MaxMessagesToPruneAtOnce: u64 = 10;
 	pub const MaxUnrewardedRelayerEntriesAtInboundLane: u64 = 16;
 	pub const MaxUnconfirmedMessagesAtInboundLane: u64 = 128;
+	pub static MaxMessagesPerRelayerPerBlock: u64 = u64::MAX;
+	pub const UpgradePeriod: u64 = 10;
+	pub const FeeEscrowPeriod: u64 = 10;
+	pub const MaxFeeEscrowsPerBlock: u32 = 4;
 	pub const TestBridgedChainId: bp_runtime::ChainId = *b"test";
 	pub const ActiveOutboundLanes: &'static [LaneId] = &[TEST_LANE_ID, TEST_LANE_ID_2];
 }
 
+/// The chain, to which messages are delivered in tests.
+pub struct ThisChain;
+
+impl Chain for ThisChain {
+	const ID: ChainId = *b"this";
+
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hasher = BlakeTwo256;
+	type Header = Header;
+	type AccountId = AccountId;
+	type Balance = Balance;
+	type Nonce = u64;
+	type Signature = MultiSignature;
+
+	fn max_extrinsic_size() -> u32 {
+		u32::MAX
+	}
+	fn max_extrinsic_weight() -> Weight {
+		Weight::MAX
+	}
+}
+
+impl ChainWithMessages for ThisChain {
+	const WITH_CHAIN_MESSAGES_PALLET_NAME: &'static str = "Messages";
+	const MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX: MessageNonce = 16;
+	const MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX: MessageNonce = 128;
+	type MaxMessagesPerRelayerPerBlock = MaxMessagesPerRelayerPerBlock;
+}
+
 /// weights of messages pallet calls we use in tests.
 pub type TestWeightInfo = ();
 
@@ -107,6 +145,8 @@ impl Config for TestRuntime {
 	type ActiveOutboundLanes = ActiveOutboundLanes;
 	type MaxUnrewardedRelayerEntriesAtInboundLane = MaxUnrewardedRelayerEntriesAtInboundLane;
 	type MaxUnconfirmedMessagesAtInboundLane = MaxUnconfirmedMessagesAtInboundLane;
+	type ThisChain = ThisChain;
+	type UpgradePeriod = UpgradePeriod;
 
 	type MaximalOutboundPayloadSize = frame_support::traits::ConstU32<MAX_OUTBOUND_PAYLOAD_SIZE>;
 	type OutboundPayload = TestPayload;
@@ -122,6 +162,10 @@ impl Config for TestRuntime {
 	type SourceHeaderChain = TestSourceHeaderChain;
 	type MessageDispatch = TestMessageDispatch;
 	type BridgedChainId = TestBridgedChainId;
+
+	type FeeEscrowCurrency = Balances;
+	type FeeEscrowPeriod = FeeEscrowPeriod;
+	type MaxFeeEscrowsPerBlock = MaxFeeEscrowsPerBlock;
 }
 
 #[cfg(feature = "runtime-benchmarks")]
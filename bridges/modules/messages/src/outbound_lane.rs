@@ -44,7 +44,7 @@ pub trait OutboundLaneStorage {
 	fn message(&self, nonce: &MessageNonce) -> Option<Self::StoredMessagePayload>;
 	/// Save outbound message in the storage.
 	fn save_message(&mut self, nonce: MessageNonce, message_payload: Self::StoredMessagePayload);
-	/// Remove outbound message from the storage.
+	/// Remove outbound message and its sender (if known) from the storage.
 	fn remove_message(&mut self, nonce: &MessageNonce);
 }
 
@@ -146,17 +146,18 @@ impl<S: OutboundLaneStorage> OutboundLane<S> {
 		db_weight: RuntimeDbWeight,
 		mut remaining_weight: Weight,
 	) -> Weight {
+		// removing the message also removes its sender entry
 		let write_weight = db_weight.writes(1);
-		let two_writes_weight = write_weight + write_weight;
+		let remove_message_weight = db_weight.writes(2);
 		let mut spent_weight = Weight::zero();
 		let mut data = self.storage.data();
-		while remaining_weight.all_gte(two_writes_weight) &&
+		while remaining_weight.all_gte(remove_message_weight + write_weight) &&
 			data.oldest_unpruned_nonce <= data.latest_received_nonce
 		{
 			self.storage.remove_message(&data.oldest_unpruned_nonce);
 
-			spent_weight += write_weight;
-			remaining_weight -= write_weight;
+			spent_weight += remove_message_weight;
+			remaining_weight -= remove_message_weight;
 			data.oldest_unpruned_nonce += 1;
 		}
 
@@ -378,7 +379,7 @@ mod tests {
 			);
 			assert_eq!(
 				lane.prune_messages(RocksDbWeight::get(), RocksDbWeight::get().writes(101)),
-				RocksDbWeight::get().writes(3),
+				RocksDbWeight::get().writes(5),
 			);
 			assert!(lane.storage.message(&1).is_none());
 			assert!(lane.storage.message(&2).is_none());
@@ -391,7 +392,7 @@ mod tests {
 			);
 			assert_eq!(
 				lane.prune_messages(RocksDbWeight::get(), RocksDbWeight::get().writes(101)),
-				RocksDbWeight::get().writes(2),
+				RocksDbWeight::get().writes(3),
 			);
 			assert!(lane.storage.message(&1).is_none());
 			assert!(lane.storage.message(&2).is_none());
@@ -22,6 +22,11 @@
 //! HOSTNAME: `covid`, CPU: `11th Gen Intel(R) Core(TM) i7-11800H @ 2.30GHz`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
 
+// Weights of `set_lane_priority`, `escrow_message_fee`, `close_fee_escrows`,
+// `refund_expired_fee_escrows`, `start_lane_upgrade`, `complete_lane_upgrade` and `set_lane_closed`
+// have not been generated by the benchmark CLI yet. They are estimated from the storage accesses
+// of their benchmarks and must be replaced by the output of the command below.
+
 // Executed Command:
 // target/release/unknown-bridge-node
 // benchmark
@@ -59,6 +64,13 @@ pub trait WeightInfo {
 	fn receive_delivery_proof_for_two_messages_by_single_relayer() -> Weight;
 	fn receive_delivery_proof_for_two_messages_by_two_relayers() -> Weight;
 	fn receive_single_message_proof_with_dispatch(i: u32) -> Weight;
+	fn set_lane_priority() -> Weight;
+	fn escrow_message_fee() -> Weight;
+	fn close_fee_escrows(n: u32) -> Weight;
+	fn refund_expired_fee_escrows(n: u32) -> Weight;
+	fn start_lane_upgrade() -> Weight;
+	fn complete_lane_upgrade() -> Weight;
+	fn set_lane_closed() -> Weight;
 }
 
 /// Weights for `pallet_bridge_messages` that are generated using one of the Bridge testnets.
@@ -292,6 +304,169 @@ impl<T: frame_system::Config> WeightInfo for BridgeWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(3_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: BridgeUnknownMessages PalletOwner (r:1 w:0)
+	///
+	/// Proof: BridgeUnknownMessages PalletOwner (max_values: Some(1), max_size: Some(32), added:
+	/// 527, mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeUnknownMessages OutboundLanePriorities (r:0 w:1)
+	///
+	/// Proof: BridgeUnknownMessages OutboundLanePriorities (max_values: None, max_size: Some(21),
+	/// added: 2496, mode: MaxEncodedLen)
+	fn set_lane_priority() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(8_464_000, 1517)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: BridgeUnknownMessages PalletOperatingMode (r:1 w:0)
+	///
+	/// Proof: BridgeUnknownMessages PalletOperatingMode (max_values: Some(1), max_size: Some(2),
+	/// added: 497, mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeUnknownMessages OutboundLanes (r:1 w:0)
+	///
+	/// Proof: BridgeUnknownMessages OutboundLanes (max_values: Some(1), max_size: Some(44), added:
+	/// 539, mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeUnknownMessages OutboundMessageSenders (r:1 w:0)
+	///
+	/// Proof: BridgeUnknownMessages OutboundMessageSenders (max_values: None, max_size: Some(60),
+	/// added: 2535, mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeUnknownMessages EscrowedFees (r:1 w:1)
+	///
+	/// Proof: BridgeUnknownMessages EscrowedFees (max_values: None, max_size: Some(80), added:
+	/// 2555, mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeUnknownMessages FeeEscrowExpirations (r:1 w:1)
+	///
+	/// Proof: BridgeUnknownMessages FeeEscrowExpirations (max_values: None, max_size: Some(781),
+	/// added: 3256, mode: MaxEncodedLen)
+	///
+	/// Storage: System Account (r:2 w:2)
+	///
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode:
+	/// MaxEncodedLen)
+	///
+	/// Storage: BridgeUnknownMessages LaneFeeEscrows (r:1 w:1)
+	///
+	/// Proof: BridgeUnknownMessages LaneFeeEscrows (max_values: None, max_size: Some(24), added:
+	/// 2499, mode: MaxEncodedLen)
+	fn escrow_message_fee() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(61_204_000, 4246)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: BridgeUnknownMessages LaneFeeEscrows (r:1 w:1)
+	///
+	/// Proof: BridgeUnknownMessages LaneFeeEscrows (max_values: None, max_size: Some(24), added:
+	/// 2499, mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeUnknownMessages EscrowedFees (r:n w:n)
+	///
+	/// Proof: BridgeUnknownMessages EscrowedFees (max_values: None, max_size: Some(80), added:
+	/// 2555, mode: MaxEncodedLen)
+	///
+	/// Storage: System Account (r:n w:n)
+	///
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode:
+	/// MaxEncodedLen)
+	fn close_fee_escrows(n: u32) -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(7_913_000, 3593)
+			.saturating_add(Weight::from_parts(34_520_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(n.into()))
+	}
+	/// Storage: BridgeUnknownMessages FeeEscrowExpirations (r:1 w:1)
+	///
+	/// Proof: BridgeUnknownMessages FeeEscrowExpirations (max_values: None, max_size: Some(781),
+	/// added: 3256, mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeUnknownMessages EscrowedFees (r:n w:n)
+	///
+	/// Proof: BridgeUnknownMessages EscrowedFees (max_values: None, max_size: Some(80), added:
+	/// 2555, mode: MaxEncodedLen)
+	///
+	/// Storage: System Account (r:n w:n)
+	///
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode:
+	/// MaxEncodedLen)
+	///
+	/// Storage: BridgeUnknownMessages LaneFeeEscrows (r:n w:n)
+	///
+	/// Proof: BridgeUnknownMessages LaneFeeEscrows (max_values: None, max_size: Some(24), added:
+	/// 2499, mode: MaxEncodedLen)
+	fn refund_expired_fee_escrows(n: u32) -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(6_381_000, 4246)
+			.saturating_add(Weight::from_parts(38_075_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 7657).saturating_mul(n.into()))
+	}
+	/// Storage: BridgeUnknownMessages PalletOwner (r:1 w:0)
+	///
+	/// Proof: BridgeUnknownMessages PalletOwner (max_values: Some(1), max_size: Some(32), added:
+	/// 527, mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeUnknownMessages LaneStates (r:1 w:1)
+	///
+	/// Proof: BridgeUnknownMessages LaneStates (max_values: None, max_size: Some(29), added: 2504,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeUnknownMessages LaneVersions (r:1 w:0)
+	///
+	/// Proof: BridgeUnknownMessages LaneVersions (max_values: None, max_size: Some(24), added:
+	/// 2499, mode: MaxEncodedLen)
+	fn start_lane_upgrade() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(14_118_000, 3489)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: BridgeUnknownMessages PalletOwner (r:1 w:0)
+	///
+	/// Proof: BridgeUnknownMessages PalletOwner (max_values: Some(1), max_size: Some(32), added:
+	/// 527, mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeUnknownMessages LaneStates (r:1 w:1)
+	///
+	/// Proof: BridgeUnknownMessages LaneStates (max_values: None, max_size: Some(29), added: 2504,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeUnknownMessages LaneVersions (r:0 w:1)
+	///
+	/// Proof: BridgeUnknownMessages LaneVersions (max_values: None, max_size: Some(24), added:
+	/// 2499, mode: MaxEncodedLen)
+	fn complete_lane_upgrade() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(13_290_000, 3494)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: BridgeUnknownMessages PalletOwner (r:1 w:0)
+	///
+	/// Proof: BridgeUnknownMessages PalletOwner (max_values: Some(1), max_size: Some(32), added:
+	/// 527, mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeUnknownMessages LaneStates (r:1 w:1)
+	///
+	/// Proof: BridgeUnknownMessages LaneStates (max_values: None, max_size: Some(29), added: 2504,
+	/// mode: MaxEncodedLen)
+	fn set_lane_closed() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(11_027_000, 3494)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -522,4 +697,167 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(3_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: BridgeUnknownMessages PalletOwner (r:1 w:0)
+	///
+	/// Proof: BridgeUnknownMessages PalletOwner (max_values: Some(1), max_size: Some(32), added:
+	/// 527, mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeUnknownMessages OutboundLanePriorities (r:0 w:1)
+	///
+	/// Proof: BridgeUnknownMessages OutboundLanePriorities (max_values: None, max_size: Some(21),
+	/// added: 2496, mode: MaxEncodedLen)
+	fn set_lane_priority() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(8_464_000, 1517)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: BridgeUnknownMessages PalletOperatingMode (r:1 w:0)
+	///
+	/// Proof: BridgeUnknownMessages PalletOperatingMode (max_values: Some(1), max_size: Some(2),
+	/// added: 497, mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeUnknownMessages OutboundLanes (r:1 w:0)
+	///
+	/// Proof: BridgeUnknownMessages OutboundLanes (max_values: Some(1), max_size: Some(44), added:
+	/// 539, mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeUnknownMessages OutboundMessageSenders (r:1 w:0)
+	///
+	/// Proof: BridgeUnknownMessages OutboundMessageSenders (max_values: None, max_size: Some(60),
+	/// added: 2535, mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeUnknownMessages EscrowedFees (r:1 w:1)
+	///
+	/// Proof: BridgeUnknownMessages EscrowedFees (max_values: None, max_size: Some(80), added:
+	/// 2555, mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeUnknownMessages FeeEscrowExpirations (r:1 w:1)
+	///
+	/// Proof: BridgeUnknownMessages FeeEscrowExpirations (max_values: None, max_size: Some(781),
+	/// added: 3256, mode: MaxEncodedLen)
+	///
+	/// Storage: System Account (r:2 w:2)
+	///
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode:
+	/// MaxEncodedLen)
+	///
+	/// Storage: BridgeUnknownMessages LaneFeeEscrows (r:1 w:1)
+	///
+	/// Proof: BridgeUnknownMessages LaneFeeEscrows (max_values: None, max_size: Some(24), added:
+	/// 2499, mode: MaxEncodedLen)
+	fn escrow_message_fee() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(61_204_000, 4246)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: BridgeUnknownMessages LaneFeeEscrows (r:1 w:1)
+	///
+	/// Proof: BridgeUnknownMessages LaneFeeEscrows (max_values: None, max_size: Some(24), added:
+	/// 2499, mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeUnknownMessages EscrowedFees (r:n w:n)
+	///
+	/// Proof: BridgeUnknownMessages EscrowedFees (max_values: None, max_size: Some(80), added:
+	/// 2555, mode: MaxEncodedLen)
+	///
+	/// Storage: System Account (r:n w:n)
+	///
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode:
+	/// MaxEncodedLen)
+	fn close_fee_escrows(n: u32) -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(7_913_000, 3593)
+			.saturating_add(Weight::from_parts(34_520_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(n.into()))
+	}
+	/// Storage: BridgeUnknownMessages FeeEscrowExpirations (r:1 w:1)
+	///
+	/// Proof: BridgeUnknownMessages FeeEscrowExpirations (max_values: None, max_size: Some(781),
+	/// added: 3256, mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeUnknownMessages EscrowedFees (r:n w:n)
+	///
+	/// Proof: BridgeUnknownMessages EscrowedFees (max_values: None, max_size: Some(80), added:
+	/// 2555, mode: MaxEncodedLen)
+	///
+	/// Storage: System Account (r:n w:n)
+	///
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode:
+	/// MaxEncodedLen)
+	///
+	/// Storage: BridgeUnknownMessages LaneFeeEscrows (r:n w:n)
+	///
+	/// Proof: BridgeUnknownMessages LaneFeeEscrows (max_values: None, max_size: Some(24), added:
+	/// 2499, mode: MaxEncodedLen)
+	fn refund_expired_fee_escrows(n: u32) -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(6_381_000, 4246)
+			.saturating_add(Weight::from_parts(38_075_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 7657).saturating_mul(n.into()))
+	}
+	/// Storage: BridgeUnknownMessages PalletOwner (r:1 w:0)
+	///
+	/// Proof: BridgeUnknownMessages PalletOwner (max_values: Some(1), max_size: Some(32), added:
+	/// 527, mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeUnknownMessages LaneStates (r:1 w:1)
+	///
+	/// Proof: BridgeUnknownMessages LaneStates (max_values: None, max_size: Some(29), added: 2504,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeUnknownMessages LaneVersions (r:1 w:0)
+	///
+	/// Proof: BridgeUnknownMessages LaneVersions (max_values: None, max_size: Some(24), added:
+	/// 2499, mode: MaxEncodedLen)
+	fn start_lane_upgrade() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(14_118_000, 3489)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: BridgeUnknownMessages PalletOwner (r:1 w:0)
+	///
+	/// Proof: BridgeUnknownMessages PalletOwner (max_values: Some(1), max_size: Some(32), added:
+	/// 527, mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeUnknownMessages LaneStates (r:1 w:1)
+	///
+	/// Proof: BridgeUnknownMessages LaneStates (max_values: None, max_size: Some(29), added: 2504,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeUnknownMessages LaneVersions (r:0 w:1)
+	///
+	/// Proof: BridgeUnknownMessages LaneVersions (max_values: None, max_size: Some(24), added:
+	/// 2499, mode: MaxEncodedLen)
+	fn complete_lane_upgrade() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(13_290_000, 3494)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: BridgeUnknownMessages PalletOwner (r:1 w:0)
+	///
+	/// Proof: BridgeUnknownMessages PalletOwner (max_values: Some(1), max_size: Some(32), added:
+	/// 527, mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeUnknownMessages LaneStates (r:1 w:1)
+	///
+	/// Proof: BridgeUnknownMessages LaneStates (max_values: None, max_size: Some(29), added: 2504,
+	/// mode: MaxEncodedLen)
+	fn set_lane_closed() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(11_027_000, 3494)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
@@ -20,7 +20,7 @@ use crate as pallet_xcm_bridge_hub;
 
 use bp_messages::{
 	target_chain::{DispatchMessage, MessageDispatch},
-	LaneId,
+	ChainWithMessages, LaneId, MessageNonce,
 };
 use bp_runtime::{messages::MessageDispatchResult, Chain, ChainId, UnderlyingChainProvider};
 use bridge_runtime_common::{
@@ -31,7 +31,11 @@ use bridge_runtime_common::{
 	messages_xcm_extension::{SenderAndLane, XcmBlobHauler},
 };
 use codec::Encode;
-use frame_support::{derive_impl, parameter_types, traits::ConstU32, weights::RuntimeDbWeight};
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{ConstU32, ConstU64},
+	weights::RuntimeDbWeight,
+};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header as SubstrateHeader,
@@ -89,6 +93,8 @@ impl pallet_bridge_messages::Config for TestRuntime {
 	type ActiveOutboundLanes = ActiveOutboundLanes;
 	type MaxUnrewardedRelayerEntriesAtInboundLane = ();
 	type MaxUnconfirmedMessagesAtInboundLane = ();
+	type ThisChain = ThisChain;
+	type UpgradePeriod = ConstU64<10>;
 	type MaximalOutboundPayloadSize = ConstU32<2048>;
 	type OutboundPayload = Vec<u8>;
 	type InboundPayload = Vec<u8>;
@@ -99,6 +105,9 @@ impl pallet_bridge_messages::Config for TestRuntime {
 	type OnMessagesDelivered = ();
 	type SourceHeaderChain = SourceHeaderChainAdapter<OnThisChainBridge>;
 	type MessageDispatch = TestMessageDispatch;
+	type FeeEscrowCurrency = Balances;
+	type FeeEscrowPeriod = ConstU64<10>;
+	type MaxFeeEscrowsPerBlock = ConstU32<4>;
 }
 
 pub struct TestMessagesWeights;
@@ -135,6 +144,34 @@ impl pallet_bridge_messages::WeightInfo for TestMessagesWeights {
 	fn receive_single_message_proof_with_dispatch(_: u32) -> Weight {
 		Weight::from_parts(1, 0)
 	}
+
+	fn set_lane_priority() -> Weight {
+		Weight::zero()
+	}
+
+	fn escrow_message_fee() -> Weight {
+		Weight::zero()
+	}
+
+	fn close_fee_escrows(_: u32) -> Weight {
+		Weight::zero()
+	}
+
+	fn refund_expired_fee_escrows(_: u32) -> Weight {
+		Weight::zero()
+	}
+
+	fn start_lane_upgrade() -> Weight {
+		Weight::zero()
+	}
+
+	fn complete_lane_upgrade() -> Weight {
+		Weight::zero()
+	}
+
+	fn set_lane_closed() -> Weight {
+		Weight::zero()
+	}
 }
 
 impl pallet_bridge_messages::WeightInfoExt for TestMessagesWeights {
@@ -220,6 +257,13 @@ impl Chain for ThisChain {
 	}
 }
 
+impl ChainWithMessages for ThisChain {
+	const WITH_CHAIN_MESSAGES_PALLET_NAME: &'static str = "Messages";
+	const MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX: MessageNonce = 16;
+	const MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX: MessageNonce = 1_000;
+	type MaxMessagesPerRelayerPerBlock = ConstU64<{ MessageNonce::MAX }>;
+}
+
 pub struct BridgedChain;
 pub type BridgedHeaderHash = H256;
 pub type BridgedChainHeader = SubstrateHeader;
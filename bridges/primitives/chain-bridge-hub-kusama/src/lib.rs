@@ -28,6 +28,7 @@ use bp_runtime::{
 use frame_support::{
 	dispatch::DispatchClass,
 	sp_runtime::{MultiAddress, MultiSigner},
+	traits::ConstU64,
 };
 use sp_runtime::RuntimeDebug;
 
@@ -71,6 +72,7 @@ impl ChainWithMessages for BridgeHubKusama {
 		MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX;
 	const MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX: MessageNonce =
 		MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX;
+	type MaxMessagesPerRelayerPerBlock = ConstU64<{ MessageNonce::MAX }>;
 }
 
 /// Public key of the chain account that may be used to verify signatures.
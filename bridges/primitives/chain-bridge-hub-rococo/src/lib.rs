@@ -25,7 +25,7 @@ use bp_messages::*;
 use bp_runtime::{
 	decl_bridge_finality_runtime_apis, decl_bridge_messages_runtime_apis, Chain, ChainId, Parachain,
 };
-use frame_support::dispatch::DispatchClass;
+use frame_support::{dispatch::DispatchClass, traits::ConstU64};
 use sp_runtime::{MultiAddress, MultiSigner, RuntimeDebug};
 
 /// BridgeHubRococo parachain.
@@ -69,6 +69,7 @@ impl ChainWithMessages for BridgeHubRococo {
 		MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX;
 	const MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX: MessageNonce =
 		MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX;
+	type MaxMessagesPerRelayerPerBlock = ConstU64<{ MessageNonce::MAX }>;
 }
 
 /// Public key of the chain account that may be used to verify signatures.
@@ -33,6 +33,7 @@ use codec::{Decode, Encode};
 use frame_support::{
 	dispatch::DispatchClass,
 	parameter_types,
+	traits::ConstU64,
 	weights::{constants::WEIGHT_REF_TIME_PER_SECOND, Weight},
 };
 use frame_system::limits;
@@ -221,6 +222,7 @@ impl ChainWithMessages for PolkadotBulletin {
 		MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX;
 	const MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX: MessageNonce =
 		MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX;
+	type MaxMessagesPerRelayerPerBlock = ConstU64<{ MessageNonce::MAX }>;
 }
 
 decl_bridge_finality_runtime_apis!(polkadot_bulletin, grandpa);
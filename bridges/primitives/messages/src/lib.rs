@@ -25,7 +25,7 @@ use bp_runtime::{
 	StorageProofError, UnderlyingChainOf, UnderlyingChainProvider,
 };
 use codec::{Decode, Encode, MaxEncodedLen};
-use frame_support::PalletError;
+use frame_support::{sp_runtime::traits::Saturating, traits::Get, PalletError};
 // Weight is reexported to avoid additional frame-support dependencies in related crates.
 pub use frame_support::weights::Weight;
 use scale_info::TypeInfo;
@@ -53,6 +53,9 @@ pub trait ChainWithMessages: Chain {
 	/// Maximal number of unconfirmed messages in a single confirmation transaction at this
 	/// `ChainWithMessages`.
 	const MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX: MessageNonce;
+	/// Maximal number of messages that a single relayer may deliver to this `ChainWithMessages`
+	/// in a single block. There's no limit if it is `MessageNonce::MAX`.
+	type MaxMessagesPerRelayerPerBlock: Get<MessageNonce>;
 }
 
 impl<T> ChainWithMessages for T
@@ -66,6 +69,8 @@ where
 		UnderlyingChainOf::<T>::MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX;
 	const MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX: MessageNonce =
 		UnderlyingChainOf::<T>::MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX;
+	type MaxMessagesPerRelayerPerBlock =
+		<UnderlyingChainOf<T> as ChainWithMessages>::MaxMessagesPerRelayerPerBlock;
 }
 
 /// Messages pallet operating mode.
@@ -93,6 +98,13 @@ pub enum MessagesOperatingMode {
 	/// queued messages to the bridged chain. Once upgrade is completed, the mode may be switched
 	/// back to `Normal`.
 	RejectingOutboundMessages,
+	/// The pallet is not accepting outbound messages and inbound messages. Only messages
+	/// delivery proofs are accepted, so relayers may still be rewarded for already delivered
+	/// messages.
+	///
+	/// Unlike the `Basic(BasicOperatingMode::Halted)` mode, this mode is not treated as halted.
+	/// It may be used to temporarily pause the bridge, e.g. during bridge upgrades.
+	Paused,
 }
 
 impl Default for MessagesOperatingMode {
@@ -108,6 +120,10 @@ impl OperatingMode for MessagesOperatingMode {
 			_ => false,
 		}
 	}
+
+	fn is_paused(&self) -> bool {
+		*self == Self::Paused
+	}
 }
 
 /// Lane id which implements `TypeId`.
@@ -132,6 +148,75 @@ impl TypeId for LaneId {
 	const TYPE_ID: [u8; 4] = *b"blan";
 }
 
+/// Priority of the outbound lane.
+///
+/// Lanes with higher priority (e.g. lanes, used to deliver governance messages) are served by
+/// relayers before lanes with lower priority.
+#[derive(
+	Clone,
+	Copy,
+	Decode,
+	Encode,
+	Eq,
+	Ord,
+	PartialOrd,
+	PartialEq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+	Serialize,
+	Deserialize,
+)]
+pub struct LanePriority(pub u8);
+
+impl LanePriority {
+	/// Default lane priority.
+	pub const DEFAULT: LanePriority = LanePriority(128);
+}
+
+impl Default for LanePriority {
+	fn default() -> Self {
+		Self::DEFAULT
+	}
+}
+
+/// State of the bridge lane.
+#[derive(Clone, Copy, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum LaneState<BlockNumber> {
+	/// The lane is opened: messages may be sent and delivered over it.
+	Opened,
+	/// The lane is closed: messages may be neither sent, nor delivered over it.
+	Closed,
+	/// The lane protocol is being upgraded.
+	///
+	/// New messages may not be sent over the lane, but already sent messages are still
+	/// delivered. Once the upgrade is completed, the lane is opened again.
+	Upgrading {
+		/// Number of the block where the upgrade has been started.
+		since: BlockNumber,
+		/// Version of the lane protocol after the upgrade.
+		new_version: u32,
+	},
+}
+
+impl<BlockNumber> LaneState<BlockNumber> {
+	/// Returns true if new messages may be sent over the lane.
+	pub fn is_open(&self) -> bool {
+		matches!(self, LaneState::Opened)
+	}
+
+	/// Returns true if messages may not be delivered over the lane.
+	pub fn is_closed(&self) -> bool {
+		matches!(self, LaneState::Closed)
+	}
+}
+
+impl<BlockNumber> Default for LaneState<BlockNumber> {
+	fn default() -> Self {
+		LaneState::Opened
+	}
+}
+
 /// Message nonce. Valid messages will never have 0 nonce.
 pub type MessageNonce = u64;
 
@@ -142,7 +227,12 @@ pub type BridgeMessageId = (LaneId, MessageNonce);
 pub type MessagePayload = Vec<u8>;
 
 /// Message key (unique message identifier) as it is stored in the storage.
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+///
+/// Keys are ordered by lane first and then by nonce, so messages of the same lane are iterated
+/// in the order they have been sent.
+#[derive(
+	Encode, Decode, Clone, PartialEq, Eq, PartialOrd, Ord, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
 pub struct MessageKey {
 	/// ID of the message lane.
 	pub lane_id: LaneId,
@@ -150,6 +240,19 @@ pub struct MessageKey {
 	pub nonce: MessageNonce,
 }
 
+/// Message fee, paid by the sender and escrowed until the message delivery is confirmed.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct EscrowedFee<AccountId, Balance, BlockNumber> {
+	/// Account that has paid the fee. The fee is refunded to this account if it can't be paid to
+	/// the relayer that has delivered the message.
+	pub payer: AccountId,
+	/// Amount of the escrowed fee.
+	pub amount: Balance,
+	/// Number of the block, at which the fee is refunded to the payer, unless the delivery of
+	/// the message is confirmed before.
+	pub expires_at: BlockNumber,
+}
+
 /// Message as it is stored in the storage.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 pub struct Message {
@@ -243,6 +346,78 @@ impl<RelayerId> InboundLaneData<RelayerId> {
 			_ => 0,
 		}
 	}
+
+	/// Returns the relayer that has delivered the oldest unconfirmed message.
+	///
+	/// This relayer is the first one to be rewarded once the delivery confirmation is received.
+	pub fn last_confirmed_relayer(&self) -> Option<&RelayerId> {
+		self.relayers.front().map(|entry| &entry.relayer)
+	}
+
+	/// Nonce of the oldest message that is not yet confirmed at the source chain.
+	///
+	/// If all delivered messages are confirmed, returns the nonce of the next message.
+	pub fn oldest_unconfirmed_nonce(&self) -> MessageNonce {
+		self.relayers
+			.front()
+			.map(|entry| entry.messages.begin)
+			.unwrap_or(self.last_confirmed_nonce.saturating_add(1))
+	}
+
+	/// Returns `true` if some messages are missing between the last confirmed message and the
+	/// first entry of the `relayers` vector, or between its consecutive entries.
+	///
+	/// Messages are delivered in order, so the first entry must begin right after the last
+	/// confirmed message and the next entry must begin right after the previous one ends. A gap
+	/// means that the skipped messages have been lost.
+	pub fn has_gap(&self) -> bool {
+		let first_gap = self
+			.relayers
+			.front()
+			.map(|entry| entry.messages.begin > self.last_confirmed_nonce.saturating_add(1))
+			.unwrap_or(false);
+		first_gap ||
+			self.relayers
+				.iter()
+				.zip(self.relayers.iter().skip(1))
+				.any(|(prev, next)| next.messages.begin > prev.messages.end.saturating_add(1))
+	}
+
+	/// Prune all unrewarded relayer entries, covered by the given confirmed nonce.
+	///
+	/// If the first non-pruned entry is only partially covered by the `confirmed_nonce`, it is
+	/// split and only its uncovered part is left in the `relayers` set. The confirmed part
+	/// of every pruned entry is returned, so that the caller may reward relayers.
+	pub fn drain_confirmed(
+		&mut self,
+		confirmed_nonce: MessageNonce,
+	) -> Vec<UnrewardedRelayer<RelayerId>>
+	where
+		RelayerId: Clone,
+	{
+		let mut drained = Vec::new();
+		while let Some(entry) = self.relayers.front_mut() {
+			if entry.messages.begin > confirmed_nonce {
+				break
+			}
+
+			if entry.messages.end <= confirmed_nonce {
+				drained.extend(self.relayers.pop_front());
+				continue
+			}
+
+			// the entry is partially confirmed - we know that `begin <= confirmed_nonce < end`, so
+			// it is always split
+			if let Some((confirmed, unconfirmed)) = entry.split_at_nonce(confirmed_nonce) {
+				drained.push(confirmed);
+				*entry = unconfirmed;
+			}
+			break
+		}
+
+		self.last_confirmed_nonce = sp_std::cmp::max(self.last_confirmed_nonce, confirmed_nonce);
+		drained
+	}
 }
 
 /// Outbound message details, returned by runtime APIs.
@@ -283,6 +458,30 @@ pub struct UnrewardedRelayer<RelayerId> {
 	pub messages: DeliveredMessages,
 }
 
+impl<RelayerId: Clone> UnrewardedRelayer<RelayerId> {
+	/// Split the entry into messages up to the `nonce` (inclusive) and messages after it.
+	///
+	/// Returns `None` if the `nonce` is outside of the messages range, or if it is the last
+	/// message of the range, so there's nothing to split off.
+	pub fn split_at_nonce(&self, nonce: MessageNonce) -> Option<(Self, Self)> {
+		if nonce < self.messages.begin || nonce >= self.messages.end {
+			return None
+		}
+
+		// we know that `nonce < end`, so `nonce + 1` can't overflow
+		Some((
+			UnrewardedRelayer {
+				relayer: self.relayer.clone(),
+				messages: DeliveredMessages { begin: self.messages.begin, end: nonce },
+			},
+			UnrewardedRelayer {
+				relayer: self.relayer.clone(),
+				messages: DeliveredMessages { begin: nonce + 1, end: self.messages.end },
+			},
+		))
+	}
+}
+
 /// Received messages with their dispatch result.
 #[derive(Clone, Default, Encode, Decode, RuntimeDebug, PartialEq, Eq, TypeInfo)]
 pub struct ReceivedMessages<DispatchLevelResult> {
@@ -323,6 +522,21 @@ pub enum ReceivalResult<DispatchLevelResult> {
 	TooManyUnconfirmedMessages,
 }
 
+impl<DispatchLevelResult> ReceivalResult<DispatchLevelResult> {
+	/// Returns `true` if the lane is stuck and requires operator attention.
+	///
+	/// Fatal results mean that no more messages will be accepted by the lane until delivery
+	/// confirmations reach it. Other results are benign - e.g. messages with invalid nonces may
+	/// be safely ignored after a fork.
+	pub fn is_fatal(&self) -> bool {
+		match self {
+			ReceivalResult::TooManyUnrewardedRelayers |
+			ReceivalResult::TooManyUnconfirmedMessages => true,
+			ReceivalResult::Dispatched(_) | ReceivalResult::InvalidNonce => false,
+		}
+	}
+}
+
 /// Delivered messages with their dispatch result.
 #[derive(Clone, Default, Encode, Decode, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
 pub struct DeliveredMessages {
@@ -345,16 +559,59 @@ impl DeliveredMessages {
 	}
 
 	/// Note new dispatched message.
-	pub fn note_dispatched_message(&mut self) {
-		self.end += 1;
+	///
+	/// Returns error if the nonce of the new message would overflow `MessageNonce`. The range
+	/// is left unchanged in this case.
+	pub fn note_dispatched_message(&mut self) -> Result<(), MessageNonceOverflow> {
+		self.end = self.end.checked_add(1).ok_or(MessageNonceOverflow)?;
+		Ok(())
 	}
 
 	/// Returns true if delivered messages contain message with given nonce.
 	pub fn contains_message(&self, nonce: MessageNonce) -> bool {
 		(self.begin..=self.end).contains(&nonce)
 	}
+
+	/// Returns true if both ranges contain at least one common message.
+	///
+	/// Delivery proofs of the same lane must never intersect, because that would mean that some
+	/// messages have been delivered twice.
+	pub fn intersects(&self, other: &DeliveredMessages) -> bool {
+		self.begin <= other.end && other.begin <= self.end
+	}
+
+	/// Returns true if all messages of the `other` range are also in this range.
+	pub fn contains_range(&self, other: &DeliveredMessages) -> bool {
+		self.begin <= other.begin && other.end <= self.end
+	}
+
+	/// Returns true if ranges have no common messages.
+	pub fn is_disjoint_from(&self, other: &DeliveredMessages) -> bool {
+		!self.intersects(other)
+	}
+
+	/// Merge two ranges of delivered messages into a single range.
+	///
+	/// Returns `None` if ranges are neither adjacent nor overlapping, because their union
+	/// can't be represented by a single `DeliveredMessages` struct then.
+	pub fn merge(&self, other: &DeliveredMessages) -> Option<DeliveredMessages> {
+		let is_disjoint =
+			other.begin > self.end.saturating_add(1) || self.begin > other.end.saturating_add(1);
+		if is_disjoint {
+			return None
+		}
+
+		Some(DeliveredMessages {
+			begin: sp_std::cmp::min(self.begin, other.begin),
+			end: sp_std::cmp::max(self.end, other.end),
+		})
+	}
 }
 
+/// Error that is returned when message nonce overflows `MessageNonce` limits.
+#[derive(Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub struct MessageNonceOverflow;
+
 /// Gist of `InboundLaneData::relayers` field used by runtime APIs.
 #[derive(Clone, Default, Encode, Decode, RuntimeDebug, PartialEq, Eq, TypeInfo)]
 pub struct UnrewardedRelayersState {
@@ -377,6 +634,50 @@ impl UnrewardedRelayersState {
 	pub fn is_valid<RelayerId>(&self, lane_data: &InboundLaneData<RelayerId>) -> bool {
 		self == &lane_data.into()
 	}
+
+	/// Compute changes of the relayers state since the `previous` state.
+	///
+	/// Returns error if the `previous` state has larger `last_delivered_nonce` than this
+	/// state, because messages may not be undelivered.
+	pub fn diff(
+		&self,
+		previous: &UnrewardedRelayersState,
+	) -> Result<UnrewardedRelayersStateDiff, UnrewardedRelayersStateDiffError> {
+		let new_entries = self
+			.last_delivered_nonce
+			.checked_sub(previous.last_delivered_nonce)
+			.ok_or(UnrewardedRelayersStateDiffError::LastDeliveredNonceDecreased)?;
+		let confirmed_entries = previous
+			.total_messages
+			.saturating_add(new_entries)
+			.saturating_sub(self.total_messages);
+
+		Ok(UnrewardedRelayersStateDiff {
+			new_entries,
+			confirmed_entries,
+			nonce_delta: i64::try_from(new_entries).unwrap_or(i64::MAX),
+		})
+	}
+}
+
+/// Changes of the `UnrewardedRelayersState` between two consecutive observations.
+#[derive(Clone, Default, Encode, Decode, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub struct UnrewardedRelayersStateDiff {
+	/// Number of messages that have been delivered since the previous state.
+	pub new_entries: MessageNonce,
+	/// Number of previously unrewarded messages that have been confirmed since the previous
+	/// state.
+	pub confirmed_entries: MessageNonce,
+	/// Difference between the current and previous `last_delivered_nonce`.
+	pub nonce_delta: i64,
+}
+
+/// Error that may happen when computing `UnrewardedRelayersStateDiff`.
+#[derive(Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub enum UnrewardedRelayersStateDiffError {
+	/// The `last_delivered_nonce` of the current state is less than the `last_delivered_nonce`
+	/// of the previous state.
+	LastDeliveredNonceDecreased,
 }
 
 impl<RelayerId> From<&InboundLaneData<RelayerId>> for UnrewardedRelayersState {
@@ -424,6 +725,39 @@ impl OutboundLaneData {
 	pub fn queued_messages(&self) -> RangeInclusive<MessageNonce> {
 		(self.latest_received_nonce + 1)..=self.latest_generated_nonce
 	}
+
+	/// Returns the number of messages that we have generated, but the bridged chain has not yet
+	/// confirmed their delivery.
+	pub fn messages_pending_confirmation(&self) -> MessageNonce {
+		self.latest_generated_nonce.saturating_sub(self.latest_received_nonce)
+	}
+
+	/// Returns `true` if more than `threshold` messages are waiting for the delivery
+	/// confirmation.
+	///
+	/// Relayers may use it to prioritize the delivery confirmation transactions.
+	pub fn is_backlogged(&self, threshold: MessageNonce) -> bool {
+		self.messages_pending_confirmation() > threshold
+	}
+
+	/// Returns `true` if there are messages that we believe are not delivered yet.
+	pub fn has_queued_messages(&self) -> bool {
+		!self.queued_messages().is_empty()
+	}
+
+	/// Returns `true` if the lane has queued messages and there has been no activity at the lane
+	/// for more than `stuck_threshold` blocks.
+	///
+	/// Relayers may use it to detect that the bridge is stuck.
+	pub fn is_stuck<BlockNumber: Saturating + PartialOrd>(
+		&self,
+		current_block: BlockNumber,
+		stuck_threshold: BlockNumber,
+		last_activity_block: BlockNumber,
+	) -> bool {
+		self.has_queued_messages() &&
+			current_block.saturating_sub(last_activity_block) > stuck_threshold
+	}
 }
 
 /// Calculate the number of messages that the relayers have delivered.
@@ -447,6 +781,33 @@ where
 	relayers_rewards
 }
 
+/// Calculate the number of messages that the relayers have delivered, capping the reward of
+/// every relayer at `max_reward_per_relayer` messages.
+///
+/// Returns the capped rewards and the excess of the relayers that have hit the cap, which is left
+/// unclaimed. With no cap, the rewards are the same as the ones of [`calc_relayers_rewards`] and
+/// there's no excess.
+pub fn calc_capped_relayers_rewards<AccountId>(
+	messages_relayers: VecDeque<UnrewardedRelayer<AccountId>>,
+	received_range: &RangeInclusive<MessageNonce>,
+	max_reward_per_relayer: Option<MessageNonce>,
+) -> (RelayersRewards<AccountId>, Vec<(AccountId, MessageNonce)>)
+where
+	AccountId: sp_std::cmp::Ord + Clone,
+{
+	let mut relayers_rewards = calc_relayers_rewards(messages_relayers, received_range);
+	let mut excess = Vec::new();
+	if let Some(max_reward) = max_reward_per_relayer {
+		for (relayer, reward) in relayers_rewards.iter_mut() {
+			if *reward > max_reward {
+				excess.push((relayer.clone(), *reward - max_reward));
+				*reward = max_reward;
+			}
+		}
+	}
+	(relayers_rewards, excess)
+}
+
 /// A minimized version of `pallet-bridge-messages::Call` that can be used without a runtime.
 #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone, TypeInfo)]
 #[allow(non_camel_case_types)]
@@ -498,6 +859,28 @@ pub enum VerificationError {
 	Other(#[codec(skip)] &'static str),
 }
 
+impl VerificationError {
+	/// Returns `true` if the error depends on the external state, so the verification may succeed
+	/// with a fresh proof.
+	///
+	/// Relayers should retry immediately after a transient error, generating the proof again.
+	/// Permanent errors won't go away on retry and should raise an alert instead.
+	pub fn is_transient(&self) -> bool {
+		match self {
+			VerificationError::HeaderChain(_) |
+			VerificationError::InboundLaneStorage(_) |
+			VerificationError::MessageStorage(_) |
+			VerificationError::OutboundLaneStorage(_) |
+			VerificationError::StorageProof(_) => true,
+			VerificationError::EmptyMessageProof |
+			VerificationError::InvalidMessageWeight |
+			VerificationError::MessagesCountMismatch |
+			VerificationError::MessageTooLarge |
+			VerificationError::Other(_) => false,
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -519,6 +902,183 @@ mod tests {
 		assert_eq!(lane_data.total_unrewarded_messages(), MessageNonce::MAX);
 	}
 
+	fn inbound_lane_data_with_relayers(
+		relayers: Vec<(u8, MessageNonce, MessageNonce)>,
+	) -> InboundLaneData<u8> {
+		InboundLaneData {
+			relayers: relayers
+				.into_iter()
+				.map(|(relayer, begin, end)| UnrewardedRelayer {
+					relayer,
+					messages: DeliveredMessages { begin, end },
+				})
+				.collect(),
+			last_confirmed_nonce: 0,
+		}
+	}
+
+	#[test]
+	fn has_gap_works() {
+		assert!(!inbound_lane_data_with_relayers(vec![]).has_gap());
+		assert!(!inbound_lane_data_with_relayers(vec![(1, 1, 2)]).has_gap());
+		assert!(!inbound_lane_data_with_relayers(vec![(1, 1, 2), (2, 3, 5), (1, 6, 6)]).has_gap());
+		assert!(inbound_lane_data_with_relayers(vec![(1, 1, 2), (2, 4, 5)]).has_gap());
+		assert!(inbound_lane_data_with_relayers(vec![(1, 1, 2), (2, 3, 5), (1, 7, 7)]).has_gap());
+
+		// the first entry must begin right after the last confirmed message
+		assert!(inbound_lane_data_with_relayers(vec![(1, 2, 2)]).has_gap());
+		let mut lane_data = inbound_lane_data_with_relayers(vec![(1, 3, 4), (2, 5, 5)]);
+		lane_data.last_confirmed_nonce = 2;
+		assert!(!lane_data.has_gap());
+		lane_data.last_confirmed_nonce = 1;
+		assert!(lane_data.has_gap());
+	}
+
+	#[test]
+	fn drain_confirmed_works_with_empty_relayers() {
+		let mut lane_data = inbound_lane_data_with_relayers(vec![]);
+
+		assert_eq!(lane_data.drain_confirmed(10), vec![]);
+		assert_eq!(lane_data.last_confirmed_nonce, 10);
+		assert!(lane_data.relayers.is_empty());
+	}
+
+	#[test]
+	fn drain_confirmed_drains_all_entries() {
+		let mut lane_data = inbound_lane_data_with_relayers(vec![(1, 1, 2), (2, 3, 5)]);
+		let expected_drained = lane_data.relayers.iter().cloned().collect::<Vec<_>>();
+
+		assert_eq!(lane_data.drain_confirmed(5), expected_drained);
+		assert_eq!(lane_data.last_confirmed_nonce, 5);
+		assert!(lane_data.relayers.is_empty());
+	}
+
+	#[test]
+	fn drain_confirmed_drains_some_entries() {
+		let mut lane_data = inbound_lane_data_with_relayers(vec![(1, 1, 2), (2, 3, 5), (3, 6, 8)]);
+
+		assert_eq!(
+			lane_data.drain_confirmed(4),
+			inbound_lane_data_with_relayers(vec![(1, 1, 2), (2, 3, 4)])
+				.relayers
+				.into_iter()
+				.collect::<Vec<_>>(),
+		);
+		assert_eq!(lane_data.last_confirmed_nonce, 4);
+		assert_eq!(
+			lane_data.relayers,
+			inbound_lane_data_with_relayers(vec![(2, 5, 5), (3, 6, 8)]).relayers
+		);
+	}
+
+	#[test]
+	fn drain_confirmed_splits_single_multi_message_entry() {
+		let mut lane_data = inbound_lane_data_with_relayers(vec![(1, 1, 10)]);
+
+		assert_eq!(
+			lane_data.drain_confirmed(3),
+			vec![UnrewardedRelayer {
+				relayer: 1,
+				messages: DeliveredMessages { begin: 1, end: 3 }
+			}],
+		);
+		assert_eq!(lane_data.last_confirmed_nonce, 3);
+		assert_eq!(lane_data.relayers, inbound_lane_data_with_relayers(vec![(1, 4, 10)]).relayers);
+
+		assert_eq!(lane_data.drain_confirmed(3), vec![]);
+		assert_eq!(lane_data.relayers, inbound_lane_data_with_relayers(vec![(1, 4, 10)]).relayers);
+	}
+
+	fn unrewarded_relayer(begin: MessageNonce, end: MessageNonce) -> UnrewardedRelayer<u8> {
+		UnrewardedRelayer { relayer: 1, messages: DeliveredMessages { begin, end } }
+	}
+
+	#[test]
+	fn split_at_nonce_works_at_range_begin() {
+		assert_eq!(
+			unrewarded_relayer(5, 10).split_at_nonce(5),
+			Some((unrewarded_relayer(5, 5), unrewarded_relayer(6, 10))),
+		);
+	}
+
+	#[test]
+	fn split_at_nonce_works_in_range_middle() {
+		assert_eq!(
+			unrewarded_relayer(5, 10).split_at_nonce(7),
+			Some((unrewarded_relayer(5, 7), unrewarded_relayer(8, 10))),
+		);
+	}
+
+	#[test]
+	fn split_at_nonce_at_range_end_leaves_nothing_to_split() {
+		assert_eq!(unrewarded_relayer(5, 10).split_at_nonce(10), None);
+		assert_eq!(unrewarded_relayer(5, 5).split_at_nonce(5), None);
+	}
+
+	#[test]
+	fn split_at_nonce_fails_outside_of_range() {
+		assert_eq!(unrewarded_relayer(5, 10).split_at_nonce(4), None);
+		assert_eq!(unrewarded_relayer(5, 10).split_at_nonce(11), None);
+		assert_eq!(unrewarded_relayer(5, 10).split_at_nonce(MessageNonce::MAX), None);
+	}
+
+	#[test]
+	fn note_dispatched_message_does_not_overflow() {
+		let mut delivered_messages =
+			DeliveredMessages { begin: MessageNonce::MAX, end: MessageNonce::MAX };
+
+		assert_eq!(delivered_messages.note_dispatched_message(), Err(MessageNonceOverflow));
+		assert_eq!(
+			delivered_messages,
+			DeliveredMessages { begin: MessageNonce::MAX, end: MessageNonce::MAX },
+		);
+	}
+
+	#[test]
+	fn note_dispatched_message_works() {
+		let mut delivered_messages = DeliveredMessages::new(1);
+
+		assert_eq!(delivered_messages.note_dispatched_message(), Ok(()));
+		assert_eq!(delivered_messages, DeliveredMessages { begin: 1, end: 2 });
+	}
+
+	#[test]
+	fn unrewarded_relayers_state_diff_works() {
+		let mut lane_data = inbound_lane_data_with_relayers(vec![(1, 1, 2), (2, 3, 5)]);
+		let previous_state = UnrewardedRelayersState::from(&lane_data);
+
+		// confirm messages 1..=3 and deliver messages 6..=7
+		lane_data.drain_confirmed(3);
+		lane_data.relayers.push_back(UnrewardedRelayer {
+			relayer: 3,
+			messages: DeliveredMessages { begin: 6, end: 7 },
+		});
+		let state = UnrewardedRelayersState::from(&lane_data);
+
+		assert_eq!(
+			state.diff(&previous_state),
+			Ok(UnrewardedRelayersStateDiff {
+				new_entries: 2,
+				confirmed_entries: 3,
+				nonce_delta: 2
+			}),
+		);
+		assert_eq!(state.diff(&state), Ok(UnrewardedRelayersStateDiff::default()));
+	}
+
+	#[test]
+	fn unrewarded_relayers_state_diff_fails_if_last_delivered_nonce_decreases() {
+		let lane_data = inbound_lane_data_with_relayers(vec![(1, 1, 2), (2, 3, 5)]);
+		let previous_state = UnrewardedRelayersState::from(&lane_data);
+		let state =
+			UnrewardedRelayersState::from(&inbound_lane_data_with_relayers(vec![(1, 1, 2)]));
+
+		assert_eq!(
+			state.diff(&previous_state),
+			Err(UnrewardedRelayersStateDiffError::LastDeliveredNonceDecreased),
+		);
+	}
+
 	#[test]
 	fn inbound_lane_data_returns_correct_hint() {
 		let test_cases = vec![
@@ -560,8 +1120,316 @@ mod tests {
 		assert!(!delivered_messages.contains_message(151));
 	}
 
+	#[test]
+	fn merge_rejects_non_adjacent_ranges() {
+		let delivered_messages = DeliveredMessages { begin: 100, end: 150 };
+
+		assert_eq!(delivered_messages.merge(&DeliveredMessages { begin: 152, end: 200 }), None);
+		assert_eq!(delivered_messages.merge(&DeliveredMessages { begin: 10, end: 98 }), None);
+	}
+
+	#[test]
+	fn merge_works_for_adjacent_ranges() {
+		let delivered_messages = DeliveredMessages { begin: 100, end: 150 };
+
+		assert_eq!(
+			delivered_messages.merge(&DeliveredMessages { begin: 151, end: 200 }),
+			Some(DeliveredMessages { begin: 100, end: 200 }),
+		);
+		assert_eq!(
+			delivered_messages.merge(&DeliveredMessages { begin: 10, end: 99 }),
+			Some(DeliveredMessages { begin: 10, end: 150 }),
+		);
+	}
+
+	#[test]
+	fn merge_works_for_overlapping_ranges() {
+		let delivered_messages = DeliveredMessages { begin: 100, end: 150 };
+
+		assert_eq!(
+			delivered_messages.merge(&DeliveredMessages { begin: 150, end: 200 }),
+			Some(DeliveredMessages { begin: 100, end: 200 }),
+		);
+		assert_eq!(
+			delivered_messages.merge(&DeliveredMessages { begin: 10, end: 120 }),
+			Some(DeliveredMessages { begin: 10, end: 150 }),
+		);
+	}
+
+	#[test]
+	fn merge_returns_wider_range_if_one_contains_another() {
+		let delivered_messages = DeliveredMessages { begin: 100, end: 150 };
+
+		assert_eq!(
+			delivered_messages.merge(&DeliveredMessages { begin: 120, end: 130 }),
+			Some(delivered_messages.clone()),
+		);
+		assert_eq!(
+			DeliveredMessages { begin: 120, end: 130 }.merge(&delivered_messages),
+			Some(delivered_messages.clone()),
+		);
+	}
+
+	#[test]
+	fn merge_does_not_overflow() {
+		let delivered_messages = DeliveredMessages::new(MessageNonce::MAX);
+
+		assert_eq!(
+			delivered_messages.merge(&DeliveredMessages::new(MessageNonce::MAX - 1)),
+			Some(DeliveredMessages { begin: MessageNonce::MAX - 1, end: MessageNonce::MAX }),
+		);
+	}
+
+	#[test]
+	fn messages_pending_confirmation_works_for_empty_lane() {
+		let lane_data = OutboundLaneData::default();
+
+		assert_eq!(lane_data.messages_pending_confirmation(), 0);
+		assert!(!lane_data.is_backlogged(0));
+	}
+
+	#[test]
+	fn messages_pending_confirmation_works_for_partially_confirmed_lane() {
+		let lane_data = OutboundLaneData {
+			oldest_unpruned_nonce: 3,
+			latest_received_nonce: 5,
+			latest_generated_nonce: 8,
+		};
+
+		assert_eq!(lane_data.messages_pending_confirmation(), 3);
+		assert!(lane_data.is_backlogged(2));
+		assert!(!lane_data.is_backlogged(3));
+	}
+
+	#[test]
+	fn messages_pending_confirmation_works_for_fully_backlogged_lane() {
+		let lane_data =
+			OutboundLaneData { latest_generated_nonce: MessageNonce::MAX, ..Default::default() };
+
+		assert_eq!(lane_data.messages_pending_confirmation(), MessageNonce::MAX);
+		assert!(lane_data.is_backlogged(MessageNonce::MAX - 1));
+		assert!(!lane_data.is_backlogged(MessageNonce::MAX));
+	}
+
+	#[test]
+	fn lane_with_queued_messages_is_stuck_after_threshold() {
+		let lane_data = OutboundLaneData {
+			latest_received_nonce: 5,
+			latest_generated_nonce: 8,
+			..Default::default()
+		};
+
+		assert!(lane_data.has_queued_messages());
+		assert!(!lane_data.is_stuck(20u32, 10, 10));
+		assert!(lane_data.is_stuck(21u32, 10, 10));
+		// no overflow if the activity block is ahead of the current block
+		assert!(!lane_data.is_stuck(10u32, 10, 21));
+	}
+
+	#[test]
+	fn lane_is_not_stuck_after_all_messages_are_confirmed() {
+		let mut lane_data = OutboundLaneData {
+			latest_received_nonce: 5,
+			latest_generated_nonce: 8,
+			..Default::default()
+		};
+		assert!(lane_data.is_stuck(100u32, 10, 10));
+
+		lane_data.latest_received_nonce = 8;
+		assert!(!lane_data.has_queued_messages());
+		assert!(!lane_data.is_stuck(100u32, 10, 10));
+	}
+
+	#[test]
+	fn lane_without_queued_messages_is_never_stuck() {
+		let lane_data = OutboundLaneData::default();
+
+		assert!(!lane_data.has_queued_messages());
+		assert!(!lane_data.is_stuck(u32::MAX, 0, 0));
+	}
+
 	#[test]
 	fn lane_id_debug_format_matches_inner_array_format() {
 		assert_eq!(format!("{:?}", LaneId([0, 0, 0, 0])), format!("{:?}", [0, 0, 0, 0]),);
 	}
+
+	#[test]
+	fn lane_state_is_open_and_is_closed_work() {
+		assert!(LaneState::<u64>::Opened.is_open());
+		assert!(!LaneState::<u64>::Opened.is_closed());
+
+		assert!(!LaneState::<u64>::Closed.is_open());
+		assert!(LaneState::<u64>::Closed.is_closed());
+
+		let upgrading = LaneState::<u64>::Upgrading { since: 1, new_version: 2 };
+		assert!(!upgrading.is_open());
+		assert!(!upgrading.is_closed());
+	}
+
+	fn relayers_with_rewards(rewards: &[(u64, MessageNonce)]) -> VecDeque<UnrewardedRelayer<u64>> {
+		let mut begin = 1;
+		rewards
+			.iter()
+			.map(|(relayer, messages)| {
+				let entry = UnrewardedRelayer {
+					relayer: *relayer,
+					messages: DeliveredMessages { begin, end: begin + messages - 1 },
+				};
+				begin += messages;
+				entry
+			})
+			.collect()
+	}
+
+	#[test]
+	fn calc_capped_relayers_rewards_caps_dominating_relayer() {
+		let relayers = relayers_with_rewards(&[(1, 2), (2, 90), (1, 3), (3, 5)]);
+
+		let (rewards, excess) = calc_capped_relayers_rewards(relayers, &(1..=100), Some(10));
+		assert_eq!(rewards, vec![(1, 5), (2, 10), (3, 5)].into_iter().collect());
+		assert_eq!(excess, vec![(2, 80)]);
+	}
+
+	#[test]
+	fn calc_capped_relayers_rewards_keeps_rewards_under_cap() {
+		let relayers = relayers_with_rewards(&[(1, 5), (2, 5), (3, 5)]);
+
+		let (rewards, excess) = calc_capped_relayers_rewards(relayers, &(1..=15), Some(5));
+		assert_eq!(rewards, vec![(1, 5), (2, 5), (3, 5)].into_iter().collect());
+		assert!(excess.is_empty());
+	}
+
+	#[test]
+	fn calc_capped_relayers_rewards_without_cap_matches_uncapped_rewards() {
+		let relayers = relayers_with_rewards(&[(1, 2), (2, 90), (1, 3), (3, 5)]);
+
+		for received_range in [1..=100, 1..=50, 3..=95, 101..=200] {
+			let (rewards, excess) =
+				calc_capped_relayers_rewards(relayers.clone(), &received_range, None);
+			assert_eq!(rewards, calc_relayers_rewards(relayers.clone(), &received_range));
+			assert!(excess.is_empty());
+		}
+	}
+
+	#[test]
+	fn inbound_lane_data_returns_oldest_unconfirmed_entry() {
+		let mut lane_data =
+			InboundLaneData::<u8> { relayers: VecDeque::new(), last_confirmed_nonce: 5 };
+		assert_eq!(lane_data.last_confirmed_relayer(), None);
+		assert_eq!(lane_data.oldest_unconfirmed_nonce(), 6);
+
+		lane_data.relayers.push_back(UnrewardedRelayer {
+			relayer: 1,
+			messages: DeliveredMessages { begin: 6, end: 8 },
+		});
+		assert_eq!(lane_data.last_confirmed_relayer(), Some(&1));
+		assert_eq!(lane_data.oldest_unconfirmed_nonce(), 6);
+
+		lane_data.relayers.push_back(UnrewardedRelayer {
+			relayer: 2,
+			messages: DeliveredMessages { begin: 9, end: 10 },
+		});
+		assert_eq!(lane_data.last_confirmed_relayer(), Some(&1));
+		assert_eq!(lane_data.oldest_unconfirmed_nonce(), 6);
+
+		lane_data.relayers.pop_front();
+		assert_eq!(lane_data.last_confirmed_relayer(), Some(&2));
+		assert_eq!(lane_data.oldest_unconfirmed_nonce(), 9);
+	}
+
+	#[test]
+	fn delivered_messages_relationships_work() {
+		let range = |begin, end| DeliveredMessages { begin, end };
+		let base = range(10, 20);
+
+		// (name, other range, intersects, contains)
+		let cases = [
+			("before", range(1, 5), false, false),
+			("adjacent-before", range(5, 9), false, false),
+			("overlapping-before", range(5, 12), true, false),
+			("equal", range(10, 20), true, true),
+			("inner", range(12, 15), true, true),
+			("overlapping-after", range(15, 25), true, false),
+			("adjacent-after", range(21, 25), false, false),
+			("after", range(25, 30), false, false),
+		];
+		for (name, other, intersects, contains) in cases {
+			assert_eq!(base.intersects(&other), intersects, "{name}");
+			assert_eq!(other.intersects(&base), intersects, "{name}");
+			assert_eq!(base.is_disjoint_from(&other), !intersects, "{name}");
+			assert_eq!(base.contains_range(&other), contains, "{name}");
+		}
+	}
+
+	#[test]
+	fn delivered_messages_relationships_match_nonce_sets() {
+		let ranges = (0..8u64)
+			.flat_map(|begin| (begin..8).map(move |end| DeliveredMessages { begin, end }))
+			.collect::<Vec<_>>();
+		for a in &ranges {
+			for b in &ranges {
+				let common = (a.begin..=a.end).filter(|nonce| b.contains_message(*nonce)).count();
+				assert_eq!(a.intersects(b), common != 0, "{a:?} {b:?}");
+				assert_eq!(a.is_disjoint_from(b), common == 0, "{a:?} {b:?}");
+				assert_eq!(
+					a.contains_range(b),
+					common as MessageNonce == b.total_messages(),
+					"{a:?} {b:?}",
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn receival_result_is_fatal_works() {
+		assert!(!ReceivalResult::Dispatched(MessageDispatchResult {
+			unspent_weight: Weight::zero(),
+			dispatch_level_result: (),
+		})
+		.is_fatal());
+		assert!(!ReceivalResult::<()>::InvalidNonce.is_fatal());
+		assert!(ReceivalResult::<()>::TooManyUnrewardedRelayers.is_fatal());
+		assert!(ReceivalResult::<()>::TooManyUnconfirmedMessages.is_fatal());
+	}
+
+	#[test]
+	fn message_keys_are_ordered_by_lane_and_nonce() {
+		let message = |lane: u8, nonce| Message {
+			key: MessageKey { lane_id: LaneId([lane; 4]), nonce },
+			payload: vec![lane, nonce as u8],
+		};
+
+		let mut messages = sp_std::collections::btree_map::BTreeMap::new();
+		for message in [message(1, 3), message(0, 2), message(1, 1), message(0, 1), message(1, 2)] {
+			messages.insert(message.key.clone(), message);
+		}
+
+		assert_eq!(
+			messages.keys().map(|key| (key.lane_id.0[0], key.nonce)).collect::<Vec<_>>(),
+			vec![(0, 1), (0, 2), (1, 1), (1, 2), (1, 3)],
+		);
+		assert!(messages
+			.values()
+			.all(|message| message.payload ==
+				vec![message.key.lane_id.0[0], message.key.nonce as u8]));
+	}
+
+	#[test]
+	fn verification_error_is_transient_works() {
+		let proof_error = || StorageProofError::StorageValueUnavailable;
+
+		assert!(VerificationError::HeaderChain(HeaderChainError::UnknownHeader).is_transient());
+		assert!(VerificationError::HeaderChain(HeaderChainError::StorageProof(proof_error()))
+			.is_transient());
+		assert!(VerificationError::InboundLaneStorage(proof_error()).is_transient());
+		assert!(VerificationError::MessageStorage(proof_error()).is_transient());
+		assert!(VerificationError::OutboundLaneStorage(proof_error()).is_transient());
+		assert!(VerificationError::StorageProof(proof_error()).is_transient());
+
+		assert!(!VerificationError::EmptyMessageProof.is_transient());
+		assert!(!VerificationError::InvalidMessageWeight.is_transient());
+		assert!(!VerificationError::MessagesCountMismatch.is_transient());
+		assert!(!VerificationError::MessageTooLarge.is_transient());
+		assert!(!VerificationError::Other("test").is_transient());
+	}
 }
@@ -345,6 +345,8 @@ macro_rules! decl_bridge_finality_runtime_apis {
 ///     - `To<ThisChain>OutboundLaneApi`
 ///     - `From<ThisChain>InboundLaneApi`
 /// - constants that are stringified names of runtime API methods:
+///     - `TO_<THIS_CHAIN>_MESSAGE_DETAILS_METHOD`,
+///     - `TO_<THIS_CHAIN>_MESSAGES_PENDING_CONFIRMATION_METHOD`,
 ///     - `FROM_<THIS_CHAIN>_MESSAGE_DETAILS_METHOD`,
 /// The name of the chain has to be specified in snake case (e.g. `bridge_hub_polkadot`).
 #[macro_export]
@@ -358,6 +360,15 @@ macro_rules! decl_bridge_messages_runtime_apis {
 				pub const [<TO_ $chain:upper _MESSAGE_DETAILS_METHOD>]: &str =
 					stringify!([<To $chain:camel OutboundLaneApi_message_details>]);
 
+				/// Name of the `To<ThisChain>OutboundLaneApi::messages_pending_confirmation` runtime
+				/// method.
+				pub const [<TO_ $chain:upper _MESSAGES_PENDING_CONFIRMATION_METHOD>]: &str =
+					stringify!([<To $chain:camel OutboundLaneApi_messages_pending_confirmation>]);
+
+				/// Name of the `To<ThisChain>OutboundLaneApi::lanes_by_priority` runtime method.
+				pub const [<TO_ $chain:upper _LANES_BY_PRIORITY_METHOD>]: &str =
+					stringify!([<To $chain:camel OutboundLaneApi_lanes_by_priority>]);
+
 				/// Name of the `From<ThisChain>InboundLaneApi::message_details` runtime method.
 				pub const [<FROM_ $chain:upper _MESSAGE_DETAILS_METHOD>]: &str =
 					stringify!([<From $chain:camel InboundLaneApi_message_details>]);
@@ -378,6 +389,21 @@ macro_rules! decl_bridge_messages_runtime_apis {
 							begin: bp_messages::MessageNonce,
 							end: bp_messages::MessageNonce,
 						) -> sp_std::vec::Vec<bp_messages::OutboundMessageDetails>;
+
+						/// Returns the number of messages at given lane, that are waiting for the
+						/// delivery confirmation.
+						#[api_version(2)]
+						fn messages_pending_confirmation(
+							lane: bp_messages::LaneId,
+						) -> bp_messages::MessageNonce;
+
+						/// Returns given lanes along with their priorities, ordered by decreasing
+						/// priority. Relayers, serving multiple lanes, should deliver messages in
+						/// that order.
+						#[api_version(3)]
+						fn lanes_by_priority(
+							lanes: sp_std::vec::Vec<bp_messages::LaneId>,
+						) -> sp_std::vec::Vec<(bp_messages::LaneId, bp_messages::LanePriority)>;
 					}
 
 					/// Inbound message lane API for messages sent by this chain.
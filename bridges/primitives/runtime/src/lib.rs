@@ -323,6 +323,13 @@ pub enum OwnedBridgeModuleError {
 pub trait OperatingMode: Send + Copy + Debug + FullCodec {
 	/// Returns true if the bridge module is halted.
 	fn is_halted(&self) -> bool;
+
+	/// Returns true if the bridge module is temporarily paused.
+	///
+	/// Paused module is not halted, so some of its operations may still be allowed.
+	fn is_paused(&self) -> bool {
+		false
+	}
 }
 
 /// Basic operating modes for a bridges module (Normal/Halted).
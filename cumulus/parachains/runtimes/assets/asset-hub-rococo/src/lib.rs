@@ -265,10 +265,11 @@ impl pallet_assets::Config<TrustBackedAssetsInstance> for Runtime {
 	type MetadataDepositPerByte = MetadataDepositPerByte;
 	type ApprovalDeposit = ApprovalDeposit;
 	type StringLimit = AssetsStringLimit;
-	type Freezer = ();
+	type Freezer = NftFractionalization;
 	type Extra = ();
 	type WeightInfo = weights::pallet_assets_local::WeightInfo<Runtime>;
 	type CallbackHandle = ();
+	type TransferCallback = ();
 	type AssetAccountDeposit = AssetAccountDeposit;
 	type RemoveItemsLimit = frame_support::traits::ConstU32<1000>;
 	#[cfg(feature = "runtime-benchmarks")]
@@ -307,6 +308,7 @@ impl pallet_assets::Config<PoolAssetsInstance> for Runtime {
 	type Extra = ();
 	type WeightInfo = weights::pallet_assets_pool::WeightInfo<Runtime>;
 	type CallbackHandle = ();
+	type TransferCallback = ();
 	#[cfg(feature = "runtime-benchmarks")]
 	type BenchmarkHelper = ();
 }
@@ -402,6 +404,7 @@ impl pallet_assets::Config<ForeignAssetsInstance> for Runtime {
 	type Extra = ();
 	type WeightInfo = weights::pallet_assets_foreign::WeightInfo<Runtime>;
 	type CallbackHandle = ();
+	type TransferCallback = ();
 	type AssetAccountDeposit = ForeignAssetsAssetAccountDeposit;
 	type RemoveItemsLimit = frame_support::traits::ConstU32<1000>;
 	#[cfg(feature = "runtime-benchmarks")]
@@ -792,6 +795,9 @@ parameter_types! {
 	pub const NftFractionalizationPalletId: PalletId = PalletId(*b"fraction");
 	pub NewAssetSymbol: BoundedVec<u8, AssetsStringLimit> = (*b"FRAC").to_vec().try_into().unwrap();
 	pub NewAssetName: BoundedVec<u8, AssetsStringLimit> = (*b"Frac").to_vec().try_into().unwrap();
+	pub const CrossChainUnificationTimeout: BlockNumber = HOURS;
+	pub NftFractionalizationRemoteFee: Option<xcm::latest::Asset> =
+		Some((xcm_config::TokenLocation::get(), UNITS / 10).into());
 }
 
 impl pallet_nft_fractionalization::Config for Runtime {
@@ -808,6 +814,20 @@ impl pallet_nft_fractionalization::Config for Runtime {
 	type Assets = Assets;
 	type Nfts = Nfts;
 	type PalletId = NftFractionalizationPalletId;
+	type MaxUnificationContributors = ConstU32<10>;
+	type MaxFreezes = ConstU32<10>;
+	type XcmSender = xcm_config::XcmRouter;
+	type XcmExecutor = xcm_executor::XcmExecutor<xcm_config::XcmConfig>;
+	type RemoteExecutionFee = NftFractionalizationRemoteFee;
+	type XcmQueries = PolkadotXcm;
+	type AssetLocation = assets_common::AssetIdForTrustBackedAssetsConvertLatest<
+		xcm_config::TrustBackedAssetsPalletLocation,
+	>;
+	type AccountIdToLocation = xcm_builder::AliasesIntoAccountId32<(), AccountId>;
+	type CrossChainUnificationTimeout = CrossChainUnificationTimeout;
+	type MaxBatchSize = ConstU32<10>;
+	type AutoUnify = ConstBool<false>;
+	type MinRoyaltyPayout = ConstU128<1>;
 	type WeightInfo = pallet_nft_fractionalization::weights::SubstrateWeight<Runtime>;
 	type RuntimeHoldReason = RuntimeHoldReason;
 	#[cfg(feature = "runtime-benchmarks")]
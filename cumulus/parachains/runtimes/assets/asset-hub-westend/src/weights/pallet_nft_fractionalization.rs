@@ -111,4 +111,64 @@ impl<T: frame_system::Config> pallet_nft_fractionalization::WeightInfo for Weigh
 			.saturating_add(T::DbWeight::get().reads(9))
 			.saturating_add(T::DbWeight::get().writes(10))
 	}
+	/// Not benchmarked for this runtime yet, uses the weight of the Substrate node.
+	fn unify_partial() -> Weight {
+		<pallet_nft_fractionalization::weights::SubstrateWeight<T> as pallet_nft_fractionalization::WeightInfo>::unify_partial()
+	}
+	/// Not benchmarked for this runtime yet, uses the weight of the Substrate node.
+	fn cancel_partial_unification(c: u32, ) -> Weight {
+		<pallet_nft_fractionalization::weights::SubstrateWeight<T> as pallet_nft_fractionalization::WeightInfo>::cancel_partial_unification(c)
+	}
+	/// Not benchmarked for this runtime yet, uses the weight of the Substrate node.
+	fn fractionalize_cross_chain() -> Weight {
+		<pallet_nft_fractionalization::weights::SubstrateWeight<T> as pallet_nft_fractionalization::WeightInfo>::fractionalize_cross_chain()
+	}
+	/// Not benchmarked for this runtime yet, uses the weight of the Substrate node.
+	fn unify_cross_chain() -> Weight {
+		<pallet_nft_fractionalization::weights::SubstrateWeight<T> as pallet_nft_fractionalization::WeightInfo>::unify_cross_chain()
+	}
+	/// Not benchmarked for this runtime yet, uses the weight of the Substrate node.
+	fn finish_cross_chain_unification() -> Weight {
+		<pallet_nft_fractionalization::weights::SubstrateWeight<T> as pallet_nft_fractionalization::WeightInfo>::finish_cross_chain_unification()
+	}
+	/// Not benchmarked for this runtime yet, uses the weight of the Substrate node.
+	fn fractionalize_batch(n: u32, ) -> Weight {
+		<pallet_nft_fractionalization::weights::SubstrateWeight<T> as pallet_nft_fractionalization::WeightInfo>::fractionalize_batch(n)
+	}
+	/// Not benchmarked for this runtime yet, uses the weight of the Substrate node.
+	fn burn_fractions() -> Weight {
+		<pallet_nft_fractionalization::weights::SubstrateWeight<T> as pallet_nft_fractionalization::WeightInfo>::burn_fractions()
+	}
+	/// Not benchmarked for this runtime yet, uses the weight of the Substrate node.
+	fn transfer_fractions() -> Weight {
+		<pallet_nft_fractionalization::weights::SubstrateWeight<T> as pallet_nft_fractionalization::WeightInfo>::transfer_fractions()
+	}
+	/// Not benchmarked for this runtime yet, uses the weight of the Substrate node.
+	fn fractionalize_with_royalty() -> Weight {
+		<pallet_nft_fractionalization::weights::SubstrateWeight<T> as pallet_nft_fractionalization::WeightInfo>::fractionalize_with_royalty()
+	}
+	/// Not benchmarked for this runtime yet, uses the weight of the Substrate node.
+	fn offer_loan() -> Weight {
+		<pallet_nft_fractionalization::weights::SubstrateWeight<T> as pallet_nft_fractionalization::WeightInfo>::offer_loan()
+	}
+	/// Not benchmarked for this runtime yet, uses the weight of the Substrate node.
+	fn pledge_fractions_as_collateral() -> Weight {
+		<pallet_nft_fractionalization::weights::SubstrateWeight<T> as pallet_nft_fractionalization::WeightInfo>::pledge_fractions_as_collateral()
+	}
+	/// Not benchmarked for this runtime yet, uses the weight of the Substrate node.
+	fn repay_loan() -> Weight {
+		<pallet_nft_fractionalization::weights::SubstrateWeight<T> as pallet_nft_fractionalization::WeightInfo>::repay_loan()
+	}
+	/// Not benchmarked for this runtime yet, uses the weight of the Substrate node.
+	fn liquidate_fraction_collateral() -> Weight {
+		<pallet_nft_fractionalization::weights::SubstrateWeight<T> as pallet_nft_fractionalization::WeightInfo>::liquidate_fraction_collateral()
+	}
+	/// Not benchmarked for this runtime yet, uses the weight of the Substrate node.
+	fn cancel_loan_offer() -> Weight {
+		<pallet_nft_fractionalization::weights::SubstrateWeight<T> as pallet_nft_fractionalization::WeightInfo>::cancel_loan_offer()
+	}
+	/// Not benchmarked for this runtime yet, uses the weight of the Substrate node.
+	fn transfer_callback() -> Weight {
+		<pallet_nft_fractionalization::weights::SubstrateWeight<T> as pallet_nft_fractionalization::WeightInfo>::transfer_callback()
+	}
 }
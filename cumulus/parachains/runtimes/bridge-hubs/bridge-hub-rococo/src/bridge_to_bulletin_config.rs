@@ -23,8 +23,8 @@ use crate::{
 	bridge_common_config::{BridgeGrandpaRococoBulletinInstance, BridgeHubRococo},
 	weights,
 	xcm_config::UniversalLocation,
-	AccountId, BridgeRococoBulletinGrandpa, BridgeRococoBulletinMessages, PolkadotXcm, Runtime,
-	RuntimeEvent, XcmOverRococoBulletin, XcmRouter,
+	AccountId, Balances, BridgeRococoBulletinGrandpa, BridgeRococoBulletinMessages, PolkadotXcm,
+	Runtime, RuntimeEvent, XcmOverRococoBulletin, XcmRouter,
 };
 use bp_messages::LaneId;
 use bp_runtime::Chain;
@@ -63,6 +63,9 @@ parameter_types! {
 	/// unconfirmed messages that the single confirmation transaction at Rococo Bulletin Chain may process.
 	pub const MaxUnconfirmedMessagesAtInboundLane: bp_messages::MessageNonce =
 		bp_polkadot_bulletin::MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX;
+	pub const LaneUpgradePeriod: crate::BlockNumber = 6 * crate::HOURS;
+	pub const FeeEscrowPeriod: crate::BlockNumber = 24 * crate::HOURS;
+	pub const MaxFeeEscrowsPerBlock: u32 = 64;
 	/// Bridge specific chain (network) identifier of the Rococo Bulletin Chain.
 	pub const RococoBulletinChainId: bp_runtime::ChainId = bp_polkadot_bulletin::PolkadotBulletin::ID;
 	/// Interior location (relative to this runtime) of the with-RococoBulletin messages pallet.
@@ -193,6 +196,8 @@ impl pallet_bridge_messages::Config<WithRococoBulletinMessagesInstance> for Runt
 	type ActiveOutboundLanes = ActiveOutboundLanesToRococoBulletin;
 	type MaxUnrewardedRelayerEntriesAtInboundLane = MaxUnrewardedRelayerEntriesAtInboundLane;
 	type MaxUnconfirmedMessagesAtInboundLane = MaxUnconfirmedMessagesAtInboundLane;
+	type ThisChain = BridgeHubRococo;
+	type UpgradePeriod = LaneUpgradePeriod;
 
 	type MaximalOutboundPayloadSize = ToRococoBulletinMaximalOutboundPayloadSize;
 	type OutboundPayload = XcmAsPlainPayload;
@@ -208,6 +213,10 @@ impl pallet_bridge_messages::Config<WithRococoBulletinMessagesInstance> for Runt
 	type MessageDispatch =
 		XcmBlobMessageDispatch<FromRococoBulletinMessageBlobDispatcher, Self::WeightInfo, ()>;
 	type OnMessagesDelivered = OnMessagesDeliveredFromRococoBulletin;
+
+	type FeeEscrowCurrency = Balances;
+	type FeeEscrowPeriod = FeeEscrowPeriod;
+	type MaxFeeEscrowsPerBlock = MaxFeeEscrowsPerBlock;
 }
 
 /// Add support for the export and dispatch of XCM programs.
@@ -22,8 +22,8 @@ use crate::{
 	},
 	weights,
 	xcm_config::UniversalLocation,
-	AccountId, BridgeWestendMessages, PolkadotXcm, Runtime, RuntimeEvent, XcmOverBridgeHubWestend,
-	XcmRouter,
+	AccountId, Balances, BridgeWestendMessages, PolkadotXcm, Runtime, RuntimeEvent,
+	XcmOverBridgeHubWestend, XcmRouter,
 };
 use bp_messages::LaneId;
 use bp_runtime::Chain;
@@ -58,6 +58,9 @@ parameter_types! {
 		bp_bridge_hub_rococo::MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX;
 	pub const MaxUnconfirmedMessagesAtInboundLane: bp_messages::MessageNonce =
 		bp_bridge_hub_rococo::MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX;
+	pub const LaneUpgradePeriod: crate::BlockNumber = 6 * crate::HOURS;
+	pub const FeeEscrowPeriod: crate::BlockNumber = 24 * crate::HOURS;
+	pub const MaxFeeEscrowsPerBlock: u32 = 64;
 	pub const BridgeHubWestendChainId: bp_runtime::ChainId = BridgeHubWestend::ID;
 	pub BridgeRococoToWestendMessagesPalletInstance: InteriorLocation = [PalletInstance(<BridgeWestendMessages as PalletInfoAccess>::index() as u8)].into();
 	pub WestendGlobalConsensusNetwork: NetworkId = NetworkId::Westend;
@@ -200,6 +203,8 @@ impl pallet_bridge_messages::Config<WithBridgeHubWestendMessagesInstance> for Ru
 	type ActiveOutboundLanes = ActiveOutboundLanesToBridgeHubWestend;
 	type MaxUnrewardedRelayerEntriesAtInboundLane = MaxUnrewardedRelayerEntriesAtInboundLane;
 	type MaxUnconfirmedMessagesAtInboundLane = MaxUnconfirmedMessagesAtInboundLane;
+	type ThisChain = BridgeHubRococo;
+	type UpgradePeriod = LaneUpgradePeriod;
 
 	type MaximalOutboundPayloadSize = ToBridgeHubWestendMaximalOutboundPayloadSize;
 	type OutboundPayload = XcmAsPlainPayload;
@@ -225,6 +230,10 @@ impl pallet_bridge_messages::Config<WithBridgeHubWestendMessagesInstance> for Ru
 		>,
 	>;
 	type OnMessagesDelivered = OnMessagesDeliveredFromWestend;
+
+	type FeeEscrowCurrency = Balances;
+	type FeeEscrowPeriod = FeeEscrowPeriod;
+	type MaxFeeEscrowsPerBlock = MaxFeeEscrowsPerBlock;
 }
 
 /// Add support for the export and dispatch of XCM programs.
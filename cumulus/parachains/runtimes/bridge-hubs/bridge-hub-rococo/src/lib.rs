@@ -148,6 +148,14 @@ pub type Migrations = (
 		ConstU32<BRIDGE_HUB_ID>,
 		ConstU32<ASSET_HUB_ID>,
 	>,
+	pallet_bridge_messages::migration::CompleteExpiredLaneUpgrades<
+		Runtime,
+		bridge_to_westend_config::WithBridgeHubWestendMessagesInstance,
+	>,
+	pallet_bridge_messages::migration::CompleteExpiredLaneUpgrades<
+		Runtime,
+		bridge_to_bulletin_config::WithRococoBulletinMessagesInstance,
+	>,
 	// permanent
 	pallet_xcm::migration::MigrateToLatestXcmVersion<Runtime>,
 );
@@ -968,6 +976,7 @@ impl_runtime_apis! {
 	}
 
 	// This is exposed by BridgeHubRococo
+	#[api_version(3)]
 	impl bp_bridge_hub_westend::ToBridgeHubWestendOutboundLaneApi<Block> for Runtime {
 		fn message_details(
 			lane: bp_messages::LaneId,
@@ -979,6 +988,22 @@ impl_runtime_apis! {
 				bridge_to_westend_config::WithBridgeHubWestendMessagesInstance,
 			>(lane, begin, end)
 		}
+
+		fn messages_pending_confirmation(lane: bp_messages::LaneId) -> bp_messages::MessageNonce {
+			bridge_runtime_common::messages_api::outbound_messages_pending_confirmation::<
+				Runtime,
+				bridge_to_westend_config::WithBridgeHubWestendMessagesInstance,
+			>(lane)
+		}
+
+		fn lanes_by_priority(
+			lanes: Vec<bp_messages::LaneId>,
+		) -> Vec<(bp_messages::LaneId, bp_messages::LanePriority)> {
+			bridge_runtime_common::messages_api::outbound_lanes_by_priority::<
+				Runtime,
+				bridge_to_westend_config::WithBridgeHubWestendMessagesInstance,
+			>(lanes)
+		}
 	}
 
 	impl bp_polkadot_bulletin::PolkadotBulletinFinalityApi<Block> for Runtime {
@@ -1004,6 +1029,7 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(3)]
 	impl bp_polkadot_bulletin::ToPolkadotBulletinOutboundLaneApi<Block> for Runtime {
 		fn message_details(
 			lane: bp_messages::LaneId,
@@ -1015,6 +1041,22 @@ impl_runtime_apis! {
 				bridge_to_bulletin_config::WithRococoBulletinMessagesInstance,
 			>(lane, begin, end)
 		}
+
+		fn messages_pending_confirmation(lane: bp_messages::LaneId) -> bp_messages::MessageNonce {
+			bridge_runtime_common::messages_api::outbound_messages_pending_confirmation::<
+				Runtime,
+				bridge_to_bulletin_config::WithRococoBulletinMessagesInstance,
+			>(lane)
+		}
+
+		fn lanes_by_priority(
+			lanes: Vec<bp_messages::LaneId>,
+		) -> Vec<(bp_messages::LaneId, bp_messages::LanePriority)> {
+			bridge_runtime_common::messages_api::outbound_lanes_by_priority::<
+				Runtime,
+				bridge_to_bulletin_config::WithRococoBulletinMessagesInstance,
+			>(lanes)
+		}
 	}
 
 	impl snowbridge_outbound_queue_runtime_api::OutboundQueueApi<Block, Balance> for Runtime {
@@ -22,6 +22,11 @@
 //! HOSTNAME: `runner-itmxxexx-project-674-concurrent-0`, CPU: `Intel(R) Xeon(R) CPU @ 2.60GHz`
 //! WASM-EXECUTION: `Compiled`, CHAIN: `Some("bridge-hub-rococo-dev")`, DB CACHE: 1024
 
+// Weights of `set_lane_priority`, `escrow_message_fee`, `close_fee_escrows`,
+// `refund_expired_fee_escrows`, `start_lane_upgrade`, `complete_lane_upgrade` and `set_lane_closed`
+// have not been generated by the benchmark CLI yet. They are estimated from the storage accesses
+// of their benchmarks and must be replaced by the output of the command below.
+
 // Executed Command:
 // target/production/polkadot-parachain
 // benchmark
@@ -240,4 +245,109 @@ impl<T: frame_system::Config> pallet_bridge_messages::WeightInfo for WeightInfo<
 			.saturating_add(T::DbWeight::get().reads(10))
 			.saturating_add(T::DbWeight::get().writes(4))
 	}
+	/// Storage: `BridgeWestendMessages::PalletOwner` (r:1 w:0)
+	/// Proof: `BridgeWestendMessages::PalletOwner` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeWestendMessages::OutboundLanePriorities` (r:0 w:1)
+	/// Proof: `BridgeWestendMessages::OutboundLanePriorities` (`max_values`: None, `max_size`: Some(21), added: 2496, mode: `MaxEncodedLen`)
+	fn set_lane_priority() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(8_712_000, 0)
+			.saturating_add(Weight::from_parts(0, 1517))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `BridgeWestendMessages::PalletOperatingMode` (r:1 w:0)
+	/// Proof: `BridgeWestendMessages::PalletOperatingMode` (`max_values`: Some(1), `max_size`: Some(2), added: 497, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeWestendMessages::OutboundLanes` (r:1 w:0)
+	/// Proof: `BridgeWestendMessages::OutboundLanes` (`max_values`: Some(1), `max_size`: Some(44), added: 539, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeWestendMessages::OutboundMessageSenders` (r:1 w:0)
+	/// Proof: `BridgeWestendMessages::OutboundMessageSenders` (`max_values`: None, `max_size`: Some(60), added: 2535, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeWestendMessages::EscrowedFees` (r:1 w:1)
+	/// Proof: `BridgeWestendMessages::EscrowedFees` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeWestendMessages::FeeEscrowExpirations` (r:1 w:1)
+	/// Proof: `BridgeWestendMessages::FeeEscrowExpirations` (`max_values`: None, `max_size`: Some(781), added: 3256, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeWestendMessages::LaneFeeEscrows` (r:1 w:1)
+	/// Proof: `BridgeWestendMessages::LaneFeeEscrows` (`max_values`: None, `max_size`: Some(24), added: 2499, mode: `MaxEncodedLen`)
+	fn escrow_message_fee() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(61_204_000, 0)
+			.saturating_add(Weight::from_parts(0, 4246))
+			.saturating_add(T::DbWeight::get().reads(8))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	/// Storage: `BridgeWestendMessages::LaneFeeEscrows` (r:1 w:1)
+	/// Proof: `BridgeWestendMessages::LaneFeeEscrows` (`max_values`: None, `max_size`: Some(24), added: 2499, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeWestendMessages::EscrowedFees` (r:n w:n)
+	/// Proof: `BridgeWestendMessages::EscrowedFees` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:n w:n)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn close_fee_escrows(n: u32) -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(7_913_000, 0)
+			.saturating_add(Weight::from_parts(0, 3593))
+			.saturating_add(Weight::from_parts(34_520_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(2))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(n.into()))
+	}
+	/// Storage: `BridgeWestendMessages::FeeEscrowExpirations` (r:1 w:1)
+	/// Proof: `BridgeWestendMessages::FeeEscrowExpirations` (`max_values`: None, `max_size`: Some(781), added: 3256, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeWestendMessages::EscrowedFees` (r:n w:n)
+	/// Proof: `BridgeWestendMessages::EscrowedFees` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:n w:n)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeWestendMessages::LaneFeeEscrows` (r:n w:n)
+	/// Proof: `BridgeWestendMessages::LaneFeeEscrows` (`max_values`: None, `max_size`: Some(24), added: 2499, mode: `MaxEncodedLen`)
+	fn refund_expired_fee_escrows(n: u32) -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(6_381_000, 0)
+			.saturating_add(Weight::from_parts(0, 4246))
+			.saturating_add(Weight::from_parts(38_075_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 7657).saturating_mul(n.into()))
+	}
+	/// Storage: `BridgeWestendMessages::PalletOwner` (r:1 w:0)
+	/// Proof: `BridgeWestendMessages::PalletOwner` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeWestendMessages::LaneStates` (r:1 w:1)
+	/// Proof: `BridgeWestendMessages::LaneStates` (`max_values`: None, `max_size`: Some(29), added: 2504, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeWestendMessages::LaneVersions` (r:1 w:0)
+	/// Proof: `BridgeWestendMessages::LaneVersions` (`max_values`: None, `max_size`: Some(24), added: 2499, mode: `MaxEncodedLen`)
+	fn start_lane_upgrade() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(14_118_000, 0)
+			.saturating_add(Weight::from_parts(0, 3489))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `BridgeWestendMessages::PalletOwner` (r:1 w:0)
+	/// Proof: `BridgeWestendMessages::PalletOwner` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeWestendMessages::LaneStates` (r:1 w:1)
+	/// Proof: `BridgeWestendMessages::LaneStates` (`max_values`: None, `max_size`: Some(29), added: 2504, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeWestendMessages::LaneVersions` (r:0 w:1)
+	/// Proof: `BridgeWestendMessages::LaneVersions` (`max_values`: None, `max_size`: Some(24), added: 2499, mode: `MaxEncodedLen`)
+	fn complete_lane_upgrade() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(13_290_000, 0)
+			.saturating_add(Weight::from_parts(0, 3494))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `BridgeWestendMessages::PalletOwner` (r:1 w:0)
+	/// Proof: `BridgeWestendMessages::PalletOwner` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeWestendMessages::LaneStates` (r:1 w:1)
+	/// Proof: `BridgeWestendMessages::LaneStates` (`max_values`: None, `max_size`: Some(29), added: 2504, mode: `MaxEncodedLen`)
+	fn set_lane_closed() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(11_027_000, 0)
+			.saturating_add(Weight::from_parts(0, 3494))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
@@ -18,7 +18,7 @@
 
 use crate::{
 	bridge_common_config::DeliveryRewardInBalance, weights, xcm_config::UniversalLocation,
-	AccountId, BridgeRococoMessages, PolkadotXcm, Runtime, RuntimeEvent, RuntimeOrigin,
+	AccountId, Balances, BridgeRococoMessages, PolkadotXcm, Runtime, RuntimeEvent, RuntimeOrigin,
 	XcmOverBridgeHubRococo, XcmRouter,
 };
 use bp_messages::LaneId;
@@ -63,6 +63,9 @@ parameter_types! {
 		bp_bridge_hub_westend::MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX;
 	pub const MaxUnconfirmedMessagesAtInboundLane: bp_messages::MessageNonce =
 		bp_bridge_hub_westend::MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX;
+	pub const LaneUpgradePeriod: crate::BlockNumber = 6 * crate::HOURS;
+	pub const FeeEscrowPeriod: crate::BlockNumber = 24 * crate::HOURS;
+	pub const MaxFeeEscrowsPerBlock: u32 = 64;
 	pub const BridgeHubRococoChainId: bp_runtime::ChainId = BridgeHubRococo::ID;
 	pub BridgeWestendToRococoMessagesPalletInstance: InteriorLocation = [PalletInstance(<BridgeRococoMessages as PalletInfoAccess>::index() as u8)].into();
 	pub RococoGlobalConsensusNetwork: NetworkId = NetworkId::Rococo;
@@ -237,6 +240,8 @@ impl pallet_bridge_messages::Config<WithBridgeHubRococoMessagesInstance> for Run
 	type ActiveOutboundLanes = ActiveOutboundLanesToBridgeHubRococo;
 	type MaxUnrewardedRelayerEntriesAtInboundLane = MaxUnrewardedRelayerEntriesAtInboundLane;
 	type MaxUnconfirmedMessagesAtInboundLane = MaxUnconfirmedMessagesAtInboundLane;
+	type ThisChain = BridgeHubWestend;
+	type UpgradePeriod = LaneUpgradePeriod;
 
 	type MaximalOutboundPayloadSize = ToBridgeHubRococoMaximalOutboundPayloadSize;
 	type OutboundPayload = XcmAsPlainPayload;
@@ -262,6 +267,10 @@ impl pallet_bridge_messages::Config<WithBridgeHubRococoMessagesInstance> for Run
 		>,
 	>;
 	type OnMessagesDelivered = OnMessagesDelivered;
+
+	type FeeEscrowCurrency = Balances;
+	type FeeEscrowPeriod = FeeEscrowPeriod;
+	type MaxFeeEscrowsPerBlock = MaxFeeEscrowsPerBlock;
 }
 
 /// Add support for the export and dispatch of XCM programs.
@@ -122,6 +122,10 @@ pub type Migrations = (
 	InitStorageVersions,
 	// unreleased
 	cumulus_pallet_xcmp_queue::migration::v4::MigrationToV4<Runtime>,
+	pallet_bridge_messages::migration::CompleteExpiredLaneUpgrades<
+		Runtime,
+		bridge_to_rococo_config::WithBridgeHubRococoMessagesInstance,
+	>,
 	// permanent
 	pallet_xcm::migration::MigrateToLatestXcmVersion<Runtime>,
 );
@@ -716,6 +720,7 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(3)]
 	impl bp_bridge_hub_rococo::ToBridgeHubRococoOutboundLaneApi<Block> for Runtime {
 		fn message_details(
 			lane: bp_messages::LaneId,
@@ -727,6 +732,22 @@ impl_runtime_apis! {
 				bridge_to_rococo_config::WithBridgeHubRococoMessagesInstance,
 			>(lane, begin, end)
 		}
+
+		fn messages_pending_confirmation(lane: bp_messages::LaneId) -> bp_messages::MessageNonce {
+			bridge_runtime_common::messages_api::outbound_messages_pending_confirmation::<
+				Runtime,
+				bridge_to_rococo_config::WithBridgeHubRococoMessagesInstance,
+			>(lane)
+		}
+
+		fn lanes_by_priority(
+			lanes: Vec<bp_messages::LaneId>,
+		) -> Vec<(bp_messages::LaneId, bp_messages::LanePriority)> {
+			bridge_runtime_common::messages_api::outbound_lanes_by_priority::<
+				Runtime,
+				bridge_to_rococo_config::WithBridgeHubRococoMessagesInstance,
+			>(lanes)
+		}
 	}
 
 	#[cfg(feature = "try-runtime")]
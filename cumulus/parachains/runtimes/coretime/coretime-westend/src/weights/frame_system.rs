@@ -187,4 +187,12 @@ impl<T: frame_system::Config> frame_system::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(5))
 			.saturating_add(T::DbWeight::get().writes(4))
 	}
+	/// Not benchmarked for this runtime yet, uses the weight of the Substrate node.
+	fn freeze_account() -> Weight {
+		<frame_system::SubstrateWeight<T> as frame_system::WeightInfo>::freeze_account()
+	}
+	/// Not benchmarked for this runtime yet, uses the weight of the Substrate node.
+	fn thaw_account() -> Weight {
+		<frame_system::SubstrateWeight<T> as frame_system::WeightInfo>::thaw_account()
+	}
 }
@@ -0,0 +1,133 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Pallet limiting the number of bytes that may be sent by XCM routers in a single block.
+//!
+//! Bridges and other transports have finite bandwidth, so unlimited number of XCM messages, sent
+//! in a single block, may starve other chains. The [`BandwidthLimitedRouter`] wraps other router
+//! and counts bytes of messages, delivered by it, in the `XcmBytesUsedThisBlock` value, which is
+//! cleared at the beginning of every block.
+//!
+//! The pallet is instantiable, so several routers with different limits may be used by the same
+//! runtime - every router needs its own pallet instance.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Encode;
+use frame_support::{dispatch::DispatchClass, traits::Get};
+use sp_std::{marker::PhantomData, result::Result};
+use xcm::prelude::*;
+use xcm_builder::CONGESTED;
+
+pub use pallet::*;
+
+mod mock;
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config {
+		/// Maximal number of bytes that may be sent by the router in a single block.
+		#[pallet::constant]
+		type MaxBytesPerBlock: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			XcmBytesUsedThisBlock::<T, I>::kill();
+			T::DbWeight::get().writes(1)
+		}
+	}
+
+	/// Number of bytes, sent by the router in the current block.
+	///
+	/// The value is cleared at the beginning of every block.
+	#[pallet::storage]
+	pub type XcmBytesUsedThisBlock<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, u32, ValueQuery>;
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Returns number of bytes that have been sent by the router in the current block.
+	pub fn bytes_used_this_block() -> u32 {
+		XcmBytesUsedThisBlock::<T, I>::get()
+	}
+}
+
+/// Router that limits the number of bytes that may be sent using the `Inner` router in a single
+/// block.
+///
+/// Messages that are not accepted by the `Inner` router are rejected with the same error, so the
+/// router may be used in a tuple with other routers (including other `BandwidthLimitedRouter`s,
+/// backed by other pallet instances). Messages that would make the number of bytes, sent in the
+/// current block, exceed the `Config::MaxBytesPerBlock` are rejected with the
+/// `SendError::Transport(CONGESTED)` error.
+///
+/// The cost of accessing the counter is registered as the mandatory weight of the block.
+pub struct BandwidthLimitedRouter<T, I, Inner>(PhantomData<(T, I, Inner)>);
+impl<T: Config<I>, I: 'static, Inner: SendXcm> SendXcm for BandwidthLimitedRouter<T, I, Inner> {
+	type Ticket = (Inner::Ticket, u32);
+
+	fn validate(
+		destination: &mut Option<Location>,
+		message: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket> {
+		let message_size = message.as_ref().map(|m| m.encoded_size()).unwrap_or(0);
+		let message_size =
+			u32::try_from(message_size).map_err(|_| SendError::ExceedsMaxMessageSize)?;
+		let (ticket, price) = Inner::validate(destination, message)?;
+
+		// we read the counter
+		frame_system::Pallet::<T>::register_extra_weight_unchecked(
+			T::DbWeight::get().reads(1),
+			DispatchClass::Mandatory,
+		);
+		let bytes_used = Pallet::<T, I>::bytes_used_this_block().saturating_add(message_size);
+		if bytes_used > T::MaxBytesPerBlock::get() {
+			log::trace!(
+				target: "xcm::routing",
+				"BandwidthLimitedRouter rejected message of {} bytes: limit of {} bytes per block is reached",
+				message_size,
+				T::MaxBytesPerBlock::get(),
+			);
+			return Err(SendError::Transport(CONGESTED))
+		}
+
+		Ok(((ticket, message_size), price))
+	}
+
+	fn deliver(ticket: Self::Ticket) -> Result<XcmHash, SendError> {
+		let (ticket, message_size) = ticket;
+		let hash = Inner::deliver(ticket)?;
+
+		// we update the counter
+		frame_system::Pallet::<T>::register_extra_weight_unchecked(
+			T::DbWeight::get().reads_writes(1, 1),
+			DispatchClass::Mandatory,
+		);
+		XcmBytesUsedThisBlock::<T, I>::mutate(|bytes| *bytes = bytes.saturating_add(message_size));
+		Ok(hash)
+	}
+}
@@ -0,0 +1,107 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use crate as pallet_xcm_bandwidth_limit;
+
+use frame_support::{
+	construct_runtime, derive_impl, parameter_types,
+	traits::{ConstU32, Get},
+	weights::RuntimeDbWeight,
+};
+use sp_runtime::BuildStorage;
+use sp_std::marker::PhantomData;
+use xcm::prelude::*;
+
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+
+construct_runtime! {
+	pub enum TestRuntime
+	{
+		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
+		FirstBandwidthLimit: pallet_xcm_bandwidth_limit::<Instance1>::{Pallet, Storage},
+		SecondBandwidthLimit: pallet_xcm_bandwidth_limit::<Instance2>::{Pallet, Storage},
+	}
+}
+
+parameter_types! {
+	pub static SentXcm: Vec<(Location, Xcm<()>)> = vec![];
+	pub const FirstRouterDestination: Location = Location::parent();
+	pub const SecondRouterDestination: Location = Location::new(1, [Parachain(1000)]);
+	pub const TestDbWeight: RuntimeDbWeight = RuntimeDbWeight { read: 1, write: 10 };
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for TestRuntime {
+	type Block = Block;
+	type DbWeight = TestDbWeight;
+}
+
+impl pallet_xcm_bandwidth_limit::Config<pallet_xcm_bandwidth_limit::Instance1> for TestRuntime {
+	type MaxBytesPerBlock = ConstU32<8>;
+}
+
+impl pallet_xcm_bandwidth_limit::Config<pallet_xcm_bandwidth_limit::Instance2> for TestRuntime {
+	type MaxBytesPerBlock = ConstU32<4>;
+}
+
+/// Router that only accepts messages to the `Destination` and stores them in the `SentXcm`.
+pub struct TestDestinationRouter<Destination>(PhantomData<Destination>);
+impl<Destination: Get<Location>> SendXcm for TestDestinationRouter<Destination> {
+	type Ticket = (Location, Xcm<()>);
+
+	fn validate(
+		dest: &mut Option<Location>,
+		msg: &mut Option<Xcm<()>>,
+	) -> SendResult<(Location, Xcm<()>)> {
+		if dest.as_ref() != Some(&Destination::get()) {
+			return Err(SendError::NotApplicable)
+		}
+		let (dest, msg) = (dest.take().unwrap(), msg.take().unwrap());
+		Ok(((dest, msg), Assets::new()))
+	}
+
+	fn deliver(ticket: (Location, Xcm<()>)) -> Result<XcmHash, SendError> {
+		SentXcm::mutate(|sent| sent.push(ticket));
+		Ok([0; 32])
+	}
+}
+
+pub type FirstLimitedRouter = crate::BandwidthLimitedRouter<
+	TestRuntime,
+	pallet_xcm_bandwidth_limit::Instance1,
+	TestDestinationRouter<FirstRouterDestination>,
+>;
+pub type SecondLimitedRouter = crate::BandwidthLimitedRouter<
+	TestRuntime,
+	pallet_xcm_bandwidth_limit::Instance2,
+	TestDestinationRouter<SecondRouterDestination>,
+>;
+
+/// Return test externalities to use in tests.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::<TestRuntime>::default().build_storage().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Run pallet test.
+pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
+	SentXcm::set(vec![]);
+	new_test_ext().execute_with(test)
+}
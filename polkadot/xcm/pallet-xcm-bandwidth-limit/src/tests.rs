@@ -0,0 +1,129 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::*;
+
+use codec::Encode;
+use frame_support::{assert_ok, traits::Hooks};
+
+/// Every message, used in tests is 4 bytes long when encoded.
+fn test_message() -> Xcm<()> {
+	let message = Xcm(vec![ClearOrigin, ClearError, ClearTopic]);
+	assert_eq!(message.encoded_size(), 4);
+	message
+}
+
+type FirstPallet = Pallet<TestRuntime, Instance1>;
+type SecondPallet = Pallet<TestRuntime, Instance2>;
+
+#[test]
+fn bandwidth_limited_router_enforces_limit() {
+	run_test(|| {
+		assert_ok!(send_xcm::<FirstLimitedRouter>(Parent.into(), test_message()));
+		assert_ok!(send_xcm::<FirstLimitedRouter>(Parent.into(), test_message()));
+		assert_eq!(FirstPallet::bytes_used_this_block(), 8);
+		assert_eq!(
+			send_xcm::<FirstLimitedRouter>(Parent.into(), test_message()),
+			Err(SendError::Transport(CONGESTED)),
+		);
+		assert_eq!(SentXcm::get().len(), 2);
+	});
+}
+
+#[test]
+fn bandwidth_limited_router_registers_mandatory_weight() {
+	run_test(|| {
+		let db_weight = TestDbWeight::get();
+		assert_ok!(send_xcm::<FirstLimitedRouter>(Parent.into(), test_message()));
+		assert_eq!(
+			frame_system::Pallet::<TestRuntime>::block_weight().get(DispatchClass::Mandatory),
+			&db_weight.reads(1).saturating_add(db_weight.reads_writes(1, 1)),
+		);
+	});
+}
+
+#[test]
+fn bandwidth_limited_router_resets_counter_at_next_block() {
+	run_test(|| {
+		assert_ok!(send_xcm::<FirstLimitedRouter>(Parent.into(), test_message()));
+		assert_ok!(send_xcm::<FirstLimitedRouter>(Parent.into(), test_message()));
+		assert_eq!(
+			send_xcm::<FirstLimitedRouter>(Parent.into(), test_message()),
+			Err(SendError::Transport(CONGESTED)),
+		);
+
+		assert_eq!(FirstPallet::on_initialize(2), TestDbWeight::get().writes(1));
+		assert_eq!(FirstPallet::bytes_used_this_block(), 0);
+		assert_ok!(send_xcm::<FirstLimitedRouter>(Parent.into(), test_message()));
+		assert_eq!(FirstPallet::bytes_used_this_block(), 4);
+		assert_eq!(SentXcm::get().len(), 3);
+	});
+}
+
+#[test]
+fn bandwidth_limited_routers_in_tuple_do_not_interfere() {
+	type Router = (FirstLimitedRouter, SecondLimitedRouter);
+
+	run_test(|| {
+		let second_destination = SecondRouterDestination::get();
+
+		// second router has smaller limit
+		assert_ok!(send_xcm::<Router>(second_destination.clone(), test_message()));
+		assert_eq!(
+			send_xcm::<Router>(second_destination.clone(), test_message()),
+			Err(SendError::Transport(CONGESTED)),
+		);
+
+		// first router is still able to send messages
+		assert_ok!(send_xcm::<Router>(Parent.into(), test_message()));
+		assert_ok!(send_xcm::<Router>(Parent.into(), test_message()));
+		assert_eq!(
+			send_xcm::<Router>(Parent.into(), test_message()),
+			Err(SendError::Transport(CONGESTED))
+		);
+
+		assert_eq!(FirstPallet::bytes_used_this_block(), 8);
+		assert_eq!(SecondPallet::bytes_used_this_block(), 4);
+		assert_eq!(SentXcm::get().len(), 3);
+
+		// resetting the counter of one router doesn't affect the other one
+		SecondPallet::on_initialize(2);
+		assert_eq!(FirstPallet::bytes_used_this_block(), 8);
+		assert_eq!(SecondPallet::bytes_used_this_block(), 0);
+	});
+}
+
+#[test]
+fn bandwidth_limited_router_triggers_fallback_router() {
+	type Router = xcm_builder::WithFallbackRouter<
+		FirstLimitedRouter,
+		TestDestinationRouter<FirstRouterDestination>,
+	>;
+
+	run_test(|| {
+		assert_ok!(send_xcm::<Router>(Parent.into(), test_message()));
+		assert_ok!(send_xcm::<Router>(Parent.into(), test_message()));
+		assert_eq!(FirstPallet::bytes_used_this_block(), 8);
+
+		// the limit is reached, so the message is sent using the fallback router
+		assert_ok!(send_xcm::<Router>(Parent.into(), test_message()));
+		assert_eq!(FirstPallet::bytes_used_this_block(), 8);
+		assert_eq!(SentXcm::get().len(), 3);
+	});
+}
@@ -25,7 +25,7 @@ use xcm::{
 	DoubleEncoded,
 };
 use xcm_executor::{
-	traits::{ConvertLocation, FeeReason},
+	traits::{ConvertLocation, FeeReason, ProcessTransaction},
 	ExecutorError, FeesMode,
 };
 
@@ -751,6 +751,15 @@ benchmarks! {
 		assert_eq!(executor.origin(), &Some(target));
 	}
 
+	// The overhead of processing an instruction by the `TransactionalProcessor`, e.g. the storage
+	// layer and the tracking of its nesting depth. It is a part of the weight of every
+	// transactional instruction above, so it is not used to weigh the instructions separately.
+	process_transaction {
+	}: {
+		<T::XcmConfig as xcm_executor::Config>::TransactionalProcessor::process(|| Ok(()))
+			.map_err(|_| BenchmarkError::Stop("transactional processor has failed"))?;
+	}
+
 	impl_benchmark_test_suite!(
 		Pallet,
 		crate::generic::mock::new_test_ext(),
@@ -0,0 +1,235 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Pallet that retries dispatching blobs, which have failed to be dispatched over the bridge.
+//!
+//! The [`RetryBlobDispatcher`] wraps the `Config::Dispatcher`. When the dispatcher fails with
+//! `DispatchBlobError::RoutingError` (e.g. when the router is temporarily unavailable), the blob
+//! is stored in the [`PendingBlobs`] map and retried at the beginning of the next blocks with
+//! exponential backoff: `2^retry_count` blocks after the previous attempt. After
+//! `Config::MaxRetries` failed retries, the blob is dropped and the [`Event::BlobDropped`] is
+//! deposited.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{dispatch::DispatchClass, traits::Get, weights::Weight, BoundedVec};
+use frame_system::pallet_prelude::BlockNumberFor;
+use scale_info::TypeInfo;
+use sp_runtime::{traits::Saturating, RuntimeDebug};
+use sp_std::{marker::PhantomData, prelude::*};
+use xcm_builder::{DispatchBlob, DispatchBlobError};
+
+pub use pallet::*;
+
+mod mock;
+mod tests;
+
+/// The target that will be used when publishing logs related to this pallet.
+pub const LOG_TARGET: &str = "xcm::blob-retry";
+
+/// Blob that has failed to be dispatched and is waiting for the next attempt.
+#[derive(Clone, Encode, Decode, MaxEncodedLen, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub struct PendingBlob<Blob, BlockNumber> {
+	/// The blob itself.
+	pub blob: Blob,
+	/// Number of failed retries.
+	pub retry_count: u32,
+	/// The block, starting from which the next retry may happen.
+	pub next_retry_block: BlockNumber,
+}
+
+/// Blob, waiting for the next attempt in the given runtime.
+pub type PendingBlobOf<T> =
+	PendingBlob<BoundedVec<u8, <T as Config>::MaxBlobSize>, BlockNumberFor<T>>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Dispatcher of blobs.
+		type Dispatcher: DispatchBlob;
+		/// Maximal number of failed retries, after which the blob is dropped.
+		#[pallet::constant]
+		type MaxRetries: Get<u32>;
+		/// Maximal number of blobs that may wait for the next attempt.
+		#[pallet::constant]
+		type MaxPendingBlobs: Get<u32>;
+		/// Maximal number of blobs that are retried in a single block.
+		#[pallet::constant]
+		type MaxRetriesPerBlock: Get<u32>;
+		/// Maximal size of the blob that may wait for the next attempt.
+		#[pallet::constant]
+		type MaxBlobSize: Get<u32>;
+		/// Weight of a single `dispatch_blob` call of the `Dispatcher`.
+		#[pallet::constant]
+		type RetryWeight: Get<Weight>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			Self::retry_pending_blobs(now)
+		}
+	}
+
+	/// Blobs that are waiting for the next attempt, by their index.
+	#[pallet::storage]
+	pub type PendingBlobs<T: Config> = CountedStorageMap<_, Twox64Concat, u32, PendingBlobOf<T>>;
+
+	/// Index of the next blob, stored in the `PendingBlobs`.
+	#[pallet::storage]
+	pub type NextPendingBlobIndex<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The blob has failed to be dispatched and is stored at `index` for the next attempt.
+		BlobQueued { index: u32 },
+		/// The blob at `index` has been dispatched on a retry.
+		BlobDispatched { index: u32 },
+		/// The blob at `index` has been dropped after `retry_count` failed retries.
+		BlobDropped { index: u32, retry_count: u32 },
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Returns number of blobs that are waiting for the next attempt.
+	pub fn pending_blob_count() -> u32 {
+		PendingBlobs::<T>::count()
+	}
+
+	/// Store the blob for the next attempt.
+	///
+	/// Returns false if the blob is too large or the queue is full.
+	fn queue_blob(blob: Vec<u8>) -> bool {
+		if T::MaxRetries::get() == 0 || Self::pending_blob_count() >= T::MaxPendingBlobs::get() {
+			return false
+		}
+		let Ok(blob) = BoundedVec::try_from(blob) else { return false };
+
+		let index = NextPendingBlobIndex::<T>::mutate(|next_index| {
+			let index = *next_index;
+			*next_index = next_index.wrapping_add(1);
+			index
+		});
+		let now = frame_system::Pallet::<T>::block_number();
+		PendingBlobs::<T>::insert(
+			index,
+			PendingBlob { blob, retry_count: 0, next_retry_block: Self::next_retry_block(now, 0) },
+		);
+		Self::deposit_event(Event::BlobQueued { index });
+		true
+	}
+
+	/// Retries dispatching blobs whose backoff is over. At most `Config::MaxRetriesPerBlock`
+	/// blobs are retried, others are retried at next blocks. Returns the consumed weight.
+	fn retry_pending_blobs(now: BlockNumberFor<T>) -> Weight {
+		let db_weight = T::DbWeight::get();
+		let mut weight_used = db_weight.reads(1);
+		if Self::pending_blob_count() == 0 {
+			return weight_used
+		}
+
+		// there are at most `MaxPendingBlobs` blobs in the queue
+		let pending = PendingBlobs::<T>::iter().collect::<Vec<_>>();
+		weight_used.saturating_accrue(db_weight.reads(pending.len() as u64));
+
+		let retry_weight = T::RetryWeight::get().saturating_add(db_weight.reads_writes(1, 2));
+		let mut retried = 0;
+		for (index, mut pending_blob) in pending {
+			if pending_blob.next_retry_block > now || retried >= T::MaxRetriesPerBlock::get() {
+				continue
+			}
+
+			retried += 1;
+			weight_used.saturating_accrue(retry_weight);
+			match T::Dispatcher::dispatch_blob(pending_blob.blob.to_vec()) {
+				Ok(()) => {
+					PendingBlobs::<T>::remove(index);
+					Self::deposit_event(Event::BlobDispatched { index });
+				},
+				Err(error) => {
+					pending_blob.retry_count = pending_blob.retry_count.saturating_add(1);
+					if pending_blob.retry_count >= T::MaxRetries::get() {
+						log::debug!(
+							target: LOG_TARGET,
+							"Dropped blob {} after {} retries: {:?}",
+							index,
+							pending_blob.retry_count,
+							error,
+						);
+						PendingBlobs::<T>::remove(index);
+						Self::deposit_event(Event::BlobDropped {
+							index,
+							retry_count: pending_blob.retry_count,
+						});
+					} else {
+						pending_blob.next_retry_block =
+							Self::next_retry_block(now, pending_blob.retry_count);
+						PendingBlobs::<T>::insert(index, pending_blob);
+					}
+				},
+			}
+		}
+		weight_used
+	}
+
+	fn next_retry_block(now: BlockNumberFor<T>, retry_count: u32) -> BlockNumberFor<T> {
+		let backoff = 2u32.checked_pow(retry_count).unwrap_or(u32::MAX);
+		now.saturating_add(backoff.into())
+	}
+}
+
+/// `DispatchBlob` implementation that stores blobs, which have failed to be dispatched by the
+/// `Config::Dispatcher` because of `DispatchBlobError::RoutingError`, in the [`PendingBlobs`].
+///
+/// The error is always returned to the caller, even if the blob has been queued. Note that the
+/// blob is only kept if the caller doesn't roll back storage changes on that error. Blobs larger
+/// than `Config::MaxBlobSize` bytes, or blobs that fail once there are
+/// `Config::MaxPendingBlobs` blobs in the queue, are not queued. All other errors are returned to
+/// the caller immediately. The cost of queueing the blob is registered as the mandatory weight of
+/// the block.
+pub struct RetryBlobDispatcher<T>(PhantomData<T>);
+impl<T: Config> DispatchBlob for RetryBlobDispatcher<T> {
+	fn dispatch_blob(blob: Vec<u8>) -> Result<(), DispatchBlobError> {
+		match T::Dispatcher::dispatch_blob(blob.clone()) {
+			Err(DispatchBlobError::RoutingError) => {
+				// we read the counter and the index and write them back with the blob
+				frame_system::Pallet::<T>::register_extra_weight_unchecked(
+					T::DbWeight::get().reads_writes(2, 3),
+					DispatchClass::Mandatory,
+				);
+				if !Pallet::<T>::queue_blob(blob) {
+					log::debug!(
+						target: LOG_TARGET,
+						"Failed to queue blob: it is too large or the queue is full",
+					);
+				}
+				Err(DispatchBlobError::RoutingError)
+			},
+			result => result,
+		}
+	}
+}
@@ -0,0 +1,87 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use crate as pallet_xcm_blob_retry;
+
+use frame_support::{
+	construct_runtime, derive_impl, parameter_types,
+	traits::ConstU32,
+	weights::{RuntimeDbWeight, Weight},
+};
+use sp_runtime::BuildStorage;
+use xcm_builder::{DispatchBlob, DispatchBlobError};
+
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+
+construct_runtime! {
+	pub enum TestRuntime
+	{
+		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
+		XcmBlobRetry: pallet_xcm_blob_retry::{Pallet, Storage, Event<T>},
+	}
+}
+
+parameter_types! {
+	pub static BridgeAvailable: bool = false;
+	pub static DispatchedBlobs: Vec<Vec<u8>> = vec![];
+	pub static MaxRetriesPerBlock: u32 = 10;
+	pub const TestDbWeight: RuntimeDbWeight = RuntimeDbWeight { read: 1, write: 10 };
+	pub const RetryWeight: Weight = Weight::from_parts(100, 0);
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for TestRuntime {
+	type Block = Block;
+	type DbWeight = TestDbWeight;
+}
+
+impl pallet_xcm_blob_retry::Config for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
+	type Dispatcher = TestBlobDispatcher;
+	type MaxRetries = ConstU32<3>;
+	type MaxPendingBlobs = ConstU32<2>;
+	type MaxRetriesPerBlock = MaxRetriesPerBlock;
+	type MaxBlobSize = ConstU32<8>;
+	type RetryWeight = RetryWeight;
+}
+
+/// Blob dispatcher that records all blobs in the `DispatchedBlobs`, unless `BridgeAvailable` is
+/// unset.
+pub struct TestBlobDispatcher;
+impl DispatchBlob for TestBlobDispatcher {
+	fn dispatch_blob(blob: Vec<u8>) -> Result<(), DispatchBlobError> {
+		if !BridgeAvailable::get() {
+			return Err(DispatchBlobError::RoutingError)
+		}
+		DispatchedBlobs::mutate(|blobs| blobs.push(blob));
+		Ok(())
+	}
+}
+
+/// Return test externalities to use in tests.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::<TestRuntime>::default().build_storage().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Run pallet test.
+pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
+	new_test_ext().execute_with(test)
+}
@@ -0,0 +1,150 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::*;
+
+use frame_support::{traits::Hooks, weights::Weight};
+
+fn retry_weight(pending: u64, retried: u64) -> Weight {
+	let db_weight = TestDbWeight::get();
+	db_weight
+		.reads(1 + pending)
+		.saturating_add(RetryWeight::get().saturating_add(db_weight.reads_writes(1, 2)) * retried)
+}
+
+#[test]
+fn failed_blob_is_queued_and_dispatched_on_retry() {
+	run_test(|| {
+		BridgeAvailable::set(false);
+		assert_eq!(
+			RetryBlobDispatcher::<TestRuntime>::dispatch_blob(vec![42]),
+			Err(DispatchBlobError::RoutingError),
+		);
+		assert_eq!(XcmBlobRetry::pending_blob_count(), 1);
+		System::assert_last_event(Event::BlobQueued { index: 0 }.into());
+
+		// nothing is retried in the same block
+		assert_eq!(XcmBlobRetry::on_initialize(1), retry_weight(1, 0));
+		assert!(DispatchedBlobs::get().is_empty());
+
+		BridgeAvailable::set(true);
+		System::set_block_number(2);
+		assert_eq!(XcmBlobRetry::on_initialize(2), retry_weight(1, 1));
+		assert_eq!(DispatchedBlobs::get(), vec![vec![42]]);
+		assert_eq!(XcmBlobRetry::pending_blob_count(), 0);
+		System::assert_last_event(Event::BlobDispatched { index: 0 }.into());
+
+		// nothing to retry anymore
+		assert_eq!(XcmBlobRetry::on_initialize(3), TestDbWeight::get().reads(1));
+	});
+}
+
+#[test]
+fn successful_blob_is_not_queued() {
+	run_test(|| {
+		BridgeAvailable::set(true);
+		assert_eq!(RetryBlobDispatcher::<TestRuntime>::dispatch_blob(vec![42]), Ok(()));
+		assert_eq!(DispatchedBlobs::get(), vec![vec![42]]);
+		assert_eq!(XcmBlobRetry::pending_blob_count(), 0);
+	});
+}
+
+#[test]
+fn blob_is_retried_with_backoff_and_dropped_after_max_retries() {
+	run_test(|| {
+		BridgeAvailable::set(false);
+		let _ = RetryBlobDispatcher::<TestRuntime>::dispatch_blob(vec![42]);
+
+		// first retry fails at block 2, the next one is scheduled at block 2 + 2
+		assert_eq!(XcmBlobRetry::on_initialize(2), retry_weight(1, 1));
+		assert_eq!(
+			PendingBlobs::<TestRuntime>::get(0)
+				.map(|pending| (pending.retry_count, pending.next_retry_block)),
+			Some((1, 4)),
+		);
+
+		// backoff is not over yet
+		assert_eq!(XcmBlobRetry::on_initialize(3), retry_weight(1, 0));
+		assert_eq!(PendingBlobs::<TestRuntime>::get(0).map(|pending| pending.retry_count), Some(1));
+
+		// second retry fails at block 4, the next one is scheduled at block 4 + 4
+		assert_eq!(XcmBlobRetry::on_initialize(4), retry_weight(1, 1));
+		assert_eq!(
+			PendingBlobs::<TestRuntime>::get(0)
+				.map(|pending| (pending.retry_count, pending.next_retry_block)),
+			Some((2, 8)),
+		);
+
+		// third retry fails and the blob is dropped
+		assert_eq!(XcmBlobRetry::on_initialize(8), retry_weight(1, 1));
+		assert_eq!(XcmBlobRetry::pending_blob_count(), 0);
+		System::assert_last_event(Event::BlobDropped { index: 0, retry_count: 3 }.into());
+		assert!(DispatchedBlobs::get().is_empty());
+	});
+}
+
+#[test]
+fn queue_is_bounded() {
+	run_test(|| {
+		BridgeAvailable::set(false);
+
+		// too large blob is not queued
+		assert_eq!(
+			RetryBlobDispatcher::<TestRuntime>::dispatch_blob(vec![42; 9]),
+			Err(DispatchBlobError::RoutingError),
+		);
+		assert_eq!(XcmBlobRetry::pending_blob_count(), 0);
+
+		// at most 2 blobs are queued
+		for i in 0..3 {
+			assert_eq!(
+				RetryBlobDispatcher::<TestRuntime>::dispatch_blob(vec![i]),
+				Err(DispatchBlobError::RoutingError),
+			);
+		}
+		assert_eq!(XcmBlobRetry::pending_blob_count(), 2);
+		assert_eq!(
+			PendingBlobs::<TestRuntime>::get(0).map(|pending| pending.blob.to_vec()),
+			Some(vec![0])
+		);
+		assert_eq!(
+			PendingBlobs::<TestRuntime>::get(1).map(|pending| pending.blob.to_vec()),
+			Some(vec![1])
+		);
+	});
+}
+
+#[test]
+fn retries_per_block_are_bounded() {
+	run_test(|| {
+		MaxRetriesPerBlock::set(1);
+		BridgeAvailable::set(false);
+		let _ = RetryBlobDispatcher::<TestRuntime>::dispatch_blob(vec![1]);
+		let _ = RetryBlobDispatcher::<TestRuntime>::dispatch_blob(vec![2]);
+
+		BridgeAvailable::set(true);
+		assert_eq!(XcmBlobRetry::on_initialize(2), retry_weight(2, 1));
+		assert_eq!(DispatchedBlobs::get().len(), 1);
+		assert_eq!(XcmBlobRetry::pending_blob_count(), 1);
+
+		assert_eq!(XcmBlobRetry::on_initialize(3), retry_weight(1, 1));
+		assert_eq!(DispatchedBlobs::get().len(), 2);
+		assert_eq!(XcmBlobRetry::pending_blob_count(), 0);
+	});
+}
@@ -0,0 +1,99 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Pallet that tracks sequence numbers of blobs, sent and dispatched over the bridge.
+//!
+//! The [`OutboundSequenceTracker`] is meant to be used by the `SequencedBlobHauler` at the
+//! sending side of the bridge and the [`InboundSequenceTracker`] - by the
+//! `OrderedBridgeBlobDispatcher` at the receiving side. Sequence numbers of every channel are
+//! stored in the [`OutboundSequences`] and [`InboundSequences`] maps respectively. They start at
+//! zero and wrap around after `u64::MAX`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::{dispatch::DispatchClass, traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+use xcm_builder::SequenceTracker;
+
+pub use pallet::*;
+
+mod mock;
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Identifier of the channel (e.g. the bridge lane) the blobs are delivered over.
+		type Channel: Parameter + MaxEncodedLen;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Sequence numbers of the next blobs, sent over every channel.
+	#[pallet::storage]
+	pub type OutboundSequences<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::Channel, u64, ValueQuery>;
+
+	/// Sequence numbers of the next blobs, expected over every channel.
+	#[pallet::storage]
+	pub type InboundSequences<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::Channel, u64, ValueQuery>;
+}
+
+fn register_weight<T: Config>(weight: Weight) {
+	frame_system::Pallet::<T>::register_extra_weight_unchecked(weight, DispatchClass::Mandatory);
+}
+
+/// `SequenceTracker` of blobs, sent over the bridge, backed by the [`OutboundSequences`].
+///
+/// The cost of accessing the map is registered as the mandatory weight of the block.
+pub struct OutboundSequenceTracker<T>(PhantomData<T>);
+impl<T: Config> SequenceTracker for OutboundSequenceTracker<T> {
+	type Channel = T::Channel;
+
+	fn expected_sequence(channel: &T::Channel) -> u64 {
+		register_weight::<T>(T::DbWeight::get().reads(1));
+		OutboundSequences::<T>::get(channel)
+	}
+
+	fn advance_sequence(channel: &T::Channel) {
+		register_weight::<T>(T::DbWeight::get().reads_writes(1, 1));
+		OutboundSequences::<T>::mutate(channel, |sequence| *sequence = sequence.wrapping_add(1));
+	}
+}
+
+/// `SequenceTracker` of blobs, dispatched from the bridge, backed by the [`InboundSequences`].
+///
+/// The cost of accessing the map is registered as the mandatory weight of the block.
+pub struct InboundSequenceTracker<T>(PhantomData<T>);
+impl<T: Config> SequenceTracker for InboundSequenceTracker<T> {
+	type Channel = T::Channel;
+
+	fn expected_sequence(channel: &T::Channel) -> u64 {
+		register_weight::<T>(T::DbWeight::get().reads(1));
+		InboundSequences::<T>::get(channel)
+	}
+
+	fn advance_sequence(channel: &T::Channel) {
+		register_weight::<T>(T::DbWeight::get().reads_writes(1, 1));
+		InboundSequences::<T>::mutate(channel, |sequence| *sequence = sequence.wrapping_add(1));
+	}
+}
@@ -0,0 +1,105 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::*;
+
+use xcm_builder::{
+	DispatchBlob, DispatchBlobError, HaulBlob, HaulBlobError, OrderedBridgeBlobDispatcher,
+	SequencedBlobHauler,
+};
+
+type HaulerA = SequencedBlobHauler<TestBlobHauler, OutboundSequenceTracker<TestRuntime>, ChannelA>;
+type HaulerB = SequencedBlobHauler<TestBlobHauler, OutboundSequenceTracker<TestRuntime>, ChannelB>;
+type Dispatcher =
+	OrderedBridgeBlobDispatcher<TestBlobDispatcher, InboundSequenceTracker<TestRuntime>>;
+
+#[test]
+fn blobs_are_sequenced_and_dispatched_in_order() {
+	run_test(|| {
+		assert_eq!(HaulerA::haul_blob(vec![1]), Ok(()));
+		assert_eq!(HaulerA::haul_blob(vec![2]), Ok(()));
+		assert_eq!(OutboundSequences::<TestRuntime>::get(ChannelA::get()), 2);
+
+		let hauled = HauledBlobs::get();
+		assert_eq!(
+			Dispatcher::dispatch_blob(hauled[1].clone()),
+			Err(DispatchBlobError::WrongVersion),
+		);
+		for blob in hauled {
+			assert_eq!(Dispatcher::dispatch_blob(blob), Ok(()));
+		}
+		assert_eq!(DispatchedBlobs::get(), vec![vec![1], vec![2]]);
+		assert_eq!(InboundSequences::<TestRuntime>::get(ChannelA::get()), 2);
+
+		// the blob from the past is rejected
+		assert_eq!(
+			Dispatcher::dispatch_blob(HauledBlobs::get()[0].clone()),
+			Err(DispatchBlobError::WrongVersion),
+		);
+	});
+}
+
+#[test]
+fn sequence_is_not_advanced_if_blob_has_not_been_sent() {
+	run_test(|| {
+		BridgeAvailable::set(false);
+		assert_eq!(HaulerA::haul_blob(vec![1]), Err(HaulBlobError::Transport("unavailable")));
+		assert_eq!(OutboundSequences::<TestRuntime>::get(ChannelA::get()), 0);
+
+		BridgeAvailable::set(true);
+		assert_eq!(HaulerA::haul_blob(vec![1]), Ok(()));
+		assert_eq!(Dispatcher::dispatch_blob(HauledBlobs::get()[0].clone()), Ok(()));
+	});
+}
+
+#[test]
+fn channels_are_sequenced_independently() {
+	run_test(|| {
+		assert_eq!(HaulerA::haul_blob(vec![1]), Ok(()));
+		assert_eq!(HaulerA::haul_blob(vec![2]), Ok(()));
+		assert_eq!(HaulerB::haul_blob(vec![3]), Ok(()));
+		assert_eq!(OutboundSequences::<TestRuntime>::get(ChannelA::get()), 2);
+		assert_eq!(OutboundSequences::<TestRuntime>::get(ChannelB::get()), 1);
+
+		// the blob of the other channel doesn't need to wait for the first channel
+		let hauled = HauledBlobs::get();
+		assert_eq!(Dispatcher::dispatch_blob(hauled[2].clone()), Ok(()));
+		assert_eq!(Dispatcher::dispatch_blob(hauled[0].clone()), Ok(()));
+		assert_eq!(InboundSequences::<TestRuntime>::get(ChannelA::get()), 1);
+		assert_eq!(InboundSequences::<TestRuntime>::get(ChannelB::get()), 1);
+	});
+}
+
+#[test]
+fn sequences_wrap_around() {
+	run_test(|| {
+		OutboundSequences::<TestRuntime>::insert(ChannelA::get(), u64::MAX);
+		InboundSequences::<TestRuntime>::insert(ChannelA::get(), u64::MAX);
+
+		assert_eq!(HaulerA::haul_blob(vec![1]), Ok(()));
+		assert_eq!(HaulerA::haul_blob(vec![2]), Ok(()));
+		assert_eq!(OutboundSequences::<TestRuntime>::get(ChannelA::get()), 1);
+
+		for blob in HauledBlobs::get() {
+			assert_eq!(Dispatcher::dispatch_blob(blob), Ok(()));
+		}
+		assert_eq!(DispatchedBlobs::get(), vec![vec![1], vec![2]]);
+		assert_eq!(InboundSequences::<TestRuntime>::get(ChannelA::get()), 1);
+	});
+}
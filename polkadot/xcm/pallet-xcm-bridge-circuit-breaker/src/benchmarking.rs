@@ -0,0 +1,55 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for the XCM bridge circuit breaker pallet.
+
+use super::*;
+use frame_benchmarking::v1::{benchmarks, BenchmarkError};
+use frame_support::traits::EnsureOrigin;
+
+fn note_some_failures<T: Config>() -> T::BridgeId {
+	let bridge = T::BenchmarkHelper::bridge_id();
+	Breakers::<T>::insert(&bridge, (frame_system::Pallet::<T>::block_number(), 1));
+	bridge
+}
+
+benchmarks! {
+	force_reset_circuit_breaker {
+		let bridge = note_some_failures::<T>();
+		let origin = T::ResetOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+	}: _<T::RuntimeOrigin>(origin, bridge.clone())
+	verify {
+		assert!(Breakers::<T>::get(&bridge).is_none());
+	}
+
+	// The overhead of the `BridgeCircuitBreaker` over the inner dispatcher, when the inner
+	// dispatcher fails.
+	dispatch_blob {
+		let bridge = note_some_failures::<T>();
+	}: {
+		let failures = Pallet::<T>::failure_count(&bridge);
+		Breakers::<T>::insert(
+			&bridge,
+			(frame_system::Pallet::<T>::block_number(), failures.saturating_add(1)),
+		);
+	}
+	verify {
+		assert_eq!(Pallet::<T>::failure_count(&bridge), 2);
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::TestRuntime);
+}
@@ -0,0 +1,188 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Pallet that stops dispatching blobs over the malfunctioning bridges.
+//!
+//! If a bridge is malfunctioning, every blob that is dispatched over it fails, wasting the block
+//! weight. The [`BridgeCircuitBreaker`] wraps the blob dispatcher of the bridge and counts the
+//! consecutive failures of the bridge in the current block in the [`Breakers`] map. Once there
+//! are `Config::MaxFailuresPerBlock` of them, the circuit breaker of the bridge opens and all
+//! blobs over the bridge are rejected without trying the dispatcher, until the next block. The
+//! `Config::ResetOrigin` may also close the circuit breaker using the
+//! `force_reset_circuit_breaker` call.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::{dispatch::DispatchClass, traits::Get};
+use sp_std::{marker::PhantomData, prelude::*};
+use xcm_builder::{DispatchBlob, DispatchBlobError};
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+mod mock;
+mod tests;
+pub mod weights;
+
+/// The target that will be used when publishing logs related to this pallet.
+pub const LOG_TARGET: &str = "xcm::bridge-circuit-breaker";
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Identifier of the bridge.
+		type BridgeId: Parameter + MaxEncodedLen;
+		/// Number of consecutive failures in a single block, after which the circuit breaker of
+		/// the bridge opens.
+		#[pallet::constant]
+		type MaxFailuresPerBlock: Get<u32>;
+		/// Origin that may close the circuit breakers.
+		type ResetOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Weight information for extrinsics and the blob dispatcher of this pallet.
+		type WeightInfo: WeightInfo;
+		/// Helper for creating the bridge identifiers in benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::BridgeId>;
+	}
+
+	/// Helper for creating the bridge identifiers in benchmarks.
+	#[cfg(feature = "runtime-benchmarks")]
+	pub trait BenchmarkHelper<BridgeId> {
+		/// Returns the identifier of a bridge, guarded by the circuit breaker.
+		fn bridge_id() -> BridgeId;
+	}
+	#[cfg(feature = "runtime-benchmarks")]
+	impl<BridgeId: From<u32>> BenchmarkHelper<BridgeId> for () {
+		fn bridge_id() -> BridgeId {
+			0u32.into()
+		}
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Close the circuit breaker of the `bridge`, so that blobs are dispatched over it again.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::force_reset_circuit_breaker())]
+		pub fn force_reset_circuit_breaker(
+			origin: OriginFor<T>,
+			bridge: T::BridgeId,
+		) -> DispatchResult {
+			T::ResetOrigin::ensure_origin(origin)?;
+
+			Breakers::<T>::remove(&bridge);
+			Self::deposit_event(Event::CircuitBreakerReset { bridge });
+			Ok(())
+		}
+	}
+
+	/// Consecutive failures of every bridge and the block where they have happened.
+	///
+	/// Failures from the previous blocks are ignored, so there's no need to clear the map at the
+	/// beginning of every block.
+	#[pallet::storage]
+	pub type Breakers<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::BridgeId, (BlockNumberFor<T>, u32)>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The circuit breaker of the `bridge` has opened after `failures` consecutive failures.
+		CircuitBreakerOpened { bridge: T::BridgeId, failures: u32 },
+		/// The circuit breaker of the `bridge` has been closed by the `Config::ResetOrigin`.
+		CircuitBreakerReset { bridge: T::BridgeId },
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Returns number of consecutive failures of the `bridge` in the current block.
+	pub fn failure_count(bridge: &T::BridgeId) -> u32 {
+		match Breakers::<T>::get(bridge) {
+			Some((block, failures)) if block == frame_system::Pallet::<T>::block_number() =>
+				failures,
+			_ => 0,
+		}
+	}
+
+	/// Returns `true` if the circuit breaker of the `bridge` is open and blobs are rejected.
+	pub fn is_open(bridge: &T::BridgeId) -> bool {
+		Self::failure_count(bridge) >= T::MaxFailuresPerBlock::get()
+	}
+}
+
+/// `DispatchBlob` implementation that stops passing blobs to the `Inner` dispatcher of the
+/// malfunctioning `Bridge`.
+///
+/// After `Config::MaxFailuresPerBlock` consecutive failures of the `Inner` dispatcher in the
+/// current block, all blobs are rejected with `DispatchBlobError::NotApplicable` without trying
+/// the `Inner` dispatcher. The cost of accessing the failure count is registered as the mandatory
+/// weight of the block.
+pub struct BridgeCircuitBreaker<T, Bridge, Inner>(PhantomData<(T, Bridge, Inner)>);
+impl<T: Config, Bridge: Get<T::BridgeId>, Inner: DispatchBlob> DispatchBlob
+	for BridgeCircuitBreaker<T, Bridge, Inner>
+{
+	fn dispatch_blob(blob: Vec<u8>) -> Result<(), DispatchBlobError> {
+		// we read and update the failure count of the bridge
+		frame_system::Pallet::<T>::register_extra_weight_unchecked(
+			T::WeightInfo::dispatch_blob(),
+			DispatchClass::Mandatory,
+		);
+
+		let bridge = Bridge::get();
+		let failures = Pallet::<T>::failure_count(&bridge);
+		if failures >= T::MaxFailuresPerBlock::get() {
+			return Err(DispatchBlobError::NotApplicable)
+		}
+
+		match Inner::dispatch_blob(blob) {
+			Ok(()) => {
+				if failures != 0 {
+					Breakers::<T>::remove(&bridge);
+				}
+				Ok(())
+			},
+			Err(error) => {
+				let failures = failures.saturating_add(1);
+				Breakers::<T>::insert(
+					&bridge,
+					(frame_system::Pallet::<T>::block_number(), failures),
+				);
+				if failures >= T::MaxFailuresPerBlock::get() {
+					log::debug!(
+						target: LOG_TARGET,
+						"Circuit breaker of the bridge {:?} has opened after {} failures: {:?}",
+						bridge,
+						failures,
+						error,
+					);
+					Pallet::<T>::deposit_event(Event::CircuitBreakerOpened { bridge, failures });
+				}
+				Err(error)
+			},
+		}
+	}
+}
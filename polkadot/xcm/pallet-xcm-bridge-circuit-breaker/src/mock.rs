@@ -0,0 +1,82 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use crate as pallet_xcm_bridge_circuit_breaker;
+
+use frame_support::{construct_runtime, derive_impl, parameter_types, traits::ConstU32};
+use frame_system::EnsureRoot;
+use sp_runtime::BuildStorage;
+use xcm_builder::{DispatchBlob, DispatchBlobError};
+
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+
+construct_runtime! {
+	pub enum TestRuntime
+	{
+		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
+		XcmBridgeCircuitBreaker: pallet_xcm_bridge_circuit_breaker::{Pallet, Call, Storage, Event<T>},
+	}
+}
+
+parameter_types! {
+	pub static BridgeAvailable: bool = false;
+	pub static DispatchedBlobs: Vec<Vec<u8>> = vec![];
+	pub const BridgeA: u32 = 1;
+	pub const BridgeB: u32 = 2;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for TestRuntime {
+	type Block = Block;
+}
+
+impl pallet_xcm_bridge_circuit_breaker::Config for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
+	type BridgeId = u32;
+	type MaxFailuresPerBlock = ConstU32<3>;
+	type ResetOrigin = EnsureRoot<u64>;
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
+/// Blob dispatcher that records all blobs in the `DispatchedBlobs`, unless `BridgeAvailable` is
+/// unset.
+pub struct TestBlobDispatcher;
+impl DispatchBlob for TestBlobDispatcher {
+	fn dispatch_blob(blob: Vec<u8>) -> Result<(), DispatchBlobError> {
+		if !BridgeAvailable::get() {
+			return Err(DispatchBlobError::RoutingError)
+		}
+		DispatchedBlobs::mutate(|blobs| blobs.push(blob));
+		Ok(())
+	}
+}
+
+/// Return test externalities to use in tests.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::<TestRuntime>::default().build_storage().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Run pallet test.
+pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
+	new_test_ext().execute_with(test)
+}
@@ -0,0 +1,132 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::*;
+
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+use sp_runtime::DispatchError;
+
+type CircuitBreakerA = BridgeCircuitBreaker<TestRuntime, BridgeA, TestBlobDispatcher>;
+type CircuitBreakerB = BridgeCircuitBreaker<TestRuntime, BridgeB, TestBlobDispatcher>;
+
+fn open_circuit_breaker_a() {
+	BridgeAvailable::set(false);
+	for _ in 0..3 {
+		let _ = CircuitBreakerA::dispatch_blob(vec![42]);
+	}
+	assert!(XcmBridgeCircuitBreaker::is_open(&BridgeA::get()));
+}
+
+#[test]
+fn circuit_breaker_opens_after_consecutive_failures() {
+	run_test(|| {
+		BridgeAvailable::set(false);
+		for _ in 0..2 {
+			assert_eq!(
+				CircuitBreakerA::dispatch_blob(vec![42]),
+				Err(DispatchBlobError::RoutingError),
+			);
+		}
+
+		// successful dispatch resets the failure count
+		BridgeAvailable::set(true);
+		assert_eq!(CircuitBreakerA::dispatch_blob(vec![42]), Ok(()));
+		assert_eq!(XcmBridgeCircuitBreaker::failure_count(&BridgeA::get()), 0);
+
+		BridgeAvailable::set(false);
+		for _ in 0..3 {
+			assert!(!XcmBridgeCircuitBreaker::is_open(&BridgeA::get()));
+			assert_eq!(
+				CircuitBreakerA::dispatch_blob(vec![42]),
+				Err(DispatchBlobError::RoutingError),
+			);
+		}
+		assert_eq!(XcmBridgeCircuitBreaker::failure_count(&BridgeA::get()), 3);
+		assert!(XcmBridgeCircuitBreaker::is_open(&BridgeA::get()));
+		System::assert_last_event(
+			Event::CircuitBreakerOpened { bridge: BridgeA::get(), failures: 3 }.into(),
+		);
+	});
+}
+
+#[test]
+fn open_circuit_breaker_rejects_blobs() {
+	run_test(|| {
+		open_circuit_breaker_a();
+
+		// the inner dispatcher is not even tried
+		BridgeAvailable::set(true);
+		assert_eq!(CircuitBreakerA::dispatch_blob(vec![42]), Err(DispatchBlobError::NotApplicable));
+		assert_eq!(DispatchedBlobs::get(), Vec::<Vec<u8>>::new());
+		assert_eq!(XcmBridgeCircuitBreaker::failure_count(&BridgeA::get()), 3);
+	});
+}
+
+#[test]
+fn circuit_breakers_of_bridges_are_independent() {
+	run_test(|| {
+		open_circuit_breaker_a();
+
+		BridgeAvailable::set(true);
+		assert!(!XcmBridgeCircuitBreaker::is_open(&BridgeB::get()));
+		assert_eq!(CircuitBreakerB::dispatch_blob(vec![42]), Ok(()));
+		assert_eq!(DispatchedBlobs::get(), vec![vec![42]]);
+	});
+}
+
+#[test]
+fn circuit_breaker_closes_at_next_block() {
+	run_test(|| {
+		open_circuit_breaker_a();
+
+		System::set_block_number(2);
+		assert!(!XcmBridgeCircuitBreaker::is_open(&BridgeA::get()));
+		assert_eq!(XcmBridgeCircuitBreaker::failure_count(&BridgeA::get()), 0);
+
+		BridgeAvailable::set(true);
+		assert_eq!(CircuitBreakerA::dispatch_blob(vec![42]), Ok(()));
+		assert_eq!(DispatchedBlobs::get(), vec![vec![42]]);
+		assert_eq!(Breakers::<TestRuntime>::get(BridgeA::get()), None);
+	});
+}
+
+#[test]
+fn circuit_breaker_may_be_reset_manually() {
+	run_test(|| {
+		open_circuit_breaker_a();
+
+		// only `ResetOrigin` may reset the circuit breaker
+		assert_noop!(
+			XcmBridgeCircuitBreaker::force_reset_circuit_breaker(
+				RawOrigin::Signed(1).into(),
+				BridgeA::get(),
+			),
+			DispatchError::BadOrigin,
+		);
+
+		assert_ok!(XcmBridgeCircuitBreaker::force_reset_circuit_breaker(
+			RawOrigin::Root.into(),
+			BridgeA::get(),
+		));
+		assert!(!XcmBridgeCircuitBreaker::is_open(&BridgeA::get()));
+		assert_eq!(XcmBridgeCircuitBreaker::failure_count(&BridgeA::get()), 0);
+		System::assert_last_event(Event::CircuitBreakerReset { bridge: BridgeA::get() }.into());
+	});
+}
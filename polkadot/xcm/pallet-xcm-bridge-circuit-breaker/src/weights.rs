@@ -0,0 +1,89 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for `pallet_xcm_bridge_circuit_breaker`
+//!
+//! The weights in this file have not been generated by the benchmark CLI yet. They are estimated
+//! from the storage accesses of the benchmarks in `benchmarking.rs` and must be replaced by the
+//! output of the command below, once the pallet is part of a benchmarked runtime.
+
+// Command:
+// target/production/polkadot
+// benchmark
+// pallet
+// --steps=50
+// --repeat=20
+// --extrinsic=*
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --pallet=pallet_xcm_bridge_circuit_breaker
+// --chain=rococo-dev
+// --header=./polkadot/file_header.txt
+// --output=./polkadot/xcm/pallet-xcm-bridge-circuit-breaker/src/weights.rs
+// --template=./substrate/.maintain/frame-weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for `pallet_xcm_bridge_circuit_breaker`.
+pub trait WeightInfo {
+	fn force_reset_circuit_breaker() -> Weight;
+	fn dispatch_blob() -> Weight;
+}
+
+/// Weights for `pallet_xcm_bridge_circuit_breaker` using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `XcmBridgeCircuitBreaker::Breakers` (r:0 w:1)
+	/// Proof: `XcmBridgeCircuitBreaker::Breakers` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
+	fn force_reset_circuit_breaker() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `XcmBridgeCircuitBreaker::Breakers` (r:1 w:1)
+	/// Proof: `XcmBridgeCircuitBreaker::Breakers` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
+	fn dispatch_blob() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(6_000_000, 3493)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	/// Storage: `XcmBridgeCircuitBreaker::Breakers` (r:0 w:1)
+	/// Proof: `XcmBridgeCircuitBreaker::Breakers` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
+	fn force_reset_circuit_breaker() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `XcmBridgeCircuitBreaker::Breakers` (r:1 w:1)
+	/// Proof: `XcmBridgeCircuitBreaker::Breakers` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
+	fn dispatch_blob() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(6_000_000, 3493)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
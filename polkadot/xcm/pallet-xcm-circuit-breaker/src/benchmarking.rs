@@ -0,0 +1,55 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for the XCM circuit breaker pallet.
+
+use super::*;
+use frame_benchmarking::v1::{benchmarks, BenchmarkError};
+use frame_support::traits::EnsureOrigin;
+
+fn note_some_outflow<T: Config>() -> T::AssetId {
+	let asset_id = T::BenchmarkHelper::asset_id();
+	DailyOutflow::<T>::insert(&asset_id, (Pallet::<T>::current_day(), T::Balance::from(1u32)));
+	asset_id
+}
+
+benchmarks! {
+	reset_circuit_breaker {
+		let asset_id = note_some_outflow::<T>();
+		let origin = T::ResetOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+	}: _<T::RuntimeOrigin>(origin, asset_id.clone())
+	verify {
+		assert!(DailyOutflow::<T>::get(&asset_id).is_none());
+	}
+
+	ensure_within_limit {
+		let asset_id = note_some_outflow::<T>();
+	}: {
+		let _ = Pallet::<T>::ensure_within_limit(&asset_id, Zero::zero());
+	}
+
+	note_outflow {
+		let asset_id = note_some_outflow::<T>();
+	}: {
+		Pallet::<T>::note_outflow(&asset_id, T::Balance::from(2u32));
+	}
+	verify {
+		assert_eq!(Pallet::<T>::daily_outflow(&asset_id), T::Balance::from(2u32));
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::TestRuntime);
+}
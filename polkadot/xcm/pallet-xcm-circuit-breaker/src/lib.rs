@@ -0,0 +1,237 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Pallet limiting the amount of every asset that may leave accounts through XCM in a single day.
+//!
+//! Chains bridging large-value assets may be drained quickly if the bridge is compromised. The
+//! [`CircuitBreakerFungiblesAdapter`] wraps another asset transactor and counts the amount of
+//! every asset that it withdraws or transfers in the [`DailyOutflow`] map. Once the outflow of an
+//! asset would exceed the `Config::DailyLimit`, further withdrawals and transfers of the asset
+//! fail until the next day, or until the `Config::ResetOrigin` resets the circuit breaker of the
+//! asset using the `reset_circuit_breaker` call.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::{dispatch::DispatchClass, traits::Get};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_runtime::traits::{Saturating, Zero};
+use sp_std::{marker::PhantomData, result::Result};
+use xcm::prelude::*;
+use xcm_executor::{
+	traits::{MatchesFungibles, TransactAsset},
+	AssetsInHolding,
+};
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+mod mock;
+mod tests;
+pub mod weights;
+
+/// The target that will be used when publishing logs related to this pallet.
+pub const LOG_TARGET: &str = "xcm::circuit-breaker";
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::AtLeast32BitUnsigned;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Identifier of the assets, guarded by the circuit breaker.
+		type AssetId: Member + Parameter + MaxEncodedLen;
+		/// Balance of the assets, guarded by the circuit breaker.
+		type Balance: Member + Parameter + MaxEncodedLen + AtLeast32BitUnsigned + Copy;
+		/// Maximal amount of every asset that may leave accounts during a single day.
+		#[pallet::constant]
+		type DailyLimit: Get<Self::Balance>;
+		/// Number of blocks in a day.
+		#[pallet::constant]
+		type BlocksPerDay: Get<BlockNumberFor<Self>>;
+		/// Origin that may reset the circuit breaker of an asset.
+		type ResetOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Weight information for extrinsics and the asset transactor of this pallet.
+		type WeightInfo: WeightInfo;
+		/// Helper for creating the assets in benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::AssetId>;
+	}
+
+	/// Helper for creating the assets in benchmarks.
+	#[cfg(feature = "runtime-benchmarks")]
+	pub trait BenchmarkHelper<AssetId> {
+		/// Returns the identifier of an asset, guarded by the circuit breaker.
+		fn asset_id() -> AssetId;
+	}
+	#[cfg(feature = "runtime-benchmarks")]
+	impl<AssetId: From<u32>> BenchmarkHelper<AssetId> for () {
+		fn asset_id() -> AssetId {
+			0u32.into()
+		}
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Reset the outflow of the asset during the current day, so that it may leave accounts
+		/// again.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::reset_circuit_breaker())]
+		pub fn reset_circuit_breaker(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResult {
+			T::ResetOrigin::ensure_origin(origin)?;
+
+			DailyOutflow::<T>::remove(&asset_id);
+			Self::deposit_event(Event::CircuitBreakerReset { asset_id });
+			Ok(())
+		}
+	}
+
+	/// The amount of every asset that has left accounts, along with the day it has been counted
+	/// at.
+	///
+	/// The outflow, counted at one of the previous days, is ignored. So there's at most one entry
+	/// for every asset, guarded by the circuit breaker.
+	#[pallet::storage]
+	pub type DailyOutflow<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AssetId, (BlockNumberFor<T>, T::Balance)>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The circuit breaker of the asset has been reset.
+		CircuitBreakerReset { asset_id: T::AssetId },
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Returns the amount of the asset that has left accounts during the current day.
+	pub fn daily_outflow(asset_id: &T::AssetId) -> T::Balance {
+		match DailyOutflow::<T>::get(asset_id) {
+			Some((day, outflow)) if day == Self::current_day() => outflow,
+			_ => Zero::zero(),
+		}
+	}
+
+	/// Returns index of the current day.
+	fn current_day() -> BlockNumberFor<T> {
+		let blocks_per_day = T::BlocksPerDay::get();
+		if blocks_per_day.is_zero() {
+			return Zero::zero()
+		}
+		frame_system::Pallet::<T>::block_number() / blocks_per_day
+	}
+
+	/// Ensures that the `amount` of the asset may leave accounts without exceeding the daily
+	/// limit. Returns the outflow of the asset, including the `amount`.
+	fn ensure_within_limit(
+		asset_id: &T::AssetId,
+		amount: T::Balance,
+	) -> Result<T::Balance, XcmError> {
+		frame_system::Pallet::<T>::register_extra_weight_unchecked(
+			T::WeightInfo::ensure_within_limit(),
+			DispatchClass::Mandatory,
+		);
+
+		let outflow = Self::daily_outflow(asset_id).saturating_add(amount);
+		if outflow > T::DailyLimit::get() {
+			log::trace!(
+				target: LOG_TARGET,
+				"Daily outflow limit is reached for asset: {:?}",
+				asset_id,
+			);
+			return Err(XcmError::FailedToTransactAsset("circuit breaker is triggered"))
+		}
+		Ok(outflow)
+	}
+
+	/// Updates the outflow of the asset during the current day.
+	fn note_outflow(asset_id: &T::AssetId, outflow: T::Balance) {
+		frame_system::Pallet::<T>::register_extra_weight_unchecked(
+			T::WeightInfo::note_outflow(),
+			DispatchClass::Mandatory,
+		);
+
+		DailyOutflow::<T>::insert(asset_id, (Self::current_day(), outflow));
+	}
+}
+
+/// `TransactAsset` implementation that limits the amount of every asset, that may leave accounts
+/// via the `Inner` transactor (by withdrawals and internal transfers) during a single day.
+///
+/// Only the assets, matched by the `Matcher`, may be withdrawn and transferred. The cost of
+/// accessing the outflow counter, `Config::WeightInfo::ensure_within_limit` and
+/// `Config::WeightInfo::note_outflow`, is registered as the mandatory weight of the block.
+pub struct CircuitBreakerFungiblesAdapter<T, Inner, Matcher>(PhantomData<(T, Inner, Matcher)>);
+impl<T, Inner, Matcher> TransactAsset for CircuitBreakerFungiblesAdapter<T, Inner, Matcher>
+where
+	T: Config,
+	Inner: TransactAsset,
+	Matcher: MatchesFungibles<T::AssetId, T::Balance>,
+{
+	fn can_check_in(origin: &Location, what: &Asset, context: &XcmContext) -> XcmResult {
+		Inner::can_check_in(origin, what, context)
+	}
+
+	fn check_in(origin: &Location, what: &Asset, context: &XcmContext) {
+		Inner::check_in(origin, what, context)
+	}
+
+	fn can_check_out(dest: &Location, what: &Asset, context: &XcmContext) -> XcmResult {
+		Inner::can_check_out(dest, what, context)
+	}
+
+	fn check_out(dest: &Location, what: &Asset, context: &XcmContext) {
+		Inner::check_out(dest, what, context)
+	}
+
+	fn deposit_asset(what: &Asset, who: &Location, context: Option<&XcmContext>) -> XcmResult {
+		Inner::deposit_asset(what, who, context)
+	}
+
+	fn withdraw_asset(
+		what: &Asset,
+		who: &Location,
+		maybe_context: Option<&XcmContext>,
+	) -> Result<AssetsInHolding, XcmError> {
+		let (asset_id, amount) = Matcher::matches_fungibles(what)?;
+		let outflow = Pallet::<T>::ensure_within_limit(&asset_id, amount)?;
+		let withdrawn = Inner::withdraw_asset(what, who, maybe_context)?;
+		Pallet::<T>::note_outflow(&asset_id, outflow);
+		Ok(withdrawn)
+	}
+
+	fn internal_transfer_asset(
+		what: &Asset,
+		from: &Location,
+		to: &Location,
+		context: &XcmContext,
+	) -> Result<AssetsInHolding, XcmError> {
+		let (asset_id, amount) = Matcher::matches_fungibles(what)?;
+		let outflow = Pallet::<T>::ensure_within_limit(&asset_id, amount)?;
+		let transferred = Inner::internal_transfer_asset(what, from, to, context)?;
+		Pallet::<T>::note_outflow(&asset_id, outflow);
+		Ok(transferred)
+	}
+}
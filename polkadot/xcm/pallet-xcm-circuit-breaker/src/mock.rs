@@ -0,0 +1,125 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use crate as pallet_xcm_circuit_breaker;
+
+use frame_support::{
+	construct_runtime, derive_impl, parameter_types,
+	traits::{ConstU128, ConstU64},
+	weights::constants::RocksDbWeight,
+};
+use frame_system::EnsureRoot;
+use sp_runtime::BuildStorage;
+use xcm::prelude::*;
+use xcm_executor::{
+	traits::{Error as MatchError, MatchesFungibles, TransactAsset},
+	AssetsInHolding,
+};
+
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+
+construct_runtime! {
+	pub enum TestRuntime
+	{
+		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
+		XcmCircuitBreaker: pallet_xcm_circuit_breaker::{Pallet, Call, Storage, Event<T>},
+	}
+}
+
+parameter_types! {
+	pub static WithdrawalFails: bool = false;
+	pub static Withdrawn: Vec<(Location, Asset)> = vec![];
+	pub static Transferred: Vec<(Location, Location, Asset)> = vec![];
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for TestRuntime {
+	type Block = Block;
+	type DbWeight = RocksDbWeight;
+}
+
+impl pallet_xcm_circuit_breaker::Config for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
+	type AssetId = u32;
+	type Balance = u128;
+	type DailyLimit = ConstU128<25>;
+	type BlocksPerDay = ConstU64<10>;
+	type ResetOrigin = EnsureRoot<u64>;
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
+/// Matches the local asset (`Here`) as the asset `0` and the relay chain asset (`Parent`) as the
+/// asset `1`.
+pub struct TestMatcher;
+impl MatchesFungibles<u32, u128> for TestMatcher {
+	fn matches_fungibles(a: &Asset) -> Result<(u32, u128), MatchError> {
+		let asset_id = match a.id.0.unpack() {
+			(0, []) => 0,
+			(1, []) => 1,
+			_ => return Err(MatchError::AssetNotHandled),
+		};
+		match a.fun {
+			Fungible(amount) => Ok((asset_id, amount)),
+			NonFungible(_) => Err(MatchError::AssetNotHandled),
+		}
+	}
+}
+
+/// Transactor that records all withdrawals in the `Withdrawn` and all transfers in the
+/// `Transferred`. Withdrawals fail if `WithdrawalFails` is set.
+pub struct TestTransactor;
+impl TransactAsset for TestTransactor {
+	fn withdraw_asset(
+		what: &Asset,
+		who: &Location,
+		_maybe_context: Option<&XcmContext>,
+	) -> Result<AssetsInHolding, XcmError> {
+		if WithdrawalFails::get() {
+			return Err(XcmError::FailedToTransactAsset("withdrawal has failed"))
+		}
+		Withdrawn::mutate(|withdrawn| withdrawn.push((who.clone(), what.clone())));
+		Ok(what.clone().into())
+	}
+
+	fn internal_transfer_asset(
+		what: &Asset,
+		from: &Location,
+		to: &Location,
+		_context: &XcmContext,
+	) -> Result<AssetsInHolding, XcmError> {
+		Transferred::mutate(|transferred| {
+			transferred.push((from.clone(), to.clone(), what.clone()))
+		});
+		Ok(what.clone().into())
+	}
+}
+
+/// Return test externalities to use in tests.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::<TestRuntime>::default().build_storage().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Run pallet test.
+pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
+	new_test_ext().execute_with(test)
+}
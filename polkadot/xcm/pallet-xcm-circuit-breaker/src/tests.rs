@@ -0,0 +1,147 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::*;
+
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+use sp_runtime::DispatchError;
+use xcm_executor::traits::Error as MatchError;
+
+type Transactor = CircuitBreakerFungiblesAdapter<TestRuntime, TestTransactor, TestMatcher>;
+
+fn sibling_account() -> Location {
+	Location::new(1, [Parachain(42), AccountId32 { network: None, id: [3u8; 32] }])
+}
+
+fn withdraw(asset: impl Into<AssetId>, amount: u128) -> Result<AssetsInHolding, XcmError> {
+	Transactor::withdraw_asset(&(asset, amount).into(), &sibling_account(), None)
+}
+
+fn transfer(amount: u128) -> Result<AssetsInHolding, XcmError> {
+	let context = XcmContext { origin: None, message_id: XcmHash::default(), topic: None };
+	Transactor::internal_transfer_asset(
+		&(Here, amount).into(),
+		&sibling_account(),
+		&Location::new(0, [AccountId32 { network: None, id: [7u8; 32] }]),
+		&context,
+	)
+}
+
+const TRIGGERED: XcmError = XcmError::FailedToTransactAsset("circuit breaker is triggered");
+
+#[test]
+fn outflow_below_limit_is_allowed() {
+	run_test(|| {
+		assert_ok!(withdraw(Here, 10));
+		assert_ok!(withdraw(Here, 15));
+		assert_eq!(XcmCircuitBreaker::daily_outflow(&0), 25);
+		assert_eq!(
+			Withdrawn::get(),
+			vec![(sibling_account(), (Here, 10).into()), (sibling_account(), (Here, 15).into())],
+		);
+	});
+}
+
+#[test]
+fn outflow_above_limit_is_rejected() {
+	run_test(|| {
+		assert_ok!(withdraw(Here, 20));
+		assert_eq!(withdraw(Here, 10), Err(TRIGGERED));
+		assert_eq!(XcmCircuitBreaker::daily_outflow(&0), 20);
+		assert_eq!(Withdrawn::get().len(), 1);
+
+		// internal transfers are counted as well
+		assert_ok!(transfer(5));
+		assert_eq!(transfer(1), Err(TRIGGERED));
+		assert_eq!(XcmCircuitBreaker::daily_outflow(&0), 25);
+		assert_eq!(Transferred::get().len(), 1);
+
+		// every asset has its own limit
+		assert_ok!(withdraw(Parent, 25));
+		assert_eq!(XcmCircuitBreaker::daily_outflow(&1), 25);
+	});
+}
+
+#[test]
+fn failed_outflow_is_not_counted() {
+	run_test(|| {
+		WithdrawalFails::set(true);
+		assert!(withdraw(Here, 10).is_err());
+		assert_eq!(XcmCircuitBreaker::daily_outflow(&0), 0);
+		assert!(!DailyOutflow::<TestRuntime>::contains_key(0));
+	});
+}
+
+#[test]
+fn unknown_assets_are_rejected() {
+	run_test(|| {
+		assert_eq!(withdraw(Parachain(1000), 10), Err(MatchError::AssetNotHandled.into()));
+		assert!(Withdrawn::get().is_empty());
+	});
+}
+
+#[test]
+fn circuit_breaker_may_be_reset_by_reset_origin() {
+	run_test(|| {
+		assert_ok!(withdraw(Here, 20));
+		assert_eq!(withdraw(Here, 10), Err(TRIGGERED));
+
+		assert_noop!(
+			XcmCircuitBreaker::reset_circuit_breaker(RawOrigin::Signed(1).into(), 0),
+			DispatchError::BadOrigin,
+		);
+		assert_ok!(XcmCircuitBreaker::reset_circuit_breaker(RawOrigin::Root.into(), 0));
+		System::assert_last_event(Event::CircuitBreakerReset { asset_id: 0 }.into());
+		assert_eq!(XcmCircuitBreaker::daily_outflow(&0), 0);
+		assert_ok!(withdraw(Here, 10));
+	});
+}
+
+#[test]
+fn circuit_breaker_is_reset_at_new_day() {
+	run_test(|| {
+		assert_ok!(withdraw(Here, 20));
+		assert_eq!(withdraw(Here, 10), Err(TRIGGERED));
+
+		System::set_block_number(9);
+		assert_eq!(withdraw(Here, 10), Err(TRIGGERED));
+
+		System::set_block_number(10);
+		assert_eq!(XcmCircuitBreaker::daily_outflow(&0), 0);
+		assert_ok!(withdraw(Here, 10));
+		assert_eq!(XcmCircuitBreaker::daily_outflow(&0), 10);
+		assert_eq!(DailyOutflow::<TestRuntime>::get(0), Some((1, 10)));
+	});
+}
+
+#[test]
+fn storage_access_is_registered_as_block_weight() {
+	run_test(|| {
+		let weight_before = frame_system::BlockWeight::<TestRuntime>::get().total();
+		assert_ok!(withdraw(Here, 10));
+		assert_eq!(
+			frame_system::BlockWeight::<TestRuntime>::get()
+				.total()
+				.saturating_sub(weight_before),
+			<() as WeightInfo>::ensure_within_limit()
+				.saturating_add(<() as WeightInfo>::note_outflow()),
+		);
+	});
+}
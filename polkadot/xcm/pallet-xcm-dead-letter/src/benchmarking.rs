@@ -0,0 +1,50 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for the XCM dead letter pallet.
+
+use super::*;
+use frame_benchmarking::v1::{benchmarks, BenchmarkError};
+use frame_support::traits::EnsureOrigin;
+use sp_std::vec;
+
+fn store_dead_letter<T: Config>() -> u32 {
+	let index = NextDeadLetterIndex::<T>::get();
+	let destination = Location::new(1, [Parachain(1000)]);
+	let message = Xcm(vec![ClearOrigin; 8]);
+	assert!(Pallet::<T>::store_dead_letter(destination, message));
+	index
+}
+
+benchmarks! {
+	// The weight of the delivery itself is `Config::DeliveryWeight` and is added separately.
+	retry_dead_letter {
+		let index = store_dead_letter::<T>();
+		let origin = T::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+	}: _<T::RuntimeOrigin>(origin, index)
+
+	discard_dead_letter {
+		let index = store_dead_letter::<T>();
+		let origin = T::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+	}: _<T::RuntimeOrigin>(origin, index)
+	verify {
+		assert!(DeadLetters::<T>::get(index).is_none());
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::TestRuntime);
+}
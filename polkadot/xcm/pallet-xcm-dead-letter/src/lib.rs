@@ -0,0 +1,226 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Pallet that stores XCM messages which the router has failed to deliver.
+//!
+//! The [`DeadLetterQueueRouter`] may be used as the XCM router of the runtime. It wraps the
+//! `Config::Router` and, when the delivery of the validated message fails, stores the message
+//! in the [`DeadLetters`], so that it may be recovered later, e.g. when the halted bridge is
+//! resumed. Dead letters are never retried automatically: the `Config::AdminOrigin` may retry
+//! them using the `retry_dead_letter` call or drop them using the `discard_dead_letter` call.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Encode;
+use frame_support::traits::Get;
+use sp_std::{marker::PhantomData, prelude::*};
+use xcm::{prelude::*, VersionedLocation, VersionedXcm};
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+mod mock;
+mod tests;
+pub mod weights;
+
+/// The target that will be used when publishing logs related to this pallet.
+pub const LOG_TARGET: &str = "xcm::dead-letter";
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Router that delivers messages.
+		type Router: SendXcm;
+		/// Maximal number of messages in the dead letter queue.
+		#[pallet::constant]
+		type MaxDeadLetters: Get<u32>;
+		/// Origin that may retry and discard dead letters.
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Weight of the single delivery, performed by the `Router`.
+		#[pallet::constant]
+		type DeliveryWeight: Get<Weight>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Try to deliver the dead letter at `index` again, using the `Config::Router`.
+		///
+		/// The dead letter is removed from the queue if it has been delivered. Otherwise it
+		/// stays in the queue and may be retried later.
+		#[pallet::call_index(0)]
+		#[pallet::weight(
+			T::WeightInfo::retry_dead_letter().saturating_add(T::DeliveryWeight::get())
+		)]
+		pub fn retry_dead_letter(origin: OriginFor<T>, index: u32) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			let (destination, message) =
+				DeadLetters::<T>::get(index).ok_or(Error::<T>::UnknownDeadLetter)?;
+			let destination =
+				Location::try_from(destination).map_err(|_| Error::<T>::UnsupportedVersion)?;
+			let message =
+				Xcm::<()>::try_from(message).map_err(|_| Error::<T>::UnsupportedVersion)?;
+
+			let success = match send_xcm::<T::Router>(destination, message) {
+				Ok(_) => {
+					DeadLetters::<T>::remove(index);
+					true
+				},
+				Err(e) => {
+					log::debug!(
+						target: LOG_TARGET,
+						"Failed to retry dead letter {}: {:?}",
+						index,
+						e,
+					);
+					false
+				},
+			};
+			Self::deposit_event(Event::MessageRetried { index, success });
+			Ok(())
+		}
+
+		/// Remove the dead letter at `index` from the queue without delivering it.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::discard_dead_letter())]
+		pub fn discard_dead_letter(origin: OriginFor<T>, index: u32) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			DeadLetters::<T>::take(index).ok_or(Error::<T>::UnknownDeadLetter)?;
+			Self::deposit_event(Event::DeadLetterDiscarded { index });
+			Ok(())
+		}
+	}
+
+	/// Messages that the `Config::Router` has failed to deliver, by their index.
+	#[pallet::storage]
+	pub type DeadLetters<T: Config> =
+		CountedStorageMap<_, Twox64Concat, u32, (VersionedLocation, VersionedXcm<()>)>;
+
+	/// Index of the next message, stored in the `DeadLetters`.
+	#[pallet::storage]
+	pub type NextDeadLetterIndex<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The message to the `destination` has not been delivered and is stored at `index`.
+		MessageDeadLettered { index: u32, destination: VersionedLocation },
+		/// The delivery of the dead letter at `index` has been retried.
+		MessageRetried { index: u32, success: bool },
+		/// The dead letter at `index` has been discarded.
+		DeadLetterDiscarded { index: u32 },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// There is no dead letter with given index.
+		UnknownDeadLetter,
+		/// The dead letter uses the XCM version that is no longer supported.
+		UnsupportedVersion,
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Store the message that the `Config::Router` has failed to deliver.
+	///
+	/// Returns false if the queue is full.
+	fn store_dead_letter(destination: Location, message: Xcm<()>) -> bool {
+		if DeadLetters::<T>::count() >= T::MaxDeadLetters::get() {
+			return false
+		}
+
+		let index = NextDeadLetterIndex::<T>::mutate(|next_index| {
+			let index = *next_index;
+			*next_index = next_index.wrapping_add(1);
+			index
+		});
+		let destination = VersionedLocation::from(destination);
+		DeadLetters::<T>::insert(index, (destination.clone(), VersionedXcm::from(message)));
+		Self::deposit_event(Event::MessageDeadLettered { index, destination });
+		true
+	}
+}
+
+/// Router that stores messages which the `Config::Router` has failed to deliver in the
+/// [`DeadLetters`].
+///
+/// The delivery error is always returned to the caller, even if the message has been stored.
+/// Note that the dead letter is only kept if the caller doesn't roll back storage changes on
+/// that error. If there are already `Config::MaxDeadLetters` messages in the queue, the
+/// message is not stored. Messages that are not accepted by the `Config::Router` during
+/// validation are rejected with the same error.
+pub struct DeadLetterQueueRouter<T>(PhantomData<T>);
+impl<T: Config> SendXcm for DeadLetterQueueRouter<T> {
+	type Ticket = (<T::Router as SendXcm>::Ticket, Location, Xcm<()>);
+
+	fn validate(
+		destination: &mut Option<Location>,
+		message: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket> {
+		// the inner router takes the arguments, so we need to keep a copy for the dead letter
+		let dead_letter_destination = destination.clone().ok_or(SendError::MissingArgument)?;
+		let dead_letter_message = message.clone().ok_or(SendError::MissingArgument)?;
+		let (ticket, price) = T::Router::validate(destination, message)?;
+		Ok(((ticket, dead_letter_destination, dead_letter_message), price))
+	}
+
+	fn deliver(ticket: Self::Ticket) -> Result<XcmHash, SendError> {
+		let (ticket, destination, message) = ticket;
+		let error = match T::Router::deliver(ticket) {
+			Ok(hash) => return Ok(hash),
+			Err(error) => error,
+		};
+
+		let hash = match message.last() {
+			Some(SetTopic(id)) => *id,
+			_ => message.using_encoded(sp_io::hashing::blake2_256),
+		};
+		if Pallet::<T>::store_dead_letter(destination.clone(), message) {
+			log::warn!(
+				target: LOG_TARGET,
+				"Failed to deliver message {:?} to {:?}: {:?}. Stored it in the dead letter queue",
+				hash,
+				destination,
+				error,
+			);
+		} else {
+			log::warn!(
+				target: LOG_TARGET,
+				"Failed to deliver message {:?} to {:?}: {:?}. The dead letter queue is full",
+				hash,
+				destination,
+				error,
+			);
+		}
+
+		Err(error)
+	}
+}
@@ -0,0 +1,90 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use crate as pallet_xcm_dead_letter;
+
+use frame_support::{
+	construct_runtime, derive_impl, parameter_types, traits::ConstU32, weights::Weight,
+};
+use frame_system::EnsureRoot;
+use sp_runtime::BuildStorage;
+use xcm::prelude::*;
+
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+
+construct_runtime! {
+	pub enum TestRuntime
+	{
+		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
+		XcmDeadLetter: pallet_xcm_dead_letter::{Pallet, Call, Storage, Event<T>},
+	}
+}
+
+parameter_types! {
+	pub static DeliveryFails: bool = false;
+	pub static SentXcm: Vec<(Location, Xcm<()>)> = vec![];
+	pub DeliveryWeight: Weight = Weight::from_parts(1_000, 0);
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for TestRuntime {
+	type Block = Block;
+}
+
+impl pallet_xcm_dead_letter::Config for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
+	type Router = UnreliableRouter;
+	type MaxDeadLetters = ConstU32<2>;
+	type AdminOrigin = EnsureRoot<u64>;
+	type DeliveryWeight = DeliveryWeight;
+	type WeightInfo = ();
+}
+
+/// Router that records all messages in the `SentXcm`, unless `DeliveryFails` is set.
+pub struct UnreliableRouter;
+impl SendXcm for UnreliableRouter {
+	type Ticket = (Location, Xcm<()>);
+
+	fn validate(
+		dest: &mut Option<Location>,
+		msg: &mut Option<Xcm<()>>,
+	) -> SendResult<(Location, Xcm<()>)> {
+		Ok(((dest.take().unwrap(), msg.take().unwrap()), Assets::new()))
+	}
+
+	fn deliver(ticket: (Location, Xcm<()>)) -> Result<XcmHash, SendError> {
+		if DeliveryFails::get() {
+			return Err(SendError::Transport("bridge is halted"))
+		}
+		SentXcm::mutate(|sent| sent.push(ticket));
+		Ok([0u8; 32])
+	}
+}
+
+/// Return test externalities to use in tests.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::<TestRuntime>::default().build_storage().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Run pallet test.
+pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
+	new_test_ext().execute_with(test)
+}
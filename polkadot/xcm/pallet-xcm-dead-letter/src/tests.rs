@@ -0,0 +1,122 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::*;
+
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+use sp_runtime::DispatchError;
+
+type Router = DeadLetterQueueRouter<TestRuntime>;
+
+const HALTED: SendError = SendError::Transport("bridge is halted");
+
+fn dead_letter(message: Xcm<()>) -> (VersionedLocation, VersionedXcm<()>) {
+	(VersionedLocation::from(Location::parent()), VersionedXcm::from(message))
+}
+
+#[test]
+fn router_stores_failed_messages_and_returns_error() {
+	run_test(|| {
+		assert_ok!(send_xcm::<Router>(Parent.into(), Xcm(vec![ClearOrigin])));
+		assert_eq!(DeadLetters::<TestRuntime>::count(), 0);
+
+		DeliveryFails::set(true);
+		let message = Xcm(vec![ClearOrigin, SetTopic([1; 32])]);
+		assert_eq!(send_xcm::<Router>(Parent.into(), message.clone()), Err(HALTED));
+		assert_eq!(DeadLetters::<TestRuntime>::get(0), Some(dead_letter(message)));
+		System::assert_last_event(
+			Event::MessageDeadLettered { index: 0, destination: Location::parent().into() }.into(),
+		);
+	});
+}
+
+#[test]
+fn router_does_not_store_messages_when_queue_is_full() {
+	run_test(|| {
+		DeliveryFails::set(true);
+		assert_eq!(send_xcm::<Router>(Parent.into(), Xcm(vec![ClearOrigin])), Err(HALTED));
+		assert_eq!(send_xcm::<Router>(Parent.into(), Xcm(vec![ClearOrigin])), Err(HALTED));
+		assert_eq!(send_xcm::<Router>(Parent.into(), Xcm(vec![ClearOrigin])), Err(HALTED));
+		assert_eq!(DeadLetters::<TestRuntime>::count(), 2);
+
+		// the space is freed once a message is discarded
+		assert_ok!(XcmDeadLetter::discard_dead_letter(RawOrigin::Root.into(), 0));
+		assert_eq!(send_xcm::<Router>(Parent.into(), Xcm(vec![ClearOrigin])), Err(HALTED));
+		assert!(DeadLetters::<TestRuntime>::contains_key(2));
+	});
+}
+
+#[test]
+fn retry_dead_letter_delivers_message() {
+	run_test(|| {
+		DeliveryFails::set(true);
+		let message = Xcm(vec![ClearOrigin]);
+		assert_eq!(send_xcm::<Router>(Parent.into(), message.clone()), Err(HALTED));
+
+		// the bridge is still halted, so the message stays in the queue
+		assert_ok!(XcmDeadLetter::retry_dead_letter(RawOrigin::Root.into(), 0));
+		System::assert_last_event(Event::MessageRetried { index: 0, success: false }.into());
+		assert_eq!(DeadLetters::<TestRuntime>::count(), 1);
+
+		DeliveryFails::set(false);
+		assert_ok!(XcmDeadLetter::retry_dead_letter(RawOrigin::Root.into(), 0));
+		System::assert_last_event(Event::MessageRetried { index: 0, success: true }.into());
+		assert_eq!(SentXcm::get(), vec![(Parent.into(), message)]);
+		assert_eq!(DeadLetters::<TestRuntime>::count(), 0);
+		assert_noop!(
+			XcmDeadLetter::retry_dead_letter(RawOrigin::Root.into(), 0),
+			Error::<TestRuntime>::UnknownDeadLetter,
+		);
+	});
+}
+
+#[test]
+fn discard_dead_letter_removes_message() {
+	run_test(|| {
+		DeliveryFails::set(true);
+		assert_eq!(send_xcm::<Router>(Parent.into(), Xcm(vec![ClearOrigin])), Err(HALTED));
+
+		assert_ok!(XcmDeadLetter::discard_dead_letter(RawOrigin::Root.into(), 0));
+		System::assert_last_event(Event::DeadLetterDiscarded { index: 0 }.into());
+		assert_eq!(DeadLetters::<TestRuntime>::count(), 0);
+		assert_noop!(
+			XcmDeadLetter::discard_dead_letter(RawOrigin::Root.into(), 0),
+			Error::<TestRuntime>::UnknownDeadLetter,
+		);
+	});
+}
+
+#[test]
+fn only_admin_origin_may_retry_and_discard_dead_letters() {
+	run_test(|| {
+		DeliveryFails::set(true);
+		assert_eq!(send_xcm::<Router>(Parent.into(), Xcm(vec![ClearOrigin])), Err(HALTED));
+
+		assert_noop!(
+			XcmDeadLetter::retry_dead_letter(RawOrigin::Signed(1).into(), 0),
+			DispatchError::BadOrigin,
+		);
+		assert_noop!(
+			XcmDeadLetter::discard_dead_letter(RawOrigin::Signed(1).into(), 0),
+			DispatchError::BadOrigin,
+		);
+		assert_eq!(DeadLetters::<TestRuntime>::count(), 1);
+	});
+}
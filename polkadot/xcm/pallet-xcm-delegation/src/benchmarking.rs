@@ -0,0 +1,73 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for the XCM delegation pallet.
+
+use super::*;
+use frame_benchmarking::v1::{benchmarks, whitelisted_caller, BenchmarkError};
+use frame_support::traits::EnsureOrigin;
+use frame_system::RawOrigin;
+
+fn delegate(index: u32) -> Location {
+	Location::new(1, [Parachain(index), AccountId32 { network: None, id: [1u8; 32] }])
+}
+
+benchmarks! {
+	delegate_xcm_origin {
+		let origin = T::DelegatorOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let delegator = T::DelegatorOrigin::ensure_origin(origin.clone())
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let expires_at = frame_system::Pallet::<T>::block_number() + 10u32.into();
+	}: _<T::RuntimeOrigin>(origin, Box::new(delegate(0).into()), expires_at)
+	verify {
+		assert_eq!(Pallet::<T>::delegation_expiry(&delegator, &delegate(0)), Some(expires_at));
+	}
+
+	revoke_xcm_origin {
+		let origin = T::DelegatorOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let delegator = T::DelegatorOrigin::ensure_origin(origin.clone())
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let expires_at = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		Delegations::<T>::insert(
+			VersionedLocation::from(delegator.clone()),
+			VersionedLocation::from(delegate(0)),
+			expires_at,
+		);
+	}: _<T::RuntimeOrigin>(origin, Box::new(delegate(0).into()))
+	verify {
+		assert_eq!(Pallet::<T>::delegation_expiry(&delegator, &delegate(0)), None);
+	}
+
+	clean_expired_delegations {
+		let n in 0 .. 100;
+
+		let delegator = VersionedLocation::from(delegate(0));
+		let delegations = (0 .. n)
+			.map(|i| (delegator.clone(), VersionedLocation::from(delegate(i + 1))))
+			.collect::<Vec<_>>();
+		for (delegator, delegate) in &delegations {
+			Delegations::<T>::insert(delegator, delegate, frame_system::Pallet::<T>::block_number());
+		}
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), delegations)
+	verify {
+		assert_eq!(Delegations::<T>::iter().count(), 0);
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::TestRuntime);
+}
@@ -0,0 +1,203 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Pallet that allows accounts to delegate their XCM origin to other locations.
+//!
+//! The delegator registers the delegation to some location (e.g. another parachain) using the
+//! `delegate_xcm_origin` call. Until the delegation expires or is revoked with the
+//! `revoke_xcm_origin` call, the delegate may act on behalf of the delegator: the
+//! [`AllowDelegatedOrigin`] may be used as (a part of) the `Aliasers` of the XCM executor
+//! configuration, so the delegate may alias into the delegator origin with the `AliasOrigin`
+//! instruction. Expired delegations may be removed by anyone using the
+//! `clean_expired_delegations` call.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::traits::ContainsPair;
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_std::{marker::PhantomData, prelude::*};
+use xcm::{prelude::*, VersionedLocation};
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+mod mock;
+mod tests;
+pub mod weights;
+
+/// The target that will be used when publishing logs related to this pallet.
+pub const LOG_TARGET: &str = "xcm::delegation";
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Origin that may delegate its location. Returns the delegator location on success.
+		type DelegatorOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Location>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Allow the `delegate` to act on behalf of the origin until the `expires_at` block.
+		///
+		/// The existing delegation to the same `delegate` is overwritten.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::delegate_xcm_origin())]
+		pub fn delegate_xcm_origin(
+			origin: OriginFor<T>,
+			delegate: Box<VersionedLocation>,
+			expires_at: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let delegator = T::DelegatorOrigin::ensure_origin(origin)?;
+			let delegate = Location::try_from(*delegate).map_err(|_| Error::<T>::BadVersion)?;
+			ensure!(
+				expires_at > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::AlreadyExpired
+			);
+
+			Delegations::<T>::insert(
+				VersionedLocation::from(delegator.clone()),
+				VersionedLocation::from(delegate.clone()),
+				expires_at,
+			);
+			Self::deposit_event(Event::OriginDelegated { delegator, delegate, expires_at });
+			Ok(())
+		}
+
+		/// Revoke the delegation of the origin to the `delegate`.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::revoke_xcm_origin())]
+		pub fn revoke_xcm_origin(
+			origin: OriginFor<T>,
+			delegate: Box<VersionedLocation>,
+		) -> DispatchResult {
+			let delegator = T::DelegatorOrigin::ensure_origin(origin)?;
+			let delegate = Location::try_from(*delegate).map_err(|_| Error::<T>::BadVersion)?;
+
+			Delegations::<T>::take(
+				VersionedLocation::from(delegator.clone()),
+				VersionedLocation::from(delegate.clone()),
+			)
+			.ok_or(Error::<T>::NotDelegated)?;
+			Self::deposit_event(Event::DelegationRevoked { delegator, delegate });
+			Ok(())
+		}
+
+		/// Remove given `(delegator, delegate)` delegations, if they have expired.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::clean_expired_delegations(delegations.len() as u32))]
+		pub fn clean_expired_delegations(
+			origin: OriginFor<T>,
+			delegations: Vec<(VersionedLocation, VersionedLocation)>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let mut removed = 0u32;
+			for (delegator, delegate) in delegations {
+				match Delegations::<T>::get(&delegator, &delegate) {
+					Some(expires_at) if expires_at <= now => {
+						Delegations::<T>::remove(&delegator, &delegate);
+						removed += 1;
+					},
+					_ => (),
+				}
+			}
+			Self::deposit_event(Event::ExpiredDelegationsCleaned { count: removed });
+			Ok(())
+		}
+	}
+
+	/// Block, starting from which the delegation of the delegator origin to the delegate is no
+	/// longer valid, by the delegator and delegate locations.
+	#[pallet::storage]
+	pub type Delegations<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		VersionedLocation,
+		Blake2_128Concat,
+		VersionedLocation,
+		BlockNumberFor<T>,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The `delegator` origin has been delegated to the `delegate` until `expires_at`.
+		OriginDelegated { delegator: Location, delegate: Location, expires_at: BlockNumberFor<T> },
+		/// The delegation of the `delegator` origin to the `delegate` has been revoked.
+		DelegationRevoked { delegator: Location, delegate: Location },
+		/// The `count` expired delegations have been removed.
+		ExpiredDelegationsCleaned { count: u32 },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The location uses the XCM version that is not supported.
+		BadVersion,
+		/// The expiry block of the delegation has already been reached.
+		AlreadyExpired,
+		/// There is no delegation to the delegate.
+		NotDelegated,
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Returns the block, starting from which the delegation of the `delegator` origin to the
+	/// `delegate` is no longer valid. Returns `None` if there's no such delegation.
+	pub fn delegation_expiry(
+		delegator: &Location,
+		delegate: &Location,
+	) -> Option<BlockNumberFor<T>> {
+		Delegations::<T>::get(
+			VersionedLocation::from(delegator.clone()),
+			VersionedLocation::from(delegate.clone()),
+		)
+	}
+}
+
+/// Allows the delegate origin to alias into the delegator origin (with the `AliasOrigin`
+/// instruction) if the delegator has registered a non-expired delegation in the pallet.
+///
+/// Once the origin is aliased, the delegator origin is converted by the regular origin
+/// converters.
+pub struct AllowDelegatedOrigin<T>(PhantomData<T>);
+impl<T: Config> ContainsPair<Location, Location> for AllowDelegatedOrigin<T> {
+	fn contains(delegate: &Location, delegator: &Location) -> bool {
+		log::trace!(
+			target: LOG_TARGET,
+			"AllowDelegatedOrigin delegate: {:?}, delegator: {:?}",
+			delegate, delegator,
+		);
+		match Pallet::<T>::delegation_expiry(delegator, delegate) {
+			Some(expires_at) => expires_at > frame_system::Pallet::<T>::block_number(),
+			None => false,
+		}
+	}
+}
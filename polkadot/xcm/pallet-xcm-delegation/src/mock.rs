@@ -0,0 +1,74 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use crate as pallet_xcm_delegation;
+
+use frame_support::{construct_runtime, derive_impl, traits::MapSuccess};
+use frame_system::EnsureSigned;
+use sp_runtime::{traits::Morph, BuildStorage};
+use xcm::prelude::*;
+
+pub type AccountId = u64;
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+
+construct_runtime! {
+	pub enum TestRuntime
+	{
+		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
+		XcmDelegation: pallet_xcm_delegation::{Pallet, Call, Storage, Event<T>},
+	}
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for TestRuntime {
+	type Block = Block;
+}
+
+impl pallet_xcm_delegation::Config for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
+	type DelegatorOrigin = MapSuccess<EnsureSigned<AccountId>, AccountToLocation>;
+	type WeightInfo = ();
+}
+
+/// Converts the account id into the `AccountIndex64` location.
+pub struct AccountToLocation;
+impl Morph<AccountId> for AccountToLocation {
+	type Outcome = Location;
+
+	fn morph(account: AccountId) -> Location {
+		account_location(account)
+	}
+}
+
+/// Location of the test account.
+pub fn account_location(account: AccountId) -> Location {
+	AccountIndex64 { network: None, index: account }.into()
+}
+
+/// Return test externalities to use in tests.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::<TestRuntime>::default().build_storage().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Run pallet test.
+pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
+	new_test_ext().execute_with(test)
+}
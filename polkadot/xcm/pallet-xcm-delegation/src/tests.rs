@@ -0,0 +1,142 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::*;
+
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+use sp_runtime::{DispatchError, DispatchResult};
+
+type Aliaser = AllowDelegatedOrigin<TestRuntime>;
+
+const DELEGATOR: AccountId = 1;
+
+fn delegator() -> Location {
+	account_location(DELEGATOR)
+}
+
+fn delegate() -> Location {
+	(Parent, Parachain(2)).into()
+}
+
+fn delegate_until(delegate: Location, expires_at: u64) -> DispatchResult {
+	XcmDelegation::delegate_xcm_origin(
+		RawOrigin::Signed(DELEGATOR).into(),
+		Box::new(delegate.into()),
+		expires_at,
+	)
+}
+
+fn revoke(delegate: Location) -> DispatchResult {
+	XcmDelegation::revoke_xcm_origin(RawOrigin::Signed(DELEGATOR).into(), Box::new(delegate.into()))
+}
+
+#[test]
+fn delegation_allows_aliasing_until_it_expires() {
+	run_test(|| {
+		assert!(!Aliaser::contains(&delegate(), &delegator()));
+
+		// delegation is granted
+		assert_ok!(delegate_until(delegate(), 10));
+		System::assert_last_event(
+			Event::OriginDelegated { delegator: delegator(), delegate: delegate(), expires_at: 10 }
+				.into(),
+		);
+		assert!(Aliaser::contains(&delegate(), &delegator()));
+		// but only in one direction and only to the delegate
+		assert!(!Aliaser::contains(&delegator(), &delegate()));
+		assert!(!Aliaser::contains(&(Parent, Parachain(3)).into(), &delegator()));
+
+		// delegation may be used until it expires
+		System::set_block_number(9);
+		assert!(Aliaser::contains(&delegate(), &delegator()));
+		System::set_block_number(10);
+		assert!(!Aliaser::contains(&delegate(), &delegator()));
+	});
+}
+
+#[test]
+fn expired_delegation_may_not_be_granted() {
+	run_test(|| {
+		System::set_block_number(10);
+		assert_noop!(delegate_until(delegate(), 10), Error::<TestRuntime>::AlreadyExpired);
+	});
+}
+
+#[test]
+fn delegation_may_be_revoked_and_granted_again() {
+	run_test(|| {
+		assert_ok!(delegate_until(delegate(), 10));
+		assert!(Aliaser::contains(&delegate(), &delegator()));
+
+		assert_ok!(revoke(delegate()));
+		System::assert_last_event(
+			Event::DelegationRevoked { delegator: delegator(), delegate: delegate() }.into(),
+		);
+		assert!(!Aliaser::contains(&delegate(), &delegator()));
+		assert_noop!(revoke(delegate()), Error::<TestRuntime>::NotDelegated);
+
+		// the same delegate may be granted delegation again after revocation
+		assert_ok!(delegate_until(delegate(), 20));
+		System::set_block_number(15);
+		assert!(Aliaser::contains(&delegate(), &delegator()));
+	});
+}
+
+#[test]
+fn expired_delegations_may_be_cleaned() {
+	run_test(|| {
+		let other_delegate: Location = (Parent, Parachain(3)).into();
+		assert_ok!(delegate_until(delegate(), 10));
+		assert_ok!(delegate_until(other_delegate.clone(), 20));
+
+		let delegations = vec![
+			(delegator().into(), delegate().into()),
+			(delegator().into(), other_delegate.clone().into()),
+		];
+		assert_ok!(XcmDelegation::clean_expired_delegations(
+			RawOrigin::Signed(2).into(),
+			delegations.clone(),
+		));
+		System::assert_last_event(Event::ExpiredDelegationsCleaned { count: 0 }.into());
+
+		System::set_block_number(10);
+		assert_ok!(XcmDelegation::clean_expired_delegations(
+			RawOrigin::Signed(2).into(),
+			delegations,
+		));
+		System::assert_last_event(Event::ExpiredDelegationsCleaned { count: 1 }.into());
+		assert_eq!(XcmDelegation::delegation_expiry(&delegator(), &delegate()), None);
+		assert_eq!(XcmDelegation::delegation_expiry(&delegator(), &other_delegate), Some(20));
+	});
+}
+
+#[test]
+fn only_delegator_origin_may_delegate() {
+	run_test(|| {
+		assert_noop!(
+			XcmDelegation::delegate_xcm_origin(
+				RawOrigin::Root.into(),
+				Box::new(delegate().into()),
+				10,
+			),
+			DispatchError::BadOrigin,
+		);
+	});
+}
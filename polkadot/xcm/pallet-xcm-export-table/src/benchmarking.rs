@@ -0,0 +1,56 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for the XCM export table pallet.
+
+use super::*;
+use frame_benchmarking::v1::{benchmarks, BenchmarkError};
+use frame_support::traits::EnsureOrigin;
+
+const NETWORK: NetworkId = NetworkId::ByGenesis([1u8; 32]);
+
+/// Returns the export route to the `NETWORK` with `f` items in the remote location filter.
+fn export_route(f: u32) -> NetworkExportTableItem {
+	NetworkExportTableItem::new(
+		NETWORK,
+		Some((0..f).map(|i| [Parachain(i)].into()).collect()),
+		Location::new(1, [Parachain(1002)]),
+		Some((Parent, 1_000_000u128).into()),
+	)
+}
+
+benchmarks! {
+	add_export_route {
+		let f in 0 .. 100;
+
+		let origin = T::UpdateOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+	}: _<T::RuntimeOrigin>(origin, NETWORK, Box::new(export_route(f)))
+	verify {
+		assert_eq!(ExportRoutes::<T>::get(NETWORK), Some(export_route(f)));
+	}
+
+	remove_export_route {
+		ExportRoutes::<T>::insert(NETWORK, export_route(0));
+		let origin = T::UpdateOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+	}: _<T::RuntimeOrigin>(origin, NETWORK)
+	verify {
+		assert_eq!(ExportRoutes::<T>::get(NETWORK), None);
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::TestRuntime);
+}
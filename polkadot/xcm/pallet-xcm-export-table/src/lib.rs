@@ -0,0 +1,144 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Pallet keeping the bridge exporters that may be updated without a runtime upgrade.
+//!
+//! The static `NetworkExportTable` is built from compile-time constants, so adding a bridge route
+//! requires a runtime upgrade. The [`NetworkExportTableDynamic`] may be used as the `ExporterFor`
+//! of the bridge routers instead: it checks the static `Config::StaticTable` first and falls back
+//! to the [`ExportRoutes`] map, which the `Config::UpdateOrigin` may update using the
+//! `add_export_route` and `remove_export_route` calls. Routes that have been removed from the
+//! static table may be copied to the map with the [`migration::MigrateStaticToDynamic`].
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::traits::Get;
+use sp_std::{boxed::Box, marker::PhantomData, prelude::*};
+use xcm::prelude::*;
+use xcm_builder::{ExporterFor, NetworkExportTable, NetworkExportTableItem};
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod migration;
+mod mock;
+mod tests;
+pub mod weights;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Exporters that are known at compile time. They take precedence over the
+		/// [`ExportRoutes`].
+		type StaticTable: Get<Vec<NetworkExportTableItem>>;
+		/// Origin that may add and remove export routes.
+		type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Add or replace the exporter for the `network`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::add_export_route(
+			item.remote_location_filter.as_ref().map_or(0, |filter| filter.len() as u32),
+		))]
+		pub fn add_export_route(
+			origin: OriginFor<T>,
+			network: NetworkId,
+			item: Box<NetworkExportTableItem>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(item.remote_network == network, Error::<T>::NetworkMismatch);
+
+			ExportRoutes::<T>::insert(network, *item);
+			Self::deposit_event(Event::ExportRouteAdded { network });
+			Ok(())
+		}
+
+		/// Remove the exporter for the `network`.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::remove_export_route())]
+		pub fn remove_export_route(origin: OriginFor<T>, network: NetworkId) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			ExportRoutes::<T>::take(network).ok_or(Error::<T>::UnknownExportRoute)?;
+			Self::deposit_event(Event::ExportRouteRemoved { network });
+			Ok(())
+		}
+	}
+
+	/// Exporters that have been added at runtime, by their remote network.
+	#[pallet::storage]
+	pub type ExportRoutes<T: Config> =
+		StorageMap<_, Blake2_128Concat, NetworkId, NetworkExportTableItem>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The exporter for the `network` has been added or replaced.
+		ExportRouteAdded { network: NetworkId },
+		/// The exporter for the `network` has been removed.
+		ExportRouteRemoved { network: NetworkId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The exporter is configured for another remote network.
+		NetworkMismatch,
+		/// There is no exporter for the network.
+		UnknownExportRoute,
+	}
+}
+
+/// An adapter for the implementation of `ExporterFor`, which extends the `Config::StaticTable`
+/// with the [`ExportRoutes`].
+///
+/// The static table is checked first and always takes precedence. If it has no matching
+/// exporter, the single runtime-configured exporter for the requested `network` is used (if any).
+pub struct NetworkExportTableDynamic<T>(PhantomData<T>);
+impl<T: Config> ExporterFor for NetworkExportTableDynamic<T> {
+	fn exporter_for(
+		network: &NetworkId,
+		remote_location: &InteriorLocation,
+		message: &Xcm<()>,
+	) -> Option<(Location, Option<Asset>)> {
+		NetworkExportTable::<T::StaticTable>::exporter_for(network, remote_location, message)
+			.or_else(|| {
+				ExportRoutes::<T>::get(network)
+					.filter(|item| item.matches(network, remote_location))
+					.map(|item| (item.bridge, item.payment))
+			})
+	}
+}
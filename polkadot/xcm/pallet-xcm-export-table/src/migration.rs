@@ -0,0 +1,75 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Migrations of the export table.
+
+use crate::{Config, ExportRoutes, Pallet};
+use frame_support::{
+	migrations::VersionedMigration,
+	traits::{Get, OnRuntimeUpgrade},
+	weights::Weight,
+};
+use sp_std::{marker::PhantomData, prelude::*};
+use xcm_builder::NetworkExportTableItem;
+
+/// Copies exporters from the `OldStatic` table to the [`ExportRoutes`].
+///
+/// Items that are still in the `Config::StaticTable` are skipped, because the static table takes
+/// precedence anyway. Since there may be only one runtime-configured exporter per network, only
+/// the first remaining `OldStatic` item of every network is copied. Networks that already have an
+/// exporter are left untouched.
+///
+/// This migration doesn't check the storage version, so it should only be used as a part of the
+/// [`MigrateStaticToDynamic`].
+pub struct VersionUncheckedMigrateStaticToDynamic<T, OldStatic>(PhantomData<(T, OldStatic)>);
+impl<T: Config, OldStatic: Get<Vec<NetworkExportTableItem>>> OnRuntimeUpgrade
+	for VersionUncheckedMigrateStaticToDynamic<T, OldStatic>
+{
+	fn on_runtime_upgrade() -> Weight {
+		let current_items = T::StaticTable::get();
+		let (mut checked, mut migrated) = (0u64, 0u64);
+		for item in OldStatic::get() {
+			if current_items.contains(&item) {
+				continue
+			}
+			checked += 1;
+			if ExportRoutes::<T>::contains_key(item.remote_network) {
+				continue
+			}
+			ExportRoutes::<T>::insert(item.remote_network, item);
+			migrated += 1;
+		}
+
+		log::info!(
+			target: "runtime::xcm-export-table",
+			"Migrated {} of {} removed static exporters to the dynamic export table",
+			migrated,
+			checked,
+		);
+		T::DbWeight::get().reads_writes(checked, migrated)
+	}
+}
+
+/// [`VersionUncheckedMigrateStaticToDynamic`], which is only executed once, when the pallet is
+/// added to the runtime with the storage version `0`. The storage version is set to `1`
+/// afterwards.
+pub type MigrateStaticToDynamic<T, OldStatic> = VersionedMigration<
+	0,
+	1,
+	VersionUncheckedMigrateStaticToDynamic<T, OldStatic>,
+	Pallet<T>,
+	<T as frame_system::Config>::DbWeight,
+>;
@@ -0,0 +1,70 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use crate as pallet_xcm_export_table;
+
+use frame_support::{construct_runtime, derive_impl, parameter_types};
+use frame_system::EnsureRoot;
+use sp_runtime::BuildStorage;
+use xcm::prelude::*;
+use xcm_builder::NetworkExportTableItem;
+
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+
+construct_runtime! {
+	pub enum TestRuntime
+	{
+		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
+		XcmExportTable: pallet_xcm_export_table::{Pallet, Call, Storage, Event<T>},
+	}
+}
+
+parameter_types! {
+	pub StaticNetwork: NetworkId = ByGenesis([0; 32]);
+	pub DynamicNetwork: NetworkId = ByGenesis([1; 32]);
+	pub StaticBridge: Location = Location::new(1, [Parachain(1234)]);
+	pub DynamicBridge: Location = Location::new(1, [Parachain(4321)]);
+	pub StaticBridgeTable: Vec<NetworkExportTableItem> = vec![
+		NetworkExportTableItem::new(StaticNetwork::get(), None, StaticBridge::get(), None),
+	];
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for TestRuntime {
+	type Block = Block;
+}
+
+impl pallet_xcm_export_table::Config for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
+	type StaticTable = StaticBridgeTable;
+	type UpdateOrigin = EnsureRoot<u64>;
+	type WeightInfo = ();
+}
+
+/// Return test externalities to use in tests.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::<TestRuntime>::default().build_storage().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Run pallet test.
+pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
+	new_test_ext().execute_with(test)
+}
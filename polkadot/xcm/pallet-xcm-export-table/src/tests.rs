@@ -0,0 +1,163 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::*;
+
+use frame_support::{
+	assert_noop, assert_ok, parameter_types,
+	traits::{OnRuntimeUpgrade, StorageVersion},
+};
+use frame_system::RawOrigin;
+use sp_runtime::{DispatchError, DispatchResult};
+
+type DynamicTable = NetworkExportTableDynamic<TestRuntime>;
+
+fn dynamic_route(network: NetworkId) -> NetworkExportTableItem {
+	NetworkExportTableItem::new(network, None, DynamicBridge::get(), None)
+}
+
+fn add_route(item: NetworkExportTableItem) -> DispatchResult {
+	XcmExportTable::add_export_route(RawOrigin::Root.into(), item.remote_network, Box::new(item))
+}
+
+#[test]
+fn network_export_table_dynamic_adds_and_removes_routes() {
+	run_test(|| {
+		let network = DynamicNetwork::get();
+		let remote_location: InteriorLocation = [Parachain(1000)].into();
+		assert_eq!(DynamicTable::exporter_for(&network, &remote_location, &Xcm::new()), None);
+
+		// only `UpdateOrigin` may add routes
+		assert_noop!(
+			XcmExportTable::add_export_route(
+				RawOrigin::Signed(1).into(),
+				network,
+				Box::new(dynamic_route(network)),
+			),
+			DispatchError::BadOrigin,
+		);
+		// route must be configured for the same network
+		assert_noop!(
+			XcmExportTable::add_export_route(
+				RawOrigin::Root.into(),
+				network,
+				Box::new(dynamic_route(StaticNetwork::get())),
+			),
+			Error::<TestRuntime>::NetworkMismatch,
+		);
+
+		assert_ok!(add_route(dynamic_route(network)));
+		System::assert_last_event(Event::ExportRouteAdded { network }.into());
+		assert_eq!(ExportRoutes::<TestRuntime>::get(network), Some(dynamic_route(network)));
+		assert_eq!(
+			DynamicTable::exporter_for(&network, &remote_location, &Xcm::new()),
+			Some((DynamicBridge::get(), None)),
+		);
+
+		// only `UpdateOrigin` may remove routes
+		assert_noop!(
+			XcmExportTable::remove_export_route(RawOrigin::Signed(1).into(), network),
+			DispatchError::BadOrigin,
+		);
+		assert_ok!(XcmExportTable::remove_export_route(RawOrigin::Root.into(), network));
+		System::assert_last_event(Event::ExportRouteRemoved { network }.into());
+		assert_noop!(
+			XcmExportTable::remove_export_route(RawOrigin::Root.into(), network),
+			Error::<TestRuntime>::UnknownExportRoute,
+		);
+		assert_eq!(DynamicTable::exporter_for(&network, &remote_location, &Xcm::new()), None);
+	});
+}
+
+#[test]
+fn network_export_table_dynamic_prefers_static_routes() {
+	run_test(|| {
+		let network = StaticNetwork::get();
+		let remote_location: InteriorLocation = [Parachain(1000)].into();
+
+		assert_ok!(add_route(dynamic_route(network)));
+		assert_eq!(
+			DynamicTable::exporter_for(&network, &remote_location, &Xcm::new()),
+			Some((StaticBridge::get(), None)),
+		);
+	});
+}
+
+#[test]
+fn network_export_table_dynamic_respects_remote_location_filter() {
+	run_test(|| {
+		let network = DynamicNetwork::get();
+		let allowed: InteriorLocation = [Parachain(1000)].into();
+		let route = NetworkExportTableItem::new(
+			network,
+			Some(vec![allowed.clone()]),
+			DynamicBridge::get(),
+			None,
+		);
+
+		assert_ok!(add_route(route));
+		assert_eq!(
+			DynamicTable::exporter_for(&network, &allowed, &Xcm::new()),
+			Some((DynamicBridge::get(), None)),
+		);
+		assert_eq!(
+			DynamicTable::exporter_for(&network, &[Parachain(2000)].into(), &Xcm::new()),
+			None,
+		);
+	});
+}
+
+#[test]
+fn migrate_static_to_dynamic_works() {
+	run_test(|| {
+		// existing dynamic routes are not overwritten
+		let network = DynamicNetwork::get();
+		assert_ok!(add_route(dynamic_route(network)));
+
+		let removed_network = ByGenesis([2; 32]);
+		parameter_types! {
+			pub OldBridgeTable: Vec<NetworkExportTableItem> = vec![
+				NetworkExportTableItem::new(StaticNetwork::get(), None, StaticBridge::get(), None),
+				NetworkExportTableItem::new(DynamicNetwork::get(), None, StaticBridge::get(), None),
+				NetworkExportTableItem::new(ByGenesis([2; 32]), None, StaticBridge::get(), None),
+				NetworkExportTableItem::new(ByGenesis([2; 32]), None, DynamicBridge::get(), None),
+			];
+		}
+		type Migration = migration::MigrateStaticToDynamic<TestRuntime, OldBridgeTable>;
+
+		// the pallet has just been added to the runtime
+		StorageVersion::new(0).put::<XcmExportTable>();
+		Migration::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get::<XcmExportTable>(), StorageVersion::new(1));
+
+		// the item that is still in the static table is not copied
+		assert_eq!(ExportRoutes::<TestRuntime>::get(StaticNetwork::get()), None);
+		assert_eq!(ExportRoutes::<TestRuntime>::get(network), Some(dynamic_route(network)));
+		// only the first item of the removed network is copied
+		assert_eq!(
+			ExportRoutes::<TestRuntime>::get(removed_network),
+			Some(OldBridgeTable::get()[2].clone()),
+		);
+
+		// the migration is only executed once
+		assert_ok!(XcmExportTable::remove_export_route(RawOrigin::Root.into(), removed_network));
+		Migration::on_runtime_upgrade();
+		assert_eq!(ExportRoutes::<TestRuntime>::get(removed_network), None);
+	});
+}
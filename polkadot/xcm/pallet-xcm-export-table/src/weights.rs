@@ -0,0 +1,93 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for `pallet_xcm_export_table`
+//!
+//! The weights in this file have not been generated by the benchmark CLI yet. They are estimated
+//! from the storage accesses of the benchmarks in `benchmarking.rs` and must be replaced by the
+//! output of the command below, once the pallet is part of a benchmarked runtime.
+
+// Command:
+// target/production/polkadot
+// benchmark
+// pallet
+// --steps=50
+// --repeat=20
+// --extrinsic=*
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --pallet=pallet_xcm_export_table
+// --chain=rococo-dev
+// --header=./polkadot/file_header.txt
+// --output=./polkadot/xcm/pallet-xcm-export-table/src/weights.rs
+// --template=./substrate/.maintain/frame-weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for `pallet_xcm_export_table`.
+pub trait WeightInfo {
+	fn add_export_route(f: u32, ) -> Weight;
+	fn remove_export_route() -> Weight;
+}
+
+/// Weights for `pallet_xcm_export_table` using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `XcmExportTable::ExportRoutes` (r:0 w:1)
+	/// Proof: `XcmExportTable::ExportRoutes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `f` is `[0, 100]`.
+	fn add_export_route(f: u32, ) -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(13_000_000, 0)
+			.saturating_add(Weight::from_parts(45_000, 0).saturating_mul(f.into()))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `XcmExportTable::ExportRoutes` (r:1 w:1)
+	/// Proof: `XcmExportTable::ExportRoutes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn remove_export_route() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(16_000_000, 3630)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	/// Storage: `XcmExportTable::ExportRoutes` (r:0 w:1)
+	/// Proof: `XcmExportTable::ExportRoutes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `f` is `[0, 100]`.
+	fn add_export_route(f: u32, ) -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(13_000_000, 0)
+			.saturating_add(Weight::from_parts(45_000, 0).saturating_mul(f.into()))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `XcmExportTable::ExportRoutes` (r:1 w:1)
+	/// Proof: `XcmExportTable::ExportRoutes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn remove_export_route() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(16_000_000, 3630)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
@@ -0,0 +1,35 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for the XCM location registry pallet.
+
+use super::*;
+use frame_benchmarking::v1::{benchmarks, whitelisted_caller, BenchmarkError};
+use frame_system::RawOrigin;
+
+benchmarks! {
+	register_location {
+		let location = T::BenchmarkHelper::location();
+		let account = T::LocationConverter::convert_location(&location)
+			.ok_or(BenchmarkError::Weightless)?;
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), Box::new(location.into()))
+	verify {
+		assert!(RegisteredLocations::<T>::contains_key(&account));
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::TestRuntime);
+}
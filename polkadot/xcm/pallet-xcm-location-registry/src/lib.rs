@@ -0,0 +1,146 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Pallet that keeps the locations which have been converted into accounts.
+//!
+//! Hashing location converters, like the `HashedDescription`, can't be reversed. Anyone may
+//! register a location with the `register_location` call: the pallet converts it into an account
+//! using the `Config::LocationConverter` and stores it in the [`RegisteredLocations`] map. The
+//! [`RegisteredLocationConverter`] may then be used wherever a [`ConvertLocationBidirectional`]
+//! converter is expected: it converts locations with the `Config::LocationConverter` and looks up
+//! the registered locations of accounts.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::traits::Get;
+use sp_std::{boxed::Box, marker::PhantomData};
+use xcm::{prelude::*, VersionedLocation};
+use xcm_builder::ConvertLocationBidirectional;
+use xcm_executor::traits::ConvertLocation;
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+mod mock;
+mod tests;
+pub mod weights;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Converter of the registered locations into accounts.
+		type LocationConverter: ConvertLocation<Self::AccountId>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+		/// Helper for creating the registered locations in benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper;
+	}
+
+	/// Helper for creating the registered locations in benchmarks.
+	#[cfg(feature = "runtime-benchmarks")]
+	pub trait BenchmarkHelper {
+		/// Returns a location that the `Config::LocationConverter` converts into an account.
+		fn location() -> Location;
+	}
+	#[cfg(feature = "runtime-benchmarks")]
+	impl BenchmarkHelper for () {
+		fn location() -> Location {
+			Location::new(1, [Parachain(1000), AccountId32 { network: None, id: [1u8; 32] }])
+		}
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register the `location` for the reverse lookup of the account it is converted into.
+		///
+		/// Registered locations are never overwritten.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::register_location())]
+		pub fn register_location(
+			origin: OriginFor<T>,
+			location: Box<VersionedLocation>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let location =
+				Location::try_from(*location).map_err(|_| Error::<T>::UnsupportedVersion)?;
+			let account = T::LocationConverter::convert_location(&location)
+				.ok_or(Error::<T>::UnconvertibleLocation)?;
+			ensure!(
+				!RegisteredLocations::<T>::contains_key(&account),
+				Error::<T>::AlreadyRegistered
+			);
+
+			let location = VersionedLocation::from(location);
+			RegisteredLocations::<T>::insert(&account, location.clone());
+			Self::deposit_event(Event::LocationRegistered { account, location });
+			Ok(())
+		}
+	}
+
+	/// Registered locations, by the account they are converted into.
+	#[pallet::storage]
+	pub type RegisteredLocations<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, VersionedLocation>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The `location`, converted into the `account`, has been registered.
+		LocationRegistered { account: T::AccountId, location: VersionedLocation },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The location uses the XCM version that is not supported.
+		UnsupportedVersion,
+		/// The location can't be converted into an account.
+		UnconvertibleLocation,
+		/// The location of the account has already been registered.
+		AlreadyRegistered,
+	}
+}
+
+/// Location converter that uses the `Config::LocationConverter` and looks up the locations of
+/// accounts in the [`RegisteredLocations`].
+pub struct RegisteredLocationConverter<T>(PhantomData<T>);
+impl<T: Config> ConvertLocation<T::AccountId> for RegisteredLocationConverter<T> {
+	fn convert_location(location: &Location) -> Option<T::AccountId> {
+		T::LocationConverter::convert_location(location)
+	}
+}
+impl<T: Config> ConvertLocationBidirectional<T::AccountId> for RegisteredLocationConverter<T> {
+	fn account_to_location(account: &T::AccountId) -> Option<Location> {
+		let location = Location::try_from(RegisteredLocations::<T>::get(account)?).ok()?;
+
+		// never trust the registered location blindly - it must be converted into the same account
+		(T::LocationConverter::convert_location(&location).as_ref() == Some(account))
+			.then_some(location)
+	}
+}
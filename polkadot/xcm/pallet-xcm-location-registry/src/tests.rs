@@ -0,0 +1,100 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::*;
+
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+use sp_runtime::{AccountId32, DispatchError, DispatchResult};
+
+type Converter = RegisteredLocationConverter<TestRuntime>;
+
+fn signed() -> RuntimeOrigin {
+	RawOrigin::Signed(AccountId32::new([1; 32])).into()
+}
+
+fn register(location: Location) -> DispatchResult {
+	XcmLocationRegistry::register_location(signed(), Box::new(location.into()))
+}
+
+#[test]
+fn account_to_location_works_after_registration() {
+	run_test(|| {
+		let location = Location::new(1, [Parachain(1000), PalletInstance(50)]);
+		let account = Converter::convert_location(&location).unwrap();
+		assert_eq!(Converter::account_to_location(&account), None);
+
+		assert_ok!(register(location.clone()));
+		assert_eq!(Converter::account_to_location(&account), Some(location.clone()));
+		System::assert_last_event(
+			Event::LocationRegistered { account, location: location.into() }.into(),
+		);
+	});
+}
+
+#[test]
+fn account_to_location_returns_none_for_unknown_account() {
+	run_test(|| {
+		assert_ok!(register(Location::new(1, [Parachain(1000), PalletInstance(50)])));
+		assert_eq!(Converter::account_to_location(&AccountId32::new([42; 32])), None);
+	});
+}
+
+#[test]
+fn account_to_location_rejects_location_converted_into_other_account() {
+	run_test(|| {
+		let location = Location::new(1, [Parachain(1000), PalletInstance(50)]);
+		let other_location = Location::new(1, [Parachain(2000), PalletInstance(50)]);
+		let account = Converter::convert_location(&location).unwrap();
+
+		// pretend that the other location collides with the registered one
+		RegisteredLocations::<TestRuntime>::insert(
+			&account,
+			VersionedLocation::from(other_location),
+		);
+		assert_eq!(Converter::account_to_location(&account), None);
+
+		// the registered location is never overwritten
+		assert_noop!(register(location), Error::<TestRuntime>::AlreadyRegistered);
+		assert_eq!(Converter::account_to_location(&account), None);
+	});
+}
+
+#[test]
+fn register_location_rejects_unconvertible_location() {
+	run_test(|| {
+		assert_noop!(
+			register(Location::new(2, [GlobalConsensus(Kusama)])),
+			Error::<TestRuntime>::UnconvertibleLocation,
+		);
+	});
+}
+
+#[test]
+fn register_location_requires_signed_origin() {
+	run_test(|| {
+		assert_noop!(
+			XcmLocationRegistry::register_location(
+				RawOrigin::None.into(),
+				Box::new(Location::new(1, [Parachain(1000)]).into()),
+			),
+			DispatchError::BadOrigin,
+		);
+	});
+}
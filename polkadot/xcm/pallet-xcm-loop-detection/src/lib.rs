@@ -0,0 +1,225 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Pallet detecting XCM messages that are routed in a loop.
+//!
+//! In complex multi-hop setups, a misconfigured routing table may cause XCM messages to be
+//! forwarded back and forth between two bridges indefinitely. The [`LoopDetectionRouter`] wraps
+//! other router and remembers topics of messages, delivered by it, in the `InFlightXcmTopics`
+//! value, until the response arrives or the topic expires. Messages with topics that are already
+//! in flight are rejected.
+//!
+//! The pallet is instantiable, so several routers may track their topics independently - every
+//! router needs its own pallet instance.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::{dispatch::DispatchClass, traits::Get};
+use frame_system::unique;
+use sp_runtime::traits::Saturating;
+use sp_std::{marker::PhantomData, result::Result};
+use xcm::prelude::*;
+use xcm_builder::CONGESTED;
+use xcm_executor::traits::OnResponse;
+
+pub use pallet::*;
+
+mod mock;
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config {
+		/// Maximal number of topics that may be in flight at the same time.
+		#[pallet::constant]
+		type MaxInFlight: Get<u32>;
+		/// Number of blocks after which the topic is no longer considered in flight, even if the
+		/// response to the message has never arrived.
+		#[pallet::constant]
+		type Timeout: Get<BlockNumberFor<Self>>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+			let mut topics = InFlightXcmTopics::<T, I>::get();
+			let topics_count = topics.len();
+			topics.retain(|_, expires_at| *expires_at > n);
+			if topics.len() == topics_count {
+				return T::DbWeight::get().reads(1)
+			}
+
+			InFlightXcmTopics::<T, I>::put(topics);
+			T::DbWeight::get().reads_writes(1, 1)
+		}
+	}
+
+	/// Topics of the messages, sent by the router, mapped to the block at which they expire.
+	///
+	/// Expired topics are removed at the beginning of every block.
+	#[pallet::storage]
+	pub type InFlightXcmTopics<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BoundedBTreeMap<XcmHash, BlockNumberFor<T>, T::MaxInFlight>, ValueQuery>;
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Returns true if the message with given `topic` has been sent and is waiting for response.
+	pub fn is_in_flight(topic: &XcmHash) -> bool {
+		InFlightXcmTopics::<T, I>::get().contains_key(topic)
+	}
+
+	/// Note that the response to the message with given `topic` has arrived.
+	///
+	/// The cost of accessing the topics is registered as the mandatory weight of the block.
+	pub fn note_response(topic: &XcmHash) {
+		let db_weight = T::DbWeight::get();
+		frame_system::Pallet::<T>::register_extra_weight_unchecked(
+			db_weight.reads(1),
+			DispatchClass::Mandatory,
+		);
+
+		let mut topics = InFlightXcmTopics::<T, I>::get();
+		if topics.remove(topic).is_some() {
+			frame_system::Pallet::<T>::register_extra_weight_unchecked(
+				db_weight.writes(1),
+				DispatchClass::Mandatory,
+			);
+			InFlightXcmTopics::<T, I>::put(topics);
+		}
+	}
+}
+
+/// Router that detects messages which are routed in a loop.
+///
+/// Like the `WithUniqueTopic`, it appends the `SetTopic` instruction to the message, unless the
+/// message already ends with it. The topic of every delivered message is kept in the
+/// `InFlightXcmTopics` until the response to it arrives (see `Pallet::note_response` and
+/// `ClearInFlightTopicOnResponse`) or until it expires after the `Config::Timeout` blocks. If
+/// the message with the in-flight topic is sent again, e.g. because two misconfigured bridges are
+/// forwarding it back and forth, it is rejected with the `SendError::Transport("loop detected")`
+/// error.
+///
+/// The loops are told apart from the new messages by their topics only. The topics appended by
+/// the router are unique, but the topics set before the message reaches the router must be unique
+/// too, or two messages with the same topic, sent within `Config::Timeout` blocks, are taken for a
+/// loop. E.g. the topics of `WithTopicSource` with `ContentHashTopic` are the same for identical
+/// messages, so `WithUniqueTopic` should set the topics in front of the router instead.
+///
+/// Messages are rejected with the `SendError::Transport(CONGESTED)` error if there are already
+/// `Config::MaxInFlight` topics in flight.
+///
+/// The cost of accessing the topics is registered as the mandatory weight of the block.
+pub struct LoopDetectionRouter<T, I, Inner>(PhantomData<(T, I, Inner)>);
+impl<T: Config<I>, I: 'static, Inner: SendXcm> SendXcm for LoopDetectionRouter<T, I, Inner> {
+	type Ticket = (Inner::Ticket, XcmHash);
+
+	fn validate(
+		destination: &mut Option<Location>,
+		message: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket> {
+		let mut message = message.take().ok_or(SendError::MissingArgument)?;
+		let topic = if let Some(SetTopic(id)) = message.last() {
+			*id
+		} else {
+			let topic = unique(&message);
+			message.0.push(SetTopic(topic));
+			topic
+		};
+
+		// we read the topics
+		frame_system::Pallet::<T>::register_extra_weight_unchecked(
+			T::DbWeight::get().reads(1),
+			DispatchClass::Mandatory,
+		);
+		let in_flight_topics = InFlightXcmTopics::<T, I>::get();
+		if in_flight_topics.contains_key(&topic) {
+			log::trace!(
+				target: "xcm::routing",
+				"LoopDetectionRouter rejected message with topic {:?}: loop detected",
+				topic,
+			);
+			return Err(SendError::Transport("loop detected"))
+		}
+		if in_flight_topics.len() >= T::MaxInFlight::get() as usize {
+			log::trace!(
+				target: "xcm::routing",
+				"LoopDetectionRouter rejected message with topic {:?}: too many topics in flight",
+				topic,
+			);
+			return Err(SendError::Transport(CONGESTED))
+		}
+
+		let (ticket, price) = Inner::validate(destination, &mut Some(message))?;
+		Ok(((ticket, topic), price))
+	}
+
+	fn deliver(ticket: Self::Ticket) -> Result<XcmHash, SendError> {
+		let (ticket, topic) = ticket;
+		let hash = Inner::deliver(ticket)?;
+
+		// we update the topics
+		frame_system::Pallet::<T>::register_extra_weight_unchecked(
+			T::DbWeight::get().reads_writes(1, 1),
+			DispatchClass::Mandatory,
+		);
+		let expires_at =
+			frame_system::Pallet::<T>::block_number().saturating_add(T::Timeout::get());
+		InFlightXcmTopics::<T, I>::mutate(|topics| {
+			if topics.try_insert(topic, expires_at).is_err() {
+				log::warn!(
+					target: "xcm::routing",
+					"LoopDetectionRouter failed to track topic {:?}: too many topics in flight",
+					topic,
+				);
+			}
+		});
+
+		Ok(hash)
+	}
+}
+
+/// Response handler that notes the response to the message, sent by the `LoopDetectionRouter`
+/// with given pallet instance, and then passes it to the `Inner` handler.
+pub struct ClearInFlightTopicOnResponse<T, I, Inner>(PhantomData<(T, I, Inner)>);
+impl<T: Config<I>, I: 'static, Inner: OnResponse> OnResponse
+	for ClearInFlightTopicOnResponse<T, I, Inner>
+{
+	fn expecting_response(origin: &Location, query_id: u64, querier: Option<&Location>) -> bool {
+		Inner::expecting_response(origin, query_id, querier)
+	}
+
+	fn on_response(
+		origin: &Location,
+		query_id: u64,
+		querier: Option<&Location>,
+		response: Response,
+		max_weight: Weight,
+		context: &XcmContext,
+	) -> Weight {
+		if let Some(topic) = context.topic {
+			Pallet::<T, I>::note_response(&topic);
+		}
+		Inner::on_response(origin, query_id, querier, response, max_weight, context)
+	}
+}
@@ -0,0 +1,184 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::*;
+
+use frame_support::{assert_ok, traits::Hooks};
+use xcm_builder::{ContentHashTopic, SourceTopic};
+
+type BridgePallet = Pallet<TestRuntime, Instance1>;
+type OtherBridgePallet = Pallet<TestRuntime, Instance2>;
+
+/// Sends the message using the `BridgeRouter` and returns the sent message along with its topic.
+fn send_using_bridge_router(message: Xcm<()>) -> (Xcm<()>, XcmHash) {
+	assert_ok!(send_xcm::<BridgeRouter>(Parent.into(), message));
+	let (_, message) = SentXcm::get().pop().unwrap();
+	let topic = match message.last() {
+		Some(SetTopic(topic)) => *topic,
+		_ => panic!("message must end with the SetTopic instruction"),
+	};
+	(message, topic)
+}
+
+#[test]
+fn loop_detection_router_detects_message_sent_in_loop() {
+	run_test(|| {
+		let (message, topic) = send_using_bridge_router(Xcm(vec![ClearOrigin]));
+		assert!(BridgePallet::is_in_flight(&topic));
+
+		// the other bridge forwards the message back to us
+		assert_ok!(send_xcm::<OtherBridgeRouter>(Parent.into(), message.clone()));
+		let (_, message) = SentXcm::get().pop().unwrap();
+
+		// and we are trying to send it again
+		assert_eq!(
+			send_xcm::<BridgeRouter>(Parent.into(), message.clone()),
+			Err(SendError::Transport("loop detected")),
+		);
+		assert_eq!(SentXcm::get().len(), 2);
+
+		// once the response arrives, the message may be sent again
+		BridgePallet::note_response(&topic);
+		assert!(!BridgePallet::is_in_flight(&topic));
+		assert_ok!(send_xcm::<BridgeRouter>(Parent.into(), message));
+		assert_eq!(SentXcm::get().len(), 3);
+	});
+}
+
+#[test]
+fn loop_detection_router_expires_topics() {
+	run_test(|| {
+		let (message, topic) = send_using_bridge_router(Xcm(vec![ClearOrigin]));
+
+		// the response never arrives, but the topic expires after the timeout
+		assert_eq!(BridgePallet::on_initialize(10), TestDbWeight::get().reads(1));
+		assert!(BridgePallet::is_in_flight(&topic));
+		assert_eq!(BridgePallet::on_initialize(11), TestDbWeight::get().reads_writes(1, 1));
+		assert!(!BridgePallet::is_in_flight(&topic));
+		assert_ok!(send_xcm::<BridgeRouter>(Parent.into(), message));
+	});
+}
+
+#[test]
+fn loop_detection_router_takes_identical_messages_with_the_same_topic_for_loop() {
+	run_test(|| {
+		// the topics appended by the router are unique, so identical messages may be sent
+		let (_, topic) = send_using_bridge_router(Xcm(vec![ClearOrigin]));
+		let (_, other_topic) = send_using_bridge_router(Xcm(vec![ClearOrigin]));
+		assert_ne!(topic, other_topic);
+		BridgePallet::note_response(&topic);
+		BridgePallet::note_response(&other_topic);
+
+		// but the topics derived from the content of the message are the same
+		let topic = ContentHashTopic::source_topic(&Xcm::<()>(vec![ClearOrigin]));
+		let message = Xcm(vec![ClearOrigin, SetTopic(topic)]);
+		assert_ok!(send_xcm::<BridgeRouter>(Parent.into(), message.clone()));
+		assert_eq!(
+			send_xcm::<BridgeRouter>(Parent.into(), message),
+			Err(SendError::Transport("loop detected")),
+		);
+	});
+}
+
+#[test]
+fn loop_detection_routers_track_topics_independently() {
+	run_test(|| {
+		let (message, topic) = send_using_bridge_router(Xcm(vec![ClearOrigin]));
+
+		assert!(!OtherBridgePallet::is_in_flight(&topic));
+		assert_ok!(send_xcm::<OtherBridgeRouter>(Parent.into(), message));
+		assert!(BridgePallet::is_in_flight(&topic));
+		assert!(OtherBridgePallet::is_in_flight(&topic));
+
+		OtherBridgePallet::note_response(&topic);
+		assert!(BridgePallet::is_in_flight(&topic));
+		assert!(!OtherBridgePallet::is_in_flight(&topic));
+	});
+}
+
+#[test]
+fn loop_detection_router_limits_number_of_topics_in_flight() {
+	run_test(|| {
+		assert_ok!(send_xcm::<BridgeRouter>(Parent.into(), Xcm(vec![SetTopic([1; 32])])));
+		assert_ok!(send_xcm::<BridgeRouter>(Parent.into(), Xcm(vec![SetTopic([2; 32])])));
+		assert_eq!(
+			send_xcm::<BridgeRouter>(Parent.into(), Xcm(vec![SetTopic([3; 32])])),
+			Err(SendError::Transport(CONGESTED)),
+		);
+		assert_eq!(SentXcm::get().len(), 2);
+
+		// expired topics don't count towards the limit
+		BridgePallet::on_initialize(11);
+		assert_ok!(send_xcm::<BridgeRouter>(Parent.into(), Xcm(vec![SetTopic([3; 32])])));
+		assert_eq!(SentXcm::get().len(), 3);
+	});
+}
+
+#[test]
+fn loop_detection_router_registers_mandatory_weight() {
+	run_test(|| {
+		let db_weight = TestDbWeight::get();
+		let (_, topic) = send_using_bridge_router(Xcm(vec![ClearOrigin]));
+		BridgePallet::note_response(&topic);
+		assert_eq!(
+			frame_system::Pallet::<TestRuntime>::block_weight().get(DispatchClass::Mandatory),
+			&db_weight
+				.reads(1)
+				.saturating_add(db_weight.reads_writes(1, 1))
+				.saturating_add(db_weight.reads_writes(1, 1)),
+		);
+	});
+}
+
+#[test]
+fn clear_in_flight_topic_on_response_works() {
+	type ResponseHandler = ClearInFlightTopicOnResponse<TestRuntime, Instance1, ()>;
+
+	run_test(|| {
+		assert_ok!(send_xcm::<BridgeRouter>(Parent.into(), Xcm(vec![SetTopic([1; 32])])));
+		assert!(BridgePallet::is_in_flight(&[1; 32]));
+
+		let context = XcmContext { origin: None, message_id: [0; 32], topic: Some([1; 32]) };
+		ResponseHandler::on_response(
+			&Parent.into(),
+			0,
+			None,
+			Response::Null,
+			Weight::zero(),
+			&context,
+		);
+		assert!(!BridgePallet::is_in_flight(&[1; 32]));
+	});
+}
+
+#[test]
+fn loop_detection_router_triggers_fallback_router() {
+	type Router = xcm_builder::WithFallbackRouter<BridgeRouter, OtherBridgeRouter>;
+
+	run_test(|| {
+		assert_ok!(send_xcm::<Router>(Parent.into(), Xcm(vec![SetTopic([1; 32])])));
+		assert_ok!(send_xcm::<Router>(Parent.into(), Xcm(vec![SetTopic([2; 32])])));
+
+		// there are too many topics in flight, so the message is sent using the fallback router
+		assert_ok!(send_xcm::<Router>(Parent.into(), Xcm(vec![SetTopic([3; 32])])));
+		assert!(!BridgePallet::is_in_flight(&[3; 32]));
+		assert!(OtherBridgePallet::is_in_flight(&[3; 32]));
+		assert_eq!(SentXcm::get().len(), 3);
+	});
+}
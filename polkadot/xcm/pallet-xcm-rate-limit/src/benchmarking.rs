@@ -0,0 +1,75 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for the XCM rate limit pallet.
+
+use super::*;
+use frame_benchmarking::v1::benchmarks;
+use frame_support::traits::Hooks;
+use sp_runtime::traits::Zero;
+use sp_std::vec;
+
+/// Barrier that accepts all messages.
+pub struct AllowAll;
+impl ShouldExecute for AllowAll {
+	fn should_execute<RuntimeCall>(
+		_origin: &Location,
+		_instructions: &mut [Instruction<RuntimeCall>],
+		_max_weight: Weight,
+		_properties: &mut Properties,
+	) -> Result<(), ProcessMessageError> {
+		Ok(())
+	}
+}
+
+fn para(id: u32) -> Location {
+	Location::new(1, [Parachain(id)])
+}
+
+benchmarks! {
+	on_initialize {
+		let n in 0 .. T::MaxOriginsPerBlock::get();
+		for i in 0 .. n {
+			MessagesThisBlock::<T>::insert(origin_key(&para(i)), T::MaxMessagesPerOrigin::get());
+		}
+	}: {
+		Pallet::<T>::on_initialize(Zero::zero());
+	}
+	verify {
+		assert_eq!(MessagesThisBlock::<T>::count(), 0);
+	}
+
+	should_execute {
+		// the message comes from a new origin, while all other origins but one are known
+		for i in 1 .. T::MaxOriginsPerBlock::get() {
+			MessagesThisBlock::<T>::insert(origin_key(&para(i)), 1);
+		}
+		let origin = para(0);
+		let mut message = Xcm::<()>(vec![ClearOrigin]);
+	}: {
+		let _ = AllowXcmRateLimit::<T, AllowAll>::should_execute(
+			&origin,
+			message.inner_mut(),
+			Weight::zero(),
+			&mut Properties { weight_credit: Weight::zero(), message_id: None },
+		);
+	}
+	verify {
+		assert_eq!(Pallet::<T>::messages_in_current_block(&origin), 1);
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::TestRuntime);
+}
@@ -0,0 +1,145 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Pallet limiting the number of XCM messages, executed by every origin in a single block.
+//!
+//! Without such limit, a single origin (e.g. a sibling parachain) may saturate the block weight
+//! budget with XCM messages. The [`AllowXcmRateLimit`] barrier wraps other barriers and counts
+//! messages, accepted by them, in the `MessagesThisBlock` map, which is cleared at the beginning
+//! of every block.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Encode;
+use frame_support::{
+	dispatch::DispatchClass,
+	traits::{Get, ProcessMessageError},
+};
+use sp_io::hashing::blake2_256;
+use sp_std::{marker::PhantomData, result::Result};
+use xcm::prelude::*;
+use xcm_executor::traits::{Properties, ShouldExecute};
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+mod mock;
+mod tests;
+pub mod weights;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Maximal number of messages that a single origin may execute in a single block.
+		#[pallet::constant]
+		type MaxMessagesPerOrigin: Get<u32>;
+		/// Maximal number of distinct origins that may execute messages in a single block.
+		#[pallet::constant]
+		type MaxOriginsPerBlock: Get<u32>;
+		/// Weight information for the barrier and the hooks of this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			// there are at most `MaxOriginsPerBlock` entries in the map, so it is cleared at once
+			let removed = MessagesThisBlock::<T>::clear(T::MaxOriginsPerBlock::get(), None);
+			T::WeightInfo::on_initialize(removed.unique)
+		}
+	}
+
+	/// Number of messages, executed by every origin in the current block, by the hash of the
+	/// origin location.
+	///
+	/// The map is cleared at the beginning of every block.
+	#[pallet::storage]
+	pub type MessagesThisBlock<T: Config> =
+		CountedStorageMap<_, Identity, [u8; 32], u32, ValueQuery>;
+}
+
+impl<T: Config> Pallet<T> {
+	/// Returns number of messages that have been executed by the `origin` in the current block.
+	pub fn messages_in_current_block(origin: &Location) -> u32 {
+		MessagesThisBlock::<T>::get(origin_key(origin))
+	}
+}
+
+/// Returns the key of the `origin` in the `MessagesThisBlock` map.
+fn origin_key(origin: &Location) -> [u8; 32] {
+	blake2_256(&origin.encode())
+}
+
+/// Barrier that limits the number of messages that every origin may execute in a single block.
+///
+/// Messages are passed to the `InnerBarrier` and only messages that it accepts are counted.
+/// Messages above the `Config::MaxMessagesPerOrigin` limit, or from new origins once there are
+/// `Config::MaxOriginsPerBlock` origins in the current block, are rejected with the
+/// `ProcessMessageError::Overweight` error.
+///
+/// Note that a rejected message is not deferred to the next block. The executor reports any
+/// barrier error as `XcmError::Barrier` and the message queue drops the message as unsupported,
+/// so the sender has to send it again.
+///
+/// The cost of accessing the counter, `Config::WeightInfo::should_execute`, is registered as the
+/// mandatory weight of the block.
+pub struct AllowXcmRateLimit<T, InnerBarrier>(PhantomData<(T, InnerBarrier)>);
+impl<T: Config, InnerBarrier: ShouldExecute> ShouldExecute for AllowXcmRateLimit<T, InnerBarrier> {
+	fn should_execute<RuntimeCall>(
+		origin: &Location,
+		instructions: &mut [Instruction<RuntimeCall>],
+		max_weight: Weight,
+		properties: &mut Properties,
+	) -> Result<(), ProcessMessageError> {
+		log::trace!(
+			target: "xcm::barriers",
+			"AllowXcmRateLimit origin: {:?}, instructions: {:?}, max_weight: {:?}, properties: {:?}",
+			origin, instructions, max_weight, properties,
+		);
+		// we read and update the counter of the origin and the number of origins
+		frame_system::Pallet::<T>::register_extra_weight_unchecked(
+			T::WeightInfo::should_execute(),
+			DispatchClass::Mandatory,
+		);
+
+		let key = origin_key(origin);
+		let messages_in_current_block = MessagesThisBlock::<T>::get(key);
+		frame_support::ensure!(
+			messages_in_current_block < T::MaxMessagesPerOrigin::get(),
+			ProcessMessageError::Overweight(max_weight)
+		);
+		frame_support::ensure!(
+			messages_in_current_block != 0 ||
+				MessagesThisBlock::<T>::count() < T::MaxOriginsPerBlock::get(),
+			ProcessMessageError::Overweight(max_weight)
+		);
+
+		InnerBarrier::should_execute(origin, instructions, max_weight, properties)?;
+
+		MessagesThisBlock::<T>::insert(key, messages_in_current_block.saturating_add(1));
+		Ok(())
+	}
+}
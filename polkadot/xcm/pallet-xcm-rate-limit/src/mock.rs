@@ -0,0 +1,77 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use crate as pallet_xcm_rate_limit;
+
+use frame_support::{
+	construct_runtime, derive_impl,
+	traits::{ConstU32, ProcessMessageError},
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_runtime::BuildStorage;
+use xcm::prelude::*;
+use xcm_executor::traits::{Properties, ShouldExecute};
+
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+
+construct_runtime! {
+	pub enum TestRuntime
+	{
+		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
+		XcmRateLimit: pallet_xcm_rate_limit::{Pallet, Storage},
+	}
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for TestRuntime {
+	type Block = Block;
+	type DbWeight = RocksDbWeight;
+}
+
+impl pallet_xcm_rate_limit::Config for TestRuntime {
+	type MaxMessagesPerOrigin = ConstU32<2>;
+	type MaxOriginsPerBlock = ConstU32<3>;
+	type WeightInfo = ();
+}
+
+/// Barrier that rejects messages from the relay chain.
+pub struct DenyParent;
+impl ShouldExecute for DenyParent {
+	fn should_execute<RuntimeCall>(
+		origin: &Location,
+		_instructions: &mut [Instruction<RuntimeCall>],
+		_max_weight: Weight,
+		_properties: &mut Properties,
+	) -> Result<(), ProcessMessageError> {
+		if *origin == Location::parent() {
+			return Err(ProcessMessageError::Unsupported)
+		}
+		Ok(())
+	}
+}
+
+/// Return test externalities to use in tests.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::<TestRuntime>::default().build_storage().unwrap();
+	sp_io::TestExternalities::new(t)
+}
+
+/// Run pallet test.
+pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
+	new_test_ext().execute_with(test)
+}
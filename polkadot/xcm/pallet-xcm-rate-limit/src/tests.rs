@@ -0,0 +1,114 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::*;
+
+use frame_support::traits::Hooks;
+
+type Barrier = AllowXcmRateLimit<TestRuntime, DenyParent>;
+
+fn should_execute(origin: impl Into<Location>) -> Result<(), ProcessMessageError> {
+	let mut message = Xcm::<()>(vec![ClearOrigin]);
+	Barrier::should_execute(
+		&origin.into(),
+		message.inner_mut(),
+		Weight::from_parts(10, 10),
+		&mut Properties { weight_credit: Weight::zero(), message_id: None },
+	)
+}
+
+fn para(id: u32) -> Location {
+	(Parent, Parachain(id)).into()
+}
+
+#[test]
+fn every_origin_hits_the_limit_independently() {
+	run_test(|| {
+		assert_eq!(should_execute(para(1000)), Ok(()));
+		assert_eq!(should_execute(para(1000)), Ok(()));
+		assert_eq!(
+			should_execute(para(1000)),
+			Err(ProcessMessageError::Overweight(Weight::from_parts(10, 10))),
+		);
+		assert_eq!(should_execute(para(2000)), Ok(()));
+		assert_eq!(should_execute(para(2000)), Ok(()));
+		assert_eq!(
+			should_execute(para(2000)),
+			Err(ProcessMessageError::Overweight(Weight::from_parts(10, 10))),
+		);
+
+		assert_eq!(XcmRateLimit::messages_in_current_block(&para(1000)), 2);
+		assert_eq!(XcmRateLimit::messages_in_current_block(&para(2000)), 2);
+	});
+}
+
+#[test]
+fn messages_rejected_by_inner_barrier_are_not_counted() {
+	run_test(|| {
+		assert_eq!(should_execute(Parent), Err(ProcessMessageError::Unsupported));
+		assert_eq!(XcmRateLimit::messages_in_current_block(&Parent.into()), 0);
+		assert_eq!(MessagesThisBlock::<TestRuntime>::count(), 0);
+	});
+}
+
+#[test]
+fn number_of_origins_per_block_is_limited() {
+	run_test(|| {
+		assert_eq!(should_execute(para(1000)), Ok(()));
+		assert_eq!(should_execute(para(2000)), Ok(()));
+		assert_eq!(should_execute(para(3000)), Ok(()));
+		assert_eq!(
+			should_execute(para(4000)),
+			Err(ProcessMessageError::Overweight(Weight::from_parts(10, 10))),
+		);
+
+		// known origins may still execute messages
+		assert_eq!(should_execute(para(1000)), Ok(()));
+	});
+}
+
+#[test]
+fn counters_are_cleared_at_next_block() {
+	run_test(|| {
+		assert_eq!(should_execute(para(1000)), Ok(()));
+		assert_eq!(should_execute(para(1000)), Ok(()));
+		assert_eq!(should_execute(para(2000)), Ok(()));
+
+		let weight = XcmRateLimit::on_initialize(2);
+		assert_eq!(weight, <() as WeightInfo>::on_initialize(2));
+		assert_eq!(XcmRateLimit::messages_in_current_block(&para(1000)), 0);
+		assert_eq!(MessagesThisBlock::<TestRuntime>::count(), 0);
+
+		assert_eq!(should_execute(para(1000)), Ok(()));
+	});
+}
+
+#[test]
+fn storage_access_is_registered_as_block_weight() {
+	run_test(|| {
+		let block_weight = || *System::block_weight().get(DispatchClass::Mandatory);
+
+		assert_eq!(should_execute(para(1000)), Ok(()));
+		assert_eq!(block_weight(), <() as WeightInfo>::should_execute());
+
+		// rejected message pays for the counter access too
+		assert_eq!(should_execute(Parent), Err(ProcessMessageError::Unsupported));
+		assert_eq!(block_weight(), <() as WeightInfo>::should_execute().saturating_mul(2));
+	});
+}
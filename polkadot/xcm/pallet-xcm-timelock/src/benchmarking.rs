@@ -0,0 +1,63 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for the XCM timelock pallet.
+
+use super::*;
+use frame_benchmarking::v1::{benchmarks, whitelisted_caller, BenchmarkError};
+use frame_support::traits::Hooks;
+use frame_system::RawOrigin;
+
+/// Lock `n` deposits of the same beneficiary and return it, along with the block where the
+/// deposits are released.
+fn lock_deposits<T: Config>(n: u32) -> Result<(T::AccountId, BlockNumberFor<T>), BenchmarkError> {
+	let (asset, beneficiary) = T::BenchmarkHelper::deposit();
+	let who =
+		T::AccountIdConverter::convert_location(&beneficiary).ok_or(BenchmarkError::Weightless)?;
+	for _ in 0..n {
+		Pallet::<T>::lock_deposit(&asset, &beneficiary).map_err(|_| BenchmarkError::Weightless)?;
+	}
+	let release_at = frame_system::Pallet::<T>::block_number().saturating_add(T::LockPeriod::get());
+	Ok((who, release_at))
+}
+
+benchmarks! {
+	// The weight of the deposit itself is `Config::ReleaseWeight` and is added separately.
+	claim_timelocked_release {
+		let (who, release_at) = lock_deposits::<T>(1)?;
+		frame_system::Pallet::<T>::set_block_number(release_at);
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), who.clone(), 0)
+	verify {
+		assert!(LockedAssets::<T>::get(&who, 0).is_none());
+	}
+
+	// The weight of the deposits themselves is `Config::ReleaseWeight` per release and is added
+	// separately.
+	on_initialize {
+		let n in 0 .. T::MaxReleasesPerBlock::get();
+
+		let (who, release_at) = lock_deposits::<T>(n)?;
+		frame_system::Pallet::<T>::set_block_number(release_at);
+	}: {
+		Pallet::<T>::on_initialize(release_at);
+	}
+	verify {
+		assert_eq!(LockedAssets::<T>::iter_prefix(&who).count(), 0);
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::TestRuntime);
+}
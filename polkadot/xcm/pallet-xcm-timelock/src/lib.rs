@@ -0,0 +1,326 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Pallet that delays deposits of assets, transferred using XCM.
+//!
+//! Some bridges need a challenge window before transferred assets become spendable, e.g. to
+//! allow fraud proofs. The [`TimelockFungiblesAdapter`] may be used as the asset transactor of
+//! the XCM executor: instead of crediting the beneficiary, every deposit of a matching asset is
+//! stored as a [`PendingRelease`], keyed by the beneficiary account and a per-beneficiary nonce.
+//!
+//! Deposits are released by the `Config::Transactor` at the beginning of the block where their
+//! lock period is over. Once the lock period is over, anyone may also release the deposit using
+//! the `claim_timelocked_release` call. A release that fails in `on_initialize` is not retried
+//! automatically: the deposit stays locked until it is claimed using that call.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::traits::Get;
+use frame_system::pallet_prelude::BlockNumberFor;
+use scale_info::TypeInfo;
+use sp_runtime::{traits::Saturating, RuntimeDebug};
+use sp_std::{marker::PhantomData, result};
+use xcm::{prelude::*, VersionedAsset, VersionedLocation};
+use xcm_executor::traits::{ConvertLocation, Error as MatchError, MatchesFungibles, TransactAsset};
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+mod mock;
+mod tests;
+pub mod weights;
+
+/// The target that will be used when publishing logs related to this pallet.
+pub const LOG_TARGET: &str = "xcm::timelock";
+
+/// Deposit that will be released to the beneficiary at the `release_at` block.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct PendingRelease<BlockNumber> {
+	/// The deposited asset.
+	pub asset: VersionedAsset,
+	/// The beneficiary of the deposit.
+	pub beneficiary: VersionedLocation,
+	/// The block, starting from which the deposit may be released.
+	pub release_at: BlockNumber,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Converts the location of the deposit beneficiary into its account.
+		type AccountIdConverter: ConvertLocation<Self::AccountId>;
+		/// Transactor that deposits assets once their lock period is over.
+		type Transactor: TransactAsset;
+		/// Number of blocks the deposit is locked for.
+		#[pallet::constant]
+		type LockPeriod: Get<BlockNumberFor<Self>>;
+		/// Maximal number of deposits that may be released in a single block.
+		#[pallet::constant]
+		type MaxReleasesPerBlock: Get<u32>;
+		/// Weight of the single deposit, performed by the `Transactor`.
+		#[pallet::constant]
+		type ReleaseWeight: Get<Weight>;
+		/// Weight information for extrinsics and hooks of this pallet.
+		type WeightInfo: WeightInfo;
+		/// Helper for creating the deposits in benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper;
+	}
+
+	/// Helper for creating the deposits in benchmarks.
+	#[cfg(feature = "runtime-benchmarks")]
+	pub trait BenchmarkHelper {
+		/// Returns the asset that the `Config::Transactor` may deposit and the location of its
+		/// beneficiary that the `Config::AccountIdConverter` may convert into an account.
+		fn deposit() -> (Asset, Location);
+	}
+	#[cfg(feature = "runtime-benchmarks")]
+	impl BenchmarkHelper for () {
+		fn deposit() -> (Asset, Location) {
+			((Here, 1_000_000_000u128).into(), AccountIndex64 { network: None, index: 1 }.into())
+		}
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			let schedule = ReleaseSchedule::<T>::take(now);
+			let mut weight = T::WeightInfo::on_initialize(schedule.len() as u32);
+
+			for (beneficiary, nonce) in schedule {
+				// the deposit may have been claimed already
+				let Some(release) = LockedAssets::<T>::get(&beneficiary, nonce) else { continue };
+
+				weight.saturating_accrue(T::ReleaseWeight::get());
+				if let Err(e) = Self::release(&beneficiary, nonce, release) {
+					log::debug!(
+						target: LOG_TARGET,
+						"Failed to release deposit {} of {:?}: {:?}",
+						nonce,
+						beneficiary,
+						e,
+					);
+					Self::deposit_event(Event::DepositReleaseFailed { beneficiary, nonce });
+				}
+			}
+
+			weight
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Release the deposit of the `beneficiary` with given `nonce`, if its lock period is
+		/// over.
+		#[pallet::call_index(0)]
+		#[pallet::weight(
+			T::WeightInfo::claim_timelocked_release().saturating_add(T::ReleaseWeight::get())
+		)]
+		pub fn claim_timelocked_release(
+			origin: OriginFor<T>,
+			beneficiary: T::AccountId,
+			nonce: u64,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let release =
+				LockedAssets::<T>::get(&beneficiary, nonce).ok_or(Error::<T>::NotLocked)?;
+			ensure!(
+				release.release_at <= frame_system::Pallet::<T>::block_number(),
+				Error::<T>::LockNotExpired
+			);
+			Self::release(&beneficiary, nonce, release).map_err(|e| {
+				log::debug!(
+					target: LOG_TARGET,
+					"Failed to release claimed deposit {} of {:?}: {:?}",
+					nonce,
+					beneficiary,
+					e,
+				);
+
+				Error::<T>::ReleaseFailed
+			})?;
+
+			Ok(())
+		}
+	}
+
+	/// Deposits that are waiting for their lock period to end, by the beneficiary account and
+	/// nonce.
+	#[pallet::storage]
+	pub type LockedAssets<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Twox64Concat,
+		u64,
+		PendingRelease<BlockNumberFor<T>>,
+	>;
+
+	/// Nonce of the next deposit of the beneficiary account.
+	#[pallet::storage]
+	pub type NextDepositNonce<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u64, ValueQuery>;
+
+	/// Deposits that are released at the beginning of the block.
+	#[pallet::storage]
+	pub type ReleaseSchedule<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<(T::AccountId, u64), T::MaxReleasesPerBlock>,
+		ValueQuery,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The deposit has been locked until the `release_at` block.
+		DepositLocked { beneficiary: T::AccountId, nonce: u64, release_at: BlockNumberFor<T> },
+		/// The deposit has been released to the beneficiary.
+		DepositReleased { beneficiary: T::AccountId, nonce: u64 },
+		/// The deposit has not been released at the end of its lock period. It may only be
+		/// released using the `claim_timelocked_release` call.
+		DepositReleaseFailed { beneficiary: T::AccountId, nonce: u64 },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// There is no locked deposit with given nonce.
+		NotLocked,
+		/// The lock period of the deposit is not over yet.
+		LockNotExpired,
+		/// The `Transactor` has failed to deposit the asset.
+		ReleaseFailed,
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Lock the deposit of the `asset` to the `beneficiary` for the `LockPeriod`.
+	fn lock_deposit(asset: &Asset, beneficiary: &Location) -> XcmResult {
+		let who = T::AccountIdConverter::convert_location(beneficiary)
+			.ok_or(MatchError::AccountIdConversionFailed)?;
+		let nonce = NextDepositNonce::<T>::get(&who);
+		let release_at =
+			frame_system::Pallet::<T>::block_number().saturating_add(T::LockPeriod::get());
+		ReleaseSchedule::<T>::try_mutate(release_at, |schedule| {
+			schedule.try_push((who.clone(), nonce))
+		})
+		.map_err(|_| XcmError::FailedToTransactAsset("too many deposits are locked"))?;
+
+		NextDepositNonce::<T>::insert(&who, nonce.saturating_add(1));
+		LockedAssets::<T>::insert(
+			&who,
+			nonce,
+			PendingRelease {
+				asset: VersionedAsset::from(asset.clone()),
+				beneficiary: VersionedLocation::from(beneficiary.clone()),
+				release_at,
+			},
+		);
+		Self::deposit_event(Event::DepositLocked { beneficiary: who, nonce, release_at });
+		Ok(())
+	}
+
+	/// Deposit the asset of the pending `release` and remove it from the storage.
+	fn release(
+		who: &T::AccountId,
+		nonce: u64,
+		release: PendingRelease<BlockNumberFor<T>>,
+	) -> XcmResult {
+		let asset = Asset::try_from(release.asset).map_err(|_| XcmError::UnhandledXcmVersion)?;
+		let beneficiary =
+			Location::try_from(release.beneficiary).map_err(|_| XcmError::UnhandledXcmVersion)?;
+		T::Transactor::deposit_asset(&asset, &beneficiary, None)?;
+
+		LockedAssets::<T>::remove(who, nonce);
+		Self::deposit_event(Event::DepositReleased { beneficiary: who.clone(), nonce });
+		Ok(())
+	}
+}
+
+/// `TransactAsset` implementation that delays deposits of assets, matched by the `Matcher`, by
+/// the `Config::LockPeriod` blocks.
+///
+/// Deposits of other assets are rejected with the `AssetNotHandled` error, so the adapter may be
+/// used in a tuple with other transactors. All other operations are delegated to the
+/// `Config::Transactor`.
+pub struct TimelockFungiblesAdapter<T, Matcher, AssetId, Balance>(
+	PhantomData<(T, Matcher, AssetId, Balance)>,
+);
+impl<T, Matcher, AssetId, Balance> TransactAsset
+	for TimelockFungiblesAdapter<T, Matcher, AssetId, Balance>
+where
+	T: Config,
+	Matcher: MatchesFungibles<AssetId, Balance>,
+{
+	fn can_check_in(origin: &Location, what: &Asset, context: &XcmContext) -> XcmResult {
+		T::Transactor::can_check_in(origin, what, context)
+	}
+
+	fn check_in(origin: &Location, what: &Asset, context: &XcmContext) {
+		T::Transactor::check_in(origin, what, context)
+	}
+
+	fn can_check_out(dest: &Location, what: &Asset, context: &XcmContext) -> XcmResult {
+		T::Transactor::can_check_out(dest, what, context)
+	}
+
+	fn check_out(dest: &Location, what: &Asset, context: &XcmContext) {
+		T::Transactor::check_out(dest, what, context)
+	}
+
+	fn deposit_asset(what: &Asset, who: &Location, _context: Option<&XcmContext>) -> XcmResult {
+		log::trace!(
+			target: LOG_TARGET,
+			"TimelockFungiblesAdapter::deposit_asset what: {:?}, who: {:?}",
+			what, who,
+		);
+		// Check we handle this asset.
+		Matcher::matches_fungibles(what)?;
+		Pallet::<T>::lock_deposit(what, who)
+	}
+
+	fn withdraw_asset(
+		what: &Asset,
+		who: &Location,
+		maybe_context: Option<&XcmContext>,
+	) -> result::Result<xcm_executor::AssetsInHolding, XcmError> {
+		T::Transactor::withdraw_asset(what, who, maybe_context)
+	}
+
+	fn internal_transfer_asset(
+		what: &Asset,
+		from: &Location,
+		to: &Location,
+		context: &XcmContext,
+	) -> result::Result<xcm_executor::AssetsInHolding, XcmError> {
+		T::Transactor::internal_transfer_asset(what, from, to, context)
+	}
+}
@@ -0,0 +1,114 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use crate as pallet_xcm_timelock;
+
+use frame_support::{construct_runtime, derive_impl, parameter_types, weights::Weight};
+use sp_runtime::BuildStorage;
+use xcm::prelude::*;
+use xcm_executor::traits::{ConvertLocation, Error as MatchError, MatchesFungibles, TransactAsset};
+
+pub type AccountId = u64;
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+
+construct_runtime! {
+	pub enum TestRuntime
+	{
+		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Timelock: pallet_xcm_timelock::{Pallet, Call, Storage, Event<T>},
+	}
+}
+
+parameter_types! {
+	pub static DepositFails: bool = false;
+	pub static Deposits: Vec<(Asset, Location)> = vec![];
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for TestRuntime {
+	type Block = Block;
+}
+
+impl pallet_xcm_timelock::Config for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
+	type AccountIdConverter = AccountIndex64ToAccountId;
+	type Transactor = TestTransactor;
+	type LockPeriod = frame_support::traits::ConstU64<5>;
+	type MaxReleasesPerBlock = frame_support::traits::ConstU32<2>;
+	type ReleaseWeight = ReleaseWeight;
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
+parameter_types! {
+	pub ReleaseWeight: Weight = Weight::from_parts(1_000, 0);
+}
+
+/// Converts the `AccountIndex64` location into the account id.
+pub struct AccountIndex64ToAccountId;
+impl ConvertLocation<AccountId> for AccountIndex64ToAccountId {
+	fn convert_location(location: &Location) -> Option<AccountId> {
+		match location.unpack() {
+			(0, [AccountIndex64 { index, .. }]) => Some(*index),
+			_ => None,
+		}
+	}
+}
+
+/// Matches fungible assets, native to this chain.
+pub struct NativeAssetMatcher;
+impl MatchesFungibles<(), u128> for NativeAssetMatcher {
+	fn matches_fungibles(a: &Asset) -> Result<((), u128), MatchError> {
+		match (&a.id, &a.fun) {
+			(AssetId(location), Fungible(amount)) if *location == Location::here() =>
+				Ok(((), *amount)),
+			_ => Err(MatchError::AssetNotHandled),
+		}
+	}
+}
+
+/// Transactor that records all deposits in the `Deposits`, unless `DepositFails` is set.
+pub struct TestTransactor;
+impl TransactAsset for TestTransactor {
+	fn deposit_asset(what: &Asset, who: &Location, _context: Option<&XcmContext>) -> XcmResult {
+		if DepositFails::get() {
+			return Err(XcmError::FailedToTransactAsset("test error"))
+		}
+		Deposits::mutate(|deposits| deposits.push((what.clone(), who.clone())));
+		Ok(())
+	}
+}
+
+/// Location of the test account.
+pub fn account_location(account: AccountId) -> Location {
+	AccountIndex64 { network: None, index: account }.into()
+}
+
+/// Return test externalities to use in tests.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::<TestRuntime>::default().build_storage().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Run pallet test.
+pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
+	new_test_ext().execute_with(test)
+}
@@ -0,0 +1,177 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::*;
+
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use frame_system::RawOrigin;
+
+type Adapter = TimelockFungiblesAdapter<TestRuntime, NativeAssetMatcher, (), u128>;
+
+const BENEFICIARY: AccountId = 42;
+
+fn native(amount: u128) -> Asset {
+	(Here, amount).into()
+}
+
+fn deposit(amount: u128) -> XcmResult {
+	Adapter::deposit_asset(&native(amount), &account_location(BENEFICIARY), None)
+}
+
+fn run_to(block: u64) {
+	System::set_block_number(block);
+	Timelock::on_initialize(block);
+}
+
+#[test]
+fn adapter_locks_deposits() {
+	run_test(|| {
+		assert_ok!(deposit(10));
+
+		assert_eq!(Deposits::get(), vec![]);
+		assert_eq!(
+			LockedAssets::<TestRuntime>::get(BENEFICIARY, 0),
+			Some(PendingRelease {
+				asset: native(10).into(),
+				beneficiary: account_location(BENEFICIARY).into(),
+				release_at: 6,
+			}),
+		);
+		assert_eq!(ReleaseSchedule::<TestRuntime>::get(6).into_inner(), vec![(BENEFICIARY, 0)]);
+		assert_eq!(NextDepositNonce::<TestRuntime>::get(BENEFICIARY), 1);
+	});
+}
+
+#[test]
+fn adapter_rejects_unmatched_assets() {
+	run_test(|| {
+		assert_eq!(
+			Adapter::deposit_asset(&(Parent, 10).into(), &account_location(BENEFICIARY), None),
+			Err(XcmError::AssetNotFound),
+		);
+		assert_eq!(LockedAssets::<TestRuntime>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn adapter_limits_number_of_releases_per_block() {
+	run_test(|| {
+		assert_ok!(deposit(10));
+		assert_ok!(deposit(20));
+		assert_eq!(
+			deposit(30),
+			Err(XcmError::FailedToTransactAsset("too many deposits are locked"))
+		);
+		assert_eq!(NextDepositNonce::<TestRuntime>::get(BENEFICIARY), 2);
+
+		// deposits of the next block are released at the next block
+		System::set_block_number(2);
+		assert_ok!(deposit(30));
+	});
+}
+
+#[test]
+fn claim_timelocked_release_rejects_early_claim() {
+	run_test(|| {
+		assert_ok!(deposit(10));
+
+		System::set_block_number(5);
+		assert_noop!(
+			Timelock::claim_timelocked_release(RawOrigin::Signed(1).into(), BENEFICIARY, 0),
+			Error::<TestRuntime>::LockNotExpired,
+		);
+		assert_eq!(Deposits::get(), vec![]);
+	});
+}
+
+#[test]
+fn claim_timelocked_release_works_after_lock_period() {
+	run_test(|| {
+		assert_ok!(deposit(10));
+
+		System::set_block_number(6);
+		assert_ok!(Timelock::claim_timelocked_release(RawOrigin::Signed(1).into(), BENEFICIARY, 0));
+		assert_eq!(Deposits::get(), vec![(native(10), account_location(BENEFICIARY))]);
+		assert_eq!(LockedAssets::<TestRuntime>::get(BENEFICIARY, 0), None);
+
+		// deposit can't be claimed twice
+		assert_noop!(
+			Timelock::claim_timelocked_release(RawOrigin::Signed(1).into(), BENEFICIARY, 0),
+			Error::<TestRuntime>::NotLocked,
+		);
+		// and it is not released by `on_initialize`
+		Timelock::on_initialize(6);
+		assert_eq!(Deposits::get().len(), 1);
+	});
+}
+
+#[test]
+fn on_initialize_releases_deposits() {
+	run_test(|| {
+		assert_ok!(deposit(10));
+		assert_ok!(deposit(20));
+		System::set_block_number(2);
+		assert_ok!(deposit(30));
+
+		run_to(6);
+		assert_eq!(
+			Deposits::get(),
+			vec![
+				(native(10), account_location(BENEFICIARY)),
+				(native(20), account_location(BENEFICIARY))
+			],
+		);
+		assert_eq!(LockedAssets::<TestRuntime>::iter().count(), 1);
+		assert_eq!(ReleaseSchedule::<TestRuntime>::get(6).len(), 0);
+
+		run_to(7);
+		assert_eq!(Deposits::get().len(), 3);
+		assert_eq!(LockedAssets::<TestRuntime>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn on_initialize_leaves_failed_releases_claimable() {
+	run_test(|| {
+		assert_ok!(deposit(10));
+
+		DepositFails::set(true);
+		let weight = Timelock::on_initialize(6);
+		assert_eq!(
+			weight,
+			<() as WeightInfo>::on_initialize(1).saturating_add(ReleaseWeight::get())
+		);
+		System::assert_last_event(
+			Event::<TestRuntime>::DepositReleaseFailed { beneficiary: BENEFICIARY, nonce: 0 }
+				.into(),
+		);
+		assert!(LockedAssets::<TestRuntime>::get(BENEFICIARY, 0).is_some());
+
+		// the failed release is not retried automatically
+		DepositFails::set(false);
+		run_to(7);
+		assert_eq!(Deposits::get(), vec![]);
+		assert!(LockedAssets::<TestRuntime>::get(BENEFICIARY, 0).is_some());
+
+		// but it may be claimed
+		assert_ok!(Timelock::claim_timelocked_release(RawOrigin::Signed(1).into(), BENEFICIARY, 0));
+		assert_eq!(Deposits::get(), vec![(native(10), account_location(BENEFICIARY))]);
+		assert_eq!(LockedAssets::<TestRuntime>::get(BENEFICIARY, 0), None);
+	});
+}
@@ -59,7 +59,7 @@ use xcm_executor::{
 		DropAssets, MatchesFungible, OnResponse, Properties, QueryHandler, QueryResponseStatus,
 		TransactAsset, TransferType, VersionChangeNotifier, WeightBounds, XcmAssetTransfers,
 	},
-	AssetsInHolding,
+	with_sending_origin, AssetsInHolding,
 };
 
 #[cfg(any(feature = "try-runtime", test))]
@@ -1752,8 +1752,10 @@ impl<T: Config> Pallet<T> {
 		})?;
 
 		if let Some(remote_xcm) = remote_xcm {
-			let (ticket, price) = validate_send::<T::XcmRouter>(dest.clone(), remote_xcm.clone())
-				.map_err(Error::<T>::from)?;
+			let (ticket, price) = with_sending_origin(origin.clone(), || {
+				validate_send::<T::XcmRouter>(dest.clone(), remote_xcm.clone())
+			})
+			.map_err(Error::<T>::from)?;
 			if origin != Here.into_location() {
 				Self::charge_fees(origin.clone(), price).map_err(|error| {
 					log::error!(
@@ -2343,15 +2345,17 @@ impl<T: Config> Pallet<T> {
 		mut message: Xcm<()>,
 	) -> Result<XcmHash, SendError> {
 		let interior = interior.into();
+		let origin: Location = interior.clone().into();
 		let dest = dest.into();
 		let maybe_fee_payer = if interior != Junctions::Here {
-			message.0.insert(0, DescendOrigin(interior.clone()));
-			Some(interior.into())
+			message.0.insert(0, DescendOrigin(interior));
+			Some(origin.clone())
 		} else {
 			None
 		};
 		log::debug!(target: "xcm::send_xcm", "dest: {:?}, message: {:?}", &dest, &message);
-		let (ticket, price) = validate_send::<T::XcmRouter>(dest, message)?;
+		let (ticket, price) =
+			with_sending_origin(origin, || validate_send::<T::XcmRouter>(dest, message))?;
 		if let Some(fee_payer) = maybe_fee_payer {
 			Self::charge_fees(fee_payer, price).map_err(|_| SendError::Fees)?;
 		}
@@ -0,0 +1,173 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Derive macro for implementing `DescribeLocation` from a location pattern.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{DeriveInput, Error, Fields, Ident, LitByteStr, LitStr, Pat, Result};
+
+/// The junctions which may be matched by a pattern.
+const JUNCTIONS: &[&str] = &[
+	"Parachain",
+	"AccountId32",
+	"AccountIndex64",
+	"AccountKey20",
+	"PalletInstance",
+	"GeneralIndex",
+	"GeneralKey",
+	"OnlyChild",
+	"Plurality",
+	"GlobalConsensus",
+];
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream2> {
+	match &input.data {
+		syn::Data::Struct(data) if matches!(data.fields, Fields::Unit) => {},
+		_ => return Err(Error::new_spanned(&input, "Expected a unit struct")),
+	}
+
+	let mut describe = input.attrs.iter().filter(|attr| attr.path().is_ident("describe"));
+	let Some(attr) = describe.next() else {
+		return Err(Error::new_spanned(
+			&input.ident,
+			"Expected `#[describe(pattern = \"..\", tag = b\"..\")]`",
+		))
+	};
+	if let Some(duplicate) = describe.next() {
+		return Err(Error::new_spanned(duplicate, "Expected a single `describe` attribute"))
+	}
+
+	let mut pattern = None;
+	let mut tag = None;
+	attr.parse_nested_meta(|meta| {
+		if meta.path.is_ident("pattern") {
+			pattern = Some(meta.value()?.parse::<LitStr>()?);
+		} else if meta.path.is_ident("tag") {
+			tag = Some(meta.value()?.parse::<LitByteStr>()?);
+		} else {
+			return Err(meta.error("Expected `pattern` or `tag`"))
+		}
+		Ok(())
+	})?;
+	let pattern = pattern.ok_or_else(|| Error::new_spanned(attr, "Expected a `pattern`"))?;
+	let tag = tag.ok_or_else(|| Error::new_spanned(attr, "Expected a `tag`"))?;
+
+	let parsed = pattern.parse_with(Pat::parse_single)?;
+	let mut bindings = Vec::new();
+	check_location_pattern(&parsed, &mut bindings)
+		.map_err(|msg| Error::new(pattern.span(), msg))?;
+
+	let name = &input.ident;
+	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+	Ok(quote! {
+		impl #impl_generics ::xcm_builder::DescribeLocation for #name #ty_generics #where_clause {
+			fn describe_location(
+				location: &::xcm_builder::__private::Location,
+			) -> Option<::xcm_builder::__private::Vec<u8>> {
+				#[allow(unused_imports)]
+				use ::xcm_builder::__private::{prelude::*, Encode};
+				match location.unpack() {
+					#parsed => Some((#tag, #(#bindings),*).encode()),
+					_ => None,
+				}
+			}
+		}
+	})
+}
+
+/// Check that `pattern` matches the unpacked `(parents, [junctions..])` of a location, collecting
+/// the bound variables in order.
+fn check_location_pattern(
+	pattern: &Pat,
+	bindings: &mut Vec<Ident>,
+) -> core::result::Result<(), String> {
+	let Pat::Tuple(tuple) = pattern else {
+		return Err("Expected a `(parents, [junctions..])` pattern".into())
+	};
+	let [parents, junctions] = tuple.elems.iter().collect::<Vec<_>>()[..] else {
+		return Err("Expected a `(parents, [junctions..])` pattern".into())
+	};
+	match parents {
+		Pat::Lit(_) | Pat::Wild(_) => {},
+		Pat::Ident(ident) if ident.subpat.is_none() => bindings.push(ident.ident.clone()),
+		_ => return Err("Expected the number of parents to be a literal or a binding".into()),
+	}
+	let Pat::Slice(junctions) = junctions else {
+		return Err("Expected the junctions to be a `[..]` pattern".into())
+	};
+	if junctions.elems.len() > 8 {
+		return Err("Expected at most 8 junctions".into())
+	}
+	junctions
+		.elems
+		.iter()
+		.try_for_each(|junction| check_junction_pattern(junction, bindings))
+}
+
+/// Check that `pattern` matches a single junction, collecting the bound variables in order.
+fn check_junction_pattern(
+	pattern: &Pat,
+	bindings: &mut Vec<Ident>,
+) -> core::result::Result<(), String> {
+	let path = match pattern {
+		Pat::Wild(_) | Pat::Rest(_) => return Ok(()),
+		Pat::Ident(ident) if is_binding(&ident.ident) && ident.subpat.is_none() => {
+			bindings.push(ident.ident.clone());
+			return Ok(())
+		},
+		Pat::Ident(ident) => ident.ident.clone().into(),
+		Pat::Path(path) => path.path.clone(),
+		Pat::TupleStruct(tuple) => {
+			tuple.elems.iter().for_each(|field| collect_bindings(field, bindings));
+			tuple.path.clone()
+		},
+		Pat::Struct(fields) => {
+			fields.fields.iter().for_each(|field| collect_bindings(&field.pat, bindings));
+			fields.path.clone()
+		},
+		_ => return Err("Expected a junction pattern".into()),
+	};
+	match path.segments.last() {
+		Some(segment) if JUNCTIONS.contains(&segment.ident.to_string().as_str()) => Ok(()),
+		_ => Err(format!("Expected one of the junctions {}", JUNCTIONS.join(", "))),
+	}
+}
+
+/// Collect the variables bound in `pattern`, in order.
+fn collect_bindings(pattern: &Pat, bindings: &mut Vec<Ident>) {
+	match pattern {
+		Pat::Ident(ident) if is_binding(&ident.ident) => {
+			bindings.push(ident.ident.clone());
+			if let Some((_, subpat)) = &ident.subpat {
+				collect_bindings(subpat, bindings);
+			}
+		},
+		Pat::Reference(reference) => collect_bindings(&reference.pat, bindings),
+		Pat::Tuple(tuple) => tuple.elems.iter().for_each(|pat| collect_bindings(pat, bindings)),
+		Pat::TupleStruct(tuple) =>
+			tuple.elems.iter().for_each(|pat| collect_bindings(pat, bindings)),
+		Pat::Struct(fields) =>
+			fields.fields.iter().for_each(|field| collect_bindings(&field.pat, bindings)),
+		Pat::Slice(slice) => slice.elems.iter().for_each(|pat| collect_bindings(pat, bindings)),
+		_ => {},
+	}
+}
+
+/// Whether `ident` binds a variable, rather than naming a unit variant or a constant.
+fn is_binding(ident: &Ident) -> bool {
+	ident.to_string().starts_with(|c: char| c.is_lowercase() || c == '_')
+}
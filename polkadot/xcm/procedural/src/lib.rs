@@ -20,10 +20,12 @@ use proc_macro::TokenStream;
 use syn::{parse_macro_input, DeriveInput};
 
 mod builder_pattern;
+mod describe_location;
 mod v2;
 mod v3;
 mod v4;
 mod weight_info;
+mod xcm_config;
 
 #[proc_macro]
 pub fn impl_conversion_functions_for_multilocation_v2(input: TokenStream) -> TokenStream {
@@ -72,6 +74,32 @@ pub fn impl_conversion_functions_for_junctions_v4(input: TokenStream) -> TokenSt
 		.into()
 }
 
+/// Generates the same conversions from tuples of junctions that `Junctions` has, for a custom
+/// type and up to the given number of junctions:
+/// generate_junction_conversion_impls!(MyJunctions, 8);
+/// let junctions: MyJunctions = (Parachain(1000), MyJunction(5)).into();
+///
+/// The type must implement `From<xcm::latest::Junctions>`, and the tuple elements may be of any
+/// type implementing `Into<xcm::latest::Junction>`.
+#[proc_macro]
+pub fn generate_junction_conversion_impls(input: TokenStream) -> TokenStream {
+	v4::junctions::generate_custom_conversion_functions(input)
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
+
+/// Constructs the `Location` from the list of its parents and junctions:
+/// let location = junctions![P, P, Parachain(1000), PalletInstance(5)];
+///
+/// Parents may be written as `P` or `Parent` and must precede all junctions. Locations with more
+/// than 8 parents or junctions are rejected at compile time, instead of panicking at runtime.
+#[proc_macro]
+pub fn junctions(input: TokenStream) -> TokenStream {
+	v4::junctions::generate_junctions(input)
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
+
 /// This is called on the `Instruction` enum, not on the `Xcm` struct,
 /// and allows for the following syntax for building XCMs:
 /// let message = Xcm::builder()
@@ -86,3 +114,44 @@ pub fn derive_builder(input: TokenStream) -> TokenStream {
 		.unwrap_or_else(syn::Error::into_compile_error)
 		.into()
 }
+
+/// Implements `DescribeLocation` for a unit struct, describing the locations which match a
+/// pattern:
+/// #[derive(DescribeLocation)]
+/// #[describe(pattern = "(0, [PalletInstance(index)])", tag = b"Pallet")]
+/// pub struct DescribeMyPallet;
+///
+/// The pattern is matched against `Location::unpack`, and the description is the SCALE encoding
+/// of the tag followed by the variables bound in the pattern, in order. Patterns which don't
+/// match a location with known junctions are rejected at compile time.
+#[proc_macro_derive(DescribeLocation, attributes(describe))]
+pub fn derive_describe_location(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+	describe_location::derive(input)
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
+
+/// Generates the `xcm_executor::Config` implementation of a runtime, filling in defaults for the
+/// omitted fields. It must be used through `xcm_builder::xcm_config!`, which passes the path of
+/// `xcm_builder` to it, so that the generated code doesn't depend on the crates of the runtime:
+/// xcm_config! {
+/// 	pub struct XcmConfig;
+/// 	RuntimeCall = RuntimeCall;
+/// 	XcmSender = XcmRouter;
+/// 	UniversalLocation = UniversalLocation;
+/// 	Barrier = Barrier;
+/// 	Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
+/// }
+///
+/// `RuntimeCall`, `XcmSender`, `UniversalLocation`, `Barrier` and `Weigher` are mandatory. The
+/// other fields default to `()`, or to `Nothing` for the filters, so that nothing is allowed
+/// unless configured. `CallDispatcher` defaults to `RuntimeCall`, `MaxAssetsIntoHolding` to 64
+/// and `TransactionalProcessor` to `FrameTransactionalProcessor`, so that the effects of failed
+/// instructions are rolled back.
+#[proc_macro]
+pub fn xcm_config(input: TokenStream) -> TokenStream {
+	xcm_config::generate(input)
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
@@ -140,6 +140,106 @@ pub mod junctions {
 		})
 	}
 
+	/// Generates conversion functions from tuples of junctions to a custom type, which can be
+	/// converted from `Junctions`:
+	/// - generate_junction_conversion_impls!(MyJunctions, 8)
+	pub fn generate_custom_conversion_functions(
+		input: proc_macro::TokenStream,
+	) -> Result<TokenStream> {
+		let CustomConversionInput { target, max_junctions } = syn::parse(input)?;
+		let max_junctions = max_junctions
+			.base10_parse::<usize>()
+			.ok()
+			.filter(|max| (1..=MAX_JUNCTIONS).contains(max))
+			.ok_or_else(|| {
+				syn::Error::new(
+					max_junctions.span(),
+					format!(
+						"The maximum number of junctions must be between 1 and {MAX_JUNCTIONS}"
+					),
+				)
+			})?;
+
+		let junction = quote!(::xcm::latest::Junction);
+		let junctions = quote!(::xcm::latest::Junctions);
+		Ok((1..=max_junctions)
+			.map(|num_junctions| {
+				let idents =
+					(0..num_junctions).map(|i| format_ident!("j{}", i)).collect::<Vec<_>>();
+				let types = (0..num_junctions).map(|i| format_ident!("J{}", i)).collect::<Vec<_>>();
+
+				quote! {
+					impl<#(#types : Into<#junction>,)*> From<( #(#types,)* )> for #target {
+						fn from( ( #(#idents,)* ): ( #(#types,)* ) ) -> Self {
+							Self::from(#junctions::from([#(Into::<#junction>::into(#idents)),*]))
+						}
+					}
+				}
+			})
+			.collect())
+	}
+
+	/// Generates the `Location` from the list of junctions, preceded by parents:
+	/// - junctions![P, P, Parachain(1000), PalletInstance(5)]
+	///
+	/// Parents may be written as `P` or `Parent`. Both parents and junctions are limited to
+	/// `MAX_JUNCTIONS`, so that too long locations are rejected at compile time.
+	pub fn generate_junctions(input: proc_macro::TokenStream) -> Result<TokenStream> {
+		let items = syn::parse::Parser::parse(
+			syn::punctuated::Punctuated::<syn::Expr, Token![,]>::parse_terminated,
+			input,
+		)?;
+
+		let is_parent = |item: &syn::Expr| match item {
+			syn::Expr::Path(path) => path.path.is_ident("P") || path.path.is_ident("Parent"),
+			_ => false,
+		};
+		let parents = items.iter().take_while(|item| is_parent(item)).collect::<Vec<_>>();
+		let junctions = items.iter().skip(parents.len()).collect::<Vec<_>>();
+
+		if let Some(parent) = junctions.iter().find(|item| is_parent(item)) {
+			return Err(syn::Error::new_spanned(parent, "Parents must precede all junctions"))
+		}
+		if let Some(parent) = parents.get(MAX_JUNCTIONS) {
+			return Err(syn::Error::new_spanned(
+				parent,
+				format!("Expected at most {MAX_JUNCTIONS} parents, found {}", parents.len()),
+			))
+		}
+		if let Some(junction) = junctions.get(MAX_JUNCTIONS) {
+			return Err(syn::Error::new_spanned(
+				junction,
+				format!("Expected at most {MAX_JUNCTIONS} junctions, found {}", junctions.len()),
+			))
+		}
+
+		let num_parents = parents.len() as u8;
+		let junction = quote!(::xcm::latest::Junction);
+		let interior = if junctions.is_empty() {
+			quote!(::xcm::latest::Junctions::Here)
+		} else {
+			quote!(::xcm::latest::Junctions::from([#(Into::<#junction>::into(#junctions)),*]))
+		};
+		Ok(quote! {
+			::xcm::latest::Location::new(#num_parents, #interior)
+		})
+	}
+
+	struct CustomConversionInput {
+		target: syn::Type,
+		max_junctions: syn::LitInt,
+	}
+
+	impl syn::parse::Parse for CustomConversionInput {
+		fn parse(input: syn::parse::ParseStream) -> Result<Self> {
+			let target = input.parse()?;
+			input.parse::<Token![,]>()?;
+			let max_junctions = input.parse()?;
+			input.parse::<Option<Token![,]>>()?;
+			Ok(Self { target, max_junctions })
+		}
+	}
+
 	fn generate_conversion_from_tuples(max_junctions: usize) -> TokenStream {
 		(1..=max_junctions)
 			.map(|num_junctions| {
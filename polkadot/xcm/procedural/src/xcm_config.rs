@@ -0,0 +1,176 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Macro generating the `xcm_executor::Config` implementation of a runtime.
+
+use proc_macro2::{Span, TokenStream as TokenStream2, TokenTree};
+use quote::quote;
+use syn::{
+	parse::{Parse, ParseStream},
+	Error, Ident, Result, Token, Type, Visibility,
+};
+
+/// The fields which have no sensible default, with a description used in the error reported when
+/// they are missing.
+const MANDATORY: &[(&str, &str)] = &[
+	("RuntimeCall", "the aggregated `RuntimeCall` of the runtime"),
+	("XcmSender", "the router sending XCMs to other locations"),
+	("UniversalLocation", "the location of the chain in the consensus universe"),
+	("Barrier", "the barrier deciding which XCMs are executed"),
+	("Weigher", "the means of weighing XCMs"),
+];
+
+/// The fields which may be omitted, with their default.
+fn defaults(krate: &TokenTree, runtime_call: &Type) -> Vec<(&'static str, TokenStream2)> {
+	vec![
+		("AssetTransactor", quote!(())),
+		("OriginConverter", quote!(())),
+		("IsReserve", quote!(#krate::__private::frame_support::traits::Nothing)),
+		("IsTeleporter", quote!(#krate::__private::frame_support::traits::Nothing)),
+		("Aliasers", quote!(#krate::__private::frame_support::traits::Nothing)),
+		("Trader", quote!(())),
+		("ResponseHandler", quote!(())),
+		("AssetTrap", quote!(())),
+		("AssetLocker", quote!(())),
+		("AssetExchanger", quote!(())),
+		("AssetClaims", quote!(())),
+		("SubscriptionService", quote!(())),
+		("PalletInstancesInfo", quote!(())),
+		("MaxAssetsIntoHolding", quote!(#krate::__private::frame_support::traits::ConstU32<64>)),
+		("FeeManager", quote!(())),
+		("MessageExporter", quote!(())),
+		("UniversalAliases", quote!(#krate::__private::frame_support::traits::Nothing)),
+		("CallDispatcher", quote!(#runtime_call)),
+		("SafeCallFilter", quote!(#krate::__private::frame_support::traits::Nothing)),
+		("TransactionalProcessor", quote!(#krate::FrameTransactionalProcessor)),
+		("HrmpNewChannelOpenRequestHandler", quote!(())),
+		("HrmpChannelAcceptedHandler", quote!(())),
+		("HrmpChannelClosingHandler", quote!(())),
+	]
+}
+
+/// The order of the fields in the generated implementation, which is the order of the
+/// declaration in `xcm_executor::Config`.
+const ORDER: &[&str] = &[
+	"RuntimeCall",
+	"XcmSender",
+	"AssetTransactor",
+	"OriginConverter",
+	"IsReserve",
+	"IsTeleporter",
+	"Aliasers",
+	"UniversalLocation",
+	"Barrier",
+	"Weigher",
+	"Trader",
+	"ResponseHandler",
+	"AssetTrap",
+	"AssetLocker",
+	"AssetExchanger",
+	"AssetClaims",
+	"SubscriptionService",
+	"PalletInstancesInfo",
+	"MaxAssetsIntoHolding",
+	"FeeManager",
+	"MessageExporter",
+	"UniversalAliases",
+	"CallDispatcher",
+	"SafeCallFilter",
+	"TransactionalProcessor",
+	"HrmpNewChannelOpenRequestHandler",
+	"HrmpChannelAcceptedHandler",
+	"HrmpChannelClosingHandler",
+];
+
+/// The input of `xcm_config!`: the path of `xcm_builder`, which re-exports the crates the generated
+/// code refers to, followed by the declaration of the config type and its fields.
+struct XcmConfigInput {
+	krate: TokenTree,
+	vis: Visibility,
+	name: Ident,
+	fields: Vec<(Ident, Type)>,
+}
+
+impl Parse for XcmConfigInput {
+	fn parse(input: ParseStream) -> Result<Self> {
+		// `$crate` of the `xcm_config!` macro of `xcm_builder`
+		let krate = input.parse()?;
+		input.parse::<Token![;]>()?;
+		let vis = input.parse()?;
+		input.parse::<Token![struct]>()?;
+		let name = input.parse()?;
+		input.parse::<Token![;]>()?;
+
+		let mut fields: Vec<(Ident, Type)> = Vec::new();
+		while !input.is_empty() {
+			let field: Ident = input.parse()?;
+			if !ORDER.contains(&field.to_string().as_str()) {
+				return Err(Error::new(
+					field.span(),
+					format!("Unknown field `{field}` of `xcm_executor::Config`"),
+				))
+			}
+			if fields.iter().any(|(existing, _)| *existing == field) {
+				return Err(Error::new(field.span(), format!("Duplicate field `{field}`")))
+			}
+			input.parse::<Token![=]>()?;
+			let ty = input.parse()?;
+			input.parse::<Token![;]>()?;
+			fields.push((field, ty));
+		}
+
+		Ok(Self { krate, vis, name, fields })
+	}
+}
+
+pub fn generate(input: proc_macro::TokenStream) -> Result<TokenStream2> {
+	let XcmConfigInput { krate, vis, name, fields } = syn::parse(input)?;
+	let field = |name: &str| fields.iter().find(|(field, _)| field == name).map(|(_, ty)| ty);
+
+	let missing = MANDATORY
+		.iter()
+		.filter(|(name, _)| field(name).is_none())
+		.map(|(name, description)| format!("`{name}` ({description})"))
+		.collect::<Vec<_>>();
+	if !missing.is_empty() {
+		return Err(Error::new(
+			Span::call_site(),
+			format!("Missing mandatory fields of `xcm_config!`: {}", missing.join(", ")),
+		))
+	}
+
+	let runtime_call = field("RuntimeCall").expect("`RuntimeCall` is mandatory; qed");
+	let defaults = defaults(&krate, runtime_call);
+	let types = ORDER.iter().map(|name| {
+		let ident = Ident::new(name, Span::call_site());
+		let ty = match field(name) {
+			Some(ty) => quote!(#ty),
+			None => defaults
+				.iter()
+				.find(|(default, _)| default == name)
+				.map(|(_, ty)| ty.clone())
+				.expect("Every field is either mandatory or has a default; qed"),
+		};
+		quote!(type #ident = #ty;)
+	});
+
+	Ok(quote! {
+		#vis struct #name;
+		impl #krate::__private::xcm_executor::Config for #name {
+			#(#types)*
+		}
+	})
+}
@@ -0,0 +1,79 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use parity_scale_codec::Encode;
+use xcm::latest::prelude::*;
+use xcm_builder::{
+	DescribeAccountId32Terminal, DescribeLocation, DescribePalletTerminal,
+	DescribeTreasuryVoiceTerminal,
+};
+
+#[derive(DescribeLocation)]
+#[describe(pattern = "(0, [AccountId32 { id, .. }])", tag = b"AccountId32")]
+struct DerivedAccountId32Terminal;
+
+#[derive(DescribeLocation)]
+#[describe(
+	pattern = "(0, [Plurality { id: BodyId::Treasury, part: BodyPart::Voice }])",
+	tag = b"Treasury"
+)]
+struct DerivedTreasuryTerminal;
+
+#[derive(DescribeLocation)]
+#[describe(pattern = "(parents, [Parachain(para_id), PalletInstance(index)])", tag = b"Pallet")]
+struct DerivedRemotePallet;
+
+#[derive(DescribeLocation)]
+#[describe(pattern = "(1, [OnlyChild])", tag = b"ParentChild")]
+struct DerivedParentChild;
+
+#[test]
+fn derived_describe_location_matches_hand_written() {
+	let account = Location::new(0, [AccountId32 { network: None, id: [7; 32] }]);
+	assert_eq!(
+		DerivedAccountId32Terminal::describe_location(&account),
+		DescribeAccountId32Terminal::describe_location(&account),
+	);
+	assert!(DerivedAccountId32Terminal::describe_location(&account).is_some());
+
+	let treasury = Location::new(0, [Plurality { id: BodyId::Treasury, part: BodyPart::Voice }]);
+	assert_eq!(
+		DerivedTreasuryTerminal::describe_location(&treasury),
+		Some((b"Treasury",).encode()),
+	);
+	assert!(DescribeTreasuryVoiceTerminal::describe_location(&treasury).is_some());
+
+	let pallet = Location::new(0, [PalletInstance(50)]);
+	assert_eq!(DerivedAccountId32Terminal::describe_location(&pallet), None);
+	assert_eq!(DerivedTreasuryTerminal::describe_location(&pallet), None);
+	assert!(DescribePalletTerminal::describe_location(&pallet).is_some());
+}
+
+#[test]
+fn derived_describe_location_encodes_bindings_in_order() {
+	let remote_pallet = Location::new(2, [Parachain(1000), PalletInstance(50)]);
+	assert_eq!(
+		DerivedRemotePallet::describe_location(&remote_pallet),
+		Some((b"Pallet", 2u8, 1000u32, 50u8).encode()),
+	);
+	assert_eq!(DerivedRemotePallet::describe_location(&Location::new(0, [Parachain(1000)])), None);
+
+	assert_eq!(
+		DerivedParentChild::describe_location(&Location::new(1, [OnlyChild])),
+		Some((b"ParentChild",).encode()),
+	);
+	assert_eq!(DerivedParentChild::describe_location(&Location::new(0, [OnlyChild])), None);
+}
@@ -0,0 +1,75 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use xcm::latest::prelude::*;
+
+/// A custom junction, standing for a pallet instance.
+struct MyJunction(u8);
+
+impl From<MyJunction> for Junction {
+	fn from(MyJunction(index): MyJunction) -> Self {
+		PalletInstance(index)
+	}
+}
+
+/// A custom wrapper of junctions.
+#[derive(Debug, PartialEq)]
+struct MyJunctions(Junctions);
+
+impl From<Junctions> for MyJunctions {
+	fn from(junctions: Junctions) -> Self {
+		Self(junctions)
+	}
+}
+
+xcm::generate_junction_conversion_impls!(MyJunctions, 8);
+
+#[test]
+fn tuple_syntax_for_custom_junctions_works() {
+	let junctions: MyJunctions = (Parachain(1000),).into();
+	assert_eq!(junctions, MyJunctions([Parachain(1000)].into()));
+
+	let junctions: MyJunctions = (Parachain(1000), MyJunction(5)).into();
+	assert_eq!(junctions, MyJunctions([Parachain(1000), PalletInstance(5)].into()));
+
+	let junctions: MyJunctions = (
+		MyJunction(0),
+		MyJunction(1),
+		MyJunction(2),
+		MyJunction(3),
+		MyJunction(4),
+		MyJunction(5),
+		MyJunction(6),
+		GeneralIndex(7),
+	)
+		.into();
+	assert_eq!(
+		junctions,
+		MyJunctions(
+			[
+				PalletInstance(0),
+				PalletInstance(1),
+				PalletInstance(2),
+				PalletInstance(3),
+				PalletInstance(4),
+				PalletInstance(5),
+				PalletInstance(6),
+				GeneralIndex(7),
+			]
+			.into()
+		)
+	);
+}
@@ -0,0 +1,59 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use xcm::{junctions, latest::prelude::*};
+
+#[test]
+fn junctions_macro_works() {
+	assert_eq!(junctions![], Location::here());
+	assert_eq!(junctions![Parachain(1000)], Location::new(0, [Parachain(1000)]));
+	assert_eq!(junctions![P, Parachain(1)], Location::new(1, [Parachain(1)]));
+	assert_eq!(
+		junctions![P, Parent, Parachain(1000), PalletInstance(5)],
+		Location::new(2, [Parachain(1000), PalletInstance(5)]),
+	);
+	assert_eq!(junctions![P, P, P, P, P, P, P, P], Location::new(8, Here));
+}
+
+#[test]
+fn junctions_macro_works_with_max_junctions() {
+	assert_eq!(
+		junctions![
+			P,
+			Parachain(0),
+			PalletInstance(1),
+			GeneralIndex(2),
+			PalletInstance(3),
+			GeneralIndex(4),
+			PalletInstance(5),
+			GeneralIndex(6),
+			PalletInstance(7),
+		],
+		Location::new(
+			1,
+			[
+				Parachain(0),
+				PalletInstance(1),
+				GeneralIndex(2),
+				PalletInstance(3),
+				GeneralIndex(4),
+				PalletInstance(5),
+				GeneralIndex(6),
+				PalletInstance(7),
+			]
+		),
+	);
+}
@@ -0,0 +1,114 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use frame_support::{
+	dispatch::{DispatchInfo, DispatchResultWithPostInfo, GetDispatchInfo, PostDispatchInfo},
+	parameter_types,
+	sp_runtime::traits::Dispatchable,
+	traits::{ConstU32, Everything, Get, Nothing},
+};
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use xcm::latest::prelude::*;
+use xcm_builder::{
+	xcm_config, AllowUnpaidExecutionFrom, FixedWeightBounds, FrameTransactionalProcessor,
+};
+use xcm_executor::Config;
+
+/// A call which does nothing.
+#[derive(Clone, Eq, PartialEq, Debug, Encode, Decode, TypeInfo)]
+pub struct TestCall;
+
+impl Dispatchable for TestCall {
+	type RuntimeOrigin = ();
+	type Config = ();
+	type Info = ();
+	type PostInfo = PostDispatchInfo;
+	fn dispatch(self, _origin: Self::RuntimeOrigin) -> DispatchResultWithPostInfo {
+		Ok(PostDispatchInfo::default())
+	}
+}
+
+impl GetDispatchInfo for TestCall {
+	fn get_dispatch_info(&self) -> DispatchInfo {
+		DispatchInfo::default()
+	}
+}
+
+parameter_types! {
+	pub UniversalLocation: InteriorLocation = [GlobalConsensus(Polkadot), Parachain(1000)].into();
+	pub const UnitWeightCost: Weight = Weight::from_parts(10, 10);
+}
+
+xcm_config! {
+	pub struct MinimalXcmConfig;
+	RuntimeCall = TestCall;
+	XcmSender = ();
+	UniversalLocation = UniversalLocation;
+	Barrier = AllowUnpaidExecutionFrom<Everything>;
+	Weigher = FixedWeightBounds<UnitWeightCost, TestCall, ConstU32<100>>;
+}
+
+xcm_config! {
+	struct CustomXcmConfig;
+	Weigher = FixedWeightBounds<UnitWeightCost, TestCall, ConstU32<100>>;
+	Barrier = AllowUnpaidExecutionFrom<Everything>;
+	UniversalLocation = UniversalLocation;
+	XcmSender = ();
+	RuntimeCall = TestCall;
+	IsReserve = Everything;
+	MaxAssetsIntoHolding = ConstU32<8>;
+}
+
+/// Whether `T` is `U`.
+fn is<T: 'static, U: 'static>() -> bool {
+	core::any::TypeId::of::<T>() == core::any::TypeId::of::<U>()
+}
+
+#[test]
+fn minimal_xcm_config_works() {
+	assert!(is::<<MinimalXcmConfig as Config>::RuntimeCall, TestCall>());
+	assert!(is::<<MinimalXcmConfig as Config>::CallDispatcher, TestCall>());
+	assert!(is::<<MinimalXcmConfig as Config>::IsReserve, Nothing>());
+	assert!(is::<<MinimalXcmConfig as Config>::SafeCallFilter, Nothing>());
+	assert!(is::<<MinimalXcmConfig as Config>::AssetTransactor, ()>());
+	assert!(
+		is::<<MinimalXcmConfig as Config>::TransactionalProcessor, FrameTransactionalProcessor>()
+	);
+	assert_eq!(<MinimalXcmConfig as Config>::MaxAssetsIntoHolding::get(), 64);
+	assert_eq!(<MinimalXcmConfig as Config>::UniversalLocation::get(), UniversalLocation::get());
+
+	// instructions are executed in storage transactions, so we need externalities
+	sp_io::TestExternalities::default().execute_with(|| {
+		let message = Xcm::<TestCall>(vec![ClearOrigin]);
+		let mut hash = [0u8; 32];
+		let outcome = xcm_executor::XcmExecutor::<MinimalXcmConfig>::prepare_and_execute(
+			Parent,
+			message,
+			&mut hash,
+			Weight::MAX,
+			Weight::zero(),
+		);
+		assert_eq!(outcome, Outcome::Complete { used: UnitWeightCost::get() });
+	});
+}
+
+#[test]
+fn custom_xcm_config_fields_override_the_defaults() {
+	assert!(is::<<CustomXcmConfig as Config>::IsReserve, Everything>());
+	assert!(is::<<CustomXcmConfig as Config>::IsTeleporter, Nothing>());
+	assert_eq!(<CustomXcmConfig as Config>::MaxAssetsIntoHolding::get(), 8);
+}
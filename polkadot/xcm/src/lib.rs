@@ -43,6 +43,8 @@ pub mod latest {
 mod double_encoded;
 pub use double_encoded::DoubleEncoded;
 
+pub use xcm_procedural::{generate_junction_conversion_impls, junctions};
+
 #[cfg(test)]
 mod tests;
 
@@ -19,7 +19,7 @@
 use crate::{CreateMatcher, MatchXcm};
 use frame_support::{
 	ensure,
-	traits::{Contains, Get, ProcessMessageError},
+	traits::{ConstBool, Contains, Get, ProcessMessageError},
 };
 use polkadot_parachain_primitives::primitives::IsSystem;
 use sp_std::{cell::Cell, marker::PhantomData, ops::ControlFlow, result::Result};
@@ -440,3 +440,280 @@ impl ShouldExecute for DenyReserveTransferToRelayChain {
 		Ok(())
 	}
 }
+
+/// Rejects messages with the `ExportMessage` instruction, unless they come from one of the
+/// `TrustedBridgeHubs`. The `ExportMessage` instruction is also looked up in the `SetAppendix` and
+/// `SetErrorHandler` programs, which are executed locally too.
+///
+/// Messages without the `ExportMessage` instruction are permitted, so this barrier is supposed to
+/// be used as the `Deny` part of the `DenyThenTry` barrier.
+pub struct ValidateXcmOriginForBridge<TrustedBridgeHubs>(PhantomData<TrustedBridgeHubs>);
+impl<TrustedBridgeHubs: Contains<Location>> ShouldExecute
+	for ValidateXcmOriginForBridge<TrustedBridgeHubs>
+{
+	fn should_execute<RuntimeCall>(
+		origin: &Location,
+		instructions: &mut [Instruction<RuntimeCall>],
+		max_weight: Weight,
+		properties: &mut Properties,
+	) -> Result<(), ProcessMessageError> {
+		log::trace!(
+			target: "xcm::barriers",
+			"ValidateXcmOriginForBridge origin: {:?}, instructions: {:?}, max_weight: {:?}, properties: {:?}",
+			origin, instructions, max_weight, properties,
+		);
+		if contains_export_message(instructions) && !TrustedBridgeHubs::contains(origin) {
+			log::debug!(
+				target: "xcm::barriers",
+				"ValidateXcmOriginForBridge rejected export message from untrusted origin {:?}",
+				origin,
+			);
+			return Err(ProcessMessageError::Unsupported)
+		}
+
+		// Permit everything else
+		Ok(())
+	}
+}
+
+/// Returns `true` if the program or any of its locally executed nested programs contains the
+/// `ExportMessage` instruction.
+fn contains_export_message<RuntimeCall>(instructions: &[Instruction<RuntimeCall>]) -> bool {
+	instructions.iter().any(|instruction| match instruction {
+		ExportMessage { .. } => true,
+		SetAppendix(xcm) | SetErrorHandler(xcm) => contains_export_message(&xcm.0),
+		_ => false,
+	})
+}
+
+/// Rejects messages that have more than `MaxCount` instructions.
+///
+/// If `Recursive` is `true`, instructions of nested programs (e.g. `SetAppendix` or
+/// `DepositReserveAsset` programs) are counted too. Otherwise, only top-level instructions are
+/// counted.
+///
+/// Messages within the limit are accepted without any other checks, so this barrier must not be
+/// used on its own. It is supposed to be used as the `Deny` part of the `DenyThenTry` barrier,
+/// which only executes the messages that are also accepted by its `Allow` part.
+pub struct MaxInstructionCountBarrier<MaxCount, Recursive = ConstBool<false>>(
+	PhantomData<(MaxCount, Recursive)>,
+);
+impl<MaxCount: Get<u32>, Recursive: Get<bool>> ShouldExecute
+	for MaxInstructionCountBarrier<MaxCount, Recursive>
+{
+	fn should_execute<RuntimeCall>(
+		origin: &Location,
+		instructions: &mut [Instruction<RuntimeCall>],
+		max_weight: Weight,
+		_properties: &mut Properties,
+	) -> Result<(), ProcessMessageError> {
+		log::trace!(
+			target: "xcm::barriers",
+			"MaxInstructionCountBarrier origin: {:?}, instructions: {:?}, max_weight: {:?}, properties: {:?}",
+			origin, instructions, max_weight, _properties,
+		);
+		let instruction_count = count_instructions(instructions, Recursive::get());
+		ensure!(
+			instruction_count <= MaxCount::get() as usize,
+			ProcessMessageError::Overweight(max_weight)
+		);
+		Ok(())
+	}
+}
+
+/// Returns number of instructions in the program, optionally including instructions of nested
+/// programs.
+fn count_instructions<RuntimeCall>(
+	instructions: &[Instruction<RuntimeCall>],
+	recursive: bool,
+) -> usize {
+	instructions.iter().fold(0usize, |count, instruction| {
+		let nested_count = if recursive {
+			match instruction {
+				SetAppendix(xcm) | SetErrorHandler(xcm) => count_instructions(&xcm.0, recursive),
+				TransferReserveAsset { xcm, .. } |
+				DepositReserveAsset { xcm, .. } |
+				InitiateReserveWithdraw { xcm, .. } |
+				InitiateTeleport { xcm, .. } |
+				ExportMessage { xcm, .. } => count_instructions(&xcm.0, recursive),
+				_ => 0,
+			}
+		} else {
+			0
+		};
+		count.saturating_add(1).saturating_add(nested_count)
+	})
+}
+
+/// Allows execution only if all barriers of the `Barriers` tuple allow it. The empty tuple
+/// rejects every message, same as the empty tuple of barriers does.
+///
+/// Barriers are evaluated in order and evaluation stops at the first barrier that rejects the
+/// message. Note that barriers that were evaluated before may have already altered the message
+/// or its properties (e.g. `TakeWeightCredit` may have taken the weight credit).
+pub struct AllOf<Barriers>(PhantomData<Barriers>);
+impl<Barriers: ShouldExecuteAll> ShouldExecute for AllOf<Barriers> {
+	fn should_execute<RuntimeCall>(
+		origin: &Location,
+		instructions: &mut [Instruction<RuntimeCall>],
+		max_weight: Weight,
+		properties: &mut Properties,
+	) -> Result<(), ProcessMessageError> {
+		log::trace!(
+			target: "xcm::barriers",
+			"AllOf origin: {:?}, instructions: {:?}, max_weight: {:?}, properties: {:?}",
+			origin, instructions, max_weight, properties,
+		);
+		Barriers::should_execute_all(origin, instructions, max_weight, properties)
+	}
+}
+
+/// Allows execution if any barrier of the `Barriers` tuple allows it.
+///
+/// This is equivalent to using the `Barriers` tuple directly and exists to make barrier
+/// compositions that include `AllOf` more explicit.
+pub struct AnyOf<Barriers>(PhantomData<Barriers>);
+impl<Barriers: ShouldExecute> ShouldExecute for AnyOf<Barriers> {
+	fn should_execute<RuntimeCall>(
+		origin: &Location,
+		instructions: &mut [Instruction<RuntimeCall>],
+		max_weight: Weight,
+		properties: &mut Properties,
+	) -> Result<(), ProcessMessageError> {
+		log::trace!(
+			target: "xcm::barriers",
+			"AnyOf origin: {:?}, instructions: {:?}, max_weight: {:?}, properties: {:?}",
+			origin, instructions, max_weight, properties,
+		);
+		Barriers::should_execute(origin, instructions, max_weight, properties)
+	}
+}
+
+/// Tuple of barriers that all should allow execution of the message. Used by the `AllOf` barrier.
+pub trait ShouldExecuteAll {
+	/// Returns `Ok(())` if all barriers allow execution of the message. Otherwise, returns error
+	/// of the first barrier that has rejected the message. Returns
+	/// `ProcessMessageError::Unsupported` if there are no barriers.
+	fn should_execute_all<RuntimeCall>(
+		origin: &Location,
+		instructions: &mut [Instruction<RuntimeCall>],
+		max_weight: Weight,
+		properties: &mut Properties,
+	) -> Result<(), ProcessMessageError>;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+#[tuple_types_custom_trait_bound(ShouldExecute)]
+impl ShouldExecuteAll for Tuple {
+	fn should_execute_all<RuntimeCall>(
+		origin: &Location,
+		instructions: &mut [Instruction<RuntimeCall>],
+		max_weight: Weight,
+		properties: &mut Properties,
+	) -> Result<(), ProcessMessageError> {
+		let mut barriers = 0usize;
+		for_tuples!( #(
+			Tuple::should_execute(origin, instructions, max_weight, properties)?;
+			barriers += 1;
+		)* );
+		if barriers == 0 {
+			return Err(ProcessMessageError::Unsupported)
+		}
+		Ok(())
+	}
+}
+
+/// Barrier that adapts to the chain load.
+///
+/// When `CongestionIndicator` reports that the chain is not congested, it simply delegates to the
+/// `Inner` barrier. Otherwise, only messages from origins that match the `TrustedOrigins` filter
+/// are passed to the `Inner` barrier. Messages from other origins are rejected with the
+/// `ProcessMessageError::Yield` error.
+///
+/// Note that a rejected message is not deferred until the congestion is gone. The executor reports
+/// any barrier error as `XcmError::Barrier` and the message queue drops the message as
+/// unsupported, so the sender has to send it again.
+pub struct CongestionAwareBarrier<Inner, CongestionIndicator, TrustedOrigins>(
+	PhantomData<(Inner, CongestionIndicator, TrustedOrigins)>,
+);
+impl<Inner, CongestionIndicator, TrustedOrigins> ShouldExecute
+	for CongestionAwareBarrier<Inner, CongestionIndicator, TrustedOrigins>
+where
+	Inner: ShouldExecute,
+	CongestionIndicator: Get<bool>,
+	TrustedOrigins: Contains<Location>,
+{
+	fn should_execute<RuntimeCall>(
+		origin: &Location,
+		instructions: &mut [Instruction<RuntimeCall>],
+		max_weight: Weight,
+		properties: &mut Properties,
+	) -> Result<(), ProcessMessageError> {
+		log::trace!(
+			target: "xcm::barriers",
+			"CongestionAwareBarrier origin: {:?}, instructions: {:?}, max_weight: {:?}, properties: {:?}",
+			origin, instructions, max_weight, properties,
+		);
+		if CongestionIndicator::get() && !TrustedOrigins::contains(origin) {
+			log::debug!(
+				target: "xcm::barriers",
+				"CongestionAwareBarrier rejected message from untrusted origin {:?}: chain is congested",
+				origin,
+			);
+			return Err(ProcessMessageError::Yield)
+		}
+
+		Inner::should_execute(origin, instructions, max_weight, properties)
+	}
+}
+
+/// Observer of barrier decisions, that may be used for logging, metrics or off-chain tracing.
+pub trait ObserveBarrierResult {
+	/// Whether the observer is interested in the allowed messages. The message, passed to
+	/// `on_pass`, is copied out of the instructions only if it is.
+	const OBSERVES_PASSES: bool = true;
+
+	/// Called when the barrier has allowed execution of the `message`.
+	fn on_pass(origin: &Location, message: &Xcm<()>);
+	/// Called when the barrier has rejected the message with given `error`.
+	fn on_deny(origin: &Location, error: &ProcessMessageError);
+}
+
+/// Observer that ignores all barrier decisions.
+pub struct NullObserver;
+impl ObserveBarrierResult for NullObserver {
+	const OBSERVES_PASSES: bool = false;
+
+	fn on_pass(_origin: &Location, _message: &Xcm<()>) {}
+	fn on_deny(_origin: &Location, _error: &ProcessMessageError) {}
+}
+
+/// Barrier that delegates to the `Inner` barrier and reports its decision to the `Observer`.
+pub struct BarrierWithObserver<Inner, Observer = NullObserver>(PhantomData<(Inner, Observer)>);
+impl<Inner: ShouldExecute, Observer: ObserveBarrierResult> ShouldExecute
+	for BarrierWithObserver<Inner, Observer>
+{
+	fn should_execute<RuntimeCall>(
+		origin: &Location,
+		instructions: &mut [Instruction<RuntimeCall>],
+		max_weight: Weight,
+		properties: &mut Properties,
+	) -> Result<(), ProcessMessageError> {
+		log::trace!(
+			target: "xcm::barriers",
+			"BarrierWithObserver origin: {:?}, instructions: {:?}, max_weight: {:?}, properties: {:?}",
+			origin, instructions, max_weight, properties,
+		);
+		let result = Inner::should_execute(origin, instructions, max_weight, properties);
+		match result {
+			Ok(()) if Observer::OBSERVES_PASSES => {
+				let message =
+					Xcm::<()>(instructions.iter().cloned().map(Instruction::into).collect());
+				Observer::on_pass(origin, &message);
+			},
+			Ok(()) => {},
+			Err(ref error) => Observer::on_deny(origin, error),
+		}
+		result
+	}
+}
@@ -17,12 +17,17 @@
 //! Adapters to work with [`frame_support::traits::fungible`] through XCM.
 
 use super::MintLocation;
-use frame_support::traits::{
-	tokens::{
-		fungible, Fortitude::Polite, Precision::Exact, Preservation::Preserve, Provenance::Minted,
+use frame_support::{
+	storage::{with_transaction, TransactionOutcome},
+	traits::{
+		tokens::{
+			fungible, Fortitude::Polite, Precision::Exact, Preservation::Preserve,
+			Provenance::Minted,
+		},
+		Get,
 	},
-	Get,
 };
+use sp_runtime::DispatchError;
 use sp_std::{marker::PhantomData, prelude::*, result};
 use xcm::latest::prelude::*;
 use xcm_executor::{
@@ -306,3 +311,153 @@ impl<
 		)
 	}
 }
+
+/// Hook, called by [`FungibleAdapterWithHooks`] when the funds are moved.
+///
+/// `from` is `None` when the funds are minted on deposit and `to` is `None` when the funds are
+/// burned on withdrawal. Can be amalgamated into a tuple, the elements are called in order and the
+/// first error is returned.
+pub trait TransferHook<AccountId, Balance> {
+	/// Called when `amount` is moved from `from` to `to`.
+	fn on_transfer(
+		from: Option<&AccountId>,
+		to: Option<&AccountId>,
+		amount: Balance,
+	) -> Result<(), XcmError>;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl<AccountId, Balance: Clone> TransferHook<AccountId, Balance> for Tuple {
+	fn on_transfer(
+		from: Option<&AccountId>,
+		to: Option<&AccountId>,
+		amount: Balance,
+	) -> Result<(), XcmError> {
+		for_tuples!( #( Tuple::on_transfer(from, to, amount.clone())?; )* );
+		Ok(())
+	}
+}
+
+/// [`TransactAsset`] implementation that allows the use of a [`fungible`] implementation for
+/// handling an asset in the XCM executor, calling hooks whenever the funds are moved.
+///
+/// `BeforeT` is called before the funds are deposited, withdrawn or transferred and may veto the
+/// movement by returning an error. `AfterT` is called after the funds are moved, and if it returns
+/// an error, the movement is rolled back. Teleported assets are not accounted in any checking
+/// account.
+pub struct FungibleAdapterWithHooks<
+	Fungible,
+	Matcher,
+	AccountIdConverter,
+	AccountId,
+	BeforeT,
+	AfterT,
+>(PhantomData<(Fungible, Matcher, AccountIdConverter, AccountId, BeforeT, AfterT)>);
+
+impl<
+		Fungible: fungible::Mutate<AccountId>,
+		Matcher: MatchesFungible<Fungible::Balance>,
+		AccountIdConverter: ConvertLocation<AccountId>,
+		AccountId: Eq + Clone,
+		BeforeT: TransferHook<AccountId, Fungible::Balance>,
+		AfterT: TransferHook<AccountId, Fungible::Balance>,
+	> FungibleAdapterWithHooks<Fungible, Matcher, AccountIdConverter, AccountId, BeforeT, AfterT>
+{
+	/// Move `amount` of the funds with `move_funds`, calling the hooks around it.
+	fn move_with_hooks(
+		from: Option<&AccountId>,
+		to: Option<&AccountId>,
+		amount: Fungible::Balance,
+		move_funds: impl FnOnce() -> Result<(), DispatchError>,
+	) -> XcmResult {
+		BeforeT::on_transfer(from, to, amount)?;
+		with_transaction(|| -> TransactionOutcome<Result<_, DispatchError>> {
+			let result = move_funds()
+				.map_err(|error| XcmError::FailedToTransactAsset(error.into()))
+				.and_then(|()| AfterT::on_transfer(from, to, amount));
+			match &result {
+				Ok(()) => TransactionOutcome::Commit(Ok(result)),
+				_ => TransactionOutcome::Rollback(Ok(result)),
+			}
+		})
+		.map_err(|_| XcmError::ExceedsStackLimit)?
+	}
+}
+
+impl<
+		Fungible: fungible::Mutate<AccountId>,
+		Matcher: MatchesFungible<Fungible::Balance>,
+		AccountIdConverter: ConvertLocation<AccountId>,
+		AccountId: Eq + Clone,
+		BeforeT: TransferHook<AccountId, Fungible::Balance>,
+		AfterT: TransferHook<AccountId, Fungible::Balance>,
+	> TransactAsset
+	for FungibleAdapterWithHooks<Fungible, Matcher, AccountIdConverter, AccountId, BeforeT, AfterT>
+{
+	fn can_check_in(origin: &Location, what: &Asset, context: &XcmContext) -> XcmResult {
+		FungibleMutateAdapter::<Fungible, Matcher, AccountIdConverter, AccountId, ()>::can_check_in(
+			origin, what, context,
+		)
+	}
+
+	fn can_check_out(dest: &Location, what: &Asset, context: &XcmContext) -> XcmResult {
+		FungibleMutateAdapter::<Fungible, Matcher, AccountIdConverter, AccountId, ()>::can_check_out(
+			dest, what, context,
+		)
+	}
+
+	fn deposit_asset(what: &Asset, who: &Location, _context: Option<&XcmContext>) -> XcmResult {
+		log::trace!(
+			target: "xcm::fungible_adapter",
+			"deposit_asset with hooks what: {:?}, who: {:?}",
+			what, who,
+		);
+		let amount = Matcher::matches_fungible(what).ok_or(MatchError::AssetNotHandled)?;
+		let who = AccountIdConverter::convert_location(who)
+			.ok_or(MatchError::AccountIdConversionFailed)?;
+		Self::move_with_hooks(None, Some(&who), amount, || {
+			Fungible::mint_into(&who, amount).map(|_| ())
+		})
+	}
+
+	fn withdraw_asset(
+		what: &Asset,
+		who: &Location,
+		_context: Option<&XcmContext>,
+	) -> result::Result<AssetsInHolding, XcmError> {
+		log::trace!(
+			target: "xcm::fungible_adapter",
+			"withdraw_asset with hooks what: {:?}, who: {:?}",
+			what, who,
+		);
+		let amount = Matcher::matches_fungible(what).ok_or(MatchError::AssetNotHandled)?;
+		let who = AccountIdConverter::convert_location(who)
+			.ok_or(MatchError::AccountIdConversionFailed)?;
+		Self::move_with_hooks(Some(&who), None, amount, || {
+			Fungible::burn_from(&who, amount, Exact, Polite).map(|_| ())
+		})?;
+		Ok(what.clone().into())
+	}
+
+	fn internal_transfer_asset(
+		what: &Asset,
+		from: &Location,
+		to: &Location,
+		_context: &XcmContext,
+	) -> result::Result<AssetsInHolding, XcmError> {
+		log::trace!(
+			target: "xcm::fungible_adapter",
+			"internal_transfer_asset with hooks what: {:?}, from: {:?}, to: {:?}",
+			what, from, to
+		);
+		let amount = Matcher::matches_fungible(what).ok_or(MatchError::AssetNotHandled)?;
+		let source = AccountIdConverter::convert_location(from)
+			.ok_or(MatchError::AccountIdConversionFailed)?;
+		let dest = AccountIdConverter::convert_location(to)
+			.ok_or(MatchError::AccountIdConversionFailed)?;
+		Self::move_with_hooks(Some(&source), Some(&dest), amount, || {
+			Fungible::transfer(&source, &dest, amount, Preserve).map(|_| ())
+		})?;
+		Ok(what.clone().into())
+	}
+}
@@ -16,12 +16,17 @@
 
 //! Adapters to work with [`frame_support::traits::fungibles`] through XCM.
 
-use frame_support::traits::{
-	tokens::{
-		fungibles, Fortitude::Polite, Precision::Exact, Preservation::Preserve, Provenance::Minted,
+use frame_support::{
+	storage::{with_transaction, TransactionOutcome},
+	traits::{
+		tokens::{
+			fungibles, Fortitude::Polite, Precision::Exact, Preservation::Preserve,
+			Provenance::Minted,
+		},
+		Contains, Get,
 	},
-	Contains, Get,
 };
+use sp_runtime::{DispatchError, Permill};
 use sp_std::{marker::PhantomData, prelude::*, result};
 use xcm::latest::prelude::*;
 use xcm_executor::traits::{ConvertLocation, Error as MatchError, MatchesFungibles, TransactAsset};
@@ -400,3 +405,163 @@ impl<
 		)
 	}
 }
+
+/// `TransactAsset` implementation that tolerates slippage of internal transfers, performed by the
+/// `Inner` transactor (e.g. when assets are moved through a liquidity pool).
+///
+/// If the amount, received by the `Inner` transfer, is less than the requested amount, but within
+/// the `Slippage` of it, the deficit is covered by transferring it from the
+/// `SlippageDeficitAccount`. Transfers with larger slippage fail. All other operations are
+/// delegated to the `Inner` transactor.
+pub struct FungiblesAdapterWithSlippage<
+	Slippage,
+	Inner,
+	Assets,
+	Matcher,
+	AccountIdConverter,
+	AccountId,
+	SlippageDeficitAccount,
+>(
+	PhantomData<(
+		Slippage,
+		Inner,
+		Assets,
+		Matcher,
+		AccountIdConverter,
+		AccountId,
+		SlippageDeficitAccount,
+	)>,
+);
+impl<
+		Slippage: Get<Permill>,
+		Inner: TransactAsset,
+		Assets: fungibles::Mutate<AccountId>,
+		Matcher: MatchesFungibles<Assets::AssetId, Assets::Balance>,
+		AccountIdConverter: ConvertLocation<AccountId>,
+		AccountId: Eq + Clone,
+		SlippageDeficitAccount: Get<AccountId>,
+	>
+	FungiblesAdapterWithSlippage<
+		Slippage,
+		Inner,
+		Assets,
+		Matcher,
+		AccountIdConverter,
+		AccountId,
+		SlippageDeficitAccount,
+	>
+{
+	/// Transfer `what` using the `Inner` transactor and cover the deficit from the
+	/// `SlippageDeficitAccount`, if the slippage is within the `Slippage`.
+	fn transfer_covering_deficit(
+		asset_id: Assets::AssetId,
+		expected: u128,
+		what: &Asset,
+		from: &Location,
+		to: &Location,
+		context: &XcmContext,
+	) -> result::Result<xcm_executor::AssetsInHolding, XcmError> {
+		let received = Inner::internal_transfer_asset(what, from, to, context)?;
+		let received_amount = received.fungible.get(&what.id).copied().unwrap_or(0);
+		if received_amount >= expected {
+			return Ok(received)
+		}
+
+		let deficit = expected - received_amount;
+		let max_deficit = Slippage::get() * expected;
+		if deficit > max_deficit {
+			log::trace!(
+				target: "xcm::fungibles_adapter",
+				"FungiblesAdapterWithSlippage::internal_transfer_asset slippage is too large: \
+				expected {}, received {}",
+				expected, received_amount,
+			);
+			return Err(XcmError::FailedToTransactAsset("slippage is too large"))
+		}
+
+		let (_, deficit) =
+			Matcher::matches_fungibles(&Asset { id: what.id.clone(), fun: Fungible(deficit) })?;
+		let dest = AccountIdConverter::convert_location(to)
+			.ok_or(MatchError::AccountIdConversionFailed)?;
+		Assets::transfer(asset_id, &SlippageDeficitAccount::get(), &dest, deficit, Preserve)
+			.map_err(|e| XcmError::FailedToTransactAsset(e.into()))?;
+		Ok(what.clone().into())
+	}
+}
+impl<
+		Slippage: Get<Permill>,
+		Inner: TransactAsset,
+		Assets: fungibles::Mutate<AccountId>,
+		Matcher: MatchesFungibles<Assets::AssetId, Assets::Balance>,
+		AccountIdConverter: ConvertLocation<AccountId>,
+		AccountId: Eq + Clone,
+		SlippageDeficitAccount: Get<AccountId>,
+	> TransactAsset
+	for FungiblesAdapterWithSlippage<
+		Slippage,
+		Inner,
+		Assets,
+		Matcher,
+		AccountIdConverter,
+		AccountId,
+		SlippageDeficitAccount,
+	>
+{
+	fn can_check_in(origin: &Location, what: &Asset, context: &XcmContext) -> XcmResult {
+		Inner::can_check_in(origin, what, context)
+	}
+
+	fn check_in(origin: &Location, what: &Asset, context: &XcmContext) {
+		Inner::check_in(origin, what, context)
+	}
+
+	fn can_check_out(dest: &Location, what: &Asset, context: &XcmContext) -> XcmResult {
+		Inner::can_check_out(dest, what, context)
+	}
+
+	fn check_out(dest: &Location, what: &Asset, context: &XcmContext) {
+		Inner::check_out(dest, what, context)
+	}
+
+	fn deposit_asset(what: &Asset, who: &Location, context: Option<&XcmContext>) -> XcmResult {
+		Inner::deposit_asset(what, who, context)
+	}
+
+	fn withdraw_asset(
+		what: &Asset,
+		who: &Location,
+		maybe_context: Option<&XcmContext>,
+	) -> result::Result<xcm_executor::AssetsInHolding, XcmError> {
+		Inner::withdraw_asset(what, who, maybe_context)
+	}
+
+	fn internal_transfer_asset(
+		what: &Asset,
+		from: &Location,
+		to: &Location,
+		context: &XcmContext,
+	) -> result::Result<xcm_executor::AssetsInHolding, XcmError> {
+		log::trace!(
+			target: "xcm::fungibles_adapter",
+			"FungiblesAdapterWithSlippage::internal_transfer_asset what: {:?}, from: {:?}, to: {:?}",
+			what, from, to
+		);
+		// Check we handle this asset.
+		let (asset_id, _) = Matcher::matches_fungibles(what)?;
+		let expected = match what.fun {
+			Fungible(amount) => amount,
+			NonFungible(_) => return Err(MatchError::AssetNotHandled.into()),
+		};
+
+		// the `Inner` transfer is reverted if the deficit may not be covered
+		with_transaction(|| -> TransactionOutcome<Result<_, DispatchError>> {
+			let result =
+				Self::transfer_covering_deficit(asset_id, expected, what, from, to, context);
+			match &result {
+				Ok(_) => TransactionOutcome::Commit(Ok(result)),
+				_ => TransactionOutcome::Rollback(Ok(result)),
+			}
+		})
+		.map_err(|_| XcmError::ExceedsStackLimit)?
+	}
+}
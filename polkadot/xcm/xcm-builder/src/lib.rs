@@ -35,9 +35,11 @@ pub use asset_conversion::{
 
 mod barriers;
 pub use barriers::{
-	AllowExplicitUnpaidExecutionFrom, AllowKnownQueryResponses, AllowSubscriptionsFrom,
-	AllowTopLevelPaidExecutionFrom, AllowUnpaidExecutionFrom, DenyReserveTransferToRelayChain,
-	DenyThenTry, IsChildSystemParachain, RespectSuspension, TakeWeightCredit, TrailingSetTopicAsId,
+	AllOf, AllowExplicitUnpaidExecutionFrom, AllowKnownQueryResponses, AllowSubscriptionsFrom,
+	AllowTopLevelPaidExecutionFrom, AllowUnpaidExecutionFrom, AnyOf, BarrierWithObserver,
+	CongestionAwareBarrier, DenyReserveTransferToRelayChain, DenyThenTry, IsChildSystemParachain,
+	MaxInstructionCountBarrier, NullObserver, ObserveBarrierResult, RespectSuspension,
+	ShouldExecuteAll, TakeWeightCredit, TrailingSetTopicAsId, ValidateXcmOriginForBridge,
 	WithComputedOrigin,
 };
 
@@ -60,12 +62,16 @@ mod filter_asset_location;
 pub use filter_asset_location::{AllAssets, Case, LocationWithAssetFilters, NativeAsset};
 
 mod fungible_adapter;
-pub use fungible_adapter::{FungibleAdapter, FungibleMutateAdapter, FungibleTransferAdapter};
+pub use fungible_adapter::{
+	FungibleAdapter, FungibleAdapterWithHooks, FungibleMutateAdapter, FungibleTransferAdapter,
+	TransferHook,
+};
 
 mod fungibles_adapter;
 pub use fungibles_adapter::{
-	AssetChecking, DualMint, FungiblesAdapter, FungiblesMutateAdapter, FungiblesTransferAdapter,
-	LocalMint, MintLocation, NoChecking, NonLocalMint,
+	AssetChecking, DualMint, FungiblesAdapter, FungiblesAdapterWithSlippage,
+	FungiblesMutateAdapter, FungiblesTransferAdapter, LocalMint, MintLocation, NoChecking,
+	NonLocalMint,
 };
 
 mod location_conversion;
@@ -73,13 +79,39 @@ mod location_conversion;
 pub use location_conversion::ForeignChainAliasAccount;
 pub use location_conversion::{
 	Account32Hash, AccountId32Aliases, AccountKey20Aliases, AliasesIntoAccountId32,
-	ChildParachainConvertsVia, DescribeAccountId32Terminal, DescribeAccountIdTerminal,
-	DescribeAccountKey20Terminal, DescribeAllTerminal, DescribeBodyTerminal, DescribeFamily,
-	DescribeLocation, DescribePalletTerminal, DescribeTerminus, DescribeTreasuryVoiceTerminal,
-	GlobalConsensusConvertsFor, GlobalConsensusParachainConvertsFor, HashedDescription,
+	ChildParachainConvertsVia, ConvertLocationBidirectional, DescribeAccountId32Terminal,
+	DescribeAccountIdTerminal, DescribeAccountKey20Terminal, DescribeAllTerminal,
+	DescribeAncestorChain, DescribeBodyTerminal, DescribeChecked, DescribeCouncilTerminal,
+	DescribeFamily, DescribeGeneralIndexTerminal, DescribeGeneralKeyTerminal,
+	DescribeGovernanceBodiesTerminal, DescribeLocation, DescribeLocationError,
+	DescribeOnlyChildTerminal, DescribePalletTerminal, DescribeTechnicalCommitteeTerminal,
+	DescribeTerminus, DescribeTreasuryVoiceTerminal, GlobalConsensusConvertsFor,
+	GlobalConsensusParachainConvertsFor, HashedDescription, HashedDescriptionWithSalt,
 	LocalTreasuryVoiceConvertsVia, ParentIsPreset, SiblingParachainConvertsVia,
+	TryDescribeLocation, VersionedLocationConverter,
 };
 
+pub use xcm_procedural::DescribeLocation;
+
+#[doc(hidden)]
+pub mod __private {
+	pub use frame_support;
+	pub use parity_scale_codec::Encode;
+	pub use sp_std::vec::Vec;
+	pub use xcm::latest::{prelude, Location};
+	pub use xcm_executor;
+	pub use xcm_procedural::xcm_config;
+}
+
+/// Generates the `xcm_executor::Config` implementation of a runtime, filling in defaults for the
+/// omitted fields, see [`xcm_procedural::xcm_config`].
+#[macro_export]
+macro_rules! xcm_config {
+	($($config:tt)*) => {
+		$crate::__private::xcm_config! { $crate; $($config)* }
+	};
+}
+
 mod matches_location;
 pub use matches_location::{StartsWith, StartsWithExplicitGlobalConsensus};
 
@@ -105,9 +137,9 @@ pub use origin_aliases::AliasForeignAccountId32;
 mod origin_conversion;
 pub use origin_conversion::{
 	BackingToPlurality, ChildParachainAsNative, ChildSystemParachainAsSuperuser, EnsureXcmOrigin,
-	OriginToPluralityVoice, ParentAsSuperuser, RelayChainAsNative, SiblingParachainAsNative,
-	SiblingSystemParachainAsSuperuser, SignedAccountId32AsNative, SignedAccountKey20AsNative,
-	SignedToAccountId32, SovereignSignedViaLocation,
+	MultiOriginAsSingleCombinator, OriginToPluralityVoice, ParentAsSuperuser, RelayChainAsNative,
+	SiblingParachainAsNative, SiblingSystemParachainAsSuperuser, SignedAccountId32AsNative,
+	SignedAccountKey20AsNative, SignedToAccountId32, SovereignSignedViaLocation,
 };
 
 mod pay;
@@ -117,19 +149,32 @@ mod process_xcm_message;
 pub use process_xcm_message::ProcessXcmMessage;
 
 mod routing;
-pub use routing::{EnsureDelivery, WithTopicSource, WithUniqueTopic};
+pub use routing::{
+	ContentHashTopic, DowngradeXcmVersion, EnsureDecodableXcm, EnsureDelivery, NoVersionDowngrade,
+	PrioritizedRouter, QueueDepthAwareRouter, SourceTopic, WithFallbackRouter, WithTopicSource,
+	WithUniqueTopic, XcmVersionDowngrade, XcmpQueueInspector, CONGESTED,
+};
 
 mod transactional;
 pub use transactional::FrameTransactionalProcessor;
 
 mod universal_exports;
+#[cfg(feature = "lz4")]
+pub use universal_exports::LZ4BlobCompressor;
 pub use universal_exports::{
-	ensure_is_remote, BridgeBlobDispatcher, BridgeMessage, DispatchBlob, DispatchBlobError,
-	ExporterFor, HaulBlob, HaulBlobError, HaulBlobExporter, NetworkExportTable,
-	NetworkExportTableItem, SovereignPaidRemoteExporter, UnpaidLocalExporter, UnpaidRemoteExporter,
+	blob_origin, ensure_is_remote, BridgeBlobDispatcher, BridgeHop, BridgeMessage, CompressBlob,
+	CompressedHaulBlobExporter, ComputeBlobFee, DecompressError, DecompressingBlobDispatcher,
+	DispatchBlob, DispatchBlobError, ExportFeeProvider, ExporterFor, ExporterForFn, HaulBlob,
+	HaulBlobError, HaulBlobExporter, HopList, LocalExporterWithFee, MeteredBlobDispatcher,
+	MultiHopBridgeRouter, NetworkExportTable, NetworkExportTableItem, NoOpCompressor,
+	OnBlobFeeCharged, OrderedBridgeBlobDispatcher, SequenceTracker, SequencedBlobHauler,
+	SequencedBlobHeader, SovereignPaidRemoteExporter, UnpaidLocalExporter, UnpaidRemoteExporter,
 };
 
 mod weight;
+#[cfg(any(test, all(feature = "std", feature = "xcm-profiling")))]
+pub use weight::take_instruction_weight_profile;
 pub use weight::{
-	FixedRateOfFungible, FixedWeightBounds, TakeRevenue, UsingComponents, WeightInfoBounds,
+	AdaptiveWeightBounds, AssetSpecificWeightMultiplier, FixedRateOfFungible, FixedWeightBounds,
+	PerInstructionWeightProfiler, TakeRevenue, UsingComponents, WeightInfoBounds,
 };
@@ -15,7 +15,7 @@
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::universal_exports::ensure_is_remote;
-use frame_support::traits::Get;
+use frame_support::traits::{ConstU8, Get};
 use parity_scale_codec::{Compact, Decode, Encode};
 use sp_io::hashing::blake2_256;
 use sp_runtime::traits::{AccountIdConversion, TrailingZeroInput, TryConvert};
@@ -43,6 +43,59 @@ impl DescribeLocation for Tuple {
 	}
 }
 
+/// Error that may happen when describing a location.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DescribeLocationError {
+	/// The location is not supported by the describer.
+	Unsupported,
+	/// The location is malformed and can't be described by any describer.
+	Malformed(&'static str),
+}
+
+/// Means of converting a location into a stable and unique descriptive identifier, reporting why
+/// the location can't be described.
+///
+/// Any `DescribeLocation` type may be wrapped into the [`DescribeChecked`] to get the
+/// implementation with the [`DescribeLocationError`].
+pub trait TryDescribeLocation: DescribeLocation {
+	/// The error that is returned when the location can't be described.
+	type Error: core::fmt::Debug;
+
+	/// Create a description of the given `location`. No two locations should have the same
+	/// descriptor.
+	fn try_describe_location(location: &Location) -> Result<Vec<u8>, Self::Error>;
+}
+
+/// Describes the location with `Describe`, rejecting the locations with a `GeneralKey` junction
+/// longer than `MaxGeneralKeyLength` bytes as malformed.
+pub struct DescribeChecked<Describe, MaxGeneralKeyLength = ConstU8<32>>(
+	PhantomData<(Describe, MaxGeneralKeyLength)>,
+);
+impl<Describe: DescribeLocation, MaxGeneralKeyLength: Get<u8>> DescribeLocation
+	for DescribeChecked<Describe, MaxGeneralKeyLength>
+{
+	fn describe_location(location: &Location) -> Option<Vec<u8>> {
+		Self::try_describe_location(location).ok()
+	}
+}
+impl<Describe: DescribeLocation, MaxGeneralKeyLength: Get<u8>> TryDescribeLocation
+	for DescribeChecked<Describe, MaxGeneralKeyLength>
+{
+	type Error = DescribeLocationError;
+
+	fn try_describe_location(location: &Location) -> Result<Vec<u8>, Self::Error> {
+		let max_key_length = MaxGeneralKeyLength::get();
+		let has_malformed_key = location.interior().iter().any(
+			|junction| matches!(junction, GeneralKey { length, .. } if *length > max_key_length),
+		);
+		if has_malformed_key {
+			return Err(DescribeLocationError::Malformed("GeneralKey length exceeds the limit"))
+		}
+
+		Describe::describe_location(location).ok_or(DescribeLocationError::Unsupported)
+	}
+}
+
 pub struct DescribeTerminus;
 impl DescribeLocation for DescribeTerminus {
 	fn describe_location(l: &Location) -> Option<Vec<u8>> {
@@ -97,6 +150,41 @@ impl DescribeLocation for DescribeTreasuryVoiceTerminal {
 	}
 }
 
+/// Create a description of the remote technical committee `location` if possible. No two
+/// locations should have the same descriptor.
+pub struct DescribeTechnicalCommitteeTerminal;
+
+impl DescribeLocation for DescribeTechnicalCommitteeTerminal {
+	fn describe_location(location: &Location) -> Option<Vec<u8>> {
+		match location.unpack() {
+			(0, [Plurality { id: BodyId::Technical, part: BodyPart::Voice }]) =>
+				Some((b"Technical", b"Voice").encode()),
+			_ => None,
+		}
+	}
+}
+
+/// Create a description of the remote council `location` if possible. No two locations should
+/// have the same descriptor.
+pub struct DescribeCouncilTerminal;
+
+impl DescribeLocation for DescribeCouncilTerminal {
+	fn describe_location(location: &Location) -> Option<Vec<u8>> {
+		match location.unpack() {
+			(0, [Plurality { id: BodyId::Executive, part: BodyPart::Voice }]) =>
+				Some((b"Executive", b"Voice").encode()),
+			_ => None,
+		}
+	}
+}
+
+/// Describes the voices of the governance bodies: treasury, technical committee and council.
+///
+/// Other `Plurality` junctions are not described, so this may be combined with the
+/// [`DescribeBodyTerminal`].
+pub type DescribeGovernanceBodiesTerminal =
+	(DescribeTreasuryVoiceTerminal, DescribeTechnicalCommitteeTerminal, DescribeCouncilTerminal);
+
 pub type DescribeAccountIdTerminal = (DescribeAccountId32Terminal, DescribeAccountKey20Terminal);
 
 pub struct DescribeBodyTerminal;
@@ -109,6 +197,43 @@ impl DescribeLocation for DescribeBodyTerminal {
 	}
 }
 
+pub struct DescribeGeneralIndexTerminal;
+impl DescribeLocation for DescribeGeneralIndexTerminal {
+	fn describe_location(l: &Location) -> Option<Vec<u8>> {
+		match l.unpack() {
+			(0, [GeneralIndex(i)]) => Some((b"GeneralIndex", Compact::<u128>::from(*i)).encode()),
+			_ => return None,
+		}
+	}
+}
+
+/// Describes the `GeneralKey` terminal junction.
+///
+/// Only the first `length` bytes of the key data are described, so the padding of the key does
+/// not affect the descriptor. Keys that are longer than 32 bytes are not described.
+pub struct DescribeGeneralKeyTerminal;
+impl DescribeLocation for DescribeGeneralKeyTerminal {
+	fn describe_location(l: &Location) -> Option<Vec<u8>> {
+		match l.unpack() {
+			(0, [GeneralKey { length, data }]) => {
+				let key = data.get(..*length as usize)?;
+				Some((b"GeneralKey", length, key).encode())
+			},
+			_ => return None,
+		}
+	}
+}
+
+pub struct DescribeOnlyChildTerminal;
+impl DescribeLocation for DescribeOnlyChildTerminal {
+	fn describe_location(l: &Location) -> Option<Vec<u8>> {
+		match l.unpack() {
+			(0, [OnlyChild]) => Some(b"OnlyChild".encode()),
+			_ => return None,
+		}
+	}
+}
+
 pub type DescribeAllTerminal = (
 	DescribeTerminus,
 	DescribePalletTerminal,
@@ -116,6 +241,9 @@ pub type DescribeAllTerminal = (
 	DescribeAccountKey20Terminal,
 	DescribeTreasuryVoiceTerminal,
 	DescribeBodyTerminal,
+	DescribeGeneralIndexTerminal,
+	DescribeGeneralKeyTerminal,
+	DescribeOnlyChildTerminal,
 );
 
 pub struct DescribeFamily<DescribeInterior>(PhantomData<DescribeInterior>);
@@ -143,12 +271,96 @@ impl<Suffix: DescribeLocation> DescribeLocation for DescribeFamily<Suffix> {
 	}
 }
 
+/// Describes the locations `N` parent hops away, followed by any number of `Parachain` junctions
+/// and a terminal, which is described by `Suffix`.
+///
+/// Unlike nesting [`DescribeFamily`], this works for an arbitrary depth: e.g. the location
+/// `(2, [Parachain(1000), Parachain(2000), AccountId32 { .. }])` is described with
+/// `DescribeAncestorChain<ConstU8<2>, DescribeAccountId32Terminal>` as the chain path `[1000,
+/// 2000]` followed by the description of the account. Each of the leading `Parachain` junctions is
+/// a path segment and the path of a location without them is empty (`Here`).
+pub struct DescribeAncestorChain<N, Suffix>(PhantomData<(N, Suffix)>);
+impl<N: Get<u8>, Suffix: DescribeLocation> DescribeLocation for DescribeAncestorChain<N, Suffix> {
+	fn describe_location(l: &Location) -> Option<Vec<u8>> {
+		let parents = N::get();
+		if l.parent_count() != parents {
+			return None
+		}
+
+		let mut path = Vec::new();
+		let mut tail = l.interior().clone();
+		while let Some(Parachain(index)) = tail.first() {
+			path.push(Compact::<u32>::from(*index));
+			tail = tail.split_first().0;
+		}
+		let interior = Suffix::describe_location(&tail.into())?;
+		Some((b"AncestorChain", parents, path, interior).encode())
+	}
+}
+
+/// Converts a location into an account id, by hashing its description.
+///
+/// For example, this converter handles all terminal junctions (e.g. `GeneralIndex` of the
+/// asset at the sibling Asset Hub) of local, child, sibling and parent chain locations:
+///
+/// ```nocompile
+/// pub type LocationToAccount = HashedDescription<AccountId, DescribeFamily<DescribeAllTerminal>>;
+/// ```
 pub struct HashedDescription<AccountId, Describe>(PhantomData<(AccountId, Describe)>);
+impl<AccountId: From<[u8; 32]> + Clone, Describe: DescribeLocation>
+	HashedDescription<AccountId, Describe>
+{
+	/// Convert the `description` of a location into an account id.
+	fn description_to_account(description: Vec<u8>) -> AccountId {
+		blake2_256(&description).into()
+	}
+}
+impl<AccountId: From<[u8; 32]> + Clone, Describe: TryDescribeLocation>
+	HashedDescription<AccountId, Describe>
+{
+	/// Convert the given `location` into an account id, returning the describer error if the
+	/// location can't be described.
+	pub fn try_convert_location(location: &Location) -> Result<AccountId, Describe::Error> {
+		let description = Describe::try_describe_location(location)?;
+		Ok(Self::description_to_account(description))
+	}
+}
 impl<AccountId: From<[u8; 32]> + Clone, Describe: DescribeLocation> ConvertLocation<AccountId>
 	for HashedDescription<AccountId, Describe>
 {
 	fn convert_location(value: &Location) -> Option<AccountId> {
-		Some(blake2_256(&Describe::describe_location(value)?).into())
+		let description = Describe::describe_location(value)?;
+		Some(Self::description_to_account(description))
+	}
+}
+
+/// Means of converting an account id back into the location it has been converted from.
+///
+/// Hashing converters can't be reversed, so implementations (e.g. in the
+/// `pallet-xcm-location-registry`) keep the locations that have been explicitly registered for
+/// their accounts.
+pub trait ConvertLocationBidirectional<AccountId>: ConvertLocation<AccountId> {
+	/// Returns the location that is converted into the given `account`, if it is known.
+	fn account_to_location(account: &AccountId) -> Option<Location>;
+}
+
+/// Converts a location into an account id, by hashing its description, prefixed with the
+/// chain-specific `Salt`.
+///
+/// The plain `HashedDescription` generates the same account for the same location on all chains
+/// that are using the same configuration. This is desirable when e.g. the user expects to control
+/// the same account on several sibling chains. If this isn't the case, it is a security risk,
+/// because an account, derived on one chain, may be abused on another chain. Use this converter
+/// with the unique `Salt` to make derived accounts chain-specific.
+pub struct HashedDescriptionWithSalt<Salt, AccountId, Describe>(
+	PhantomData<(Salt, AccountId, Describe)>,
+);
+impl<Salt: Get<[u8; 32]>, AccountId: From<[u8; 32]> + Clone, Describe: DescribeLocation>
+	ConvertLocation<AccountId> for HashedDescriptionWithSalt<Salt, AccountId, Describe>
+{
+	fn convert_location(value: &Location) -> Option<AccountId> {
+		let description = Describe::describe_location(value)?;
+		Some(blake2_256(&(Salt::get(), description).encode()).into())
 	}
 }
 
@@ -457,6 +669,35 @@ impl<UniversalLocation, AccountId>
 	}
 }
 
+/// Converts a location into an account id with the `V4` converter, falling back to the `V3`
+/// converter if it fails.
+///
+/// The `V3` converter is meant to support locations that were originally described in XCM v3,
+/// e.g. the ones that have been received from older nodes. Before the fallback conversion, the
+/// location is converted into the v3 format and back, so the `V3` converter is only given
+/// locations that are representable in XCM v3.
+///
+/// Note that every failed `V4` conversion costs two additional location conversions and the
+/// `V3` conversion, so the converters that are expected to fail often should be put into the
+/// `V3` slot, not the `V4` one.
+pub struct VersionedLocationConverter<V3, V4>(PhantomData<(V3, V4)>);
+impl<AccountId, V3: ConvertLocation<AccountId>, V4: ConvertLocation<AccountId>>
+	ConvertLocation<AccountId> for VersionedLocationConverter<V3, V4>
+{
+	fn convert_location(location: &Location) -> Option<AccountId> {
+		V4::convert_location(location).or_else(|| {
+			let v3_location = xcm::v3::Location::try_from(location.clone()).ok()?;
+			let location = xcm::v4::Location::try_from(v3_location).ok()?;
+			log::trace!(
+				target: "xcm::location_conversion",
+				"VersionedLocationConverter falling back to v3 conversion of location: {:?}",
+				location,
+			);
+			V3::convert_location(&location)
+		})
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -984,6 +1225,384 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn describe_general_index_terminal_works() {
+		// descriptor is the `b"GeneralIndex"` prefix, followed by the compact-encoded index
+		let describe = |i: u128| {
+			DescribeGeneralIndexTerminal::describe_location(&Location::new(0, [GeneralIndex(i)]))
+				.unwrap()
+		};
+		assert_eq!(describe(0).len(), 12 + 1);
+		assert_eq!(describe(1_000).len(), 12 + 2);
+		assert_eq!(describe(u128::MAX).len(), 12 + 17);
+
+		// distinct indices produce distinct descriptors
+		let indices = [0, 1, 2, 63, 64, 1_000, u32::MAX as u128, u128::MAX - 1, u128::MAX];
+		for (n, i) in indices.iter().enumerate() {
+			for j in &indices[n + 1..] {
+				assert_ne!(describe(*i), describe(*j));
+			}
+		}
+
+		// other locations are not described
+		assert_eq!(
+			DescribeGeneralIndexTerminal::describe_location(&Location::new(1, [GeneralIndex(1)])),
+			None,
+		);
+		assert_eq!(
+			DescribeGeneralIndexTerminal::describe_location(&Location::new(
+				0,
+				[PalletInstance(50), GeneralIndex(1)]
+			)),
+			None,
+		);
+	}
+
+	#[test]
+	fn hashed_description_describes_general_index_terminal() {
+		let location = Location::new(1, [Parachain(1000), PalletInstance(50), GeneralIndex(1984)]);
+		assert_eq!(
+			HashedDescription::<[u8; 32], DescribeFamily<DescribeAllTerminal>>::convert_location(
+				&location
+			),
+			None,
+		);
+
+		let location = Location::new(1, [Parachain(1000), GeneralIndex(1984)]);
+		assert_eq!(
+			HashedDescription::<[u8; 32], DescribeFamily<DescribeAllTerminal>>::convert_location(
+				&location
+			),
+			Some(blake2_256(
+				&(
+					b"SiblingChain",
+					Compact::<u32>::from(1000),
+					(b"GeneralIndex", Compact::<u128>::from(1984)).encode()
+				)
+					.encode()
+			)),
+		);
+	}
+
+	#[test]
+	fn describe_general_key_terminal_ignores_key_padding() {
+		let describe = |length: u8, data: [u8; 32]| {
+			DescribeGeneralKeyTerminal::describe_location(&Location::new(
+				0,
+				[GeneralKey { length, data }],
+			))
+		};
+
+		let mut key = [0u8; 32];
+		key[..4].copy_from_slice(b"USDT");
+		let mut padded_key = key;
+		padded_key[4..].copy_from_slice(&[0xff; 28]);
+
+		assert_eq!(describe(4, key), Some((b"GeneralKey", 4u8, &b"USDT"[..]).encode()));
+		assert_eq!(describe(4, key), describe(4, padded_key));
+		assert_ne!(describe(4, key), describe(5, key));
+		assert_ne!(describe(0, key), describe(1, key));
+		assert_eq!(describe(33, key), None);
+
+		assert_eq!(
+			DescribeAllTerminal::describe_location(&Location::new(
+				0,
+				[GeneralKey { length: 4, data: padded_key }]
+			)),
+			describe(4, key),
+		);
+	}
+
+	#[test]
+	fn general_key_terminal_does_not_collide_with_other_terminals() {
+		let general_key = |length: u8, data: [u8; 32]| {
+			DescribeGeneralKeyTerminal::describe_location(&Location::new(
+				0,
+				[GeneralKey { length, data }],
+			))
+			.unwrap()
+		};
+		let general_index = |i: u128| {
+			DescribeGeneralIndexTerminal::describe_location(&Location::new(0, [GeneralIndex(i)]))
+				.unwrap()
+		};
+		let pallet = |i: u8| {
+			DescribePalletTerminal::describe_location(&Location::new(0, [PalletInstance(i)]))
+				.unwrap()
+		};
+
+		let mut keys = vec![general_key(0, [0u8; 32]), general_key(32, [0xff; 32])];
+		for i in 0..=32u8 {
+			let mut data = [0u8; 32];
+			data[..i as usize].copy_from_slice(&[i; 32][..i as usize]);
+			keys.push(general_key(i, data));
+		}
+		for key in &keys {
+			for i in [0, 1, 50, 255] {
+				assert_ne!(*key, pallet(i));
+			}
+			for i in [0, 1, 1_000, u64::MAX as u128, u128::MAX] {
+				assert_ne!(*key, general_index(i));
+			}
+		}
+	}
+
+	#[test]
+	fn hashed_description_describes_only_child_terminal() {
+		type OnlyChildAccount =
+			HashedDescription<[u8; 32], DescribeFamily<DescribeOnlyChildTerminal>>;
+
+		let location = Location::new(0, [Parachain(1000), OnlyChild]);
+		assert_eq!(
+			OnlyChildAccount::convert_location(&location),
+			Some([
+				205, 90, 194, 66, 122, 184, 8, 125, 5, 89, 84, 110, 56, 20, 137, 176, 72, 92, 135,
+				73, 146, 254, 8, 40, 198, 199, 194, 107, 244, 49, 158, 186
+			]),
+		);
+
+		let location = Location::new(1, [Parachain(1000), OnlyChild]);
+		assert_eq!(
+			OnlyChildAccount::convert_location(&location),
+			Some([
+				21, 152, 67, 171, 129, 25, 206, 136, 105, 106, 241, 54, 197, 253, 243, 75, 23, 166,
+				218, 223, 85, 110, 80, 158, 232, 97, 91, 83, 156, 115, 25, 53
+			]),
+		);
+	}
+
+	#[test]
+	fn only_child_terminal_does_not_collide_with_other_terminals() {
+		let only_child =
+			DescribeOnlyChildTerminal::describe_location(&Location::new(0, [OnlyChild])).unwrap();
+		let terminus = DescribeTerminus::describe_location(&Location::here()).unwrap();
+		let pallet =
+			DescribePalletTerminal::describe_location(&Location::new(0, [PalletInstance(0)]))
+				.unwrap();
+
+		assert_ne!(only_child, terminus);
+		assert_ne!(only_child, pallet);
+		assert_eq!(
+			DescribeAllTerminal::describe_location(&Location::new(0, [OnlyChild])),
+			Some(only_child),
+		);
+	}
+
+	#[test]
+	fn describe_ancestor_chain_works() {
+		use frame_support::traits::ConstU8;
+		type Describe<const N: u8> = DescribeAncestorChain<ConstU8<N>, DescribeAllTerminal>;
+
+		let account = AccountId32 { network: None, id: [1; 32] };
+		let account_description =
+			DescribeAccountId32Terminal::describe_location(&account.clone().into()).unwrap();
+		let description = |parents: u8, path: Vec<u32>| {
+			let path = path.into_iter().map(Compact::<u32>::from).collect::<Vec<_>>();
+			(b"AncestorChain", parents, path, account_description.clone()).encode()
+		};
+
+		// N = 0
+		assert_eq!(
+			Describe::<0>::describe_location(&Location::new(0, [account.clone()])),
+			Some(description(0, vec![])),
+		);
+		assert_eq!(
+			Describe::<0>::describe_location(&Location::new(0, [Parachain(1000), account.clone()])),
+			Some(description(0, vec![1000])),
+		);
+		// N = 1
+		assert_eq!(
+			Describe::<1>::describe_location(&Location::new(1, [Parachain(1000), account.clone()])),
+			Some(description(1, vec![1000])),
+		);
+		// N = 2
+		assert_eq!(
+			Describe::<2>::describe_location(&Location::new(
+				2,
+				[Parachain(1000), Parachain(2000), account.clone()]
+			)),
+			Some(description(2, vec![1000, 2000])),
+		);
+		// N = 3
+		assert_eq!(
+			Describe::<3>::describe_location(&Location::new(3, [account.clone()])),
+			Some(description(3, vec![])),
+		);
+		assert_eq!(
+			Describe::<3>::describe_location(&Location::new(3, Here)),
+			Some((b"AncestorChain", 3u8, Vec::<Compact<u32>>::new(), Vec::<u8>::new()).encode()),
+		);
+
+		// the number of parents must match exactly
+		assert_eq!(Describe::<2>::describe_location(&Location::new(1, [account.clone()])), None);
+		assert_eq!(Describe::<2>::describe_location(&Location::new(3, [account.clone()])), None);
+		// and the terminal must be supported by the suffix
+		assert_eq!(
+			DescribeAncestorChain::<ConstU8<1>, DescribeTerminus>::describe_location(
+				&Location::new(1, [Parachain(1000), account])
+			),
+			None,
+		);
+	}
+
+	#[test]
+	fn describe_ancestor_chain_is_injective() {
+		use frame_support::traits::ConstU8;
+		use sp_std::collections::btree_map::BTreeMap;
+		type Describe<const N: u8> = DescribeAncestorChain<ConstU8<N>, DescribeAllTerminal>;
+
+		let terminals = [
+			vec![],
+			vec![AccountId32 { network: None, id: [1; 32] }],
+			vec![PalletInstance(1)],
+			vec![GeneralIndex(1000)],
+		];
+		let paths = [vec![], vec![1], vec![1000], vec![1, 1000], vec![1000, 1], vec![1, 1, 1]];
+
+		let mut descriptions = BTreeMap::new();
+		for parents in 0..=3u8 {
+			for path in &paths {
+				for terminal in &terminals {
+					let mut location = Location::new(parents, Here);
+					for junction in
+						path.iter().map(|index| Parachain(*index)).chain(terminal.clone())
+					{
+						location.push_interior(junction).expect("at most 4 junctions; qed");
+					}
+					let description = match parents {
+						0 => Describe::<0>::describe_location(&location),
+						1 => Describe::<1>::describe_location(&location),
+						2 => Describe::<2>::describe_location(&location),
+						_ => Describe::<3>::describe_location(&location),
+					};
+					let description = description.expect("all terminals are supported; qed");
+					if let Some(other) = descriptions.insert(description, location.clone()) {
+						panic!("{:?} and {:?} have the same description", location, other);
+					}
+				}
+			}
+		}
+		assert_eq!(descriptions.len(), 4 * paths.len() * terminals.len());
+	}
+
+	#[test]
+	fn try_describe_location_reports_unsupported_locations() {
+		type Describe = DescribeChecked<DescribeFamily<DescribeAllTerminal>>;
+		assert_eq!(
+			Describe::try_describe_location(&Location::new(2, [])),
+			Err(DescribeLocationError::Unsupported),
+		);
+		assert_eq!(
+			HashedDescription::<[u8; 32], Describe>::try_convert_location(&Location::new(2, [])),
+			Err(DescribeLocationError::Unsupported),
+		);
+	}
+
+	#[test]
+	fn try_describe_location_reports_malformed_locations() {
+		type Describe = DescribeChecked<DescribeFamily<DescribeAllTerminal>>;
+		let location =
+			Location::new(1, [Parachain(1000), GeneralKey { length: 33, data: [0u8; 32] }]);
+		assert_eq!(
+			Describe::try_describe_location(&location),
+			Err(DescribeLocationError::Malformed("GeneralKey length exceeds the limit")),
+		);
+		assert_eq!(
+			HashedDescription::<[u8; 32], Describe>::try_convert_location(&location),
+			Err(DescribeLocationError::Malformed("GeneralKey length exceeds the limit")),
+		);
+		assert_eq!(HashedDescription::<[u8; 32], Describe>::convert_location(&location), None);
+
+		// the limit is configurable
+		let location =
+			Location::new(1, [Parachain(1000), GeneralKey { length: 20, data: [0u8; 32] }]);
+		assert!(Describe::try_describe_location(&location).is_ok());
+		assert_eq!(
+			DescribeChecked::<DescribeFamily<DescribeAllTerminal>, ConstU8<16>>::try_describe_location(
+				&location
+			),
+			Err(DescribeLocationError::Malformed("GeneralKey length exceeds the limit")),
+		);
+	}
+
+	#[test]
+	fn try_describe_location_works_for_supported_locations() {
+		let location = Location::new(1, [Parachain(1000), PalletInstance(50)]);
+		assert_eq!(
+			DescribeChecked::<DescribeFamily<DescribeAllTerminal>>::try_describe_location(
+				&location
+			)
+			.ok(),
+			DescribeFamily::<DescribeAllTerminal>::describe_location(&location),
+		);
+	}
+
+	#[test]
+	fn try_describe_location_may_use_custom_error() {
+		#[derive(Debug, PartialEq)]
+		struct NotTerminus;
+
+		struct DescribeOnlyTerminus;
+		impl DescribeLocation for DescribeOnlyTerminus {
+			fn describe_location(l: &Location) -> Option<Vec<u8>> {
+				Self::try_describe_location(l).ok()
+			}
+		}
+		impl TryDescribeLocation for DescribeOnlyTerminus {
+			type Error = NotTerminus;
+
+			fn try_describe_location(l: &Location) -> Result<Vec<u8>, NotTerminus> {
+				DescribeTerminus::describe_location(l).ok_or(NotTerminus)
+			}
+		}
+
+		assert!(HashedDescription::<[u8; 32], DescribeOnlyTerminus>::try_convert_location(
+			&Location::here()
+		)
+		.is_ok());
+		assert_eq!(
+			HashedDescription::<[u8; 32], DescribeOnlyTerminus>::try_convert_location(
+				&Location::parent()
+			),
+			Err(NotTerminus),
+		);
+	}
+
+	#[test]
+	fn hashed_description_with_salt_produces_chain_specific_accounts() {
+		parameter_types! {
+			pub const SaltA: [u8; 32] = [1u8; 32];
+			pub const SaltB: [u8; 32] = [2u8; 32];
+		}
+		type Describe = DescribeFamily<DescribeAllTerminal>;
+
+		let location =
+			Location::new(1, [Parachain(1000), AccountId32 { network: None, id: [0u8; 32] }]);
+		let account_a =
+			HashedDescriptionWithSalt::<SaltA, [u8; 32], Describe>::convert_location(&location)
+				.unwrap();
+		let account_b =
+			HashedDescriptionWithSalt::<SaltB, [u8; 32], Describe>::convert_location(&location)
+				.unwrap();
+		let unsalted_account =
+			HashedDescription::<[u8; 32], Describe>::convert_location(&location).unwrap();
+
+		assert_ne!(account_a, account_b);
+		assert_ne!(account_a, unsalted_account);
+		assert_eq!(
+			account_a,
+			blake2_256(&(SaltA::get(), Describe::describe_location(&location).unwrap()).encode()),
+		);
+
+		// locations that can't be described are not converted
+		assert_eq!(
+			HashedDescriptionWithSalt::<SaltA, [u8; 32], Describe>::convert_location(
+				&Location::new(2, [])
+			),
+			None,
+		);
+	}
+
 	#[test]
 	fn local_account_convert_on_para_from_relay_treasury() {
 		let location =
@@ -1007,4 +1626,74 @@ mod tests {
 			actual_description
 		);
 	}
+
+	#[test]
+	fn describe_governance_bodies_terminal_works() {
+		type Describe = (DescribeGovernanceBodiesTerminal, DescribeBodyTerminal);
+		let voice = |id| Location::new(0, [Plurality { id, part: BodyPart::Voice }]);
+
+		let treasury = Describe::describe_location(&voice(BodyId::Treasury)).unwrap();
+		let technical = Describe::describe_location(&voice(BodyId::Technical)).unwrap();
+		let council = Describe::describe_location(&voice(BodyId::Executive)).unwrap();
+		assert_eq!(treasury, (b"Treasury", b"Voice").encode());
+		assert_eq!(technical, (b"Technical", b"Voice").encode());
+		assert_eq!(council, (b"Executive", b"Voice").encode());
+		assert_ne!(treasury, technical);
+		assert_ne!(treasury, council);
+		assert_ne!(technical, council);
+
+		// other bodies and body parts fall through to `DescribeBodyTerminal`
+		for (id, part) in [
+			(BodyId::Legislative, BodyPart::Voice),
+			(BodyId::Technical, BodyPart::Members { count: 3 }),
+			(BodyId::Executive, BodyPart::Fraction { nom: 1, denom: 2 }),
+		] {
+			let location = Location::new(0, [Plurality { id, part }]);
+			assert_eq!(DescribeGovernanceBodiesTerminal::describe_location(&location), None);
+			assert_eq!(Describe::describe_location(&location), Some((b"Body", id, part).encode()),);
+		}
+
+		// only the terminal junction is described
+		assert_eq!(
+			DescribeGovernanceBodiesTerminal::describe_location(&Location::new(
+				1,
+				[Plurality { id: BodyId::Technical, part: BodyPart::Voice }],
+			)),
+			None,
+		);
+	}
+
+	#[test]
+	fn versioned_location_converter_falls_back_to_v3_converter() {
+		struct ParachainConverter<const ID: u32>;
+		impl<const ID: u32> ConvertLocation<u64> for ParachainConverter<ID> {
+			fn convert_location(location: &Location) -> Option<u64> {
+				match location.unpack() {
+					(1, [Parachain(id)]) if *id == ID => Some(ID as u64),
+					_ => None,
+				}
+			}
+		}
+		type Converter =
+			VersionedLocationConverter<ParachainConverter<1000>, ParachainConverter<2000>>;
+
+		assert_eq!(Converter::convert_location(&Location::new(1, [Parachain(2000)])), Some(2000));
+		assert_eq!(Converter::convert_location(&Location::new(1, [Parachain(1000)])), Some(1000));
+		assert_eq!(Converter::convert_location(&Location::new(1, [Parachain(3000)])), None);
+	}
+
+	#[test]
+	fn versioned_location_converter_matches_v3_converter() {
+		type V3Converter = HashedDescription<[u8; 32], DescribeFamily<DescribeAllTerminal>>;
+		type Converter = VersionedLocationConverter<V3Converter, ParentIsPreset<[u8; 32]>>;
+
+		let location =
+			Location::new(1, [Parachain(1000), GeneralKey { length: 3, data: [1u8; 32] }]);
+		assert!(ParentIsPreset::<[u8; 32]>::convert_location(&location).is_none());
+		assert_eq!(
+			Converter::convert_location(&location),
+			V3Converter::convert_location(&location),
+		);
+		assert!(Converter::convert_location(&location).is_some());
+	}
 }
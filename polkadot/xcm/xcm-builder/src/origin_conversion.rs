@@ -332,3 +332,29 @@ impl<RuntimeOrigin: Clone, EnsureBodyOrigin: EnsureOrigin<RuntimeOrigin>, Body:
 		}
 	}
 }
+
+/// Converts an origin, that is accepted by any of the `Origins` converters, into the single
+/// `MergedOrigin`.
+///
+/// `Origins` is usually a tuple of converters, that are tried in turn. Unlike the tuple itself,
+/// the origin, returned by the first successful converter, is replaced with the `MergedOrigin`,
+/// so that all trusted origins (e.g. all system parachains) are represented by the same origin.
+pub struct MultiOriginAsSingleCombinator<Origins, MergedOrigin>(
+	PhantomData<(Origins, MergedOrigin)>,
+);
+impl<RuntimeOrigin, Origins: ConvertOrigin<RuntimeOrigin>, MergedOrigin: Get<RuntimeOrigin>>
+	ConvertOrigin<RuntimeOrigin> for MultiOriginAsSingleCombinator<Origins, MergedOrigin>
+{
+	fn convert_origin(
+		origin: impl Into<Location>,
+		kind: OriginKind,
+	) -> Result<RuntimeOrigin, Location> {
+		let origin = origin.into();
+		log::trace!(
+			target: "xcm::origin_conversion",
+			"MultiOriginAsSingleCombinator origin: {:?}, kind: {:?}",
+			origin, kind,
+		);
+		Origins::convert_origin(origin, kind).map(|_| MergedOrigin::get())
+	}
+}
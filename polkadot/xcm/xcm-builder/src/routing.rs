@@ -16,11 +16,17 @@
 
 //! Various implementations for `SendXcm`.
 
+use frame_support::traits::{Contains, Get};
 use frame_system::unique;
-use parity_scale_codec::Encode;
+use parity_scale_codec::{DecodeLimit, Encode};
 use sp_std::{marker::PhantomData, result::Result};
-use xcm::prelude::*;
-use xcm_executor::{traits::FeeReason, FeesMode};
+use xcm::{prelude::*, MAX_XCM_DECODE_DEPTH};
+use xcm_executor::{current_sending_origin, traits::FeeReason, FeesMode};
+
+/// The reason of the `SendError::Transport` error, returned by the routers rejecting a message
+/// because the transport is temporarily congested. The message may be sent later or using another
+/// route, see [`WithFallbackRouter`].
+pub const CONGESTED: &str = "congested";
 
 /// Wrapper router which, if the message does not already end with a `SetTopic` instruction,
 /// appends one to the message filled with a universally unique ID. This ID is returned from a
@@ -61,24 +67,289 @@ impl<Inner: SendXcm> SendXcm for WithUniqueTopic<Inner> {
 	}
 }
 
+/// Router that tries to send a message using the `Primary` router first. If the `Primary` router
+/// rejects the message with either `SendError::NotApplicable` or `SendError::Transport(CONGESTED)`
+/// error during validation, the `Fallback` router is used instead.
+///
+/// The message is delivered using the router that has validated it. Since the ticket is only
+/// issued by the router that has accepted the message, the message may never be delivered twice.
+pub struct WithFallbackRouter<Primary, Fallback>(PhantomData<(Primary, Fallback)>);
+impl<Primary: SendXcm, Fallback: SendXcm> SendXcm for WithFallbackRouter<Primary, Fallback> {
+	type Ticket = (Option<Primary::Ticket>, Option<Fallback::Ticket>);
+
+	fn validate(
+		destination: &mut Option<Location>,
+		message: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket> {
+		// the primary router may take the arguments before failing, so we need to keep a copy
+		let (fallback_destination, fallback_message) = (destination.clone(), message.clone());
+		match Primary::validate(destination, message) {
+			Ok((ticket, price)) => Ok(((Some(ticket), None), price)),
+			Err(e @ SendError::NotApplicable) | Err(e @ SendError::Transport(CONGESTED)) => {
+				log::trace!(
+					target: "xcm::routing",
+					"WithFallbackRouter primary router has rejected the message: {:?}. Trying the fallback",
+					e,
+				);
+				*destination = fallback_destination;
+				*message = fallback_message;
+				let (ticket, price) = Fallback::validate(destination, message)?;
+				Ok(((None, Some(ticket)), price))
+			},
+			Err(e) => Err(e),
+		}
+	}
+
+	fn deliver(ticket: Self::Ticket) -> Result<XcmHash, SendError> {
+		match ticket {
+			(Some(ticket), None) => Primary::deliver(ticket),
+			(None, Some(ticket)) => Fallback::deliver(ticket),
+			_ => Err(SendError::Unroutable),
+		}
+	}
+}
+
+/// Router that sends messages of the `HighPriorityOrigins` using the `High` router and all other
+/// messages using the `Low` router. E.g. governance messages may be sent over the most reliable
+/// (and possibly more expensive) path, while routine transfers are sent over a cheaper one.
+///
+/// The origin of the message is passed to the router using `xcm_executor::with_sending_origin`.
+/// The executor passes the origin of messages it sends while executing XCM and `pallet-xcm`
+/// passes the origin of messages it sends on behalf of local origins. Messages without origin
+/// (e.g. version notifications, sent by `pallet-xcm` itself) are sent using the `Low` router.
+///
+/// Only one router validates the message and its errors are returned as is, so a message that is
+/// rejected by the `High` router is never sent using the `Low` router.
+pub struct PrioritizedRouter<High, Low, HighPriorityOrigins>(
+	PhantomData<(High, Low, HighPriorityOrigins)>,
+);
+impl<High, Low, HighPriorityOrigins> SendXcm for PrioritizedRouter<High, Low, HighPriorityOrigins>
+where
+	High: SendXcm,
+	Low: SendXcm,
+	HighPriorityOrigins: Contains<Location>,
+{
+	type Ticket = (Option<High::Ticket>, Option<Low::Ticket>);
+
+	fn validate(
+		destination: &mut Option<Location>,
+		message: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket> {
+		if current_sending_origin().is_some_and(|origin| HighPriorityOrigins::contains(&origin)) {
+			let (ticket, price) = High::validate(destination, message)?;
+			Ok(((Some(ticket), None), price))
+		} else {
+			let (ticket, price) = Low::validate(destination, message)?;
+			Ok(((None, Some(ticket)), price))
+		}
+	}
+
+	fn deliver(ticket: Self::Ticket) -> Result<XcmHash, SendError> {
+		match ticket {
+			(Some(ticket), None) => High::deliver(ticket),
+			(None, Some(ticket)) => Low::deliver(ticket),
+			_ => Err(SendError::Unroutable),
+		}
+	}
+}
+
+/// Inspector of the outbound XCMP queue.
+pub trait XcmpQueueInspector {
+	/// Returns the number of messages, waiting in the outbound XCMP queue for delivery to the
+	/// given `destination`.
+	fn queue_depth(destination: &Location) -> u32;
+}
+
+/// Implementation for chains that don't use XCMP: the queue is always empty.
+impl XcmpQueueInspector for () {
+	fn queue_depth(_: &Location) -> u32 {
+		0
+	}
+}
+
+/// Router that rejects messages with the `SendError::Transport(CONGESTED)` error if the outbound
+/// XCMP queue to the message destination already holds `MaxDepth` or more messages.
+///
+/// The sender may handle the error, e.g. by retrying later. Messages that are not accepted by the
+/// `Inner` router are rejected with the same error, so the router may be used in a tuple with
+/// other routers.
+pub struct QueueDepthAwareRouter<Inner, MaxDepth, Inspector>(
+	PhantomData<(Inner, MaxDepth, Inspector)>,
+);
+impl<Inner, MaxDepth, Inspector> SendXcm for QueueDepthAwareRouter<Inner, MaxDepth, Inspector>
+where
+	Inner: SendXcm,
+	MaxDepth: Get<u32>,
+	Inspector: XcmpQueueInspector,
+{
+	type Ticket = Inner::Ticket;
+
+	fn validate(
+		destination: &mut Option<Location>,
+		message: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket> {
+		let queue_depth = destination.as_ref().map(Inspector::queue_depth).unwrap_or(0);
+		let (ticket, price) = Inner::validate(destination, message)?;
+
+		if queue_depth >= MaxDepth::get() {
+			log::trace!(
+				target: "xcm::routing",
+				"QueueDepthAwareRouter rejected message: {} messages are already queued, limit is {}",
+				queue_depth,
+				MaxDepth::get(),
+			);
+			return Err(SendError::Transport(CONGESTED))
+		}
+
+		Ok((ticket, price))
+	}
+
+	fn deliver(ticket: Self::Ticket) -> Result<XcmHash, SendError> {
+		Inner::deliver(ticket)
+	}
+}
+
+/// Policy of converting the XCM to the older version, supported by its destination.
+pub trait XcmVersionDowngrade {
+	/// Convert the `xcm` to the `version`, or return `Err` if it may not be sent in that version.
+	fn downgrade(xcm: VersionedXcm<()>, version: XcmVersion) -> Result<VersionedXcm<()>, ()>;
+}
+
+/// Convert the XCM to the older version, if it may be expressed in that version.
+pub struct DowngradeXcmVersion;
+impl XcmVersionDowngrade for DowngradeXcmVersion {
+	fn downgrade(xcm: VersionedXcm<()>, version: XcmVersion) -> Result<VersionedXcm<()>, ()> {
+		xcm.into_version(version)
+	}
+}
+
+/// Never convert the XCM to the older version, for the chains that prefer to fail the delivery
+/// instead.
+pub struct NoVersionDowngrade;
+impl XcmVersionDowngrade for NoVersionDowngrade {
+	fn downgrade(xcm: VersionedXcm<()>, version: XcmVersion) -> Result<VersionedXcm<()>, ()> {
+		if version < xcm.identify_version() {
+			return Err(())
+		}
+		Ok(xcm)
+	}
+}
+
+/// Router which makes sure that the destination is able to decode the message.
+///
+/// The message is converted to the XCM version, supported by the destination according to
+/// `Versions` (e.g. `pallet_xcm`, which keeps the versions in its `SupportedVersion` storage),
+/// using the `Downgrade` policy. Messages to the destinations of unknown version, and messages
+/// that can't be converted to their version, are rejected with the
+/// `SendError::DestinationUnsupported` error. Messages that would not decode within the XCM
+/// decoding limits are rejected with the `SendError::Transport` error.
+///
+/// The `Inner` router receives the downgraded message, decoded back into the latest version, so
+/// it only carries what the destination is able to decode. The `Inner` router is still expected
+/// to convert it to the version of the destination itself (see `WrapVersion`).
+pub struct EnsureDecodableXcm<Inner, Versions, Downgrade = DowngradeXcmVersion>(
+	PhantomData<(Inner, Versions, Downgrade)>,
+);
+impl<Inner, Versions, Downgrade> SendXcm for EnsureDecodableXcm<Inner, Versions, Downgrade>
+where
+	Inner: SendXcm,
+	Versions: GetVersion,
+	Downgrade: XcmVersionDowngrade,
+{
+	type Ticket = Inner::Ticket;
+
+	fn validate(
+		destination: &mut Option<Location>,
+		message: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket> {
+		let (Some(dest), Some(xcm)) = (destination.as_ref(), message.as_ref()) else {
+			return Err(SendError::MissingArgument)
+		};
+
+		let version = Versions::get_version_for(dest).ok_or_else(|| {
+			log::trace!(
+				target: "xcm::routing",
+				"EnsureDecodableXcm rejected message: XCM version of {:?} is unknown",
+				dest,
+			);
+			SendError::DestinationUnsupported
+		})?;
+		let versioned_xcm = Downgrade::downgrade(VersionedXcm::from(xcm.clone()), version)
+			.map_err(|()| {
+				log::trace!(
+					target: "xcm::routing",
+					"EnsureDecodableXcm rejected message: {:?} supports XCM version {} only",
+					dest,
+					version,
+				);
+				SendError::DestinationUnsupported
+			})?;
+
+		let encoded = versioned_xcm.encode();
+		let decoded = VersionedXcm::<()>::decode_all_with_depth_limit(
+			MAX_XCM_DECODE_DEPTH,
+			&mut &encoded[..],
+		)
+		.map_err(|_| {
+			log::trace!(
+				target: "xcm::routing",
+				"EnsureDecodableXcm rejected message: {:?} would not be decodable",
+				xcm,
+			);
+			SendError::Transport("message is not decodable")
+		})?;
+		let downgraded_xcm = Xcm::<()>::try_from(decoded).map_err(|()| {
+			log::trace!(
+				target: "xcm::routing",
+				"EnsureDecodableXcm rejected message: {:?} can't be converted back to the latest version",
+				xcm,
+			);
+			SendError::DestinationUnsupported
+		})?;
+
+		*message = Some(downgraded_xcm);
+		Inner::validate(destination, message)
+	}
+
+	fn deliver(ticket: Self::Ticket) -> Result<XcmHash, SendError> {
+		Inner::deliver(ticket)
+	}
+}
+
+/// Source of the topics for the messages, sent by the `WithTopicSource` router.
 pub trait SourceTopic {
+	/// Returns the topic of the message, given the `entropy`, which is the message itself.
 	fn source_topic(entropy: impl Encode) -> XcmHash;
 }
 
+/// The same zero topic for all the messages.
 impl SourceTopic for () {
 	fn source_topic(_: impl Encode) -> XcmHash {
 		[0u8; 32]
 	}
 }
 
+/// The topic, derived from the content of the message: the `blake2_256` hash of its encoding.
+///
+/// Unlike the random topics of the `WithUniqueTopic`, identical messages get identical topics, so
+/// the destination may deduplicate the messages that are sent again, e.g. when retried.
+pub struct ContentHashTopic;
+impl SourceTopic for ContentHashTopic {
+	fn source_topic(entropy: impl Encode) -> XcmHash {
+		entropy.using_encoded(sp_io::hashing::blake2_256)
+	}
+}
+
 /// Wrapper router which, if the message does not already end with a `SetTopic` instruction,
-/// prepends one to the message filled with an ID from `TopicSource`. This ID is returned from a
+/// appends one to the message filled with an ID from `TopicSource`. This ID is returned from a
 /// successful `deliver`.
 ///
 /// This is designed to be at the top-level of any routers, since it will always mutate the
 /// passed `message` reference into a `None`. Don't try to combine it within a tuple except as the
 /// last element.
-pub struct WithTopicSource<Inner, TopicSource>(PhantomData<(Inner, TopicSource)>);
+pub struct WithTopicSource<Inner, TopicSource = ContentHashTopic>(
+	PhantomData<(Inner, TopicSource)>,
+);
 impl<Inner: SendXcm, TopicSource: SourceTopic> SendXcm for WithTopicSource<Inner, TopicSource> {
 	type Ticket = (Inner::Ticket, [u8; 32]);
 
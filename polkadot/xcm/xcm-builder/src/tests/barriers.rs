@@ -14,6 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
+use frame_support::traits::{ConstBool, Everything, Nothing};
 use xcm_executor::traits::Properties;
 
 use super::*;
@@ -309,3 +310,379 @@ fn suspension_should_work() {
 	);
 	assert_eq!(r, Ok(()));
 }
+
+#[test]
+fn max_instruction_count_barrier_should_work() {
+	type Barrier = MaxInstructionCountBarrier<ConstU32<3>>;
+	let max_weight = Weight::from_parts(10, 10);
+
+	let mut below_limit = Xcm::<()>(vec![ClearOrigin, ClearOrigin]);
+	let mut at_limit = Xcm::<()>(vec![ClearOrigin, ClearOrigin, ClearOrigin]);
+	let mut above_limit = Xcm::<()>(vec![ClearOrigin, ClearOrigin, ClearOrigin, ClearOrigin]);
+
+	assert_eq!(
+		Barrier::should_execute(
+			&Parent.into(),
+			below_limit.inner_mut(),
+			max_weight,
+			&mut props(Weight::zero()),
+		),
+		Ok(()),
+	);
+	assert_eq!(
+		Barrier::should_execute(
+			&Parent.into(),
+			at_limit.inner_mut(),
+			max_weight,
+			&mut props(Weight::zero()),
+		),
+		Ok(()),
+	);
+	assert_eq!(
+		Barrier::should_execute(
+			&Parent.into(),
+			above_limit.inner_mut(),
+			max_weight,
+			&mut props(Weight::zero()),
+		),
+		Err(ProcessMessageError::Overweight(max_weight)),
+	);
+}
+
+#[test]
+fn max_instruction_count_barrier_counts_nested_instructions_if_recursive() {
+	type TopLevelBarrier = MaxInstructionCountBarrier<ConstU32<3>, ConstBool<false>>;
+	type RecursiveBarrier = MaxInstructionCountBarrier<ConstU32<3>, ConstBool<true>>;
+	let max_weight = Weight::from_parts(10, 10);
+
+	// 2 top-level instructions and 3 nested instructions
+	let mut message = Xcm::<()>(vec![
+		SetAppendix(Xcm(vec![ClearOrigin, ClearOrigin])),
+		DepositReserveAsset {
+			assets: All.into(),
+			dest: Parent.into(),
+			xcm: Xcm(vec![ClearOrigin]),
+		},
+	]);
+
+	assert_eq!(
+		TopLevelBarrier::should_execute(
+			&Parent.into(),
+			message.inner_mut(),
+			max_weight,
+			&mut props(Weight::zero()),
+		),
+		Ok(()),
+	);
+	assert_eq!(
+		RecursiveBarrier::should_execute(
+			&Parent.into(),
+			message.inner_mut(),
+			max_weight,
+			&mut props(Weight::zero()),
+		),
+		Err(ProcessMessageError::Overweight(max_weight)),
+	);
+}
+
+#[test]
+fn all_of_barrier_should_work() {
+	type Accept = AllowUnpaidExecutionFrom<Everything>;
+	type Reject = AllowUnpaidExecutionFrom<Nothing>;
+	let mut message = Xcm::<()>(vec![ClearOrigin]);
+
+	assert_eq!(
+		AllOf::<(Accept, Accept)>::should_execute(
+			&Parent.into(),
+			message.inner_mut(),
+			Weight::from_parts(10, 10),
+			&mut props(Weight::zero()),
+		),
+		Ok(()),
+	);
+	assert_eq!(
+		AllOf::<(Accept, Reject)>::should_execute(
+			&Parent.into(),
+			message.inner_mut(),
+			Weight::from_parts(10, 10),
+			&mut props(Weight::zero()),
+		),
+		Err(ProcessMessageError::Unsupported),
+	);
+	assert_eq!(
+		AllOf::<(Reject, Accept)>::should_execute(
+			&Parent.into(),
+			message.inner_mut(),
+			Weight::from_parts(10, 10),
+			&mut props(Weight::zero()),
+		),
+		Err(ProcessMessageError::Unsupported),
+	);
+
+	// error of the first rejecting barrier is returned
+	assert_eq!(
+		AllOf::<(Accept, TakeWeightCredit, Reject)>::should_execute(
+			&Parent.into(),
+			message.inner_mut(),
+			Weight::from_parts(10, 10),
+			&mut props(Weight::zero()),
+		),
+		Err(ProcessMessageError::Overweight(Weight::from_parts(10, 10))),
+	);
+
+	// there's no barrier to allow the execution
+	assert_eq!(
+		AllOf::<()>::should_execute(
+			&Parent.into(),
+			message.inner_mut(),
+			Weight::from_parts(10, 10),
+			&mut props(Weight::zero()),
+		),
+		Err(ProcessMessageError::Unsupported),
+	);
+}
+
+#[test]
+fn any_of_barrier_should_work() {
+	type Accept = AllowUnpaidExecutionFrom<Everything>;
+	type Reject = AllowUnpaidExecutionFrom<Nothing>;
+	let mut message = Xcm::<()>(vec![ClearOrigin]);
+
+	assert_eq!(
+		AnyOf::<(Accept, Reject)>::should_execute(
+			&Parent.into(),
+			message.inner_mut(),
+			Weight::from_parts(10, 10),
+			&mut props(Weight::zero()),
+		),
+		Ok(()),
+	);
+	assert_eq!(
+		AnyOf::<(Reject, Accept)>::should_execute(
+			&Parent.into(),
+			message.inner_mut(),
+			Weight::from_parts(10, 10),
+			&mut props(Weight::zero()),
+		),
+		Ok(()),
+	);
+	assert_eq!(
+		AnyOf::<(Reject, Reject)>::should_execute(
+			&Parent.into(),
+			message.inner_mut(),
+			Weight::from_parts(10, 10),
+			&mut props(Weight::zero()),
+		),
+		Err(ProcessMessageError::Unsupported),
+	);
+
+	// barriers may be nested
+	assert_eq!(
+		AnyOf::<(AllOf<(Accept, Reject)>, AllOf<(Accept, Accept)>)>::should_execute(
+			&Parent.into(),
+			message.inner_mut(),
+			Weight::from_parts(10, 10),
+			&mut props(Weight::zero()),
+		),
+		Ok(()),
+	);
+}
+
+parameter_types! {
+	pub static Congested: bool = false;
+	pub static TrustedDuringCongestion: Vec<Location> = vec![Parent.into()];
+}
+
+#[test]
+fn congestion_aware_barrier_should_work() {
+	type Barrier = CongestionAwareBarrier<
+		AllowUnpaidExecutionFrom<Everything>,
+		Congested,
+		IsInVec<TrustedDuringCongestion>,
+	>;
+
+	let mut message = Xcm::<()>(vec![ClearOrigin]);
+	let mut should_execute = |origin: Location| {
+		Barrier::should_execute(
+			&origin,
+			message.inner_mut(),
+			Weight::from_parts(10, 10),
+			&mut props(Weight::zero()),
+		)
+	};
+
+	// when chain is not congested, all messages are passed to the inner barrier
+	Congested::set(false);
+	assert_eq!(should_execute(Parent.into()), Ok(()));
+	assert_eq!(should_execute(Parachain(1000).into()), Ok(()));
+
+	// when chain is congested, only messages from trusted origins are accepted
+	Congested::set(true);
+	assert_eq!(should_execute(Parent.into()), Ok(()));
+	assert_eq!(should_execute(Parachain(1000).into()), Err(ProcessMessageError::Yield));
+
+	// when congestion is gone, messages from all origins are accepted again
+	Congested::set(false);
+	assert_eq!(should_execute(Parent.into()), Ok(()));
+	assert_eq!(should_execute(Parachain(1000).into()), Ok(()));
+}
+
+#[test]
+fn congestion_aware_barrier_respects_inner_barrier() {
+	type Barrier = CongestionAwareBarrier<
+		AllowUnpaidExecutionFrom<Nothing>,
+		Congested,
+		IsInVec<TrustedDuringCongestion>,
+	>;
+
+	let mut message = Xcm::<()>(vec![ClearOrigin]);
+	for congested in [false, true] {
+		Congested::set(congested);
+		assert_eq!(
+			Barrier::should_execute(
+				&Parent.into(),
+				message.inner_mut(),
+				Weight::from_parts(10, 10),
+				&mut props(Weight::zero()),
+			),
+			Err(ProcessMessageError::Unsupported),
+		);
+	}
+}
+
+thread_local! {
+	pub static OBSERVED_PASSES: RefCell<Vec<(Location, Xcm<()>)>> = RefCell::new(vec![]);
+	pub static OBSERVED_DENIALS: RefCell<Vec<(Location, ProcessMessageError)>> = RefCell::new(vec![]);
+}
+
+pub struct TestObserver;
+impl ObserveBarrierResult for TestObserver {
+	fn on_pass(origin: &Location, message: &Xcm<()>) {
+		OBSERVED_PASSES.with(|p| p.borrow_mut().push((origin.clone(), message.clone())));
+	}
+
+	fn on_deny(origin: &Location, error: &ProcessMessageError) {
+		OBSERVED_DENIALS.with(|d| d.borrow_mut().push((origin.clone(), *error)));
+	}
+}
+
+#[test]
+fn barrier_with_observer_should_work() {
+	AllowUnpaidFrom::set(vec![Parent.into()]);
+
+	type Barrier =
+		BarrierWithObserver<AllowUnpaidExecutionFrom<IsInVec<AllowUnpaidFrom>>, TestObserver>;
+
+	let mut message = Xcm::<()>(vec![ClearOrigin]);
+	let mut should_execute = |origin: Location| {
+		Barrier::should_execute(
+			&origin,
+			message.inner_mut(),
+			Weight::from_parts(10, 10),
+			&mut props(Weight::zero()),
+		)
+	};
+
+	assert_eq!(should_execute(Parent.into()), Ok(()));
+	assert_eq!(should_execute(Parachain(1000).into()), Err(ProcessMessageError::Unsupported));
+	assert_eq!(should_execute(Parent.into()), Ok(()));
+	assert_eq!(should_execute(Parachain(2000).into()), Err(ProcessMessageError::Unsupported));
+	assert_eq!(should_execute(Parachain(3000).into()), Err(ProcessMessageError::Unsupported));
+
+	assert_eq!(
+		OBSERVED_PASSES.with(|p| p.borrow().clone()),
+		vec![
+			(Parent.into(), Xcm::<()>(vec![ClearOrigin])),
+			(Parent.into(), Xcm::<()>(vec![ClearOrigin])),
+		],
+	);
+	assert_eq!(
+		OBSERVED_DENIALS.with(|d| d.borrow().clone()),
+		vec![
+			(Parachain(1000).into(), ProcessMessageError::Unsupported),
+			(Parachain(2000).into(), ProcessMessageError::Unsupported),
+			(Parachain(3000).into(), ProcessMessageError::Unsupported),
+		],
+	);
+
+	// the default observer isn't interested in the allowed messages
+	assert!(!NullObserver::OBSERVES_PASSES);
+	assert_eq!(
+		BarrierWithObserver::<AllowUnpaidExecutionFrom<IsInVec<AllowUnpaidFrom>>>::should_execute(
+			&Parent.into(),
+			message.inner_mut(),
+			Weight::from_parts(10, 10),
+			&mut props(Weight::zero()),
+		),
+		Ok(()),
+	);
+}
+
+parameter_types! {
+	pub static TrustedBridgeHubs: Vec<Location> = vec![(Parent, Parachain(1002)).into()];
+}
+
+#[test]
+fn validate_xcm_origin_for_bridge_should_work() {
+	type Barrier = ValidateXcmOriginForBridge<IsInVec<TrustedBridgeHubs>>;
+
+	let trusted_hub: Location = (Parent, Parachain(1002)).into();
+	let untrusted_hub: Location = (Parent, Parachain(1000)).into();
+
+	let mut export_message = Xcm::<()>(vec![
+		UnpaidExecution { weight_limit: Unlimited, check_origin: None },
+		ExportMessage {
+			network: ByGenesis([0; 32]),
+			destination: Here,
+			xcm: Xcm(vec![ClearOrigin]),
+		},
+	]);
+	let should_execute = |origin: &Location, message: &mut Xcm<()>| {
+		Barrier::should_execute(
+			origin,
+			message.inner_mut(),
+			Weight::from_parts(10, 10),
+			&mut props(Weight::zero()),
+		)
+	};
+
+	// export messages are only accepted from trusted bridge hubs
+	assert_eq!(should_execute(&trusted_hub, &mut export_message), Ok(()));
+	assert_eq!(
+		should_execute(&untrusted_hub, &mut export_message),
+		Err(ProcessMessageError::Unsupported),
+	);
+
+	// other messages are accepted from everywhere
+	let mut transfer_message = Xcm::<()>(vec![
+		WithdrawAsset((Here, 100).into()),
+		DepositAsset {
+			assets: AllCounted(1).into(),
+			beneficiary: AccountIndex64 { index: 1, network: None }.into(),
+		},
+	]);
+	assert_eq!(should_execute(&trusted_hub, &mut transfer_message), Ok(()));
+	assert_eq!(should_execute(&untrusted_hub, &mut transfer_message), Ok(()));
+	assert_eq!(should_execute(&Parent.into(), &mut transfer_message), Ok(()));
+
+	// export messages, nested into locally executed programs, are rejected too
+	let export_instruction = ExportMessage {
+		network: ByGenesis([0; 32]),
+		destination: Here,
+		xcm: Xcm(vec![ClearOrigin]),
+	};
+	let mut nested_export_message = Xcm::<()>(vec![
+		SetErrorHandler(Xcm(vec![SetAppendix(Xcm(vec![export_instruction.clone()]))])),
+		ClearOrigin,
+	]);
+	assert_eq!(should_execute(&trusted_hub, &mut nested_export_message), Ok(()));
+	assert_eq!(
+		should_execute(&untrusted_hub, &mut nested_export_message),
+		Err(ProcessMessageError::Unsupported),
+	);
+	let mut appendix_export_message = Xcm::<()>(vec![SetAppendix(Xcm(vec![export_instruction]))]);
+	assert_eq!(
+		should_execute(&untrusted_hub, &mut appendix_export_message),
+		Err(ProcessMessageError::Unsupported),
+	);
+}
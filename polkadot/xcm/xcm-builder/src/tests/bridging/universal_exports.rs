@@ -106,3 +106,361 @@ fn sovereign_paid_remote_exporter_produces_xcm_which_does_not_trap_assets() {
 	// xcm exported
 	assert_eq!(exported_xcm().len(), 1);
 }
+
+mod metered_blob_dispatcher {
+	use super::*;
+	use crate::{tests::pay::mock, GlobalConsensusParachainConvertsFor};
+	use frame_support::{
+		assert_ok,
+		traits::fungible::{Inspect, Mutate},
+	};
+	use xcm_executor::traits::ConvertLocation;
+
+	parameter_types! {
+		pub OurPlace: InteriorLocation = [GlobalConsensus(Local::get()), Parachain(100)].into();
+		pub FeeCollector: mock::AccountId = mock::AccountId::new([77u8; 32]);
+		pub static DispatchedBlobs: Vec<Vec<u8>> = vec![];
+		pub static ChargedFees: Vec<(mock::AccountId, mock::Balance)> = vec![];
+		pub static DispatchFails: bool = false;
+	}
+
+	/// Dispatcher that records all blobs in the `DispatchedBlobs`, unless `DispatchFails` is set.
+	pub struct TestBlobDispatcher;
+	impl DispatchBlob for TestBlobDispatcher {
+		fn dispatch_blob(blob: Vec<u8>) -> Result<(), DispatchBlobError> {
+			if DispatchFails::get() {
+				return Err(DispatchBlobError::RoutingError)
+			}
+			DispatchedBlobs::mutate(|blobs| blobs.push(blob));
+			Ok(())
+		}
+	}
+
+	/// Charges 10 units per byte, but lets empty messages through for free.
+	pub struct PerByteFee;
+	impl ComputeBlobFee<mock::Balance> for PerByteFee {
+		fn compute_fee(blob: &[u8]) -> mock::Balance {
+			let BridgeMessage { message, .. } = Decode::decode(&mut &blob[..]).unwrap();
+			let message: Xcm<()> = message.try_into().unwrap();
+			if message.0.len() <= 2 {
+				return 0
+			}
+			blob.len() as mock::Balance * 10
+		}
+	}
+
+	pub struct TestOnBlobFeeCharged;
+	impl OnBlobFeeCharged<mock::AccountId, mock::Balance> for TestOnBlobFeeCharged {
+		fn on_blob_fee_charged(payer: &mock::AccountId, fee: mock::Balance) {
+			ChargedFees::mutate(|fees| fees.push((payer.clone(), fee)));
+		}
+	}
+
+	type SovereignAccountOf = GlobalConsensusParachainConvertsFor<OurPlace, mock::AccountId>;
+	type Dispatcher = MeteredBlobDispatcher<
+		TestBlobDispatcher,
+		PerByteFee,
+		FeeCollector,
+		mock::Balances,
+		OurPlace,
+		SovereignAccountOf,
+		mock::AccountId,
+		TestOnBlobFeeCharged,
+	>;
+
+	fn blob(instructions: Vec<Instruction<()>>) -> Vec<u8> {
+		let mut message = vec![
+			UniversalOrigin(GlobalConsensus(Remote::get())),
+			DescendOrigin(Parachain(1000).into()),
+		];
+		message.extend(instructions);
+		BridgeMessage {
+			universal_dest: VersionedInteriorLocation::from(OurPlace::get()),
+			message: VersionedXcm::from(Xcm(message)),
+		}
+		.encode()
+	}
+
+	fn payer() -> mock::AccountId {
+		let origin = Location::new(2, [GlobalConsensus(Remote::get()), Parachain(1000)]);
+		assert_eq!(blob_origin(&blob(vec![]), &OurPlace::get()), Some(origin.clone()));
+		SovereignAccountOf::convert_location(&origin).unwrap()
+	}
+
+	#[test]
+	fn metered_blob_dispatcher_charges_fee() {
+		mock::new_test_ext().execute_with(|| {
+			let blob = blob(vec![ClearOrigin]);
+			let fee = PerByteFee::compute_fee(&blob);
+			assert_ok!(mock::Balances::mint_into(&payer(), fee * 2));
+
+			assert_eq!(Dispatcher::dispatch_blob(blob.clone()), Ok(()));
+			assert_eq!(mock::Balances::balance(&payer()), fee);
+			assert_eq!(mock::Balances::balance(&FeeCollector::get()), fee);
+			assert_eq!(ChargedFees::get(), vec![(payer(), fee)]);
+			assert_eq!(DispatchedBlobs::get(), vec![blob]);
+		});
+	}
+
+	#[test]
+	fn metered_blob_dispatcher_rejects_blob_if_origin_cannot_pay() {
+		mock::new_test_ext().execute_with(|| {
+			let blob = blob(vec![ClearOrigin]);
+			let fee = PerByteFee::compute_fee(&blob);
+			assert_ok!(mock::Balances::mint_into(&payer(), fee / 2));
+
+			assert_eq!(Dispatcher::dispatch_blob(blob), Err(DispatchBlobError::NotEnoughFunds));
+			assert_eq!(mock::Balances::balance(&payer()), fee / 2);
+			assert_eq!(mock::Balances::balance(&FeeCollector::get()), 0);
+			assert_eq!(ChargedFees::get(), vec![]);
+			assert_eq!(DispatchedBlobs::get(), Vec::<Vec<u8>>::new());
+		});
+	}
+
+	#[test]
+	fn metered_blob_dispatcher_refunds_fee_if_dispatch_fails() {
+		mock::new_test_ext().execute_with(|| {
+			DispatchFails::set(true);
+			let blob = blob(vec![ClearOrigin]);
+			let fee = PerByteFee::compute_fee(&blob);
+			assert_ok!(mock::Balances::mint_into(&payer(), fee * 2));
+
+			assert_eq!(Dispatcher::dispatch_blob(blob), Err(DispatchBlobError::RoutingError));
+			assert_eq!(mock::Balances::balance(&payer()), fee * 2);
+			assert_eq!(mock::Balances::balance(&FeeCollector::get()), 0);
+			assert_eq!(ChargedFees::get(), vec![]);
+		});
+	}
+
+	#[test]
+	fn metered_blob_dispatcher_rejects_blob_with_unknown_origin() {
+		mock::new_test_ext().execute_with(|| {
+			// the blob doesn't start with the `UniversalOrigin` instruction
+			let blob = BridgeMessage {
+				universal_dest: VersionedInteriorLocation::from(OurPlace::get()),
+				message: VersionedXcm::from(Xcm::<()>(vec![ClearOrigin, ClearOrigin, ClearOrigin])),
+			}
+			.encode();
+			assert_ne!(PerByteFee::compute_fee(&blob), 0);
+
+			assert_eq!(Dispatcher::dispatch_blob(blob), Err(DispatchBlobError::UnknownOrigin));
+			assert_eq!(DispatchedBlobs::get(), Vec::<Vec<u8>>::new());
+		});
+	}
+
+	#[test]
+	fn metered_blob_dispatcher_skips_zero_fee() {
+		mock::new_test_ext().execute_with(|| {
+			// the origin has no funds at all, but the blob is free
+			let blob = blob(vec![]);
+			assert_eq!(PerByteFee::compute_fee(&blob), 0);
+
+			assert_eq!(Dispatcher::dispatch_blob(blob.clone()), Ok(()));
+			assert_eq!(mock::Balances::balance(&payer()), 0);
+			assert_eq!(ChargedFees::get(), vec![]);
+			assert_eq!(DispatchedBlobs::get(), vec![blob]);
+		});
+	}
+}
+
+mod local_exporter_with_fee {
+	use super::*;
+	use crate::tests::pay::mock;
+	use frame_support::{
+		assert_ok,
+		traits::fungible::{Inspect, Mutate},
+	};
+	use xcm_executor::with_sending_origin;
+
+	parameter_types! {
+		pub OurPlace: InteriorLocation = [GlobalConsensus(Local::get()), Parachain(100)].into();
+		pub BridgeTreasury: mock::AccountId = mock::AccountId::new([88u8; 32]);
+		pub static ExportFee: mock::Balance = 100;
+		pub FeeAssetId: AssetId = AssetId(Here.into());
+	}
+
+	pub struct TestExportFee;
+	impl ExportFeeProvider<mock::Balance> for TestExportFee {
+		fn fee_for(_network: &NetworkId, _channel: u32, _message: &Xcm<()>) -> mock::Balance {
+			ExportFee::get()
+		}
+	}
+
+	type Exporter = LocalExporterWithFee<
+		TestMessageExporter,
+		OurPlace,
+		TestExportFee,
+		FeeAssetId,
+		BridgeTreasury,
+		mock::Balances,
+		mock::SovereignAccountOf,
+		mock::AccountId,
+	>;
+
+	fn account(id: u8) -> mock::AccountId {
+		mock::AccountId::new([id; 32])
+	}
+
+	fn message_from(id: u8) -> Xcm<()> {
+		Xcm(vec![
+			DescendOrigin(Junction::AccountId32 { network: None, id: [id; 32] }.into()),
+			ClearOrigin,
+		])
+	}
+
+	fn send_without_origin(message: Xcm<()>) -> Result<(XcmHash, Assets), SendError> {
+		send_xcm::<Exporter>(
+			Location::new(2, [GlobalConsensus(Remote::get()), Parachain(1000)]),
+			message,
+		)
+	}
+
+	fn send_from(id: u8) -> Result<(XcmHash, Assets), SendError> {
+		let origin = Location::new(0, [Junction::AccountId32 { network: None, id: [id; 32] }]);
+		with_sending_origin(origin, || send_without_origin(message_from(id)))
+	}
+
+	#[test]
+	fn local_exporter_with_fee_charges_origin() {
+		mock::new_test_ext().execute_with(|| {
+			assert_ok!(mock::Balances::mint_into(&account(1), 1_000));
+
+			assert_ok!(send_from(1));
+			assert_eq!(mock::Balances::balance(&account(1)), 900);
+			assert_eq!(exported_xcm().len(), 1);
+		});
+	}
+
+	#[test]
+	fn local_exporter_with_fee_adds_fee_to_price() {
+		mock::new_test_ext().execute_with(|| {
+			assert_ok!(mock::Balances::mint_into(&account(1), 1_000));
+
+			let (_, price) = send_from(1).unwrap();
+			assert_eq!(price, Asset::from((Here, 100u128)).into());
+		});
+	}
+
+	#[test]
+	fn local_exporter_with_fee_credits_treasury() {
+		mock::new_test_ext().execute_with(|| {
+			assert_ok!(mock::Balances::mint_into(&account(1), 1_000));
+			assert_ok!(mock::Balances::mint_into(&account(2), 1_000));
+
+			assert_ok!(send_from(1));
+			assert_ok!(send_from(2));
+			assert_eq!(mock::Balances::balance(&BridgeTreasury::get()), 200);
+			assert_eq!(mock::Balances::total_issuance(), 2_000);
+		});
+	}
+
+	#[test]
+	fn local_exporter_with_fee_rejects_message_if_origin_cannot_pay() {
+		mock::new_test_ext().execute_with(|| {
+			assert_ok!(mock::Balances::mint_into(&account(1), 50));
+
+			assert_eq!(send_from(1), Err(Unroutable));
+			assert_eq!(mock::Balances::balance(&account(1)), 50);
+			assert_eq!(mock::Balances::balance(&BridgeTreasury::get()), 0);
+			assert_eq!(exported_xcm(), vec![]);
+		});
+	}
+
+	#[test]
+	fn local_exporter_with_fee_rejects_message_if_origin_is_unknown() {
+		mock::new_test_ext().execute_with(|| {
+			assert_ok!(mock::Balances::mint_into(&account(1), 1_000));
+
+			// the message origin can't be guessed from the message itself
+			assert_eq!(send_without_origin(message_from(1)), Err(Unroutable));
+			assert_eq!(mock::Balances::balance(&account(1)), 1_000);
+			assert_eq!(exported_xcm(), vec![]);
+		});
+	}
+
+	#[test]
+	fn local_exporter_with_fee_skips_zero_fee() {
+		mock::new_test_ext().execute_with(|| {
+			// the origin has no funds at all, but the export is free
+			ExportFee::set(0);
+
+			assert_eq!(send_from(1).map(|(_, price)| price), Ok(Assets::new()));
+			assert_eq!(mock::Balances::balance(&account(1)), 0);
+			assert_eq!(mock::Balances::balance(&BridgeTreasury::get()), 0);
+			assert_eq!(exported_xcm().len(), 1);
+		});
+	}
+}
+
+mod multi_hop_bridge_router {
+	use super::*;
+	use frame_support::assert_ok;
+
+	parameter_types! {
+		pub OurPlace: InteriorLocation = [GlobalConsensus(Local::get()), Parachain(100)].into();
+		pub FarRemote: NetworkId = ByGenesis([2; 32]);
+		// bridge hub in our consensus, bridging to the `Remote`
+		pub LocalBridgeHub: Location = Location::new(1, [Parachain(1002)]);
+		// bridge hub in the `Remote` consensus, bridging to the `FarRemote`
+		pub RemoteBridgeHub: Location = Parachain(1013).into();
+		pub LocalBridgeTable: Vec<NetworkExportTableItem> = vec![
+			NetworkExportTableItem::new(Remote::get(), None, LocalBridgeHub::get(), None),
+		];
+		pub RemoteBridgeTable: Vec<NetworkExportTableItem> = vec![
+			NetworkExportTableItem::new(FarRemote::get(), None, RemoteBridgeHub::get(), None),
+		];
+	}
+
+	type Router = MultiHopBridgeRouter<
+		(
+			BridgeHop<Remote, NetworkExportTable<LocalBridgeTable>>,
+			BridgeHop<FarRemote, NetworkExportTable<RemoteBridgeTable>>,
+		),
+		TestMessageSender,
+		OurPlace,
+	>;
+
+	#[test]
+	fn multi_hop_bridge_router_nests_export_messages() {
+		let message = Xcm(vec![Trap(1)]);
+		assert_ok!(send_xcm::<Router>(
+			Location::new(2, [GlobalConsensus(FarRemote::get()), Parachain(1000)]),
+			message.clone(),
+		));
+
+		let unpaid_execution = UnpaidExecution { weight_limit: Unlimited, check_origin: None };
+		let expected = Xcm(vec![
+			unpaid_execution.clone(),
+			ExportMessage {
+				network: Remote::get(),
+				destination: [Parachain(1013)].into(),
+				xcm: Xcm(vec![
+					unpaid_execution,
+					ExportMessage {
+						network: FarRemote::get(),
+						destination: [Parachain(1000)].into(),
+						xcm: message,
+					},
+				]),
+			},
+		]);
+		let sent: Vec<_> =
+			sent_xcm().into_iter().map(|(dest, message, _)| (dest, message)).collect();
+		assert_eq!(sent, vec![(LocalBridgeHub::get(), expected)]);
+	}
+
+	#[test]
+	fn multi_hop_bridge_router_is_not_applicable_to_other_networks() {
+		let dest = Location::new(2, [GlobalConsensus(Remote::get()), Parachain(1000)]);
+		let mut dest_wrapper = Some(dest.clone());
+		let mut msg_wrapper = Some(Xcm(vec![Trap(1)]));
+
+		assert_eq!(
+			Router::validate(&mut dest_wrapper, &mut msg_wrapper).err(),
+			Some(NotApplicable)
+		);
+		assert_eq!(dest_wrapper, Some(dest));
+		assert_eq!(msg_wrapper, Some(Xcm(vec![Trap(1)])));
+		assert_eq!(sent_xcm(), vec![]);
+	}
+}
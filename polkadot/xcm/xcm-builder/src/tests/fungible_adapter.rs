@@ -0,0 +1,174 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Tests for the fungible adapters.
+
+use super::{
+	pay::mock::{new_test_ext, AccountId, Balance, Balances, RelayLocation, SovereignAccountOf},
+	*,
+};
+use frame_support::{
+	assert_ok,
+	traits::tokens::fungible::{Inspect, Mutate},
+};
+
+parameter_types! {
+	pub static HookCalls: Vec<(Option<AccountId>, Option<AccountId>, Balance)> = vec![];
+}
+
+/// Hook that records all the calls.
+pub struct RecordingHook;
+impl TransferHook<AccountId, Balance> for RecordingHook {
+	fn on_transfer(
+		from: Option<&AccountId>,
+		to: Option<&AccountId>,
+		amount: Balance,
+	) -> Result<(), XcmError> {
+		HookCalls::mutate(|calls| calls.push((from.cloned(), to.cloned(), amount)));
+		Ok(())
+	}
+}
+
+/// Hook that vetoes the odd amounts.
+pub struct VetoOddAmounts;
+impl TransferHook<AccountId, Balance> for VetoOddAmounts {
+	fn on_transfer(_: Option<&AccountId>, _: Option<&AccountId>, amount: Balance) -> XcmResult {
+		if amount % 2 == 1 {
+			return Err(XcmError::FailedToTransactAsset("odd amount"))
+		}
+		Ok(())
+	}
+}
+
+/// Hook that always fails.
+pub struct FailingHook;
+impl TransferHook<AccountId, Balance> for FailingHook {
+	fn on_transfer(_: Option<&AccountId>, _: Option<&AccountId>, _: Balance) -> XcmResult {
+		Err(XcmError::FailedToTransactAsset("hook failed"))
+	}
+}
+
+type TransactorWithHooks<BeforeT, AfterT> = FungibleAdapterWithHooks<
+	Balances,
+	IsConcrete<RelayLocation>,
+	SovereignAccountOf,
+	AccountId,
+	BeforeT,
+	AfterT,
+>;
+
+fn account(id: u8) -> AccountId {
+	AccountId::new([id; 32])
+}
+
+fn location(id: u8) -> Location {
+	Junction::AccountId32 { network: None, id: [id; 32] }.into()
+}
+
+fn context() -> XcmContext {
+	XcmContext { origin: None, message_id: XcmHash::default(), topic: None }
+}
+
+#[test]
+fn fungible_adapter_with_hooks_calls_hooks() {
+	new_test_ext().execute_with(|| {
+		type Transactor = TransactorWithHooks<RecordingHook, RecordingHook>;
+
+		assert_ok!(Transactor::deposit_asset(&(Here, 100).into(), &location(1), None));
+		assert_ok!(Transactor::internal_transfer_asset(
+			&(Here, 30).into(),
+			&location(1),
+			&location(2),
+			&context(),
+		));
+		assert_ok!(Transactor::withdraw_asset(&(Here, 20).into(), &location(2), None));
+
+		assert_eq!(Balances::balance(&account(1)), 70);
+		assert_eq!(Balances::balance(&account(2)), 10);
+		// both hooks are called for every movement of the funds
+		assert_eq!(
+			HookCalls::get(),
+			vec![
+				(None, Some(account(1)), 100),
+				(None, Some(account(1)), 100),
+				(Some(account(1)), Some(account(2)), 30),
+				(Some(account(1)), Some(account(2)), 30),
+				(Some(account(2)), None, 20),
+				(Some(account(2)), None, 20),
+			],
+		);
+	});
+}
+
+#[test]
+fn fungible_adapter_with_hooks_respects_veto() {
+	new_test_ext().execute_with(|| {
+		type Transactor = TransactorWithHooks<VetoOddAmounts, RecordingHook>;
+
+		assert_eq!(
+			Transactor::deposit_asset(&(Here, 101).into(), &location(1), None),
+			Err(XcmError::FailedToTransactAsset("odd amount")),
+		);
+		assert_eq!(Balances::balance(&account(1)), 0);
+
+		assert_ok!(Transactor::deposit_asset(&(Here, 100).into(), &location(1), None));
+		assert_eq!(
+			Transactor::internal_transfer_asset(
+				&(Here, 31).into(),
+				&location(1),
+				&location(2),
+				&context(),
+			),
+			Err(XcmError::FailedToTransactAsset("odd amount")),
+		);
+		assert_eq!(Balances::balance(&account(1)), 100);
+		assert_eq!(Balances::balance(&account(2)), 0);
+
+		// the after hook is not called for the vetoed movements
+		assert_eq!(HookCalls::get(), vec![(None, Some(account(1)), 100)]);
+	});
+}
+
+#[test]
+fn fungible_adapter_with_hooks_rolls_back_when_after_hook_fails() {
+	new_test_ext().execute_with(|| {
+		type Transactor = TransactorWithHooks<RecordingHook, FailingHook>;
+
+		assert_ok!(Balances::mint_into(&account(1), 100));
+		assert_eq!(
+			Transactor::internal_transfer_asset(
+				&(Here, 30).into(),
+				&location(1),
+				&location(2),
+				&context(),
+			),
+			Err(XcmError::FailedToTransactAsset("hook failed")),
+		);
+		assert_eq!(
+			Transactor::withdraw_asset(&(Here, 30).into(), &location(1), None),
+			Err(XcmError::FailedToTransactAsset("hook failed")),
+		);
+		assert_eq!(
+			Transactor::deposit_asset(&(Here, 30).into(), &location(2), None),
+			Err(XcmError::FailedToTransactAsset("hook failed")),
+		);
+
+		// none of the movements have happened
+		assert_eq!(Balances::balance(&account(1)), 100);
+		assert_eq!(Balances::balance(&account(2)), 0);
+		assert_eq!(Balances::total_issuance(), 100);
+	});
+}
@@ -0,0 +1,132 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Tests for the fungibles adapters.
+
+use super::{
+	pay::mock::{
+		new_test_ext, AccountId, Assets, Balance, FromLocationToAsset, SovereignAccountOf,
+		TreasuryAccountId, INITIAL_BALANCE, UNITS,
+	},
+	*,
+};
+use frame_support::{
+	assert_ok,
+	traits::tokens::{fungibles::Mutate, Preservation::Preserve},
+};
+use sp_runtime::Permill;
+use xcm_executor::{traits::ConvertLocation, AssetsInHolding};
+
+type Matcher = ConvertedConcreteId<u128, Balance, FromLocationToAsset<Location, u128>, JustTry>;
+
+/// Transactor that loses 1% of every internal transfer.
+pub struct LossyTransferAdapter;
+impl TransactAsset for LossyTransferAdapter {
+	fn internal_transfer_asset(
+		what: &Asset,
+		from: &Location,
+		to: &Location,
+		_context: &XcmContext,
+	) -> Result<AssetsInHolding, XcmError> {
+		let (asset_id, amount) = Matcher::matches_fungibles(what)?;
+		let received = amount / 100 * 99;
+		let source = SovereignAccountOf::convert_location(from).unwrap();
+		let dest = SovereignAccountOf::convert_location(to).unwrap();
+		Assets::transfer(asset_id, &source, &dest, received, Preserve)
+			.map_err(|e| XcmError::FailedToTransactAsset(e.into()))?;
+		Ok((what.id.clone(), received).into())
+	}
+}
+
+parameter_types! {
+	pub SlippageDeficitAccount: AccountId = AccountId::new([13u8; 32]);
+	pub static Slippage: Permill = Permill::from_percent(2);
+}
+
+type TransactorWithSlippage = FungiblesAdapterWithSlippage<
+	Slippage,
+	LossyTransferAdapter,
+	Assets,
+	Matcher,
+	SovereignAccountOf,
+	AccountId,
+	SlippageDeficitAccount,
+>;
+
+fn treasury_location() -> Location {
+	Location::new(1, [Parachain(42), Plurality { id: BodyId::Treasury, part: BodyPart::Voice }])
+}
+
+fn recipient() -> AccountId {
+	AccountId::new([7u8; 32])
+}
+
+fn recipient_location() -> Location {
+	Junction::AccountId32 { network: None, id: recipient().into() }.into()
+}
+
+#[test]
+fn fungibles_adapter_with_slippage_covers_deficit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::mint_into(1, &SlippageDeficitAccount::get(), 10 * UNITS));
+		let context = XcmContext { origin: None, message_id: XcmHash::default(), topic: None };
+
+		let transferred = TransactorWithSlippage::internal_transfer_asset(
+			&(Parent, 10 * UNITS).into(),
+			&treasury_location(),
+			&recipient_location(),
+			&context,
+		);
+
+		// the whole requested amount is reported as transferred
+		assert_eq!(transferred, Ok((Parent, 10 * UNITS).into()));
+		// recipient has received the whole amount
+		assert_eq!(Assets::balance(1, &recipient()), 10 * UNITS);
+		// 99% have been transferred from the treasury
+		assert_eq!(
+			Assets::balance(1, &TreasuryAccountId::get()),
+			INITIAL_BALANCE - 10 * UNITS / 100 * 99
+		);
+		// and the rest has been transferred from the deficit account
+		assert_eq!(
+			Assets::balance(1, &SlippageDeficitAccount::get()),
+			10 * UNITS - 10 * UNITS / 100
+		);
+	});
+}
+
+#[test]
+fn fungibles_adapter_with_slippage_rejects_large_slippage() {
+	new_test_ext().execute_with(|| {
+		Slippage::set(Permill::from_parts(5_000));
+		assert_ok!(Assets::mint_into(1, &SlippageDeficitAccount::get(), 10 * UNITS));
+		let context = XcmContext { origin: None, message_id: XcmHash::default(), topic: None };
+
+		assert_eq!(
+			TransactorWithSlippage::internal_transfer_asset(
+				&(Parent, 10 * UNITS).into(),
+				&treasury_location(),
+				&recipient_location(),
+				&context,
+			),
+			Err(XcmError::FailedToTransactAsset("slippage is too large")),
+		);
+		// the transfer of the `Inner` transactor has been reverted
+		assert_eq!(Assets::balance(1, &TreasuryAccountId::get()), INITIAL_BALANCE);
+		assert_eq!(Assets::balance(1, &recipient()), 0);
+		assert_eq!(Assets::balance(1, &SlippageDeficitAccount::get()), 10 * UNITS);
+	});
+}
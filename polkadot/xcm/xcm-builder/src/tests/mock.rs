@@ -111,6 +111,7 @@ impl GetDispatchInfo for TestCall {
 
 thread_local! {
 	pub static SENT_XCM: RefCell<Vec<(Location, Xcm<()>, XcmHash)>> = RefCell::new(Vec::new());
+	pub static SENDING_ORIGINS: RefCell<Vec<Option<Location>>> = RefCell::new(Vec::new());
 	pub static EXPORTED_XCM: RefCell<
 		Vec<(NetworkId, u32, InteriorLocation, InteriorLocation, Xcm<()>, XcmHash)>
 	> = RefCell::new(Vec::new());
@@ -136,6 +137,9 @@ thread_local! {
 pub fn sent_xcm() -> Vec<(Location, opaque::Xcm, XcmHash)> {
 	SENT_XCM.with(|q| (*q.borrow()).clone())
 }
+pub fn sending_origins() -> Vec<Option<Location>> {
+	SENDING_ORIGINS.with(|q| (*q.borrow()).clone())
+}
 pub fn set_send_price(p: impl Into<Asset>) {
 	SEND_PRICE.with(|l| l.replace(p.into().into()));
 }
@@ -175,6 +179,7 @@ impl SendXcm for TestMessageSender {
 		let msg = msg.take().unwrap();
 		let hash = fake_message_hash(&msg);
 		let triplet = (dest.take().unwrap(), msg, hash);
+		SENDING_ORIGINS.with(|q| q.borrow_mut().push(xcm_executor::current_sending_origin()));
 		Ok((triplet, SEND_PRICE.with(|l| l.borrow().clone())))
 	}
 	fn deliver(triplet: (Location, Xcm<()>, XcmHash)) -> Result<XcmHash, SendError> {
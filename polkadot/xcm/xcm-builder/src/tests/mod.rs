@@ -32,10 +32,13 @@ mod barriers;
 mod basic;
 mod bridging;
 mod expecting;
+mod fungible_adapter;
+mod fungibles_adapter;
 mod locking;
 mod origins;
 mod pay;
 mod querying;
+mod routing;
 mod transacting;
 mod version_subscriptions;
 mod weight;
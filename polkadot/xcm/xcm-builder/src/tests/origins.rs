@@ -154,3 +154,77 @@ fn unpaid_execution_should_work() {
 	);
 	assert_eq!(r, Outcome::Complete { used: Weight::from_parts(10, 10) });
 }
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TestOrigin {
+	Parent,
+	Sibling(u32),
+	Sovereign,
+}
+
+pub struct ParentAsTestOrigin;
+impl ConvertOrigin<TestOrigin> for ParentAsTestOrigin {
+	fn convert_origin(
+		origin: impl Into<Location>,
+		kind: OriginKind,
+	) -> Result<TestOrigin, Location> {
+		let origin = origin.into();
+		match (kind, origin.unpack()) {
+			(OriginKind::Native, (1, [])) => Ok(TestOrigin::Parent),
+			_ => Err(origin),
+		}
+	}
+}
+
+pub struct SiblingAsTestOrigin;
+impl ConvertOrigin<TestOrigin> for SiblingAsTestOrigin {
+	fn convert_origin(
+		origin: impl Into<Location>,
+		kind: OriginKind,
+	) -> Result<TestOrigin, Location> {
+		let origin = origin.into();
+		match (kind, origin.unpack()) {
+			(OriginKind::Native, (1, [Parachain(id)])) => Ok(TestOrigin::Sibling(*id)),
+			_ => Err(origin),
+		}
+	}
+}
+
+parameter_types! {
+	pub const SovereignOrigin: TestOrigin = TestOrigin::Sovereign;
+}
+
+#[test]
+fn multi_origin_as_single_combinator_should_work() {
+	type Converter =
+		MultiOriginAsSingleCombinator<(ParentAsTestOrigin, SiblingAsTestOrigin), SovereignOrigin>;
+
+	// both trusted origins are converted into the same origin
+	assert_eq!(
+		Converter::convert_origin(Location::parent(), OriginKind::Native),
+		Ok(TestOrigin::Sovereign),
+	);
+	assert_eq!(
+		Converter::convert_origin(Location::new(1, [Parachain(1000)]), OriginKind::Native),
+		Ok(TestOrigin::Sovereign),
+	);
+
+	// while the tuple itself keeps origins distinct
+	assert_eq!(
+		<(ParentAsTestOrigin, SiblingAsTestOrigin)>::convert_origin(
+			Location::new(1, [Parachain(1000)]),
+			OriginKind::Native,
+		),
+		Ok(TestOrigin::Sibling(1000)),
+	);
+
+	// unknown origins are rejected
+	assert_eq!(
+		Converter::convert_origin(Location::new(0, [Parachain(1000)]), OriginKind::Native),
+		Err(Location::new(0, [Parachain(1000)])),
+	);
+	assert_eq!(
+		Converter::convert_origin(Location::parent(), OriginKind::Superuser),
+		Err(Location::parent()),
+	);
+}
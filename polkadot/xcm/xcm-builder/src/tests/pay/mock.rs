@@ -109,6 +109,7 @@ impl pallet_assets::Config for Test {
 	type RemoveItemsLimit = RemoveItemsLimit;
 	type AssetIdParameter = AssetIdForAssets;
 	type CallbackHandle = ();
+	type TransferCallback = ();
 	#[cfg(feature = "runtime-benchmarks")]
 	type BenchmarkHelper = ();
 }
@@ -238,7 +239,7 @@ impl ConvertLocation<AccountId> for TreasuryToAccount {
 	}
 }
 
-type SovereignAccountOf = (
+pub type SovereignAccountOf = (
 	AccountId32Aliases<AnyNetwork, AccountId>,
 	TreasuryToAccount,
 	HashedDescription<AccountId, DescribeFamily<DescribeAllTerminal>>,
@@ -16,6 +16,7 @@
 
 use super::*;
 
-mod mock;
+pub(super) mod mock;
 mod pay;
 mod salary;
+mod weight;
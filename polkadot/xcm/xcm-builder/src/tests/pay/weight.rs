@@ -0,0 +1,61 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Tests for the `AdaptiveWeightBounds`, which needs the block weight of the mock runtime.
+
+use super::{
+	mock::{new_test_ext, Test},
+	*,
+};
+
+use frame_support::dispatch::DispatchClass;
+use sp_runtime::Perbill;
+
+#[test]
+fn adaptive_weight_bounds_should_work() {
+	parameter_types! {
+		pub InstrWeight: Weight = Weight::from_parts(1_000_000, 1_000_000);
+		pub ScaleFactor: Perbill = Perbill::from_percent(50);
+	}
+	type Weigher = AdaptiveWeightBounds<
+		FixedWeightBounds<InstrWeight, TestCall, MaxInstructions>,
+		ScaleFactor,
+		Test,
+	>;
+
+	new_test_ext().execute_with(|| {
+		let mut message = Xcm::<TestCall>(vec![ClearOrigin, ClearOrigin]);
+
+		// empty block => base weight is returned
+		assert_eq!(Weigher::weight(&mut message), Ok(Weight::from_parts(2_000_000, 2_000_000)));
+		assert_eq!(
+			Weigher::instr_weight(&ClearOrigin),
+			Ok(Weight::from_parts(1_000_000, 1_000_000))
+		);
+
+		// block is 90% full => weight is inflated by 50% * 90% = 45%
+		let max_block = <Test as frame_system::Config>::BlockWeights::get().max_block;
+		frame_system::Pallet::<Test>::register_extra_weight_unchecked(
+			Weight::from_parts(max_block.ref_time() / 10 * 9, 0),
+			DispatchClass::Mandatory,
+		);
+		assert_eq!(Weigher::weight(&mut message), Ok(Weight::from_parts(2_900_000, 2_900_000)));
+		assert_eq!(
+			Weigher::instr_weight(&ClearOrigin),
+			Ok(Weight::from_parts(1_450_000, 1_450_000))
+		);
+	});
+}
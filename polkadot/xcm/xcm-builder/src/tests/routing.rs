@@ -0,0 +1,359 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use frame_support::{assert_ok, traits::ConstU32};
+use xcm::{GetVersion, Version as XcmVersion, VersionedXcm};
+use xcm_executor::with_sending_origin;
+
+parameter_types! {
+	pub static PrimaryRouterError: Option<SendError> = None;
+	pub static PrimarySentXcm: Vec<(Location, Xcm<()>)> = vec![];
+}
+
+/// Router that either fails with `PrimaryRouterError` or accepts all messages.
+pub struct TestPrimaryRouter;
+impl SendXcm for TestPrimaryRouter {
+	type Ticket = (Location, Xcm<()>);
+
+	fn validate(
+		dest: &mut Option<Location>,
+		msg: &mut Option<Xcm<()>>,
+	) -> SendResult<(Location, Xcm<()>)> {
+		if let Some(e) = PrimaryRouterError::get() {
+			// emulate router that takes the arguments before failing
+			if e != SendError::NotApplicable {
+				dest.take();
+				msg.take();
+			}
+			return Err(e)
+		}
+		Ok(((dest.take().unwrap(), msg.take().unwrap()), Assets::new()))
+	}
+
+	fn deliver(ticket: (Location, Xcm<()>)) -> Result<XcmHash, SendError> {
+		PrimarySentXcm::mutate(|sent| sent.push(ticket));
+		Ok([0u8; 32])
+	}
+}
+
+type Router = WithFallbackRouter<TestPrimaryRouter, TestMessageSender>;
+
+#[test]
+fn with_fallback_router_uses_primary_router() {
+	PrimaryRouterError::set(None);
+	let message = Xcm(vec![ClearOrigin]);
+	assert_ok!(send_xcm::<Router>(Parent.into(), message.clone()));
+	assert_eq!(PrimarySentXcm::get(), vec![(Parent.into(), message)]);
+	assert_eq!(sent_xcm(), vec![]);
+}
+
+#[test]
+fn with_fallback_router_uses_fallback_router_when_primary_is_not_applicable() {
+	PrimaryRouterError::set(Some(SendError::NotApplicable));
+	let message = Xcm(vec![ClearOrigin]);
+	let hash = fake_message_hash(&message);
+	assert_ok!(send_xcm::<Router>(Parent.into(), message.clone()));
+	assert_eq!(PrimarySentXcm::get(), vec![]);
+	assert_eq!(sent_xcm(), vec![(Parent.into(), message, hash)]);
+}
+
+#[test]
+fn with_fallback_router_uses_fallback_router_when_primary_is_congested() {
+	PrimaryRouterError::set(Some(SendError::Transport(CONGESTED)));
+	let message = Xcm(vec![ClearOrigin]);
+	let hash = fake_message_hash(&message);
+	assert_ok!(send_xcm::<Router>(Parent.into(), message.clone()));
+	assert_eq!(PrimarySentXcm::get(), vec![]);
+	assert_eq!(sent_xcm(), vec![(Parent.into(), message, hash)]);
+}
+
+#[test]
+fn with_fallback_router_does_not_use_fallback_router_on_fatal_errors() {
+	PrimaryRouterError::set(Some(SendError::Unroutable));
+	assert_eq!(
+		send_xcm::<Router>(Parent.into(), Xcm(vec![ClearOrigin])),
+		Err(SendError::Unroutable),
+	);
+	assert_eq!(PrimarySentXcm::get(), vec![]);
+	assert_eq!(sent_xcm(), vec![]);
+}
+
+parameter_types! {
+	pub CouncilOrigin: Location =
+		Location::new(0, [Plurality { id: BodyId::Executive, part: BodyPart::Voice }]);
+}
+
+type GovernanceRouter = PrioritizedRouter<
+	TestPrimaryRouter,
+	TestMessageSender,
+	frame_support::traits::Equals<CouncilOrigin>,
+>;
+
+fn send_from(origin: Option<Location>, message: Xcm<()>) -> Result<(XcmHash, Assets), SendError> {
+	match origin {
+		Some(origin) =>
+			with_sending_origin(origin, || send_xcm::<GovernanceRouter>(Parent.into(), message)),
+		None => send_xcm::<GovernanceRouter>(Parent.into(), message),
+	}
+}
+
+#[test]
+fn prioritized_router_uses_high_router_for_high_priority_origins() {
+	PrimaryRouterError::set(None);
+	let message = Xcm(vec![ClearOrigin]);
+	assert_ok!(send_from(Some(CouncilOrigin::get()), message.clone()));
+	assert_eq!(PrimarySentXcm::get(), vec![(Parent.into(), message)]);
+	assert_eq!(sent_xcm(), vec![]);
+}
+
+#[test]
+fn prioritized_router_uses_low_router_for_other_origins() {
+	PrimaryRouterError::set(None);
+	let origins = vec![
+		Some(AccountId32 { network: None, id: [1u8; 32] }.into()),
+		Some(Plurality { id: BodyId::Technical, part: BodyPart::Voice }.into()),
+		Some(Location::here()),
+		// origin is not passed
+		None,
+	];
+	for origin in origins {
+		assert_ok!(send_from(origin, Xcm(vec![ClearOrigin])));
+	}
+	assert_eq!(PrimarySentXcm::get(), vec![]);
+	assert_eq!(sent_xcm().len(), 4);
+}
+
+#[test]
+fn prioritized_router_ignores_origin_claimed_by_message() {
+	PrimaryRouterError::set(None);
+	let message = Xcm(vec![
+		DescendOrigin(Plurality { id: BodyId::Executive, part: BodyPart::Voice }.into()),
+		ClearOrigin,
+	]);
+	assert_ok!(send_from(None, message));
+	assert_eq!(PrimarySentXcm::get(), vec![]);
+	assert_eq!(sent_xcm().len(), 1);
+}
+
+#[test]
+fn prioritized_router_propagates_errors_of_selected_router() {
+	PrimaryRouterError::set(Some(SendError::NotApplicable));
+	assert_eq!(
+		send_from(Some(CouncilOrigin::get()), Xcm(vec![ClearOrigin])),
+		Err(SendError::NotApplicable),
+	);
+	assert_eq!(PrimarySentXcm::get(), vec![]);
+	assert_eq!(sent_xcm(), vec![]);
+
+	// the error of the high priority router doesn't affect other origins
+	assert_ok!(send_from(Some(Location::here()), Xcm(vec![ClearOrigin])));
+	assert_eq!(sent_xcm().len(), 1);
+}
+
+#[test]
+fn executor_passes_origin_of_sent_messages_to_routers() {
+	AllowUnpaidFrom::set(vec![[Parachain(1)].into()]);
+	let message = Xcm(vec![QueryPallet {
+		module_name: "Error".into(),
+		response_info: QueryResponseInfo {
+			destination: Parachain(1).into(),
+			query_id: 1,
+			max_weight: Weight::from_parts(50, 50),
+		},
+	}]);
+	let mut hash = fake_message_hash(&message);
+	let r = XcmExecutor::<TestConfig>::prepare_and_execute(
+		Parachain(1),
+		message,
+		&mut hash,
+		Weight::from_parts(50, 50),
+		Weight::zero(),
+	);
+	assert_eq!(r, Outcome::Complete { used: Weight::from_parts(10, 10) });
+	assert_eq!(sent_xcm().len(), 1);
+	assert_eq!(sending_origins(), vec![Some(Parachain(1).into())]);
+}
+
+parameter_types! {
+	pub static TestQueueDepth: u32 = 0;
+}
+
+/// Inspector that reports the `TestQueueDepth` for every destination.
+pub struct TestQueueInspector;
+impl XcmpQueueInspector for TestQueueInspector {
+	fn queue_depth(_: &Location) -> u32 {
+		TestQueueDepth::get()
+	}
+}
+
+type QueueAwareRouter = QueueDepthAwareRouter<TestMessageSender, ConstU32<2>, TestQueueInspector>;
+
+#[test]
+fn queue_depth_aware_router_rejects_messages_when_queue_is_saturated() {
+	let message = Xcm(vec![ClearOrigin]);
+
+	TestQueueDepth::set(1);
+	assert_ok!(send_xcm::<QueueAwareRouter>(Parent.into(), message.clone()));
+	assert_eq!(sent_xcm().len(), 1);
+
+	TestQueueDepth::set(2);
+	assert_eq!(
+		send_xcm::<QueueAwareRouter>(Parent.into(), message.clone()),
+		Err(SendError::Transport(CONGESTED)),
+	);
+	TestQueueDepth::set(100);
+	assert_eq!(
+		send_xcm::<QueueAwareRouter>(Parent.into(), message.clone()),
+		Err(SendError::Transport(CONGESTED)),
+	);
+	assert_eq!(sent_xcm().len(), 1);
+
+	// the message may be sent once the queue is drained
+	TestQueueDepth::set(0);
+	assert_ok!(send_xcm::<QueueAwareRouter>(Parent.into(), message));
+	assert_eq!(sent_xcm().len(), 2);
+}
+
+#[test]
+fn queue_depth_aware_router_never_rejects_messages_without_xcmp() {
+	type Router = QueueDepthAwareRouter<TestMessageSender, ConstU32<1>, ()>;
+
+	assert_eq!(<() as XcmpQueueInspector>::queue_depth(&Parent.into()), 0);
+	for _ in 0..10 {
+		assert_ok!(send_xcm::<Router>(Parent.into(), Xcm(vec![ClearOrigin])));
+	}
+	assert_eq!(sent_xcm().len(), 10);
+}
+
+/// The destinations and the XCM versions they support.
+pub struct TestVersions;
+impl GetVersion for TestVersions {
+	fn get_version_for(dest: &Location) -> Option<XcmVersion> {
+		match dest.unpack() {
+			(1, []) => Some(3),
+			(0, [Parachain(1000)]) => Some(4),
+			_ => None,
+		}
+	}
+}
+
+/// Downgrades the message and drops its topic for the destinations older than V4, so that the
+/// downgraded message differs from the original one.
+pub struct DowngradeAndDropTopic;
+impl XcmVersionDowngrade for DowngradeAndDropTopic {
+	fn downgrade(xcm: VersionedXcm<()>, version: XcmVersion) -> Result<VersionedXcm<()>, ()> {
+		match DowngradeXcmVersion::downgrade(xcm, version)? {
+			VersionedXcm::V3(mut xcm) => {
+				xcm.0.retain(|instruction| {
+					!matches!(instruction, xcm::v3::Instruction::SetTopic(_))
+				});
+				Ok(VersionedXcm::V3(xcm))
+			},
+			xcm => Ok(xcm),
+		}
+	}
+}
+
+#[test]
+fn ensure_decodable_xcm_downgrades_message_for_destination() {
+	type Router = EnsureDecodableXcm<TestMessageSender, TestVersions, DowngradeAndDropTopic>;
+
+	let message = Xcm(vec![ClearOrigin, SetTopic([1; 32])]);
+	assert!(matches!(
+		DowngradeXcmVersion::downgrade(VersionedXcm::from(message.clone()), 3),
+		Ok(VersionedXcm::V3(_)),
+	));
+
+	// the destination supports V3 only, the downgraded message is sent
+	assert_ok!(send_xcm::<Router>(Parent.into(), message.clone()));
+	// the destination supports V4, the message is sent as is
+	assert_ok!(send_xcm::<Router>(Parachain(1000).into(), message.clone()));
+	let downgraded_message = Xcm(vec![ClearOrigin]);
+	assert_eq!(
+		sent_xcm(),
+		vec![
+			(Parent.into(), downgraded_message.clone(), fake_message_hash(&downgraded_message)),
+			(Parachain(1000).into(), message.clone(), fake_message_hash(&message)),
+		],
+	);
+
+	// the version of the destination is unknown
+	assert_eq!(
+		send_xcm::<Router>(Parachain(2000).into(), message),
+		Err(SendError::DestinationUnsupported),
+	);
+}
+
+#[test]
+fn ensure_decodable_xcm_with_no_version_downgrade_rejects_older_destinations() {
+	type Router = EnsureDecodableXcm<TestMessageSender, TestVersions, NoVersionDowngrade>;
+
+	let message = Xcm(vec![ClearOrigin]);
+	assert_eq!(
+		send_xcm::<Router>(Parent.into(), message.clone()),
+		Err(SendError::DestinationUnsupported),
+	);
+	assert_ok!(send_xcm::<Router>(Parachain(1000).into(), message.clone()));
+	assert_eq!(
+		sent_xcm(),
+		vec![(Parachain(1000).into(), message.clone(), fake_message_hash(&message))],
+	);
+}
+
+#[test]
+fn ensure_decodable_xcm_rejects_undecodable_messages() {
+	type Router = EnsureDecodableXcm<TestMessageSender, TestVersions>;
+
+	let mut message = Xcm(vec![ClearOrigin]);
+	for _ in 0..xcm::MAX_XCM_DECODE_DEPTH {
+		message = Xcm(vec![SetAppendix(message)]);
+	}
+	assert_eq!(
+		send_xcm::<Router>(Parachain(1000).into(), message),
+		Err(SendError::Transport("message is not decodable")),
+	);
+	assert_eq!(sent_xcm(), vec![]);
+}
+
+#[test]
+fn with_topic_source_derives_topic_from_message_content() {
+	type Router = WithTopicSource<TestMessageSender>;
+
+	let message = Xcm(vec![ClearOrigin]);
+	let (topic, _) = send_xcm::<Router>(Parent.into(), message.clone()).unwrap();
+	assert_eq!(topic, message.using_encoded(sp_io::hashing::blake2_256));
+
+	// the same message sent again gets the same topic
+	let (same_topic, _) = send_xcm::<Router>(Parent.into(), message.clone()).unwrap();
+	assert_eq!(same_topic, topic);
+
+	// the different message gets a different topic
+	let (other_topic, _) =
+		send_xcm::<Router>(Parent.into(), Xcm(vec![ClearOrigin, ClearOrigin])).unwrap();
+	assert_ne!(other_topic, topic);
+
+	// the topic is set by the last instruction of the message
+	let mut expected = message;
+	expected.0.push(SetTopic(topic));
+	let sent: Vec<_> = sent_xcm().into_iter().map(|(_, message, _)| message).collect();
+	assert_eq!(sent[0], expected);
+	assert_eq!(sent[1], expected);
+
+	// the topic, set by the sender, is kept
+	let message = Xcm(vec![ClearOrigin, SetTopic([42; 32])]);
+	assert_eq!(send_xcm::<Router>(Parent.into(), message).unwrap().0, [42; 32]);
+}
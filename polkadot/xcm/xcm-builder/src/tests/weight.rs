@@ -241,3 +241,90 @@ fn weight_trader_tuple_should_work() {
 	// and no refund
 	assert_eq!(traders.refund_weight(Weight::from_parts(2, 2), &ctx), None);
 }
+
+#[test]
+fn per_instruction_weight_profiler_should_work() {
+	parameter_types! {
+		pub InstrWeight: Weight = Weight::from_parts(10, 10);
+	}
+	type Weigher =
+		PerInstructionWeightProfiler<FixedWeightBounds<InstrWeight, TestCall, MaxInstructions>>;
+
+	let mut message = Xcm::<TestCall>(vec![
+		WithdrawAsset((Here, 100u128).into()),
+		SetAppendix(Xcm(vec![ClearOrigin, ClearError])),
+		ClearOrigin,
+	]);
+	let total_weight = Weigher::weight(&mut message).unwrap();
+
+	let profile = take_instruction_weight_profile();
+	assert_eq!(
+		profile,
+		vec![
+			("WithdrawAsset", Weight::from_parts(10, 10)),
+			("SetAppendix", Weight::from_parts(30, 30)),
+			("ClearOrigin", Weight::from_parts(10, 10)),
+		],
+	);
+	assert_eq!(
+		profile
+			.iter()
+			.fold(Weight::zero(), |total, (_, weight)| total.saturating_add(*weight)),
+		total_weight,
+	);
+
+	// profile is cleared when taken
+	assert_eq!(take_instruction_weight_profile(), vec![]);
+}
+
+#[test]
+fn asset_specific_weight_multiplier_should_work() {
+	use sp_runtime::FixedU128;
+
+	parameter_types! {
+		pub InstrWeight: Weight = Weight::from_parts(10, 10);
+		pub ExpensiveAsset: AssetId = AssetId(Parachain(1000).into());
+		pub ExpensiveAssetMultiplier: FixedU128 = FixedU128::from_u32(3);
+		pub OtherExpensiveAsset: AssetId = AssetId(Parachain(2000).into());
+		pub OtherExpensiveAssetMultiplier: FixedU128 = FixedU128::from_rational(3, 2);
+	}
+	type Weigher = AssetSpecificWeightMultiplier<
+		ExpensiveAsset,
+		ExpensiveAssetMultiplier,
+		AssetSpecificWeightMultiplier<
+			OtherExpensiveAsset,
+			OtherExpensiveAssetMultiplier,
+			FixedWeightBounds<InstrWeight, TestCall, MaxInstructions>,
+		>,
+	>;
+
+	let buy_execution = |asset: Location| -> Instruction<TestCall> {
+		BuyExecution { fees: (asset, 100u128).into(), weight_limit: Unlimited }
+	};
+
+	// regular asset => fallback weight is used
+	assert_eq!(Weigher::instr_weight(&buy_execution(Here.into())), Ok(Weight::from_parts(10, 10)));
+	// overridden assets => weight is multiplied
+	assert_eq!(
+		Weigher::instr_weight(&buy_execution(Parachain(1000).into())),
+		Ok(Weight::from_parts(30, 30)),
+	);
+	assert_eq!(
+		Weigher::instr_weight(&buy_execution(Parachain(2000).into())),
+		Ok(Weight::from_parts(15, 15)),
+	);
+
+	// multiplier is applied to message weight too
+	let mut message = Xcm::<TestCall>(vec![
+		WithdrawAsset((Parachain(1000), 100u128).into()),
+		buy_execution(Parachain(1000).into()),
+		ClearOrigin,
+	]);
+	assert_eq!(Weigher::weight(&mut message), Ok(Weight::from_parts(70, 70)));
+	let mut message = Xcm::<TestCall>(vec![
+		WithdrawAsset((Here, 100u128).into()),
+		buy_execution(Here.into()),
+		ClearOrigin,
+	]);
+	assert_eq!(Weigher::weight(&mut message), Ok(Weight::from_parts(30, 30)));
+}
@@ -14,27 +14,164 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-use frame_support::storage::{with_transaction, TransactionOutcome};
+use frame_support::{
+	storage::{transactional::TRANSACTIONAL_LIMIT, with_transaction, TransactionOutcome},
+	traits::{ConstU32, Get},
+};
 use sp_runtime::DispatchError;
+use sp_std::marker::PhantomData;
 use xcm::latest::prelude::*;
 use xcm_executor::traits::ProcessTransaction;
 
+environmental::environmental!(rollback_depth: u32);
+
 /// Transactional processor implementation using frame transactional layers.
-pub struct FrameTransactionalProcessor;
-impl ProcessTransaction for FrameTransactionalProcessor {
+///
+/// Every instruction is processed in its own storage layer. The instructions, processed while
+/// another one is in progress (e.g. by an XCM executed recursively from a `Transact`), get layers
+/// nested into the layer of that instruction, so they are rolled back along with it. At most
+/// `MaxRollbackDepth` layers may be nested, deeper instructions fail with
+/// `XcmError::ExceedsStackLimit`.
+pub struct FrameTransactionalProcessor<MaxRollbackDepth = ConstU32<TRANSACTIONAL_LIMIT>>(
+	PhantomData<MaxRollbackDepth>,
+);
+impl<MaxRollbackDepth: Get<u32>> ProcessTransaction
+	for FrameTransactionalProcessor<MaxRollbackDepth>
+{
 	const IS_TRANSACTIONAL: bool = true;
 
 	fn process<F>(f: F) -> Result<(), XcmError>
 	where
 		F: FnOnce() -> Result<(), XcmError>,
 	{
-		with_transaction(|| -> TransactionOutcome<Result<_, DispatchError>> {
-			let output = f();
-			match &output {
-				Ok(()) => TransactionOutcome::Commit(Ok(output)),
-				_ => TransactionOutcome::Rollback(Ok(output)),
-			}
+		// Initialize the depth only for the outermost instruction.
+		rollback_depth::using_once(&mut 0, || {
+			rollback_depth::with(|depth| {
+				if *depth >= MaxRollbackDepth::get() {
+					return Err(XcmError::ExceedsStackLimit)
+				}
+				*depth = depth.saturating_add(1);
+				Ok(())
+			})
+			// This should always return `Some`, but let's play it safe.
+			.unwrap_or(Ok(()))?;
+
+			let result = with_transaction(|| -> TransactionOutcome<Result<_, DispatchError>> {
+				let output = f();
+				match &output {
+					Ok(()) => TransactionOutcome::Commit(Ok(output)),
+					_ => TransactionOutcome::Rollback(Ok(output)),
+				}
+			})
+			.map_err(|_| XcmError::ExceedsStackLimit);
+
+			rollback_depth::with(|depth| *depth = depth.saturating_sub(1));
+			result?
 		})
-		.map_err(|_| XcmError::ExceedsStackLimit)?
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::storage::unhashed;
+
+	fn put(key: &[u8]) {
+		unhashed::put(key, &true);
+	}
+
+	fn exists(key: &[u8]) -> bool {
+		unhashed::exists(key)
+	}
+
+	#[test]
+	fn single_level_rollback_works() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			assert_eq!(
+				<FrameTransactionalProcessor>::process(|| {
+					put(b"failed");
+					Err(XcmError::Unimplemented)
+				}),
+				Err(XcmError::Unimplemented),
+			);
+			assert!(!exists(b"failed"));
+
+			assert_eq!(
+				<FrameTransactionalProcessor>::process(|| {
+					put(b"succeeded");
+					Ok(())
+				}),
+				Ok(()),
+			);
+			assert!(exists(b"succeeded"));
+		});
+	}
+
+	#[test]
+	fn nested_rollback_is_scoped_to_its_level() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			// the failed inner instruction doesn't affect the outer one
+			assert_eq!(
+				FrameTransactionalProcessor::<ConstU32<2>>::process(|| {
+					put(b"outer");
+					let inner = FrameTransactionalProcessor::<ConstU32<2>>::process(|| {
+						put(b"inner");
+						Err(XcmError::Unimplemented)
+					});
+					assert_eq!(inner, Err(XcmError::Unimplemented));
+					Ok(())
+				}),
+				Ok(()),
+			);
+			assert!(exists(b"outer"));
+			assert!(!exists(b"inner"));
+
+			// the failed outer instruction rolls back the succeeded inner one
+			assert_eq!(
+				FrameTransactionalProcessor::<ConstU32<2>>::process(|| {
+					put(b"outer_2");
+					FrameTransactionalProcessor::<ConstU32<2>>::process(|| {
+						put(b"inner_2");
+						Ok(())
+					})?;
+					Err(XcmError::Unimplemented)
+				}),
+				Err(XcmError::Unimplemented),
+			);
+			assert!(!exists(b"outer_2"));
+			assert!(!exists(b"inner_2"));
+		});
+	}
+
+	#[test]
+	fn rollback_depth_is_limited() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			type Processor = FrameTransactionalProcessor<ConstU32<2>>;
+
+			let nested = || {
+				Processor::process(|| {
+					Processor::process(|| {
+						put(b"second");
+						Processor::process(|| {
+							put(b"third");
+							Ok(())
+						})
+					})
+				})
+			};
+			assert_eq!(nested(), Err(XcmError::ExceedsStackLimit));
+			assert!(!exists(b"second"));
+			assert!(!exists(b"third"));
+
+			// the depth is back to zero once the outermost instruction is processed
+			assert_eq!(
+				Processor::process(|| Processor::process(|| {
+					put(b"second");
+					Ok(())
+				})),
+				Ok(()),
+			);
+			assert!(exists(b"second"));
+		});
 	}
 }
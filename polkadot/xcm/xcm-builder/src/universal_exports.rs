@@ -16,11 +16,26 @@
 
 //! Traits and utilities to help with origin mutation and bridging.
 
-use frame_support::{ensure, traits::Get};
+use frame_support::{
+	ensure,
+	storage::{with_transaction, TransactionOutcome},
+	traits::{
+		fungible,
+		tokens::{Fortitude, Preservation},
+		Get,
+	},
+};
 use parity_scale_codec::{Decode, Encode};
+use sp_runtime::{
+	traits::{SaturatedConversion, Zero},
+	DispatchError,
+};
 use sp_std::{convert::TryInto, marker::PhantomData, prelude::*};
 use xcm::prelude::*;
-use xcm_executor::traits::{validate_export, ExportXcm};
+use xcm_executor::{
+	current_sending_origin,
+	traits::{validate_export, ConvertLocation, ExportXcm},
+};
 use SendError::*;
 
 /// Returns the network ID and consensus location within that network of the remote
@@ -87,6 +102,137 @@ impl<Exporter: ExportXcm, UniversalLocation: Get<InteriorLocation>> SendXcm
 	}
 }
 
+/// Provider of the fee for exporting a message over a bridge.
+pub trait ExportFeeProvider<Balance> {
+	/// Returns the fee for exporting the `message` to the `network` over the `channel`.
+	fn fee_for(network: &NetworkId, channel: u32, message: &Xcm<()>) -> Balance;
+}
+
+impl<Balance: Zero> ExportFeeProvider<Balance> for () {
+	fn fee_for(_network: &NetworkId, _channel: u32, _message: &Xcm<()>) -> Balance {
+		Zero::zero()
+	}
+}
+
+/// Implementation of `SendXcm` which uses the given `ExportXcm` implementation in order to forward
+/// the message over a bridge, charging the origin of the message for the export.
+///
+/// The origin of the message is the local location, passed to the router using
+/// `xcm_executor::with_sending_origin` (which is how the executor and `pallet-xcm` send
+/// messages). The fee, computed by `Fee`, is transferred from the sovereign account of the origin
+/// to the `Treasury` on delivery. If the origin is unknown or can't pay the fee, the message is
+/// `Unroutable`. Messages with zero fee are exported without any charging.
+///
+/// The fee is added to the price, returned by `validate`, as the `FeeAssetId` asset.
+///
+/// This is only useful when the local chain has bridging capabilities.
+pub struct LocalExporterWithFee<
+	Exporter,
+	UniversalLocation,
+	Fee,
+	FeeAssetId,
+	Treasury,
+	Fungible,
+	SovereignAccountOf,
+	AccountId,
+>(
+	PhantomData<(
+		Exporter,
+		UniversalLocation,
+		Fee,
+		FeeAssetId,
+		Treasury,
+		Fungible,
+		SovereignAccountOf,
+		AccountId,
+	)>,
+);
+impl<
+		Exporter: ExportXcm,
+		UniversalLocation: Get<InteriorLocation>,
+		Fee: ExportFeeProvider<Fungible::Balance>,
+		FeeAssetId: Get<AssetId>,
+		Treasury: Get<AccountId>,
+		Fungible: fungible::Mutate<AccountId>,
+		SovereignAccountOf: ConvertLocation<AccountId>,
+		AccountId,
+	> SendXcm
+	for LocalExporterWithFee<
+		Exporter,
+		UniversalLocation,
+		Fee,
+		FeeAssetId,
+		Treasury,
+		Fungible,
+		SovereignAccountOf,
+		AccountId,
+	>
+{
+	type Ticket = (Exporter::Ticket, Option<(AccountId, Fungible::Balance)>);
+
+	fn validate(
+		dest: &mut Option<Location>,
+		xcm: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket> {
+		let d = dest.take().ok_or(MissingArgument)?;
+		let universal_source = UniversalLocation::get();
+		let devolved = match ensure_is_remote(universal_source.clone(), d) {
+			Ok(x) => x,
+			Err(d) => {
+				*dest = Some(d);
+				return Err(NotApplicable)
+			},
+		};
+		let (network, destination) = devolved;
+		let xcm = xcm.take().ok_or(SendError::MissingArgument)?;
+
+		let channel = 0;
+		let fee = Fee::fee_for(&network, channel, &xcm);
+		let payment = if fee.is_zero() {
+			None
+		} else {
+			let origin = current_sending_origin().ok_or(Unroutable)?;
+			let payer = SovereignAccountOf::convert_location(&origin).ok_or(Unroutable)?;
+			let available =
+				Fungible::reducible_balance(&payer, Preservation::Preserve, Fortitude::Polite);
+			ensure!(available >= fee, Unroutable);
+			Some((payer, fee))
+		};
+
+		let (ticket, mut price) =
+			validate_export::<Exporter>(network, channel, universal_source, destination, xcm)?;
+		if !fee.is_zero() {
+			price.push((FeeAssetId::get(), fee.saturated_into::<u128>()).into());
+		}
+		Ok(((ticket, payment), price))
+	}
+
+	fn deliver((ticket, payment): Self::Ticket) -> Result<XcmHash, SendError> {
+		with_transaction(|| -> TransactionOutcome<Result<_, DispatchError>> {
+			let result = payment
+				.map_or(Ok(()), |(payer, fee)| {
+					Fungible::transfer(&payer, &Treasury::get(), fee, Preservation::Preserve)
+						.map(|_| ())
+						.map_err(|error| {
+							log::debug!(
+								target: "xcm::universal_exports",
+								"LocalExporterWithFee failed to charge fee {:?}: {:?}",
+								fee,
+								error,
+							);
+							Unroutable
+						})
+				})
+				.and_then(|()| Exporter::deliver(ticket));
+			match &result {
+				Ok(_) => TransactionOutcome::Commit(Ok(result)),
+				_ => TransactionOutcome::Rollback(Ok(result)),
+			}
+		})
+		.map_err(|_| Transport("too many nested storage layers"))?
+	}
+}
+
 pub trait ExporterFor {
 	/// Return the locally-routable bridge (if any) capable of forwarding `message` to the
 	/// `remote_location` on the remote `network`, together with the payment which is required.
@@ -110,7 +256,7 @@ impl ExporterFor for Tuple {
 	) -> Option<(Location, Option<Asset>)> {
 		for_tuples!( #(
 			if let Some(r) = Tuple::exporter_for(network, remote_location, message) {
-				return Some(r);
+				return Some(r)
 			}
 		)* );
 		None
@@ -118,6 +264,7 @@ impl ExporterFor for Tuple {
 }
 
 /// Configuration item representing a single exporter in the `NetworkExportTable`.
+#[derive(Clone, Encode, Decode, Debug, PartialEq, Eq, scale_info::TypeInfo)]
 pub struct NetworkExportTableItem {
 	/// Supported remote network.
 	pub remote_network: NetworkId,
@@ -143,6 +290,16 @@ impl NetworkExportTableItem {
 	) -> Self {
 		Self { remote_network, remote_location_filter, bridge, payment }
 	}
+
+	/// Returns true if the item may be used to export messages to `remote_location` within the
+	/// `network`.
+	pub fn matches(&self, network: &NetworkId, remote_location: &InteriorLocation) -> bool {
+		&self.remote_network == network &&
+			self.remote_location_filter
+				.as_ref()
+				.map(|filters| filters.iter().any(|filter| filter == remote_location))
+				.unwrap_or(true)
+	}
 }
 
 /// An adapter for the implementation of `ExporterFor`, which attempts to find the
@@ -157,13 +314,7 @@ impl<T: Get<Vec<NetworkExportTableItem>>> ExporterFor for NetworkExportTable<T>
 	) -> Option<(Location, Option<Asset>)> {
 		T::get()
 			.into_iter()
-			.find(|item| {
-				&item.remote_network == network &&
-					item.remote_location_filter
-						.as_ref()
-						.map(|filters| filters.iter().any(|filter| filter == remote_location))
-						.unwrap_or(true)
-			})
+			.find(|item| item.matches(network, remote_location))
 			.map(|item| (item.bridge, item.payment))
 	}
 }
@@ -335,6 +486,105 @@ impl<Bridges: ExporterFor, Router: SendXcm, UniversalLocation: Get<InteriorLocat
 	}
 }
 
+/// Function, that finds the bridge exporting messages to the given network. See
+/// [`ExporterFor::exporter_for`].
+pub type ExporterForFn =
+	fn(&NetworkId, &InteriorLocation, &Xcm<()>) -> Option<(Location, Option<Asset>)>;
+
+/// List of the hops, used by the `MultiHopBridgeRouter`.
+///
+/// Usually a tuple of [`BridgeHop`]s.
+pub trait HopList {
+	/// Returns the hops in the order in which the message passes them: the network, reached by
+	/// every hop, together with the function that finds the bridge exporting to this network.
+	fn hops() -> Vec<(NetworkId, ExporterForFn)>;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(1, 8)]
+impl HopList for Tuple {
+	fn hops() -> Vec<(NetworkId, ExporterForFn)> {
+		let mut hops = Vec::new();
+		for_tuples!( #( hops.extend(Tuple::hops()); )* );
+		hops
+	}
+}
+
+/// Single hop of the `MultiHopBridgeRouter` to the `Network` consensus, over the bridge found by
+/// `Bridges`.
+///
+/// The bridges of the first hop are relative to the local chain. The bridges of all other hops
+/// are interior locations within the consensus, reached by the previous hop.
+pub struct BridgeHop<Network, Bridges>(PhantomData<(Network, Bridges)>);
+impl<Network: Get<NetworkId>, Bridges: ExporterFor> HopList for BridgeHop<Network, Bridges> {
+	fn hops() -> Vec<(NetworkId, ExporterForFn)> {
+		vec![(Network::get(), Bridges::exporter_for)]
+	}
+}
+
+/// Implementation of `SendXcm` which passes the message over multiple bridges, e.g. when the
+/// destination consensus is only reachable through some intermediate consensus.
+///
+/// The message is wrapped inside an `ExportMessage` instruction for every hop of `Hops`, starting
+/// from the last one, and the result is sent to the bridge of the first hop. The last hop must
+/// reach the consensus of the destination, otherwise the router is not applicable. As with the
+/// `UnpaidRemoteExporter`, all the bridges must be configured to export the messages for free.
+///
+/// In the case that the message ends with a `SetTopic(T)`, then the message sent to the first
+/// bridge will also end with a `SetTopic` whose inner is `forward_id_for(T)`.
+pub struct MultiHopBridgeRouter<Hops, Router, UniversalLocation>(
+	PhantomData<(Hops, Router, UniversalLocation)>,
+);
+impl<Hops: HopList, Router: SendXcm, UniversalLocation: Get<InteriorLocation>> SendXcm
+	for MultiHopBridgeRouter<Hops, Router, UniversalLocation>
+{
+	type Ticket = Router::Ticket;
+
+	fn validate(
+		dest: &mut Option<Location>,
+		msg: &mut Option<Xcm<()>>,
+	) -> SendResult<Router::Ticket> {
+		let d = dest.as_ref().ok_or(MissingArgument)?;
+		let (remote_network, remote_location) =
+			ensure_is_remote(UniversalLocation::get(), d.clone()).map_err(|_| NotApplicable)?;
+		let hops = Hops::hops();
+		ensure!(hops.last().map(|(network, _)| *network) == Some(remote_network), NotApplicable);
+		let mut xcm = msg.take().ok_or(MissingArgument)?;
+
+		let maybe_forward_id = match xcm.last() {
+			Some(SetTopic(t)) => Some(forward_id_for(t)),
+			_ => None,
+		};
+
+		// wrap the message for every hop, starting from the last one
+		let mut destination = remote_location;
+		let mut first_bridge = None;
+		for (index, (network, exporter_for)) in hops.into_iter().enumerate().rev() {
+			let (bridge, maybe_payment) =
+				exporter_for(&network, &destination, &xcm).ok_or(Unroutable)?;
+			ensure!(maybe_payment.is_none(), Unroutable);
+			xcm = Xcm(vec![
+				UnpaidExecution { weight_limit: Unlimited, check_origin: None },
+				ExportMessage { network, destination, xcm },
+			]);
+			if index == 0 {
+				first_bridge = Some(bridge);
+				break
+			}
+			destination = bridge.try_into().map_err(|_| Unroutable)?;
+		}
+		let bridge = first_bridge.ok_or(Unroutable)?;
+
+		if let Some(forward_id) = maybe_forward_id {
+			xcm.0.push(SetTopic(forward_id));
+		}
+		validate_send::<Router>(bridge, xcm)
+	}
+
+	fn deliver(ticket: Router::Ticket) -> Result<XcmHash, SendError> {
+		Router::deliver(ticket)
+	}
+}
+
 pub trait DispatchBlob {
 	/// Takes an incoming blob from over some point-to-point link (usually from some sort of
 	/// inter-consensus bridge) and then does what needs to be done with it. Usually this means
@@ -386,6 +636,14 @@ pub enum DispatchBlobError {
 	RoutingError,
 	NonUniversalDestination,
 	WrongGlobal,
+	/// The origin of the blob is unable to pay the dispatch fee.
+	NotEnoughFunds,
+	/// The origin of the blob is unknown or may not be converted into the account.
+	UnknownOrigin,
+	/// The version (sequence number) of the blob is not the one, expected over its channel.
+	WrongVersion,
+	/// Blobs are not dispatched at the moment, e.g. because the bridge is malfunctioning.
+	NotApplicable,
 }
 
 pub struct BridgeBlobDispatcher<Router, OurPlace, OurPlaceBridgeInstance>(
@@ -429,6 +687,214 @@ impl<
 	}
 }
 
+/// Tracker of the sequence numbers of blobs, sent by the `SequencedBlobHauler` or dispatched by
+/// the `OrderedBridgeBlobDispatcher`.
+///
+/// Sequences of all channels are expected to start at zero and wrap around after `u64::MAX`.
+pub trait SequenceTracker {
+	/// Identifier of the channel (e.g. the bridge lane) the blobs are delivered over.
+	type Channel: Encode + Decode;
+
+	/// Returns the sequence number of the next blob over the `channel`.
+	fn expected_sequence(channel: &Self::Channel) -> u64;
+	/// Advances the sequence number of the `channel`.
+	fn advance_sequence(channel: &Self::Channel);
+}
+
+/// Header of blobs, sent by the `SequencedBlobHauler` and dispatched by the
+/// `OrderedBridgeBlobDispatcher`.
+#[derive(Clone, Encode, Decode, Debug, PartialEq, Eq)]
+pub struct SequencedBlobHeader<Channel> {
+	/// The channel the blob is delivered over.
+	pub channel: Channel,
+	/// Sequence number of the blob within the channel.
+	pub sequence: u64,
+}
+
+/// `HaulBlob` implementation that prepends the [`SequencedBlobHeader`] to every blob, sent over
+/// the `Channel`.
+///
+/// This is the export side of the `OrderedBridgeBlobDispatcher`. The sequence number of the
+/// `Channel` is only advanced if the `Inner` hauler succeeds.
+pub struct SequencedBlobHauler<Inner, SequenceStore, Channel>(
+	PhantomData<(Inner, SequenceStore, Channel)>,
+);
+impl<Inner: HaulBlob, SequenceStore: SequenceTracker, Channel: Get<SequenceStore::Channel>> HaulBlob
+	for SequencedBlobHauler<Inner, SequenceStore, Channel>
+{
+	fn haul_blob(blob: Vec<u8>) -> Result<(), HaulBlobError> {
+		let channel = Channel::get();
+		let sequence = SequenceStore::expected_sequence(&channel);
+		let mut sequenced_blob = SequencedBlobHeader { channel, sequence }.encode();
+		sequenced_blob.extend(blob);
+
+		Inner::haul_blob(sequenced_blob)?;
+		SequenceStore::advance_sequence(&Channel::get());
+		Ok(())
+	}
+}
+
+/// `DispatchBlob` implementation that enforces in-order delivery of blobs over every channel.
+///
+/// Every blob starts with the [`SequencedBlobHeader`], followed by the blob for the `Inner`
+/// dispatcher. Blobs with the sequence number other than the one, expected by the `SequenceStore`,
+/// are rejected with `DispatchBlobError::WrongVersion`. The expected sequence number is only
+/// advanced if the `Inner` dispatcher succeeds, so the failed blob may be delivered again.
+pub struct OrderedBridgeBlobDispatcher<Inner, SequenceStore>(PhantomData<(Inner, SequenceStore)>);
+impl<Inner: DispatchBlob, SequenceStore: SequenceTracker> DispatchBlob
+	for OrderedBridgeBlobDispatcher<Inner, SequenceStore>
+{
+	fn dispatch_blob(blob: Vec<u8>) -> Result<(), DispatchBlobError> {
+		let mut inner_blob = &blob[..];
+		let SequencedBlobHeader { channel, sequence } =
+			SequencedBlobHeader::<SequenceStore::Channel>::decode(&mut inner_blob)
+				.map_err(|_| DispatchBlobError::InvalidEncoding)?;
+		let expected = SequenceStore::expected_sequence(&channel);
+		if sequence != expected {
+			log::debug!(
+				target: "xcm::universal_exports",
+				"OrderedBridgeBlobDispatcher got blob {} while expecting {}",
+				sequence,
+				expected,
+			);
+			return Err(DispatchBlobError::WrongVersion)
+		}
+
+		Inner::dispatch_blob(inner_blob.to_vec())?;
+		SequenceStore::advance_sequence(&channel);
+		Ok(())
+	}
+}
+
+/// Computes the fee for dispatching the given blob.
+pub trait ComputeBlobFee<Balance> {
+	/// Returns the fee that must be paid for dispatching the `blob`.
+	fn compute_fee(blob: &[u8]) -> Balance;
+}
+
+impl<Balance: Zero> ComputeBlobFee<Balance> for () {
+	fn compute_fee(_blob: &[u8]) -> Balance {
+		Zero::zero()
+	}
+}
+
+/// Handler of the fee, charged by the `MeteredBlobDispatcher`.
+pub trait OnBlobFeeCharged<AccountId, Balance> {
+	/// Called when the `fee` has been charged from the `payer`. May be used e.g. to emit an
+	/// event.
+	fn on_blob_fee_charged(payer: &AccountId, fee: Balance);
+}
+
+impl<AccountId, Balance> OnBlobFeeCharged<AccountId, Balance> for () {
+	fn on_blob_fee_charged(_payer: &AccountId, _fee: Balance) {}
+}
+
+/// Returns the origin of the message in the given blob, as seen from `our_universal`.
+///
+/// Messages, exported by the `ExportMessage` instruction, are prefixed with the `UniversalOrigin`
+/// instruction and (optionally) the `DescendOrigin` instruction, which together describe the
+/// origin of the message.
+pub fn blob_origin(blob: &[u8], our_universal: &InteriorLocation) -> Option<Location> {
+	let BridgeMessage { message, .. } = Decode::decode(&mut &blob[..]).ok()?;
+	let message: Xcm<()> = message.try_into().ok()?;
+	let mut instructions = message.0.into_iter();
+	let mut origin: InteriorLocation = match instructions.next()? {
+		UniversalOrigin(junction) => junction.into(),
+		_ => return None,
+	};
+	if let Some(DescendOrigin(interior)) = instructions.next() {
+		origin.append_with(interior).ok()?;
+	}
+	Some(origin.relative_to(our_universal))
+}
+
+/// `DispatchBlob` implementation that charges the origin of the blob for the bridge bandwidth,
+/// consumed by the blob.
+///
+/// The fee is computed by `Fee` and is transferred from the sovereign account of the blob origin
+/// (see [`blob_origin`]) to the `FeeAccount`. The blob is passed to the `Inner` dispatcher in the
+/// same storage transaction, so the fee is refunded if the dispatch fails. If the origin is
+/// unknown, `DispatchBlobError::UnknownOrigin` is returned. If it can't pay the fee,
+/// `DispatchBlobError::NotEnoughFunds` is returned. Blobs with zero fee are dispatched without any
+/// charging.
+pub struct MeteredBlobDispatcher<
+	Inner,
+	Fee,
+	FeeAccount,
+	Fungible,
+	OurPlace,
+	SovereignAccountOf,
+	AccountId,
+	OnCharged = (),
+>(
+	PhantomData<(
+		Inner,
+		Fee,
+		FeeAccount,
+		Fungible,
+		OurPlace,
+		SovereignAccountOf,
+		AccountId,
+		OnCharged,
+	)>,
+);
+impl<
+		Inner: DispatchBlob,
+		Fee: ComputeBlobFee<Fungible::Balance>,
+		FeeAccount: Get<AccountId>,
+		Fungible: fungible::Mutate<AccountId>,
+		OurPlace: Get<InteriorLocation>,
+		SovereignAccountOf: ConvertLocation<AccountId>,
+		AccountId,
+		OnCharged: OnBlobFeeCharged<AccountId, Fungible::Balance>,
+	> DispatchBlob
+	for MeteredBlobDispatcher<
+		Inner,
+		Fee,
+		FeeAccount,
+		Fungible,
+		OurPlace,
+		SovereignAccountOf,
+		AccountId,
+		OnCharged,
+	>
+{
+	fn dispatch_blob(blob: Vec<u8>) -> Result<(), DispatchBlobError> {
+		let fee = Fee::compute_fee(&blob);
+		if fee.is_zero() {
+			return Inner::dispatch_blob(blob)
+		}
+
+		let payer = blob_origin(&blob, &OurPlace::get())
+			.and_then(|origin| SovereignAccountOf::convert_location(&origin))
+			.ok_or(DispatchBlobError::UnknownOrigin)?;
+		// the fee is refunded if the `Inner` dispatcher fails
+		with_transaction(|| -> TransactionOutcome<Result<_, DispatchError>> {
+			let result =
+				Fungible::transfer(&payer, &FeeAccount::get(), fee, Preservation::Preserve)
+					.map(|_| ())
+					.map_err(|error| {
+						log::debug!(
+							target: "xcm::universal_exports",
+							"MeteredBlobDispatcher failed to charge fee {:?}: {:?}",
+							fee,
+							error,
+						);
+						DispatchBlobError::NotEnoughFunds
+					})
+					.and_then(|()| Inner::dispatch_blob(blob));
+			match &result {
+				Ok(()) => TransactionOutcome::Commit(Ok(result)),
+				_ => TransactionOutcome::Rollback(Ok(result)),
+			}
+		})
+		.map_err(|_| DispatchBlobError::RoutingError)??;
+		OnCharged::on_blob_fee_charged(&payer, fee);
+
+		Ok(())
+	}
+}
+
 pub struct HaulBlobExporter<Bridge, BridgedNetwork, DestinationVersion, Price>(
 	PhantomData<(Bridge, BridgedNetwork, DestinationVersion, Price)>,
 );
@@ -523,9 +989,103 @@ impl<
 	}
 }
 
+/// Error of decompressing a blob.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DecompressError;
+
+/// Compression of blobs, hauled over a bridge.
+///
+/// The blobs, compressed by the `CompressedHaulBlobExporter` on the sending side, must be
+/// decompressed by the `DecompressingBlobDispatcher` with the same compressor on the receiving
+/// side.
+pub trait CompressBlob {
+	/// Compresses the `input`.
+	fn compress(input: &[u8]) -> Vec<u8>;
+	/// Decompresses the `input`, compressed by `compress`.
+	fn decompress(input: &[u8]) -> Result<Vec<u8>, DecompressError>;
+}
+
+/// `CompressBlob` implementation that leaves blobs as they are.
+pub struct NoOpCompressor;
+impl CompressBlob for NoOpCompressor {
+	fn compress(input: &[u8]) -> Vec<u8> {
+		input.to_vec()
+	}
+
+	fn decompress(input: &[u8]) -> Result<Vec<u8>, DecompressError> {
+		Ok(input.to_vec())
+	}
+}
+
+/// `CompressBlob` implementation that uses the LZ4 compression.
+///
+/// The compressed blob is prefixed with the size of the original blob. Blobs that would be
+/// decompressed into more than `MaxDecompressedSize` bytes are rejected before decompression.
+#[cfg(feature = "lz4")]
+pub struct LZ4BlobCompressor<MaxDecompressedSize>(PhantomData<MaxDecompressedSize>);
+#[cfg(feature = "lz4")]
+impl<MaxDecompressedSize: Get<u32>> CompressBlob for LZ4BlobCompressor<MaxDecompressedSize> {
+	fn compress(input: &[u8]) -> Vec<u8> {
+		lz4_flex::compress_prepend_size(input)
+	}
+
+	fn decompress(input: &[u8]) -> Result<Vec<u8>, DecompressError> {
+		let size = input
+			.get(..4)
+			.and_then(|size| size.try_into().ok())
+			.map(u32::from_le_bytes)
+			.ok_or(DecompressError)?;
+		ensure!(size <= MaxDecompressedSize::get(), DecompressError);
+		lz4_flex::decompress_size_prepended(input).map_err(|_| DecompressError)
+	}
+}
+
+/// `ExportXcm` implementation that compresses blobs, produced by the `Inner` exporter (e.g. the
+/// `HaulBlobExporter`), before they are hauled over the bridge.
+///
+/// The price of the export is the one, reported by the `Inner` exporter for the uncompressed
+/// blob.
+pub struct CompressedHaulBlobExporter<Inner, Compress>(PhantomData<(Inner, Compress)>);
+impl<Inner: ExportXcm<Ticket = (Vec<u8>, XcmHash)>, Compress: CompressBlob> ExportXcm
+	for CompressedHaulBlobExporter<Inner, Compress>
+{
+	type Ticket = Inner::Ticket;
+
+	fn validate(
+		network: NetworkId,
+		channel: u32,
+		universal_source: &mut Option<InteriorLocation>,
+		destination: &mut Option<InteriorLocation>,
+		message: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket> {
+		let ((blob, id), price) =
+			Inner::validate(network, channel, universal_source, destination, message)?;
+		Ok(((Compress::compress(&blob), id), price))
+	}
+
+	fn deliver(ticket: Self::Ticket) -> Result<XcmHash, SendError> {
+		Inner::deliver(ticket)
+	}
+}
+
+/// `DispatchBlob` implementation that decompresses blobs, compressed by the
+/// `CompressedHaulBlobExporter`, before passing them to the `Inner` dispatcher.
+///
+/// Blobs that can't be decompressed are rejected with `DispatchBlobError::InvalidEncoding`.
+pub struct DecompressingBlobDispatcher<Inner, Compress>(PhantomData<(Inner, Compress)>);
+impl<Inner: DispatchBlob, Compress: CompressBlob> DispatchBlob
+	for DecompressingBlobDispatcher<Inner, Compress>
+{
+	fn dispatch_blob(blob: Vec<u8>) -> Result<(), DispatchBlobError> {
+		let blob = Compress::decompress(&blob).map_err(|_| DispatchBlobError::InvalidEncoding)?;
+		Inner::dispatch_blob(blob)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use sp_std::collections::btree_map::BTreeMap;
 
 	#[test]
 	fn ensure_is_remote_works() {
@@ -550,6 +1110,325 @@ mod tests {
 		assert_eq!(x, Err((Parent, Polkadot, Parachain(1000)).into()));
 	}
 
+	frame_support::parameter_types! {
+		pub static BridgeAvailable: bool = false;
+		pub static DispatchedBlobs: Vec<Vec<u8>> = vec![];
+	}
+
+	pub struct TestBlobDispatcher;
+	impl DispatchBlob for TestBlobDispatcher {
+		fn dispatch_blob(blob: Vec<u8>) -> Result<(), DispatchBlobError> {
+			if !BridgeAvailable::get() {
+				return Err(DispatchBlobError::RoutingError)
+			}
+			DispatchedBlobs::mutate(|blobs| blobs.push(blob));
+			Ok(())
+		}
+	}
+
+	frame_support::parameter_types! {
+		pub static Sequences: BTreeMap<[u8; 4], u64> = BTreeMap::new();
+	}
+
+	pub struct TestSequenceTracker;
+	impl SequenceTracker for TestSequenceTracker {
+		type Channel = [u8; 4];
+
+		fn expected_sequence(channel: &[u8; 4]) -> u64 {
+			Sequences::get().get(channel).copied().unwrap_or_default()
+		}
+
+		fn advance_sequence(channel: &[u8; 4]) {
+			let next = Self::expected_sequence(channel).wrapping_add(1);
+			Sequences::mutate(|sequences| sequences.insert(*channel, next));
+		}
+	}
+
+	type TestOrderedBlobDispatcher =
+		OrderedBridgeBlobDispatcher<TestBlobDispatcher, TestSequenceTracker>;
+
+	fn sequenced_blob(channel: [u8; 4], sequence: u64, blob: Vec<u8>) -> Vec<u8> {
+		let mut sequenced_blob = SequencedBlobHeader { channel, sequence }.encode();
+		sequenced_blob.extend(blob);
+		sequenced_blob
+	}
+
+	#[test]
+	fn ordered_blob_dispatcher_dispatches_blobs_in_order() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			BridgeAvailable::set(true);
+			let channel = [0; 4];
+			for sequence in 0..3 {
+				assert_eq!(
+					TestOrderedBlobDispatcher::dispatch_blob(sequenced_blob(
+						channel,
+						sequence,
+						vec![sequence as u8],
+					)),
+					Ok(()),
+				);
+			}
+			assert_eq!(DispatchedBlobs::get(), vec![vec![0], vec![1], vec![2]]);
+			assert_eq!(TestSequenceTracker::expected_sequence(&channel), 3);
+		});
+	}
+
+	#[test]
+	fn ordered_blob_dispatcher_rejects_out_of_order_blobs() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			BridgeAvailable::set(true);
+			let channel = [0; 4];
+
+			// blob from the future
+			assert_eq!(
+				TestOrderedBlobDispatcher::dispatch_blob(sequenced_blob(channel, 1, vec![1])),
+				Err(DispatchBlobError::WrongVersion),
+			);
+			assert_eq!(
+				TestOrderedBlobDispatcher::dispatch_blob(sequenced_blob(channel, 0, vec![0])),
+				Ok(()),
+			);
+			// blob from the past
+			assert_eq!(
+				TestOrderedBlobDispatcher::dispatch_blob(sequenced_blob(channel, 0, vec![0])),
+				Err(DispatchBlobError::WrongVersion),
+			);
+			assert_eq!(DispatchedBlobs::get(), vec![vec![0]]);
+
+			// blob, that has failed to be dispatched, may be delivered again
+			BridgeAvailable::set(false);
+			assert_eq!(
+				TestOrderedBlobDispatcher::dispatch_blob(sequenced_blob(channel, 1, vec![1])),
+				Err(DispatchBlobError::RoutingError),
+			);
+			BridgeAvailable::set(true);
+			assert_eq!(
+				TestOrderedBlobDispatcher::dispatch_blob(sequenced_blob(channel, 1, vec![1])),
+				Ok(()),
+			);
+			assert_eq!(DispatchedBlobs::get(), vec![vec![0], vec![1]]);
+		});
+	}
+
+	#[test]
+	fn ordered_blob_dispatcher_wraps_sequence_around() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			BridgeAvailable::set(true);
+			let channel = [0; 4];
+			Sequences::mutate(|sequences| sequences.insert(channel, u64::MAX));
+
+			assert_eq!(
+				TestOrderedBlobDispatcher::dispatch_blob(sequenced_blob(
+					channel,
+					u64::MAX,
+					vec![1]
+				)),
+				Ok(()),
+			);
+			assert_eq!(TestSequenceTracker::expected_sequence(&channel), 0);
+			assert_eq!(
+				TestOrderedBlobDispatcher::dispatch_blob(sequenced_blob(channel, 0, vec![2])),
+				Ok(()),
+			);
+			assert_eq!(DispatchedBlobs::get(), vec![vec![1], vec![2]]);
+		});
+	}
+
+	#[test]
+	fn ordered_blob_dispatcher_tracks_channels_independently() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			BridgeAvailable::set(true);
+			let (channel_a, channel_b) = ([0; 4], [1; 4]);
+
+			assert_eq!(
+				TestOrderedBlobDispatcher::dispatch_blob(sequenced_blob(channel_a, 0, vec![1])),
+				Ok(()),
+			);
+			assert_eq!(
+				TestOrderedBlobDispatcher::dispatch_blob(sequenced_blob(channel_a, 1, vec![2])),
+				Ok(()),
+			);
+			// the other channel still expects its first blob
+			assert_eq!(
+				TestOrderedBlobDispatcher::dispatch_blob(sequenced_blob(channel_b, 1, vec![3])),
+				Err(DispatchBlobError::WrongVersion),
+			);
+			assert_eq!(
+				TestOrderedBlobDispatcher::dispatch_blob(sequenced_blob(channel_b, 0, vec![3])),
+				Ok(()),
+			);
+			assert_eq!(TestSequenceTracker::expected_sequence(&channel_a), 2);
+			assert_eq!(TestSequenceTracker::expected_sequence(&channel_b), 1);
+		});
+	}
+
+	frame_support::parameter_types! {
+		pub static HaulerAvailable: bool = true;
+		pub static SentSequencedBlobs: Vec<Vec<u8>> = vec![];
+		pub static OutboundSequences: BTreeMap<[u8; 4], u64> = BTreeMap::new();
+		pub const TestChannel: [u8; 4] = [7; 4];
+	}
+
+	pub struct TestHauler;
+	impl HaulBlob for TestHauler {
+		fn haul_blob(blob: Vec<u8>) -> Result<(), HaulBlobError> {
+			if !HaulerAvailable::get() {
+				return Err(HaulBlobError::Transport("unavailable"))
+			}
+			SentSequencedBlobs::mutate(|blobs| blobs.push(blob));
+			Ok(())
+		}
+	}
+
+	pub struct TestOutboundSequenceTracker;
+	impl SequenceTracker for TestOutboundSequenceTracker {
+		type Channel = [u8; 4];
+
+		fn expected_sequence(channel: &[u8; 4]) -> u64 {
+			OutboundSequences::get().get(channel).copied().unwrap_or_default()
+		}
+
+		fn advance_sequence(channel: &[u8; 4]) {
+			let next = Self::expected_sequence(channel).wrapping_add(1);
+			OutboundSequences::mutate(|sequences| sequences.insert(*channel, next));
+		}
+	}
+
+	type TestSequencedBlobHauler =
+		SequencedBlobHauler<TestHauler, TestOutboundSequenceTracker, TestChannel>;
+
+	#[test]
+	fn sequenced_blobs_are_dispatched_in_order() {
+		BridgeAvailable::set(true);
+		assert_eq!(TestSequencedBlobHauler::haul_blob(vec![1]), Ok(()));
+
+		// sequence is not advanced if the blob has failed to be sent
+		HaulerAvailable::set(false);
+		assert_eq!(
+			TestSequencedBlobHauler::haul_blob(vec![2]),
+			Err(HaulBlobError::Transport("unavailable")),
+		);
+		HaulerAvailable::set(true);
+		assert_eq!(TestSequencedBlobHauler::haul_blob(vec![2]), Ok(()));
+		assert_eq!(
+			SentSequencedBlobs::get(),
+			vec![sequenced_blob([7; 4], 0, vec![1]), sequenced_blob([7; 4], 1, vec![2])],
+		);
+
+		// blobs are only dispatched in order
+		let sent = SentSequencedBlobs::get();
+		assert_eq!(
+			TestOrderedBlobDispatcher::dispatch_blob(sent[1].clone()),
+			Err(DispatchBlobError::WrongVersion),
+		);
+		for blob in sent {
+			assert_eq!(TestOrderedBlobDispatcher::dispatch_blob(blob), Ok(()));
+		}
+		assert_eq!(DispatchedBlobs::get(), vec![vec![1], vec![2]]);
+	}
+
+	frame_support::parameter_types! {
+		pub static HauledBlobs: Vec<Vec<u8>> = vec![];
+	}
+
+	/// Exporter that hauls encoded messages.
+	pub struct TestBlobExporter;
+	impl ExportXcm for TestBlobExporter {
+		type Ticket = (Vec<u8>, XcmHash);
+
+		fn validate(
+			_network: NetworkId,
+			_channel: u32,
+			_universal_source: &mut Option<InteriorLocation>,
+			_destination: &mut Option<InteriorLocation>,
+			message: &mut Option<Xcm<()>>,
+		) -> SendResult<Self::Ticket> {
+			let message = message.take().ok_or(MissingArgument)?;
+			Ok(((message.encode(), [0; 32]), Assets::new()))
+		}
+
+		fn deliver((blob, id): Self::Ticket) -> Result<XcmHash, SendError> {
+			HauledBlobs::mutate(|blobs| blobs.push(blob));
+			Ok(id)
+		}
+	}
+
+	/// Prefixes blobs with the `0xC0` byte.
+	pub struct TestCompressor;
+	impl CompressBlob for TestCompressor {
+		fn compress(input: &[u8]) -> Vec<u8> {
+			let mut output = vec![0xC0];
+			output.extend_from_slice(input);
+			output
+		}
+
+		fn decompress(input: &[u8]) -> Result<Vec<u8>, DecompressError> {
+			match input.split_first() {
+				Some((0xC0, output)) => Ok(output.to_vec()),
+				_ => Err(DecompressError),
+			}
+		}
+	}
+
+	#[test]
+	fn compressed_blobs_are_decompressed_on_dispatch() {
+		BridgeAvailable::set(true);
+		let message = Xcm(vec![ClearOrigin]);
+		let (ticket, _) = validate_export::<
+			CompressedHaulBlobExporter<TestBlobExporter, TestCompressor>,
+		>(ByGenesis([1; 32]), 0, Here, Here, message.clone())
+		.unwrap();
+		assert_eq!(
+			CompressedHaulBlobExporter::<TestBlobExporter, TestCompressor>::deliver(ticket),
+			Ok([0; 32]),
+		);
+		assert_eq!(HauledBlobs::get(), vec![TestCompressor::compress(&message.encode())]);
+
+		type Dispatcher = DecompressingBlobDispatcher<TestBlobDispatcher, TestCompressor>;
+		assert_eq!(Dispatcher::dispatch_blob(HauledBlobs::get()[0].clone()), Ok(()));
+		assert_eq!(DispatchedBlobs::get(), vec![message.encode()]);
+
+		// blobs that are not compressed are rejected
+		assert_eq!(
+			Dispatcher::dispatch_blob(message.encode()),
+			Err(DispatchBlobError::InvalidEncoding),
+		);
+	}
+
+	#[test]
+	fn no_op_compressor_keeps_blobs() {
+		let blob = vec![42; 64];
+		assert_eq!(NoOpCompressor::compress(&blob), blob);
+		assert_eq!(NoOpCompressor::decompress(&blob), Ok(blob));
+	}
+
+	#[cfg(feature = "lz4")]
+	type TestLZ4Compressor = LZ4BlobCompressor<frame_support::traits::ConstU32<1024>>;
+
+	#[cfg(feature = "lz4")]
+	#[test]
+	fn lz4_compressor_shrinks_compressible_blobs() {
+		let blob = vec![42; 1024];
+		let compressed = TestLZ4Compressor::compress(&blob);
+		assert!(compressed.len() < blob.len());
+		assert_eq!(TestLZ4Compressor::decompress(&compressed), Ok(blob));
+	}
+
+	#[cfg(feature = "lz4")]
+	#[test]
+	fn lz4_compressor_roundtrips() {
+		let blob = Xcm::<()>(vec![ClearOrigin, SetTopic([7; 32])]).encode();
+		assert_eq!(TestLZ4Compressor::decompress(&TestLZ4Compressor::compress(&blob)), Ok(blob));
+		assert_eq!(TestLZ4Compressor::decompress(&[]), Err(DecompressError));
+	}
+
+	#[cfg(feature = "lz4")]
+	#[test]
+	fn lz4_compressor_rejects_too_large_blobs() {
+		let compressed = TestLZ4Compressor::compress(&vec![42; 1025]);
+		assert_eq!(TestLZ4Compressor::decompress(&compressed), Err(DecompressError));
+	}
+
 	pub struct OkSender;
 	impl SendXcm for OkSender {
 		type Ticket = ();
@@ -23,7 +23,10 @@ use frame_support::{
 	},
 };
 use parity_scale_codec::Decode;
-use sp_runtime::traits::{SaturatedConversion, Saturating, Zero};
+use sp_runtime::{
+	traits::{SaturatedConversion, Saturating, Zero},
+	FixedPointNumber, FixedU128, Perbill,
+};
 use sp_std::{marker::PhantomData, result::Result};
 use xcm::latest::{prelude::*, GetWeight, Weight};
 use xcm_executor::{
@@ -113,6 +116,210 @@ where
 	}
 }
 
+/// Weight bounds that are inflated when the current block is close to being full.
+///
+/// The weight, computed by the `Base` bounds, is increased by `ScaleFactor * fill_ratio` of
+/// itself, where `fill_ratio` is the ratio of weight, already consumed by the current block, to
+/// the maximal block weight of the `Runtime`. So costly XCM programs become even more expensive
+/// during high load, while weights in empty blocks are left unchanged.
+pub struct AdaptiveWeightBounds<Base, ScaleFactor, Runtime>(
+	PhantomData<(Base, ScaleFactor, Runtime)>,
+);
+impl<C, Base, ScaleFactor, Runtime> WeightBounds<C>
+	for AdaptiveWeightBounds<Base, ScaleFactor, Runtime>
+where
+	Base: WeightBounds<C>,
+	ScaleFactor: Get<Perbill>,
+	Runtime: frame_system::Config,
+{
+	fn weight(message: &mut Xcm<C>) -> Result<Weight, ()> {
+		log::trace!(target: "xcm::weight", "AdaptiveWeightBounds message: {:?}", message);
+		Base::weight(message).map(Self::adjust)
+	}
+	fn instr_weight(instruction: &Instruction<C>) -> Result<Weight, ()> {
+		Base::instr_weight(instruction).map(Self::adjust)
+	}
+}
+
+impl<Base, ScaleFactor: Get<Perbill>, Runtime: frame_system::Config>
+	AdaptiveWeightBounds<Base, ScaleFactor, Runtime>
+{
+	/// Returns ratio of weight, consumed by the current block, to the maximal block weight.
+	pub fn block_fill_ratio() -> Perbill {
+		let consumed = frame_system::Pallet::<Runtime>::block_weight().total();
+		let max_block = <Runtime as frame_system::Config>::BlockWeights::get().max_block;
+		Perbill::from_rational(consumed.ref_time(), max_block.ref_time())
+			.max(Perbill::from_rational(consumed.proof_size(), max_block.proof_size()))
+	}
+
+	fn adjust(weight: Weight) -> Weight {
+		let factor = ScaleFactor::get() * Self::block_fill_ratio();
+		weight.saturating_add(Weight::from_parts(
+			factor * weight.ref_time(),
+			factor * weight.proof_size(),
+		))
+	}
+}
+
+/// Weight bounds that record weight of every top-level instruction of weighed messages.
+///
+/// Weights are computed by the `Base` bounds. Recorded `(instruction_name, weight)` pairs may be
+/// retrieved with the `take_instruction_weight_profile` function. Recording only happens in `std`
+/// builds with the `xcm-profiling` feature enabled (and in tests). Otherwise this is a zero-cost
+/// wrapper around the `Base` bounds.
+pub struct PerInstructionWeightProfiler<Base>(PhantomData<Base>);
+impl<C, Base: WeightBounds<C>> WeightBounds<C> for PerInstructionWeightProfiler<Base> {
+	fn weight(message: &mut Xcm<C>) -> Result<Weight, ()> {
+		let weight = Base::weight(message)?;
+		#[cfg(any(test, all(feature = "std", feature = "xcm-profiling")))]
+		for instruction in message.0.iter() {
+			if let Ok(instruction_weight) = Base::instr_weight(instruction) {
+				INSTRUCTION_WEIGHT_PROFILE.with(|profile| {
+					profile.borrow_mut().push((instruction_name(instruction), instruction_weight))
+				});
+			}
+		}
+		Ok(weight)
+	}
+	fn instr_weight(instruction: &Instruction<C>) -> Result<Weight, ()> {
+		Base::instr_weight(instruction)
+	}
+}
+
+#[cfg(any(test, all(feature = "std", feature = "xcm-profiling")))]
+std::thread_local! {
+	static INSTRUCTION_WEIGHT_PROFILE: sp_std::cell::RefCell<Vec<(&'static str, Weight)>> =
+		sp_std::cell::RefCell::new(Vec::new());
+}
+
+/// Returns and clears all `(instruction_name, weight)` pairs, recorded by the
+/// `PerInstructionWeightProfiler`.
+#[cfg(any(test, all(feature = "std", feature = "xcm-profiling")))]
+pub fn take_instruction_weight_profile() -> Vec<(&'static str, Weight)> {
+	INSTRUCTION_WEIGHT_PROFILE.with(|profile| sp_std::mem::take(&mut *profile.borrow_mut()))
+}
+
+/// Returns name of the given instruction.
+#[cfg(any(test, all(feature = "std", feature = "xcm-profiling")))]
+fn instruction_name<C>(instruction: &Instruction<C>) -> &'static str {
+	match instruction {
+		WithdrawAsset(..) => "WithdrawAsset",
+		ReserveAssetDeposited(..) => "ReserveAssetDeposited",
+		ReceiveTeleportedAsset(..) => "ReceiveTeleportedAsset",
+		QueryResponse { .. } => "QueryResponse",
+		TransferAsset { .. } => "TransferAsset",
+		TransferReserveAsset { .. } => "TransferReserveAsset",
+		Transact { .. } => "Transact",
+		HrmpNewChannelOpenRequest { .. } => "HrmpNewChannelOpenRequest",
+		HrmpChannelAccepted { .. } => "HrmpChannelAccepted",
+		HrmpChannelClosing { .. } => "HrmpChannelClosing",
+		ClearOrigin => "ClearOrigin",
+		DescendOrigin(..) => "DescendOrigin",
+		ReportError(..) => "ReportError",
+		DepositAsset { .. } => "DepositAsset",
+		DepositReserveAsset { .. } => "DepositReserveAsset",
+		ExchangeAsset { .. } => "ExchangeAsset",
+		InitiateReserveWithdraw { .. } => "InitiateReserveWithdraw",
+		InitiateTeleport { .. } => "InitiateTeleport",
+		ReportHolding { .. } => "ReportHolding",
+		BuyExecution { .. } => "BuyExecution",
+		RefundSurplus => "RefundSurplus",
+		SetErrorHandler(..) => "SetErrorHandler",
+		SetAppendix(..) => "SetAppendix",
+		ClearError => "ClearError",
+		ClaimAsset { .. } => "ClaimAsset",
+		Trap(..) => "Trap",
+		SubscribeVersion { .. } => "SubscribeVersion",
+		UnsubscribeVersion => "UnsubscribeVersion",
+		BurnAsset(..) => "BurnAsset",
+		ExpectAsset(..) => "ExpectAsset",
+		ExpectOrigin(..) => "ExpectOrigin",
+		ExpectError(..) => "ExpectError",
+		ExpectTransactStatus(..) => "ExpectTransactStatus",
+		QueryPallet { .. } => "QueryPallet",
+		ExpectPallet { .. } => "ExpectPallet",
+		ReportTransactStatus(..) => "ReportTransactStatus",
+		ClearTransactStatus => "ClearTransactStatus",
+		UniversalOrigin(..) => "UniversalOrigin",
+		ExportMessage { .. } => "ExportMessage",
+		LockAsset { .. } => "LockAsset",
+		UnlockAsset { .. } => "UnlockAsset",
+		NoteUnlockable { .. } => "NoteUnlockable",
+		RequestUnlock { .. } => "RequestUnlock",
+		SetFeesMode { .. } => "SetFeesMode",
+		SetTopic(..) => "SetTopic",
+		ClearTopic => "ClearTopic",
+		AliasOrigin(..) => "AliasOrigin",
+		UnpaidExecution { .. } => "UnpaidExecution",
+	}
+}
+
+/// Weight bounds that multiply weight of instructions, operating with the `Id` asset, by the
+/// `Multiplier`.
+///
+/// Weights are computed by the `Fallback` bounds. Only top-level instructions that carry assets
+/// (e.g. `WithdrawAsset` or `BuyExecution`) are checked. Since `Fallback` may be another
+/// `AssetSpecificWeightMultiplier`, multiple asset overrides may be chained:
+/// `AssetSpecificWeightMultiplier<A, MA, AssetSpecificWeightMultiplier<B, MB, Base>>`.
+pub struct AssetSpecificWeightMultiplier<Id, Multiplier, Fallback>(
+	PhantomData<(Id, Multiplier, Fallback)>,
+);
+impl<C, Id, Multiplier, Fallback> WeightBounds<C>
+	for AssetSpecificWeightMultiplier<Id, Multiplier, Fallback>
+where
+	Id: Get<AssetId>,
+	Multiplier: Get<FixedU128>,
+	Fallback: WeightBounds<C>,
+{
+	fn weight(message: &mut Xcm<C>) -> Result<Weight, ()> {
+		log::trace!(target: "xcm::weight", "AssetSpecificWeightMultiplier message: {:?}", message);
+		let mut weight = Fallback::weight(message)?;
+		for instruction in message.0.iter().filter(|i| Self::is_affected(i)) {
+			let instr_weight = Fallback::instr_weight(instruction)?;
+			weight =
+				weight.saturating_sub(instr_weight).saturating_add(Self::multiply(instr_weight));
+		}
+		Ok(weight)
+	}
+	fn instr_weight(instruction: &Instruction<C>) -> Result<Weight, ()> {
+		let weight = Fallback::instr_weight(instruction)?;
+		Ok(if Self::is_affected(instruction) { Self::multiply(weight) } else { weight })
+	}
+}
+
+impl<Id: Get<AssetId>, Multiplier: Get<FixedU128>, Fallback>
+	AssetSpecificWeightMultiplier<Id, Multiplier, Fallback>
+{
+	/// Returns true if instruction is operating with the `Id` asset.
+	fn is_affected<C>(instruction: &Instruction<C>) -> bool {
+		let id = Id::get();
+		match instruction {
+			WithdrawAsset(assets) |
+			ReserveAssetDeposited(assets) |
+			ReceiveTeleportedAsset(assets) |
+			TransferAsset { assets, .. } |
+			TransferReserveAsset { assets, .. } |
+			ClaimAsset { assets, .. } |
+			BurnAsset(assets) |
+			ExpectAsset(assets) => assets.inner().iter().any(|asset| asset.id == id),
+			BuyExecution { fees: asset, .. } |
+			LockAsset { asset, .. } |
+			UnlockAsset { asset, .. } |
+			NoteUnlockable { asset, .. } |
+			RequestUnlock { asset, .. } => asset.id == id,
+			_ => false,
+		}
+	}
+
+	fn multiply(weight: Weight) -> Weight {
+		let multiplier = Multiplier::get();
+		Weight::from_parts(
+			multiplier.saturating_mul_int(weight.ref_time()),
+			multiplier.saturating_mul_int(weight.proof_size()),
+		)
+	}
+}
+
 /// Function trait for handling some revenue. Similar to a negative imbalance (credit) handler, but
 /// for a `Asset`. Sensible implementations will deposit the asset in some known treasury or
 /// block-author account.
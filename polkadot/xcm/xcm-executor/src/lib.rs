@@ -56,6 +56,23 @@ const RECURSION_LIMIT: u8 = 10;
 
 environmental::environmental!(recursion_count: u8);
 
+environmental::environmental!(sending_origin: Location);
+
+/// Runs `f`, passing the local `origin` of all messages it sends to the routers that need it (e.g.
+/// `PrioritizedRouter` of the `xcm-builder`).
+///
+/// The executor uses it to pass the origin of messages that are sent while executing XCM.
+/// `pallet-xcm` uses it to pass the origin of messages that it sends on behalf of local origins.
+pub fn with_sending_origin<R>(mut origin: Location, f: impl FnOnce() -> R) -> R {
+	sending_origin::using(&mut origin, f)
+}
+
+/// Returns the local origin of the message that is being sent, if it has been passed using
+/// `with_sending_origin`.
+pub fn current_sending_origin() -> Option<Location> {
+	sending_origin::with(|origin| origin.clone())
+}
+
 /// The XCM executor.
 pub struct XcmExecutor<Config: config::Config> {
 	holding: AssetsInHolding,
@@ -347,11 +364,25 @@ impl<Config: config::Config> XcmExecutor<Config> {
 		msg: Xcm<()>,
 		reason: FeeReason,
 	) -> Result<XcmHash, XcmError> {
-		let (ticket, fee) = validate_send::<Config::XcmSender>(dest, msg)?;
+		let (ticket, fee) = Self::validate_send_from(self.cloned_origin(), dest, msg)?;
 		self.take_fee(fee, reason)?;
 		Config::XcmSender::deliver(ticket).map_err(Into::into)
 	}
 
+	/// Validate the message using the `XcmSender`, passing the local `origin` of the message to
+	/// the routers.
+	fn validate_send_from(
+		origin: Option<Location>,
+		dest: Location,
+		msg: Xcm<()>,
+	) -> SendResult<<Config::XcmSender as SendXcm>::Ticket> {
+		match origin {
+			Some(origin) =>
+				with_sending_origin(origin, || validate_send::<Config::XcmSender>(dest, msg)),
+			None => validate_send::<Config::XcmSender>(dest, msg),
+		}
+	}
+
 	/// Remove the registered error handler and return it. Do not refund its weight.
 	fn take_error_handler(&mut self) -> Xcm<Config::RuntimeCall> {
 		let mut r = Xcm::<Config::RuntimeCall>(vec![]);
@@ -831,8 +862,11 @@ impl<Config: config::Config> XcmExecutor<Config> {
 					let mut message_to_weigh =
 						vec![ReserveAssetDeposited(to_weigh_reanchored), ClearOrigin];
 					message_to_weigh.extend(xcm.0.clone().into_iter());
-					let (_, fee) =
-						validate_send::<Config::XcmSender>(dest.clone(), Xcm(message_to_weigh))?;
+					let (_, fee) = Self::validate_send_from(
+						self.cloned_origin(),
+						dest.clone(),
+						Xcm(message_to_weigh),
+					)?;
 					// set aside fee to be charged by XcmSender
 					let transport_fee = self.holding.saturating_take(fee.into());
 
@@ -1117,7 +1151,8 @@ impl<Config: config::Config> XcmExecutor<Config> {
 						.reanchored(&unlocker, &context)
 						.map_err(|_| XcmError::ReanchorFailed)?;
 					let msg = Xcm::<()>(vec![NoteUnlockable { asset: remote_asset, owner }]);
-					let (ticket, price) = validate_send::<Config::XcmSender>(unlocker, msg)?;
+					let (ticket, price) =
+						Self::validate_send_from(self.cloned_origin(), unlocker, msg)?;
 					self.take_fee(price, FeeReason::LockAsset)?;
 					lock_ticket.enact()?;
 					Config::XcmSender::deliver(ticket)?;
@@ -1149,7 +1184,7 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				)?;
 				let msg =
 					Xcm::<()>(vec![UnlockAsset { asset: remote_asset, target: remote_target }]);
-				let (ticket, price) = validate_send::<Config::XcmSender>(locker, msg)?;
+				let (ticket, price) = Self::validate_send_from(Some(origin), locker, msg)?;
 				let old_holding = self.holding.clone();
 				let result = Config::TransactionalProcessor::process(|| {
 					self.take_fee(price, FeeReason::RequestUnlock)?;
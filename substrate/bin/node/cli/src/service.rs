@@ -110,6 +110,7 @@ pub fn create_extrinsic(
 	let extra: kitchensink_runtime::SignedExtra =
 		(
 			frame_system::CheckNonZeroSender::<kitchensink_runtime::Runtime>::new(),
+			frame_system::CheckFrozenAccount::<kitchensink_runtime::Runtime>::new(),
 			frame_system::CheckSpecVersion::<kitchensink_runtime::Runtime>::new(),
 			frame_system::CheckTxVersion::<kitchensink_runtime::Runtime>::new(),
 			frame_system::CheckGenesis::<kitchensink_runtime::Runtime>::new(),
@@ -130,6 +131,7 @@ pub fn create_extrinsic(
 		function.clone(),
 		extra.clone(),
 		(
+			(),
 			(),
 			kitchensink_runtime::VERSION.spec_version,
 			kitchensink_runtime::VERSION.transaction_version,
@@ -984,6 +986,7 @@ mod tests {
 				});
 
 				let check_non_zero_sender = frame_system::CheckNonZeroSender::new();
+				let check_frozen_account = frame_system::CheckFrozenAccount::new();
 				let check_spec_version = frame_system::CheckSpecVersion::new();
 				let check_tx_version = frame_system::CheckTxVersion::new();
 				let check_genesis = frame_system::CheckGenesis::new();
@@ -995,6 +998,7 @@ mod tests {
 				);
 				let extra = (
 					check_non_zero_sender,
+					check_frozen_account,
 					check_spec_version,
 					check_tx_version,
 					check_genesis,
@@ -1006,7 +1010,17 @@ mod tests {
 				let raw_payload = SignedPayload::from_raw(
 					function,
 					extra,
-					((), spec_version, transaction_version, genesis_hash, genesis_hash, (), (), ()),
+					(
+						(),
+						(),
+						spec_version,
+						transaction_version,
+						genesis_hash,
+						genesis_hash,
+						(),
+						(),
+						(),
+					),
 				);
 				let signature = raw_payload.using_encoded(|payload| signer.sign(payload));
 				let (function, extra, _) = raw_payload.deconstruct();
@@ -161,7 +161,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_version: 268,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 2,
+	transaction_version: 3,
 	state_version: 1,
 };
 
@@ -1204,6 +1204,8 @@ parameter_types! {
 	pub const SpendPeriod: BlockNumber = 1 * DAYS;
 	pub const Burn: Permill = Permill::from_percent(50);
 	pub const TipCountdown: BlockNumber = 1 * DAYS;
+	pub const TipperCooldown: BlockNumber = 1 * HOURS;
+	pub const TipVestingClaimPeriod: BlockNumber = 30 * DAYS;
 	pub const TipFindersFee: Percent = Percent::from_percent(20);
 	pub const TipReportDepositBase: Balance = 1 * DOLLARS;
 	pub const DataDepositPerByte: Balance = 1 * CENTS;
@@ -1318,13 +1320,32 @@ impl pallet_child_bounties::Config for Runtime {
 
 impl pallet_tips::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	type DataDepositPerByte = DataDepositPerByte;
+	type DataDeposit = pallet_tips::LinearDataDepositCurve<DataDepositPerByte>;
 	type MaximumReasonLength = MaximumReasonLength;
 	type Tippers = Elections;
 	type TipCountdown = TipCountdown;
 	type TipFindersFee = TipFindersFee;
 	type TipReportDepositBase = TipReportDepositBase;
 	type MaxTipAmount = ConstU128<{ 500 * DOLLARS }>;
+	type MaxBeneficiaries = ConstU32<10>;
+	type MaxRecurringTips = ConstU32<10>;
+	type TipperCooldown = TipperCooldown;
+	type MaxVotesPerBatch = ConstU32<20>;
+	type VestingClaimPeriod = TipVestingClaimPeriod;
+	type MaxVestingExpiriesPerBlock = ConstU32<10>;
+	// the kitchensink runtime has no XCM transport to pay other chains with `PayOverXcm`, so the
+	// tips are paid locally; see `pallet-tips-mock-network` for a relay chain paying over XCM.
+	type CrossChainBeneficiary = AccountId;
+	type CrossChainPaymentError = sp_runtime::DispatchError;
+	type CrossChainPaymaster = PayFromAccount<Balances, TreasuryAccount>;
+	type CrossChainAssetKind = ();
+	type MatchOrigin = EitherOfDiverse<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 3, 5>,
+	>;
+	type MaxTipAmountPerPeriod = ConstU128<{ 5_000 * DOLLARS }>;
+	type MaxDeferredTips = ConstU32<100>;
+	type MaxDeferredClosuresPerBlock = ConstU32<10>;
 	type WeightInfo = pallet_tips::weights::SubstrateWeight<Runtime>;
 }
 
@@ -1411,6 +1432,7 @@ where
 		let era = Era::mortal(period, current_block);
 		let extra = (
 			frame_system::CheckNonZeroSender::<Runtime>::new(),
+			frame_system::CheckFrozenAccount::<Runtime>::new(),
 			frame_system::CheckSpecVersion::<Runtime>::new(),
 			frame_system::CheckTxVersion::<Runtime>::new(),
 			frame_system::CheckGenesis::<Runtime>::new(),
@@ -1644,9 +1666,10 @@ impl pallet_assets::Config<Instance1> for Runtime {
 	type MetadataDepositPerByte = MetadataDepositPerByte;
 	type ApprovalDeposit = ApprovalDeposit;
 	type StringLimit = StringLimit;
-	type Freezer = ();
+	type Freezer = NftFractionalization;
 	type Extra = ();
 	type CallbackHandle = ();
+	type TransferCallback = ();
 	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
 	type RemoveItemsLimit = ConstU32<1000>;
 	#[cfg(feature = "runtime-benchmarks")]
@@ -1676,6 +1699,7 @@ impl pallet_assets::Config<Instance2> for Runtime {
 	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
 	type RemoveItemsLimit = ConstU32<1000>;
 	type CallbackHandle = ();
+	type TransferCallback = ();
 	#[cfg(feature = "runtime-benchmarks")]
 	type BenchmarkHelper = ();
 }
@@ -1821,6 +1845,7 @@ parameter_types! {
 	pub const NftFractionalizationPalletId: PalletId = PalletId(*b"fraction");
 	pub NewAssetSymbol: BoundedVec<u8, StringLimit> = (*b"FRAC").to_vec().try_into().unwrap();
 	pub NewAssetName: BoundedVec<u8, StringLimit> = (*b"Frac").to_vec().try_into().unwrap();
+	pub const CrossChainUnificationTimeout: BlockNumber = HOURS;
 }
 
 impl pallet_nft_fractionalization::Config for Runtime {
@@ -1837,6 +1862,18 @@ impl pallet_nft_fractionalization::Config for Runtime {
 	type Assets = Assets;
 	type Nfts = Nfts;
 	type PalletId = NftFractionalizationPalletId;
+	type MaxUnificationContributors = ConstU32<10>;
+	type MaxFreezes = ConstU32<10>;
+	type XcmSender = ();
+	type XcmExecutor = ();
+	type RemoteExecutionFee = ();
+	type XcmQueries = ();
+	type AssetLocation = ();
+	type AccountIdToLocation = ();
+	type CrossChainUnificationTimeout = CrossChainUnificationTimeout;
+	type MaxBatchSize = ConstU32<10>;
+	type AutoUnify = ConstBool<false>;
+	type MinRoyaltyPayout = ConstU128<1>;
 	type WeightInfo = pallet_nft_fractionalization::weights::SubstrateWeight<Runtime>;
 	type RuntimeHoldReason = RuntimeHoldReason;
 	#[cfg(feature = "runtime-benchmarks")]
@@ -2457,6 +2494,7 @@ pub type BlockId = generic::BlockId<Block>;
 /// [`sign`]: <../../testing/src/keyring.rs.html>
 pub type SignedExtra = (
 	frame_system::CheckNonZeroSender<Runtime>,
+	frame_system::CheckFrozenAccount<Runtime>,
 	frame_system::CheckSpecVersion<Runtime>,
 	frame_system::CheckTxVersion<Runtime>,
 	frame_system::CheckGenesis<Runtime>,
@@ -2497,6 +2535,7 @@ type Migrations = (
 	pallet_alliance::migration::Migration<Runtime>,
 	pallet_contracts::Migration<Runtime>,
 	pallet_identity::migration::versioned::V0ToV1<Runtime, IDENTITY_MIGRATION_KEY_LIMIT>,
+	pallet_tips::migrations::v5::MigrateV4ToV5<Runtime>,
 );
 
 type EventRecord = frame_system::EventRecord<
@@ -2792,6 +2831,12 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl frame_system_rpc_runtime_api::BlockMetadataApi<Block, BlockNumber> for Runtime {
+		fn get_block_metadata(block_number: BlockNumber, key: Vec<u8>) -> Option<Vec<u8>> {
+			System::block_metadata(block_number, &key)
+		}
+	}
+
 	impl assets_api::AssetsApi<
 		Block,
 		AccountId,
@@ -2915,6 +2960,24 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_nft_fractionalization::NftFractionalizationApi<
+		Block,
+		AccountId,
+		u32,
+		u32,
+		Balance,
+		BlockNumber,
+	> for Runtime
+	{
+		fn governance_weight(who: AccountId, collection: u32, nft: u32) -> Balance {
+			NftFractionalization::governance_weight(&who, collection, nft)
+		}
+
+		fn original_owner(collection: u32, nft: u32) -> Option<(AccountId, BlockNumber)> {
+			NftFractionalization::original_owner(collection, nft)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentCallApi<Block, Balance, RuntimeCall>
 		for Runtime
 	{
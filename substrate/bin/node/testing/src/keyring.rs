@@ -73,6 +73,7 @@ pub fn session_keys_from_seed(seed: &str) -> SessionKeys {
 pub fn signed_extra(nonce: Nonce, extra_fee: Balance) -> SignedExtra {
 	(
 		frame_system::CheckNonZeroSender::new(),
+		frame_system::CheckFrozenAccount::new(),
 		frame_system::CheckSpecVersion::new(),
 		frame_system::CheckTxVersion::new(),
 		frame_system::CheckGenesis::new(),
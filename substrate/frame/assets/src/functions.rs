@@ -587,8 +587,9 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		let (balance, died) =
 			Self::transfer_and_die(id.clone(), source, dest, amount, maybe_need_admin, f)?;
 		if let Some(Remove) = died {
-			T::Freezer::died(id, source);
+			T::Freezer::died(id.clone(), source);
 		}
+		T::TransferCallback::transferred(&id, source, dest, balance);
 		Ok(balance)
 	}
 
@@ -943,10 +944,11 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			},
 		)?;
 
-		// Execute hook outside of `mutate`.
+		// Execute hooks outside of `mutate`.
 		if let Some(Remove) = owner_died {
-			T::Freezer::died(id, owner);
+			T::Freezer::died(id.clone(), owner);
 		}
+		T::TransferCallback::transferred(&id, owner, destination, amount);
 		Ok(())
 	}
 
@@ -105,11 +105,12 @@ impl<T: Config<I>, I: 'static> fungibles::Mutate<<T as SystemConfig>::AccountId>
 		amount: Self::Balance,
 	) {
 		Self::deposit_event(Event::Transferred {
-			asset_id,
+			asset_id: asset_id.clone(),
 			from: source.clone(),
 			to: dest.clone(),
 			amount,
 		});
+		T::TransferCallback::transferred(&asset_id, source, dest, amount);
 	}
 }
 
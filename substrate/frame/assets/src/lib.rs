@@ -127,7 +127,8 @@
 //! ### Callbacks
 //!
 //! Using `CallbackHandle` associated type, user can configure custom callback functions which are
-//! executed when new asset is created or an existing asset is destroyed.
+//! executed when new asset is created or an existing asset is destroyed. The `TransferCallback`
+//! associated type configures the callback, executed when an asset is transferred.
 //!
 //! ## Related Modules
 //!
@@ -199,6 +200,20 @@ pub trait AssetsCallback<AssetId, AccountId> {
 /// Empty implementation in case no callbacks are required.
 impl<AssetId, AccountId> AssetsCallback<AssetId, AccountId> for () {}
 
+/// Trait with the callback that is executed after a successful asset transfer.
+pub trait AssetsTransferCallback<AssetId, AccountId, Balance> {
+	/// Indicates that `amount` of the asset with `id` has just been transferred from `from` to
+	/// `to`.
+	///
+	/// It can't fail the transfer, since the transfers made through `fungibles::Mutate` are
+	/// already final when it is called. The weight of the callback is not included in the weight
+	/// of the transfers, so the runtime must account for it if it does any work.
+	fn transferred(_id: &AssetId, _from: &AccountId, _to: &AccountId, _amount: Balance) {}
+}
+
+/// Empty implementation in case no transfer callback is required.
+impl<AssetId, AccountId, Balance> AssetsTransferCallback<AssetId, AccountId, Balance> for () {}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -251,6 +266,7 @@ pub mod pallet {
 			type StringLimit = ConstU32<50>;
 			type Extra = ();
 			type CallbackHandle = ();
+			type TransferCallback = ();
 			type WeightInfo = ();
 			#[cfg(feature = "runtime-benchmarks")]
 			type BenchmarkHelper = ();
@@ -353,6 +369,9 @@ pub mod pallet {
 		/// Callback methods for asset state change (e.g. asset created or destroyed)
 		type CallbackHandle: AssetsCallback<Self::AssetId, Self::AccountId>;
 
+		/// Callback method for asset transfers.
+		type TransferCallback: AssetsTransferCallback<Self::AssetId, Self::AccountId, Self::Balance>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 
@@ -86,9 +86,16 @@ impl AssetsCallback<AssetId, AccountId> for AssetsCallbackHandle {
 	}
 }
 
+impl AssetsTransferCallback<AssetId, AccountId, u64> for AssetsCallbackHandle {
+	fn transferred(_id: &AssetId, from: &AccountId, to: &AccountId, amount: u64) {
+		storage::set(Self::TRANSFERRED.as_bytes(), &(from, to, amount).encode());
+	}
+}
+
 impl AssetsCallbackHandle {
 	pub const CREATED: &'static str = "asset_created";
 	pub const DESTROYED: &'static str = "asset_destroyed";
+	pub const TRANSFERRED: &'static str = "asset_transferred";
 
 	const RETURN_ERROR: &'static str = "return_error";
 
@@ -115,6 +122,7 @@ impl Config for Test {
 	type ForceOrigin = frame_system::EnsureRoot<u64>;
 	type Freezer = TestFreezer;
 	type CallbackHandle = AssetsCallbackHandle;
+	type TransferCallback = AssetsCallbackHandle;
 }
 
 use std::collections::HashMap;
@@ -1678,6 +1678,30 @@ fn normal_asset_create_and_destroy_callbacks_should_work() {
 	});
 }
 
+#[test]
+fn asset_transfer_callback_should_work() {
+	new_test_ext().execute_with(|| {
+		let transferred = || {
+			storage::get(AssetsCallbackHandle::TRANSFERRED.as_bytes())
+				.map(|v| <(u64, u64, u64) as codec::Decode>::decode(&mut &v[..]).unwrap())
+		};
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 1, 100));
+		assert_eq!(transferred(), None);
+
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 50));
+		assert_eq!(transferred(), Some((1, 2, 50)));
+
+		Balances::make_free_balance_be(&2, 100);
+		assert_ok!(Assets::approve_transfer(RuntimeOrigin::signed(2), 0, 1, 20));
+		assert_ok!(Assets::transfer_approved(RuntimeOrigin::signed(1), 0, 2, 3, 20));
+		assert_eq!(transferred(), Some((2, 3, 20)));
+
+		assert_ok!(<Assets as fungibles::Mutate<_>>::transfer(0, &3, &4, 10, Protect));
+		assert_eq!(transferred(), Some((3, 4, 10)));
+	});
+}
+
 #[test]
 fn root_asset_create_should_work() {
 	new_test_ext().execute_with(|| {
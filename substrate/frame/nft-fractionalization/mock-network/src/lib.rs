@@ -0,0 +1,181 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A mock network for testing the cross-chain fractionalization of the NFTs.
+//!
+//! `ParaA` fractionalizes the NFTs, deposits their fractions to the accounts on `ParaB` and
+//! unifies them once `ParaB` reports that the fractions are burned.
+//!
+//! Like `pallet-contracts-mock-network`, it is a crate of its own, so that `xcm-simulator`,
+//! `pallet-xcm` and the relay chain runtime it needs don't become dependencies of the pallet. The
+//! unit tests of the pallet can't deliver the messages, so only the error paths of the cross-chain
+//! fractionalization are tested there.
+
+pub mod mocks;
+pub mod parachain;
+pub mod primitives;
+pub mod relay_chain;
+pub mod remote_parachain;
+
+#[cfg(test)]
+mod tests;
+
+use crate::primitives::{AccountId, UNITS};
+use sp_runtime::BuildStorage;
+use xcm::latest::prelude::*;
+use xcm_executor::traits::ConvertLocation;
+pub use xcm_simulator::TestExt;
+use xcm_simulator::{decl_test_network, decl_test_parachain, decl_test_relay_chain};
+
+// Accounts
+pub const ADMIN: sp_runtime::AccountId32 = sp_runtime::AccountId32::new([0u8; 32]);
+pub const ALICE: sp_runtime::AccountId32 = sp_runtime::AccountId32::new([1u8; 32]);
+pub const BOB: sp_runtime::AccountId32 = sp_runtime::AccountId32::new([2u8; 32]);
+
+// Balances
+pub const INITIAL_BALANCE: u128 = 1_000_000_000 * UNITS;
+
+/// The parachain, fractionalizing the NFTs.
+pub const FRACTIONALIZATION_PARA_ID: u32 = 1;
+/// The parachain, holding the fractions.
+pub const REMOTE_PARA_ID: u32 = 2;
+
+/// The asset of `ParaB`, keeping the fractions of the asset `0` of `ParaA`.
+pub const REMOTE_FRACTIONS: u32 = 0;
+
+decl_test_parachain! {
+	pub struct ParaA {
+		Runtime = parachain::Runtime,
+		XcmpMessageHandler = parachain::MsgQueue,
+		DmpMessageHandler = parachain::MsgQueue,
+		new_ext = para_ext(FRACTIONALIZATION_PARA_ID),
+	}
+}
+
+decl_test_parachain! {
+	pub struct ParaB {
+		Runtime = remote_parachain::Runtime,
+		XcmpMessageHandler = remote_parachain::MsgQueue,
+		DmpMessageHandler = remote_parachain::MsgQueue,
+		new_ext = remote_para_ext(REMOTE_PARA_ID),
+	}
+}
+
+decl_test_relay_chain! {
+	pub struct Relay {
+		Runtime = relay_chain::Runtime,
+		RuntimeCall = relay_chain::RuntimeCall,
+		RuntimeEvent = relay_chain::RuntimeEvent,
+		XcmConfig = relay_chain::XcmConfig,
+		MessageQueue = relay_chain::MessageQueue,
+		System = relay_chain::System,
+		new_ext = relay_ext(),
+	}
+}
+
+decl_test_network! {
+	pub struct MockNet {
+		relay_chain = Relay,
+		parachains = vec![
+			(1, ParaA),
+			(2, ParaB),
+		],
+	}
+}
+
+/// The sovereign account of `ParaA` at `ParaB`.
+pub fn sibling_account_id() -> AccountId {
+	let location = Location::new(1, [Parachain(FRACTIONALIZATION_PARA_ID)]);
+	remote_parachain::LocationToAccountId::convert_location(&location).unwrap()
+}
+
+/// The account of `who` at `ParaA`, as seen by `ParaB`.
+pub fn remote_account_id(who: AccountId) -> AccountId {
+	let location = Location::new(
+		1,
+		[Parachain(FRACTIONALIZATION_PARA_ID), AccountId32 { network: None, id: who.into() }],
+	);
+	remote_parachain::LocationToAccountId::convert_location(&location).unwrap()
+}
+
+pub fn para_ext(para_id: u32) -> sp_io::TestExternalities {
+	use parachain::{MsgQueue, Runtime, System};
+
+	let mut t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+
+	pallet_balances::GenesisConfig::<Runtime> {
+		balances: vec![(ALICE, INITIAL_BALANCE), (BOB, INITIAL_BALANCE)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		sp_tracing::try_init_simple();
+		System::set_block_number(1);
+		MsgQueue::set_para_id(para_id.into());
+	});
+	ext
+}
+
+pub fn remote_para_ext(para_id: u32) -> sp_io::TestExternalities {
+	use remote_parachain::{MsgQueue, Runtime, System};
+
+	let mut t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+
+	// the execution of the messages of `ParaA` is paid by its sovereign account, and by the
+	// accounts requesting the unifications
+	pallet_balances::GenesisConfig::<Runtime> {
+		balances: vec![
+			(ALICE, INITIAL_BALANCE),
+			(sibling_account_id(), INITIAL_BALANCE),
+			(remote_account_id(ALICE), INITIAL_BALANCE),
+			(remote_account_id(BOB), INITIAL_BALANCE),
+		],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	pallet_assets::GenesisConfig::<Runtime> {
+		// the fractions are sufficient, so that the derived accounts may hold them
+		assets: vec![(REMOTE_FRACTIONS, ADMIN, true, 1)],
+		metadata: Default::default(),
+		accounts: Default::default(),
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		sp_tracing::try_init_simple();
+		System::set_block_number(1);
+		MsgQueue::set_para_id(para_id.into());
+	});
+	ext
+}
+
+pub fn relay_ext() -> sp_io::TestExternalities {
+	use relay_chain::{Runtime, System};
+
+	let t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+	});
+	ext
+}
@@ -0,0 +1,222 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime mock of the parachain, holding the cross-chain fractions as a foreign asset.
+
+use crate::{
+	mocks::msg_queue::pallet as mock_msg_queue,
+	primitives::{AccountId, AssetIdForAssets, Balance},
+};
+use frame_support::{
+	construct_runtime, derive_impl, parameter_types,
+	traits::{AsEnsureOriginWithArg, ConstU128, ContainsPair, Everything, Nothing},
+	weights::Weight,
+};
+use frame_system::{EnsureRoot, EnsureSigned};
+use sp_core::{ConstU32, H256};
+use sp_runtime::traits::{IdentityLookup, MaybeEquivalence};
+
+use sp_std::prelude::*;
+use xcm::latest::prelude::*;
+use xcm_builder::{
+	AccountId32Aliases, AllowTopLevelPaidExecutionFrom, ConvertedConcreteId, DescribeAllTerminal,
+	DescribeFamily, FixedRateOfFungible, FixedWeightBounds, FrameTransactionalProcessor,
+	FungibleAdapter, FungiblesAdapter, HashedDescription, IsConcrete, NoChecking, ParentIsPreset,
+	SiblingParachainConvertsVia, SignedAccountId32AsNative, SovereignSignedViaLocation,
+	WithComputedOrigin,
+};
+use xcm_executor::{traits::JustTry, Config, XcmExecutor};
+
+/// Accounts of the remote locations. The accounts of the sibling parachains are derived from
+/// their descriptions.
+pub type LocationToAccountId = (
+	ParentIsPreset<AccountId>,
+	SiblingParachainConvertsVia<polkadot_parachain_primitives::primitives::Sibling, AccountId>,
+	AccountId32Aliases<RelayNetwork, AccountId>,
+	HashedDescription<AccountId, DescribeFamily<DescribeAllTerminal>>,
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Runtime {
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Block = Block;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type RuntimeEvent = RuntimeEvent;
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type BaseCallFilter = Everything;
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Runtime {
+	type AccountStore = System;
+	type Balance = Balance;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU128<1>;
+	type FreezeIdentifier = ();
+	type MaxFreezes = ConstU32<0>;
+	type MaxLocks = ConstU32<50>;
+	type MaxReserves = ConstU32<50>;
+	type ReserveIdentifier = [u8; 8];
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type WeightInfo = ();
+}
+
+impl pallet_assets::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = Balance;
+	type AssetId = AssetIdForAssets;
+	type AssetIdParameter = AssetIdForAssets;
+	type Currency = Balances;
+	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<AccountId>>;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type AssetDeposit = ConstU128<1>;
+	type AssetAccountDeposit = ConstU128<10>;
+	type MetadataDepositBase = ConstU128<1>;
+	type MetadataDepositPerByte = ConstU128<1>;
+	type ApprovalDeposit = ConstU128<1>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type CallbackHandle = ();
+	type TransferCallback = ();
+	type WeightInfo = ();
+	type RemoveItemsLimit = ConstU32<1000>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
+parameter_types! {
+	pub const RelayNetwork: NetworkId = ByGenesis([0; 32]);
+	pub const RelayLocation: Location = Location::parent();
+	pub UniversalLocation: InteriorLocation = Parachain(MsgQueue::parachain_id().into()).into();
+	pub CheckingAccount: AccountId = AccountId::new([0xff; 32]);
+	/// A unit of the relay chain token for every unit of the weight.
+	pub RelayTokenPerSecondPerByte: (AssetId, u128, u128) =
+		(AssetId(Parent.into()), 1_000_000_000_000, 1024 * 1024);
+}
+
+/// The relay chain token is kept in `Balances`. It pays for the execution of the messages.
+pub type RelayTransactor =
+	FungibleAdapter<Balances, IsConcrete<RelayLocation>, LocationToAccountId, AccountId, ()>;
+
+/// The fractions of the NFTs, fractionalized by the parachain `FRACTIONALIZATION_PARA_ID`, are
+/// kept in the local assets with the same ID.
+pub struct FractionsAssetId;
+impl MaybeEquivalence<Location, AssetIdForAssets> for FractionsAssetId {
+	fn convert(location: &Location) -> Option<AssetIdForAssets> {
+		match location.unpack() {
+			(1, [Parachain(para_id), PalletInstance(_), GeneralIndex(id)])
+				if *para_id == crate::FRACTIONALIZATION_PARA_ID =>
+				(*id).try_into().ok(),
+			_ => None,
+		}
+	}
+
+	fn convert_back(_id: &AssetIdForAssets) -> Option<Location> {
+		None
+	}
+}
+
+pub type FractionsTransactor = FungiblesAdapter<
+	Assets,
+	ConvertedConcreteId<AssetIdForAssets, Balance, FractionsAssetId, JustTry>,
+	LocationToAccountId,
+	AccountId,
+	NoChecking,
+	CheckingAccount,
+>;
+
+/// Trusts the sibling parachains as the reserves of their own assets.
+pub struct SiblingReserves;
+impl ContainsPair<Asset, Location> for SiblingReserves {
+	fn contains(asset: &Asset, origin: &Location) -> bool {
+		matches!(origin.unpack(), (1, [Parachain(_)])) && asset.id.0.starts_with(origin)
+	}
+}
+
+pub type XcmOriginToCallOrigin = (
+	SovereignSignedViaLocation<LocationToAccountId, RuntimeOrigin>,
+	SignedAccountId32AsNative<RelayNetwork, RuntimeOrigin>,
+);
+
+parameter_types! {
+	pub const XcmInstructionWeight: Weight = Weight::from_parts(1_000, 1_000);
+	pub const MaxInstructions: u32 = 100;
+	pub const MaxAssetsIntoHolding: u32 = 64;
+}
+
+pub type XcmRouter = crate::ParachainXcmRouter<MsgQueue>;
+
+pub type Barrier =
+	WithComputedOrigin<AllowTopLevelPaidExecutionFrom<Everything>, UniversalLocation, ConstU32<1>>;
+
+pub struct XcmConfig;
+impl Config for XcmConfig {
+	type RuntimeCall = RuntimeCall;
+	type XcmSender = XcmRouter;
+	type AssetTransactor = (RelayTransactor, FractionsTransactor);
+	type OriginConverter = XcmOriginToCallOrigin;
+	type IsReserve = SiblingReserves;
+	type IsTeleporter = ();
+	type UniversalLocation = UniversalLocation;
+	type Barrier = Barrier;
+	type Weigher = FixedWeightBounds<XcmInstructionWeight, RuntimeCall, MaxInstructions>;
+	type Trader = FixedRateOfFungible<RelayTokenPerSecondPerByte, ()>;
+	type ResponseHandler = ();
+	type AssetTrap = ();
+	type AssetLocker = ();
+	type AssetExchanger = ();
+	type AssetClaims = ();
+	type SubscriptionService = ();
+	type PalletInstancesInfo = AllPalletsWithSystem;
+	type FeeManager = ();
+	type MaxAssetsIntoHolding = MaxAssetsIntoHolding;
+	type MessageExporter = ();
+	type UniversalAliases = Nothing;
+	type CallDispatcher = RuntimeCall;
+	type SafeCallFilter = Nothing;
+	type Aliasers = Nothing;
+	type TransactionalProcessor = FrameTransactionalProcessor;
+	type HrmpNewChannelOpenRequestHandler = ();
+	type HrmpChannelAcceptedHandler = ();
+	type HrmpChannelClosingHandler = ();
+}
+
+impl mock_msg_queue::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type XcmExecutor = XcmExecutor<XcmConfig>;
+}
+
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		MsgQueue: mock_msg_queue,
+		Assets: pallet_assets,
+	}
+);
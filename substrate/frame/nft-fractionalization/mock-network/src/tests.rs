@@ -0,0 +1,283 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	parachain::{self, Assets, NftFractionalization, Nfts, RuntimeOrigin, System},
+	primitives::AccountId,
+	remote_account_id, remote_parachain, sibling_account_id, MockNet, ParaA, ParaB, ALICE, BOB,
+	INITIAL_BALANCE, REMOTE_FRACTIONS, REMOTE_PARA_ID,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{
+		fungible::{hold::Inspect as InspectHold, Inspect as InspectFungible},
+		fungibles::Inspect,
+	},
+};
+use pallet_nft_fractionalization::{
+	CrossChainFractionalizationLock, Error, Event, PendingCrossChainUnification,
+};
+use pallet_nfts::CollectionConfig;
+use xcm::latest::prelude::*;
+use xcm_simulator::TestExt;
+
+type RemoteAssets = remote_parachain::Assets;
+type RemoteBalances = remote_parachain::Balances;
+
+fn remote_beneficiary(who: AccountId) -> Location {
+	Location::new(1, [Parachain(REMOTE_PARA_ID), AccountId32 { network: None, id: who.into() }])
+}
+
+fn events() -> Vec<Event<parachain::Runtime>> {
+	let result = System::events()
+		.into_iter()
+		.map(|r| r.event)
+		.filter_map(|e| {
+			if let parachain::RuntimeEvent::NftFractionalization(inner) = e {
+				Some(inner)
+			} else {
+				None
+			}
+		})
+		.collect();
+
+	System::reset_events();
+
+	result
+}
+
+/// Fractionalize the NFT of `ALICE`, depositing the `fractions` to `BOB` at `ParaB`.
+fn fractionalize_nft_cross_chain(fractions: u128) {
+	ParaA::execute_with(|| {
+		assert_ok!(Nfts::force_create(RuntimeOrigin::root(), ALICE, CollectionConfig::default()));
+		assert_ok!(Nfts::mint(RuntimeOrigin::signed(ALICE), 0, 0, ALICE, None));
+		assert_ok!(NftFractionalization::fractionalize_cross_chain(
+			RuntimeOrigin::signed(ALICE),
+			0,
+			0,
+			0,
+			Box::new(remote_beneficiary(BOB).into()),
+			fractions,
+		));
+	});
+}
+
+/// Move the fractions of `BOB` at `ParaB` to his account, derived for `ParaA`.
+fn surrender_fractions(fractions: u128) {
+	ParaB::execute_with(|| {
+		assert_ok!(RemoteAssets::transfer(
+			remote_parachain::RuntimeOrigin::signed(BOB),
+			REMOTE_FRACTIONS,
+			remote_account_id(BOB),
+			fractions,
+		));
+	});
+}
+
+fn unify(who: AccountId) {
+	ParaA::execute_with(|| {
+		assert_ok!(NftFractionalization::unify_cross_chain(RuntimeOrigin::signed(who), 0, 0, 0));
+	});
+}
+
+fn finish() -> Vec<Event<parachain::Runtime>> {
+	ParaA::execute_with(|| {
+		System::reset_events();
+		assert_ok!(NftFractionalization::finish_cross_chain_unification(
+			RuntimeOrigin::signed(ALICE),
+			0,
+			0,
+		));
+		events()
+	})
+}
+
+#[test]
+fn cross_chain_unification_should_work() {
+	MockNet::reset();
+
+	let (reserve_providers, issuance) = ParaA::execute_with(|| {
+		(
+			System::providers(&NftFractionalization::get_pallet_account()),
+			parachain::Balances::total_issuance(),
+		)
+	});
+	fractionalize_nft_cross_chain(1000);
+	ParaA::execute_with(|| {
+		let pallet_account = NftFractionalization::get_pallet_account();
+		assert_eq!(Assets::balance(0, &pallet_account), 1000);
+		assert_eq!(System::providers(&pallet_account), reserve_providers + 1);
+		// the caller is charged for the execution of the deposit on the remote chain
+		assert_eq!(
+			parachain::Balances::total_issuance(),
+			issuance - parachain::REMOTE_EXECUTION_FEE
+		);
+		assert_eq!(
+			CrossChainFractionalizationLock::<parachain::Runtime>::get((0, 0)),
+			Some(remote_beneficiary(BOB))
+		);
+		assert_eq!(
+			events().last(),
+			Some(&Event::NftFractionalizedCrossChain {
+				nft_collection: 0,
+				nft: 0,
+				fractions: 1000,
+				asset: 0,
+				beneficiary: remote_beneficiary(BOB),
+			})
+		);
+	});
+	ParaB::execute_with(|| {
+		assert_eq!(RemoteAssets::balance(REMOTE_FRACTIONS, &BOB), 1000);
+		assert_eq!(
+			RemoteBalances::total_balance(&sibling_account_id()),
+			INITIAL_BALANCE - parachain::REMOTE_EXECUTION_FEE
+		);
+		// what is left of the fee goes to the beneficiary
+		let surplus = RemoteBalances::total_balance(&BOB);
+		assert!(surplus > 0 && surplus < parachain::REMOTE_EXECUTION_FEE);
+	});
+
+	// the fractions can't be burned by someone who didn't surrender them
+	surrender_fractions(1000);
+	unify(ALICE);
+	assert_eq!(finish(), vec![Event::CrossChainUnificationFailed { nft_collection: 0, nft: 0 }]);
+	ParaB::execute_with(|| {
+		assert_eq!(RemoteAssets::balance(REMOTE_FRACTIONS, &remote_account_id(BOB)), 1000);
+	});
+
+	// the NFT goes to the account that surrendered the fractions
+	unify(BOB);
+	ParaB::execute_with(|| {
+		assert_eq!(RemoteAssets::balance(REMOTE_FRACTIONS, &remote_account_id(BOB)), 0);
+		assert_eq!(RemoteAssets::total_issuance(REMOTE_FRACTIONS), 0);
+		// the burn request is paid by the account that surrendered the fractions, and the unused
+		// fee is returned to it
+		let paid = INITIAL_BALANCE - RemoteBalances::total_balance(&remote_account_id(BOB));
+		assert!(paid > 0 && paid < parachain::REMOTE_EXECUTION_FEE);
+	});
+	assert_eq!(
+		finish().last(),
+		Some(&Event::NftUnified { nft_collection: 0, nft: 0, asset: 0, beneficiary: BOB })
+	);
+	ParaA::execute_with(|| {
+		assert_eq!(Nfts::owner(0, 0), Some(BOB));
+		assert_eq!(Assets::total_issuance(0), 0);
+		assert_eq!(parachain::Balances::total_balance_on_hold(&ALICE), 0);
+		assert!(!CrossChainFractionalizationLock::<parachain::Runtime>::contains_key((0, 0)));
+		assert!(!PendingCrossChainUnification::<parachain::Runtime>::contains_key((0, 0)));
+		assert_eq!(
+			System::providers(&NftFractionalization::get_pallet_account()),
+			reserve_providers
+		);
+	});
+}
+
+#[test]
+fn cross_chain_fractionalization_should_revert_when_xcm_fails() {
+	MockNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_ok!(Nfts::force_create(RuntimeOrigin::root(), ALICE, CollectionConfig::default()));
+		assert_ok!(Nfts::mint(RuntimeOrigin::signed(ALICE), 0, 0, ALICE, None));
+
+		// there's no route to the parachain `3`
+		assert_noop!(
+			NftFractionalization::fractionalize_cross_chain(
+				RuntimeOrigin::signed(ALICE),
+				0,
+				0,
+				0,
+				Box::new(
+					Location::new(1, [Parachain(3), AccountId32 { network: None, id: BOB.into() }])
+						.into()
+				),
+				1000,
+			),
+			Error::<parachain::Runtime>::XcmSendFailed
+		);
+		assert!(!Assets::asset_exists(0));
+		assert_ok!(Nfts::transfer(RuntimeOrigin::signed(ALICE), 0, 0, BOB));
+		assert_ok!(Nfts::transfer(RuntimeOrigin::signed(BOB), 0, 0, ALICE));
+
+		// locally fractionalized NFTs are not unified cross-chain
+		assert_ok!(NftFractionalization::fractionalize(
+			RuntimeOrigin::signed(ALICE),
+			0,
+			0,
+			0,
+			ALICE,
+			1000,
+		));
+		assert_noop!(
+			NftFractionalization::unify_cross_chain(RuntimeOrigin::signed(ALICE), 0, 0, 0),
+			Error::<parachain::Runtime>::NotCrossChainFractionalized
+		);
+	});
+}
+
+#[test]
+fn cross_chain_unification_should_recover_from_timeout() {
+	MockNet::reset();
+
+	fractionalize_nft_cross_chain(1000);
+	surrender_fractions(1000);
+
+	// the burn request is lost on its way to the remote chain
+	ParaA::execute_without_dispatch(|| {
+		assert_ok!(NftFractionalization::unify_cross_chain(RuntimeOrigin::signed(BOB), 0, 0, 0));
+		assert_noop!(
+			NftFractionalization::unify_cross_chain(RuntimeOrigin::signed(BOB), 0, 0, 0),
+			Error::<parachain::Runtime>::CrossChainUnificationPending
+		);
+	});
+	xcm_simulator::PARA_MESSAGE_BUS.with(|bus| bus.borrow_mut().clear());
+
+	ParaA::execute_with(|| {
+		let deadline = PendingCrossChainUnification::<parachain::Runtime>::get((0, 0))
+			.unwrap()
+			.deadline;
+		assert_eq!(deadline, 1 + parachain::CROSS_CHAIN_UNIFICATION_TIMEOUT);
+		System::set_block_number(deadline);
+		assert_noop!(
+			NftFractionalization::finish_cross_chain_unification(
+				RuntimeOrigin::signed(ALICE),
+				0,
+				0,
+			),
+			Error::<parachain::Runtime>::CrossChainUnificationPending
+		);
+		System::set_block_number(deadline + 1);
+	});
+	assert_eq!(finish(), vec![Event::CrossChainUnificationTimedOut { nft_collection: 0, nft: 0 }]);
+	ParaA::execute_with(|| {
+		assert_eq!(Nfts::owner(0, 0), Some(ALICE));
+	});
+	ParaB::execute_with(|| {
+		assert_eq!(RemoteAssets::balance(REMOTE_FRACTIONS, &remote_account_id(BOB)), 1000);
+	});
+
+	// the unification can be requested again
+	unify(BOB);
+	assert_eq!(
+		finish().last(),
+		Some(&Event::NftUnified { nft_collection: 0, nft: 0, asset: 0, beneficiary: BOB })
+	);
+	ParaA::execute_with(|| {
+		assert_eq!(Nfts::owner(0, 0), Some(BOB));
+	});
+}
@@ -20,19 +20,25 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 use super::*;
-use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_benchmarking::{account, benchmarks, whitelisted_caller, BenchmarkError};
 use frame_support::{
 	assert_ok,
 	traits::{
 		fungible::{Inspect as InspectFungible, Mutate as MutateFungible},
-		tokens::nonfungibles_v2::{Create, Mutate},
+		fungibles::{Inspect as InspectFungibles, Mutate as MutateFungibles},
+		tokens::{
+			nonfungibles_v2::{Create, Mutate},
+			Preservation::{Expendable, Preserve},
+		},
 		Get,
 	},
 };
 use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin as SystemOrigin};
 use pallet_nfts::{CollectionConfig, CollectionSettings, ItemConfig, MintSettings};
-use sp_runtime::traits::StaticLookup;
+use sp_runtime::traits::{One, StaticLookup, Zero};
 use sp_std::prelude::*;
+use xcm::latest::{Location, Response};
+use xcm_executor::traits::QueryHandler;
 
 use crate::Pallet as NftFractionalization;
 
@@ -70,6 +76,83 @@ where
 	(caller, caller_lookup)
 }
 
+fn fractionalize_nft<T: Config>(nft_id: T::NftId) -> (T::AccountId, AccountIdLookupOf<T>)
+where
+	T::Nfts: Create<T::AccountId, CollectionConfig<BalanceOf<T>, BlockNumberFor<T>, T::NftCollectionId>>
+		+ Mutate<T::AccountId, ItemConfig>,
+{
+	let (caller, caller_lookup) = mint_nft::<T>(nft_id);
+	assert_ok!(NftFractionalization::<T>::fractionalize(
+		SystemOrigin::Signed(caller.clone()).into(),
+		T::BenchmarkHelper::collection(0),
+		nft_id,
+		T::BenchmarkHelper::asset(0),
+		caller_lookup.clone(),
+		1000u32.into(),
+	));
+	(caller, caller_lookup)
+}
+
+fn fractionalize_nft_cross_chain<T: Config>(
+	nft_id: T::NftId,
+) -> Result<(T::AccountId, Location), BenchmarkError>
+where
+	T::Nfts: Create<T::AccountId, CollectionConfig<BalanceOf<T>, BlockNumberFor<T>, T::NftCollectionId>>
+		+ Mutate<T::AccountId, ItemConfig>,
+{
+	let beneficiary = T::BenchmarkHelper::remote_beneficiary().ok_or(BenchmarkError::Skip)?;
+	let (caller, _) = mint_nft::<T>(nft_id);
+	NftFractionalization::<T>::fractionalize_cross_chain(
+		SystemOrigin::Signed(caller.clone()).into(),
+		T::BenchmarkHelper::collection(0),
+		nft_id,
+		T::BenchmarkHelper::asset(0),
+		Box::new(beneficiary.clone().into()),
+		1000u32.into(),
+	)?;
+	Ok((caller, beneficiary))
+}
+
+/// Offer the loan, backed by 100 fractions of the NFT, to the `borrower`.
+fn offer_loan_to<T: Config>(
+	nft_id: T::NftId,
+	borrower: &AccountIdLookupOf<T>,
+) -> (T::AccountId, BalanceOf<T>, BlockNumberFor<T>) {
+	let lender: T::AccountId = account("lender", 0, 0);
+	let loan_amount = T::Currency::minimum_balance() * 10u32.into();
+	T::Currency::set_balance(&lender, loan_amount * 10u32.into());
+	let repay_by = frame_system::Pallet::<T>::block_number() + 10u32.into();
+	assert_ok!(NftFractionalization::<T>::offer_loan(
+		SystemOrigin::Signed(lender.clone()).into(),
+		T::BenchmarkHelper::collection(0),
+		nft_id,
+		borrower.clone(),
+		100u32.into(),
+		loan_amount,
+		repay_by,
+	));
+	(lender, loan_amount, repay_by)
+}
+
+/// Take the loan, offered by `offer_loan_to`.
+fn pledge_fractions<T: Config>(
+	nft_id: T::NftId,
+	borrower: &T::AccountId,
+	borrower_lookup: &AccountIdLookupOf<T>,
+) -> (T::AccountId, BlockNumberFor<T>) {
+	let (lender, loan_amount, repay_by) = offer_loan_to::<T>(nft_id, borrower_lookup);
+	assert_ok!(NftFractionalization::<T>::pledge_fractions_as_collateral(
+		SystemOrigin::Signed(borrower.clone()).into(),
+		T::BenchmarkHelper::collection(0),
+		nft_id,
+		100u32.into(),
+		T::Lookup::unlookup(lender.clone()),
+		loan_amount,
+		repay_by,
+	));
+	(lender, repay_by)
+}
+
 fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
 	let events = frame_system::Pallet::<T>::events();
 	let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
@@ -103,6 +186,59 @@ benchmarks! {
 		);
 	}
 
+	fractionalize_with_royalty {
+		let asset = T::BenchmarkHelper::asset(0);
+		let collection = T::BenchmarkHelper::collection(0);
+		let nft = T::BenchmarkHelper::nft(0);
+		let (caller, caller_lookup) = mint_nft::<T>(nft);
+		let royalty = sp_runtime::Perbill::from_percent(5);
+	}: _(SystemOrigin::Signed(caller.clone()), collection, nft, asset.clone(), caller_lookup.clone(), 1000u32.into(), royalty, caller_lookup)
+	verify {
+		assert_eq!(RoyaltyConfig::<T>::get((collection, nft)), Some((caller.clone(), royalty)));
+		assert_last_event::<T>(
+			Event::NftFractionalized {
+				nft_collection: collection,
+				nft,
+				fractions: 1000u32.into(),
+				asset,
+				beneficiary: caller,
+			}.into()
+		);
+	}
+
+	fractionalize_batch {
+		let n in 1 .. T::MaxBatchSize::get();
+		let collection = T::BenchmarkHelper::collection(0);
+		let (caller, caller_lookup) = mint_nft::<T>(T::BenchmarkHelper::nft(0));
+		let ed = T::Currency::minimum_balance();
+		let multiplier = BalanceOf::<T>::from(100u8);
+		T::Currency::set_balance(&caller, ed * multiplier + T::Deposit::get() * (n + 100).into());
+
+		let mut items = Vec::new();
+		for i in 0 .. n {
+			let nft = T::BenchmarkHelper::nft(i);
+			if i > 0 {
+				assert_ok!(T::Nfts::mint_into(&collection, &nft, &caller, &ItemConfig::default(), true));
+			}
+			items.push((collection, nft, T::BenchmarkHelper::asset(i), caller_lookup.clone(), 1000u32.into()));
+		}
+		let items: frame_support::BoundedVec<_, T::MaxBatchSize> = items.try_into().unwrap();
+	}: _(SystemOrigin::Signed(caller.clone()), items)
+	verify {
+		assert_last_event::<T>(
+			Event::NftFractionalized {
+				nft_collection: collection,
+				nft: T::BenchmarkHelper::nft(n - 1),
+				fractions: 1000u32.into(),
+				asset: T::BenchmarkHelper::asset(n - 1),
+				beneficiary: caller,
+			}.into()
+		);
+		// batching must not cost more than fractionalizing the NFTs one by one
+		assert!(T::WeightInfo::fractionalize_batch(n)
+			.all_lte(T::WeightInfo::fractionalize().saturating_mul(n.into())));
+	}
+
 	unify {
 		let asset = T::BenchmarkHelper::asset(0);
 		let collection = T::BenchmarkHelper::collection(0);
@@ -128,5 +264,341 @@ benchmarks! {
 		);
 	}
 
+	unify_partial {
+		let asset = T::BenchmarkHelper::asset(0);
+		let collection = T::BenchmarkHelper::collection(0);
+		let nft = T::BenchmarkHelper::nft(0);
+		let (caller, caller_lookup) = mint_nft::<T>(nft);
+		NftFractionalization::<T>::fractionalize(
+			SystemOrigin::Signed(caller.clone()).into(),
+			collection,
+			nft,
+			asset.clone(),
+			caller_lookup.clone(),
+			1000u32.into(),
+		)?;
+		NftFractionalization::<T>::unify_partial(
+			SystemOrigin::Signed(caller.clone()).into(),
+			collection,
+			nft,
+			asset.clone(),
+			caller_lookup.clone(),
+			1u32.into(),
+		)?;
+	}: _(SystemOrigin::Signed(caller.clone()), collection, nft, asset.clone(), caller_lookup, 999u32.into())
+	verify {
+		assert_last_event::<T>(
+			Event::NftUnified {
+				nft_collection: collection,
+				nft,
+				asset,
+				beneficiary: caller,
+			}.into()
+		);
+	}
+
+	cancel_partial_unification {
+		let c in 1 .. T::MaxUnificationContributors::get();
+
+		let asset = T::BenchmarkHelper::asset(0);
+		let collection = T::BenchmarkHelper::collection(0);
+		let nft = T::BenchmarkHelper::nft(0);
+		let (caller, caller_lookup) = mint_nft::<T>(nft);
+		NftFractionalization::<T>::fractionalize(
+			SystemOrigin::Signed(caller.clone()).into(),
+			collection,
+			nft,
+			asset.clone(),
+			caller_lookup.clone(),
+			1000u32.into(),
+		)?;
+		NftFractionalization::<T>::unify_partial(
+			SystemOrigin::Signed(caller.clone()).into(),
+			collection,
+			nft,
+			asset.clone(),
+			caller_lookup.clone(),
+			10u32.into(),
+		)?;
+		for i in 1 .. c {
+			let contributor: T::AccountId = account("contributor", i, 0);
+			T::Currency::set_balance(&contributor, T::Currency::minimum_balance() * 10u32.into());
+			T::Assets::transfer(asset.clone(), &caller, &contributor, 1u32.into(), Preserve)?;
+			NftFractionalization::<T>::unify_partial(
+				SystemOrigin::Signed(contributor).into(),
+				collection,
+				nft,
+				asset.clone(),
+				caller_lookup.clone(),
+				1u32.into(),
+			)?;
+		}
+	}: _(SystemOrigin::Signed(caller), collection, nft, asset.clone())
+	verify {
+		assert_last_event::<T>(
+			Event::PartialUnificationCancelled {
+				nft_collection: collection,
+				nft,
+				asset,
+				reclaimed: (9 + c).into(),
+			}.into()
+		);
+	}
+
+	fractionalize_cross_chain {
+		let asset = T::BenchmarkHelper::asset(0);
+		let collection = T::BenchmarkHelper::collection(0);
+		let nft = T::BenchmarkHelper::nft(0);
+		let beneficiary = T::BenchmarkHelper::remote_beneficiary().ok_or(BenchmarkError::Skip)?;
+		let (caller, _) = mint_nft::<T>(nft);
+	}: _(SystemOrigin::Signed(caller), collection, nft, asset.clone(), Box::new(beneficiary.clone().into()), 1000u32.into())
+	verify {
+		assert_last_event::<T>(
+			Event::NftFractionalizedCrossChain {
+				nft_collection: collection,
+				nft,
+				fractions: 1000u32.into(),
+				asset,
+				beneficiary,
+			}.into()
+		);
+	}
+
+	unify_cross_chain {
+		let asset = T::BenchmarkHelper::asset(0);
+		let collection = T::BenchmarkHelper::collection(0);
+		let nft = T::BenchmarkHelper::nft(0);
+		let (caller, _) = fractionalize_nft_cross_chain::<T>(nft)?;
+	}: _(SystemOrigin::Signed(caller), collection, nft, asset)
+	verify {
+		assert!(PendingCrossChainUnification::<T>::contains_key((collection, nft)));
+	}
+
+	finish_cross_chain_unification {
+		let asset = T::BenchmarkHelper::asset(0);
+		let collection = T::BenchmarkHelper::collection(0);
+		let nft = T::BenchmarkHelper::nft(0);
+		let (caller, _) = fractionalize_nft_cross_chain::<T>(nft)?;
+		NftFractionalization::<T>::unify_cross_chain(
+			SystemOrigin::Signed(caller.clone()).into(),
+			collection,
+			nft,
+			asset.clone(),
+		)?;
+		// the remote chain confirms the burn, so the NFT is unified
+		let query_id = PendingCrossChainUnification::<T>::get((collection, nft))
+			.ok_or(BenchmarkError::Stop("the unification is not pending"))?
+			.query_id;
+		T::XcmQueries::expect_response(query_id, Response::ExecutionResult(None));
+	}: _(SystemOrigin::Signed(caller.clone()), collection, nft)
+	verify {
+		assert_last_event::<T>(
+			Event::NftUnified {
+				nft_collection: collection,
+				nft,
+				asset,
+				beneficiary: caller,
+			}.into()
+		);
+	}
+
+	burn_fractions {
+		let asset = T::BenchmarkHelper::asset(0);
+		let collection = T::BenchmarkHelper::collection(0);
+		let nft = T::BenchmarkHelper::nft(0);
+		let (caller, _) = fractionalize_nft::<T>(nft);
+	}: _(SystemOrigin::Signed(caller.clone()), collection, nft, 1000u32.into())
+	verify {
+		// burning all the fractions unifies the NFT
+		assert_last_event::<T>(
+			Event::NftUnified {
+				nft_collection: collection,
+				nft,
+				asset,
+				beneficiary: caller,
+			}.into()
+		);
+	}
+
+	transfer_fractions {
+		let asset = T::BenchmarkHelper::asset(0);
+		let collection = T::BenchmarkHelper::collection(0);
+		let nft = T::BenchmarkHelper::nft(0);
+		let (caller, caller_lookup) = mint_nft::<T>(nft);
+		let receiver: T::AccountId = account("receiver", 0, 0);
+		let dest: T::AccountId = account("dest", 0, 0);
+		T::Currency::set_balance(&receiver, T::Currency::minimum_balance() * 10u32.into());
+		T::Currency::set_balance(&dest, T::Currency::minimum_balance() * 10u32.into());
+		NftFractionalization::<T>::fractionalize_with_royalty(
+			SystemOrigin::Signed(caller.clone()).into(),
+			collection,
+			nft,
+			asset.clone(),
+			caller_lookup,
+			1000u32.into(),
+			sp_runtime::Perbill::from_percent(10),
+			T::Lookup::unlookup(receiver),
+		)?;
+		let dest_lookup = T::Lookup::unlookup(dest.clone());
+	}: _(SystemOrigin::Signed(caller), collection, nft, dest_lookup, 1000u32.into())
+	verify {
+		// the royalty is taken from the transferred fractions
+		assert_eq!(T::Assets::balance(asset, &dest), 900u32.into());
+	}
+
+	offer_loan {
+		let collection = T::BenchmarkHelper::collection(0);
+		let nft = T::BenchmarkHelper::nft(0);
+		let (borrower, borrower_lookup) = fractionalize_nft::<T>(nft);
+		// the new offer replaces the previous one
+		let (lender, loan_amount, repay_by) = offer_loan_to::<T>(nft, &borrower_lookup);
+	}: _(SystemOrigin::Signed(lender.clone()), collection, nft, borrower_lookup, 100u32.into(), loan_amount, repay_by)
+	verify {
+		assert_last_event::<T>(
+			Event::LoanOffered {
+				nft_collection: collection,
+				nft,
+				lender,
+				borrower,
+			}.into()
+		);
+	}
+
+	pledge_fractions_as_collateral {
+		let collection = T::BenchmarkHelper::collection(0);
+		let nft = T::BenchmarkHelper::nft(0);
+		let (borrower, borrower_lookup) = fractionalize_nft::<T>(nft);
+		let (lender, loan_amount, repay_by) = offer_loan_to::<T>(nft, &borrower_lookup);
+		let lender_lookup = T::Lookup::unlookup(lender.clone());
+	}: _(SystemOrigin::Signed(borrower.clone()), collection, nft, 100u32.into(), lender_lookup, loan_amount, repay_by)
+	verify {
+		assert_last_event::<T>(
+			Event::FractionsPledged {
+				nft_collection: collection,
+				nft,
+				borrower,
+				lender,
+				fractions: 100u32.into(),
+				loan_amount,
+				repay_by,
+			}.into()
+		);
+	}
+
+	repay_loan {
+		let collection = T::BenchmarkHelper::collection(0);
+		let nft = T::BenchmarkHelper::nft(0);
+		let (borrower, borrower_lookup) = fractionalize_nft::<T>(nft);
+		let (lender, _) = pledge_fractions::<T>(nft, &borrower, &borrower_lookup);
+	}: _(SystemOrigin::Signed(borrower.clone()), collection, nft)
+	verify {
+		assert_last_event::<T>(
+			Event::LoanRepaid {
+				nft_collection: collection,
+				nft,
+				borrower,
+				lender,
+			}.into()
+		);
+	}
+
+	liquidate_fraction_collateral {
+		let collection = T::BenchmarkHelper::collection(0);
+		let nft = T::BenchmarkHelper::nft(0);
+		let (borrower, borrower_lookup) = fractionalize_nft::<T>(nft);
+		let (lender, repay_by) = pledge_fractions::<T>(nft, &borrower, &borrower_lookup);
+		frame_system::Pallet::<T>::set_block_number(repay_by + One::one());
+	}: _(SystemOrigin::Signed(lender.clone()), collection, nft, borrower_lookup)
+	verify {
+		assert_last_event::<T>(
+			Event::FractionCollateralLiquidated {
+				nft_collection: collection,
+				nft,
+				borrower,
+				lender,
+				fractions: 100u32.into(),
+			}.into()
+		);
+	}
+
+	cancel_loan_offer {
+		let collection = T::BenchmarkHelper::collection(0);
+		let nft = T::BenchmarkHelper::nft(0);
+		let (borrower, borrower_lookup) = fractionalize_nft::<T>(nft);
+		let (lender, _, _) = offer_loan_to::<T>(nft, &borrower_lookup);
+	}: _(SystemOrigin::Signed(lender.clone()), collection, nft, borrower_lookup)
+	verify {
+		assert_last_event::<T>(
+			Event::LoanOfferCancelled {
+				nft_collection: collection,
+				nft,
+				lender,
+				borrower,
+			}.into()
+		);
+	}
+
+	transfer_callback {
+		let asset = T::BenchmarkHelper::asset(0);
+		let collection = T::BenchmarkHelper::collection(0);
+		let nft = T::BenchmarkHelper::nft(0);
+		let (caller, caller_lookup) = mint_nft::<T>(nft);
+		let receiver: T::AccountId = account("receiver", 0, 0);
+		let dest: T::AccountId = account("dest", 0, 0);
+		T::Currency::set_balance(&receiver, T::Currency::minimum_balance() * 10u32.into());
+		T::Currency::set_balance(&dest, T::Currency::minimum_balance() * 10u32.into());
+		NftFractionalization::<T>::fractionalize_with_royalty(
+			SystemOrigin::Signed(caller.clone()).into(),
+			collection,
+			nft,
+			asset.clone(),
+			caller_lookup,
+			1000u32.into(),
+			sp_runtime::Perbill::from_percent(10),
+			T::Lookup::unlookup(receiver),
+		)?;
+		// the runtime may already charge the royalty of this transfer through the callback
+		T::Assets::transfer(asset.clone(), &caller, &dest, 1000u32.into(), Expendable)?;
+		let received = T::Assets::balance(asset.clone(), &dest);
+	}: {
+		<NftFractionalization<T> as AssetsTransferCallback<_, _, _>>::transferred(
+			&asset,
+			&caller,
+			&dest,
+			received,
+		);
+	}
+	verify {
+		// the royalty is taken from the transferred fractions
+		assert!(T::Assets::balance(asset, &dest) < received);
+	}
+
+	// Demonstrates how the voting weight in the governance of a fractionalized NFT is computed from
+	// the fraction holdings.
+	governance_weight {
+		let asset = T::BenchmarkHelper::asset(0);
+		let collection = T::BenchmarkHelper::collection(0);
+		let nft = T::BenchmarkHelper::nft(0);
+		let (caller, caller_lookup) = mint_nft::<T>(nft);
+		NftFractionalization::<T>::fractionalize(
+			SystemOrigin::Signed(caller.clone()).into(),
+			collection,
+			nft,
+			asset.clone(),
+			caller_lookup,
+			1000u32.into(),
+		)?;
+		let voter: T::AccountId = account("voter", 0, 0);
+		T::Currency::set_balance(&voter, T::Currency::minimum_balance() * 10u32.into());
+		T::Assets::transfer(asset, &caller, &voter, 400u32.into(), Preserve)?;
+		let mut weight = Zero::zero();
+	}: {
+		weight = NftFractionalization::<T>::governance_weight(&voter, collection, nft);
+	}
+	verify {
+		assert_eq!(weight, 400u32.into());
+		assert_eq!(NftFractionalization::<T>::governance_weight(&caller, collection, nft), 600u32.into());
+	}
+
 	impl_benchmark_test_suite!(NftFractionalization, crate::mock::new_test_ext(), crate::mock::Test);
 }
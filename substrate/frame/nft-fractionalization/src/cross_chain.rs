@@ -0,0 +1,177 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! XCM messages of the cross-chain fractionalization.
+//!
+//! The fractions of a cross-chain fractionalized NFT are minted into the pallet account, which
+//! keeps them as the reserve of the fractions, deposited to the remote beneficiary. To unify such
+//! NFT, the fractions have to be gathered in the account, derived by the remote chain for the
+//! account requesting the unification on this chain. They are burned from there by the
+//! unification request, which is sent with the origin of that account, so only the account that
+//! surrendered the fractions may receive the NFT. The NFT is only unlocked once the remote chain
+//! reports that the burn succeeded.
+//!
+//! The messages pay for their execution on the remote chain with `RemoteExecutionFee`, and the
+//! caller is charged their delivery fees, see `Config::RemoteExecutionFee`.
+
+use super::*;
+use frame_support::traits::Get;
+use sp_runtime::{
+	traits::{MaybeEquivalence, TryConvert, Zero},
+	DispatchError, DispatchResult, SaturatedConversion,
+};
+use xcm::latest::prelude::*;
+use xcm_executor::traits::QueryHandler;
+
+impl<T: Config> Pallet<T> {
+	/// Make sure the pallet account can hold the reserve of the fractions of one more NFT.
+	///
+	/// The fractions are not a sufficient asset, so the account holding them needs a provider.
+	/// Every cross-chain fractionalized NFT adds one, removed by `release_reserve_account` once
+	/// its reserve is burned.
+	pub(crate) fn acquire_reserve_account() -> T::AccountId {
+		let pallet_account = Self::get_pallet_account();
+		frame_system::Pallet::<T>::inc_providers(&pallet_account);
+		pallet_account
+	}
+
+	/// Remove the provider, added by `acquire_reserve_account` for the burned reserve.
+	pub(crate) fn release_reserve_account() -> DispatchResult {
+		frame_system::Pallet::<T>::dec_providers(&Self::get_pallet_account())?;
+		Ok(())
+	}
+
+	/// The `RemoteExecutionFee`, as seen from `dest`.
+	fn remote_fee(dest: &Location) -> Result<Asset, DispatchError> {
+		let universal_location = <T::XcmQueries as QueryHandler>::UniversalLocation::get();
+		T::RemoteExecutionFee::get()
+			.ok_or(Error::<T>::XcmSendFailed)?
+			.reanchored(dest, &universal_location)
+			.map_err(|_| Error::<T>::BadLocation.into())
+	}
+
+	/// Send the `message` to `dest`, charging `who` its delivery fees along with the `extra_fee`.
+	fn send_charged(
+		who: &T::AccountId,
+		dest: Location,
+		message: Xcm<()>,
+		extra_fee: Option<Asset>,
+	) -> DispatchResult {
+		let (ticket, mut fees) =
+			validate_send::<T::XcmSender>(dest, message).map_err(|_| Error::<T>::XcmSendFailed)?;
+		if let Some(fee) = extra_fee {
+			fees.push(fee);
+		}
+		let payer =
+			T::AccountIdToLocation::try_convert(who).map_err(|_| Error::<T>::BadLocation)?;
+		T::XcmExecutor::charge_fees(payer, fees).map_err(|_| Error::<T>::FeesNotMet)?;
+		T::XcmSender::deliver(ticket).map_err(|_| Error::<T>::XcmSendFailed)?;
+		Ok(())
+	}
+
+	/// The `fractions` of the asset `asset_id`, as seen from `dest`.
+	fn remote_fractions(
+		asset_id: &AssetIdOf<T>,
+		fractions: AssetBalanceOf<T>,
+		dest: &Location,
+	) -> Result<Assets, DispatchError> {
+		let universal_location = <T::XcmQueries as QueryHandler>::UniversalLocation::get();
+		let asset_location = T::AssetLocation::convert_back(asset_id)
+			.ok_or(Error::<T>::UnknownAssetLocation)?
+			.reanchored(dest, &universal_location)
+			.map_err(|_| Error::<T>::UnknownAssetLocation)?;
+		Ok((asset_location, fractions.saturated_into::<u128>()).into())
+	}
+
+	/// Deposit the `fractions`, kept in the pallet account, to the `beneficiary` on its chain.
+	///
+	/// The execution is bought with the fee withdrawn from the sovereign account of this chain,
+	/// which is charged to `who` here. What is left of the fee goes to the `beneficiary`.
+	pub(crate) fn do_send_fractions(
+		who: &T::AccountId,
+		asset_id: &AssetIdOf<T>,
+		fractions: AssetBalanceOf<T>,
+		beneficiary: &Location,
+	) -> DispatchResult {
+		let dest = beneficiary.chain_location();
+		let universal_location = <T::XcmQueries as QueryHandler>::UniversalLocation::get();
+		let assets = Self::remote_fractions(asset_id, fractions, &dest)?;
+		let fee = Self::remote_fee(&dest)?;
+		let beneficiary = beneficiary
+			.clone()
+			.reanchored(&dest, &universal_location)
+			.map_err(|_| Error::<T>::BadLocation)?;
+
+		let message = Xcm(vec![
+			WithdrawAsset(fee.clone().into()),
+			BuyExecution { fees: fee, weight_limit: Unlimited },
+			ReserveAssetDeposited(assets),
+			ClearOrigin,
+			DepositAsset { assets: Wild(AllCounted(2)), beneficiary },
+		]);
+		Self::send_charged(who, dest, message, T::RemoteExecutionFee::get())
+	}
+
+	/// Ask the chain of the `remote` beneficiary to burn the `fractions`, gathered in the account
+	/// of `who` there, and to report the outcome back.
+	///
+	/// The execution is bought with the fee withdrawn from the account of `who` on the remote
+	/// chain, and what is left of it is returned there. `who` is charged the delivery fees here.
+	pub(crate) fn do_request_remote_burn(
+		who: &T::AccountId,
+		asset_id: &AssetIdOf<T>,
+		fractions: AssetBalanceOf<T>,
+		remote: &Location,
+	) -> Result<QueryIdOf<T>, DispatchError> {
+		let dest = remote.chain_location();
+		let assets = Self::remote_fractions(asset_id, fractions, &dest)?;
+		let fee = Self::remote_fee(&dest)?;
+		let account = T::AccountIdToLocation::try_convert(who)
+			.map_err(|_| Error::<T>::BadLocation)?
+			.interior()
+			.clone();
+		let destination = <T::XcmQueries as QueryHandler>::UniversalLocation::get()
+			.invert_target(&dest)
+			.map_err(|()| Error::<T>::BadLocation)?;
+
+		let remote_account = destination
+			.clone()
+			.appended_with(account.clone())
+			.map_err(|_| Error::<T>::BadLocation)?;
+
+		// The timeout is tracked by this pallet, see `Config::CrossChainUnificationTimeout`.
+		let query_id = T::XcmQueries::new_query(dest.clone(), Zero::zero(), account.clone());
+		let message = Xcm(vec![
+			DescendOrigin(account),
+			WithdrawAsset(fee.clone().into()),
+			BuyExecution { fees: fee, weight_limit: Unlimited },
+			SetAppendix(Xcm(vec![
+				ReportError(QueryResponseInfo {
+					destination,
+					query_id,
+					max_weight: Weight::zero(),
+				}),
+				RefundSurplus,
+				DepositAsset { assets: Wild(AllCounted(1)), beneficiary: remote_account },
+			])),
+			WithdrawAsset(assets.clone()),
+			BurnAsset(assets),
+		]);
+		Self::send_charged(who, dest, message, None)?;
+		Ok(query_id)
+	}
+}
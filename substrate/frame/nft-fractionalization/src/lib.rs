@@ -26,17 +26,45 @@
 //! fungible asset, and mint a set amount of tokens (`fractions`).
 //!
 //! It also allows the user to burn 100% of the asset and to unlock the NFT
-//! into their account.
+//! into their account. Alternatively, the asset holders may burn their fractions
+//! incrementally, and the NFT is unlocked once all the fractions are burned.
 //!
 //! ### Functions
 //!
 //! * `fractionalize`: Lock the NFT and create and mint a new fungible asset.
+//! * `fractionalize_batch`: Fractionalize several NFTs at once.
+//! * `fractionalize_with_royalty`: Fractionalize the NFT, charging a royalty on the transfers of
+//!   the fractions.
 //! * `unify`: Return 100% of the asset and unlock the NFT.
+//! * `unify_partial`: Burn a part of the asset, unlocking the NFT once the whole asset is burned.
+//! * `cancel_partial_unification`: Return the fractions burned by `unify_partial` to their holders.
+//! * `burn_fractions`: Permanently burn a part of the asset, unlocking the NFT to the last holder
+//!   of the asset.
+//! * `transfer_fractions`: Transfer a part of the asset, unlocking the NFT to the receiver if it
+//!   ends up holding the whole asset and `AutoUnify` is enabled.
+//! * `fractionalize_cross_chain`: Lock the NFT, create a new fungible asset, and send the minted
+//!   asset to a beneficiary on another chain over XCM.
+//! * `unify_cross_chain`: Ask the remote chain to burn 100% of the cross-chain fractionalized
+//!   asset.
+//! * `finish_cross_chain_unification`: Unlock the NFT once the remote chain confirms the burn.
+//!
+//! * `offer_loan`: Offer a loan to a fraction holder, backed by its fractions.
+//! * `cancel_loan_offer`: Withdraw the loan offer, which hasn't been taken yet.
+//! * `pledge_fractions_as_collateral`: Take the offered loan, freezing the pledged fractions.
+//! * `repay_loan`: Return the lent funds and release the pledged fractions.
+//! * `liquidate_fraction_collateral`: Confiscate the fractions, pledged for an overdue loan.
+//!
+//! The fraction holdings may also serve as the voting weight in the governance of the NFT, see
+//! [`FractionVotingWeight`] and [`NftFractionalizationApi::governance_weight`].
 
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
 
+mod cross_chain;
+mod loans;
+mod royalty;
 mod types;
+mod voting;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
@@ -47,12 +75,20 @@ mod tests;
 
 pub mod weights;
 
+use codec::Codec;
+use frame_support::storage::with_storage_layer;
 use frame_system::Config as SystemConfig;
+pub use loans::COLLATERAL_FREEZE_ID;
 pub use pallet::*;
+use pallet_assets::AssetsTransferCallback;
 pub use scale_info::Type;
+use sp_runtime::traits::Zero;
 pub use types::*;
+pub use voting::FractionVotingWeight;
 pub use weights::WeightInfo;
 
+const LOG_TARGET: &str = "runtime::nft-fractionalization";
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -75,15 +111,25 @@ pub mod pallet {
 				AssetId, Balance as AssetBalance,
 				Fortitude::Polite,
 				Precision::{BestEffort, Exact},
-				Preservation::Preserve,
+				Preservation::{Expendable, Preserve},
+				Restriction,
 			},
+			LockIdentifier,
 		},
 		BoundedVec, PalletId,
 	};
 	use frame_system::pallet_prelude::*;
 	use scale_info::prelude::{format, string::String};
-	use sp_runtime::traits::{One, Zero};
+	use sp_runtime::{
+		traits::{CheckedAdd, MaybeEquivalence, One, Saturating, TryConvert, Zero},
+		Perbill,
+	};
 	use sp_std::{fmt::Display, prelude::*};
+	use xcm::{
+		latest::{Asset, ExecuteXcm, Location, Response, SendXcm},
+		VersionedLocation,
+	};
+	use xcm_executor::traits::{QueryHandler, QueryResponseStatus};
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
@@ -149,6 +195,62 @@ pub mod pallet {
 		#[pallet::constant]
 		type StringLimit: Get<u32>;
 
+		/// The maximum number of accounts that may burn fractions towards a partial unification
+		/// of a single NFT.
+		#[pallet::constant]
+		type MaxUnificationContributors: Get<u32>;
+
+		/// The maximum number of freezes, placed on the fractions of a single account through
+		/// [`FractionVotingWeight`].
+		#[pallet::constant]
+		type MaxFreezes: Get<u32>;
+
+		/// The XCM router, delivering the fractions of the cross-chain fractionalized NFTs.
+		type XcmSender: SendXcm;
+
+		/// Charges the callers for the XCM messages sent on their behalf, see
+		/// [`ExecuteXcm::charge_fees`].
+		type XcmExecutor: ExecuteXcm<<Self as SystemConfig>::RuntimeCall>;
+
+		/// The asset, as seen from this chain, buying the execution of the messages on the remote
+		/// chains.
+		///
+		/// The fractions are deposited with the fee withdrawn from the sovereign account of this
+		/// chain, so the caller is charged the fee here, along with the delivery fees. The burn
+		/// requests withdraw the fee from the account of the caller on the remote chain. No
+		/// message is sent if it's `None`.
+		type RemoteExecutionFee: Get<Option<Asset>>;
+
+		/// Registers the queries, expecting the remote chains to confirm the cross-chain
+		/// unifications.
+		type XcmQueries: QueryHandler;
+
+		/// Converts between the location of an asset, relative to this chain, and its ID.
+		type AssetLocation: MaybeEquivalence<Location, AssetIdOf<Self>>;
+
+		/// Converts an account into its location, relative to this chain. The remote chains burn
+		/// the fractions of the cross-chain unification from the account they derive for it.
+		type AccountIdToLocation: for<'a> TryConvert<&'a Self::AccountId, Location>;
+
+		/// The number of blocks to wait for the remote chain to confirm a cross-chain
+		/// unification, before it may be abandoned.
+		#[pallet::constant]
+		type CrossChainUnificationTimeout: Get<BlockNumberFor<Self>>;
+
+		/// The maximum number of NFTs, fractionalized by a single `fractionalize_batch` call.
+		#[pallet::constant]
+		type MaxBatchSize: Get<u32>;
+
+		/// Whether the NFT is unified automatically once a single account holds all of its
+		/// fractions.
+		#[pallet::constant]
+		type AutoUnify: Get<bool>;
+
+		/// The smallest amount of fractions paid out to a royalty receiver. Smaller royalties are
+		/// accumulated until they reach this amount.
+		#[pallet::constant]
+		type MinRoyaltyPayout: Get<AssetBalanceOf<Self>>;
+
 		/// A set of helper functions for benchmarking.
 		#[cfg(feature = "runtime-benchmarks")]
 		type BenchmarkHelper: BenchmarkHelper<Self::AssetId, Self::NftCollectionId, Self::NftId>;
@@ -168,6 +270,125 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// The fractionalized NFT, whose fractions are the given asset. The reverse of `NftToAsset`,
+	/// used to find the NFT when its fractions are transferred.
+	///
+	/// Only recorded for the NFTs fractionalized since this map was introduced.
+	#[pallet::storage]
+	pub type AssetToNft<T: Config> =
+		StorageMap<_, Blake2_128Concat, AssetIdOf<T>, (T::NftCollectionId, T::NftId), OptionQuery>;
+
+	/// The owner of a fractionalized NFT at the time of its fractionalization, along with the
+	/// block it was fractionalized at.
+	#[pallet::storage]
+	pub type OriginalOwner<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(T::NftCollectionId, T::NftId),
+		(T::AccountId, BlockNumberFor<T>),
+		OptionQuery,
+	>;
+
+	/// The amount of fractions burned so far towards a partial unification of the NFT.
+	#[pallet::storage]
+	pub type PartialUnification<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(T::NftCollectionId, T::NftId),
+		AssetBalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// The accounts that burned fractions towards a partial unification of the NFT, along with
+	/// the amounts they burned.
+	#[pallet::storage]
+	pub type PartialUnificationContributors<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(T::NftCollectionId, T::NftId),
+		BoundedVec<(T::AccountId, AssetBalanceOf<T>), T::MaxUnificationContributors>,
+		ValueQuery,
+	>;
+
+	/// The amount of fractions of the NFT, permanently burned by their holders.
+	#[pallet::storage]
+	pub type TotalBurned<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(T::NftCollectionId, T::NftId),
+		AssetBalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// The remote beneficiary of the fractions of a cross-chain fractionalized NFT.
+	#[pallet::storage]
+	pub type CrossChainFractionalizationLock<T: Config> =
+		StorageMap<_, Blake2_128Concat, (T::NftCollectionId, T::NftId), Location, OptionQuery>;
+
+	/// Cross-chain unifications, waiting for the remote chain to confirm that the fractions are
+	/// burned.
+	#[pallet::storage]
+	pub type PendingCrossChainUnification<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(T::NftCollectionId, T::NftId),
+		PendingUnification<T::AccountId, QueryIdOf<T>, BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	/// The receiver of the royalties of the NFT, along with the part of every transfer of the
+	/// fractions it is paid.
+	#[pallet::storage]
+	pub type RoyaltyConfig<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(T::NftCollectionId, T::NftId),
+		(T::AccountId, Perbill),
+		OptionQuery,
+	>;
+
+	/// The royalties of the NFT, kept in the pallet account until they reach `MinRoyaltyPayout`.
+	#[pallet::storage]
+	pub type PendingRoyalties<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(T::NftCollectionId, T::NftId),
+		AssetBalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// Loans, offered to the fraction holders, keyed by the NFT and the borrower.
+	#[pallet::storage]
+	pub type LoanOffers<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(T::NftCollectionId, T::NftId, T::AccountId),
+		LoanRecordOf<T>,
+		OptionQuery,
+	>;
+
+	/// Loans, backed by the fractions of the NFT, keyed by the NFT and the borrower.
+	#[pallet::storage]
+	pub type ActiveLoans<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(T::NftCollectionId, T::NftId, T::AccountId),
+		LoanRecordOf<T>,
+		OptionQuery,
+	>;
+
+	/// Freezes on the fractions of an account, placed through [`FractionVotingWeight`].
+	#[pallet::storage]
+	pub type Freezes<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AssetIdOf<T>,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<(LockIdentifier, AssetBalanceOf<T>), T::MaxFreezes>,
+		ValueQuery,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -186,6 +407,93 @@ pub mod pallet {
 			asset: AssetIdOf<T>,
 			beneficiary: T::AccountId,
 		},
+		/// Fractions were burned towards a partial unification of an NFT.
+		FractionsBurned {
+			nft_collection: T::NftCollectionId,
+			nft: T::NftId,
+			asset: AssetIdOf<T>,
+			who: T::AccountId,
+			amount: AssetBalanceOf<T>,
+			total_burned: AssetBalanceOf<T>,
+		},
+		/// A partial unification was cancelled and the burned fractions were returned.
+		PartialUnificationCancelled {
+			nft_collection: T::NftCollectionId,
+			nft: T::NftId,
+			asset: AssetIdOf<T>,
+			reclaimed: AssetBalanceOf<T>,
+		},
+		/// An NFT was fractionalized and the fractions were sent to another chain.
+		NftFractionalizedCrossChain {
+			nft_collection: T::NftCollectionId,
+			nft: T::NftId,
+			fractions: AssetBalanceOf<T>,
+			asset: AssetIdOf<T>,
+			beneficiary: Location,
+		},
+		/// The remote chain was asked to burn the fractions of a cross-chain fractionalized NFT.
+		CrossChainUnificationRequested {
+			nft_collection: T::NftCollectionId,
+			nft: T::NftId,
+			query_id: QueryIdOf<T>,
+		},
+		/// The remote chain failed to burn the fractions of a cross-chain fractionalized NFT.
+		CrossChainUnificationFailed { nft_collection: T::NftCollectionId, nft: T::NftId },
+		/// The remote chain did not confirm the burn of the fractions in time.
+		CrossChainUnificationTimedOut { nft_collection: T::NftCollectionId, nft: T::NftId },
+		/// Fractions were permanently burned by their holder.
+		FractionsDestroyed {
+			nft_collection: T::NftCollectionId,
+			nft: T::NftId,
+			amount: AssetBalanceOf<T>,
+			burner: T::AccountId,
+		},
+		/// A loan, backed by the fractions of an NFT, was offered.
+		LoanOffered {
+			nft_collection: T::NftCollectionId,
+			nft: T::NftId,
+			lender: T::AccountId,
+			borrower: T::AccountId,
+		},
+		/// A loan offer was withdrawn by the lender.
+		LoanOfferCancelled {
+			nft_collection: T::NftCollectionId,
+			nft: T::NftId,
+			lender: T::AccountId,
+			borrower: T::AccountId,
+		},
+		/// Fractions were pledged as the collateral of a loan.
+		FractionsPledged {
+			nft_collection: T::NftCollectionId,
+			nft: T::NftId,
+			borrower: T::AccountId,
+			lender: T::AccountId,
+			fractions: AssetBalanceOf<T>,
+			loan_amount: DepositOf<T>,
+			repay_by: BlockNumberFor<T>,
+		},
+		/// A loan was repaid and the pledged fractions were released.
+		LoanRepaid {
+			nft_collection: T::NftCollectionId,
+			nft: T::NftId,
+			borrower: T::AccountId,
+			lender: T::AccountId,
+		},
+		/// The fractions, pledged for an overdue loan, were confiscated by the lender.
+		FractionCollateralLiquidated {
+			nft_collection: T::NftCollectionId,
+			nft: T::NftId,
+			borrower: T::AccountId,
+			lender: T::AccountId,
+			fractions: AssetBalanceOf<T>,
+		},
+		/// Royalties of an NFT were paid out to the royalty receiver.
+		RoyaltyPaid {
+			nft_collection: T::NftCollectionId,
+			nft: T::NftId,
+			receiver: T::AccountId,
+			amount: AssetBalanceOf<T>,
+		},
 	}
 
 	#[pallet::error]
@@ -198,6 +506,44 @@ pub mod pallet {
 		NftNotFound,
 		/// NFT has not yet been fractionalised.
 		NftNotFractionalized,
+		/// The amount of fractions is zero or exceeds the fractions left to burn.
+		InvalidFractionAmount,
+		/// Too many accounts burned fractions towards a partial unification of the NFT.
+		TooManyContributors,
+		/// No fractions have been burned towards a partial unification of the NFT.
+		NoPartialUnification,
+		/// Too many freezes are placed on the fractions of the account.
+		TooManyFreezes,
+		/// The location is not supported.
+		BadLocation,
+		/// The location of the asset is unknown.
+		UnknownAssetLocation,
+		/// The XCM message could not be sent.
+		XcmSendFailed,
+		/// NFT has not been fractionalized cross-chain.
+		NotCrossChainFractionalized,
+		/// The remote chain has not confirmed the unification yet.
+		CrossChainUnificationPending,
+		/// No cross-chain unification has been requested.
+		NoCrossChainUnification,
+		/// The royalty must be less than 100%.
+		InvalidRoyalty,
+		/// No loan with the given terms has been offered to the account.
+		NoLoanOffer,
+		/// The account already has a loan, backed by the fractions of the NFT.
+		LoanAlreadyActive,
+		/// The account has no loan, backed by the fractions of the NFT.
+		NoActiveLoan,
+		/// The loan is overdue.
+		LoanExpired,
+		/// The loan is not overdue yet.
+		LoanNotExpired,
+		/// The fractions are frozen and can't be transferred.
+		FractionsFrozen,
+		/// The fees of the XCM message could not be charged.
+		FeesNotMet,
+		/// Another lender has already offered a loan to the account.
+		LoanAlreadyOffered,
 	}
 
 	/// A reason for the pallet placing a hold on funds.
@@ -206,6 +552,20 @@ pub mod pallet {
 		/// Reserved for a fractionalized NFT.
 		#[codec(index = 0)]
 		Fractionalized,
+		/// Reserved for a loan, offered to a fraction holder.
+		#[codec(index = 1)]
+		LoanOffer,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn integrity_test() {
+			assert!(
+				Self::freezes_are_enforced(),
+				"The pallet must be the `Freezer` of `Assets`, otherwise the pledged fractions \
+				and the voting weight are not frozen",
+			);
+		}
 	}
 
 	#[pallet::call]
@@ -240,29 +600,15 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 			let beneficiary = T::Lookup::lookup(beneficiary)?;
 
-			let nft_owner =
-				T::Nfts::owner(&nft_collection_id, &nft_id).ok_or(Error::<T>::NftNotFound)?;
-			ensure!(nft_owner == who, Error::<T>::NoPermission);
-
-			let pallet_account = Self::get_pallet_account();
-			let deposit = T::Deposit::get();
-			T::Currency::hold(&HoldReason::Fractionalized.into(), &nft_owner, deposit)?;
-			Self::do_lock_nft(nft_collection_id, nft_id)?;
-			Self::do_create_asset(asset_id.clone(), pallet_account.clone())?;
-			Self::do_mint_asset(asset_id.clone(), &beneficiary, fractions)?;
-			Self::do_set_metadata(
+			Self::do_fractionalize(
+				who,
+				nft_collection_id,
+				nft_id,
 				asset_id.clone(),
-				&who,
-				&pallet_account,
-				&nft_collection_id,
-				&nft_id,
+				&beneficiary,
+				fractions,
 			)?;
 
-			NftToAsset::<T>::insert(
-				(nft_collection_id, nft_id),
-				Details { asset: asset_id.clone(), fractions, asset_creator: nft_owner, deposit },
-			);
-
 			Self::deposit_event(Event::NftFractionalized {
 				nft_collection: nft_collection_id,
 				nft: nft_id,
@@ -308,7 +654,15 @@ pub mod pallet {
 
 				let deposit = details.deposit;
 				let asset_creator = details.asset_creator;
-				Self::do_burn_asset(asset_id.clone(), &who, details.fractions)?;
+				AssetToNft::<T>::remove(&asset_id);
+				let burned = TotalBurned::<T>::take((nft_collection_id, nft_id));
+				OriginalOwner::<T>::remove((nft_collection_id, nft_id));
+				let royalties = Self::clear_royalty(nft_collection_id, nft_id, &asset_id)?;
+				Self::do_burn_asset(
+					asset_id.clone(),
+					&who,
+					details.fractions.saturating_sub(burned).saturating_sub(royalties),
+				)?;
 				Self::do_unlock_nft(nft_collection_id, nft_id, &beneficiary)?;
 				T::Currency::release(
 					&HoldReason::Fractionalized.into(),
@@ -327,75 +681,1052 @@ pub mod pallet {
 				Ok(())
 			})
 		}
-	}
 
-	impl<T: Config> Pallet<T> {
-		/// The account ID of the pallet.
+		/// Burn a part of the fungible asset towards the unification of the locked NFT.
 		///
-		/// This actually does computation. If you need to keep using it, then make sure you cache
-		/// the value and only call this once.
-		fn get_pallet_account() -> T::AccountId {
-			T::PalletId::get().into_account_truncating()
-		}
-
-		/// Prevent further transferring of NFT.
-		fn do_lock_nft(nft_collection_id: T::NftCollectionId, nft_id: T::NftId) -> DispatchResult {
-			T::Nfts::disable_transfer(&nft_collection_id, &nft_id)
-		}
-
-		/// Remove the transfer lock and transfer the NFT to the account returning the tokens.
-		fn do_unlock_nft(
+		/// The dispatch origin for this call must be Signed.
+		///
+		/// Once the total amount of burned fractions reaches the amount minted on
+		/// fractionalization, the NFT is unlocked and `Deposit` funds are returned to
+		/// `asset_creator`.
+		///
+		/// - `nft_collection_id`: The ID used to identify the collection of the NFT.
+		/// Is used within the context of `pallet_nfts`.
+		/// - `nft_id`: The ID used to identify the NFT within the given collection.
+		/// Is used within the context of `pallet_nfts`.
+		/// - `asset_id`: The ID of the asset being burned. Must match the original ID of the
+		/// created asset, corresponding to the NFT.
+		/// Is used within the context of `pallet_assets`.
+		/// - `beneficiary`: The account that will receive the unified NFT, if this call burns the
+		/// last of the fractions.
+		/// - `fraction_amount`: The amount of fractions to burn.
+		///
+		/// Emits `FractionsBurned` event when successful, followed by `NftUnified` event if the
+		/// NFT was unlocked.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::unify_partial())]
+		pub fn unify_partial(
+			origin: OriginFor<T>,
 			nft_collection_id: T::NftCollectionId,
 			nft_id: T::NftId,
-			account: &T::AccountId,
+			asset_id: AssetIdOf<T>,
+			beneficiary: AccountIdLookupOf<T>,
+			fraction_amount: AssetBalanceOf<T>,
 		) -> DispatchResult {
-			T::Nfts::enable_transfer(&nft_collection_id, &nft_id)?;
-			T::Nfts::transfer(&nft_collection_id, &nft_id, account)
-		}
+			let who = ensure_signed(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
 
-		/// Create the new asset.
-		fn do_create_asset(asset_id: AssetIdOf<T>, admin: T::AccountId) -> DispatchResult {
-			T::Assets::create(asset_id, admin, false, One::one())
-		}
+			let key = (nft_collection_id, nft_id);
+			let details = NftToAsset::<T>::get(key).ok_or(Error::<T>::NftNotFractionalized)?;
+			ensure!(details.asset == asset_id, Error::<T>::IncorrectAssetId);
+			ensure!(!fraction_amount.is_zero(), Error::<T>::InvalidFractionAmount);
+
+			// permanently burned fractions and pending royalties can't take part in the unification
+			let fractions = details
+				.fractions
+				.saturating_sub(TotalBurned::<T>::get(key))
+				.saturating_sub(PendingRoyalties::<T>::get(key));
+			let total_burned = PartialUnification::<T>::get(key)
+				.checked_add(&fraction_amount)
+				.filter(|total_burned| *total_burned <= fractions)
+				.ok_or(Error::<T>::InvalidFractionAmount)?;
+
+			PartialUnificationContributors::<T>::try_mutate(key, |contributors| {
+				match contributors.iter_mut().find(|(contributor, _)| *contributor == who) {
+					Some((_, burned)) => *burned = burned.saturating_add(fraction_amount),
+					None => contributors
+						.try_push((who.clone(), fraction_amount))
+						.map_err(|_| Error::<T>::TooManyContributors)?,
+				}
+				Ok::<_, Error<T>>(())
+			})?;
+			T::Assets::burn_from(asset_id.clone(), &who, fraction_amount, Exact, Polite)?;
+
+			Self::deposit_event(Event::FractionsBurned {
+				nft_collection: nft_collection_id,
+				nft: nft_id,
+				asset: asset_id.clone(),
+				who,
+				amount: fraction_amount,
+				total_burned,
+			});
+
+			if total_burned < fractions {
+				PartialUnification::<T>::insert(key, total_burned);
+				return Ok(())
+			}
+
+			NftToAsset::<T>::remove(key);
+			AssetToNft::<T>::remove(&asset_id);
+			OriginalOwner::<T>::remove(key);
+			TotalBurned::<T>::remove(key);
+			PartialUnification::<T>::remove(key);
+			PartialUnificationContributors::<T>::remove(key);
+			Self::clear_royalty(nft_collection_id, nft_id, &asset_id)?;
+
+			T::Assets::start_destroy(asset_id.clone(), None)?;
+			Self::do_unlock_nft(nft_collection_id, nft_id, &beneficiary)?;
+			T::Currency::release(
+				&HoldReason::Fractionalized.into(),
+				&details.asset_creator,
+				details.deposit,
+				BestEffort,
+			)?;
+
+			Self::deposit_event(Event::NftUnified {
+				nft_collection: nft_collection_id,
+				nft: nft_id,
+				asset: asset_id,
+				beneficiary,
+			});
 
-		/// Mint the `amount` of tokens with `asset_id` into the beneficiary's account.
-		fn do_mint_asset(
-			asset_id: AssetIdOf<T>,
-			beneficiary: &T::AccountId,
-			amount: AssetBalanceOf<T>,
-		) -> DispatchResult {
-			T::Assets::mint_into(asset_id, beneficiary, amount)?;
 			Ok(())
 		}
 
-		/// Burn tokens from the account.
-		fn do_burn_asset(
+		/// Cancel the partial unification of the NFT, minting the burned fractions back to the
+		/// accounts that burned them.
+		///
+		/// The dispatch origin for this call must be Signed by the account that burned the most
+		/// fractions towards the unification. Of the accounts that burned equal amounts, the one
+		/// that started burning first is considered the largest contributor.
+		///
+		/// - `nft_collection_id`: The ID used to identify the collection of the NFT.
+		/// Is used within the context of `pallet_nfts`.
+		/// - `nft_id`: The ID used to identify the NFT within the given collection.
+		/// Is used within the context of `pallet_nfts`.
+		/// - `asset_id`: The ID of the asset being burned. Must match the original ID of the
+		/// created asset, corresponding to the NFT.
+		/// Is used within the context of `pallet_assets`.
+		///
+		/// Emits `PartialUnificationCancelled` event when successful.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::cancel_partial_unification(
+			T::MaxUnificationContributors::get()
+		))]
+		pub fn cancel_partial_unification(
+			origin: OriginFor<T>,
+			nft_collection_id: T::NftCollectionId,
+			nft_id: T::NftId,
 			asset_id: AssetIdOf<T>,
-			account: &T::AccountId,
-			amount: AssetBalanceOf<T>,
-		) -> DispatchResult {
-			T::Assets::burn_from(asset_id.clone(), account, amount, Exact, Polite)?;
-			T::Assets::start_destroy(asset_id, None)
-		}
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
 
-		/// Set the metadata for the newly created asset.
-		fn do_set_metadata(
-			asset_id: AssetIdOf<T>,
-			depositor: &T::AccountId,
-			pallet_account: &T::AccountId,
-			nft_collection_id: &T::NftCollectionId,
-			nft_id: &T::NftId,
-		) -> DispatchResult {
-			let name = format!(
-				"{} {nft_collection_id}-{nft_id}",
-				String::from_utf8_lossy(&T::NewAssetName::get())
-			);
-			let symbol: &[u8] = &T::NewAssetSymbol::get();
-			let existential_deposit = T::Currency::minimum_balance();
-			let pallet_account_balance = T::Currency::balance(&pallet_account);
+			let key = (nft_collection_id, nft_id);
+			let details = NftToAsset::<T>::get(key).ok_or(Error::<T>::NftNotFractionalized)?;
+			ensure!(details.asset == asset_id, Error::<T>::IncorrectAssetId);
 
-			if pallet_account_balance < existential_deposit {
-				T::Currency::transfer(&depositor, &pallet_account, existential_deposit, Preserve)?;
+			let contributors = PartialUnificationContributors::<T>::take(key);
+			// `max_by_key` returns the last of the equal elements, hence the `rev`.
+			let (largest_contributor, _) = contributors
+				.iter()
+				.rev()
+				.max_by_key(|(_, burned)| *burned)
+				.ok_or(Error::<T>::NoPartialUnification)?;
+			ensure!(*largest_contributor == who, Error::<T>::NoPermission);
+
+			let reclaimed = PartialUnification::<T>::take(key);
+			for (contributor, burned) in contributors.iter() {
+				Self::do_mint_asset(asset_id.clone(), contributor, *burned)?;
+			}
+
+			Self::deposit_event(Event::PartialUnificationCancelled {
+				nft_collection: nft_collection_id,
+				nft: nft_id,
+				asset: asset_id,
+				reclaimed,
+			});
+
+			Ok(Some(T::WeightInfo::cancel_partial_unification(contributors.len() as u32)).into())
+		}
+
+		/// Lock the NFT, mint a new fungible asset and send it to a beneficiary on another chain.
+		///
+		/// The dispatch origin for this call must be Signed.
+		/// The origin must be the owner of the NFT they are trying to lock.
+		///
+		/// `Deposit` funds of sender are reserved.
+		///
+		/// The minted fractions are kept in the pallet account as the reserve of the fractions,
+		/// deposited to the `beneficiary` on its chain. The caller is charged the fees of the XCM
+		/// message, see [`Config::RemoteExecutionFee`].
+		///
+		/// - `nft_collection_id`: The ID used to identify the collection of the NFT.
+		/// Is used within the context of `pallet_nfts`.
+		/// - `nft_id`: The ID used to identify the NFT within the given collection.
+		/// Is used within the context of `pallet_nfts`.
+		/// - `asset_id`: The ID of the new asset. It must not exist.
+		/// Is used within the context of `pallet_assets`.
+		/// - `beneficiary`: The location of the account on another chain that will receive the
+		/// newly created asset.
+		/// - `fractions`: The total issuance of the newly created asset class.
+		///
+		/// Emits `NftFractionalizedCrossChain` event when successful.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::fractionalize_cross_chain())]
+		pub fn fractionalize_cross_chain(
+			origin: OriginFor<T>,
+			nft_collection_id: T::NftCollectionId,
+			nft_id: T::NftId,
+			asset_id: AssetIdOf<T>,
+			beneficiary: Box<VersionedLocation>,
+			fractions: AssetBalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let beneficiary: Location =
+				(*beneficiary).try_into().map_err(|()| Error::<T>::BadLocation)?;
+
+			Self::do_fractionalize(
+				who,
+				nft_collection_id,
+				nft_id,
+				asset_id.clone(),
+				&Self::acquire_reserve_account(),
+				fractions,
+			)?;
+			Self::do_send_fractions(&who, &asset_id, fractions, &beneficiary)?;
+			CrossChainFractionalizationLock::<T>::insert(
+				(nft_collection_id, nft_id),
+				beneficiary.clone(),
+			);
+
+			Self::deposit_event(Event::NftFractionalizedCrossChain {
+				nft_collection: nft_collection_id,
+				nft: nft_id,
+				fractions,
+				asset: asset_id,
+				beneficiary,
+			});
+
+			Ok(())
+		}
+
+		/// Ask the remote chain to burn the total issuance of the cross-chain fractionalized
+		/// asset.
+		///
+		/// The dispatch origin for this call must be Signed.
+		///
+		/// All the fractions must be gathered in the account, derived for the origin on the chain
+		/// of the beneficiary, given to `fractionalize_cross_chain`. They are burned from there,
+		/// so only the origin that surrendered them receives the NFT. Once the remote chain
+		/// reports the burn, the NFT can be unlocked with `finish_cross_chain_unification`.
+		///
+		/// The caller is charged the delivery fees of the XCM message here, and its execution is
+		/// paid from the account of the caller on the remote chain.
+		///
+		/// - `nft_collection_id`: The ID used to identify the collection of the NFT.
+		/// Is used within the context of `pallet_nfts`.
+		/// - `nft_id`: The ID used to identify the NFT within the given collection.
+		/// Is used within the context of `pallet_nfts`.
+		/// - `asset_id`: The ID of the asset being burned. Must match the original ID of the
+		/// created asset, corresponding to the NFT.
+		/// Is used within the context of `pallet_assets`.
+		///
+		/// Emits `CrossChainUnificationRequested` event when successful.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::unify_cross_chain())]
+		pub fn unify_cross_chain(
+			origin: OriginFor<T>,
+			nft_collection_id: T::NftCollectionId,
+			nft_id: T::NftId,
+			asset_id: AssetIdOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let key = (nft_collection_id, nft_id);
+			let details = NftToAsset::<T>::get(key).ok_or(Error::<T>::NftNotFractionalized)?;
+			ensure!(details.asset == asset_id, Error::<T>::IncorrectAssetId);
+			let remote = CrossChainFractionalizationLock::<T>::get(key)
+				.ok_or(Error::<T>::NotCrossChainFractionalized)?;
+			ensure!(
+				!PendingCrossChainUnification::<T>::contains_key(key),
+				Error::<T>::CrossChainUnificationPending
+			);
+
+			let query_id =
+				Self::do_request_remote_burn(&who, &asset_id, details.fractions, &remote)?;
+			let deadline = frame_system::Pallet::<T>::block_number()
+				.saturating_add(T::CrossChainUnificationTimeout::get());
+			PendingCrossChainUnification::<T>::insert(
+				key,
+				PendingUnification { beneficiary: who, query_id, deadline },
+			);
+
+			Self::deposit_event(Event::CrossChainUnificationRequested {
+				nft_collection: nft_collection_id,
+				nft: nft_id,
+				query_id,
+			});
+
+			Ok(())
+		}
+
+		/// Complete the cross-chain unification, requested with `unify_cross_chain`.
+		///
+		/// The dispatch origin for this call must be Signed.
+		///
+		/// If the remote chain has confirmed that the fractions are burned, the reserve of the
+		/// fractions is burned, the NFT is unlocked and `Deposit` funds are returned to
+		/// `asset_creator`. If the remote chain has reported a failure, or has not responded until
+		/// `CrossChainUnificationTimeout` blocks passed, the request is dropped, so that it can be
+		/// made again.
+		///
+		/// - `nft_collection_id`: The ID used to identify the collection of the NFT.
+		/// Is used within the context of `pallet_nfts`.
+		/// - `nft_id`: The ID used to identify the NFT within the given collection.
+		/// Is used within the context of `pallet_nfts`.
+		///
+		/// Emits `NftUnified`, `CrossChainUnificationFailed` or `CrossChainUnificationTimedOut`
+		/// event when successful.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::finish_cross_chain_unification())]
+		pub fn finish_cross_chain_unification(
+			origin: OriginFor<T>,
+			nft_collection_id: T::NftCollectionId,
+			nft_id: T::NftId,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let key = (nft_collection_id, nft_id);
+			let pending = PendingCrossChainUnification::<T>::get(key)
+				.ok_or(Error::<T>::NoCrossChainUnification)?;
+
+			match T::XcmQueries::take_response(pending.query_id) {
+				QueryResponseStatus::Ready {
+					response: Response::ExecutionResult(None), ..
+				} => {
+					let details =
+						NftToAsset::<T>::take(key).ok_or(Error::<T>::NftNotFractionalized)?;
+					AssetToNft::<T>::remove(&details.asset);
+					OriginalOwner::<T>::remove(key);
+					PendingCrossChainUnification::<T>::remove(key);
+					CrossChainFractionalizationLock::<T>::remove(key);
+
+					Self::do_burn_asset(
+						details.asset.clone(),
+						&Self::get_pallet_account(),
+						details.fractions,
+					)?;
+					Self::release_reserve_account()?;
+					Self::do_unlock_nft(nft_collection_id, nft_id, &pending.beneficiary)?;
+					T::Currency::release(
+						&HoldReason::Fractionalized.into(),
+						&details.asset_creator,
+						details.deposit,
+						BestEffort,
+					)?;
+
+					Self::deposit_event(Event::NftUnified {
+						nft_collection: nft_collection_id,
+						nft: nft_id,
+						asset: details.asset,
+						beneficiary: pending.beneficiary,
+					});
+				},
+				QueryResponseStatus::Ready { .. } => {
+					PendingCrossChainUnification::<T>::remove(key);
+					Self::deposit_event(Event::CrossChainUnificationFailed {
+						nft_collection: nft_collection_id,
+						nft: nft_id,
+					});
+				},
+				_ if frame_system::Pallet::<T>::block_number() > pending.deadline => {
+					PendingCrossChainUnification::<T>::remove(key);
+					Self::deposit_event(Event::CrossChainUnificationTimedOut {
+						nft_collection: nft_collection_id,
+						nft: nft_id,
+					});
+				},
+				_ => return Err(Error::<T>::CrossChainUnificationPending.into()),
+			}
+
+			Ok(())
+		}
+
+		/// Lock several NFTs and mint a new fungible asset for each of them.
+		///
+		/// The dispatch origin for this call must be Signed.
+		/// The origin must be the owner of all the NFTs they are trying to lock.
+		///
+		/// `Deposit` funds of sender are reserved for each of the NFTs.
+		///
+		/// - `items`: The arguments of `fractionalize` for each of the NFTs, that is the ID of the
+		/// collection, the ID of the NFT, the ID of the new asset, the beneficiary and the
+		/// fractions. If any of the items fails, none of the NFTs are fractionalized.
+		///
+		/// Emits `NftFractionalized` event for each of the NFTs when successful.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::fractionalize_batch(items.len() as u32))]
+		pub fn fractionalize_batch(
+			origin: OriginFor<T>,
+			items: BoundedVec<FractionalizeArgsOf<T>, T::MaxBatchSize>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			for (nft_collection_id, nft_id, asset_id, beneficiary, fractions) in items {
+				let beneficiary = T::Lookup::lookup(beneficiary)?;
+				Self::do_fractionalize(
+					who.clone(),
+					nft_collection_id,
+					nft_id,
+					asset_id.clone(),
+					&beneficiary,
+					fractions,
+				)?;
+
+				Self::deposit_event(Event::NftFractionalized {
+					nft_collection: nft_collection_id,
+					nft: nft_id,
+					fractions,
+					asset: asset_id,
+					beneficiary,
+				});
+			}
+
+			Ok(())
+		}
+
+		/// Permanently burn a part of the fractions held by the caller.
+		///
+		/// The dispatch origin for this call must be Signed.
+		///
+		/// The burned fractions can't be recovered. If the caller holds all the fractions that are
+		/// left after the burn, the NFT is unified: the rest of the fractions are burned, the NFT
+		/// is unlocked into the caller's account and `Deposit` funds are returned to
+		/// `asset_creator`.
+		///
+		/// - `nft_collection_id`: The ID used to identify the collection of the NFT.
+		/// Is used within the context of `pallet_nfts`.
+		/// - `nft_id`: The ID used to identify the NFT within the given collection.
+		/// Is used within the context of `pallet_nfts`.
+		/// - `amount`: The amount of fractions to burn.
+		///
+		/// Emits `FractionsDestroyed` event when successful, followed by `NftUnified` event if the
+		/// NFT was unlocked.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::burn_fractions())]
+		pub fn burn_fractions(
+			origin: OriginFor<T>,
+			nft_collection_id: T::NftCollectionId,
+			nft_id: T::NftId,
+			amount: AssetBalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let key = (nft_collection_id, nft_id);
+			let details = NftToAsset::<T>::get(key).ok_or(Error::<T>::NftNotFractionalized)?;
+			ensure!(!amount.is_zero(), Error::<T>::InvalidFractionAmount);
+
+			T::Assets::burn_from(details.asset.clone(), &who, amount, Exact, Polite)?;
+			let total_burned = TotalBurned::<T>::mutate(key, |burned| {
+				*burned = burned.saturating_add(amount);
+				*burned
+			});
+
+			Self::deposit_event(Event::FractionsDestroyed {
+				nft_collection: nft_collection_id,
+				nft: nft_id,
+				amount,
+				burner: who.clone(),
+			});
+
+			// the fractions, burned towards a partial unification, may still be minted back
+			let remaining = T::Assets::balance(details.asset.clone(), &who)
+				.saturating_add(PendingRoyalties::<T>::get(key));
+			if !PartialUnification::<T>::get(key).is_zero() ||
+				total_burned.saturating_add(remaining) != details.fractions
+			{
+				return Ok(())
+			}
+
+			Self::do_unify_holder(who, nft_collection_id, nft_id, details)
+		}
+
+		/// Transfer the fractions of the NFT to another account.
+		///
+		/// The dispatch origin for this call must be Signed.
+		///
+		/// If `AutoUnify` is enabled and `dest` ends up holding all the fractions of the NFT, the
+		/// NFT is unified into the account of `dest`, see [`Pallet::on_fraction_transfer`].
+		///
+		/// If the NFT was fractionalized with a royalty, the royalty is taken from the fractions
+		/// received by `dest` and paid to the royalty receiver.
+		///
+		/// - `nft_collection_id`: The ID used to identify the collection of the NFT.
+		/// Is used within the context of `pallet_nfts`.
+		/// - `nft_id`: The ID used to identify the NFT within the given collection.
+		/// Is used within the context of `pallet_nfts`.
+		/// - `dest`: The account that will receive the fractions.
+		/// - `amount`: The amount of fractions to transfer.
+		///
+		/// Emits `RoyaltyPaid` event if the royalties were paid out, and `NftUnified` event if the
+		/// NFT was unlocked.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::transfer_fractions())]
+		pub fn transfer_fractions(
+			origin: OriginFor<T>,
+			nft_collection_id: T::NftCollectionId,
+			nft_id: T::NftId,
+			dest: AccountIdLookupOf<T>,
+			amount: AssetBalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let dest = T::Lookup::lookup(dest)?;
+
+			let details = NftToAsset::<T>::get((nft_collection_id, nft_id))
+				.ok_or(Error::<T>::NftNotFractionalized)?;
+			ensure!(!amount.is_zero(), Error::<T>::InvalidFractionAmount);
+			Self::ensure_not_frozen(details.asset.clone(), &who, amount)?;
+
+			Self::transfer_with_royalty(
+				nft_collection_id,
+				nft_id,
+				&details.asset,
+				&who,
+				&dest,
+				amount,
+			)?;
+			Self::on_fraction_transfer(nft_collection_id, nft_id, &dest)
+		}
+
+		/// Lock the NFT and mint a new fungible asset, charging a royalty on the transfers of
+		/// the fractions.
+		///
+		/// The dispatch origin for this call must be Signed.
+		/// The origin must be the owner of the NFT they are trying to lock.
+		///
+		/// `Deposit` funds of sender are reserved.
+		///
+		/// Until the NFT is unified, `royalty` of every amount of fractions transferred is paid to
+		/// `royalty_receiver`. Transfers made directly through the assets pallet are only charged
+		/// if the pallet is set as its `TransferCallback`, see [`AssetsTransferCallback`].
+		/// Royalties below `MinRoyaltyPayout` are accumulated and paid out once they reach it.
+		///
+		/// - `nft_collection_id`: The ID used to identify the collection of the NFT.
+		/// Is used within the context of `pallet_nfts`.
+		/// - `nft_id`: The ID used to identify the NFT within the given collection.
+		/// Is used within the context of `pallet_nfts`.
+		/// - `asset_id`: The ID of the new asset. It must not exist.
+		/// Is used within the context of `pallet_assets`.
+		/// - `beneficiary`: The account that will receive the newly created asset.
+		/// - `fractions`: The total issuance of the newly created asset class.
+		/// - `royalty`: The part of the transferred fractions paid to `royalty_receiver`. Must be
+		/// less than 100%.
+		/// - `royalty_receiver`: The account that will receive the royalties.
+		///
+		/// Emits `NftFractionalized` event when successful.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::fractionalize_with_royalty())]
+		pub fn fractionalize_with_royalty(
+			origin: OriginFor<T>,
+			nft_collection_id: T::NftCollectionId,
+			nft_id: T::NftId,
+			asset_id: AssetIdOf<T>,
+			beneficiary: AccountIdLookupOf<T>,
+			fractions: AssetBalanceOf<T>,
+			royalty: Perbill,
+			royalty_receiver: AccountIdLookupOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+			let royalty_receiver = T::Lookup::lookup(royalty_receiver)?;
+			ensure!(royalty < Perbill::one(), Error::<T>::InvalidRoyalty);
+
+			Self::do_fractionalize(
+				who,
+				nft_collection_id,
+				nft_id,
+				asset_id.clone(),
+				&beneficiary,
+				fractions,
+			)?;
+			// transfers of the NFTs without royalty don't pay for the royalty bookkeeping
+			if !royalty.is_zero() {
+				RoyaltyConfig::<T>::insert(
+					(nft_collection_id, nft_id),
+					(royalty_receiver, royalty),
+				);
+			}
+
+			Self::deposit_event(Event::NftFractionalized {
+				nft_collection: nft_collection_id,
+				nft: nft_id,
+				fractions,
+				asset: asset_id,
+				beneficiary,
+			});
+
+			Ok(())
+		}
+
+		/// Offer a loan to a holder of the fractions of the NFT.
+		///
+		/// The dispatch origin for this call must be Signed by the lender.
+		///
+		/// The borrower takes the loan with `pledge_fractions_as_collateral`, giving the same
+		/// terms. The offer replaces the previous offer of the lender to the borrower for this NFT,
+		/// and can no longer be taken once `repay_by` has passed. The borrower may only have a
+		/// single offer for the NFT, so the offers of other lenders are rejected until it is taken
+		/// or cancelled.
+		///
+		/// `loan_amount` of the lender is held until the offer is taken or cancelled with
+		/// `cancel_loan_offer`.
+		///
+		/// - `nft_collection_id`: The ID used to identify the collection of the NFT.
+		/// Is used within the context of `pallet_nfts`.
+		/// - `nft_id`: The ID used to identify the NFT within the given collection.
+		/// Is used within the context of `pallet_nfts`.
+		/// - `borrower`: The account the loan is offered to.
+		/// - `fractions`: The amount of fractions to be pledged as the collateral.
+		/// - `loan_amount`: The amount of funds to lend.
+		/// - `repay_by`: The block after which the lender may confiscate the collateral.
+		///
+		/// Emits `LoanOffered` event when successful.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::offer_loan())]
+		pub fn offer_loan(
+			origin: OriginFor<T>,
+			nft_collection_id: T::NftCollectionId,
+			nft_id: T::NftId,
+			borrower: AccountIdLookupOf<T>,
+			fractions: AssetBalanceOf<T>,
+			loan_amount: DepositOf<T>,
+			repay_by: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let lender = ensure_signed(origin)?;
+			let borrower = T::Lookup::lookup(borrower)?;
+
+			ensure!(
+				NftToAsset::<T>::contains_key((nft_collection_id, nft_id)),
+				Error::<T>::NftNotFractionalized
+			);
+			ensure!(!fractions.is_zero(), Error::<T>::InvalidFractionAmount);
+			ensure!(repay_by > frame_system::Pallet::<T>::block_number(), Error::<T>::LoanExpired);
+
+			let key = (nft_collection_id, nft_id, borrower.clone());
+			if let Some(previous_offer) = LoanOffers::<T>::get(&key) {
+				ensure!(previous_offer.lender == lender, Error::<T>::LoanAlreadyOffered);
+				Self::release_loan_offer(&previous_offer)?;
+			}
+			T::Currency::hold(&HoldReason::LoanOffer.into(), &lender, loan_amount)?;
+			LoanOffers::<T>::insert(
+				key,
+				LoanRecord { lender: lender.clone(), fractions, loan_amount, repay_by },
+			);
+
+			Self::deposit_event(Event::LoanOffered {
+				nft_collection: nft_collection_id,
+				nft: nft_id,
+				lender,
+				borrower,
+			});
+
+			Ok(())
+		}
+
+		/// Take the loan, offered by `lender`, pledging the fractions of the NFT as its
+		/// collateral.
+		///
+		/// The dispatch origin for this call must be Signed by the borrower.
+		///
+		/// The pledged fractions are frozen until the loan is repaid, and `loan_amount`, held by
+		/// `offer_loan`, is transferred from `lender` to the borrower. The terms must match the
+		/// ones given to `offer_loan` by `lender`.
+		///
+		/// - `nft_collection_id`: The ID used to identify the collection of the NFT.
+		/// Is used within the context of `pallet_nfts`.
+		/// - `nft_id`: The ID used to identify the NFT within the given collection.
+		/// Is used within the context of `pallet_nfts`.
+		/// - `amount`: The amount of fractions to pledge.
+		/// - `lender`: The account that offered the loan.
+		/// - `loan_amount`: The amount of funds to borrow.
+		/// - `repay_by`: The block after which the lender may confiscate the collateral.
+		///
+		/// Emits `FractionsPledged` event when successful.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::pledge_fractions_as_collateral())]
+		pub fn pledge_fractions_as_collateral(
+			origin: OriginFor<T>,
+			nft_collection_id: T::NftCollectionId,
+			nft_id: T::NftId,
+			amount: AssetBalanceOf<T>,
+			lender: AccountIdLookupOf<T>,
+			loan_amount: DepositOf<T>,
+			repay_by: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let lender = T::Lookup::lookup(lender)?;
+
+			let key = (nft_collection_id, nft_id, who.clone());
+			let details = NftToAsset::<T>::get((nft_collection_id, nft_id))
+				.ok_or(Error::<T>::NftNotFractionalized)?;
+			ensure!(!ActiveLoans::<T>::contains_key(&key), Error::<T>::LoanAlreadyActive);
+			let loan =
+				LoanRecord { lender: lender.clone(), fractions: amount, loan_amount, repay_by };
+			ensure!(LoanOffers::<T>::get(&key) == Some(loan.clone()), Error::<T>::NoLoanOffer);
+			ensure!(repay_by > frame_system::Pallet::<T>::block_number(), Error::<T>::LoanExpired);
+			ensure!(
+				T::Assets::balance(details.asset.clone(), &who) >= amount,
+				Error::<T>::InvalidFractionAmount
+			);
+
+			Self::freeze_collateral(details.asset, &who, amount)?;
+			T::Currency::transfer_on_hold(
+				&HoldReason::LoanOffer.into(),
+				&lender,
+				&who,
+				loan_amount,
+				Exact,
+				Restriction::Free,
+				Polite,
+			)?;
+			LoanOffers::<T>::remove(&key);
+			ActiveLoans::<T>::insert(&key, loan);
+
+			Self::deposit_event(Event::FractionsPledged {
+				nft_collection: nft_collection_id,
+				nft: nft_id,
+				borrower: who,
+				lender,
+				fractions: amount,
+				loan_amount,
+				repay_by,
+			});
+
+			Ok(())
+		}
+
+		/// Repay the loan, backed by the fractions of the NFT, and release the pledged fractions.
+		///
+		/// The dispatch origin for this call must be Signed by the borrower.
+		///
+		/// The loan may be repaid after `repay_by` too, as long as the lender hasn't confiscated
+		/// the collateral.
+		///
+		/// - `nft_collection_id`: The ID used to identify the collection of the NFT.
+		/// Is used within the context of `pallet_nfts`.
+		/// - `nft_id`: The ID used to identify the NFT within the given collection.
+		/// Is used within the context of `pallet_nfts`.
+		///
+		/// Emits `LoanRepaid` event when successful.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::repay_loan())]
+		pub fn repay_loan(
+			origin: OriginFor<T>,
+			nft_collection_id: T::NftCollectionId,
+			nft_id: T::NftId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let loan = ActiveLoans::<T>::take((nft_collection_id, nft_id, who.clone()))
+				.ok_or(Error::<T>::NoActiveLoan)?;
+			let details = NftToAsset::<T>::get((nft_collection_id, nft_id))
+				.ok_or(Error::<T>::NftNotFractionalized)?;
+
+			T::Currency::transfer(&who, &loan.lender, loan.loan_amount, Expendable)?;
+			Self::thaw_collateral(details.asset, &who)?;
+
+			Self::deposit_event(Event::LoanRepaid {
+				nft_collection: nft_collection_id,
+				nft: nft_id,
+				borrower: who,
+				lender: loan.lender,
+			});
+
+			Ok(())
+		}
+
+		/// Confiscate the fractions, pledged as the collateral of an overdue loan.
+		///
+		/// The dispatch origin for this call must be Signed by the lender.
+		///
+		/// The pledged fractions are transferred from the borrower to the lender, once `repay_by`
+		/// of the loan has passed.
+		///
+		/// - `nft_collection_id`: The ID used to identify the collection of the NFT.
+		/// Is used within the context of `pallet_nfts`.
+		/// - `nft_id`: The ID used to identify the NFT within the given collection.
+		/// Is used within the context of `pallet_nfts`.
+		/// - `borrower`: The account that took the loan.
+		///
+		/// Emits `FractionCollateralLiquidated` event when successful.
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::liquidate_fraction_collateral())]
+		pub fn liquidate_fraction_collateral(
+			origin: OriginFor<T>,
+			nft_collection_id: T::NftCollectionId,
+			nft_id: T::NftId,
+			borrower: AccountIdLookupOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let borrower = T::Lookup::lookup(borrower)?;
+
+			let key = (nft_collection_id, nft_id, borrower.clone());
+			let loan = ActiveLoans::<T>::get(&key).ok_or(Error::<T>::NoActiveLoan)?;
+			ensure!(loan.lender == who, Error::<T>::NoPermission);
+			ensure!(
+				frame_system::Pallet::<T>::block_number() > loan.repay_by,
+				Error::<T>::LoanNotExpired
+			);
+			let details = NftToAsset::<T>::get((nft_collection_id, nft_id))
+				.ok_or(Error::<T>::NftNotFractionalized)?;
+
+			ActiveLoans::<T>::remove(&key);
+			Self::confiscate_collateral(details.asset, &borrower, &who, loan.fractions)?;
+
+			Self::deposit_event(Event::FractionCollateralLiquidated {
+				nft_collection: nft_collection_id,
+				nft: nft_id,
+				borrower,
+				lender: who,
+				fractions: loan.fractions,
+			});
+
+			Ok(())
+		}
+
+		/// Withdraw the loan offer, which hasn't been taken yet, releasing the held funds.
+		///
+		/// The dispatch origin for this call must be Signed by the lender.
+		///
+		/// - `nft_collection_id`: The ID used to identify the collection of the NFT.
+		/// Is used within the context of `pallet_nfts`.
+		/// - `nft_id`: The ID used to identify the NFT within the given collection.
+		/// Is used within the context of `pallet_nfts`.
+		/// - `borrower`: The account the loan is offered to.
+		///
+		/// Emits `LoanOfferCancelled` event when successful.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::cancel_loan_offer())]
+		pub fn cancel_loan_offer(
+			origin: OriginFor<T>,
+			nft_collection_id: T::NftCollectionId,
+			nft_id: T::NftId,
+			borrower: AccountIdLookupOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let borrower = T::Lookup::lookup(borrower)?;
+
+			let key = (nft_collection_id, nft_id, borrower.clone());
+			let offer = LoanOffers::<T>::get(&key).ok_or(Error::<T>::NoLoanOffer)?;
+			ensure!(offer.lender == who, Error::<T>::NoPermission);
+
+			LoanOffers::<T>::remove(&key);
+			Self::release_loan_offer(&offer)?;
+
+			Self::deposit_event(Event::LoanOfferCancelled {
+				nft_collection: nft_collection_id,
+				nft: nft_id,
+				lender: who,
+				borrower,
+			});
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Lock the NFT of `who`, create the new asset and mint the `fractions` into the account
+		/// of `holder`.
+		fn do_fractionalize(
+			who: T::AccountId,
+			nft_collection_id: T::NftCollectionId,
+			nft_id: T::NftId,
+			asset_id: AssetIdOf<T>,
+			holder: &T::AccountId,
+			fractions: AssetBalanceOf<T>,
+		) -> DispatchResult {
+			let nft_owner =
+				T::Nfts::owner(&nft_collection_id, &nft_id).ok_or(Error::<T>::NftNotFound)?;
+			ensure!(nft_owner == who, Error::<T>::NoPermission);
+
+			let pallet_account = Self::get_pallet_account();
+			let deposit = T::Deposit::get();
+			T::Currency::hold(&HoldReason::Fractionalized.into(), &nft_owner, deposit)?;
+			Self::do_lock_nft(nft_collection_id, nft_id)?;
+			Self::do_create_asset(asset_id.clone(), pallet_account.clone())?;
+			Self::do_mint_asset(asset_id.clone(), holder, fractions)?;
+			Self::do_set_metadata(
+				asset_id.clone(),
+				&who,
+				&pallet_account,
+				&nft_collection_id,
+				&nft_id,
+			)?;
+
+			OriginalOwner::<T>::insert(
+				(nft_collection_id, nft_id),
+				(nft_owner.clone(), frame_system::Pallet::<T>::block_number()),
+			);
+			AssetToNft::<T>::insert(&asset_id, (nft_collection_id, nft_id));
+			NftToAsset::<T>::insert(
+				(nft_collection_id, nft_id),
+				Details { asset: asset_id, fractions, asset_creator: nft_owner, deposit },
+			);
+			Ok(())
+		}
+
+		/// The owner of the fractionalized NFT at the time of its fractionalization, along with
+		/// the block it was fractionalized at. `None` if the NFT is not fractionalized.
+		pub fn original_owner(
+			nft_collection_id: T::NftCollectionId,
+			nft_id: T::NftId,
+		) -> Option<(T::AccountId, BlockNumberFor<T>)> {
+			OriginalOwner::<T>::get((nft_collection_id, nft_id))
+		}
+
+		/// The voting weight of `who` in the governance of the NFT, which is the amount of the NFT
+		/// fractions it holds. Zero if the NFT is not fractionalized.
+		pub fn governance_weight(
+			who: &T::AccountId,
+			nft_collection_id: T::NftCollectionId,
+			nft_id: T::NftId,
+		) -> AssetBalanceOf<T> {
+			NftToAsset::<T>::get((nft_collection_id, nft_id))
+				.map_or_else(Zero::zero, |details| T::Assets::balance(details.asset, who))
+		}
+
+		/// Hook to be called after the fractions of the NFT are transferred to `dest`.
+		///
+		/// If `AutoUnify` is enabled and `dest` holds the whole issuance of the fractions, the
+		/// NFT is unified into the account of `dest`. Nothing happens while a partial
+		/// unification of the NFT is in progress, or if the NFT is fractionalized cross-chain.
+		///
+		/// Called by `transfer_fractions`. It is also called by the [`AssetsTransferCallback`] of
+		/// the pallet on every transfer of the fractions, if the pallet is set as the
+		/// `TransferCallback` of the assets pallet holding them.
+		pub fn on_fraction_transfer(
+			nft_collection_id: T::NftCollectionId,
+			nft_id: T::NftId,
+			dest: &T::AccountId,
+		) -> DispatchResult {
+			if !T::AutoUnify::get() {
+				return Ok(())
+			}
+
+			let key = (nft_collection_id, nft_id);
+			let Some(details) = NftToAsset::<T>::get(key) else { return Ok(()) };
+			if !PartialUnification::<T>::get(key).is_zero() ||
+				CrossChainFractionalizationLock::<T>::contains_key(key)
+			{
+				return Ok(())
+			}
+
+			// the pending royalties are burned on unification
+			let balance = T::Assets::balance(details.asset.clone(), dest);
+			if balance.is_zero() ||
+				balance.saturating_add(PendingRoyalties::<T>::get(key)) !=
+					T::Assets::total_issuance(details.asset.clone())
+			{
+				return Ok(())
+			}
+
+			Self::do_unify_holder(dest.clone(), nft_collection_id, nft_id, details)
+		}
+
+		/// Burn all the fractions left of the NFT, held by `holder`, and unlock the NFT into its
+		/// account.
+		fn do_unify_holder(
+			holder: T::AccountId,
+			nft_collection_id: T::NftCollectionId,
+			nft_id: T::NftId,
+			details: Details<AssetIdOf<T>, AssetBalanceOf<T>, DepositOf<T>, T::AccountId>,
+		) -> DispatchResult {
+			let key = (nft_collection_id, nft_id);
+			NftToAsset::<T>::remove(key);
+			AssetToNft::<T>::remove(&details.asset);
+			OriginalOwner::<T>::remove(key);
+			let burned = TotalBurned::<T>::take(key);
+			let royalties = Self::clear_royalty(nft_collection_id, nft_id, &details.asset)?;
+
+			Self::do_burn_asset(
+				details.asset.clone(),
+				&holder,
+				details.fractions.saturating_sub(burned).saturating_sub(royalties),
+			)?;
+			Self::do_unlock_nft(nft_collection_id, nft_id, &holder)?;
+			T::Currency::release(
+				&HoldReason::Fractionalized.into(),
+				&details.asset_creator,
+				details.deposit,
+				BestEffort,
+			)?;
+
+			Self::deposit_event(Event::NftUnified {
+				nft_collection: nft_collection_id,
+				nft: nft_id,
+				asset: details.asset,
+				beneficiary: holder,
+			});
+
+			Ok(())
+		}
+
+		/// The account ID of the pallet.
+		///
+		/// This actually does computation. If you need to keep using it, then make sure you cache
+		/// the value and only call this once.
+		pub(crate) fn get_pallet_account() -> T::AccountId {
+			T::PalletId::get().into_account_truncating()
+		}
+
+		/// Prevent further transferring of NFT.
+		fn do_lock_nft(nft_collection_id: T::NftCollectionId, nft_id: T::NftId) -> DispatchResult {
+			T::Nfts::disable_transfer(&nft_collection_id, &nft_id)
+		}
+
+		/// Remove the transfer lock and transfer the NFT to the account returning the tokens.
+		fn do_unlock_nft(
+			nft_collection_id: T::NftCollectionId,
+			nft_id: T::NftId,
+			account: &T::AccountId,
+		) -> DispatchResult {
+			T::Nfts::enable_transfer(&nft_collection_id, &nft_id)?;
+			T::Nfts::transfer(&nft_collection_id, &nft_id, account)
+		}
+
+		/// Create the new asset.
+		fn do_create_asset(asset_id: AssetIdOf<T>, admin: T::AccountId) -> DispatchResult {
+			T::Assets::create(asset_id, admin, false, One::one())
+		}
+
+		/// Mint the `amount` of tokens with `asset_id` into the beneficiary's account.
+		fn do_mint_asset(
+			asset_id: AssetIdOf<T>,
+			beneficiary: &T::AccountId,
+			amount: AssetBalanceOf<T>,
+		) -> DispatchResult {
+			T::Assets::mint_into(asset_id, beneficiary, amount)?;
+			Ok(())
+		}
+
+		/// Burn tokens from the account.
+		fn do_burn_asset(
+			asset_id: AssetIdOf<T>,
+			account: &T::AccountId,
+			amount: AssetBalanceOf<T>,
+		) -> DispatchResult {
+			T::Assets::burn_from(asset_id.clone(), account, amount, Exact, Polite)?;
+			T::Assets::start_destroy(asset_id, None)
+		}
+
+		/// Set the metadata for the newly created asset.
+		fn do_set_metadata(
+			asset_id: AssetIdOf<T>,
+			depositor: &T::AccountId,
+			pallet_account: &T::AccountId,
+			nft_collection_id: &T::NftCollectionId,
+			nft_id: &T::NftId,
+		) -> DispatchResult {
+			let name = format!(
+				"{} {nft_collection_id}-{nft_id}",
+				String::from_utf8_lossy(&T::NewAssetName::get())
+			);
+			let symbol: &[u8] = &T::NewAssetSymbol::get();
+			let existential_deposit = T::Currency::minimum_balance();
+			let pallet_account_balance = T::Currency::balance(&pallet_account);
+
+			if pallet_account_balance < existential_deposit {
+				T::Currency::transfer(&depositor, &pallet_account, existential_deposit, Preserve)?;
 			}
 			let metadata_deposit = T::Assets::calc_metadata_deposit(name.as_bytes(), symbol);
 			if !metadata_deposit.is_zero() {
@@ -405,3 +1736,77 @@ pub mod pallet {
 		}
 	}
 }
+
+/// Charges the royalty of the NFT and unifies it automatically once its fractions are transferred
+/// to a single holder, no matter how they are transferred. Requires the pallet to be the
+/// `TransferCallback` of the assets pallet holding the fractions.
+///
+/// The callback reads `AssetToNft` on every transfer of any asset, and may pay out the royalty and
+/// unify the NFT, which is not included in the weight of the transfers of the assets pallet. A
+/// runtime setting the pallet as the `TransferCallback` must account for it in the weights of the
+/// assets pallet, see [`WeightInfo::transfer_callback`].
+impl<T: Config> AssetsTransferCallback<AssetIdOf<T>, T::AccountId, AssetBalanceOf<T>>
+	for Pallet<T>
+{
+	fn transferred(
+		asset: &AssetIdOf<T>,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: AssetBalanceOf<T>,
+	) {
+		if from == to || amount.is_zero() {
+			return
+		}
+		let Some((nft_collection_id, nft_id)) = AssetToNft::<T>::get(asset) else { return };
+
+		// the transfer can't be failed anymore, so a failed royalty payout or unification is only
+		// rolled back
+		if let Err(e) =
+			Self::charge_royalty_on_callback(nft_collection_id, nft_id, asset, from, to, amount)
+		{
+			log::error!(
+				target: LOG_TARGET,
+				"Failed to charge the royalty of the NFT {:?} in collection {:?}: {:?}",
+				nft_id,
+				nft_collection_id,
+				e,
+			);
+		}
+		if let Err(e) =
+			with_storage_layer(|| Self::on_fraction_transfer(nft_collection_id, nft_id, to))
+		{
+			log::error!(
+				target: LOG_TARGET,
+				"Failed to unify the NFT {:?} in collection {:?}: {:?}",
+				nft_id,
+				nft_collection_id,
+				e,
+			);
+		}
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// This runtime api allows to query the voting weight of the fraction holders and the
+	/// original owners of the fractionalized NFTs.
+	pub trait NftFractionalizationApi<AccountId, CollectionId, NftId, Balance, BlockNumber>
+	where
+		AccountId: Codec,
+		CollectionId: Codec,
+		NftId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// Returns the amount of the NFT fractions held by `who`, or zero if the NFT is not
+		/// fractionalized.
+		///
+		/// See [`Pallet::governance_weight`].
+		fn governance_weight(who: AccountId, collection: CollectionId, nft: NftId) -> Balance;
+
+		/// Returns the owner of the NFT at the time of its fractionalization and the block it was
+		/// fractionalized at, or `None` if the NFT is not fractionalized.
+		///
+		/// See [`Pallet::original_owner`].
+		fn original_owner(collection: CollectionId, nft: NftId) -> Option<(AccountId, BlockNumber)>;
+	}
+}
@@ -0,0 +1,129 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Loans, backed by the fractions.
+//!
+//! A lender offers a loan to a fraction holder with `offer_loan`, and the holder takes it with
+//! `pledge_fractions_as_collateral`. The pledged fractions are frozen with the same freezes that
+//! back the votes of [`FractionVotingWeight`], so the collateral may also back the votes of the
+//! borrower. The collateral is released by `repay_loan`, or confiscated by the lender with
+//! `liquidate_fraction_collateral` once the loan is overdue.
+//!
+//! The loan amount is held from the lender when the loan is offered. The freezes are only
+//! enforced on the transfers, made with [`pallet_assets`], if this pallet is its `Freezer`, which
+//! is checked by the `integrity_test`.
+
+use super::*;
+use codec::Decode;
+use frame_support::{
+	ensure,
+	traits::{
+		fungible::hold::Mutate as HoldMutate,
+		fungibles::{Create, Inspect, Mutate},
+		tokens::{Fortitude::Polite, Precision::Exact, Preservation::Expendable},
+		Get, LockIdentifier,
+	},
+};
+use pallet_assets::FrozenBalance;
+use sp_runtime::{
+	traits::{AccountIdConversion, One, Saturating, TrailingZeroInput, Zero},
+	DispatchResult,
+};
+
+/// The identifier of the freeze, placed on the pledged fractions.
+pub const COLLATERAL_FREEZE_ID: LockIdentifier = *b"fraccoll";
+
+impl<T: Config> Pallet<T> {
+	/// Freeze `amount` of the fractions of `who`, pledged as the collateral of a loan.
+	pub(crate) fn freeze_collateral(
+		asset: AssetIdOf<T>,
+		who: &T::AccountId,
+		amount: AssetBalanceOf<T>,
+	) -> DispatchResult {
+		Self::update_freeze(asset, &COLLATERAL_FREEZE_ID, who, |_| amount)
+	}
+
+	/// Release the collateral of `who`.
+	pub(crate) fn thaw_collateral(asset: AssetIdOf<T>, who: &T::AccountId) -> DispatchResult {
+		Self::update_freeze(asset, &COLLATERAL_FREEZE_ID, who, |_| Zero::zero())
+	}
+
+	/// Release the funds of the lender, held for the loan `offer`.
+	pub(crate) fn release_loan_offer(offer: &LoanRecordOf<T>) -> DispatchResult {
+		T::Currency::release(
+			&HoldReason::LoanOffer.into(),
+			&offer.lender,
+			offer.loan_amount,
+			Exact,
+		)?;
+		Ok(())
+	}
+
+	/// Ensure that `amount` of the fractions of `who` is not frozen.
+	pub(crate) fn ensure_not_frozen(
+		asset: AssetIdOf<T>,
+		who: &T::AccountId,
+		amount: AssetBalanceOf<T>,
+	) -> DispatchResult {
+		let frozen = <Self as FrozenBalance<_, _, _>>::frozen_balance(asset.clone(), who)
+			.unwrap_or_else(Zero::zero);
+		ensure!(
+			T::Assets::balance(asset, who).saturating_sub(frozen) >= amount,
+			Error::<T>::FractionsFrozen
+		);
+		Ok(())
+	}
+
+	/// Returns true if the freezes of this pallet are enforced by `T::Assets`, i.e. this pallet is
+	/// its `Freezer`.
+	///
+	/// A probe asset is created and frozen, so it must only be called on a throwaway storage,
+	/// e.g. by the `integrity_test`.
+	pub(crate) fn freezes_are_enforced() -> bool {
+		let asset = AssetIdOf::<T>::decode(&mut TrailingZeroInput::zeroes())
+			.expect("infinite length input; no invalid inputs for type; qed");
+		let who: T::AccountId = T::PalletId::get().into_sub_account_truncating(b"probe");
+		let frozen: AssetBalanceOf<T> = One::one();
+		let balance = frozen.saturating_add(One::one());
+
+		let probe = T::Assets::create(asset.clone(), who.clone(), true, One::one())
+			.and_then(|_| T::Assets::mint_into(asset.clone(), &who, balance))
+			.and_then(|_| Self::freeze_collateral(asset.clone(), &who, frozen));
+		probe.is_ok() && T::Assets::reducible_balance(asset, &who, Expendable, Polite) < balance
+	}
+
+	/// Move the `amount` of the fractions, pledged by `borrower`, to `lender`.
+	///
+	/// The other freezes of the borrower are lifted for the transfer, since the collateral may
+	/// also back its votes, and restored afterwards.
+	pub(crate) fn confiscate_collateral(
+		asset: AssetIdOf<T>,
+		borrower: &T::AccountId,
+		lender: &T::AccountId,
+		amount: AssetBalanceOf<T>,
+	) -> DispatchResult {
+		let mut freezes = Freezes::<T>::take(&asset, borrower);
+		freezes.retain(|(id, _)| *id != COLLATERAL_FREEZE_ID);
+
+		T::Assets::transfer(asset.clone(), borrower, lender, amount, Expendable)?;
+
+		if !freezes.is_empty() && !T::Assets::balance(asset.clone(), borrower).is_zero() {
+			Freezes::<T>::insert(asset, borrower, freezes);
+		}
+		Ok(())
+	}
+}
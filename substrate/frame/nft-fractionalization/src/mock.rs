@@ -28,9 +28,10 @@ use frame_support::{
 use frame_system::EnsureSigned;
 use pallet_nfts::PalletFeatures;
 use sp_runtime::{
-	traits::{IdentifyAccount, IdentityLookup, Verify},
+	traits::{IdentifyAccount, IdentityLookup, MaybeEquivalence, Verify},
 	BuildStorage, MultiSignature,
 };
+use xcm::latest::prelude::*;
 
 type Block = frame_system::mocking::MockBlock<Test>;
 type Signature = MultiSignature;
@@ -88,9 +89,10 @@ impl pallet_assets::Config for Test {
 	type MetadataDepositPerByte = ConstU64<1>;
 	type ApprovalDeposit = ConstU64<1>;
 	type StringLimit = ConstU32<50>;
-	type Freezer = ();
+	type Freezer = NftFractionalization;
 	type Extra = ();
 	type CallbackHandle = ();
+	type TransferCallback = TestTransferCallback;
 	type WeightInfo = ();
 	pallet_assets::runtime_benchmarks_enabled! {
 		type BenchmarkHelper = ();
@@ -136,6 +138,38 @@ parameter_types! {
 	pub const NftFractionalizationPalletId: PalletId = PalletId(*b"fraction");
 	pub NewAssetSymbol: BoundedVec<u8, StringLimit> = (*b"FRAC").to_vec().try_into().unwrap();
 	pub NewAssetName: BoundedVec<u8, StringLimit> = (*b"Frac").to_vec().try_into().unwrap();
+	pub static AutoUnify: bool = false;
+	pub static MinRoyaltyPayout: u64 = 5;
+	pub static TransferCallbackEnabled: bool = true;
+	pub RemoteExecutionFee: Option<Asset> = Some((Parent, 1_000).into());
+}
+
+/// Calls the transfer callback of the pallet unless it is disabled, to test the pallet both with
+/// and without it.
+pub struct TestTransferCallback;
+impl pallet_assets::AssetsTransferCallback<u32, AccountId, u64> for TestTransferCallback {
+	fn transferred(id: &u32, from: &AccountId, to: &AccountId, amount: u64) {
+		if TransferCallbackEnabled::get() {
+			<NftFractionalization as pallet_assets::AssetsTransferCallback<_, _, _>>::transferred(
+				id, from, to, amount,
+			)
+		}
+	}
+}
+
+/// Assets of the mock, located at the `GeneralIndex` of their ID in the pallet instance `2`.
+pub struct TestAssetLocation;
+impl MaybeEquivalence<Location, u32> for TestAssetLocation {
+	fn convert(location: &Location) -> Option<u32> {
+		match location.unpack() {
+			(0, [PalletInstance(2), GeneralIndex(id)]) => (*id).try_into().ok(),
+			_ => None,
+		}
+	}
+
+	fn convert_back(id: &u32) -> Option<Location> {
+		Some(Location::new(0, [PalletInstance(2), GeneralIndex((*id).into())]))
+	}
 }
 
 impl Config for Test {
@@ -151,6 +185,18 @@ impl Config for Test {
 	type Assets = Assets;
 	type Nfts = Nfts;
 	type PalletId = NftFractionalizationPalletId;
+	type MaxUnificationContributors = ConstU32<3>;
+	type MaxFreezes = ConstU32<2>;
+	type XcmSender = ();
+	type XcmExecutor = ();
+	type RemoteExecutionFee = RemoteExecutionFee;
+	type XcmQueries = ();
+	type AssetLocation = TestAssetLocation;
+	type AccountIdToLocation = ();
+	type CrossChainUnificationTimeout = ConstU64<10>;
+	type MaxBatchSize = ConstU32<3>;
+	type AutoUnify = AutoUnify;
+	type MinRoyaltyPayout = MinRoyaltyPayout;
 	type WeightInfo = ();
 	type StringLimit = StringLimit;
 	#[cfg(feature = "runtime-benchmarks")]
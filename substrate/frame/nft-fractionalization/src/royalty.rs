@@ -0,0 +1,163 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Royalties on the transfers of fractions.
+//!
+//! An NFT, fractionalized with `fractionalize_with_royalty`, charges a part of every amount of
+//! fractions transferred and routes it to the royalty receiver. The royalty is taken from the
+//! fractions received. The transfers made directly through the assets pallet are charged by the
+//! [`AssetsTransferCallback`] of the pallet, so it must be set as the `TransferCallback` of the
+//! assets pallet holding the fractions. Otherwise only `transfer_fractions` charges the royalty.
+//! Small royalties are kept in the pallet account until they add up to `MinRoyaltyPayout`, so that
+//! the receiver isn't paid dust. The royalties that are still pending when the NFT is unified are
+//! burned along with the rest of the fractions.
+
+use super::*;
+use frame_support::traits::{
+	fungibles::{Inspect, Mutate},
+	tokens::{Fortitude::Polite, Precision::Exact, Preservation::Expendable},
+	Get,
+};
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	DispatchError, DispatchResult,
+};
+
+// Set by the transfer callback once it has charged the royalty of a transfer, so that the pallet
+// doesn't charge it again.
+environmental::environmental!(royalty_charged: bool);
+
+impl<T: Config> Pallet<T> {
+	/// Transfer `amount` fractions of the NFT from `from` to `to`, charging the royalty of the NFT.
+	///
+	/// The royalty is charged here unless it has already been charged by the transfer callback.
+	pub(crate) fn transfer_with_royalty(
+		nft_collection_id: T::NftCollectionId,
+		nft_id: T::NftId,
+		asset: &AssetIdOf<T>,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: AssetBalanceOf<T>,
+	) -> DispatchResult {
+		let mut charged = false;
+		royalty_charged::using(&mut charged, || {
+			T::Assets::transfer(asset.clone(), from, to, amount, Expendable)
+		})?;
+		if !charged {
+			Self::charge_royalty(nft_collection_id, nft_id, asset, from, to, amount)?;
+		}
+		Ok(())
+	}
+
+	/// Charge the royalty of the NFT on a transfer, reported by the transfer callback.
+	///
+	/// The royalty is only charged if all of its transfers succeed. Otherwise it is left to the
+	/// pallet call, transferring the fractions, if there is any.
+	pub(crate) fn charge_royalty_on_callback(
+		nft_collection_id: T::NftCollectionId,
+		nft_id: T::NftId,
+		asset: &AssetIdOf<T>,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: AssetBalanceOf<T>,
+	) -> DispatchResult {
+		with_storage_layer(|| {
+			Self::charge_royalty(nft_collection_id, nft_id, asset, from, to, amount)
+		})?;
+		royalty_charged::with(|charged| *charged = true);
+		Ok(())
+	}
+
+	/// Charge the royalty of the NFT on the transfer of `amount` fractions from `from` to `to`.
+	///
+	/// The royalty is taken from the fractions just received by `to`. Nothing is charged if the
+	/// NFT has no royalty, or if either side of the transfer is the royalty receiver itself or the
+	/// pallet account, which holds the pending royalties.
+	pub(crate) fn charge_royalty(
+		nft_collection_id: T::NftCollectionId,
+		nft_id: T::NftId,
+		asset: &AssetIdOf<T>,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: AssetBalanceOf<T>,
+	) -> DispatchResult {
+		let key = (nft_collection_id, nft_id);
+		let Some((receiver, royalty)) = RoyaltyConfig::<T>::get(key) else { return Ok(()) };
+		let fee = royalty.mul_floor(amount);
+		let pallet_account = Self::get_pallet_account();
+		if fee.is_zero() ||
+			receiver == *from ||
+			receiver == *to ||
+			pallet_account == *from ||
+			pallet_account == *to
+		{
+			return Ok(())
+		}
+
+		let pending = PendingRoyalties::<T>::get(key).saturating_add(fee);
+		let min_payout = T::MinRoyaltyPayout::get().max(T::Assets::minimum_balance(asset.clone()));
+		if pending < min_payout {
+			T::Assets::transfer(asset.clone(), to, &pallet_account, fee, Expendable)?;
+			PendingRoyalties::<T>::insert(key, pending);
+			return Ok(())
+		}
+
+		T::Assets::transfer(asset.clone(), to, &receiver, fee, Expendable)?;
+		let accumulated = PendingRoyalties::<T>::take(key);
+		if !accumulated.is_zero() {
+			T::Assets::transfer(
+				asset.clone(),
+				&pallet_account,
+				&receiver,
+				accumulated,
+				Expendable,
+			)?;
+		}
+
+		Self::deposit_event(Event::RoyaltyPaid {
+			nft_collection: nft_collection_id,
+			nft: nft_id,
+			receiver,
+			amount: pending,
+		});
+
+		Ok(())
+	}
+
+	/// Remove the royalty of the NFT, burning the royalties that haven't been paid out yet.
+	///
+	/// Returns the burned royalties. Must be called before the asset is destroyed on unification.
+	pub(crate) fn clear_royalty(
+		nft_collection_id: T::NftCollectionId,
+		nft_id: T::NftId,
+		asset: &AssetIdOf<T>,
+	) -> Result<AssetBalanceOf<T>, DispatchError> {
+		let key = (nft_collection_id, nft_id);
+		RoyaltyConfig::<T>::remove(key);
+		let pending = PendingRoyalties::<T>::take(key);
+		if !pending.is_zero() {
+			T::Assets::burn_from(
+				asset.clone(),
+				&Self::get_pallet_account(),
+				pending,
+				Exact,
+				Polite,
+			)?;
+		}
+		Ok(pending)
+	}
+}
@@ -18,15 +18,22 @@
 //! Tests for Nft fractionalization pallet.
 
 use crate::{mock::*, *};
+use codec::{Decode, Encode};
 use frame_support::{
-	assert_noop, assert_ok,
+	assert_noop, assert_ok, bounded_vec, parameter_types,
 	traits::{
-		fungible::{hold::Inspect as InspectHold, Mutate as MutateFungible},
-		fungibles::{metadata::Inspect, InspectEnumerable},
+		fungible::{
+			hold::Inspect as InspectHold, Inspect as InspectFungible, InspectFreeze,
+			Mutate as MutateFungible, MutateFreeze,
+		},
+		fungibles::{metadata::Inspect, InspectEnumerable, Mutate},
+		tokens::Preservation::Expendable,
+		Get,
 	},
+	BoundedVec,
 };
 use pallet_nfts::CollectionConfig;
-use sp_runtime::{DispatchError, ModuleError, TokenError::FundsUnavailable};
+use sp_runtime::{DispatchError, ModuleError, Perbill, TokenError::FundsUnavailable};
 
 fn assets() -> Vec<u32> {
 	let mut s: Vec<_> = <<Test as Config>::Assets>::asset_ids().collect();
@@ -303,3 +310,1422 @@ fn unify_should_work() {
 		assert_eq!(Nfts::owner(nft_collection_id, nft_id), Some(account(2)));
 	});
 }
+
+fn fractionalize_nft(fractions: u64) {
+	Balances::set_balance(&account(1), 100);
+	Balances::set_balance(&account(2), 100);
+	Balances::set_balance(&account(3), 100);
+
+	assert_ok!(Nfts::force_create(RuntimeOrigin::root(), account(1), CollectionConfig::default()));
+	assert_ok!(Nfts::mint(RuntimeOrigin::signed(account(1)), 0, 0, account(1), None));
+	assert_ok!(NftFractionalization::fractionalize(
+		RuntimeOrigin::signed(account(1)),
+		0,
+		0,
+		0,
+		account(1),
+		fractions,
+	));
+	assert_ok!(Assets::transfer(RuntimeOrigin::signed(account(1)), 0, account(2), fractions / 2));
+	assert_ok!(Assets::transfer(RuntimeOrigin::signed(account(1)), 0, account(3), fractions / 4));
+}
+
+#[test]
+fn unify_partial_should_burn_incrementally() {
+	new_test_ext().execute_with(|| {
+		fractionalize_nft(1000);
+
+		assert_noop!(
+			NftFractionalization::unify_partial(
+				RuntimeOrigin::signed(account(2)),
+				0,
+				0,
+				1,
+				account(2),
+				100,
+			),
+			Error::<Test>::IncorrectAssetId
+		);
+		assert_noop!(
+			NftFractionalization::unify_partial(
+				RuntimeOrigin::signed(account(2)),
+				0,
+				0,
+				0,
+				account(2),
+				0,
+			),
+			Error::<Test>::InvalidFractionAmount
+		);
+		assert_noop!(
+			NftFractionalization::unify_partial(
+				RuntimeOrigin::signed(account(2)),
+				0,
+				0,
+				0,
+				account(2),
+				501,
+			),
+			DispatchError::Token(FundsUnavailable)
+		);
+
+		assert_ok!(NftFractionalization::unify_partial(
+			RuntimeOrigin::signed(account(2)),
+			0,
+			0,
+			0,
+			account(2),
+			300,
+		));
+		assert_ok!(NftFractionalization::unify_partial(
+			RuntimeOrigin::signed(account(3)),
+			0,
+			0,
+			0,
+			account(3),
+			100,
+		));
+		assert_ok!(NftFractionalization::unify_partial(
+			RuntimeOrigin::signed(account(2)),
+			0,
+			0,
+			0,
+			account(2),
+			100,
+		));
+
+		assert_eq!(Assets::balance(0, account(2)), 100);
+		assert_eq!(Assets::balance(0, account(3)), 150);
+		assert_eq!(PartialUnification::<Test>::get((0, 0)), 500);
+		assert_eq!(
+			PartialUnificationContributors::<Test>::get((0, 0)).into_inner(),
+			vec![(account(2), 400), (account(3), 100)]
+		);
+		assert_eq!(
+			events().last(),
+			Some(&Event::<Test>::FractionsBurned {
+				nft_collection: 0,
+				nft: 0,
+				asset: 0,
+				who: account(2),
+				amount: 100,
+				total_burned: 500,
+			})
+		);
+
+		// the NFT is still locked
+		assert!(NftToAsset::<Test>::contains_key((0, 0)));
+		assert_eq!(Nfts::owner(0, 0), Some(account(1)));
+
+		// the number of contributors is bounded
+		Balances::set_balance(&account(4), 100);
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(account(1)), 0, account(4), 10));
+		assert_ok!(NftFractionalization::unify_partial(
+			RuntimeOrigin::signed(account(1)),
+			0,
+			0,
+			0,
+			account(1),
+			10,
+		));
+		assert_noop!(
+			NftFractionalization::unify_partial(
+				RuntimeOrigin::signed(account(4)),
+				0,
+				0,
+				0,
+				account(4),
+				10,
+			),
+			Error::<Test>::TooManyContributors
+		);
+	});
+}
+
+#[test]
+fn unify_partial_should_unlock_nft_once_all_fractions_are_burned() {
+	new_test_ext().execute_with(|| {
+		fractionalize_nft(1000);
+
+		assert_ok!(NftFractionalization::unify_partial(
+			RuntimeOrigin::signed(account(1)),
+			0,
+			0,
+			0,
+			account(1),
+			250,
+		));
+		assert_ok!(NftFractionalization::unify_partial(
+			RuntimeOrigin::signed(account(3)),
+			0,
+			0,
+			0,
+			account(3),
+			250,
+		));
+		assert_ok!(NftFractionalization::unify_partial(
+			RuntimeOrigin::signed(account(2)),
+			0,
+			0,
+			0,
+			account(3),
+			500,
+		));
+
+		assert_eq!(Nfts::owner(0, 0), Some(account(3)));
+		assert_eq!(Balances::total_balance_on_hold(&account(1)), 1);
+		assert!(!NftToAsset::<Test>::contains_key((0, 0)));
+		assert!(!PartialUnification::<Test>::contains_key((0, 0)));
+		assert!(!PartialUnificationContributors::<Test>::contains_key((0, 0)));
+
+		let events = events();
+		assert!(events.contains(&Event::<Test>::FractionsBurned {
+			nft_collection: 0,
+			nft: 0,
+			asset: 0,
+			who: account(2),
+			amount: 500,
+			total_burned: 1000,
+		}));
+		assert_eq!(
+			events.last(),
+			Some(&Event::<Test>::NftUnified {
+				nft_collection: 0,
+				nft: 0,
+				asset: 0,
+				beneficiary: account(3),
+			})
+		);
+
+		// the NFT can be fractionalized again
+		assert_ok!(NftFractionalization::fractionalize(
+			RuntimeOrigin::signed(account(3)),
+			0,
+			0,
+			1,
+			account(3),
+			1000,
+		));
+	});
+}
+
+#[test]
+fn unify_partial_should_not_be_completed_twice() {
+	new_test_ext().execute_with(|| {
+		fractionalize_nft(1000);
+
+		assert_ok!(NftFractionalization::unify_partial(
+			RuntimeOrigin::signed(account(1)),
+			0,
+			0,
+			0,
+			account(1),
+			250,
+		));
+
+		// both remaining holders try to complete the unification within the same block, only the
+		// last burn completes it and the NFT goes to its beneficiary
+		assert_ok!(NftFractionalization::unify_partial(
+			RuntimeOrigin::signed(account(3)),
+			0,
+			0,
+			0,
+			account(3),
+			250,
+		));
+		assert_eq!(Nfts::owner(0, 0), Some(account(1)));
+		assert_ok!(NftFractionalization::unify_partial(
+			RuntimeOrigin::signed(account(2)),
+			0,
+			0,
+			0,
+			account(2),
+			500,
+		));
+		assert_eq!(Nfts::owner(0, 0), Some(account(2)));
+
+		// the NFT has already been released
+		assert_noop!(
+			NftFractionalization::unify_partial(
+				RuntimeOrigin::signed(account(3)),
+				0,
+				0,
+				0,
+				account(3),
+				250,
+			),
+			Error::<Test>::NftNotFractionalized
+		);
+		assert_noop!(
+			NftFractionalization::unify(RuntimeOrigin::signed(account(3)), 0, 0, 0, account(3),),
+			Error::<Test>::NftNotFractionalized
+		);
+		assert_noop!(
+			NftFractionalization::cancel_partial_unification(
+				RuntimeOrigin::signed(account(2)),
+				0,
+				0,
+				0,
+			),
+			Error::<Test>::NftNotFractionalized
+		);
+	});
+}
+
+#[test]
+fn cancel_partial_unification_should_work() {
+	new_test_ext().execute_with(|| {
+		fractionalize_nft(1000);
+
+		assert_noop!(
+			NftFractionalization::cancel_partial_unification(
+				RuntimeOrigin::signed(account(2)),
+				0,
+				0,
+				0,
+			),
+			Error::<Test>::NoPartialUnification
+		);
+
+		assert_ok!(NftFractionalization::unify_partial(
+			RuntimeOrigin::signed(account(3)),
+			0,
+			0,
+			0,
+			account(3),
+			200,
+		));
+		assert_ok!(NftFractionalization::unify_partial(
+			RuntimeOrigin::signed(account(2)),
+			0,
+			0,
+			0,
+			account(2),
+			200,
+		));
+		assert_ok!(NftFractionalization::unify_partial(
+			RuntimeOrigin::signed(account(1)),
+			0,
+			0,
+			0,
+			account(1),
+			100,
+		));
+
+		// of the equal contributions, the earliest one is the largest
+		assert_noop!(
+			NftFractionalization::cancel_partial_unification(
+				RuntimeOrigin::signed(account(2)),
+				0,
+				0,
+				0,
+			),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			NftFractionalization::cancel_partial_unification(
+				RuntimeOrigin::signed(account(3)),
+				0,
+				0,
+				1,
+			),
+			Error::<Test>::IncorrectAssetId
+		);
+
+		assert_ok!(NftFractionalization::cancel_partial_unification(
+			RuntimeOrigin::signed(account(3)),
+			0,
+			0,
+			0,
+		));
+
+		assert_eq!(Assets::balance(0, account(1)), 250);
+		assert_eq!(Assets::balance(0, account(2)), 500);
+		assert_eq!(Assets::balance(0, account(3)), 250);
+		assert!(!PartialUnification::<Test>::contains_key((0, 0)));
+		assert!(!PartialUnificationContributors::<Test>::contains_key((0, 0)));
+		assert!(events().contains(&Event::<Test>::PartialUnificationCancelled {
+			nft_collection: 0,
+			nft: 0,
+			asset: 0,
+			reclaimed: 500,
+		}));
+
+		// the full unification is possible again
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(account(2)), 0, account(1), 500));
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(account(3)), 0, account(1), 250));
+		assert_ok!(NftFractionalization::unify(
+			RuntimeOrigin::signed(account(1)),
+			0,
+			0,
+			0,
+			account(1),
+		));
+		assert_eq!(Nfts::owner(0, 0), Some(account(1)));
+	});
+}
+
+parameter_types! {
+	pub const VotedCollection: u32 = 0;
+	pub const VotedNft: u32 = 0;
+}
+
+type Votes = FractionVotingWeight<Test, VotedCollection, VotedNft>;
+
+#[test]
+fn governance_weight_should_follow_fraction_holdings() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(NftFractionalization::governance_weight(&account(2), 0, 0), 0);
+		assert_eq!(Votes::total_issuance(), 0);
+		assert_eq!(Votes::balance(&account(2)), 0);
+
+		fractionalize_nft(1000);
+
+		assert_eq!(NftFractionalization::governance_weight(&account(1), 0, 0), 250);
+		assert_eq!(NftFractionalization::governance_weight(&account(2), 0, 0), 500);
+		assert_eq!(NftFractionalization::governance_weight(&account(2), 0, 1), 0);
+		assert_eq!(Votes::total_issuance(), 1000);
+		assert_eq!(Votes::balance(&account(2)), 500);
+
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(account(2)), 0, account(3), 100));
+		assert_eq!(NftFractionalization::governance_weight(&account(2), 0, 0), 400);
+		assert_eq!(NftFractionalization::governance_weight(&account(3), 0, 0), 350);
+		assert_eq!(Votes::balance(&account(3)), 350);
+	});
+}
+
+#[test]
+fn fraction_voting_weight_freezes_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Votes::set_freeze(b"vote____", &account(2), 100),
+			Error::<Test>::NftNotFractionalized
+		);
+		assert!(!Votes::can_freeze(b"vote____", &account(2)));
+
+		fractionalize_nft(1000);
+
+		assert_ok!(Votes::set_freeze(b"vote____", &account(2), 300));
+		assert_ok!(Votes::extend_freeze(b"vote____", &account(2), 200));
+		assert_ok!(Votes::extend_freeze(b"delegate", &account(2), 400));
+		assert_eq!(Votes::balance_frozen(b"vote____", &account(2)), 300);
+		assert_eq!(Votes::balance_frozen(b"delegate", &account(2)), 400);
+
+		// the largest freeze, along with the minimum balance, is enforced on transfers and burns
+		assert_noop!(
+			Assets::transfer(RuntimeOrigin::signed(account(2)), 0, account(3), 100),
+			pallet_assets::Error::<Test>::BalanceLow
+		);
+		assert_noop!(
+			NftFractionalization::unify_partial(
+				RuntimeOrigin::signed(account(2)),
+				0,
+				0,
+				0,
+				account(2),
+				100,
+			),
+			DispatchError::Token(FundsUnavailable)
+		);
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(account(2)), 0, account(3), 99));
+
+		// the number of freezes is bounded
+		assert!(!Votes::can_freeze(b"other___", &account(2)));
+		assert!(Votes::can_freeze(b"vote____", &account(2)));
+		assert_noop!(Votes::set_freeze(b"other___", &account(2), 1), Error::<Test>::TooManyFreezes);
+
+		assert_ok!(Votes::thaw(b"delegate", &account(2)));
+		assert_ok!(Votes::set_freeze(b"vote____", &account(2), 0));
+		assert!(!Freezes::<Test>::contains_key(0, account(2)));
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(account(2)), 0, account(3), 400));
+	});
+}
+
+#[test]
+fn fractionalize_batch_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::set_balance(&account(1), 100);
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			account(1),
+			CollectionConfig::default()
+		));
+		for nft_id in 0..3 {
+			assert_ok!(Nfts::mint(RuntimeOrigin::signed(account(1)), 0, nft_id, account(1), None));
+		}
+
+		assert_ok!(NftFractionalization::fractionalize_batch(
+			RuntimeOrigin::signed(account(1)),
+			bounded_vec![
+				(0, 0, 0, account(1), 1000),
+				(0, 1, 1, account(2), 500),
+				(0, 2, 2, account(3), 100),
+			],
+		));
+
+		assert_eq!(assets(), vec![0, 1, 2]);
+		assert_eq!(Assets::balance(0, account(1)), 1000);
+		assert_eq!(Assets::balance(1, account(2)), 500);
+		assert_eq!(Assets::balance(2, account(3)), 100);
+		assert_eq!(Balances::total_balance_on_hold(&account(1)), 3);
+		assert_eq!(NftToAsset::<Test>::get((0, 1)).unwrap().asset, 1);
+		assert_eq!(
+			events(),
+			vec![
+				Event::<Test>::NftFractionalized {
+					nft_collection: 0,
+					nft: 0,
+					fractions: 1000,
+					asset: 0,
+					beneficiary: account(1),
+				},
+				Event::<Test>::NftFractionalized {
+					nft_collection: 0,
+					nft: 1,
+					fractions: 500,
+					asset: 1,
+					beneficiary: account(2),
+				},
+				Event::<Test>::NftFractionalized {
+					nft_collection: 0,
+					nft: 2,
+					fractions: 100,
+					asset: 2,
+					beneficiary: account(3),
+				},
+			]
+		);
+	});
+}
+
+#[test]
+fn fractionalize_batch_should_roll_back_on_failure() {
+	new_test_ext().execute_with(|| {
+		Balances::set_balance(&account(1), 100);
+		Balances::set_balance(&account(2), 100);
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			account(1),
+			CollectionConfig::default()
+		));
+		assert_ok!(Nfts::mint(RuntimeOrigin::signed(account(1)), 0, 0, account(1), None));
+		assert_ok!(Nfts::mint(RuntimeOrigin::signed(account(1)), 0, 1, account(2), None));
+
+		// the second NFT is not owned by the origin
+		assert_noop!(
+			NftFractionalization::fractionalize_batch(
+				RuntimeOrigin::signed(account(1)),
+				bounded_vec![(0, 0, 0, account(1), 1000), (0, 1, 1, account(1), 1000)],
+			),
+			Error::<Test>::NoPermission
+		);
+		// the second item fractionalizes the same NFT again
+		assert_noop!(
+			NftFractionalization::fractionalize_batch(
+				RuntimeOrigin::signed(account(2)),
+				bounded_vec![(0, 1, 1, account(2), 1000), (0, 1, 2, account(2), 1000)],
+			),
+			DispatchError::Module(ModuleError {
+				index: 4,
+				error: [12, 0, 0, 0],
+				message: Some("ItemLocked")
+			})
+		);
+
+		assert_eq!(assets(), Vec::<u32>::new());
+		assert_eq!(Balances::total_balance_on_hold(&account(1)), 0);
+		assert_ok!(Nfts::transfer(RuntimeOrigin::signed(account(1)), 0, 0, account(3)));
+	});
+}
+
+#[test]
+fn fractionalize_batch_should_respect_max_batch_size() {
+	new_test_ext().execute_with(|| {
+		let items: Vec<FractionalizeArgsOf<Test>> =
+			(0..4).map(|id| (0, id, id, account(1), 1000)).collect();
+		assert!(BoundedVec::<_, <Test as Config>::MaxBatchSize>::try_from(items.clone()).is_err());
+
+		// a call with too many items can't even be decoded
+		let call = (1u8, 7u8, &items[..3]).encode();
+		assert!(RuntimeCall::decode(&mut &call[..]).is_ok());
+		let call = (1u8, 7u8, items).encode();
+		assert!(RuntimeCall::decode(&mut &call[..]).is_err());
+	});
+}
+
+#[test]
+fn fractionalize_batch_weight_should_not_exceed_individual_calls() {
+	type Weights = weights::SubstrateWeight<Test>;
+	for n in 1..=<Test as Config>::MaxBatchSize::get() {
+		assert!(Weights::fractionalize_batch(n)
+			.all_lte(Weights::fractionalize().saturating_mul(n.into())));
+	}
+}
+
+#[test]
+fn original_owner_should_be_recorded_until_unification() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		fractionalize_nft(1000);
+		assert_eq!(NftFractionalization::original_owner(0, 0), Some((account(1), 5)));
+
+		// the transfers of the fractions don't change the snapshot
+		System::set_block_number(6);
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(account(1)), 0, account(2), 250));
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(account(3)), 0, account(2), 250));
+		assert_eq!(Assets::balance(0, account(2)), 1000);
+		assert_eq!(NftFractionalization::original_owner(0, 0), Some((account(1), 5)));
+
+		assert_ok!(NftFractionalization::unify(
+			RuntimeOrigin::signed(account(2)),
+			0,
+			0,
+			0,
+			account(2),
+		));
+		assert_eq!(NftFractionalization::original_owner(0, 0), None);
+
+		// the new owner is recorded on the next fractionalization
+		System::set_block_number(7);
+		assert_ok!(NftFractionalization::fractionalize(
+			RuntimeOrigin::signed(account(2)),
+			0,
+			0,
+			1,
+			account(3),
+			1000,
+		));
+		assert_eq!(NftFractionalization::original_owner(0, 0), Some((account(2), 7)));
+		assert_ok!(NftFractionalization::unify_partial(
+			RuntimeOrigin::signed(account(3)),
+			0,
+			0,
+			1,
+			account(3),
+			1000,
+		));
+		assert!(!OriginalOwner::<Test>::contains_key((0, 0)));
+	});
+}
+
+#[test]
+fn burn_fractions_should_work() {
+	new_test_ext().execute_with(|| {
+		fractionalize_nft(1000);
+
+		assert_noop!(
+			NftFractionalization::burn_fractions(RuntimeOrigin::signed(account(2)), 0, 1, 100),
+			Error::<Test>::NftNotFractionalized
+		);
+		assert_noop!(
+			NftFractionalization::burn_fractions(RuntimeOrigin::signed(account(2)), 0, 0, 0),
+			Error::<Test>::InvalidFractionAmount
+		);
+		assert_noop!(
+			NftFractionalization::burn_fractions(RuntimeOrigin::signed(account(2)), 0, 0, 501),
+			DispatchError::Token(FundsUnavailable)
+		);
+
+		assert_ok!(NftFractionalization::burn_fractions(
+			RuntimeOrigin::signed(account(2)),
+			0,
+			0,
+			100
+		));
+		assert_eq!(Assets::balance(0, account(2)), 400);
+		assert_eq!(Assets::total_issuance(0), 900);
+		assert_eq!(TotalBurned::<Test>::get((0, 0)), 100);
+		assert_eq!(
+			events().last(),
+			Some(&Event::<Test>::FractionsDestroyed {
+				nft_collection: 0,
+				nft: 0,
+				amount: 100,
+				burner: account(2),
+			})
+		);
+
+		// the NFT is still locked
+		assert_eq!(Nfts::owner(0, 0), Some(account(1)));
+
+		// the burned fractions aren't required to unify the NFT
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(account(1)), 0, account(2), 250));
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(account(3)), 0, account(2), 250));
+		assert_ok!(NftFractionalization::unify(
+			RuntimeOrigin::signed(account(2)),
+			0,
+			0,
+			0,
+			account(2),
+		));
+		assert_eq!(Nfts::owner(0, 0), Some(account(2)));
+		assert!(!TotalBurned::<Test>::contains_key((0, 0)));
+	});
+}
+
+#[test]
+fn burn_fractions_should_unify_nft_for_the_last_holder() {
+	new_test_ext().execute_with(|| {
+		fractionalize_nft(1000);
+
+		assert_ok!(NftFractionalization::burn_fractions(
+			RuntimeOrigin::signed(account(2)),
+			0,
+			0,
+			500
+		));
+		assert_ok!(NftFractionalization::burn_fractions(
+			RuntimeOrigin::signed(account(3)),
+			0,
+			0,
+			250
+		));
+		assert_eq!(Nfts::owner(0, 0), Some(account(1)));
+		assert_eq!(TotalBurned::<Test>::get((0, 0)), 750);
+
+		assert_ok!(NftFractionalization::burn_fractions(
+			RuntimeOrigin::signed(account(1)),
+			0,
+			0,
+			50
+		));
+
+		assert_eq!(Nfts::owner(0, 0), Some(account(1)));
+		assert_eq!(Assets::balance(0, account(1)), 0);
+		assert_eq!(Balances::total_balance_on_hold(&account(1)), 0);
+		assert!(!NftToAsset::<Test>::contains_key((0, 0)));
+		assert!(!TotalBurned::<Test>::contains_key((0, 0)));
+
+		let events = events();
+		assert!(events.contains(&Event::<Test>::FractionsDestroyed {
+			nft_collection: 0,
+			nft: 0,
+			amount: 50,
+			burner: account(1),
+		}));
+		assert_eq!(
+			events.last(),
+			Some(&Event::<Test>::NftUnified {
+				nft_collection: 0,
+				nft: 0,
+				asset: 0,
+				beneficiary: account(1),
+			})
+		);
+	});
+}
+
+#[test]
+fn burn_fractions_should_be_tracked_per_nft() {
+	new_test_ext().execute_with(|| {
+		fractionalize_nft(1000);
+		assert_ok!(Nfts::mint(RuntimeOrigin::signed(account(1)), 0, 1, account(1), None));
+		assert_ok!(NftFractionalization::fractionalize(
+			RuntimeOrigin::signed(account(1)),
+			0,
+			1,
+			1,
+			account(2),
+			100,
+		));
+
+		assert_ok!(NftFractionalization::burn_fractions(
+			RuntimeOrigin::signed(account(2)),
+			0,
+			0,
+			100
+		));
+		assert_ok!(NftFractionalization::burn_fractions(
+			RuntimeOrigin::signed(account(2)),
+			0,
+			1,
+			10
+		));
+
+		assert_eq!(TotalBurned::<Test>::get((0, 0)), 100);
+		assert_eq!(TotalBurned::<Test>::get((0, 1)), 10);
+		assert_eq!(Assets::balance(0, account(2)), 400);
+		assert_eq!(Assets::balance(1, account(2)), 90);
+	});
+}
+
+#[test]
+fn transfer_fractions_should_auto_unify_for_the_single_holder() {
+	new_test_ext().execute_with(|| {
+		AutoUnify::set(true);
+		// `transfer_fractions` doesn't rely on the transfer callback of the assets pallet
+		TransferCallbackEnabled::set(false);
+		fractionalize_nft(1000);
+
+		assert_noop!(
+			NftFractionalization::transfer_fractions(
+				RuntimeOrigin::signed(account(1)),
+				0,
+				1,
+				account(2),
+				100,
+			),
+			Error::<Test>::NftNotFractionalized
+		);
+
+		// partial holders don't unify the NFT
+		assert_ok!(NftFractionalization::transfer_fractions(
+			RuntimeOrigin::signed(account(1)),
+			0,
+			0,
+			account(2),
+			250,
+		));
+		assert_eq!(Assets::balance(0, account(2)), 750);
+		assert_eq!(Nfts::owner(0, 0), Some(account(1)));
+		assert!(NftToAsset::<Test>::contains_key((0, 0)));
+
+		assert_ok!(NftFractionalization::transfer_fractions(
+			RuntimeOrigin::signed(account(3)),
+			0,
+			0,
+			account(2),
+			250,
+		));
+
+		assert_eq!(Nfts::owner(0, 0), Some(account(2)));
+		assert_eq!(Assets::balance(0, account(2)), 0);
+		assert_eq!(Balances::total_balance_on_hold(&account(1)), 0);
+		assert!(!NftToAsset::<Test>::contains_key((0, 0)));
+		assert!(!OriginalOwner::<Test>::contains_key((0, 0)));
+		assert_eq!(
+			events().last(),
+			Some(&Event::<Test>::NftUnified {
+				nft_collection: 0,
+				nft: 0,
+				asset: 0,
+				beneficiary: account(2),
+			})
+		);
+	});
+}
+
+#[test]
+fn transfer_fractions_should_not_auto_unify_unless_enabled() {
+	new_test_ext().execute_with(|| {
+		fractionalize_nft(1000);
+
+		assert_ok!(NftFractionalization::transfer_fractions(
+			RuntimeOrigin::signed(account(1)),
+			0,
+			0,
+			account(2),
+			250,
+		));
+		assert_ok!(NftFractionalization::transfer_fractions(
+			RuntimeOrigin::signed(account(3)),
+			0,
+			0,
+			account(2),
+			250,
+		));
+
+		assert_eq!(Assets::balance(0, account(2)), 1000);
+		assert_eq!(Nfts::owner(0, 0), Some(account(1)));
+		assert!(NftToAsset::<Test>::contains_key((0, 0)));
+	});
+}
+
+#[test]
+fn auto_unify_should_account_for_burned_fractions() {
+	new_test_ext().execute_with(|| {
+		AutoUnify::set(true);
+		fractionalize_nft(1000);
+
+		assert_ok!(NftFractionalization::burn_fractions(
+			RuntimeOrigin::signed(account(3)),
+			0,
+			0,
+			100
+		));
+
+		assert_ok!(NftFractionalization::transfer_fractions(
+			RuntimeOrigin::signed(account(3)),
+			0,
+			0,
+			account(2),
+			150,
+		));
+		assert_eq!(Nfts::owner(0, 0), Some(account(1)));
+
+		assert_ok!(NftFractionalization::transfer_fractions(
+			RuntimeOrigin::signed(account(1)),
+			0,
+			0,
+			account(2),
+			250,
+		));
+		assert_eq!(Nfts::owner(0, 0), Some(account(2)));
+		assert!(!TotalBurned::<Test>::contains_key((0, 0)));
+	});
+}
+
+#[test]
+fn assets_transfer_should_auto_unify_for_the_single_holder() {
+	new_test_ext().execute_with(|| {
+		AutoUnify::set(true);
+		fractionalize_nft(1000);
+		assert_eq!(AssetToNft::<Test>::get(0), Some((0, 0)));
+
+		// the fractions, transferred through the assets pallet, are picked up by its callback
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(account(3)), 0, account(2), 250));
+		assert_eq!(Nfts::owner(0, 0), Some(account(1)));
+
+		assert_ok!(<Assets as Mutate<_>>::transfer(0, &account(1), &account(2), 250, Expendable));
+		assert_eq!(Nfts::owner(0, 0), Some(account(2)));
+		assert_eq!(Assets::balance(0, account(2)), 0);
+		assert!(!NftToAsset::<Test>::contains_key((0, 0)));
+		assert!(!AssetToNft::<Test>::contains_key(0));
+		assert_eq!(
+			events().last(),
+			Some(&Event::<Test>::NftUnified {
+				nft_collection: 0,
+				nft: 0,
+				asset: 0,
+				beneficiary: account(2),
+			})
+		);
+	});
+}
+
+fn fractionalize_nft_with_royalty(fractions: u64, royalty: Perbill) {
+	Balances::set_balance(&account(1), 100);
+	Balances::set_balance(&account(2), 100);
+	Balances::set_balance(&account(4), 100);
+
+	assert_ok!(Nfts::force_create(RuntimeOrigin::root(), account(1), CollectionConfig::default()));
+	assert_ok!(Nfts::mint(RuntimeOrigin::signed(account(1)), 0, 0, account(1), None));
+	assert_ok!(NftFractionalization::fractionalize_with_royalty(
+		RuntimeOrigin::signed(account(1)),
+		0,
+		0,
+		0,
+		account(1),
+		fractions,
+		royalty,
+		account(4),
+	));
+}
+
+#[test]
+fn fractionalize_with_royalty_should_deduct_royalty_on_transfer() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			NftFractionalization::fractionalize_with_royalty(
+				RuntimeOrigin::signed(account(1)),
+				0,
+				0,
+				0,
+				account(1),
+				1000,
+				Perbill::one(),
+				account(4),
+			),
+			Error::<Test>::InvalidRoyalty
+		);
+
+		fractionalize_nft_with_royalty(1000, Perbill::from_percent(10));
+		assert_eq!(
+			RoyaltyConfig::<Test>::get((0, 0)),
+			Some((account(4), Perbill::from_percent(10)))
+		);
+
+		assert_ok!(NftFractionalization::transfer_fractions(
+			RuntimeOrigin::signed(account(1)),
+			0,
+			0,
+			account(2),
+			100,
+		));
+		assert_eq!(Assets::balance(0, account(1)), 900);
+		assert_eq!(Assets::balance(0, account(2)), 90);
+		assert_eq!(Assets::balance(0, account(4)), 10);
+		assert_eq!(
+			events().last(),
+			Some(&Event::<Test>::RoyaltyPaid {
+				nft_collection: 0,
+				nft: 0,
+				receiver: account(4),
+				amount: 10,
+			})
+		);
+
+		// the receiver doesn't pay the royalty to itself
+		assert_ok!(NftFractionalization::transfer_fractions(
+			RuntimeOrigin::signed(account(4)),
+			0,
+			0,
+			account(2),
+			10,
+		));
+		assert_eq!(Assets::balance(0, account(2)), 100);
+		assert_eq!(Assets::balance(0, account(4)), 0);
+
+		// transfers made directly through the assets pallet are charged by the transfer callback
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(account(2)), 0, account(1), 100));
+		assert_eq!(Assets::balance(0, account(2)), 0);
+		assert_eq!(Assets::balance(0, account(1)), 990);
+		assert_eq!(Assets::balance(0, account(4)), 10);
+		assert!(!PendingRoyalties::<Test>::contains_key((0, 0)));
+	});
+}
+
+#[test]
+fn transfer_fractions_should_charge_royalty_without_transfer_callback() {
+	new_test_ext().execute_with(|| {
+		TransferCallbackEnabled::set(false);
+		fractionalize_nft_with_royalty(1000, Perbill::from_percent(10));
+
+		assert_ok!(NftFractionalization::transfer_fractions(
+			RuntimeOrigin::signed(account(1)),
+			0,
+			0,
+			account(2),
+			100,
+		));
+		assert_eq!(Assets::balance(0, account(2)), 90);
+		assert_eq!(Assets::balance(0, account(4)), 10);
+
+		// only the pallet charges the royalty without the transfer callback
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(account(2)), 0, account(1), 90));
+		assert_eq!(Assets::balance(0, account(1)), 990);
+		assert_eq!(Assets::balance(0, account(4)), 10);
+	});
+}
+
+#[test]
+fn small_royalties_should_accumulate_until_min_payout() {
+	new_test_ext().execute_with(|| {
+		fractionalize_nft_with_royalty(1000, Perbill::from_percent(10));
+		let pallet_account = NftFractionalization::get_pallet_account();
+
+		for _ in 0..2 {
+			assert_ok!(NftFractionalization::transfer_fractions(
+				RuntimeOrigin::signed(account(1)),
+				0,
+				0,
+				account(2),
+				20,
+			));
+		}
+		assert_eq!(PendingRoyalties::<Test>::get((0, 0)), 4);
+		assert_eq!(Assets::balance(0, &pallet_account), 4);
+		assert_eq!(Assets::balance(0, account(4)), 0);
+		assert_eq!(Assets::balance(0, account(2)), 36);
+		assert!(!events().iter().any(|e| matches!(e, Event::<Test>::RoyaltyPaid { .. })));
+
+		// the accumulated royalties are swept along with the one reaching the min payout
+		assert_ok!(NftFractionalization::transfer_fractions(
+			RuntimeOrigin::signed(account(1)),
+			0,
+			0,
+			account(2),
+			20,
+		));
+		assert!(!PendingRoyalties::<Test>::contains_key((0, 0)));
+		assert_eq!(Assets::balance(0, &pallet_account), 0);
+		assert_eq!(Assets::balance(0, account(4)), 6);
+		assert_eq!(Assets::balance(0, account(2)), 54);
+		assert_eq!(
+			events().last(),
+			Some(&Event::<Test>::RoyaltyPaid {
+				nft_collection: 0,
+				nft: 0,
+				receiver: account(4),
+				amount: 6,
+			})
+		);
+	});
+}
+
+#[test]
+fn pending_royalties_should_be_burned_on_unification() {
+	new_test_ext().execute_with(|| {
+		AutoUnify::set(true);
+		fractionalize_nft_with_royalty(1000, Perbill::from_percent(10));
+
+		assert_ok!(NftFractionalization::transfer_fractions(
+			RuntimeOrigin::signed(account(1)),
+			0,
+			0,
+			account(2),
+			20,
+		));
+		assert_eq!(PendingRoyalties::<Test>::get((0, 0)), 2);
+
+		// the holder of all the fractions, but the pending royalties, gets the NFT
+		assert_ok!(NftFractionalization::transfer_fractions(
+			RuntimeOrigin::signed(account(2)),
+			0,
+			0,
+			account(1),
+			18,
+		));
+		assert_eq!(Nfts::owner(0, 0), Some(account(1)));
+		assert!(!NftToAsset::<Test>::contains_key((0, 0)));
+		assert!(!RoyaltyConfig::<Test>::contains_key((0, 0)));
+		assert!(!PendingRoyalties::<Test>::contains_key((0, 0)));
+		assert_eq!(Assets::total_supply(0), 0);
+	});
+}
+
+#[test]
+fn zero_royalty_should_not_be_stored() {
+	new_test_ext().execute_with(|| {
+		fractionalize_nft_with_royalty(1000, Perbill::zero());
+		assert!(!RoyaltyConfig::<Test>::contains_key((0, 0)));
+
+		assert_ok!(NftFractionalization::transfer_fractions(
+			RuntimeOrigin::signed(account(1)),
+			0,
+			0,
+			account(2),
+			100,
+		));
+		assert_eq!(Assets::balance(0, account(2)), 100);
+		assert_eq!(Assets::balance(0, account(4)), 0);
+		assert!(!PendingRoyalties::<Test>::contains_key((0, 0)));
+	});
+}
+
+fn offer_and_pledge_loan() {
+	fractionalize_nft(1000);
+	assert_ok!(NftFractionalization::offer_loan(
+		RuntimeOrigin::signed(account(3)),
+		0,
+		0,
+		account(2),
+		300,
+		50,
+		10,
+	));
+	assert_ok!(NftFractionalization::pledge_fractions_as_collateral(
+		RuntimeOrigin::signed(account(2)),
+		0,
+		0,
+		300,
+		account(3),
+		50,
+		10,
+	));
+}
+
+#[test]
+fn pledge_fractions_as_collateral_should_work() {
+	new_test_ext().execute_with(|| {
+		fractionalize_nft(1000);
+
+		// the lender has to offer the loan first
+		assert_noop!(
+			NftFractionalization::pledge_fractions_as_collateral(
+				RuntimeOrigin::signed(account(2)),
+				0,
+				0,
+				300,
+				account(3),
+				50,
+				10,
+			),
+			Error::<Test>::NoLoanOffer
+		);
+		assert_ok!(NftFractionalization::offer_loan(
+			RuntimeOrigin::signed(account(3)),
+			0,
+			0,
+			account(2),
+			300,
+			50,
+			10,
+		));
+		assert_eq!(Balances::balance_on_hold(&HoldReason::LoanOffer.into(), &account(3)), 50);
+		assert_eq!(Balances::balance(&account(3)), 50);
+		assert_noop!(
+			NftFractionalization::pledge_fractions_as_collateral(
+				RuntimeOrigin::signed(account(2)),
+				0,
+				0,
+				300,
+				account(3),
+				60,
+				10,
+			),
+			Error::<Test>::NoLoanOffer
+		);
+
+		assert_ok!(NftFractionalization::pledge_fractions_as_collateral(
+			RuntimeOrigin::signed(account(2)),
+			0,
+			0,
+			300,
+			account(3),
+			50,
+			10,
+		));
+		assert_eq!(Balances::balance(&account(2)), 150);
+		assert_eq!(Balances::balance(&account(3)), 50);
+		assert_eq!(Balances::balance_on_hold(&HoldReason::LoanOffer.into(), &account(3)), 0);
+		assert!(!LoanOffers::<Test>::contains_key((0, 0, account(2))));
+		assert_eq!(
+			ActiveLoans::<Test>::get((0, 0, account(2))),
+			Some(LoanRecord { lender: account(3), fractions: 300, loan_amount: 50, repay_by: 10 })
+		);
+		assert_eq!(
+			events().last(),
+			Some(&Event::<Test>::FractionsPledged {
+				nft_collection: 0,
+				nft: 0,
+				borrower: account(2),
+				lender: account(3),
+				fractions: 300,
+				loan_amount: 50,
+				repay_by: 10,
+			})
+		);
+
+		// the pledged fractions can't be moved
+		assert_noop!(
+			NftFractionalization::transfer_fractions(
+				RuntimeOrigin::signed(account(2)),
+				0,
+				0,
+				account(1),
+				201,
+			),
+			Error::<Test>::FractionsFrozen
+		);
+		assert_noop!(
+			Assets::transfer(RuntimeOrigin::signed(account(2)), 0, account(1), 200),
+			pallet_assets::Error::<Test>::BalanceLow
+		);
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(account(2)), 0, account(1), 199));
+
+		// a single loan per NFT is active for the borrower
+		assert_ok!(NftFractionalization::offer_loan(
+			RuntimeOrigin::signed(account(1)),
+			0,
+			0,
+			account(2),
+			1,
+			1,
+			10,
+		));
+		assert_noop!(
+			NftFractionalization::pledge_fractions_as_collateral(
+				RuntimeOrigin::signed(account(2)),
+				0,
+				0,
+				1,
+				account(1),
+				1,
+				10,
+			),
+			Error::<Test>::LoanAlreadyActive
+		);
+	});
+}
+
+#[test]
+fn cancel_loan_offer_should_release_held_funds() {
+	new_test_ext().execute_with(|| {
+		fractionalize_nft(1000);
+		assert_ok!(NftFractionalization::offer_loan(
+			RuntimeOrigin::signed(account(3)),
+			0,
+			0,
+			account(2),
+			300,
+			50,
+			10,
+		));
+
+		// another lender can't replace the offer
+		assert_noop!(
+			NftFractionalization::offer_loan(
+				RuntimeOrigin::signed(account(1)),
+				0,
+				0,
+				account(2),
+				300,
+				40,
+				10,
+			),
+			Error::<Test>::LoanAlreadyOffered
+		);
+
+		// the replaced offer releases the funds of its lender
+		assert_ok!(NftFractionalization::offer_loan(
+			RuntimeOrigin::signed(account(3)),
+			0,
+			0,
+			account(2),
+			300,
+			40,
+			10,
+		));
+		assert_eq!(Balances::balance_on_hold(&HoldReason::LoanOffer.into(), &account(3)), 40);
+
+		assert_noop!(
+			NftFractionalization::cancel_loan_offer(
+				RuntimeOrigin::signed(account(1)),
+				0,
+				0,
+				account(2),
+			),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(NftFractionalization::cancel_loan_offer(
+			RuntimeOrigin::signed(account(3)),
+			0,
+			0,
+			account(2),
+		));
+		assert_eq!(Balances::balance_on_hold(&HoldReason::LoanOffer.into(), &account(3)), 0);
+		assert!(!LoanOffers::<Test>::contains_key((0, 0, account(2))));
+		assert_eq!(
+			events().last(),
+			Some(&Event::<Test>::LoanOfferCancelled {
+				nft_collection: 0,
+				nft: 0,
+				lender: account(3),
+				borrower: account(2),
+			})
+		);
+
+		assert_noop!(
+			NftFractionalization::pledge_fractions_as_collateral(
+				RuntimeOrigin::signed(account(2)),
+				0,
+				0,
+				300,
+				account(3),
+				40,
+				10,
+			),
+			Error::<Test>::NoLoanOffer
+		);
+	});
+}
+
+#[test]
+fn repay_loan_should_release_collateral() {
+	new_test_ext().execute_with(|| {
+		offer_and_pledge_loan();
+		assert_noop!(
+			NftFractionalization::repay_loan(RuntimeOrigin::signed(account(1)), 0, 0),
+			Error::<Test>::NoActiveLoan
+		);
+
+		assert_ok!(NftFractionalization::repay_loan(RuntimeOrigin::signed(account(2)), 0, 0));
+		assert_eq!(Balances::balance(&account(2)), 100);
+		assert_eq!(Balances::balance(&account(3)), 100);
+		assert!(!ActiveLoans::<Test>::contains_key((0, 0, account(2))));
+		assert!(!Freezes::<Test>::contains_key(0, account(2)));
+		assert_eq!(
+			events().last(),
+			Some(&Event::<Test>::LoanRepaid {
+				nft_collection: 0,
+				nft: 0,
+				borrower: account(2),
+				lender: account(3),
+			})
+		);
+
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(account(2)), 0, account(1), 500));
+		// the repaid loan can't be liquidated
+		System::set_block_number(11);
+		assert_noop!(
+			NftFractionalization::liquidate_fraction_collateral(
+				RuntimeOrigin::signed(account(3)),
+				0,
+				0,
+				account(2),
+			),
+			Error::<Test>::NoActiveLoan
+		);
+	});
+}
+
+#[test]
+fn liquidate_fraction_collateral_should_work_once_loan_is_overdue() {
+	new_test_ext().execute_with(|| {
+		offer_and_pledge_loan();
+		// the collateral also backs the votes of the borrower
+		assert_ok!(Votes::set_freeze(b"vote____", &account(2), 400));
+
+		System::set_block_number(11);
+		assert_noop!(
+			NftFractionalization::liquidate_fraction_collateral(
+				RuntimeOrigin::signed(account(1)),
+				0,
+				0,
+				account(2),
+			),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(NftFractionalization::liquidate_fraction_collateral(
+			RuntimeOrigin::signed(account(3)),
+			0,
+			0,
+			account(2),
+		));
+
+		assert_eq!(Assets::balance(0, account(2)), 200);
+		assert_eq!(Assets::balance(0, account(3)), 550);
+		assert_eq!(Balances::balance(&account(2)), 150);
+		assert!(!ActiveLoans::<Test>::contains_key((0, 0, account(2))));
+		assert_eq!(Votes::balance_frozen(&COLLATERAL_FREEZE_ID, &account(2)), 0);
+		assert_eq!(Votes::balance_frozen(b"vote____", &account(2)), 400);
+		assert_eq!(
+			events().last(),
+			Some(&Event::<Test>::FractionCollateralLiquidated {
+				nft_collection: 0,
+				nft: 0,
+				borrower: account(2),
+				lender: account(3),
+				fractions: 300,
+			})
+		);
+	});
+}
+
+#[test]
+fn liquidate_fraction_collateral_should_not_work_before_expiry() {
+	new_test_ext().execute_with(|| {
+		offer_and_pledge_loan();
+
+		System::set_block_number(10);
+		assert_noop!(
+			NftFractionalization::liquidate_fraction_collateral(
+				RuntimeOrigin::signed(account(3)),
+				0,
+				0,
+				account(2),
+			),
+			Error::<Test>::LoanNotExpired
+		);
+
+		// overdue loans may still be repaid until liquidated
+		System::set_block_number(11);
+		assert_ok!(NftFractionalization::repay_loan(RuntimeOrigin::signed(account(2)), 0, 0));
+		assert_eq!(Assets::balance(0, account(3)), 250);
+
+		// expired offers can't be taken
+		assert_noop!(
+			NftFractionalization::offer_loan(
+				RuntimeOrigin::signed(account(3)),
+				0,
+				0,
+				account(2),
+				300,
+				50,
+				11,
+			),
+			Error::<Test>::LoanExpired
+		);
+	});
+}
@@ -20,8 +20,10 @@
 use super::*;
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::traits::{fungible::Inspect as FunInspect, fungibles::Inspect};
+use frame_system::pallet_prelude::BlockNumberFor;
 use scale_info::TypeInfo;
-use sp_runtime::traits::StaticLookup;
+use sp_runtime::{traits::StaticLookup, RuntimeDebug};
+use xcm_executor::traits::QueryHandler;
 
 pub type AssetIdOf<T> = <<T as Config>::Assets as Inspect<<T as SystemConfig>::AccountId>>::AssetId;
 pub type AssetBalanceOf<T> =
@@ -29,6 +31,17 @@ pub type AssetBalanceOf<T> =
 pub type DepositOf<T> =
 	<<T as Config>::Currency as FunInspect<<T as SystemConfig>::AccountId>>::Balance;
 pub type AccountIdLookupOf<T> = <<T as SystemConfig>::Lookup as StaticLookup>::Source;
+pub type QueryIdOf<T> = <<T as Config>::XcmQueries as QueryHandler>::QueryId;
+pub type LoanRecordOf<T> =
+	LoanRecord<<T as SystemConfig>::AccountId, AssetBalanceOf<T>, DepositOf<T>, BlockNumberFor<T>>;
+/// The arguments of `fractionalize` for a single item of `fractionalize_batch`.
+pub type FractionalizeArgsOf<T> = (
+	<T as Config>::NftCollectionId,
+	<T as Config>::NftId,
+	AssetIdOf<T>,
+	AccountIdLookupOf<T>,
+	AssetBalanceOf<T>,
+);
 
 /// Stores the details of a fractionalized item.
 #[derive(Decode, Encode, Default, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
@@ -46,6 +59,36 @@ pub struct Details<AssetId, Fractions, Deposit, AccountId> {
 	pub asset_creator: AccountId,
 }
 
+/// A cross-chain unification, waiting for the remote chain to confirm that the fractions are
+/// burned.
+#[derive(Decode, Encode, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+pub struct PendingUnification<AccountId, QueryId, BlockNumber> {
+	/// Account that will receive the unified item.
+	pub beneficiary: AccountId,
+
+	/// Query, expecting the confirmation from the remote chain.
+	pub query_id: QueryId,
+
+	/// Block after which the unification may be abandoned, if not confirmed.
+	pub deadline: BlockNumber,
+}
+
+/// A loan, backed by the fractions of its borrower.
+#[derive(Clone, Decode, Encode, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct LoanRecord<AccountId, Fractions, Balance, BlockNumber> {
+	/// Account that lent the funds.
+	pub lender: AccountId,
+
+	/// Fractions, pledged as the collateral of the loan.
+	pub fractions: Fractions,
+
+	/// Amount of the lent funds.
+	pub loan_amount: Balance,
+
+	/// Block after which the lender may confiscate the collateral, unless the loan is repaid.
+	pub repay_by: BlockNumber,
+}
+
 /// Benchmark Helper
 #[cfg(feature = "runtime-benchmarks")]
 pub trait BenchmarkHelper<AssetId, CollectionId, ItemId> {
@@ -55,6 +98,11 @@ pub trait BenchmarkHelper<AssetId, CollectionId, ItemId> {
 	fn collection(id: u32) -> CollectionId;
 	/// Returns an nft id from a given integer.
 	fn nft(id: u32) -> ItemId;
+	/// Returns a beneficiary on another chain, which `XcmSender` delivers the fractions to, or
+	/// `None` if the cross-chain fractionalization can't be benchmarked.
+	fn remote_beneficiary() -> Option<xcm::latest::Location> {
+		None
+	}
 }
 
 #[cfg(feature = "runtime-benchmarks")]
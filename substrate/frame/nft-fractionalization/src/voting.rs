@@ -0,0 +1,201 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Voting weight of the fraction holders.
+//!
+//! [`FractionVotingWeight`] exposes the fractions of a single NFT as a fungible asset which can be
+//! frozen, so that the fraction holdings may back the votes in the governance of the NFT.
+//!
+//! Nothing here is integrated with any governance pallet automatically. The runtime has to pass
+//! [`FractionVotingWeight`] as the currency of the governance pallet of its choice, and set this
+//! pallet as the `Freezer` of the assets pallet for the freezes to be enforced.
+
+use super::*;
+use frame_support::traits::{
+	fungible,
+	fungibles::Inspect as InspectFungibles,
+	tokens::{DepositConsequence, Fortitude, Preservation, Provenance, WithdrawConsequence},
+	Get, LockIdentifier,
+};
+use pallet_assets::FrozenBalance;
+use sp_runtime::{traits::Zero, DispatchResult};
+use sp_std::marker::PhantomData;
+
+/// The fractions of the NFT `Nft` in the collection `Collection`, viewed as a fungible asset.
+///
+/// The balance of an account is its voting weight in the governance of the NFT. Balances may be
+/// frozen with [`fungible::MutateFreeze`], which is how the governance pallets lock the voting
+/// funds. All the methods treat an NFT which is not fractionalized as an unknown asset.
+pub struct FractionVotingWeight<T, Collection, Nft>(PhantomData<(T, Collection, Nft)>);
+
+impl<T, Collection, Nft> FractionVotingWeight<T, Collection, Nft>
+where
+	T: Config,
+	Collection: Get<T::NftCollectionId>,
+	Nft: Get<T::NftId>,
+{
+	/// The asset, minted on fractionalization of the NFT.
+	fn asset() -> Option<AssetIdOf<T>> {
+		NftToAsset::<T>::get((Collection::get(), Nft::get())).map(|details| details.asset)
+	}
+
+	/// Set the amount frozen under `id` to the result of `amount`, given the current one.
+	fn update_freeze(
+		id: &LockIdentifier,
+		who: &T::AccountId,
+		amount: impl FnOnce(AssetBalanceOf<T>) -> AssetBalanceOf<T>,
+	) -> DispatchResult {
+		let asset = Self::asset().ok_or(Error::<T>::NftNotFractionalized)?;
+		Pallet::<T>::update_freeze(asset, id, who, amount)
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Set the amount of the fractions of `who`, frozen under `id`, to the result of `amount`,
+	/// given the current one.
+	pub(crate) fn update_freeze(
+		asset: AssetIdOf<T>,
+		id: &LockIdentifier,
+		who: &T::AccountId,
+		amount: impl FnOnce(AssetBalanceOf<T>) -> AssetBalanceOf<T>,
+	) -> DispatchResult {
+		Freezes::<T>::try_mutate_exists(asset, who, |maybe_freezes| {
+			let mut freezes = maybe_freezes.take().unwrap_or_default();
+			match freezes.iter_mut().find(|(freeze_id, _)| freeze_id == id) {
+				Some((_, frozen)) => *frozen = amount(*frozen),
+				None => {
+					let amount = amount(Zero::zero());
+					if !amount.is_zero() {
+						freezes.try_push((*id, amount)).map_err(|_| Error::<T>::TooManyFreezes)?;
+					}
+				},
+			}
+			freezes.retain(|(_, frozen)| !frozen.is_zero());
+			if !freezes.is_empty() {
+				*maybe_freezes = Some(freezes);
+			}
+			Ok(())
+		})
+	}
+}
+
+impl<T, Collection, Nft> fungible::Inspect<T::AccountId>
+	for FractionVotingWeight<T, Collection, Nft>
+where
+	T: Config,
+	Collection: Get<T::NftCollectionId>,
+	Nft: Get<T::NftId>,
+{
+	type Balance = AssetBalanceOf<T>;
+
+	fn total_issuance() -> Self::Balance {
+		Self::asset().map_or_else(Zero::zero, T::Assets::total_issuance)
+	}
+	fn minimum_balance() -> Self::Balance {
+		Self::asset().map_or_else(Zero::zero, T::Assets::minimum_balance)
+	}
+	fn total_balance(who: &T::AccountId) -> Self::Balance {
+		Self::asset().map_or_else(Zero::zero, |asset| T::Assets::total_balance(asset, who))
+	}
+	fn balance(who: &T::AccountId) -> Self::Balance {
+		Self::asset().map_or_else(Zero::zero, |asset| T::Assets::balance(asset, who))
+	}
+	fn reducible_balance(
+		who: &T::AccountId,
+		preservation: Preservation,
+		force: Fortitude,
+	) -> Self::Balance {
+		Self::asset().map_or_else(Zero::zero, |asset| {
+			T::Assets::reducible_balance(asset, who, preservation, force)
+		})
+	}
+	fn can_deposit(
+		who: &T::AccountId,
+		amount: Self::Balance,
+		provenance: Provenance,
+	) -> DepositConsequence {
+		Self::asset().map_or(DepositConsequence::UnknownAsset, |asset| {
+			T::Assets::can_deposit(asset, who, amount, provenance)
+		})
+	}
+	fn can_withdraw(
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> WithdrawConsequence<Self::Balance> {
+		Self::asset().map_or(WithdrawConsequence::UnknownAsset, |asset| {
+			T::Assets::can_withdraw(asset, who, amount)
+		})
+	}
+}
+
+impl<T, Collection, Nft> fungible::InspectFreeze<T::AccountId>
+	for FractionVotingWeight<T, Collection, Nft>
+where
+	T: Config,
+	Collection: Get<T::NftCollectionId>,
+	Nft: Get<T::NftId>,
+{
+	type Id = LockIdentifier;
+
+	fn balance_frozen(id: &Self::Id, who: &T::AccountId) -> Self::Balance {
+		Self::asset()
+			.and_then(|asset| {
+				Freezes::<T>::get(asset, who)
+					.into_iter()
+					.find(|(freeze_id, _)| freeze_id == id)
+					.map(|(_, frozen)| frozen)
+			})
+			.unwrap_or_else(Zero::zero)
+	}
+	fn can_freeze(id: &Self::Id, who: &T::AccountId) -> bool {
+		Self::asset().map_or(false, |asset| {
+			let freezes = Freezes::<T>::get(asset, who);
+			!freezes.is_full() || freezes.iter().any(|(freeze_id, _)| freeze_id == id)
+		})
+	}
+}
+
+impl<T, Collection, Nft> fungible::MutateFreeze<T::AccountId>
+	for FractionVotingWeight<T, Collection, Nft>
+where
+	T: Config,
+	Collection: Get<T::NftCollectionId>,
+	Nft: Get<T::NftId>,
+{
+	fn set_freeze(id: &Self::Id, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		Self::update_freeze(id, who, |_| amount)
+	}
+	fn extend_freeze(id: &Self::Id, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(())
+		}
+		Self::update_freeze(id, who, |frozen| frozen.max(amount))
+	}
+	fn thaw(id: &Self::Id, who: &T::AccountId) -> DispatchResult {
+		Self::update_freeze(id, who, |_| Zero::zero())
+	}
+}
+
+impl<T: Config> FrozenBalance<AssetIdOf<T>, T::AccountId, AssetBalanceOf<T>> for Pallet<T> {
+	fn frozen_balance(asset: AssetIdOf<T>, who: &T::AccountId) -> Option<AssetBalanceOf<T>> {
+		Freezes::<T>::get(asset, who).into_iter().map(|(_, frozen)| frozen).max()
+	}
+
+	fn died(asset: AssetIdOf<T>, who: &T::AccountId) {
+		Freezes::<T>::remove(asset, who)
+	}
+}
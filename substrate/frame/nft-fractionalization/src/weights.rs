@@ -23,6 +23,14 @@
 //! HOSTNAME: `runner-e8ezs4ez-project-145-concurrent-0`, CPU: `Intel(R) Xeon(R) CPU @ 2.60GHz`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
 
+// The weights of `unify_partial`, `cancel_partial_unification`, `fractionalize_cross_chain`,
+// `unify_cross_chain`, `finish_cross_chain_unification`, `fractionalize_batch`, `burn_fractions`,
+// `transfer_fractions`, `fractionalize_with_royalty`, `offer_loan`,
+// `pledge_fractions_as_collateral`, `repay_loan`, `liquidate_fraction_collateral`,
+// `cancel_loan_offer`, `transfer_callback` have not been generated by the benchmark CLI yet. They are
+// estimated from the weights of similar calls and the storage accesses of their benchmarks and must
+// be replaced by the output of the command below.
+
 // Executed Command:
 // ./target/production/substrate
 // benchmark
@@ -54,6 +62,21 @@ use core::marker::PhantomData;
 pub trait WeightInfo {
 	fn fractionalize() -> Weight;
 	fn unify() -> Weight;
+	fn unify_partial() -> Weight;
+	fn cancel_partial_unification(c: u32, ) -> Weight;
+	fn fractionalize_cross_chain() -> Weight;
+	fn unify_cross_chain() -> Weight;
+	fn finish_cross_chain_unification() -> Weight;
+	fn fractionalize_batch(n: u32, ) -> Weight;
+	fn burn_fractions() -> Weight;
+	fn transfer_fractions() -> Weight;
+	fn fractionalize_with_royalty() -> Weight;
+	fn offer_loan() -> Weight;
+	fn pledge_fractions_as_collateral() -> Weight;
+	fn repay_loan() -> Weight;
+	fn liquidate_fraction_collateral() -> Weight;
+	fn cancel_loan_offer() -> Weight;
+	fn transfer_callback() -> Weight;
 }
 
 /// Weights for pallet_nft_fractionalization using the Substrate node and recommended hardware.
@@ -119,6 +142,247 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(9_u64))
 			.saturating_add(T::DbWeight::get().writes(10_u64))
 	}
+	/// Storage: NftFractionalization NftToAsset (r:1 w:1)
+	/// Proof: NftFractionalization NftToAsset (max_values: None, max_size: Some(92), added: 2567, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization PartialUnification (r:1 w:1)
+	/// Proof: NftFractionalization PartialUnification (max_values: None, max_size: Some(40), added: 2515, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization PartialUnificationContributors (r:1 w:1)
+	/// Proof: NftFractionalization PartialUnificationContributors (max_values: None, max_size: Some(513), added: 2988, mode: MaxEncodedLen)
+	fn unify_partial() -> Weight {
+		// Not generated yet, estimated as `unify` plus the partial unification bookkeeping.
+		Weight::from_parts(150_000_000, 4326)
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(12_u64))
+	}
+	/// Storage: NftFractionalization NftToAsset (r:1 w:0)
+	/// Proof: NftFractionalization NftToAsset (max_values: None, max_size: Some(92), added: 2567, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization PartialUnificationContributors (r:1 w:1)
+	/// Proof: NftFractionalization PartialUnificationContributors (max_values: None, max_size: Some(513), added: 2988, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization PartialUnification (r:1 w:1)
+	/// Proof: NftFractionalization PartialUnification (max_values: None, max_size: Some(40), added: 2515, mode: MaxEncodedLen)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Proof: Assets Asset (max_values: None, max_size: Some(210), added: 2685, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:10 w:10)
+	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// The range of component `c` is `[1, 10]`.
+	fn cancel_partial_unification(c: u32, ) -> Weight {
+		// Not generated yet, estimated as minting the fractions back to every contributor.
+		Weight::from_parts(20_000_000, 3978)
+			.saturating_add(Weight::from_parts(25_000_000, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(c.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(c.into())))
+			.saturating_add(Weight::from_parts(0, 2609).saturating_mul(c.into()))
+	}
+	/// Storage: NftFractionalization CrossChainFractionalizationLock (r:0 w:1)
+	/// Proof: NftFractionalization CrossChainFractionalizationLock (max_values: None, max_size: Some(617), added: 3092, mode: MaxEncodedLen)
+	fn fractionalize_cross_chain() -> Weight {
+		// Not generated yet, estimated as `fractionalize` plus sending the fractions over XCM.
+		Weight::from_parts(230_000_000, 4326)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	/// Storage: NftFractionalization NftToAsset (r:1 w:0)
+	/// Proof: NftFractionalization NftToAsset (max_values: None, max_size: Some(92), added: 2567, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization CrossChainFractionalizationLock (r:1 w:0)
+	/// Proof: NftFractionalization CrossChainFractionalizationLock (max_values: None, max_size: Some(617), added: 3092, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization PendingCrossChainUnification (r:1 w:1)
+	/// Proof: NftFractionalization PendingCrossChainUnification (max_values: None, max_size: Some(97), added: 2572, mode: MaxEncodedLen)
+	fn unify_cross_chain() -> Weight {
+		// Not generated yet, estimated as registering the query and sending the burn request over XCM.
+		Weight::from_parts(60_000_000, 4082)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: NftFractionalization PendingCrossChainUnification (r:1 w:1)
+	/// Proof: NftFractionalization PendingCrossChainUnification (max_values: None, max_size: Some(97), added: 2572, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization CrossChainFractionalizationLock (r:0 w:1)
+	/// Proof: NftFractionalization CrossChainFractionalizationLock (max_values: None, max_size: Some(617), added: 3092, mode: MaxEncodedLen)
+	fn finish_cross_chain_unification() -> Weight {
+		// Not generated yet, estimated as `unify` plus taking the XCM response.
+		Weight::from_parts(160_000_000, 4326)
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(13_u64))
+	}
+	/// Storage: Nfts Item (r:1 w:0)
+	/// Proof: Nfts Item (max_values: None, max_size: Some(861), added: 3336, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(85), added: 2560, mode: MaxEncodedLen)
+	/// Storage: Nfts Collection (r:1 w:1)
+	/// Proof: Nfts Collection (max_values: None, max_size: Some(84), added: 2559, mode: MaxEncodedLen)
+	/// Storage: Nfts Attribute (r:1 w:1)
+	/// Proof: Nfts Attribute (max_values: None, max_size: Some(446), added: 2921, mode: MaxEncodedLen)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Proof: Assets Asset (max_values: None, max_size: Some(210), added: 2685, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:1 w:1)
+	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Assets Metadata (r:1 w:1)
+	/// Proof: Assets Metadata (max_values: None, max_size: Some(140), added: 2615, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization NftToAsset (r:0 w:1)
+	/// Proof: NftFractionalization NftToAsset (max_values: None, max_size: Some(92), added: 2567, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 10]`.
+	fn fractionalize_batch(n: u32, ) -> Weight {
+		// Not generated yet, estimated as `fractionalize` per item, sharing the dispatch overhead.
+		Weight::from_parts(10_000_000, 990)
+			.saturating_add(Weight::from_parts(180_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads((8_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((8_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 3336).saturating_mul(n.into()))
+	}
+	/// Storage: NftFractionalization NftToAsset (r:1 w:1)
+	/// Proof: NftFractionalization NftToAsset (max_values: None, max_size: Some(92), added: 2567, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization TotalBurned (r:1 w:1)
+	/// Proof: NftFractionalization TotalBurned (max_values: None, max_size: Some(40), added: 2515, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization PartialUnification (r:1 w:0)
+	/// Proof: NftFractionalization PartialUnification (max_values: None, max_size: Some(40), added: 2515, mode: MaxEncodedLen)
+	fn burn_fractions() -> Weight {
+		// Not generated yet, estimated as `unify` plus the burned fractions bookkeeping.
+		Weight::from_parts(150_000_000, 4326)
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(11_u64))
+	}
+	/// Storage: NftFractionalization NftToAsset (r:1 w:1)
+	/// Proof: NftFractionalization NftToAsset (max_values: None, max_size: Some(92), added: 2567, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization PartialUnification (r:1 w:0)
+	/// Proof: NftFractionalization PartialUnification (max_values: None, max_size: Some(40), added: 2515, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization RoyaltyConfig (r:1 w:0)
+	/// Proof: NftFractionalization RoyaltyConfig (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization PendingRoyalties (r:1 w:1)
+	/// Proof: NftFractionalization PendingRoyalties (max_values: None, max_size: Some(40), added: 2515, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization Freezes (r:1 w:0)
+	/// Proof: NftFractionalization Freezes (max_values: None, max_size: Some(277), added: 2752, mode: MaxEncodedLen)
+	fn transfer_fractions() -> Weight {
+		// Not generated yet, estimated as a royalty payout and an asset transfer, followed by a possible
+		// `unify`.
+		Weight::from_parts(245_000_000, 8817)
+			.saturating_add(T::DbWeight::get().reads(18_u64))
+			.saturating_add(T::DbWeight::get().writes(16_u64))
+	}
+	/// Storage: Nfts Item (r:1 w:0)
+	/// Proof: Nfts Item (max_values: None, max_size: Some(861), added: 3336, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(85), added: 2560, mode: MaxEncodedLen)
+	/// Storage: Nfts Collection (r:1 w:1)
+	/// Proof: Nfts Collection (max_values: None, max_size: Some(84), added: 2559, mode: MaxEncodedLen)
+	/// Storage: Nfts Attribute (r:1 w:1)
+	/// Proof: Nfts Attribute (max_values: None, max_size: Some(446), added: 2921, mode: MaxEncodedLen)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Proof: Assets Asset (max_values: None, max_size: Some(210), added: 2685, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:1 w:1)
+	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Assets Metadata (r:1 w:1)
+	/// Proof: Assets Metadata (max_values: None, max_size: Some(140), added: 2615, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization NftToAsset (r:0 w:1)
+	/// Proof: NftFractionalization NftToAsset (max_values: None, max_size: Some(92), added: 2567, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization RoyaltyConfig (r:0 w:1)
+	/// Proof: NftFractionalization RoyaltyConfig (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	fn fractionalize_with_royalty() -> Weight {
+		// Not generated yet, estimated as `fractionalize` plus the royalty configuration.
+		Weight::from_parts(195_000_000, 4326)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	/// Storage: NftFractionalization NftToAsset (r:1 w:0)
+	/// Proof: NftFractionalization NftToAsset (max_values: None, max_size: Some(92), added: 2567, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization LoanOffers (r:1 w:1)
+	/// Proof: NftFractionalization LoanOffers (max_values: None, max_size: Some(140), added: 2615, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:2 w:2)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(157), added: 2632, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn offer_loan() -> Weight {
+		// Not generated yet, estimated as a hold, replacing the hold of the previous offer.
+		Weight::from_parts(60_000_000, 6254)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: NftFractionalization NftToAsset (r:1 w:0)
+	/// Proof: NftFractionalization NftToAsset (max_values: None, max_size: Some(92), added: 2567, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization ActiveLoans (r:1 w:1)
+	/// Proof: NftFractionalization ActiveLoans (max_values: None, max_size: Some(140), added: 2615, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization LoanOffers (r:1 w:1)
+	/// Proof: NftFractionalization LoanOffers (max_values: None, max_size: Some(140), added: 2615, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:1 w:0)
+	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization Freezes (r:1 w:1)
+	/// Proof: NftFractionalization Freezes (max_values: None, max_size: Some(277), added: 2752, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(157), added: 2632, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn pledge_fractions_as_collateral() -> Weight {
+		// Not generated yet, estimated as a freeze and a transfer of the held funds.
+		Weight::from_parts(85_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: NftFractionalization ActiveLoans (r:1 w:1)
+	/// Proof: NftFractionalization ActiveLoans (max_values: None, max_size: Some(140), added: 2615, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization NftToAsset (r:1 w:0)
+	/// Proof: NftFractionalization NftToAsset (max_values: None, max_size: Some(92), added: 2567, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization Freezes (r:1 w:1)
+	/// Proof: NftFractionalization Freezes (max_values: None, max_size: Some(277), added: 2752, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn repay_loan() -> Weight {
+		// Not generated yet, estimated as a thaw and a balance transfer.
+		Weight::from_parts(70_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: NftFractionalization ActiveLoans (r:1 w:1)
+	/// Proof: NftFractionalization ActiveLoans (max_values: None, max_size: Some(140), added: 2615, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization NftToAsset (r:1 w:0)
+	/// Proof: NftFractionalization NftToAsset (max_values: None, max_size: Some(92), added: 2567, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization Freezes (r:1 w:1)
+	/// Proof: NftFractionalization Freezes (max_values: None, max_size: Some(277), added: 2752, mode: MaxEncodedLen)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Proof: Assets Asset (max_values: None, max_size: Some(210), added: 2685, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	fn liquidate_fraction_collateral() -> Weight {
+		// Not generated yet, estimated as an asset transfer with the freezes of the borrower lifted.
+		Weight::from_parts(90_000_000, 6208)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: NftFractionalization LoanOffers (r:1 w:1)
+	/// Proof: NftFractionalization LoanOffers (max_values: None, max_size: Some(140), added: 2615, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(157), added: 2632, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn cancel_loan_offer() -> Weight {
+		// Not generated yet, estimated as a release of the held funds.
+		Weight::from_parts(40_000_000, 3622)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: NftFractionalization AssetToNft (r:1 w:0)
+	/// Proof: NftFractionalization AssetToNft (max_values: None, max_size: Some(44), added: 2519, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization RoyaltyConfig (r:1 w:0)
+	/// Proof: NftFractionalization RoyaltyConfig (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization PendingRoyalties (r:1 w:1)
+	/// Proof: NftFractionalization PendingRoyalties (max_values: None, max_size: Some(40), added: 2515, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:3 w:3)
+	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization NftToAsset (r:1 w:1)
+	/// Proof: NftFractionalization NftToAsset (max_values: None, max_size: Some(92), added: 2567, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization PartialUnification (r:1 w:0)
+	/// Proof: NftFractionalization PartialUnification (max_values: None, max_size: Some(40), added: 2515, mode: MaxEncodedLen)
+	fn transfer_callback() -> Weight {
+		// Not generated yet, estimated as `transfer_fractions` without the transfer of the fractions.
+		Weight::from_parts(215_000_000, 8817)
+			.saturating_add(T::DbWeight::get().reads(17_u64))
+			.saturating_add(T::DbWeight::get().writes(15_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -183,4 +447,245 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(9_u64))
 			.saturating_add(RocksDbWeight::get().writes(10_u64))
 	}
+	/// Storage: NftFractionalization NftToAsset (r:1 w:1)
+	/// Proof: NftFractionalization NftToAsset (max_values: None, max_size: Some(92), added: 2567, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization PartialUnification (r:1 w:1)
+	/// Proof: NftFractionalization PartialUnification (max_values: None, max_size: Some(40), added: 2515, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization PartialUnificationContributors (r:1 w:1)
+	/// Proof: NftFractionalization PartialUnificationContributors (max_values: None, max_size: Some(513), added: 2988, mode: MaxEncodedLen)
+	fn unify_partial() -> Weight {
+		// Not generated yet, estimated as `unify` plus the partial unification bookkeeping.
+		Weight::from_parts(150_000_000, 4326)
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(12_u64))
+	}
+	/// Storage: NftFractionalization NftToAsset (r:1 w:0)
+	/// Proof: NftFractionalization NftToAsset (max_values: None, max_size: Some(92), added: 2567, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization PartialUnificationContributors (r:1 w:1)
+	/// Proof: NftFractionalization PartialUnificationContributors (max_values: None, max_size: Some(513), added: 2988, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization PartialUnification (r:1 w:1)
+	/// Proof: NftFractionalization PartialUnification (max_values: None, max_size: Some(40), added: 2515, mode: MaxEncodedLen)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Proof: Assets Asset (max_values: None, max_size: Some(210), added: 2685, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:10 w:10)
+	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// The range of component `c` is `[1, 10]`.
+	fn cancel_partial_unification(c: u32, ) -> Weight {
+		// Not generated yet, estimated as minting the fractions back to every contributor.
+		Weight::from_parts(20_000_000, 3978)
+			.saturating_add(Weight::from_parts(25_000_000, 0).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(c.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(c.into())))
+			.saturating_add(Weight::from_parts(0, 2609).saturating_mul(c.into()))
+	}
+	/// Storage: NftFractionalization CrossChainFractionalizationLock (r:0 w:1)
+	/// Proof: NftFractionalization CrossChainFractionalizationLock (max_values: None, max_size: Some(617), added: 3092, mode: MaxEncodedLen)
+	fn fractionalize_cross_chain() -> Weight {
+		// Not generated yet, estimated as `fractionalize` plus sending the fractions over XCM.
+		Weight::from_parts(230_000_000, 4326)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	/// Storage: NftFractionalization NftToAsset (r:1 w:0)
+	/// Proof: NftFractionalization NftToAsset (max_values: None, max_size: Some(92), added: 2567, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization CrossChainFractionalizationLock (r:1 w:0)
+	/// Proof: NftFractionalization CrossChainFractionalizationLock (max_values: None, max_size: Some(617), added: 3092, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization PendingCrossChainUnification (r:1 w:1)
+	/// Proof: NftFractionalization PendingCrossChainUnification (max_values: None, max_size: Some(97), added: 2572, mode: MaxEncodedLen)
+	fn unify_cross_chain() -> Weight {
+		// Not generated yet, estimated as registering the query and sending the burn request over XCM.
+		Weight::from_parts(60_000_000, 4082)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: NftFractionalization PendingCrossChainUnification (r:1 w:1)
+	/// Proof: NftFractionalization PendingCrossChainUnification (max_values: None, max_size: Some(97), added: 2572, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization CrossChainFractionalizationLock (r:0 w:1)
+	/// Proof: NftFractionalization CrossChainFractionalizationLock (max_values: None, max_size: Some(617), added: 3092, mode: MaxEncodedLen)
+	fn finish_cross_chain_unification() -> Weight {
+		// Not generated yet, estimated as `unify` plus taking the XCM response.
+		Weight::from_parts(160_000_000, 4326)
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(13_u64))
+	}
+	/// Storage: Nfts Item (r:1 w:0)
+	/// Proof: Nfts Item (max_values: None, max_size: Some(861), added: 3336, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(85), added: 2560, mode: MaxEncodedLen)
+	/// Storage: Nfts Collection (r:1 w:1)
+	/// Proof: Nfts Collection (max_values: None, max_size: Some(84), added: 2559, mode: MaxEncodedLen)
+	/// Storage: Nfts Attribute (r:1 w:1)
+	/// Proof: Nfts Attribute (max_values: None, max_size: Some(446), added: 2921, mode: MaxEncodedLen)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Proof: Assets Asset (max_values: None, max_size: Some(210), added: 2685, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:1 w:1)
+	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Assets Metadata (r:1 w:1)
+	/// Proof: Assets Metadata (max_values: None, max_size: Some(140), added: 2615, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization NftToAsset (r:0 w:1)
+	/// Proof: NftFractionalization NftToAsset (max_values: None, max_size: Some(92), added: 2567, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 10]`.
+	fn fractionalize_batch(n: u32, ) -> Weight {
+		// Not generated yet, estimated as `fractionalize` per item, sharing the dispatch overhead.
+		Weight::from_parts(10_000_000, 990)
+			.saturating_add(Weight::from_parts(180_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads((8_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((8_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 3336).saturating_mul(n.into()))
+	}
+	/// Storage: NftFractionalization NftToAsset (r:1 w:1)
+	/// Proof: NftFractionalization NftToAsset (max_values: None, max_size: Some(92), added: 2567, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization TotalBurned (r:1 w:1)
+	/// Proof: NftFractionalization TotalBurned (max_values: None, max_size: Some(40), added: 2515, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization PartialUnification (r:1 w:0)
+	/// Proof: NftFractionalization PartialUnification (max_values: None, max_size: Some(40), added: 2515, mode: MaxEncodedLen)
+	fn burn_fractions() -> Weight {
+		// Not generated yet, estimated as `unify` plus the burned fractions bookkeeping.
+		Weight::from_parts(150_000_000, 4326)
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(11_u64))
+	}
+	/// Storage: NftFractionalization NftToAsset (r:1 w:1)
+	/// Proof: NftFractionalization NftToAsset (max_values: None, max_size: Some(92), added: 2567, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization PartialUnification (r:1 w:0)
+	/// Proof: NftFractionalization PartialUnification (max_values: None, max_size: Some(40), added: 2515, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization RoyaltyConfig (r:1 w:0)
+	/// Proof: NftFractionalization RoyaltyConfig (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization PendingRoyalties (r:1 w:1)
+	/// Proof: NftFractionalization PendingRoyalties (max_values: None, max_size: Some(40), added: 2515, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization Freezes (r:1 w:0)
+	/// Proof: NftFractionalization Freezes (max_values: None, max_size: Some(277), added: 2752, mode: MaxEncodedLen)
+	fn transfer_fractions() -> Weight {
+		// Not generated yet, estimated as a royalty payout and an asset transfer, followed by a possible
+		// `unify`.
+		Weight::from_parts(245_000_000, 8817)
+			.saturating_add(RocksDbWeight::get().reads(18_u64))
+			.saturating_add(RocksDbWeight::get().writes(16_u64))
+	}
+	/// Storage: Nfts Item (r:1 w:0)
+	/// Proof: Nfts Item (max_values: None, max_size: Some(861), added: 3336, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(85), added: 2560, mode: MaxEncodedLen)
+	/// Storage: Nfts Collection (r:1 w:1)
+	/// Proof: Nfts Collection (max_values: None, max_size: Some(84), added: 2559, mode: MaxEncodedLen)
+	/// Storage: Nfts Attribute (r:1 w:1)
+	/// Proof: Nfts Attribute (max_values: None, max_size: Some(446), added: 2921, mode: MaxEncodedLen)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Proof: Assets Asset (max_values: None, max_size: Some(210), added: 2685, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:1 w:1)
+	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Assets Metadata (r:1 w:1)
+	/// Proof: Assets Metadata (max_values: None, max_size: Some(140), added: 2615, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization NftToAsset (r:0 w:1)
+	/// Proof: NftFractionalization NftToAsset (max_values: None, max_size: Some(92), added: 2567, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization RoyaltyConfig (r:0 w:1)
+	/// Proof: NftFractionalization RoyaltyConfig (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	fn fractionalize_with_royalty() -> Weight {
+		// Not generated yet, estimated as `fractionalize` plus the royalty configuration.
+		Weight::from_parts(195_000_000, 4326)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	/// Storage: NftFractionalization NftToAsset (r:1 w:0)
+	/// Proof: NftFractionalization NftToAsset (max_values: None, max_size: Some(92), added: 2567, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization LoanOffers (r:1 w:1)
+	/// Proof: NftFractionalization LoanOffers (max_values: None, max_size: Some(140), added: 2615, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:2 w:2)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(157), added: 2632, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn offer_loan() -> Weight {
+		// Not generated yet, estimated as a hold, replacing the hold of the previous offer.
+		Weight::from_parts(60_000_000, 6254)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: NftFractionalization NftToAsset (r:1 w:0)
+	/// Proof: NftFractionalization NftToAsset (max_values: None, max_size: Some(92), added: 2567, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization ActiveLoans (r:1 w:1)
+	/// Proof: NftFractionalization ActiveLoans (max_values: None, max_size: Some(140), added: 2615, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization LoanOffers (r:1 w:1)
+	/// Proof: NftFractionalization LoanOffers (max_values: None, max_size: Some(140), added: 2615, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:1 w:0)
+	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization Freezes (r:1 w:1)
+	/// Proof: NftFractionalization Freezes (max_values: None, max_size: Some(277), added: 2752, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(157), added: 2632, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn pledge_fractions_as_collateral() -> Weight {
+		// Not generated yet, estimated as a freeze and a transfer of the held funds.
+		Weight::from_parts(85_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: NftFractionalization ActiveLoans (r:1 w:1)
+	/// Proof: NftFractionalization ActiveLoans (max_values: None, max_size: Some(140), added: 2615, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization NftToAsset (r:1 w:0)
+	/// Proof: NftFractionalization NftToAsset (max_values: None, max_size: Some(92), added: 2567, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization Freezes (r:1 w:1)
+	/// Proof: NftFractionalization Freezes (max_values: None, max_size: Some(277), added: 2752, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn repay_loan() -> Weight {
+		// Not generated yet, estimated as a thaw and a balance transfer.
+		Weight::from_parts(70_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: NftFractionalization ActiveLoans (r:1 w:1)
+	/// Proof: NftFractionalization ActiveLoans (max_values: None, max_size: Some(140), added: 2615, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization NftToAsset (r:1 w:0)
+	/// Proof: NftFractionalization NftToAsset (max_values: None, max_size: Some(92), added: 2567, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization Freezes (r:1 w:1)
+	/// Proof: NftFractionalization Freezes (max_values: None, max_size: Some(277), added: 2752, mode: MaxEncodedLen)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Proof: Assets Asset (max_values: None, max_size: Some(210), added: 2685, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	fn liquidate_fraction_collateral() -> Weight {
+		// Not generated yet, estimated as an asset transfer with the freezes of the borrower lifted.
+		Weight::from_parts(90_000_000, 6208)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: NftFractionalization LoanOffers (r:1 w:1)
+	/// Proof: NftFractionalization LoanOffers (max_values: None, max_size: Some(140), added: 2615, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(157), added: 2632, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn cancel_loan_offer() -> Weight {
+		// Not generated yet, estimated as a release of the held funds.
+		Weight::from_parts(40_000_000, 3622)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: NftFractionalization AssetToNft (r:1 w:0)
+	/// Proof: NftFractionalization AssetToNft (max_values: None, max_size: Some(44), added: 2519, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization RoyaltyConfig (r:1 w:0)
+	/// Proof: NftFractionalization RoyaltyConfig (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization PendingRoyalties (r:1 w:1)
+	/// Proof: NftFractionalization PendingRoyalties (max_values: None, max_size: Some(40), added: 2515, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:3 w:3)
+	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization NftToAsset (r:1 w:1)
+	/// Proof: NftFractionalization NftToAsset (max_values: None, max_size: Some(92), added: 2567, mode: MaxEncodedLen)
+	/// Storage: NftFractionalization PartialUnification (r:1 w:0)
+	/// Proof: NftFractionalization PartialUnification (max_values: None, max_size: Some(40), added: 2515, mode: MaxEncodedLen)
+	fn transfer_callback() -> Weight {
+		// Not generated yet, estimated as `transfer_fractions` without the transfer of the fractions.
+		Weight::from_parts(215_000_000, 8817)
+			.saturating_add(RocksDbWeight::get().reads(17_u64))
+			.saturating_add(RocksDbWeight::get().writes(15_u64))
+	}
 }
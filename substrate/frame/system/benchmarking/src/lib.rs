@@ -231,5 +231,27 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn freeze_account() {
+		let who: T::AccountId = account("who", 0, 0);
+
+		#[extrinsic_call]
+		freeze_account(RawOrigin::Root, who.clone());
+
+		assert!(frame_system::FrozenAccounts::<T>::contains_key(&who));
+	}
+
+	#[benchmark]
+	fn thaw_account() -> Result<(), BenchmarkError> {
+		let who: T::AccountId = account("who", 0, 0);
+		System::<T>::freeze_account(RawOrigin::Root.into(), who.clone())?;
+
+		#[extrinsic_call]
+		thaw_account(RawOrigin::Root, who.clone());
+
+		assert!(!frame_system::FrozenAccounts::<T>::contains_key(&who));
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
 }
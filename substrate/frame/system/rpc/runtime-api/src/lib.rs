@@ -23,6 +23,10 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+use alloc::vec::Vec;
+
 sp_api::decl_runtime_apis! {
 	/// The API to query account nonce.
 	pub trait AccountNonceApi<AccountId, Nonce> where
@@ -32,4 +36,13 @@ sp_api::decl_runtime_apis! {
 		/// Get current account nonce of given `AccountId`.
 		fn account_nonce(account: AccountId) -> Nonce;
 	}
+
+	/// The API to query the key-value metadata, annotating the blocks.
+	pub trait BlockMetadataApi<BlockNumber> where
+		BlockNumber: codec::Codec,
+	{
+		/// Get the metadata of the block `block_number` under the `key`. Only the metadata of the
+		/// block whose state is queried is available.
+		fn get_block_metadata(block_number: BlockNumber, key: Vec<u8>) -> Option<Vec<u8>>;
+	}
 }
@@ -0,0 +1,125 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Call, Config, Pallet};
+use codec::{Decode, Encode};
+use frame_support::{dispatch::DispatchInfo, traits::IsSubType, DefaultNoBound};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{DispatchInfoOf, Dispatchable, SignedExtension},
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+};
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// Check to ensure that the condition of
+/// [`execute_if_block_weight_below`](crate::Call::execute_if_block_weight_below) holds before the
+/// extrinsic is included in a block.
+///
+/// If the block weight is not below the threshold, the extrinsic is rejected with
+/// [`InvalidTransaction::ExhaustsResources`], so that it stays in the pool for a later block and
+/// no fee is paid. The check is skipped by the transaction pool validation, since the block weight
+/// is only known when the block is built.
+///
+/// Should come before [`CheckWeight`](crate::CheckWeight), so that the weight of the extrinsic
+/// itself is not counted.
+#[derive(Encode, Decode, DefaultNoBound, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckConditional<T>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> sp_std::fmt::Debug for CheckConditional<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "CheckConditional")
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config + Send + Sync> CheckConditional<T> {
+	/// Create new `SignedExtension` to check the condition of conditional calls.
+	pub fn new() -> Self {
+		Self(sp_std::marker::PhantomData)
+	}
+}
+
+impl<T: Config + Send + Sync> SignedExtension for CheckConditional<T>
+where
+	T::RuntimeCall: Dispatchable<Info = DispatchInfo> + IsSubType<Call<T>>,
+{
+	type AccountId = T::AccountId;
+	type Call = T::RuntimeCall;
+	type AdditionalSigned = ();
+	type Pre = ();
+	const IDENTIFIER: &'static str = "CheckConditional";
+
+	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn pre_dispatch(
+		self,
+		_who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		if let Some(Call::execute_if_block_weight_below { weight_threshold, .. }) =
+			call.is_sub_type()
+		{
+			if !Pallet::<T>::block_weight_below(*weight_threshold) {
+				return Err(InvalidTransaction::ExhaustsResources.into())
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, RuntimeCall, System, Test, CALL};
+	use frame_support::{assert_noop, assert_ok, dispatch::DispatchClass};
+	use sp_weights::Weight;
+
+	#[test]
+	fn conditional_call_is_rejected_in_heavy_block() {
+		new_test_ext().execute_with(|| {
+			let info = DispatchInfo::default();
+			let len = 0_usize;
+			let call: RuntimeCall = Call::execute_if_block_weight_below {
+				weight_threshold: Weight::from_parts(100, 100),
+				call: Box::new(CALL.clone()),
+			}
+			.into();
+			assert_ok!(CheckConditional::<Test>::new().pre_dispatch(&1, &call, &info, len));
+
+			System::register_extra_weight_unchecked(
+				Weight::from_parts(100, 0),
+				DispatchClass::Normal,
+			);
+			assert_noop!(
+				CheckConditional::<Test>::new().pre_dispatch(&1, &call, &info, len),
+				InvalidTransaction::ExhaustsResources
+			);
+			// other calls are not affected
+			assert_ok!(CheckConditional::<Test>::new().pre_dispatch(&1, CALL, &info, len));
+		})
+	}
+}
@@ -0,0 +1,127 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Config, FrozenAccounts};
+use codec::{Decode, Encode};
+use frame_support::{dispatch::DispatchInfo, DefaultNoBound};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{DispatchInfoOf, Dispatchable, SignedExtension},
+	transaction_validity::{
+		InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+	},
+};
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// The [`InvalidTransaction::Custom`] code of a transaction, signed by a frozen account.
+pub const FROZEN_ACCOUNT: u8 = 1;
+
+/// Check to ensure that the sender has not been frozen with
+/// [`freeze_account`](crate::Call::freeze_account).
+#[derive(Encode, Decode, DefaultNoBound, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckFrozenAccount<T>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> sp_std::fmt::Debug for CheckFrozenAccount<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "CheckFrozenAccount")
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config + Send + Sync> CheckFrozenAccount<T> {
+	/// Create new `SignedExtension` to check whether the sender is frozen.
+	pub fn new() -> Self {
+		Self(sp_std::marker::PhantomData)
+	}
+}
+
+impl<T: Config + Send + Sync> SignedExtension for CheckFrozenAccount<T>
+where
+	T::RuntimeCall: Dispatchable<Info = DispatchInfo>,
+{
+	type AccountId = T::AccountId;
+	type Call = T::RuntimeCall;
+	type AdditionalSigned = ();
+	type Pre = ();
+	const IDENTIFIER: &'static str = "CheckFrozenAccount";
+
+	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		self.validate(who, call, info, len).map(|_| ())
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		_call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		if FrozenAccounts::<T>::contains_key(who) {
+			return Err(InvalidTransaction::Custom(FROZEN_ACCOUNT).into())
+		}
+		Ok(ValidTransaction::default())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, RuntimeOrigin, SignedExtra, System, Test, CALL};
+	use frame_support::{assert_noop, assert_ok};
+
+	#[test]
+	fn frozen_account_is_rejected_until_thawed() {
+		new_test_ext().execute_with(|| {
+			let info = DispatchInfo::default();
+			let len = 0_usize;
+			let extra: SignedExtra = (CheckFrozenAccount::<Test>::new(),);
+			assert_ok!(extra.validate(&1, CALL, &info, len));
+
+			assert_ok!(System::freeze_account(RuntimeOrigin::root(), 1));
+			assert_noop!(
+				extra.validate(&1, CALL, &info, len),
+				InvalidTransaction::Custom(FROZEN_ACCOUNT)
+			);
+			assert_noop!(
+				extra.clone().pre_dispatch(&1, CALL, &info, len),
+				InvalidTransaction::Custom(FROZEN_ACCOUNT)
+			);
+			// other accounts are not affected
+			assert_ok!(extra.validate(&2, CALL, &info, len));
+
+			assert_ok!(System::thaw_account(RuntimeOrigin::root(), 1));
+			assert_ok!(extra.validate(&1, CALL, &info, len));
+			assert_ok!(extra.pre_dispatch(&1, CALL, &info, len));
+		})
+	}
+}
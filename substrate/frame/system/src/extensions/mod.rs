@@ -15,6 +15,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod check_conditional;
+pub mod check_frozen_account;
 pub mod check_genesis;
 pub mod check_mortality;
 pub mod check_non_zero_sender;
@@ -53,6 +53,11 @@
 //! - `authorize_upgrade_without_checks`: Authorize new runtime code and an upgrade sans
 //!   verification.
 //! - `apply_authorized_upgrade`: Provide new, already-authorized runtime code.
+//! - `freeze_account`: Reject all the transactions signed by an account.
+//! - `thaw_account`: Accept the transactions signed by a frozen account again.
+//! - `set_block_metadata`: Annotate the current block with some key-value data.
+//! - `execute_if_block_weight_below`: Dispatch a call only if the block is not congested.
+//! - `fill_nonce_gap`: Skip the nonce of a lost transaction.
 //!
 //! #### A Note on Upgrades
 //!
@@ -91,6 +96,8 @@
 //!     the transaction.
 //!   - [`CheckTxVersion`]: Checks that the transaction version is the same as the one used to sign
 //!     the transaction.
+//!   - [`CheckFrozenAccount`]: Checks that the sender of the transaction has not been frozen.
+//!   - [`CheckConditional`]: Checks the condition of a conditional call before its fee is paid.
 //!
 //! Look up the runtime aggregator file (e.g. `node/runtime`) to see the full list of signed
 //! extensions included in a chain.
@@ -114,7 +121,7 @@ use sp_runtime::{
 		InvalidTransaction, TransactionLongevity, TransactionSource, TransactionValidity,
 		ValidTransaction,
 	},
-	DispatchError, RuntimeDebug,
+	ArithmeticError, DispatchError, DispatchErrorWithPostInfo, RuntimeDebug, TokenError,
 };
 #[cfg(any(feature = "std", test))]
 use sp_std::map;
@@ -127,18 +134,19 @@ use frame_support::traits::BuildGenesisConfig;
 use frame_support::{
 	dispatch::{
 		extract_actual_pays_fee, extract_actual_weight, DispatchClass, DispatchInfo,
-		DispatchResult, DispatchResultWithPostInfo, PerDispatchClass, PostDispatchInfo,
+		DispatchResult, DispatchResultWithPostInfo, GetDispatchInfo, PerDispatchClass,
+		PostDispatchInfo, WithPostDispatchInfo,
 	},
 	ensure, impl_ensure_origin_with_arg_ignoring_arg,
 	migrations::MultiStepMigrator,
 	pallet_prelude::Pays,
 	storage::{self, StorageStreamIter},
 	traits::{
-		ConstU32, Contains, EnsureOrigin, EnsureOriginWithArg, Get, HandleLifetime,
-		OnKilledAccount, OnNewAccount, OnRuntimeUpgrade, OriginTrait, PalletInfo, SortedMembers,
-		StoredMap, TypedGet,
+		ConstU32, Contains, DefensiveSaturating, EnsureOrigin, EnsureOriginWithArg, Get,
+		HandleLifetime, OnKilledAccount, OnNewAccount, OnRuntimeUpgrade, OriginTrait, PalletInfo,
+		SortedMembers, StoredMap, TypedGet,
 	},
-	Parameter,
+	BoundedVec, Parameter,
 };
 use scale_info::TypeInfo;
 use sp_core::storage::well_known_keys;
@@ -163,6 +171,7 @@ pub mod weights;
 pub mod migrations;
 
 pub use extensions::{
+	check_conditional::CheckConditional, check_frozen_account::CheckFrozenAccount,
 	check_genesis::CheckGenesis, check_mortality::CheckMortality,
 	check_non_zero_sender::CheckNonZeroSender, check_nonce::CheckNonce,
 	check_spec_version::CheckSpecVersion, check_tx_version::CheckTxVersion,
@@ -307,6 +316,12 @@ pub mod pallet {
 			type PreInherents = ();
 			type PostInherents = ();
 			type PostTransactions = ();
+			type MetadataOrigin = crate::EnsureRoot<Self::AccountId>;
+			type MaxMetadataKeyLen = frame_support::traits::ConstU32<32>;
+			type MaxMetadataValueLen = frame_support::traits::ConstU32<256>;
+			type MaxMetadataEntries = frame_support::traits::ConstU32<16>;
+			type MaxDeletionsPerBlock = frame_support::traits::ConstU32<0>;
+			type KilledAccountCleanupWeight = frame_support::traits::GetDefault;
 		}
 
 		/// Default configurations of this pallet in a solochain environment.
@@ -406,6 +421,24 @@ pub mod pallet {
 			type PreInherents = ();
 			type PostInherents = ();
 			type PostTransactions = ();
+
+			/// Only root may annotate blocks with metadata.
+			type MetadataOrigin = crate::EnsureRoot<Self::AccountId>;
+
+			/// The maximum length of a block metadata key. Using 32 as default.
+			type MaxMetadataKeyLen = frame_support::traits::ConstU32<32>;
+
+			/// The maximum length of a block metadata value. Using 256 as default.
+			type MaxMetadataValueLen = frame_support::traits::ConstU32<256>;
+
+			/// The maximum number of block metadata entries. Using 16 as default.
+			type MaxMetadataEntries = frame_support::traits::ConstU32<16>;
+
+			/// Killed accounts are cleaned up immediately.
+			type MaxDeletionsPerBlock = frame_support::traits::ConstU32<0>;
+
+			/// Unused, since killed accounts are cleaned up immediately.
+			type KilledAccountCleanupWeight = frame_support::traits::GetDefault;
 		}
 
 		/// Default configurations of this pallet in a relay-chain environment.
@@ -469,7 +502,8 @@ pub mod pallet {
 		/// The aggregated `RuntimeCall` type.
 		#[pallet::no_default_bounds]
 		type RuntimeCall: Parameter
-			+ Dispatchable<RuntimeOrigin = Self::RuntimeOrigin>
+			+ Dispatchable<RuntimeOrigin = Self::RuntimeOrigin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo
 			+ Debug
 			+ From<Call<Self>>;
 
@@ -614,6 +648,39 @@ pub mod pallet {
 		///
 		/// See `frame_executive::block_flowchart` for a in-depth explanation when it runs.
 		type PostTransactions: PostTransactions;
+
+		/// The origin which may annotate the current block with `set_block_metadata`.
+		#[pallet::no_default_bounds]
+		type MetadataOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The maximum length of a block metadata key.
+		#[pallet::constant]
+		type MaxMetadataKeyLen: Get<u32>;
+
+		/// The maximum length of a block metadata value.
+		#[pallet::constant]
+		type MaxMetadataValueLen: Get<u32>;
+
+		/// The maximum number of metadata entries of a single block.
+		///
+		/// Bounds the cost of removing the metadata at the beginning of the next block.
+		#[pallet::constant]
+		type MaxMetadataEntries: Get<u32>;
+
+		/// The maximum number of killed accounts cleaned up with `OnKilledAccount` in the
+		/// `on_idle` hook of a single block.
+		///
+		/// If zero, accounts are cleaned up immediately, within the transaction that kills them.
+		/// Otherwise the cleanup is deferred to `PendingDeletion`, so the weight of such
+		/// transactions doesn't depend on the cleanup cost.
+		#[pallet::constant]
+		type MaxDeletionsPerBlock: Get<u32>;
+
+		/// The maximal weight of a single `OnKilledAccount` call.
+		///
+		/// Used to account for the deferred cleanup of killed accounts.
+		#[pallet::constant]
+		type KilledAccountCleanupWeight: Get<Weight>;
 	}
 
 	#[pallet::pallet]
@@ -625,6 +692,10 @@ pub mod pallet {
 		fn integrity_test() {
 			T::BlockWeights::get().validate().expect("The weights are invalid.");
 		}
+
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::process_pending_deletions(remaining_weight)
+		}
 	}
 
 	#[pallet::call]
@@ -810,6 +881,132 @@ pub mod pallet {
 			let post = Self::do_apply_authorize_upgrade(code)?;
 			Ok(post)
 		}
+
+		/// Freeze the account `who`, so that the transactions it signs are rejected by
+		/// [`CheckFrozenAccount`].
+		///
+		/// Has no effect on runtimes that don't include [`CheckFrozenAccount`] in their signed
+		/// extensions.
+		///
+		/// This call requires Root origin.
+		#[pallet::call_index(12)]
+		#[pallet::weight((T::SystemWeightInfo::freeze_account(), DispatchClass::Operational))]
+		pub fn freeze_account(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(!FrozenAccounts::<T>::contains_key(&who), Error::<T>::AccountAlreadyFrozen);
+			FrozenAccounts::<T>::insert(&who, ());
+			Self::deposit_event(Event::AccountFrozen { account: who });
+			Ok(())
+		}
+
+		/// Thaw the account `who`, frozen with `freeze_account`.
+		///
+		/// This call requires Root origin.
+		#[pallet::call_index(13)]
+		#[pallet::weight((T::SystemWeightInfo::thaw_account(), DispatchClass::Operational))]
+		pub fn thaw_account(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(FrozenAccounts::<T>::contains_key(&who), Error::<T>::AccountNotFrozen);
+			FrozenAccounts::<T>::remove(&who);
+			Self::deposit_event(Event::AccountThawed { account: who });
+			Ok(())
+		}
+
+		/// Annotate the current block with the `value` under the `key`.
+		///
+		/// The metadata is removed at the beginning of the next block and may be queried with
+		/// [`Pallet::block_metadata`] in the meantime.
+		///
+		/// This call requires `MetadataOrigin`.
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 2))]
+		pub fn set_block_metadata(
+			origin: OriginFor<T>,
+			key: Vec<u8>,
+			value: Vec<u8>,
+		) -> DispatchResult {
+			T::MetadataOrigin::ensure_origin(origin)?;
+			let key: BoundedVec<_, _> =
+				key.try_into().map_err(|_| Error::<T>::BlockMetadataKeyTooLong)?;
+			let value: BoundedVec<_, _> =
+				value.try_into().map_err(|_| Error::<T>::BlockMetadataValueTooLong)?;
+			ensure!(
+				BlockMetadata::<T>::contains_key(&key) ||
+					BlockMetadata::<T>::count() < T::MaxMetadataEntries::get(),
+				Error::<T>::TooManyBlockMetadataEntries
+			);
+			BlockMetadata::<T>::insert(key, value);
+			Ok(())
+		}
+
+		/// Dispatch `call` with the same origin, but only if the weight consumed by the block so
+		/// far is below `weight_threshold`. Otherwise fail with `BlockTooHeavy`.
+		///
+		/// The weight of this extrinsic is already accounted for in the block weight when it is
+		/// dispatched. Use [`CheckConditional`] to drop the extrinsic from a congested block
+		/// before any fee is paid.
+		///
+		/// The fee is charged for the worst-case weight of `call`, and refunded down to the cost
+		/// of the check if `call` is not executed.
+		#[pallet::call_index(15)]
+		#[pallet::weight({
+			let dispatch_info = call.get_dispatch_info();
+			(
+				T::DbWeight::get().reads(1).saturating_add(dispatch_info.weight),
+				dispatch_info.class,
+			)
+		})]
+		pub fn execute_if_block_weight_below(
+			origin: OriginFor<T>,
+			weight_threshold: Weight,
+			call: Box<<T as Config>::RuntimeCall>,
+		) -> DispatchResultWithPostInfo {
+			let check_weight = T::DbWeight::get().reads(1);
+			ensure!(
+				Self::block_weight_below(weight_threshold),
+				Error::<T>::BlockTooHeavy.with_weight(check_weight)
+			);
+
+			let call_info = call.get_dispatch_info();
+			let actual_weight = |post_info: &PostDispatchInfo| {
+				Some(check_weight.saturating_add(post_info.calc_actual_weight(&call_info)))
+			};
+			match call.dispatch(origin) {
+				Ok(post_info) => Ok(actual_weight(&post_info).into()),
+				Err(err) => Err(DispatchErrorWithPostInfo {
+					post_info: actual_weight(&err.post_info).into(),
+					error: err.error,
+				}),
+			}
+		}
+
+		/// Let the nonce of `account` skip `missing_nonce`, the nonce of a transaction which got
+		/// lost, so that the transactions with the later nonces may be included.
+		///
+		/// Nothing changes if the nonce of `account` is already past `missing_nonce`. In
+		/// particular, when signed by `account` itself, this extrinsic consumes the missing nonce
+		/// on its own.
+		///
+		/// This call requires Root origin or to be signed by `account`.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn fill_nonce_gap(
+			origin: OriginFor<T>,
+			account: T::AccountId,
+			missing_nonce: T::Nonce,
+		) -> DispatchResult {
+			if let Some(who) = ensure_signed_or_root(origin)? {
+				ensure!(who == account, DispatchError::BadOrigin);
+			}
+			let nonce = Self::account_nonce(&account);
+			ensure!(missing_nonce <= nonce, Error::<T>::FutureNonce);
+			if missing_nonce == nonce {
+				ensure!(Self::account_exists(&account), DispatchError::NoProviders);
+				Self::inc_account_nonce(&account);
+			}
+			Self::deposit_event(Event::NonceFilled { account, nonce: missing_nonce });
+			Ok(())
+		}
 	}
 
 	/// Event for the System pallet.
@@ -838,6 +1035,12 @@ pub mod pallet {
 		TaskFailed { task: T::RuntimeTask, err: DispatchError },
 		/// An upgrade was authorized.
 		UpgradeAuthorized { code_hash: T::Hash, check_version: bool },
+		/// An account was frozen.
+		AccountFrozen { account: T::AccountId },
+		/// An account was thawed.
+		AccountThawed { account: T::AccountId },
+		/// The nonce of an account was moved past a missing nonce.
+		NonceFilled { account: T::AccountId, nonce: T::Nonce },
 	}
 
 	/// Error for the System pallet
@@ -871,6 +1074,20 @@ pub mod pallet {
 		NothingAuthorized,
 		/// The submitted code is not authorized.
 		Unauthorized,
+		/// The account is already frozen.
+		AccountAlreadyFrozen,
+		/// The account is not frozen.
+		AccountNotFrozen,
+		/// The block metadata key is longer than `MaxMetadataKeyLen`.
+		BlockMetadataKeyTooLong,
+		/// The block metadata value is longer than `MaxMetadataValueLen`.
+		BlockMetadataValueTooLong,
+		/// The current block already has `MaxMetadataEntries` metadata entries.
+		TooManyBlockMetadataEntries,
+		/// The block weight is not below the threshold of a conditional call.
+		BlockTooHeavy,
+		/// The nonce to fill is beyond the current nonce of the account.
+		FutureNonce,
 	}
 
 	/// Exposed trait-generic origin type.
@@ -997,6 +1214,30 @@ pub mod pallet {
 	pub(super) type AuthorizedUpgrade<T: Config> =
 		StorageValue<_, CodeUpgradeAuthorization<T>, OptionQuery>;
 
+	/// Accounts whose transactions are rejected by [`CheckFrozenAccount`].
+	#[pallet::storage]
+	pub type FrozenAccounts<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ()>;
+
+	/// Killed accounts, whose cleanup with `OnKilledAccount` is deferred to `on_idle`.
+	///
+	/// Such accounts are locked: their data may not be re-created through the `StoredMap`
+	/// implementation of this pallet until they are cleaned up. If such account is re-created by
+	/// incrementing its reference counters directly, the cleanup happens right before the
+	/// re-creation.
+	#[pallet::storage]
+	pub type PendingDeletion<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ()>;
+
+	/// Key-value metadata of the current block, set with `set_block_metadata`.
+	///
+	/// Removed at the beginning of each block, just like the events.
+	#[pallet::storage]
+	pub type BlockMetadata<T: Config> = CountedStorageMap<
+		_,
+		Blake2_128Concat,
+		BoundedVec<u8, T::MaxMetadataKeyLen>,
+		BoundedVec<u8, T::MaxMetadataValueLen>,
+	>;
+
 	#[derive(frame_support::DefaultNoBound)]
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
@@ -1389,6 +1630,26 @@ pub enum DecRefStatus {
 	Exists,
 }
 
+/// The reference counter of an account would exceed [`RefCount::MAX`].
+#[derive(Eq, PartialEq, RuntimeDebug)]
+pub struct OverflowError;
+
+/// The reference counter of an account would drop below zero.
+#[derive(Eq, PartialEq, RuntimeDebug)]
+pub struct UnderflowError;
+
+impl From<OverflowError> for DispatchError {
+	fn from(_: OverflowError) -> Self {
+		ArithmeticError::Overflow.into()
+	}
+}
+
+impl From<UnderflowError> for DispatchError {
+	fn from(_: UnderflowError) -> Self {
+		ArithmeticError::Underflow.into()
+	}
+}
+
 impl<T: Config> Pallet<T> {
 	/// Returns the `spec_version` of the last runtime upgrade.
 	///
@@ -1438,16 +1699,48 @@ impl<T: Config> Pallet<T> {
 	}
 
 	/// Increment the reference counter on an account.
-	#[deprecated = "Use `inc_consumers` instead"]
+	///
+	/// Saturates at [`RefCount::MAX`], which is a logic error.
+	#[deprecated = "Use `inc_consumers` or `checked_inc_ref` instead"]
 	pub fn inc_ref(who: &T::AccountId) {
-		let _ = Self::inc_consumers(who);
+		if Self::checked_inc_ref(who).is_err() {
+			log::warn!(target: LOG_TARGET, "Reference counter overflow on increment");
+			Account::<T>::mutate(who, |a| a.consumers = a.consumers.defensive_saturating_add(1));
+		}
 	}
 
 	/// Decrement the reference counter on an account. This *MUST* only be done once for every time
-	/// you called `inc_consumers` on `who`.
-	#[deprecated = "Use `dec_consumers` instead"]
+	/// you called `inc_ref` on `who`.
+	///
+	/// Saturates at zero, which is a logic error.
+	#[deprecated = "Use `dec_consumers` or `checked_dec_ref` instead"]
 	pub fn dec_ref(who: &T::AccountId) {
-		let _ = Self::dec_consumers(who);
+		if Self::checked_dec_ref(who).is_err() {
+			log::warn!(target: LOG_TARGET, "Reference counter underflow on decrement");
+			Account::<T>::mutate(who, |a| a.consumers = a.consumers.defensive_saturating_sub(1));
+		}
+	}
+
+	/// Increment the reference counter on an account, or fail if it would overflow.
+	///
+	/// Unlike [`Self::inc_consumers`], neither the providers nor the `MaxConsumers` limit of the
+	/// account are checked. The counter is left untouched on error.
+	pub fn checked_inc_ref(who: &T::AccountId) -> Result<(), OverflowError> {
+		Account::<T>::try_mutate(who, |a| {
+			a.consumers = a.consumers.checked_add(1).ok_or(OverflowError)?;
+			Ok(())
+		})
+	}
+
+	/// Decrement the reference counter on an account, or fail if it is already zero.
+	///
+	/// This *MUST* only be done once for every time you called `checked_inc_ref` on `who`. The
+	/// counter is left untouched on error.
+	pub fn checked_dec_ref(who: &T::AccountId) -> Result<(), UnderflowError> {
+		Account::<T>::try_mutate(who, |a| {
+			a.consumers = a.consumers.checked_sub(1).ok_or(UnderflowError)?;
+			Ok(())
+		})
 	}
 
 	/// The number of outstanding references for the account `who`.
@@ -1760,6 +2053,14 @@ impl<T: Config> Pallet<T> {
 
 		// Remove previous block data from storage
 		BlockWeight::<T>::kill();
+		let metadata_entries = BlockMetadata::<T>::count();
+		if metadata_entries > 0 {
+			let _ = BlockMetadata::<T>::clear(metadata_entries, None);
+			Self::register_extra_weight_unchecked(
+				T::DbWeight::get().reads_writes(1, metadata_entries as u64 + 1),
+				DispatchClass::Mandatory,
+			);
+		}
 	}
 
 	/// Remove temporary "environment" entries in storage, compute the storage root and return the
@@ -1971,6 +2272,23 @@ impl<T: Config> Pallet<T> {
 		T::Version::get()
 	}
 
+	/// The metadata of the block `block_number` under the `key`.
+	///
+	/// Only the metadata of the current block is kept in storage, so `None` is returned for any
+	/// other block.
+	pub fn block_metadata(block_number: BlockNumberFor<T>, key: &[u8]) -> Option<Vec<u8>> {
+		if block_number != Self::block_number() {
+			return None
+		}
+		let key = BoundedVec::<u8, T::MaxMetadataKeyLen>::try_from(key.to_vec()).ok()?;
+		BlockMetadata::<T>::get(key).map(Into::into)
+	}
+
+	/// Whether the weight consumed by the current block is below `weight_threshold`.
+	pub fn block_weight_below(weight_threshold: Weight) -> bool {
+		Self::block_weight().total().all_lt(weight_threshold)
+	}
+
 	/// Retrieve the account transaction counter from storage.
 	pub fn account_nonce(who: impl EncodeLike<T::AccountId>) -> T::Nonce {
 		Account::<T>::get(who).nonce
@@ -2035,16 +2353,52 @@ impl<T: Config> Pallet<T> {
 
 	/// An account is being created.
 	pub fn on_created_account(who: T::AccountId, _a: &mut AccountInfo<T::Nonce, T::AccountData>) {
+		// finish the deferred cleanup first, so that it doesn't touch the new account later
+		if PendingDeletion::<T>::take(&who).is_some() {
+			T::OnKilledAccount::on_killed_account(&who);
+			Self::register_extra_weight_unchecked(
+				Self::deletion_weight(),
+				DispatchClass::Mandatory,
+			);
+		}
 		T::OnNewAccount::on_new_account(&who);
 		Self::deposit_event(Event::NewAccount { account: who });
 	}
 
 	/// Do anything that needs to be done after an account has been killed.
 	fn on_killed_account(who: T::AccountId) {
-		T::OnKilledAccount::on_killed_account(&who);
+		if T::MaxDeletionsPerBlock::get().is_zero() {
+			T::OnKilledAccount::on_killed_account(&who);
+		} else {
+			PendingDeletion::<T>::insert(&who, ());
+		}
 		Self::deposit_event(Event::KilledAccount { account: who });
 	}
 
+	/// Clean up at most `MaxDeletionsPerBlock` accounts from `PendingDeletion`, fitting into the
+	/// `remaining_weight`.
+	///
+	/// Returns the consumed weight.
+	fn process_pending_deletions(remaining_weight: Weight) -> Weight {
+		let deletion_weight = Self::deletion_weight();
+		let mut consumed_weight = Weight::zero();
+		let mut pending_deletions = PendingDeletion::<T>::drain();
+		for _ in 0..T::MaxDeletionsPerBlock::get() {
+			if !consumed_weight.saturating_add(deletion_weight).all_lte(remaining_weight) {
+				break
+			}
+			let Some((who, ())) = pending_deletions.next() else { break };
+			T::OnKilledAccount::on_killed_account(&who);
+			consumed_weight.saturating_accrue(deletion_weight);
+		}
+		consumed_weight
+	}
+
+	/// Weight of the cleanup of a single account from `PendingDeletion`.
+	fn deletion_weight() -> Weight {
+		T::KilledAccountCleanupWeight::get().saturating_add(T::DbWeight::get().reads_writes(1, 1))
+	}
+
 	/// Determine whether or not it is possible to update the code.
 	///
 	/// Checks the given code if it is a valid runtime wasm blob by instantiating
@@ -2191,6 +2545,13 @@ impl<T: Config> StoredMap<T::AccountId, T::AccountData> for Pallet<T> {
 	) -> Result<R, E> {
 		let account = Account::<T>::get(k);
 		let is_default = account.data == T::AccountData::default();
+		// killed accounts are locked until their deferred cleanup is done
+		if is_default &&
+			!T::MaxDeletionsPerBlock::get().is_zero() &&
+			PendingDeletion::<T>::contains_key(k)
+		{
+			return Err(DispatchError::Token(TokenError::Blocked).into())
+		}
 		let mut some_data = if is_default { None } else { Some(account.data) };
 		let result = f(&mut some_data)?;
 		if Self::providers(k) > 0 || Self::sufficients(k) > 0 {
@@ -19,7 +19,13 @@ use crate::{self as frame_system, *};
 use frame_support::{derive_impl, parameter_types};
 use sp_runtime::{BuildStorage, Perbill};
 
-type Block = mocking::MockBlock<Test>;
+/// The signed extensions of the mock.
+pub type SignedExtra = (CheckFrozenAccount<Test>,);
+
+type Block = generic::Block<
+	generic::Header<u64, sp_runtime::traits::BlakeTwo256>,
+	mocking::MockUncheckedExtrinsic<Test, (), SignedExtra>,
+>;
 
 frame_support::construct_runtime!(
 	pub enum Test
@@ -69,6 +75,8 @@ parameter_types! {
 
 parameter_types! {
 	pub static Killed: Vec<u64> = vec![];
+	pub static MaxDeletionsPerBlock: u32 = 0;
+	pub static KilledAccountCleanupWeight: Weight = Weight::from_parts(1_000, 0);
 }
 
 pub struct RecordKilled;
@@ -87,6 +95,11 @@ impl Config for Test {
 	type AccountData = u32;
 	type OnKilledAccount = RecordKilled;
 	type MultiBlockMigrator = MockedMigrator;
+	type MaxMetadataKeyLen = ConstU32<4>;
+	type MaxMetadataValueLen = ConstU32<8>;
+	type MaxMetadataEntries = ConstU32<2>;
+	type MaxDeletionsPerBlock = MaxDeletionsPerBlock;
+	type KilledAccountCleanupWeight = KilledAccountCleanupWeight;
 }
 
 parameter_types! {
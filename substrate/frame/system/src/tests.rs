@@ -19,7 +19,7 @@ use crate::*;
 use frame_support::{
 	assert_noop, assert_ok,
 	dispatch::{Pays, PostDispatchInfo, WithPostDispatchInfo},
-	traits::{OnRuntimeUpgrade, WhitelistedStorageKeys},
+	traits::{Hooks, OnRuntimeUpgrade, WhitelistedStorageKeys},
 };
 use std::collections::BTreeSet;
 
@@ -121,6 +121,91 @@ fn stored_map_works() {
 	});
 }
 
+#[test]
+fn killed_accounts_are_cleaned_up_in_on_idle() {
+	new_test_ext().execute_with(|| {
+		MaxDeletionsPerBlock::set(2);
+		for who in 1..=3 {
+			assert_eq!(System::inc_providers(&who), IncRefStatus::Created);
+			assert_ok!(System::dec_providers(&who));
+			assert!(!System::account_exists(&who));
+		}
+
+		// the cleanup is deferred
+		assert!(Killed::get().is_empty());
+		assert_eq!(PendingDeletion::<Test>::iter_keys().count(), 3);
+
+		// not enough weight for a single deletion
+		assert_eq!(System::on_idle(1, Weight::zero()), Weight::zero());
+		assert!(Killed::get().is_empty());
+
+		// at most `MaxDeletionsPerBlock` accounts are cleaned up per block
+		let deletion_weight = KilledAccountCleanupWeight::get() +
+			<Test as Config>::DbWeight::get().reads_writes(1, 1);
+		assert_eq!(System::on_idle(1, Weight::MAX), deletion_weight * 2);
+		assert_eq!(Killed::get().len(), 2);
+		assert_eq!(PendingDeletion::<Test>::iter_keys().count(), 1);
+
+		System::on_idle(2, Weight::MAX);
+		Killed::mutate(|killed| killed.sort());
+		assert_eq!(Killed::get(), vec![1, 2, 3]);
+		assert_eq!(PendingDeletion::<Test>::iter_keys().count(), 0);
+
+		// accounts may be re-created after the cleanup
+		assert_eq!(System::inc_providers(&1), IncRefStatus::Created);
+		assert_eq!(Killed::get().len(), 3);
+	});
+}
+
+#[test]
+fn pending_deletion_is_completed_before_account_recreation() {
+	new_test_ext().execute_with(|| {
+		MaxDeletionsPerBlock::set(2);
+		assert_eq!(System::inc_providers(&1), IncRefStatus::Created);
+		assert_ok!(System::dec_providers(&1));
+		assert!(PendingDeletion::<Test>::contains_key(1));
+		assert!(Killed::get().is_empty());
+
+		let weight_before = System::block_weight();
+		assert_eq!(System::inc_providers(&1), IncRefStatus::Created);
+		assert!(!PendingDeletion::<Test>::contains_key(1));
+		assert_eq!(Killed::get(), vec![1]);
+		// the cleanup is accounted in the block weight
+		assert_eq!(
+			System::block_weight().get(DispatchClass::Mandatory),
+			weight_before.get(DispatchClass::Mandatory) +
+				KilledAccountCleanupWeight::get() +
+				<Test as Config>::DbWeight::get().reads_writes(1, 1),
+		);
+
+		// nothing is cleaned up for the new account
+		System::on_idle(1, Weight::MAX);
+		assert_eq!(Killed::get(), vec![1]);
+		assert!(System::account_exists(&1));
+	});
+}
+
+#[test]
+fn pending_deletion_locks_account_data() {
+	new_test_ext().execute_with(|| {
+		MaxDeletionsPerBlock::set(2);
+		assert_eq!(System::inc_providers(&1), IncRefStatus::Created);
+		assert_ok!(System::insert(&1, 42));
+		assert_ok!(System::remove(&1));
+		assert_ok!(System::dec_providers(&1));
+		assert!(PendingDeletion::<Test>::contains_key(1));
+
+		// the account data may not be re-created until the account is cleaned up
+		assert_noop!(System::insert(&1, 42), TokenError::Blocked);
+
+		System::on_idle(1, Weight::MAX);
+		assert_eq!(Killed::get(), vec![1]);
+		assert_eq!(System::inc_providers(&1), IncRefStatus::Created);
+		assert_ok!(System::insert(&1, 42));
+		assert_eq!(System::get(&1), 42);
+	});
+}
+
 #[test]
 fn provider_ref_handover_to_self_sufficient_ref_works() {
 	new_test_ext().execute_with(|| {
@@ -825,6 +910,311 @@ fn ensure_signed_stuff_works() {
 	}
 }
 
+#[test]
+fn freeze_and_thaw_account_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(System::freeze_account(RuntimeOrigin::signed(1), 2), DispatchError::BadOrigin);
+
+		assert_ok!(System::freeze_account(RuntimeOrigin::root(), 2));
+		assert!(FrozenAccounts::<Test>::contains_key(2));
+		System::assert_last_event(SysEvent::AccountFrozen { account: 2 }.into());
+		assert_noop!(
+			System::freeze_account(RuntimeOrigin::root(), 2),
+			Error::<Test>::AccountAlreadyFrozen
+		);
+
+		assert_noop!(System::thaw_account(RuntimeOrigin::signed(1), 2), DispatchError::BadOrigin);
+		assert_ok!(System::thaw_account(RuntimeOrigin::root(), 2));
+		assert!(!FrozenAccounts::<Test>::contains_key(2));
+		System::assert_last_event(SysEvent::AccountThawed { account: 2 }.into());
+		assert_noop!(
+			System::thaw_account(RuntimeOrigin::root(), 2),
+			Error::<Test>::AccountNotFrozen
+		);
+	});
+}
+
+#[test]
+fn block_metadata_is_available_within_the_block() {
+	new_test_ext().execute_with(|| {
+		System::initialize(&1, &[0u8; 32].into(), &Default::default());
+		assert_noop!(
+			System::set_block_metadata(
+				RuntimeOrigin::signed(1),
+				b"root".to_vec(),
+				b"hash".to_vec()
+			),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(System::set_block_metadata(
+			RuntimeOrigin::root(),
+			b"root".to_vec(),
+			b"hash".to_vec()
+		));
+		assert_eq!(System::block_metadata(1, b"root"), Some(b"hash".to_vec()));
+		assert_eq!(System::block_metadata(1, b"head"), None);
+		assert_eq!(System::block_metadata(0, b"root"), None);
+
+		// the metadata of the previous block is gone
+		System::initialize(&2, &[0u8; 32].into(), &Default::default());
+		assert_eq!(System::block_metadata(2, b"root"), None);
+		assert_eq!(BlockMetadata::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn block_metadata_length_is_limited() {
+	new_test_ext().execute_with(|| {
+		System::initialize(&1, &[0u8; 32].into(), &Default::default());
+		assert_ok!(System::set_block_metadata(RuntimeOrigin::root(), vec![1; 4], vec![2; 8]));
+		assert_noop!(
+			System::set_block_metadata(RuntimeOrigin::root(), vec![1; 5], vec![2; 8]),
+			Error::<Test>::BlockMetadataKeyTooLong
+		);
+		assert_noop!(
+			System::set_block_metadata(RuntimeOrigin::root(), vec![1; 4], vec![2; 9]),
+			Error::<Test>::BlockMetadataValueTooLong
+		);
+		assert_eq!(System::block_metadata(1, &[1; 4]), Some(vec![2; 8]));
+		assert_eq!(System::block_metadata(1, &[1; 5]), None);
+	});
+}
+
+#[test]
+fn block_metadata_entries_are_limited() {
+	new_test_ext().execute_with(|| {
+		System::initialize(&1, &[0u8; 32].into(), &Default::default());
+		assert_ok!(System::set_block_metadata(RuntimeOrigin::root(), vec![1], vec![1]));
+		assert_ok!(System::set_block_metadata(RuntimeOrigin::root(), vec![2], vec![2]));
+		assert_noop!(
+			System::set_block_metadata(RuntimeOrigin::root(), vec![3], vec![3]),
+			Error::<Test>::TooManyBlockMetadataEntries
+		);
+		// overwriting an existing entry is fine
+		assert_ok!(System::set_block_metadata(RuntimeOrigin::root(), vec![2], vec![3]));
+		assert_eq!(System::block_metadata(1, &[2]), Some(vec![3]));
+
+		// the cleanup is accounted for in the next block
+		System::initialize(&2, &[0u8; 32].into(), &Default::default());
+		assert_eq!(BlockMetadata::<Test>::count(), 0);
+		assert_eq!(
+			System::block_weight().get(DispatchClass::Mandatory),
+			&<Test as Config>::DbWeight::get().reads_writes(1, 3)
+		);
+		assert_ok!(System::set_block_metadata(RuntimeOrigin::root(), vec![3], vec![3]));
+	});
+}
+
+#[test]
+fn execute_if_block_weight_below_works() {
+	new_test_ext().execute_with(|| {
+		System::initialize(&1, &[0u8; 32].into(), &Default::default());
+		let remark: RuntimeCall = Call::remark_with_event { remark: vec![1] }.into();
+		let check_weight = <Test as Config>::DbWeight::get().reads(1);
+		let remark_weight = remark.get_dispatch_info().weight;
+		let threshold = System::block_weight().total().saturating_add(Weight::from_parts(1, 1));
+
+		assert_eq!(
+			System::execute_if_block_weight_below(
+				RuntimeOrigin::signed(1),
+				threshold,
+				Box::new(remark.clone())
+			),
+			Ok(Some(check_weight + remark_weight).into())
+		);
+		System::assert_last_event(
+			Event::Remarked { sender: 1, hash: BlakeTwo256::hash(&[1]) }.into(),
+		);
+		let events = System::events().len();
+
+		// the block is congested now
+		System::register_extra_weight_unchecked(Weight::from_parts(1, 1), DispatchClass::Normal);
+		assert_eq!(
+			System::execute_if_block_weight_below(
+				RuntimeOrigin::signed(1),
+				threshold,
+				Box::new(remark)
+			),
+			Err(Error::<Test>::BlockTooHeavy.with_weight(check_weight))
+		);
+		assert_eq!(System::events().len(), events);
+	});
+}
+
+#[test]
+fn execute_if_block_weight_below_forwards_the_call_error() {
+	new_test_ext().execute_with(|| {
+		let set_heap_pages: RuntimeCall = Call::set_heap_pages { pages: 1 }.into();
+		let check_weight = <Test as Config>::DbWeight::get().reads(1);
+		let call_weight = set_heap_pages.get_dispatch_info().weight;
+
+		assert_eq!(
+			System::execute_if_block_weight_below(
+				RuntimeOrigin::signed(1),
+				Weight::MAX,
+				Box::new(set_heap_pages.clone())
+			),
+			Err(DispatchError::BadOrigin.with_weight(check_weight + call_weight))
+		);
+		assert_ok!(System::execute_if_block_weight_below(
+			RuntimeOrigin::root(),
+			Weight::MAX,
+			Box::new(set_heap_pages)
+		));
+	});
+}
+
+#[test]
+fn execute_if_block_weight_below_fee_covers_the_call() {
+	let remark: RuntimeCall = Call::remark { remark: vec![0; 32] }.into();
+	let set_heap_pages: RuntimeCall = Call::set_heap_pages { pages: 1 }.into();
+	let check_weight = <Test as Config>::DbWeight::get().reads(1);
+
+	for call in [remark, set_heap_pages] {
+		let call_info = call.get_dispatch_info();
+		let info = RuntimeCall::from(Call::execute_if_block_weight_below {
+			weight_threshold: Weight::zero(),
+			call: Box::new(call),
+		})
+		.get_dispatch_info();
+		// the worst case weight of the call is paid upfront
+		assert_eq!(info.weight, check_weight + call_info.weight);
+		assert_eq!(info.class, call_info.class);
+		assert_eq!(info.pays_fee, Pays::Yes);
+	}
+
+	// only the check is paid if the call is not executed
+	let rejected = Error::<Test>::BlockTooHeavy.with_weight(check_weight).post_info;
+	let info = RuntimeCall::from(Call::execute_if_block_weight_below {
+		weight_threshold: Weight::zero(),
+		call: Box::new(Call::remark { remark: vec![0; 32] }.into()),
+	})
+	.get_dispatch_info();
+	assert_eq!(rejected.calc_actual_weight(&info), check_weight);
+}
+
+#[test]
+fn checked_ref_counting_works() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(System::checked_dec_ref(&1), Err(UnderflowError));
+		assert_ok!(System::checked_inc_ref(&1));
+		assert_ok!(System::checked_inc_ref(&1));
+		assert_eq!(System::consumers(&1), 2);
+		assert_ok!(System::checked_dec_ref(&1));
+		assert_ok!(System::checked_dec_ref(&1));
+		assert_eq!(System::checked_dec_ref(&1), Err(UnderflowError));
+		assert_eq!(System::consumers(&1), 0);
+
+		Account::<Test>::mutate(&1, |a| a.consumers = RefCount::MAX);
+		assert_eq!(System::checked_inc_ref(&1), Err(OverflowError));
+		assert_eq!(System::consumers(&1), RefCount::MAX);
+		assert_eq!(DispatchError::from(OverflowError), ArithmeticError::Overflow.into());
+	});
+}
+
+#[test]
+#[allow(deprecated)]
+fn deprecated_ref_counting_uses_checked_ref_counting() {
+	new_test_ext().execute_with(|| {
+		System::inc_ref(&1);
+		System::inc_ref(&1);
+		assert_eq!(System::refs(&1), 2);
+		System::dec_ref(&1);
+		assert_eq!(System::refs(&1), 1);
+		assert_ok!(System::checked_dec_ref(&1));
+		assert_eq!(System::refs(&1), 0);
+	});
+}
+
+#[test]
+fn checked_ref_counting_never_panics() {
+	use rand::{rngs::StdRng, Rng, SeedableRng};
+
+	let mut rng = StdRng::seed_from_u64(42);
+	for _ in 0..100 {
+		new_test_ext().execute_with(|| {
+			// start anywhere, including close to the bounds
+			let mut expected = match rng.gen_range(0..3) {
+				0 => rng.gen_range(0..10),
+				1 => RefCount::MAX - rng.gen_range(0..10),
+				_ => rng.gen(),
+			};
+			Account::<Test>::mutate(&1, |a| a.consumers = expected);
+
+			for _ in 0..50 {
+				if rng.gen() {
+					let result = System::checked_inc_ref(&1);
+					assert_eq!(result.is_err(), expected == RefCount::MAX);
+					expected = expected.saturating_add(1);
+				} else {
+					let result = System::checked_dec_ref(&1);
+					assert_eq!(result.is_err(), expected == 0);
+					expected = expected.saturating_sub(1);
+				}
+				assert_eq!(System::consumers(&1), expected);
+			}
+		});
+	}
+}
+
+#[test]
+fn fill_nonce_gap_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		System::inc_providers(&1);
+		System::inc_account_nonce(&1);
+		System::inc_account_nonce(&1);
+
+		// the transaction with nonce 2 got lost
+		assert_ok!(System::fill_nonce_gap(RuntimeOrigin::root(), 1, 2));
+		assert_eq!(System::account_nonce(&1), 3);
+		System::assert_last_event(Event::NonceFilled { account: 1, nonce: 2 }.into());
+
+		// the account may fill its own gaps
+		assert_ok!(System::fill_nonce_gap(RuntimeOrigin::signed(1), 1, 3));
+		assert_eq!(System::account_nonce(&1), 4);
+		assert_noop!(
+			System::fill_nonce_gap(RuntimeOrigin::signed(2), 1, 4),
+			DispatchError::BadOrigin
+		);
+
+		// accounts which do not exist are not created
+		assert_noop!(
+			System::fill_nonce_gap(RuntimeOrigin::root(), 2, 0),
+			DispatchError::NoProviders
+		);
+	});
+}
+
+#[test]
+fn fill_nonce_gap_is_idempotent() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		System::inc_providers(&1);
+
+		assert_ok!(System::fill_nonce_gap(RuntimeOrigin::root(), 1, 0));
+		assert_eq!(System::account_nonce(&1), 1);
+		assert_ok!(System::fill_nonce_gap(RuntimeOrigin::root(), 1, 0));
+		assert_eq!(System::account_nonce(&1), 1);
+	});
+}
+
+#[test]
+fn fill_nonce_gap_beyond_current_nonce_fails() {
+	new_test_ext().execute_with(|| {
+		System::inc_providers(&1);
+		System::inc_account_nonce(&1);
+
+		assert_noop!(
+			System::fill_nonce_gap(RuntimeOrigin::root(), 1, 2),
+			Error::<Test>::FutureNonce
+		);
+		assert_eq!(System::account_nonce(&1), 1);
+	});
+}
+
 pub fn from_actual_ref_time(ref_time: Option<u64>) -> PostDispatchInfo {
 	PostDispatchInfo {
 		actual_weight: ref_time.map(|t| Weight::from_all(t)),
@@ -23,6 +23,10 @@
 //! HOSTNAME: `runner-s7kdgajz-project-145-concurrent-0`, CPU: `Intel(R) Xeon(R) CPU @ 2.60GHz`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
 
+// The weights of `freeze_account` and `thaw_account` have not been generated by the benchmark
+// CLI yet. They are estimated from the storage accesses of their benchmarks and must be replaced
+// by the output of the command below.
+
 // Executed Command:
 // target/production/substrate
 // benchmark
@@ -59,6 +63,8 @@ pub trait WeightInfo {
 	fn kill_prefix(p: u32, ) -> Weight;
 	fn authorize_upgrade() -> Weight;
 	fn apply_authorized_upgrade() -> Weight;
+	fn freeze_account() -> Weight;
+	fn thaw_account() -> Weight;
 }
 
 /// Weights for frame_system using the Substrate node and recommended hardware.
@@ -178,6 +184,24 @@ impl<T: crate::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2))
 			.saturating_add(T::DbWeight::get().writes(3))
 	}
+	/// Storage: `System::FrozenAccounts` (r:1 w:1)
+	/// Proof: `System::FrozenAccounts` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	fn freeze_account() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(9_718_000, 0)
+			.saturating_add(Weight::from_parts(0, 3513))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `System::FrozenAccounts` (r:1 w:1)
+	/// Proof: `System::FrozenAccounts` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	fn thaw_account() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(11_054_000, 0)
+			.saturating_add(Weight::from_parts(0, 3513))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
 
 // For backwards compatibility and tests
@@ -296,4 +320,22 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2))
 			.saturating_add(RocksDbWeight::get().writes(3))
 	}
+	/// Storage: `System::FrozenAccounts` (r:1 w:1)
+	/// Proof: `System::FrozenAccounts` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	fn freeze_account() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(9_718_000, 0)
+			.saturating_add(Weight::from_parts(0, 3513))
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	/// Storage: `System::FrozenAccounts` (r:1 w:1)
+	/// Proof: `System::FrozenAccounts` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	fn thaw_account() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(11_054_000, 0)
+			.saturating_add(Weight::from_parts(0, 3513))
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
 }
@@ -0,0 +1,130 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A mock network for testing the cross-chain payouts of the tips.
+//!
+//! The tips of the relay chain are paid out to the beneficiaries on `ParaA` with `PayOverXcm`, out
+//! of the account that the treasury of the relay chain controls there.
+//!
+//! Like `pallet-nft-fractionalization-mock-network`, it is a crate of its own, so that
+//! `xcm-simulator`, `pallet-xcm` and the relay chain runtime it needs don't become dependencies of
+//! the pallet. The unit tests of the pallet use a paymaster that only records the payments.
+
+pub mod mocks;
+pub mod parachain;
+pub mod primitives;
+pub mod relay_chain;
+
+#[cfg(test)]
+mod tests;
+
+use crate::primitives::{AccountId, UNITS};
+use sp_runtime::BuildStorage;
+use xcm::latest::prelude::*;
+use xcm_executor::traits::ConvertLocation;
+pub use xcm_simulator::TestExt;
+use xcm_simulator::{decl_test_network, decl_test_parachain, decl_test_relay_chain};
+
+// Accounts
+pub const ALICE: sp_runtime::AccountId32 = sp_runtime::AccountId32::new([1u8; 32]);
+pub const BOB: sp_runtime::AccountId32 = sp_runtime::AccountId32::new([2u8; 32]);
+pub const CHARLIE: sp_runtime::AccountId32 = sp_runtime::AccountId32::new([3u8; 32]);
+
+// Balances
+pub const INITIAL_BALANCE: u128 = 1_000_000_000 * UNITS;
+
+/// The parachain, on which the cross-chain tips are paid out.
+pub const PAYOUT_PARA_ID: u32 = 1;
+
+decl_test_parachain! {
+	pub struct ParaA {
+		Runtime = parachain::Runtime,
+		XcmpMessageHandler = parachain::MsgQueue,
+		DmpMessageHandler = parachain::MsgQueue,
+		new_ext = para_ext(PAYOUT_PARA_ID),
+	}
+}
+
+decl_test_relay_chain! {
+	pub struct Relay {
+		Runtime = relay_chain::Runtime,
+		RuntimeCall = relay_chain::RuntimeCall,
+		RuntimeEvent = relay_chain::RuntimeEvent,
+		XcmConfig = relay_chain::XcmConfig,
+		MessageQueue = relay_chain::MessageQueue,
+		System = relay_chain::System,
+		new_ext = relay_ext(),
+	}
+}
+
+decl_test_network! {
+	pub struct MockNet {
+		relay_chain = Relay,
+		parachains = vec![
+			(1, ParaA),
+		],
+	}
+}
+
+/// The account of the relay chain treasury at `ParaA`.
+pub fn relay_treasury_account_id() -> AccountId {
+	let location = Location::new(1, relay_chain::TreasuryInteriorLocation::get());
+	parachain::LocationToAccountId::convert_location(&location).unwrap()
+}
+
+pub fn para_ext(para_id: u32) -> sp_io::TestExternalities {
+	use parachain::{MsgQueue, Runtime, System};
+
+	let mut t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+
+	pallet_balances::GenesisConfig::<Runtime> {
+		balances: vec![(ALICE, INITIAL_BALANCE), (relay_treasury_account_id(), INITIAL_BALANCE)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		sp_tracing::try_init_simple();
+		System::set_block_number(1);
+		MsgQueue::set_para_id(para_id.into());
+	});
+	ext
+}
+
+pub fn relay_ext() -> sp_io::TestExternalities {
+	use relay_chain::{Runtime, System, TreasuryAccount};
+
+	let mut t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+
+	// the finder's fees are paid out of the local funds of the treasury
+	pallet_balances::GenesisConfig::<Runtime> {
+		balances: vec![
+			(ALICE, INITIAL_BALANCE),
+			(BOB, INITIAL_BALANCE),
+			(TreasuryAccount::get(), INITIAL_BALANCE),
+		],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+	});
+	ext
+}
@@ -0,0 +1,355 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Relay chain runtime mock, paying the tips out to the beneficiaries on the parachains.
+
+use frame_support::{
+	construct_runtime, derive_impl, parameter_types,
+	traits::{
+		tokens::{pay::PayFromAccount, UnityAssetBalanceConversion},
+		ConstU128, ConstU64, Contains, ContainsLengthBound, Everything, NeverEnsureOrigin, Nothing,
+		PalletInfoAccess, SortedMembers,
+	},
+	weights::Weight,
+	PalletId,
+};
+
+use frame_system::EnsureRoot;
+use pallet_tips::LinearDataDepositCurve;
+use sp_core::{ConstU32, H256};
+use sp_runtime::{
+	traits::{AccountIdConversion, IdentityLookup},
+	Percent, Permill,
+};
+
+use polkadot_parachain_primitives::primitives::Id as ParaId;
+use polkadot_runtime_parachains::{configuration, origin, shared};
+use xcm::latest::prelude::*;
+use xcm_builder::{
+	AccountId32Aliases, AliasesIntoAccountId32, AllowExplicitUnpaidExecutionFrom,
+	AllowKnownQueryResponses, AllowSubscriptionsFrom, AllowTopLevelPaidExecutionFrom,
+	ChildParachainAsNative, ChildParachainConvertsVia, ChildSystemParachainAsSuperuser,
+	DescribeAllTerminal, DescribeFamily, FixedLocation, FixedRateOfFungible, FixedWeightBounds,
+	FrameTransactionalProcessor, FungibleAdapter, HashedDescription, IsConcrete, PayOverXcm,
+	SignedAccountId32AsNative, SignedToAccountId32, SovereignSignedViaLocation, WithComputedOrigin,
+};
+use xcm_executor::{Config, XcmExecutor};
+
+use super::{
+	mocks::relay_message_queue::*,
+	primitives::{AccountId, Balance, UNITS},
+};
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Runtime {
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Block = Block;
+	type Nonce = u64;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = Everything;
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+parameter_types! {
+	pub ExistentialDeposit: Balance = 1;
+	pub const MaxLocks: u32 = 50;
+	pub const MaxReserves: u32 = 50;
+}
+
+impl pallet_balances::Config for Runtime {
+	type MaxLocks = MaxLocks;
+	type Balance = Balance;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type MaxReserves = MaxReserves;
+	type ReserveIdentifier = [u8; 8];
+	type FreezeIdentifier = ();
+	type MaxFreezes = ConstU32<0>;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+}
+
+impl shared::Config for Runtime {
+	type DisabledValidators = ();
+}
+
+impl configuration::Config for Runtime {
+	type WeightInfo = configuration::TestWeightInfo;
+}
+
+parameter_types! {
+	pub RelayNetwork: NetworkId = ByGenesis([0; 32]);
+	pub const TokenLocation: Location = Here.into_location();
+	pub UniversalLocation: InteriorLocation = Here;
+	pub UnitWeightCost: u64 = 1_000;
+}
+
+pub type SovereignAccountOf = (
+	HashedDescription<AccountId, DescribeFamily<DescribeAllTerminal>>,
+	AccountId32Aliases<RelayNetwork, AccountId>,
+	ChildParachainConvertsVia<ParaId, AccountId>,
+);
+
+pub type LocalBalancesTransactor =
+	FungibleAdapter<Balances, IsConcrete<TokenLocation>, SovereignAccountOf, AccountId, ()>;
+
+pub type AssetTransactors = LocalBalancesTransactor;
+
+type LocalOriginConverter = (
+	SovereignSignedViaLocation<SovereignAccountOf, RuntimeOrigin>,
+	ChildParachainAsNative<origin::Origin, RuntimeOrigin>,
+	SignedAccountId32AsNative<RelayNetwork, RuntimeOrigin>,
+	ChildSystemParachainAsSuperuser<ParaId, RuntimeOrigin>,
+);
+
+parameter_types! {
+	pub const XcmInstructionWeight: Weight = Weight::from_parts(1_000, 1_000);
+	pub TokensPerSecondPerMegabyte: (AssetId, u128, u128) =
+		(AssetId(TokenLocation::get()), 1_000_000_000_000, 1024 * 1024);
+	pub const MaxInstructions: u32 = 100;
+	pub const MaxAssetsIntoHolding: u32 = 64;
+}
+
+pub struct ChildrenParachains;
+impl Contains<Location> for ChildrenParachains {
+	fn contains(location: &Location) -> bool {
+		matches!(location.unpack(), (0, [Parachain(_)]))
+	}
+}
+
+pub type XcmRouter = crate::RelayChainXcmRouter;
+pub type Barrier = WithComputedOrigin<
+	(
+		AllowKnownQueryResponses<XcmPallet>,
+		AllowExplicitUnpaidExecutionFrom<ChildrenParachains>,
+		AllowTopLevelPaidExecutionFrom<Everything>,
+		AllowSubscriptionsFrom<Everything>,
+	),
+	UniversalLocation,
+	ConstU32<1>,
+>;
+
+pub struct XcmConfig;
+impl Config for XcmConfig {
+	type RuntimeCall = RuntimeCall;
+	type XcmSender = XcmRouter;
+	type AssetTransactor = AssetTransactors;
+	type OriginConverter = LocalOriginConverter;
+	type IsReserve = ();
+	type IsTeleporter = ();
+	type UniversalLocation = UniversalLocation;
+	type Barrier = Barrier;
+	type Weigher = FixedWeightBounds<XcmInstructionWeight, RuntimeCall, MaxInstructions>;
+	type Trader = FixedRateOfFungible<TokensPerSecondPerMegabyte, ()>;
+	type ResponseHandler = XcmPallet;
+	type AssetTrap = XcmPallet;
+	type AssetLocker = XcmPallet;
+	type AssetExchanger = ();
+	type AssetClaims = XcmPallet;
+	type SubscriptionService = XcmPallet;
+	type PalletInstancesInfo = AllPalletsWithSystem;
+	type FeeManager = ();
+	type MaxAssetsIntoHolding = MaxAssetsIntoHolding;
+	type MessageExporter = ();
+	type UniversalAliases = Nothing;
+	type CallDispatcher = RuntimeCall;
+	type SafeCallFilter = Everything;
+	type Aliasers = Nothing;
+	type TransactionalProcessor = FrameTransactionalProcessor;
+	type HrmpNewChannelOpenRequestHandler = ();
+	type HrmpChannelAcceptedHandler = ();
+	type HrmpChannelClosingHandler = ();
+}
+
+pub type LocalOriginToLocation = SignedToAccountId32<RuntimeOrigin, AccountId, RelayNetwork>;
+
+impl pallet_xcm::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type SendXcmOrigin = xcm_builder::EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
+	type XcmRouter = XcmRouter;
+	type ExecuteXcmOrigin = xcm_builder::EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
+	type XcmExecuteFilter = Everything;
+	type XcmExecutor = XcmExecutor<XcmConfig>;
+	type XcmTeleportFilter = Everything;
+	type XcmReserveTransferFilter = Everything;
+	type Weigher = FixedWeightBounds<XcmInstructionWeight, RuntimeCall, MaxInstructions>;
+	type UniversalLocation = UniversalLocation;
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
+	type AdvertisedXcmVersion = pallet_xcm::CurrentXcmVersion;
+	type Currency = Balances;
+	type CurrencyMatcher = IsConcrete<TokenLocation>;
+	type TrustedLockers = ();
+	type SovereignAccountOf = SovereignAccountOf;
+	type MaxLockers = ConstU32<8>;
+	type MaxRemoteLockConsumers = ConstU32<0>;
+	type RemoteLockConsumerIdentifier = ();
+	type WeightInfo = pallet_xcm::TestWeightInfo;
+	type AdminOrigin = EnsureRoot<AccountId>;
+}
+
+impl origin::Config for Runtime {}
+
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+impl pallet_message_queue::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Size = u32;
+	type HeapSize = MessageQueueHeapSize;
+	type MaxStale = MessageQueueMaxStale;
+	type ServiceWeight = MessageQueueServiceWeight;
+	type MessageProcessor = MessageProcessor;
+	type QueueChangeHandler = ();
+	type WeightInfo = ();
+	type QueuePausedQuery = ();
+}
+
+parameter_types! {
+	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub TreasuryAccount: AccountId = TreasuryPalletId::get().into_account_truncating();
+	pub const ProposalBond: Permill = Permill::from_percent(5);
+	pub const Burn: Permill = Permill::from_percent(0);
+}
+
+impl pallet_treasury::Config for Runtime {
+	type PalletId = TreasuryPalletId;
+	type Currency = Balances;
+	type ApproveOrigin = EnsureRoot<AccountId>;
+	type RejectOrigin = EnsureRoot<AccountId>;
+	type RuntimeEvent = RuntimeEvent;
+	type OnSlash = ();
+	type ProposalBond = ProposalBond;
+	type ProposalBondMinimum = ConstU128<1>;
+	type ProposalBondMaximum = ();
+	type SpendPeriod = ConstU64<100>;
+	type Burn = Burn;
+	type BurnDestination = ();
+	type WeightInfo = ();
+	type SpendFunds = ();
+	type MaxApprovals = ConstU32<100>;
+	type SpendOrigin = NeverEnsureOrigin<Balance>;
+	type AssetKind = ();
+	type Beneficiary = AccountId;
+	type BeneficiaryLookup = IdentityLookup<Self::Beneficiary>;
+	type Paymaster = PayFromAccount<Balances, TreasuryAccount>;
+	type BalanceConverter = UnityAssetBalanceConversion;
+	type PayoutPeriod = ConstU64<10>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
+/// The tippers of the relay chain.
+pub struct Tippers;
+impl SortedMembers<AccountId> for Tippers {
+	fn sorted_members() -> Vec<AccountId> {
+		vec![crate::ALICE, crate::BOB]
+	}
+}
+impl ContainsLengthBound for Tippers {
+	fn max_len() -> usize {
+		2
+	}
+	fn min_len() -> usize {
+		0
+	}
+}
+
+parameter_types! {
+	/// The interior location of the treasury, controlling its funds on the parachains.
+	pub TreasuryInteriorLocation: InteriorLocation =
+		PalletInstance(<Treasury as PalletInfoAccess>::index() as u8).into();
+	/// The tips to the beneficiaries on other chains are paid out on `ParaA`.
+	pub PayoutParachain: Location = Parachain(crate::PAYOUT_PARA_ID).into_location();
+	/// The relay chain token, as seen by the parachains.
+	pub RelayTokenOnParachain: AssetId = AssetId(Location::parent());
+	pub const TipFindersFee: Percent = Percent::from_percent(20);
+}
+
+/// Pays the tips out of the account of the treasury on `ParaA`, in the relay chain token.
+pub type TreasuryPayOverXcm = PayOverXcm<
+	TreasuryInteriorLocation,
+	XcmRouter,
+	XcmPallet,
+	ConstU64<10>,
+	AccountId,
+	AssetId,
+	FixedLocation<PayoutParachain>,
+	AliasesIntoAccountId32<(), AccountId>,
+>;
+
+impl pallet_tips::Config for Runtime {
+	type MaximumReasonLength = ConstU32<16384>;
+	type Tippers = Tippers;
+	type TipCountdown = ConstU64<1>;
+	type TipFindersFee = TipFindersFee;
+	type TipReportDepositBase = ConstU128<1>;
+	type DataDeposit = LinearDataDepositCurve<ConstU128<1>>;
+	type MaxTipAmount = ConstU128<{ 1_000 * UNITS }>;
+	type MaxBeneficiaries = ConstU32<3>;
+	type MaxRecurringTips = ConstU32<2>;
+	type TipperCooldown = ConstU64<0>;
+	type MaxVotesPerBatch = ConstU32<20>;
+	type VestingClaimPeriod = ConstU64<5>;
+	type MaxVestingExpiriesPerBlock = ConstU32<1>;
+	type MatchOrigin = EnsureRoot<AccountId>;
+	type CrossChainBeneficiary = AccountId;
+	type CrossChainPaymentError = XcmError;
+	type CrossChainPaymaster = TreasuryPayOverXcm;
+	type CrossChainAssetKind = RelayTokenOnParachain;
+	type MaxTipAmountPerPeriod = ConstU128<{ 1_000 * UNITS }>;
+	type MaxDeferredTips = ConstU32<1>;
+	type MaxDeferredClosuresPerBlock = ConstU32<1>;
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+}
+
+construct_runtime!(
+	pub enum Runtime {
+		System: frame_system,
+		Balances: pallet_balances,
+		ParasOrigin: origin,
+		XcmPallet: pallet_xcm,
+		MessageQueue: pallet_message_queue,
+		Treasury: pallet_treasury,
+		Tips: pallet_tips,
+	}
+);
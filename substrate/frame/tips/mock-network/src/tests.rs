@@ -0,0 +1,131 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	parachain,
+	primitives::{AccountId, UNITS},
+	relay_chain::{self, RuntimeOrigin, System, Tips, TreasuryPayOverXcm},
+	relay_treasury_account_id, MockNet, ParaA, Relay, ALICE, BOB, CHARLIE, INITIAL_BALANCE,
+};
+use frame_support::{
+	assert_ok,
+	traits::{
+		fungible::{Inspect, Mutate},
+		tokens::{Pay, PaymentStatus},
+	},
+};
+use pallet_tips::Event;
+use xcm_simulator::TestExt;
+
+type ParaBalances = parachain::Balances;
+type RelayBalances = relay_chain::Balances;
+
+fn events() -> Vec<Event<relay_chain::Runtime>> {
+	let result =
+		System::events()
+			.into_iter()
+			.map(|r| r.event)
+			.filter_map(|e| {
+				if let relay_chain::RuntimeEvent::Tips(inner) = e {
+					Some(inner)
+				} else {
+					None
+				}
+			})
+			.collect();
+
+	System::reset_events();
+
+	result
+}
+
+/// Report a tip of `amount` to the `beneficiary` on `ParaA`, found by `ALICE`, and close it.
+/// Returns the ID of the cross-chain payment.
+fn tip_cross_chain(beneficiary: AccountId, amount: u128) -> u64 {
+	Relay::execute_with(|| {
+		assert_ok!(Tips::report_awesome_cross_chain(
+			RuntimeOrigin::signed(ALICE),
+			b"awesome.dot".to_vec(),
+			Box::new(beneficiary),
+		));
+		let hash = match events().last() {
+			Some(Event::NewTip { tip_hash }) => *tip_hash,
+			e => panic!("unexpected event: {:?}", e),
+		};
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(ALICE), hash, amount));
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(BOB), hash, amount));
+
+		System::set_block_number(2);
+		assert_ok!(Tips::close_tip(RuntimeOrigin::signed(BOB), hash));
+		match events().last() {
+			Some(Event::CrossChainPayoutSent { tip_hash, payout, payment_id }) => {
+				assert_eq!(*tip_hash, hash);
+				// the finder's fee is 20%
+				assert_eq!(*payout, amount * 4 / 5);
+				*payment_id
+			},
+			e => panic!("unexpected event: {:?}", e),
+		}
+	})
+}
+
+fn payment_status(payment_id: u64) -> PaymentStatus {
+	Relay::execute_with(|| <TreasuryPayOverXcm as Pay>::check_payment(payment_id))
+}
+
+#[test]
+fn cross_chain_tip_is_paid_out_on_parachain() {
+	MockNet::reset();
+
+	let payment_id = tip_cross_chain(CHARLIE, 100 * UNITS);
+
+	ParaA::execute_with(|| {
+		assert_eq!(ParaBalances::balance(&CHARLIE), 80 * UNITS);
+		assert_eq!(
+			ParaBalances::balance(&relay_treasury_account_id()),
+			INITIAL_BALANCE - 80 * UNITS
+		);
+	});
+	Relay::execute_with(|| {
+		// only the finder's fee is paid locally
+		assert_eq!(RelayBalances::balance(&CHARLIE), 0);
+		assert_eq!(
+			RelayBalances::balance(&relay_chain::TreasuryAccount::get()),
+			INITIAL_BALANCE - 20 * UNITS
+		);
+	});
+	// `ParaA` has reported the successful transfer back
+	assert_eq!(payment_status(payment_id), PaymentStatus::Success);
+}
+
+#[test]
+fn failed_cross_chain_payout_is_reported_by_parachain() {
+	MockNet::reset();
+
+	// the treasury can't afford the tip at `ParaA`
+	ParaA::execute_with(|| {
+		ParaBalances::set_balance(&relay_treasury_account_id(), 10 * UNITS);
+	});
+
+	let payment_id = tip_cross_chain(CHARLIE, 100 * UNITS);
+
+	ParaA::execute_with(|| {
+		assert_eq!(ParaBalances::balance(&CHARLIE), 0);
+		assert_eq!(ParaBalances::balance(&relay_treasury_account_id()), 10 * UNITS);
+	});
+	assert_eq!(payment_status(payment_id), PaymentStatus::Failure);
+}
@@ -34,15 +34,25 @@ const SEED: u32 = 0;
 // Create the pre-requisite information needed to create a `report_awesome`.
 fn setup_awesome<T: Config<I>, I: 'static>(length: u32) -> (T::AccountId, Vec<u8>, T::AccountId) {
 	let caller = whitelisted_caller();
-	let value = T::TipReportDepositBase::get() +
-		T::DataDepositPerByte::get() * length.into() +
-		T::Currency::minimum_balance();
+	let value = TipsMod::<T, I>::report_deposit(length) + T::Currency::minimum_balance();
 	let _ = T::Currency::make_free_balance_be(&caller, value);
 	let reason = vec![0; length as usize];
 	let awesome_person = account("awesome", 0, SEED);
 	(caller, reason, awesome_person)
 }
 
+// Create `b` beneficiaries of the split tip, whose shares sum up to 100%.
+fn setup_beneficiaries<T: Config<I>, I: 'static>(b: u32) -> BeneficiariesOf<T, I> {
+	let share = 100 / b;
+	let beneficiaries = (0..b)
+		.map(|i| {
+			let share = if i == 0 { 100 - share * (b - 1) } else { share };
+			(account("beneficiary", i, SEED), Percent::from_percent(share as u8))
+		})
+		.collect::<Vec<_>>();
+	BoundedVec::truncate_from(beneficiaries)
+}
+
 // Create the pre-requisite information needed to call `tip_new`.
 fn setup_tip<T: Config<I>, I: 'static>(
 	r: u32,
@@ -64,6 +74,29 @@ fn setup_tip<T: Config<I>, I: 'static>(
 	Ok((caller, reason, beneficiary, value))
 }
 
+// Schedule `n` recurring tips, which are all renewed at the next block.
+fn setup_recurring_tips<T: Config<I>, I: 'static>(
+	caller: &T::AccountId,
+	n: u32,
+) -> Result<(), &'static str> {
+	for i in 0..n {
+		let reason = (b"recurring", i).encode();
+		let beneficiary: T::AccountId = account("beneficiary", i, SEED);
+		TipsMod::<T, I>::schedule_recurring_tip(
+			RawOrigin::Signed(caller.clone()).into(),
+			reason.clone(),
+			T::Lookup::unlookup(beneficiary.clone()),
+			1u32.into(),
+			10,
+		)?;
+		// close the tip, so that it's re-opened at the renewal
+		let reason_hash = T::Hashing::hash(&reason[..]);
+		Reasons::<T, I>::remove(reason_hash);
+		Tips::<T, I>::remove(T::Hashing::hash_of(&(&reason_hash, &beneficiary)));
+	}
+	Ok(())
+}
+
 // Create `t` new tips for the tip proposal with `hash`.
 // This function automatically makes the tip able to close.
 fn create_tips<T: Config<I>, I: 'static>(
@@ -71,6 +104,9 @@ fn create_tips<T: Config<I>, I: 'static>(
 	hash: T::Hash,
 	value: BalanceOf<T, I>,
 ) -> Result<(), &'static str> {
+	// the tippers may have already declared their tip values
+	let now = frame_system::Pallet::<T>::block_number();
+	frame_system::Pallet::<T>::set_block_number(now.saturating_add(T::TipperCooldown::get()));
 	for i in 0..t {
 		let caller = account("member", i, SEED);
 		ensure!(T::Tippers::contains(&caller), "caller is not a tipper");
@@ -90,6 +126,42 @@ fn setup_pot_account<T: Config<I>, I: 'static>() {
 	let _ = T::Currency::make_free_balance_be(&pot_account, value);
 }
 
+// Record the last votes of the first `n` tippers on the tip with the given `hash`.
+fn setup_tip_votes<T: Config<I>, I: 'static>(hash: T::Hash, n: u32) {
+	let now = frame_system::Pallet::<T>::block_number();
+	for i in 0..n {
+		let tipper: T::AccountId = account("member", i, SEED);
+		LastTipBlock::<T, I>::insert(hash, tipper, now);
+	}
+}
+
+// Set up the worst case of a tip closure: a split tip between `MaxBeneficiaries` beneficiaries,
+// which pays a finder's fee and a matched amount and has been tipped by `t` tippers.
+fn setup_closing_tip<T: Config<I>, I: 'static>(t: u32) -> Result<T::Hash, &'static str> {
+	// Make sure pot is funded
+	setup_pot_account::<T, I>();
+
+	let (member, reason, _, value) = setup_tip::<T, I>(0, t)?;
+	let deposit = TipsMod::<T, I>::report_deposit(0) + T::Currency::minimum_balance();
+	let _ = T::Currency::make_free_balance_be(&member, deposit);
+	let beneficiaries = setup_beneficiaries::<T, I>(T::MaxBeneficiaries::get());
+	TipsMod::<T, I>::tip_new_split(
+		RawOrigin::Signed(member).into(),
+		reason.clone(),
+		beneficiaries.clone(),
+	)?;
+
+	// Create a bunch of tips
+	let reason_hash = T::Hashing::hash(&reason[..]);
+	let hash = T::Hashing::hash_of(&(&reason_hash, &beneficiaries));
+	ensure!(Tips::<T, I>::contains_key(hash), "tip does not exist");
+
+	create_tips::<T, I>(t, hash, value)?;
+	setup_tip_votes::<T, I>(hash, t);
+	MatchedTips::<T, I>::insert(hash, value);
+	Ok(hash)
+}
+
 benchmarks_instance_pallet! {
 	report_awesome {
 		let r in 0 .. T::MaximumReasonLength::get();
@@ -100,6 +172,26 @@ benchmarks_instance_pallet! {
 		frame_benchmarking::benchmarking::add_to_whitelist(caller_key.into());
 	}: _(RawOrigin::Signed(caller), reason, awesome_person_lookup)
 
+	report_awesome_cross_chain {
+		let r in 0 .. T::MaximumReasonLength::get();
+		let (caller, reason, _) = setup_awesome::<T, I>(r);
+		let beneficiary = T::CrossChainBeneficiary::decode(&mut TrailingZeroInput::zeroes())
+			.map_err(|_| BenchmarkError::Weightless)?;
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		frame_benchmarking::benchmarking::add_to_whitelist(caller_key.into());
+	}: _(RawOrigin::Signed(caller), reason, Box::new(beneficiary))
+
+	tip_new_split {
+		let r in 0 .. T::MaximumReasonLength::get();
+		let b in 1 .. T::MaxBeneficiaries::get();
+		let (caller, reason, _) = setup_awesome::<T, I>(r);
+		let beneficiaries = setup_beneficiaries::<T, I>(b);
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		frame_benchmarking::benchmarking::add_to_whitelist(caller_key.into());
+	}: _(RawOrigin::Signed(caller), reason, beneficiaries)
+
 	retract_tip {
 		let r = T::MaximumReasonLength::get();
 		let (caller, reason, awesome_person) = setup_awesome::<T, I>(r);
@@ -111,6 +203,8 @@ benchmarks_instance_pallet! {
 		)?;
 		let reason_hash = T::Hashing::hash(&reason[..]);
 		let hash = T::Hashing::hash_of(&(&reason_hash, &awesome_person));
+		setup_tip_votes::<T, I>(hash, T::Tippers::max_len() as u32);
+		MatchedTips::<T, I>::insert(hash, T::Currency::minimum_balance());
 		// Whitelist caller account from further DB operations.
 		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
 		frame_benchmarking::benchmarking::add_to_whitelist(caller_key.into());
@@ -151,32 +245,27 @@ benchmarks_instance_pallet! {
 	close_tip {
 		let t in 1 .. T::Tippers::max_len() as u32;
 
-		// Make sure pot is funded
-		setup_pot_account::<T, I>();
+		let hash = setup_closing_tip::<T, I>(t)?;
 
-		// Set up a new tip proposal
-		let (member, reason, beneficiary, value) = setup_tip::<T, I>(0, t)?;
-		let beneficiary_lookup = T::Lookup::unlookup(beneficiary.clone());
-		let value = T::Currency::minimum_balance().saturating_mul(100u32.into());
-		TipsMod::<T, I>::tip_new(
-			RawOrigin::Signed(member).into(),
-			reason.clone(),
-			beneficiary_lookup,
-			value
-		)?;
+		let caller = account("caller", t, SEED);
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		frame_benchmarking::benchmarking::add_to_whitelist(caller_key.into());
+	}: _(RawOrigin::Signed(caller), hash)
 
-		// Create a bunch of tips
-		let reason_hash = T::Hashing::hash(&reason[..]);
-		let hash = T::Hashing::hash_of(&(&reason_hash, &beneficiary));
-		ensure!(Tips::<T, I>::contains_key(hash), "tip does not exist");
+	close_tip_vested {
+		let t in 1 .. T::Tippers::max_len() as u32;
 
-		create_tips::<T, I>(t, hash, value)?;
+		let hash = setup_closing_tip::<T, I>(t)?;
 
 		let caller = account("caller", t, SEED);
 		// Whitelist caller account from further DB operations.
 		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
 		frame_benchmarking::benchmarking::add_to_whitelist(caller_key.into());
-	}: _(RawOrigin::Signed(caller), hash)
+	}: _(RawOrigin::Signed(caller), hash, 10u32.into())
+	verify {
+		ensure!(TipVestingSchedules::<T, I>::contains_key(hash), "tip payout is not vested");
+	}
 
 	slash_tip {
 		let t in 1 .. T::Tippers::max_len() as u32;
@@ -202,5 +291,109 @@ benchmarks_instance_pallet! {
 			T::RejectOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
 	}: _<T::RuntimeOrigin>(reject_origin, hash)
 
+	schedule_recurring_tip {
+		let r in 0 .. T::MaximumReasonLength::get();
+		let t in 1 .. T::Tippers::max_len() as u32;
+
+		let (caller, reason, beneficiary, _) = setup_tip::<T, I>(r, t)?;
+		// the renewal is scheduled next to the renewals of all other recurring tips
+		setup_recurring_tips::<T, I>(&caller, T::MaxRecurringTips::get().saturating_sub(1))?;
+		let beneficiary_lookup = T::Lookup::unlookup(beneficiary);
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		frame_benchmarking::benchmarking::add_to_whitelist(caller_key.into());
+	}: _(RawOrigin::Signed(caller), reason, beneficiary_lookup, 1u32.into(), 10)
+
+	cancel_recurring_tip {
+		let (caller, reason, beneficiary, _) = setup_tip::<T, I>(0, 1)?;
+		// the renewal is removed from the renewals of all other recurring tips
+		setup_recurring_tips::<T, I>(&caller, T::MaxRecurringTips::get().saturating_sub(1))?;
+		let beneficiary_lookup = T::Lookup::unlookup(beneficiary.clone());
+		TipsMod::<T, I>::schedule_recurring_tip(
+			RawOrigin::Signed(caller.clone()).into(),
+			reason.clone(),
+			beneficiary_lookup,
+			1u32.into(),
+			10,
+		)?;
+		let reason_hash = T::Hashing::hash(&reason[..]);
+		let hash = T::Hashing::hash_of(&(&reason_hash, &beneficiary));
+		ensure!(RecurringTips::<T, I>::contains_key(hash), "recurring tip does not exist");
+	}: _(RawOrigin::Signed(caller), hash)
+
+	renew_recurring_tips {
+		let n in 0 .. T::MaxRecurringTips::get();
+
+		let (caller, _, _, _) = setup_tip::<T, I>(0, 1)?;
+		setup_recurring_tips::<T, I>(&caller, n)?;
+		let now = frame_system::Pallet::<T>::block_number() + 1u32.into();
+	}: {
+		TipsMod::<T, I>::renew_recurring_tips(now);
+	}
+	verify {
+		ensure!(Tips::<T, I>::iter().count() == n as usize, "not all tips were re-opened");
+	}
+
+	tip_match {
+		let (caller, reason, beneficiary, value) = setup_tip::<T, I>(0, 1)?;
+		TipsMod::<T, I>::tip_new(
+			RawOrigin::Signed(caller).into(),
+			reason.clone(),
+			T::Lookup::unlookup(beneficiary.clone()),
+			value,
+		)?;
+		let reason_hash = T::Hashing::hash(&reason[..]);
+		let hash = T::Hashing::hash_of(&(&reason_hash, &beneficiary));
+		let match_origin =
+			T::MatchOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+	}: _<T::RuntimeOrigin>(match_origin, hash, value)
+	verify {
+		ensure!(MatchedTips::<T, I>::contains_key(hash), "tip is not matched");
+	}
+
+	cancel_tip_match {
+		let (caller, reason, beneficiary, value) = setup_tip::<T, I>(0, 1)?;
+		TipsMod::<T, I>::tip_new(
+			RawOrigin::Signed(caller).into(),
+			reason.clone(),
+			T::Lookup::unlookup(beneficiary.clone()),
+			value,
+		)?;
+		let reason_hash = T::Hashing::hash(&reason[..]);
+		let hash = T::Hashing::hash_of(&(&reason_hash, &beneficiary));
+		MatchedTips::<T, I>::insert(hash, value);
+		let match_origin =
+			T::MatchOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+	}: _<T::RuntimeOrigin>(match_origin, hash)
+	verify {
+		ensure!(!MatchedTips::<T, I>::contains_key(hash), "tip is still matched");
+	}
+
+	claim_tip_vesting {
+		let b in 1 .. T::MaxBeneficiaries::get();
+
+		let value = T::Currency::minimum_balance().saturating_mul(100u32.into());
+		let beneficiaries = setup_beneficiaries::<T, I>(b)
+			.into_iter()
+			.map(|(who, _)| (who, value, Zero::zero()))
+			.collect::<Vec<_>>();
+		let _ = T::Currency::make_free_balance_be(
+			&TipsMod::<T, I>::vesting_account(),
+			value.saturating_mul(b.into()).saturating_add(T::Currency::minimum_balance()),
+		);
+		let hash = T::Hashing::hash(&b"vested"[..]);
+		TipVestingSchedules::<T, I>::insert(hash, TipVestingSchedule {
+			beneficiaries: BoundedVec::truncate_from(beneficiaries),
+			start: Zero::zero(),
+			period: 10u32.into(),
+		});
+		// half of the payouts is vested
+		frame_system::Pallet::<T>::set_block_number(5u32.into());
+		let caller = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), hash)
+	verify {
+		ensure!(TipVestingSchedules::<T, I>::contains_key(hash), "vesting has been finished");
+	}
+
 	impl_benchmark_test_suite!(TipsMod, crate::tests::new_test_ext(), crate::tests::Test);
 }
@@ -27,6 +27,8 @@
 //! period is entered where any remaining members can declare their tip amounts also. After the
 //! close of the countdown period, the median of all declared tips is paid to the reported
 //! beneficiary, along with any finders fee, in case of a public (and bonded) original report.
+//! A tip may also be split between several beneficiaries, each receiving a fixed percentage of
+//! the paid amount.
 //!
 //!
 //! ### Terminology
@@ -47,10 +49,21 @@
 //!
 //! Tipping protocol:
 //! - `report_awesome` - Report something worthy of a tip and register for a finders fee.
+//! - `report_awesome_cross_chain` - Report something worthy of a tip for a beneficiary on another
+//!   chain and register for a finders fee.
+//! - `tip_new_split` - Report something worthy of a tip, split between several beneficiaries, and
+//!   register for a finders fee.
+//! - `schedule_recurring_tip` - Report an item worthy of a tip and re-open the tip periodically.
+//! - `cancel_recurring_tip` - Stop re-opening a recurring tip.
 //! - `retract_tip` - Retract a previous (finders fee registered) report.
 //! - `tip_new` - Report an item worthy of a tip and declare a specific amount to tip.
 //! - `tip` - Declare or redeclare an amount to tip for a particular reason.
+//! - `batch_tip` - Declare or redeclare the amounts to tip for several reasons at once.
+//! - `tip_match` - Promise to add an amount from the treasury to the payout of a tip.
+//! - `cancel_tip_match` - Withdraw the promise to match a tip.
+//! - `claim_tip_vesting` - Pay out the vested installments of a tip.
 //! - `close_tip` - Close and pay out a tip.
+//! - `close_tip_vested` - Close a tip and pay it out in installments over a number of blocks.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -61,19 +74,26 @@ pub mod migrations;
 pub mod weights;
 
 use sp_runtime::{
-	traits::{AccountIdConversion, BadOrigin, Hash, StaticLookup, TrailingZeroInput, Zero},
-	Percent, RuntimeDebug,
+	traits::{
+		AccountIdConversion, AtLeast32BitUnsigned, BadOrigin, Hash, One, Saturating, StaticLookup,
+		TrailingZeroInput, UniqueSaturatedInto, Zero,
+	},
+	DispatchError, DispatchResult, Percent, RuntimeDebug,
 };
-use sp_std::prelude::*;
+use sp_std::{marker::PhantomData, prelude::*};
 
 use codec::{Decode, Encode};
 use frame_support::{
-	ensure,
+	defensive, ensure,
+	storage::with_storage_layer,
 	traits::{
-		ContainsLengthBound, Currency, EnsureOrigin, ExistenceRequirement::KeepAlive, Get,
-		OnUnbalanced, ReservableCurrency, SortedMembers,
+		tokens::Pay,
+		ContainsLengthBound, Currency, EnsureOrigin,
+		ExistenceRequirement::{AllowDeath, KeepAlive},
+		Get, OnUnbalanced, ReservableCurrency, SortedMembers,
 	},
-	Parameter,
+	weights::Weight,
+	BoundedVec, CloneNoBound, EqNoBound, Parameter, PartialEqNoBound, RuntimeDebugNoBound,
 };
 use frame_system::pallet_prelude::BlockNumberFor;
 
@@ -88,21 +108,87 @@ const LOG_TARGET: &str = "runtime::tips";
 pub type BalanceOf<T, I = ()> = pallet_treasury::BalanceOf<T, I>;
 pub type NegativeImbalanceOf<T, I = ()> = pallet_treasury::NegativeImbalanceOf<T, I>;
 type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
+type BeneficiariesOf<T, I = ()> = BoundedVec<
+	(<T as frame_system::Config>::AccountId, Percent),
+	<T as Config<I>>::MaxBeneficiaries,
+>;
+type CrossChainPaymentIdOf<T, I = ()> = <<T as Config<I>>::CrossChainPaymaster as Pay>::Id;
+type TipVotesOf<T, I = ()> = BoundedVec<
+	(<T as frame_system::Config>::Hash, BalanceOf<T, I>),
+	<T as Config<I>>::MaxVotesPerBatch,
+>;
+/// The `OpenTip` type, used by the pallet.
+pub type OpenTipOf<T, I = ()> = OpenTip<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T, I>,
+	BlockNumberFor<T>,
+	<T as frame_system::Config>::Hash,
+	<T as Config<I>>::MaxBeneficiaries,
+>;
+/// The `TipVestingSchedule` type, used by the pallet.
+pub type TipVestingScheduleOf<T, I = ()> = TipVestingSchedule<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T, I>,
+	BlockNumberFor<T>,
+	<T as Config<I>>::MaxBeneficiaries,
+>;
+
+/// Computes the deposit, held for the tip reason of the given length.
+pub trait DataDepositCurve<Balance> {
+	/// Returns the deposit for the reason of `len` bytes.
+	fn deposit_for_length(len: u32) -> Balance;
+}
+
+/// Deposit of `PerByte` for every byte of the reason.
+pub struct LinearDataDepositCurve<PerByte>(PhantomData<PerByte>);
+impl<Balance: AtLeast32BitUnsigned, PerByte: Get<Balance>> DataDepositCurve<Balance>
+	for LinearDataDepositCurve<PerByte>
+{
+	fn deposit_for_length(len: u32) -> Balance {
+		PerByte::get().saturating_mul(len.into())
+	}
+}
+
+/// Deposit of `PerByte * len + PerByteSquared * len^2` for the reason of `len` bytes.
+pub struct QuadraticDataDepositCurve<PerByte, PerByteSquared>(
+	PhantomData<(PerByte, PerByteSquared)>,
+);
+impl<Balance: AtLeast32BitUnsigned, PerByte: Get<Balance>, PerByteSquared: Get<Balance>>
+	DataDepositCurve<Balance> for QuadraticDataDepositCurve<PerByte, PerByteSquared>
+{
+	fn deposit_for_length(len: u32) -> Balance {
+		let len: Balance = len.into();
+		PerByte::get()
+			.saturating_mul(len.clone())
+			.saturating_add(PerByteSquared::get().saturating_mul(len.clone()).saturating_mul(len))
+	}
+}
 
 /// An open tipping "motion". Retains all details of a tip including information on the finder
 /// and the members who have voted.
-#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, scale_info::TypeInfo)]
+#[derive(
+	CloneNoBound,
+	EqNoBound,
+	PartialEqNoBound,
+	Encode,
+	Decode,
+	RuntimeDebugNoBound,
+	scale_info::TypeInfo,
+)]
+#[scale_info(skip_type_params(MaxBeneficiaries))]
 pub struct OpenTip<
 	AccountId: Parameter,
 	Balance: Parameter,
 	BlockNumber: Parameter,
 	Hash: Parameter,
+	MaxBeneficiaries: Get<u32>,
 > {
 	/// The hash of the reason for the tip. The reason should be a human-readable UTF-8 encoded
 	/// string. A URL would be sensible.
 	reason: Hash,
-	/// The account to be tipped.
-	who: AccountId,
+	/// The accounts to be tipped, along with the share of the tip each of them receives. The
+	/// shares always sum up to 100%. Empty if the tip is paid to a beneficiary on another chain.
+	beneficiaries: BoundedVec<(AccountId, Percent), MaxBeneficiaries>,
 	/// The account who began this tip.
 	finder: AccountId,
 	/// The amount held on deposit for this tip.
@@ -116,6 +202,49 @@ pub struct OpenTip<
 	finders_fee: bool,
 }
 
+/// A recurring tip, which is re-opened every `interval` blocks.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, scale_info::TypeInfo)]
+pub struct RecurringTipConfig<AccountId, BlockNumber> {
+	/// The reason for the tip.
+	reason: Vec<u8>,
+	/// The account to be tipped.
+	who: AccountId,
+	/// The tipper who has scheduled the tip and who may cancel it.
+	owner: AccountId,
+	/// The number of blocks between two openings of the tip.
+	interval: BlockNumber,
+	/// The block at which the tip is re-opened next time.
+	next_renewal: BlockNumber,
+	/// How many more times the tip is going to be re-opened.
+	remaining_renewals: u32,
+}
+
+/// The payout of a closed tip, which is vested over a number of blocks.
+#[derive(
+	CloneNoBound,
+	EqNoBound,
+	PartialEqNoBound,
+	Encode,
+	Decode,
+	RuntimeDebugNoBound,
+	scale_info::TypeInfo,
+)]
+#[scale_info(skip_type_params(MaxBeneficiaries))]
+pub struct TipVestingSchedule<
+	AccountId: Parameter,
+	Balance: Parameter,
+	BlockNumber: Parameter,
+	MaxBeneficiaries: Get<u32>,
+> {
+	/// The beneficiaries of the tip, along with their total payouts and the amounts they have
+	/// already claimed.
+	beneficiaries: BoundedVec<(AccountId, Balance, Balance), MaxBeneficiaries>,
+	/// The block at which the vesting has started.
+	start: BlockNumber,
+	/// The number of blocks over which the payouts are vested.
+	period: BlockNumber,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -123,7 +252,7 @@ pub mod pallet {
 	use frame_system::pallet_prelude::*;
 
 	/// The in-code storage version.
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
 
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
@@ -142,9 +271,11 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaximumReasonLength: Get<u32>;
 
-		/// The amount held on deposit per byte within the tip report reason or bounty description.
-		#[pallet::constant]
-		type DataDepositPerByte: Get<BalanceOf<Self, I>>;
+		/// The amount held on deposit for the tip report reason, depending on its length.
+		///
+		/// Use [`LinearDataDepositCurve`] for a flat per-byte deposit or
+		/// [`QuadraticDataDepositCurve`] to make very long reasons prohibitively expensive.
+		type DataDeposit: DataDepositCurve<BalanceOf<Self, I>>;
 
 		/// The period for which a tip remains open after is has achieved threshold tippers.
 		#[pallet::constant]
@@ -162,6 +293,75 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxTipAmount: Get<BalanceOf<Self, I>>;
 
+		/// The maximum number of beneficiaries of a single tip.
+		#[pallet::constant]
+		type MaxBeneficiaries: Get<u32>;
+
+		/// The maximum total amount of tips, paid out within a single treasury spend period.
+		///
+		/// The payout of a single tip is capped at this amount.
+		#[pallet::constant]
+		type MaxTipAmountPerPeriod: Get<BalanceOf<Self, I>>;
+
+		/// The maximum number of tips, whose closure may be deferred to the next spend period.
+		#[pallet::constant]
+		type MaxDeferredTips: Get<u32>;
+
+		/// The maximum number of deferred tip closures, retried by the `on_initialize` of a single
+		/// block. The remaining closures are retried in the following blocks.
+		#[pallet::constant]
+		type MaxDeferredClosuresPerBlock: Get<u32>;
+
+		/// The maximum number of recurring tips.
+		///
+		/// All recurring tips may be re-opened by the `on_initialize` of the same block, so this
+		/// should be kept small.
+		#[pallet::constant]
+		type MaxRecurringTips: Get<u32>;
+
+		/// The number of blocks a tipper must wait before declaring a new tip value for the same
+		/// tip. Zero disables the cooldown.
+		#[pallet::constant]
+		type TipperCooldown: Get<BlockNumberFor<Self>>;
+
+		/// The maximum number of tip values, declared by a single `batch_tip` call.
+		#[pallet::constant]
+		type MaxVotesPerBatch: Get<u32>;
+
+		/// The number of blocks after the end of a tip vesting, within which the vested
+		/// installments must be claimed. Unclaimed installments are returned to the treasury
+		/// afterwards.
+		#[pallet::constant]
+		type VestingClaimPeriod: Get<BlockNumberFor<Self>>;
+
+		/// The maximum number of tip vestings, which expire at a single block. The expiries of the
+		/// vestings, started once the block is full, spill over to the following blocks.
+		#[pallet::constant]
+		type MaxVestingExpiriesPerBlock: Get<u32>;
+
+		/// The type by which the beneficiaries on other chains are identified, e.g. `Location`.
+		type CrossChainBeneficiary: Parameter;
+
+		/// The error, reported by the `CrossChainPaymaster` when a payout has failed.
+		type CrossChainPaymentError: Parameter;
+
+		/// Pays out the tips to the beneficiaries on other chains, e.g. `PayOverXcm`.
+		///
+		/// The payouts are expected to be funded by the treasury, e.g. from its sovereign account
+		/// on the beneficiary chain.
+		type CrossChainPaymaster: Pay<
+			Balance = BalanceOf<Self, I>,
+			Beneficiary = Self::CrossChainBeneficiary,
+			Error = Self::CrossChainPaymentError,
+		>;
+
+		/// The kind of asset in which the tips to the beneficiaries on other chains are paid.
+		type CrossChainAssetKind: Get<<Self::CrossChainPaymaster as Pay>::AssetKind>;
+
+		/// Origin from which the community tips may be matched from the treasury, e.g. root or
+		/// a collective that manages the treasury.
+		type MatchOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
 		/// Origin from which tippers must come.
 		///
 		/// `ContainsLengthBound::max_len` must be cost free (i.e. no storage read or heavy
@@ -178,13 +378,8 @@ pub mod pallet {
 	/// guaranteed to be a secure hash.
 	#[pallet::storage]
 	#[pallet::getter(fn tips)]
-	pub type Tips<T: Config<I>, I: 'static = ()> = StorageMap<
-		_,
-		Twox64Concat,
-		T::Hash,
-		OpenTip<T::AccountId, BalanceOf<T, I>, BlockNumberFor<T>, T::Hash>,
-		OptionQuery,
-	>;
+	pub type Tips<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::Hash, OpenTipOf<T, I>, OptionQuery>;
 
 	/// Simple preimage lookup from the reason's hash to the original data. Again, has an
 	/// insecure enumerable hash since the key is guaranteed to be the result of a secure hash.
@@ -193,6 +388,98 @@ pub mod pallet {
 	pub type Reasons<T: Config<I>, I: 'static = ()> =
 		StorageMap<_, Identity, T::Hash, Vec<u8>, OptionQuery>;
 
+	/// The total amount of tips, paid out within the current treasury spend period.
+	#[pallet::storage]
+	pub type TipPayoutsThisPeriod<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BalanceOf<T, I>, ValueQuery>;
+
+	/// Tips whose closure would have exceeded `MaxTipAmountPerPeriod` and which are closed at the
+	/// beginning of the next spend period instead. Tips that are retracted or slashed in the
+	/// meantime are skipped.
+	#[pallet::storage]
+	pub type DeferredTipClosures<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BoundedVec<T::Hash, T::MaxDeferredTips>, ValueQuery>;
+
+	/// The number of tips at the front of `DeferredTipClosures`, whose closure is still to be
+	/// retried within the current spend period.
+	#[pallet::storage]
+	pub type DeferredClosuresToRetry<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, u32, ValueQuery>;
+
+	/// Recurring tips, keyed by the hash of the tip they re-open.
+	#[pallet::storage]
+	pub type RecurringTips<T: Config<I>, I: 'static = ()> = CountedStorageMap<
+		_,
+		Twox64Concat,
+		T::Hash,
+		RecurringTipConfig<T::AccountId, BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	/// The recurring tips, which are re-opened at the given block.
+	#[pallet::storage]
+	pub type RecurringTipRenewals<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<T::Hash, T::MaxRecurringTips>,
+		ValueQuery,
+	>;
+
+	/// The block at which a tipper last declared a tip value for a tip. Only recorded while
+	/// `TipperCooldown` is non-zero.
+	///
+	/// The entries are removed when the tip is retracted, closed or slashed.
+	#[pallet::storage]
+	pub type LastTipBlock<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::Hash,
+		Twox64Concat,
+		T::AccountId,
+		BlockNumberFor<T>,
+		OptionQuery,
+	>;
+
+	/// The amounts, promised to be added from the treasury to the payout of the open tips.
+	///
+	/// Nothing is moved from the treasury until the tip is closed. The promise is dropped when
+	/// the tip is closed, retracted or slashed.
+	#[pallet::storage]
+	pub type MatchedTips<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::Hash, BalanceOf<T, I>, OptionQuery>;
+
+	/// The number of blocks over which the payout of an open tip is vested once the tip is
+	/// closed, as requested by the last `close_tip_vested` call. Only kept while the closure is
+	/// deferred. Tips without an entry are paid out immediately.
+	#[pallet::storage]
+	pub type TipVestingPeriods<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::Hash, BlockNumberFor<T>, OptionQuery>;
+
+	/// The payouts of the closed tips, which are being vested. The vested amounts are held by the
+	/// `vesting_account` until they are claimed.
+	#[pallet::storage]
+	pub type TipVestingSchedules<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::Hash, TipVestingScheduleOf<T, I>, OptionQuery>;
+
+	/// The tip vestings, which expire at the given block. The installments, which haven't been
+	/// claimed by then, are returned to the treasury. Vestings that have already been finished are
+	/// skipped.
+	#[pallet::storage]
+	pub type TipVestingExpiries<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<T::Hash, T::MaxVestingExpiriesPerBlock>,
+		ValueQuery,
+	>;
+
+	/// The beneficiaries on other chains of the open tips, reported with
+	/// `report_awesome_cross_chain`.
+	#[pallet::storage]
+	pub type CrossChainBeneficiaries<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::Hash, T::CrossChainBeneficiary, OptionQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config<I>, I: 'static = ()> {
@@ -200,12 +487,57 @@ pub mod pallet {
 		NewTip { tip_hash: T::Hash },
 		/// A tip suggestion has reached threshold and is closing.
 		TipClosing { tip_hash: T::Hash },
-		/// A tip suggestion has been closed.
+		/// A tip suggestion has been closed and `payout` has been paid to `who`. Tips, split
+		/// between several beneficiaries, emit this event once per beneficiary.
 		TipClosed { tip_hash: T::Hash, who: T::AccountId, payout: BalanceOf<T, I> },
 		/// A tip suggestion has been retracted.
 		TipRetracted { tip_hash: T::Hash },
 		/// A tip suggestion has been slashed.
 		TipSlashed { tip_hash: T::Hash, finder: T::AccountId, deposit: BalanceOf<T, I> },
+		/// A tip closure has been deferred to the next spend period.
+		TipClosureDeferred { tip_hash: T::Hash },
+		/// A tip has been dropped without a payout, because none of its tippers is a member of
+		/// `Tippers` anymore. The finder's deposit has been returned.
+		TipDropped { tip_hash: T::Hash },
+		/// A recurring tip has been scheduled.
+		RecurringTipScheduled { tip_hash: T::Hash },
+		/// A recurring tip has been cancelled.
+		RecurringTipCancelled { tip_hash: T::Hash },
+		/// A recurring tip has been re-opened for the last time.
+		RecurringTipExhausted { tip_hash: T::Hash },
+		/// The treasury has promised to add `amount` to the payout of a tip.
+		TipMatched { tip_hash: T::Hash, amount: BalanceOf<T, I> },
+		/// The promise to match a tip has been withdrawn.
+		TipMatchCancelled { tip_hash: T::Hash },
+		/// A tip has been closed and its payout is vested until the block `until`.
+		TipVestingStarted { tip_hash: T::Hash, until: BlockNumberFor<T> },
+		/// The vested `amount` of a tip payout has been paid to `who`.
+		TipVestingClaimed { tip_hash: T::Hash, who: T::AccountId, amount: BalanceOf<T, I> },
+		/// The vested `amount` of a tip payout couldn't be paid to `who`, e.g. because it is below
+		/// the existential deposit. It may be claimed again later.
+		TipVestingClaimFailed {
+			tip_hash: T::Hash,
+			who: T::AccountId,
+			amount: BalanceOf<T, I>,
+			error: DispatchError,
+		},
+		/// The vested installments of a tip have not been claimed in time and the `unclaimed`
+		/// amount has been returned to the treasury.
+		TipVestingExpired { tip_hash: T::Hash, unclaimed: BalanceOf<T, I> },
+		/// A tip has been closed and the payment of `payout` to its beneficiary on another chain
+		/// has been initiated.
+		CrossChainPayoutSent {
+			tip_hash: T::Hash,
+			payout: BalanceOf<T, I>,
+			payment_id: CrossChainPaymentIdOf<T, I>,
+		},
+		/// A tip has been closed, but the payment of `payout` to its beneficiary on another chain
+		/// has failed with the given `reason`. The payout is kept by the treasury.
+		CrossChainPayoutFailed {
+			tip_hash: T::Hash,
+			payout: BalanceOf<T, I>,
+			reason: T::CrossChainPaymentError,
+		},
 	}
 
 	#[pallet::error]
@@ -224,6 +556,28 @@ pub mod pallet {
 		StillOpen,
 		/// The tip cannot be claimed/closed because it's still in the countdown period.
 		Premature,
+		/// The shares of the tip beneficiaries don't sum up to 100%.
+		InvalidSplit,
+		/// The recurring tip is unknown.
+		UnknownRecurringTip,
+		/// The account attempting to cancel the recurring tip is not its owner.
+		NotRecurringTipOwner,
+		/// The interval of the recurring tip must be non-zero.
+		ZeroInterval,
+		/// There are too many recurring tips.
+		TooManyRecurringTips,
+		/// The tipper has declared a tip value for this tip too recently.
+		CooldownNotElapsed,
+		/// The tip is not matched by the treasury.
+		NotMatched,
+		/// The payout of the tip is not being vested.
+		UnknownTipVesting,
+		/// Nothing has been vested since the last claim.
+		NothingVested,
+		/// There are too many tips, whose closure is deferred to the next spend period.
+		TooManyDeferredTips,
+		/// The payout of the previous tip with the same hash is still being vested.
+		TipVestingInProgress,
 	}
 
 	#[pallet::call]
@@ -233,7 +587,7 @@ pub mod pallet {
 		/// The dispatch origin for this call must be _Signed_.
 		///
 		/// Payment: `TipReportDepositBase` will be reserved from the origin account, as well as
-		/// `DataDepositPerByte` for each byte in `reason`.
+		/// `DataDeposit` for the length of the `reason`.
 		///
 		/// - `reason`: The reason for, or the thing that deserves, the tip; generally this will be
 		///   a UTF-8-encoded URL.
@@ -264,14 +618,13 @@ pub mod pallet {
 			let hash = T::Hashing::hash_of(&(&reason_hash, &who));
 			ensure!(!Tips::<T, I>::contains_key(&hash), Error::<T, I>::AlreadyKnown);
 
-			let deposit = T::TipReportDepositBase::get() +
-				T::DataDepositPerByte::get() * (reason.len() as u32).into();
+			let deposit = Self::report_deposit(reason.len() as u32);
 			T::Currency::reserve(&finder, deposit)?;
 
 			Reasons::<T, I>::insert(&reason_hash, &reason);
 			let tip = OpenTip {
 				reason: reason_hash,
-				who,
+				beneficiaries: Self::single_beneficiary(who),
 				finder,
 				deposit,
 				closes: None,
@@ -297,8 +650,7 @@ pub mod pallet {
 		/// Emits `TipRetracted` if successful.
 		///
 		/// ## Complexity
-		/// - `O(1)`
-		///   - Depends on the length of `T::Hash` which is fixed.
+		/// - `O(T)` where `T` is the number of tippers, whose last votes are removed.
 		#[pallet::call_index(1)]
 		#[pallet::weight(<T as Config<I>>::WeightInfo::retract_tip())]
 		pub fn retract_tip(origin: OriginFor<T>, hash: T::Hash) -> DispatchResult {
@@ -308,6 +660,10 @@ pub mod pallet {
 
 			Reasons::<T, I>::remove(&tip.reason);
 			Tips::<T, I>::remove(&hash);
+			Self::clear_tip_votes(hash);
+			Self::drop_tip_match(hash);
+			TipVestingPeriods::<T, I>::remove(&hash);
+			CrossChainBeneficiaries::<T, I>::remove(&hash);
 			if !tip.deposit.is_zero() {
 				let err_amount = T::Currency::unreserve(&who, tip.deposit);
 				debug_assert!(err_amount.is_zero());
@@ -353,12 +709,13 @@ pub mod pallet {
 			ensure!(!Reasons::<T, I>::contains_key(&reason_hash), Error::<T, I>::AlreadyKnown);
 
 			let hash = T::Hashing::hash_of(&(&reason_hash, &who));
+			Self::note_tip_vote(hash, &tipper)?;
 			Reasons::<T, I>::insert(&reason_hash, &reason);
 			Self::deposit_event(Event::NewTip { tip_hash: hash });
 			let tips = vec![(tipper.clone(), tip_value)];
 			let tip = OpenTip {
 				reason: reason_hash,
-				who,
+				beneficiaries: Self::single_beneficiary(who),
 				finder: tipper,
 				deposit: Zero::zero(),
 				closes: None,
@@ -380,6 +737,9 @@ pub mod pallet {
 		/// - `tip_value`: The amount of tip that the sender would like to give. The median tip
 		///   value of active tippers will be given to the `who`.
 		///
+		/// A tipper may not redeclare its tip value within `TipperCooldown` blocks of its last
+		/// declaration for the same tip.
+		///
 		/// Emits `TipClosing` if the threshold of tippers has been reached and the countdown period
 		/// has started.
 		///
@@ -400,15 +760,7 @@ pub mod pallet {
 			let tipper = ensure_signed(origin)?;
 			ensure!(T::Tippers::contains(&tipper), BadOrigin);
 
-			ensure!(T::MaxTipAmount::get() >= tip_value, Error::<T, I>::MaxTipAmountExceeded);
-
-			let mut tip = Tips::<T, I>::get(hash).ok_or(Error::<T, I>::UnknownTip)?;
-
-			if Self::insert_tip_and_check_closing(&mut tip, tipper, tip_value) {
-				Self::deposit_event(Event::TipClosing { tip_hash: hash });
-			}
-			Tips::<T, I>::insert(&hash, tip);
-			Ok(())
+			Self::do_tip(tipper, hash, tip_value)
 		}
 
 		/// Close and payout a tip.
@@ -417,9 +769,19 @@ pub mod pallet {
 		///
 		/// The tip identified by `hash` must have finished its countdown period.
 		///
+		/// If paying the tip out would exceed `MaxTipAmountPerPeriod` within the current spend
+		/// period, the closure is deferred to the beginning of the next spend period. At most
+		/// `MaxDeferredTips` closures may be deferred at once.
+		///
+		/// If none of the tippers is a member of `Tippers` anymore, the tip is dropped without a
+		/// payout and the finder's deposit is returned.
+		///
 		/// - `hash`: The identity of the open tip for which a tip value is declared. This is formed
 		///   as the hash of the tuple of the original tip `reason` and the beneficiary account ID.
 		///
+		/// Emits `TipClosureDeferred` if the closure has been deferred or `TipDropped` if the tip
+		/// has been dropped.
+		///
 		/// ## Complexity
 		/// - : `O(T)` where `T` is the number of tippers. decoding `Tipper` vec of length `T`. `T`
 		///   is charged as upper bound given by `ContainsLengthBound`. The actual cost depends on
@@ -429,14 +791,7 @@ pub mod pallet {
 		pub fn close_tip(origin: OriginFor<T>, hash: T::Hash) -> DispatchResult {
 			ensure_signed(origin)?;
 
-			let tip = Tips::<T, I>::get(hash).ok_or(Error::<T, I>::UnknownTip)?;
-			let n = tip.closes.as_ref().ok_or(Error::<T, I>::StillOpen)?;
-			ensure!(frame_system::Pallet::<T>::block_number() >= *n, Error::<T, I>::Premature);
-			// closed.
-			Reasons::<T, I>::remove(&tip.reason);
-			Tips::<T, I>::remove(hash);
-			Self::payout_tip(hash, tip);
-			Ok(())
+			Self::do_close_tip(hash, Zero::zero())
 		}
 
 		/// Remove and slash an already-open tip.
@@ -461,6 +816,10 @@ pub mod pallet {
 				T::OnSlash::on_unbalanced(imbalance);
 			}
 			Reasons::<T, I>::remove(&tip.reason);
+			Self::clear_tip_votes(hash);
+			Self::drop_tip_match(hash);
+			TipVestingPeriods::<T, I>::remove(&hash);
+			CrossChainBeneficiaries::<T, I>::remove(&hash);
 			Self::deposit_event(Event::TipSlashed {
 				tip_hash: hash,
 				finder: tip.finder,
@@ -468,15 +827,450 @@ pub mod pallet {
 			});
 			Ok(())
 		}
+
+		/// Report something `reason` that deserves a tip, which is split between several
+		/// `beneficiaries`, and claim any eventual the finder's fee.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Payment: `TipReportDepositBase` will be reserved from the origin account, as well as
+		/// `DataDeposit` for the length of the `reason`.
+		///
+		/// - `reason`: The reason for, or the thing that deserves, the tip; generally this will be
+		///   a UTF-8-encoded URL.
+		/// - `beneficiaries`: The accounts which should be credited for the tip, along with the
+		///   share of the tip each of them receives. The shares must sum up to 100%. Any remainder
+		///   left after splitting the tip goes to the first beneficiary.
+		///
+		/// Emits `NewTip` if successful.
+		///
+		/// ## Complexity
+		/// - `O(R + B)` where `R` length of `reason` and `B` is the number of beneficiaries.
+		///   - encoding and hashing of 'reason' and 'beneficiaries'
+		#[pallet::call_index(6)]
+		#[pallet::weight(<T as Config<I>>::WeightInfo::tip_new_split(
+			reason.len() as u32,
+			beneficiaries.len() as u32,
+		))]
+		pub fn tip_new_split(
+			origin: OriginFor<T>,
+			reason: Vec<u8>,
+			beneficiaries: BeneficiariesOf<T, I>,
+		) -> DispatchResult {
+			let finder = ensure_signed(origin)?;
+
+			ensure!(
+				reason.len() <= T::MaximumReasonLength::get() as usize,
+				Error::<T, I>::ReasonTooBig
+			);
+			let total_share = beneficiaries
+				.iter()
+				.fold(0u32, |total, (_, share)| total.saturating_add(share.deconstruct().into()));
+			ensure!(total_share == 100, Error::<T, I>::InvalidSplit);
+
+			let reason_hash = T::Hashing::hash(&reason[..]);
+			ensure!(!Reasons::<T, I>::contains_key(&reason_hash), Error::<T, I>::AlreadyKnown);
+			let hash = T::Hashing::hash_of(&(&reason_hash, &beneficiaries));
+			ensure!(!Tips::<T, I>::contains_key(&hash), Error::<T, I>::AlreadyKnown);
+
+			let deposit = Self::report_deposit(reason.len() as u32);
+			T::Currency::reserve(&finder, deposit)?;
+
+			Reasons::<T, I>::insert(&reason_hash, &reason);
+			let tip = OpenTip {
+				reason: reason_hash,
+				beneficiaries,
+				finder,
+				deposit,
+				closes: None,
+				tips: vec![],
+				finders_fee: true,
+			};
+			Tips::<T, I>::insert(&hash, tip);
+			Self::deposit_event(Event::NewTip { tip_hash: hash });
+			Ok(())
+		}
+
+		/// Open a tip for something new and re-open it every `interval_blocks` blocks, once the
+		/// previous tip is closed; no finder's fee will be taken.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the signing account must be a
+		/// member of the `Tippers` set.
+		///
+		/// - `reason`: The reason for, or the thing that deserves, the tip; generally this will be
+		///   a UTF-8-encoded URL.
+		/// - `who`: The account which should be credited for the tip.
+		/// - `interval_blocks`: The number of blocks between two openings of the tip. If the
+		///   previous tip is still open when the interval elapses, the tip is re-opened after the
+		///   next interval.
+		/// - `max_renewals`: How many times the tip is re-opened after the initial opening.
+		///
+		/// Emits `NewTip` and `RecurringTipScheduled` if successful.
+		///
+		/// ## Complexity
+		/// - `O(R + T)` where `R` length of `reason`, `T` is the number of tippers.
+		#[pallet::call_index(7)]
+		#[pallet::weight(<T as Config<I>>::WeightInfo::schedule_recurring_tip(
+			reason.len() as u32,
+			T::Tippers::max_len() as u32,
+		))]
+		pub fn schedule_recurring_tip(
+			origin: OriginFor<T>,
+			reason: Vec<u8>,
+			who: AccountIdLookupOf<T>,
+			interval_blocks: BlockNumberFor<T>,
+			max_renewals: u32,
+		) -> DispatchResult {
+			let tipper = ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			ensure!(T::Tippers::contains(&tipper), BadOrigin);
+
+			ensure!(
+				reason.len() <= T::MaximumReasonLength::get() as usize,
+				Error::<T, I>::ReasonTooBig
+			);
+			ensure!(!interval_blocks.is_zero(), Error::<T, I>::ZeroInterval);
+
+			let reason_hash = T::Hashing::hash(&reason[..]);
+			ensure!(!Reasons::<T, I>::contains_key(&reason_hash), Error::<T, I>::AlreadyKnown);
+			let hash = T::Hashing::hash_of(&(&reason_hash, &who));
+			ensure!(!RecurringTips::<T, I>::contains_key(&hash), Error::<T, I>::AlreadyKnown);
+			ensure!(
+				RecurringTips::<T, I>::count() < T::MaxRecurringTips::get(),
+				Error::<T, I>::TooManyRecurringTips
+			);
+
+			let next_renewal = if max_renewals > 0 {
+				let next_renewal = Self::schedule_recurring_tip_renewal(
+					hash,
+					frame_system::Pallet::<T>::block_number() + interval_blocks,
+				)
+				.ok_or(Error::<T, I>::TooManyRecurringTips)?;
+				Some(next_renewal)
+			} else {
+				None
+			};
+
+			Self::open_recurring_tip(hash, &reason, who.clone(), tipper.clone());
+			if let Some(next_renewal) = next_renewal {
+				RecurringTips::<T, I>::insert(
+					&hash,
+					RecurringTipConfig {
+						reason,
+						who,
+						owner: tipper,
+						interval: interval_blocks,
+						next_renewal,
+						remaining_renewals: max_renewals,
+					},
+				);
+				Self::deposit_event(Event::RecurringTipScheduled { tip_hash: hash });
+			}
+			Ok(())
+		}
+
+		/// Stop re-opening a recurring tip. The currently open tip, if any, is not affected.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the signing account must be the
+		/// account, which has scheduled the recurring tip.
+		///
+		/// - `hash`: The identity of the recurring tip. This is formed as the hash of the tuple of
+		///   the original tip `reason` and the beneficiary account ID.
+		///
+		/// Emits `RecurringTipCancelled` if successful.
+		///
+		/// ## Complexity
+		/// - `O(1)`
+		#[pallet::call_index(8)]
+		#[pallet::weight(<T as Config<I>>::WeightInfo::cancel_recurring_tip())]
+		pub fn cancel_recurring_tip(origin: OriginFor<T>, hash: T::Hash) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let recurring_tip =
+				RecurringTips::<T, I>::get(&hash).ok_or(Error::<T, I>::UnknownRecurringTip)?;
+			ensure!(recurring_tip.owner == who, Error::<T, I>::NotRecurringTipOwner);
+
+			RecurringTips::<T, I>::remove(&hash);
+			RecurringTipRenewals::<T, I>::mutate_exists(recurring_tip.next_renewal, |hashes| {
+				if let Some(renewed) = hashes {
+					renewed.retain(|renewed_hash| *renewed_hash != hash);
+					if renewed.is_empty() {
+						*hashes = None;
+					}
+				}
+			});
+			Self::deposit_event(Event::RecurringTipCancelled { tip_hash: hash });
+			Ok(())
+		}
+
+		/// Declare tip values for several already-open tips.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the signing account must be a
+		/// member of the `Tippers` set.
+		///
+		/// The votes are applied in order, as if `tip` was called for each of them. If any of the
+		/// votes fails, none of them is applied.
+		///
+		/// - `votes`: The identities of the open tips, along with the tip values the sender would
+		///   like to give.
+		///
+		/// Emits `TipClosing` for every tip whose threshold of tippers has been reached.
+		///
+		/// ## Complexity
+		/// - `O(V * T)` where `V` is the number of votes and `T` is the number of tippers.
+		#[pallet::call_index(9)]
+		#[pallet::weight(<T as Config<I>>::WeightInfo::tip(T::Tippers::max_len() as u32)
+			.saturating_mul(votes.len() as u64))]
+		pub fn batch_tip(origin: OriginFor<T>, votes: TipVotesOf<T, I>) -> DispatchResult {
+			let tipper = ensure_signed(origin)?;
+			ensure!(T::Tippers::contains(&tipper), BadOrigin);
+
+			for (hash, tip_value) in votes {
+				Self::do_tip(tipper.clone(), hash, tip_value)?;
+			}
+			Ok(())
+		}
+
+		/// Promise to add `match_amount` from the treasury to the payout of an open tip.
+		///
+		/// May only be called from `T::MatchOrigin`.
+		///
+		/// The matched amount is added to the median of the declared tip values when the tip is
+		/// closed, and the total payout is capped at the treasury balance. Matching an already
+		/// matched tip replaces the previous amount.
+		///
+		/// - `hash`: The identity of the open tip. This is formed as the hash of the tuple of the
+		///   original tip `reason` and the beneficiary account ID.
+		/// - `match_amount`: The amount the treasury would like to add to the tip.
+		///
+		/// Emits `TipMatched` if successful.
+		///
+		/// ## Complexity
+		/// - `O(1)`
+		#[pallet::call_index(10)]
+		#[pallet::weight(<T as Config<I>>::WeightInfo::tip_match())]
+		pub fn tip_match(
+			origin: OriginFor<T>,
+			hash: T::Hash,
+			#[pallet::compact] match_amount: BalanceOf<T, I>,
+		) -> DispatchResult {
+			T::MatchOrigin::ensure_origin(origin)?;
+
+			ensure!(T::MaxTipAmount::get() >= match_amount, Error::<T, I>::MaxTipAmountExceeded);
+			ensure!(Tips::<T, I>::contains_key(&hash), Error::<T, I>::UnknownTip);
+
+			MatchedTips::<T, I>::insert(&hash, match_amount);
+			Self::deposit_event(Event::TipMatched { tip_hash: hash, amount: match_amount });
+			Ok(())
+		}
+
+		/// Withdraw the promise to match a tip.
+		///
+		/// May only be called from `T::MatchOrigin`.
+		///
+		/// - `hash`: The identity of the matched tip.
+		///
+		/// Emits `TipMatchCancelled` if successful.
+		///
+		/// ## Complexity
+		/// - `O(1)`
+		#[pallet::call_index(11)]
+		#[pallet::weight(<T as Config<I>>::WeightInfo::cancel_tip_match())]
+		pub fn cancel_tip_match(origin: OriginFor<T>, hash: T::Hash) -> DispatchResult {
+			T::MatchOrigin::ensure_origin(origin)?;
+
+			ensure!(Self::drop_tip_match(hash), Error::<T, I>::NotMatched);
+			Ok(())
+		}
+
+		/// Pay out the installments of a vested tip payout, which have not been claimed yet.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// All beneficiaries of the tip are paid at once. A beneficiary that can't be paid, e.g.
+		/// because its installment is below the existential deposit, is skipped and may be paid
+		/// by a later claim. If the installments have not been claimed within
+		/// `VestingClaimPeriod` blocks after the end of the vesting, the unclaimed amount is
+		/// returned to the treasury instead. This is also done automatically at the beginning of
+		/// the first block after the claim period.
+		///
+		/// - `hash`: The identity of the closed tip.
+		///
+		/// Emits `TipVestingClaimed` for every paid beneficiary, `TipVestingClaimFailed` for every
+		/// skipped one, or `TipVestingExpired`.
+		///
+		/// ## Complexity
+		/// - `O(B)` where `B` is the number of beneficiaries.
+		#[pallet::call_index(13)]
+		#[pallet::weight(<T as Config<I>>::WeightInfo::claim_tip_vesting(
+			T::MaxBeneficiaries::get(),
+		))]
+		pub fn claim_tip_vesting(origin: OriginFor<T>, hash: T::Hash) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let mut schedule =
+				TipVestingSchedules::<T, I>::get(&hash).ok_or(Error::<T, I>::UnknownTipVesting)?;
+			let vesting_account = Self::vesting_account();
+			let elapsed = frame_system::Pallet::<T>::block_number().saturating_sub(schedule.start);
+
+			if elapsed > schedule.period.saturating_add(T::VestingClaimPeriod::get()) {
+				Self::expire_tip_vesting(hash, schedule);
+				return Ok(())
+			}
+
+			let mut vested_any = false;
+			for (who, total, claimed) in schedule.beneficiaries.iter_mut() {
+				let vested = Self::vested_amount(*total, elapsed, schedule.period);
+				let amount = vested.saturating_sub(*claimed);
+				if amount.is_zero() {
+					continue
+				}
+				vested_any = true;
+
+				// a beneficiary that can't be paid must not block the payouts of the others
+				if let Err(error) = with_storage_layer(|| {
+					T::Currency::transfer(&vesting_account, who, amount, AllowDeath)
+				}) {
+					Self::deposit_event(Event::TipVestingClaimFailed {
+						tip_hash: hash,
+						who: who.clone(),
+						amount,
+						error,
+					});
+					continue
+				}
+				*claimed = vested;
+				Self::deposit_event(Event::TipVestingClaimed {
+					tip_hash: hash,
+					who: who.clone(),
+					amount,
+				});
+			}
+			ensure!(vested_any, Error::<T, I>::NothingVested);
+
+			if schedule.beneficiaries.iter().all(|(_, total, claimed)| claimed == total) {
+				TipVestingSchedules::<T, I>::remove(&hash);
+			} else {
+				TipVestingSchedules::<T, I>::insert(&hash, schedule);
+			}
+			Ok(())
+		}
+
+		/// Report something `reason` that deserves a tip, which is paid to a `beneficiary` on
+		/// another chain, and claim any eventual the finder's fee.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Payment: `TipReportDepositBase` will be reserved from the origin account, as well as
+		/// `DataDeposit` for the length of the `reason`.
+		///
+		/// The tip is paid out with the `CrossChainPaymaster` when it is closed. The finder's fee
+		/// is paid locally.
+		///
+		/// - `reason`: The reason for, or the thing that deserves, the tip; generally this will be
+		///   a UTF-8-encoded URL.
+		/// - `beneficiary`: The beneficiary on another chain, which should be credited for the tip.
+		///
+		/// Emits `NewTip` if successful.
+		///
+		/// ## Complexity
+		/// - `O(R)` where `R` length of `reason`.
+		///   - encoding and hashing of 'reason'
+		#[pallet::call_index(14)]
+		#[pallet::weight(<T as Config<I>>::WeightInfo::report_awesome_cross_chain(
+			reason.len() as u32,
+		))]
+		pub fn report_awesome_cross_chain(
+			origin: OriginFor<T>,
+			reason: Vec<u8>,
+			beneficiary: Box<T::CrossChainBeneficiary>,
+		) -> DispatchResult {
+			let finder = ensure_signed(origin)?;
+
+			ensure!(
+				reason.len() <= T::MaximumReasonLength::get() as usize,
+				Error::<T, I>::ReasonTooBig
+			);
+
+			let reason_hash = T::Hashing::hash(&reason[..]);
+			ensure!(!Reasons::<T, I>::contains_key(&reason_hash), Error::<T, I>::AlreadyKnown);
+			let hash = T::Hashing::hash_of(&(&reason_hash, &beneficiary));
+			ensure!(!Tips::<T, I>::contains_key(&hash), Error::<T, I>::AlreadyKnown);
+
+			let deposit = Self::report_deposit(reason.len() as u32);
+			T::Currency::reserve(&finder, deposit)?;
+
+			Reasons::<T, I>::insert(&reason_hash, &reason);
+			CrossChainBeneficiaries::<T, I>::insert(&hash, *beneficiary);
+			let tip = OpenTip {
+				reason: reason_hash,
+				beneficiaries: BoundedVec::new(),
+				finder,
+				deposit,
+				closes: None,
+				tips: vec![],
+				finders_fee: true,
+			};
+			Tips::<T, I>::insert(&hash, tip);
+			Self::deposit_event(Event::NewTip { tip_hash: hash });
+			Ok(())
+		}
+
+		/// Close a tip and vest its payout over `vesting_blocks` blocks.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Same as `close_tip`, but the payout is not made immediately. The finder's fee is still
+		/// paid out immediately. The rest of the payout is moved to the `vesting_account` and may
+		/// be claimed in equal installments with `claim_tip_vesting`. The tips to the
+		/// beneficiaries on other chains are always paid out immediately. If `vesting_blocks` is
+		/// zero, the payout is made immediately, like with `close_tip`.
+		///
+		/// - `hash`: The identity of the open tip for which a tip value is declared. This is formed
+		///   as the hash of the tuple of the original tip `reason` and the beneficiary account ID.
+		/// - `vesting_blocks`: The number of blocks over which the payout is vested.
+		///
+		/// Emits `TipClosureDeferred` if the closure has been deferred.
+		///
+		/// ## Complexity
+		/// - : `O(T)` where `T` is the number of tippers. decoding `Tipper` vec of length `T`. `T`
+		///   is charged as upper bound given by `ContainsLengthBound`. The actual cost depends on
+		///   the implementation of `T::Tippers`.
+		#[pallet::call_index(15)]
+		#[pallet::weight(<T as Config<I>>::WeightInfo::close_tip_vested(T::Tippers::max_len() as u32))]
+		pub fn close_tip_vested(
+			origin: OriginFor<T>,
+			hash: T::Hash,
+			vesting_blocks: BlockNumberFor<T>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			Self::do_close_tip(hash, vesting_blocks)
+		}
 	}
 
 	#[pallet::hooks]
 	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+			let mut weight = Self::renew_recurring_tips(n);
+			if (n % <T as pallet_treasury::Config<I>>::SpendPeriod::get()).is_zero() {
+				weight.saturating_accrue(Self::start_payout_period());
+			}
+			weight.saturating_accrue(Self::retry_deferred_tip_closures());
+			weight.saturating_accrue(Self::expire_tip_vestings(n));
+			weight
+		}
+
 		fn integrity_test() {
 			assert!(
 				!T::TipReportDepositBase::get().is_zero(),
 				"`TipReportDepositBase` should not be zero",
 			);
+			assert!(!T::MaxBeneficiaries::get().is_zero(), "`MaxBeneficiaries` should not be zero");
+			assert!(
+				T::MaximumReasonLength::get().is_zero() ||
+					!T::DataDeposit::deposit_for_length(T::MaximumReasonLength::get()).is_zero(),
+				"`DataDeposit` should not be zero",
+			);
 		}
 
 		#[cfg(feature = "try-runtime")]
@@ -497,12 +1291,137 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		T::PalletId::get().into_account_truncating()
 	}
 
+	/// The account which holds the vested tip payouts until they are claimed.
+	pub fn vesting_account() -> T::AccountId {
+		T::PalletId::get().into_sub_account_truncating(b"tip-vesting")
+	}
+
+	/// The amount held on deposit for reporting a tip with the reason of `reason_len` bytes.
+	pub fn report_deposit(reason_len: u32) -> BalanceOf<T, I> {
+		T::TipReportDepositBase::get()
+			.saturating_add(T::DataDeposit::deposit_for_length(reason_len))
+	}
+
+	/// Declare the `tip_value` of `tipper` for the open tip with the given `hash`.
+	fn do_tip(tipper: T::AccountId, hash: T::Hash, tip_value: BalanceOf<T, I>) -> DispatchResult {
+		ensure!(T::MaxTipAmount::get() >= tip_value, Error::<T, I>::MaxTipAmountExceeded);
+
+		let mut tip = Tips::<T, I>::get(hash).ok_or(Error::<T, I>::UnknownTip)?;
+		Self::note_tip_vote(hash, &tipper)?;
+
+		if Self::insert_tip_and_check_closing(&mut tip, tipper, tip_value) {
+			Self::deposit_event(Event::TipClosing { tip_hash: hash });
+		}
+		Tips::<T, I>::insert(&hash, tip);
+		Ok(())
+	}
+
+	/// Returns beneficiaries of a tip, which is entirely paid to `who`.
+	fn single_beneficiary(who: T::AccountId) -> BeneficiariesOf<T, I> {
+		BoundedVec::truncate_from(vec![(who, Percent::one())])
+	}
+
+	/// Open a tip on behalf of the recurring tip with the given `hash`.
+	fn open_recurring_tip(hash: T::Hash, reason: &[u8], who: T::AccountId, finder: T::AccountId) {
+		let reason_hash = T::Hashing::hash(reason);
+		Reasons::<T, I>::insert(&reason_hash, reason);
+		let tip = OpenTip {
+			reason: reason_hash,
+			beneficiaries: Self::single_beneficiary(who),
+			finder,
+			deposit: Zero::zero(),
+			closes: None,
+			tips: vec![],
+			finders_fee: false,
+		};
+		Tips::<T, I>::insert(&hash, tip);
+		Self::deposit_event(Event::NewTip { tip_hash: hash });
+	}
+
+	/// Schedule the recurring tip with the given `hash` to be re-opened at the block `at`.
+	///
+	/// If the renewals of block `at` are full, the renewal spills over to the first of the
+	/// following `MaxRecurringTips` blocks with room for it. Returns the block at which the
+	/// renewal has been scheduled, or `None` if none of these blocks has room for it.
+	fn schedule_recurring_tip_renewal(
+		hash: T::Hash,
+		mut at: BlockNumberFor<T>,
+	) -> Option<BlockNumberFor<T>> {
+		for _ in 0..T::MaxRecurringTips::get() {
+			if RecurringTipRenewals::<T, I>::try_mutate(at, |hashes| hashes.try_push(hash)).is_ok()
+			{
+				return Some(at)
+			}
+			at = at.saturating_add(One::one());
+		}
+		None
+	}
+
+	/// Schedule the next renewal of the recurring tip with the given `hash` and store its
+	/// updated configuration. The recurring tip is dropped if its renewal can't be scheduled.
+	fn reschedule_recurring_tip(
+		hash: T::Hash,
+		mut recurring_tip: RecurringTipConfig<T::AccountId, BlockNumberFor<T>>,
+	) {
+		match Self::schedule_recurring_tip_renewal(hash, recurring_tip.next_renewal) {
+			Some(next_renewal) => {
+				recurring_tip.next_renewal = next_renewal;
+				RecurringTips::<T, I>::insert(&hash, recurring_tip);
+			},
+			None => {
+				defensive!(
+					"there are at most `MaxRecurringTips` recurring tips, each scheduled once"
+				);
+				RecurringTips::<T, I>::remove(&hash);
+				Self::deposit_event(Event::RecurringTipExhausted { tip_hash: hash });
+			},
+		}
+	}
+
+	/// Re-open all recurring tips, scheduled to be re-opened at block `now`.
+	///
+	/// `O(N)` where `N` is the number of recurring tips, scheduled at block `now`.
+	fn renew_recurring_tips(now: BlockNumberFor<T>) -> Weight {
+		let scheduled = RecurringTipRenewals::<T, I>::take(now);
+		if scheduled.is_empty() {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let count = scheduled.len() as u32;
+		for hash in scheduled {
+			let Some(mut recurring_tip) = RecurringTips::<T, I>::get(&hash) else { continue };
+			recurring_tip.next_renewal = now + recurring_tip.interval;
+			let reason_hash = T::Hashing::hash(&recurring_tip.reason[..]);
+			if Tips::<T, I>::contains_key(&hash) || Reasons::<T, I>::contains_key(&reason_hash) {
+				// the previous tip is still open, try again after the next interval
+				Self::reschedule_recurring_tip(hash, recurring_tip);
+				continue
+			}
+
+			Self::open_recurring_tip(
+				hash,
+				&recurring_tip.reason,
+				recurring_tip.who.clone(),
+				recurring_tip.owner.clone(),
+			);
+			recurring_tip.remaining_renewals -= 1;
+			if recurring_tip.remaining_renewals == 0 {
+				RecurringTips::<T, I>::remove(&hash);
+				Self::deposit_event(Event::RecurringTipExhausted { tip_hash: hash });
+			} else {
+				Self::reschedule_recurring_tip(hash, recurring_tip);
+			}
+		}
+
+		<T as Config<I>>::WeightInfo::renew_recurring_tips(count)
+	}
+
 	/// Given a mutable reference to an `OpenTip`, insert the tip into it and check whether it
 	/// closes, if so, then deposit the relevant event and set closing accordingly.
 	///
 	/// `O(T)` and one storage access.
 	fn insert_tip_and_check_closing(
-		tip: &mut OpenTip<T::AccountId, BalanceOf<T, I>, BlockNumberFor<T>, T::Hash>,
+		tip: &mut OpenTipOf<T, I>,
 		tipper: T::AccountId,
 		tip_value: BalanceOf<T, I>,
 	) -> bool {
@@ -541,28 +1460,187 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		});
 	}
 
-	/// Execute the payout of a tip.
+	/// Close the tip with the given `hash`, vesting its payout over `vesting_blocks` blocks unless
+	/// it is zero. The closure is deferred if paying out the tip would exceed
+	/// `MaxTipAmountPerPeriod`.
+	fn do_close_tip(hash: T::Hash, vesting_blocks: BlockNumberFor<T>) -> DispatchResult {
+		let tip = Tips::<T, I>::get(hash).ok_or(Error::<T, I>::UnknownTip)?;
+		let n = tip.closes.as_ref().ok_or(Error::<T, I>::StillOpen)?;
+		ensure!(frame_system::Pallet::<T>::block_number() >= *n, Error::<T, I>::Premature);
+		if vesting_blocks.is_zero() {
+			TipVestingPeriods::<T, I>::remove(hash);
+		} else {
+			ensure!(
+				!TipVestingSchedules::<T, I>::contains_key(hash),
+				Error::<T, I>::TipVestingInProgress
+			);
+			TipVestingPeriods::<T, I>::insert(hash, vesting_blocks);
+		}
+		if !Self::try_close_tip(hash, tip) {
+			DeferredTipClosures::<T, I>::try_mutate(|deferred| {
+				if !deferred.contains(&hash) {
+					deferred.try_push(hash).map_err(|_| Error::<T, I>::TooManyDeferredTips)?;
+				}
+				Ok::<_, Error<T, I>>(())
+			})?;
+			Self::deposit_event(Event::TipClosureDeferred { tip_hash: hash });
+		}
+		Ok(())
+	}
+
+	/// Close and pay out the tip, unless that would exceed `MaxTipAmountPerPeriod`. Returns
+	/// `false` if the tip has been left open.
 	///
-	/// Up to three balance operations.
-	/// Plus `O(T)` (`T` is Tippers length).
-	fn payout_tip(
-		hash: T::Hash,
-		tip: OpenTip<T::AccountId, BalanceOf<T, I>, BlockNumberFor<T>, T::Hash>,
-	) {
-		let mut tips = tip.tips;
+	/// The tip is dropped without a payout if none of its tippers is a member of `Tippers`
+	/// anymore.
+	fn try_close_tip(hash: T::Hash, tip: OpenTipOf<T, I>) -> bool {
+		let Some(payout) = Self::tip_payout(hash, &tip) else {
+			Self::drop_tip(hash, tip);
+			return true
+		};
+		let paid = TipPayoutsThisPeriod::<T, I>::get().saturating_add(payout);
+		if paid > T::MaxTipAmountPerPeriod::get() {
+			return false
+		}
+
+		Reasons::<T, I>::remove(&tip.reason);
+		Tips::<T, I>::remove(hash);
+		Self::clear_tip_votes(hash);
+		// the part of the payout, kept by the treasury, is not counted
+		let unpaid = Self::payout_tip(hash, tip, payout);
+		TipPayoutsThisPeriod::<T, I>::put(paid.saturating_sub(unpaid));
+		MatchedTips::<T, I>::remove(hash);
+		true
+	}
+
+	/// Remove the tip with the given `hash` without paying it out and return the finder's deposit.
+	fn drop_tip(hash: T::Hash, tip: OpenTipOf<T, I>) {
+		Reasons::<T, I>::remove(&tip.reason);
+		Tips::<T, I>::remove(hash);
+		Self::clear_tip_votes(hash);
+		Self::drop_tip_match(hash);
+		TipVestingPeriods::<T, I>::remove(&hash);
+		CrossChainBeneficiaries::<T, I>::remove(&hash);
+		if !tip.deposit.is_zero() {
+			let err_amount = T::Currency::unreserve(&tip.finder, tip.deposit);
+			debug_assert!(err_amount.is_zero());
+		}
+		Self::deposit_event(Event::TipDropped { tip_hash: hash });
+	}
+
+	/// Ensure `tipper` hasn't declared a tip value for the tip with the given `hash` within the
+	/// last `TipperCooldown` blocks and record the current block as its last vote.
+	fn note_tip_vote(hash: T::Hash, tipper: &T::AccountId) -> DispatchResult {
+		let cooldown = T::TipperCooldown::get();
+		if cooldown.is_zero() {
+			return Ok(())
+		}
+
+		let now = frame_system::Pallet::<T>::block_number();
+		if let Some(last) = LastTipBlock::<T, I>::get(hash, tipper) {
+			ensure!(now >= last.saturating_add(cooldown), Error::<T, I>::CooldownNotElapsed);
+		}
+		LastTipBlock::<T, I>::insert(hash, tipper, now);
+		Ok(())
+	}
+
+	/// Drop the promise to match the tip with the given `hash`. Returns `false` if the tip is not
+	/// matched.
+	fn drop_tip_match(hash: T::Hash) -> bool {
+		if MatchedTips::<T, I>::take(hash).is_none() {
+			return false
+		}
+		Self::deposit_event(Event::TipMatchCancelled { tip_hash: hash });
+		true
+	}
+
+	/// Remove the last votes of the tippers on the tip with the given `hash`. `O(T)`.
+	fn clear_tip_votes(hash: T::Hash) {
+		let _ = LastTipBlock::<T, I>::clear_prefix(hash, T::Tippers::max_len() as u32, None);
+	}
+
+	/// Reset the tip payouts counter and schedule the retry of the tip closures, deferred in the
+	/// previous spend period.
+	fn start_payout_period() -> Weight {
+		TipPayoutsThisPeriod::<T, I>::kill();
+		let deferred = DeferredTipClosures::<T, I>::decode_len().unwrap_or(0) as u32;
+		DeferredClosuresToRetry::<T, I>::put(deferred);
+		T::DbWeight::get().reads_writes(1, 2)
+	}
+
+	/// Retry at most `MaxDeferredClosuresPerBlock` deferred tip closures, which are still to be
+	/// retried within the current spend period. Tips that still can't be closed are deferred to
+	/// the next spend period again.
+	///
+	/// `O(D * T)` where `D` is `MaxDeferredClosuresPerBlock` and `T` is Tippers length.
+	fn retry_deferred_tip_closures() -> Weight {
+		let to_retry = DeferredClosuresToRetry::<T, I>::get();
+		if to_retry == 0 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut deferred = DeferredTipClosures::<T, I>::get().into_inner();
+		let count = to_retry.min(T::MaxDeferredClosuresPerBlock::get()).min(deferred.len() as u32);
+		let retried: Vec<_> = deferred.drain(..count as usize).collect();
+		for hash in retried {
+			// the tip has been retracted or slashed if it is missing
+			let Some(tip) = Tips::<T, I>::get(hash) else { continue };
+			if !Self::try_close_tip(hash, tip) {
+				deferred.push(hash);
+			}
+		}
+
+		DeferredClosuresToRetry::<T, I>::put(to_retry - count);
+		if deferred.is_empty() {
+			DeferredTipClosures::<T, I>::kill();
+		} else {
+			// the retried closures have been removed first, so the length hasn't grown
+			DeferredTipClosures::<T, I>::put(BoundedVec::truncate_from(deferred));
+		}
+		T::DbWeight::get().reads_writes(2, 2).saturating_add(
+			<T as Config<I>>::WeightInfo::close_tip(T::Tippers::max_len() as u32)
+				.saturating_mul(count as u64),
+		)
+	}
+
+	/// Returns the total amount (including the finder's fee and the treasury match), which is paid
+	/// out when the tip with the given `hash` is closed. The amount is capped at both the
+	/// treasury balance and `MaxTipAmountPerPeriod`. `O(T)` (`T` is Tippers length).
+	///
+	/// Returns `None` if none of the tippers is a member of `Tippers` anymore.
+	fn tip_payout(hash: T::Hash, tip: &OpenTipOf<T, I>) -> Option<BalanceOf<T, I>> {
+		let mut tips = tip.tips.clone();
 		Self::retain_active_tips(&mut tips);
 		tips.sort_by_key(|i| i.1);
+		let median = tips.get(tips.len() / 2)?.1;
 
-		let treasury = Self::account_id();
+		let matched = MatchedTips::<T, I>::get(hash).unwrap_or_else(Zero::zero);
 		let max_payout = pallet_treasury::Pallet::<T, I>::pot();
+		Some(
+			median
+				.saturating_add(matched)
+				.min(max_payout)
+				.min(T::MaxTipAmountPerPeriod::get()),
+		)
+	}
 
-		let mut payout = tips[tips.len() / 2].1.min(max_payout);
+	/// Execute the `payout` of a tip. Returns the part of the payout that has not been paid out
+	/// and is kept by the treasury.
+	///
+	/// Up to two balance operations plus one per beneficiary.
+	fn payout_tip(
+		hash: T::Hash,
+		tip: OpenTipOf<T, I>,
+		mut payout: BalanceOf<T, I>,
+	) -> BalanceOf<T, I> {
+		let treasury = Self::account_id();
 		if !tip.deposit.is_zero() {
 			let err_amount = T::Currency::unreserve(&tip.finder, tip.deposit);
 			debug_assert!(err_amount.is_zero());
 		}
 
-		if tip.finders_fee && tip.finder != tip.who {
+		let finder_is_beneficiary = tip.beneficiaries.iter().any(|(who, _)| *who == tip.finder);
+		if tip.finders_fee && !finder_is_beneficiary {
 			// pay out the finder's fee.
 			let finders_fee = T::TipFindersFee::get() * payout;
 			payout -= finders_fee;
@@ -572,10 +1650,209 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			debug_assert!(res.is_ok());
 		}
 
-		// same as above: best-effort only.
-		let res = T::Currency::transfer(&treasury, &tip.who, payout, KeepAlive);
-		debug_assert!(res.is_ok());
-		Self::deposit_event(Event::TipClosed { tip_hash: hash, who: tip.who, payout });
+		if let Some(beneficiary) = CrossChainBeneficiaries::<T, I>::take(hash) {
+			TipVestingPeriods::<T, I>::remove(hash);
+			return Self::payout_tip_cross_chain(hash, &beneficiary, payout)
+		}
+
+		// split the payout between beneficiaries; the rounding remainder goes to the first one.
+		let mut shares: Vec<_> = tip
+			.beneficiaries
+			.into_iter()
+			.map(|(who, share)| (who, share.mul_floor(payout)))
+			.collect();
+		let remainder = shares
+			.iter()
+			.fold(payout, |remainder, (_, amount)| remainder.saturating_sub(*amount));
+		if let Some((_, amount)) = shares.first_mut() {
+			amount.saturating_accrue(remainder);
+		}
+
+		let vesting_period = TipVestingPeriods::<T, I>::take(hash).unwrap_or_else(Zero::zero);
+		if !vesting_period.is_zero() && Self::start_tip_vesting(hash, &shares, vesting_period) {
+			return Zero::zero()
+		}
+
+		for (who, payout) in shares {
+			// same as above: best-effort only.
+			let res = T::Currency::transfer(&treasury, &who, payout, KeepAlive);
+			debug_assert!(res.is_ok());
+			Self::deposit_event(Event::TipClosed { tip_hash: hash, who, payout });
+		}
+		Zero::zero()
+	}
+
+	/// Pay the `payout` of the tip with the given `hash` to the `beneficiary` on another chain.
+	/// Returns the `payout` if the payment has failed and zero otherwise.
+	fn payout_tip_cross_chain(
+		hash: T::Hash,
+		beneficiary: &T::CrossChainBeneficiary,
+		payout: BalanceOf<T, I>,
+	) -> BalanceOf<T, I> {
+		match T::CrossChainPaymaster::pay(beneficiary, T::CrossChainAssetKind::get(), payout) {
+			Ok(payment_id) => {
+				Self::deposit_event(Event::CrossChainPayoutSent {
+					tip_hash: hash,
+					payout,
+					payment_id,
+				});
+				Zero::zero()
+			},
+			Err(e) => {
+				log::error!(
+					target: LOG_TARGET,
+					"Failed to pay out tip {:?} to {:?}: {:?}",
+					hash,
+					beneficiary,
+					e,
+				);
+				Self::deposit_event(Event::CrossChainPayoutFailed {
+					tip_hash: hash,
+					payout,
+					reason: e,
+				});
+				payout
+			},
+		}
+	}
+
+	/// Move the `shares` of the tip payout to the `vesting_account` and vest them over `period`
+	/// blocks.
+	///
+	/// Returns `false` if the payout can't be moved to the `vesting_account` and should be paid
+	/// out immediately.
+	fn start_tip_vesting(
+		hash: T::Hash,
+		shares: &[(T::AccountId, BalanceOf<T, I>)],
+		period: BlockNumberFor<T>,
+	) -> bool {
+		// `close_tip_vested` doesn't vest the payout while the previous one is still being vested.
+		if TipVestingSchedules::<T, I>::contains_key(hash) {
+			defensive!("the payout of the previous tip is still being vested");
+			return false
+		}
+
+		let total = shares.iter().fold(Zero::zero(), |total: BalanceOf<T, I>, (_, amount)| {
+			total.saturating_add(*amount)
+		});
+		if T::Currency::transfer(&Self::account_id(), &Self::vesting_account(), total, KeepAlive)
+			.is_err()
+		{
+			return false
+		}
+
+		let start = frame_system::Pallet::<T>::block_number();
+		let beneficiaries = shares
+			.iter()
+			.map(|(who, amount)| (who.clone(), *amount, Zero::zero()))
+			.collect::<Vec<_>>();
+		TipVestingSchedules::<T, I>::insert(
+			hash,
+			TipVestingSchedule {
+				beneficiaries: BoundedVec::truncate_from(beneficiaries),
+				start,
+				period,
+			},
+		);
+		Self::schedule_tip_vesting_expiry(
+			hash,
+			start
+				.saturating_add(period)
+				.saturating_add(T::VestingClaimPeriod::get())
+				.saturating_add(One::one()),
+		);
+		Self::deposit_event(Event::TipVestingStarted {
+			tip_hash: hash,
+			until: start.saturating_add(period),
+		});
+		true
+	}
+
+	/// Schedule the vesting of the tip with the given `hash` to expire at the block `at`.
+	///
+	/// If the expiries of block `at` are full, the expiry spills over to the first of the
+	/// following `MaxVestingExpiriesPerBlock` blocks with room for it. If none of them has room
+	/// for it, the vesting only expires when it is claimed with `claim_tip_vesting`.
+	fn schedule_tip_vesting_expiry(hash: T::Hash, mut at: BlockNumberFor<T>) {
+		for _ in 0..T::MaxVestingExpiriesPerBlock::get() {
+			if TipVestingExpiries::<T, I>::try_mutate(at, |hashes| hashes.try_push(hash)).is_ok() {
+				return
+			}
+			at = at.saturating_add(One::one());
+		}
+		log::warn!(
+			target: LOG_TARGET,
+			"Failed to schedule the expiry of the tip vesting {:?}: too many expiries",
+			hash,
+		);
+	}
+
+	/// Return the unclaimed installments of the tip vestings, which expire at block `now`, to the
+	/// treasury.
+	///
+	/// `O(E * B)` where `E` is `MaxVestingExpiriesPerBlock` and `B` is the number of
+	/// beneficiaries.
+	fn expire_tip_vestings(now: BlockNumberFor<T>) -> Weight {
+		let expiring = TipVestingExpiries::<T, I>::take(now);
+		if expiring.is_empty() {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let count = expiring.len() as u64;
+		for hash in expiring {
+			// the vesting has been finished, or a later vesting of the tip hasn't expired yet
+			let Some(schedule) = TipVestingSchedules::<T, I>::get(hash) else { continue };
+			let elapsed = now.saturating_sub(schedule.start);
+			if elapsed > schedule.period.saturating_add(T::VestingClaimPeriod::get()) {
+				Self::expire_tip_vesting(hash, schedule);
+			}
+		}
+
+		T::DbWeight::get().reads_writes(1, 1).saturating_add(
+			<T as Config<I>>::WeightInfo::claim_tip_vesting(T::MaxBeneficiaries::get())
+				.saturating_mul(count),
+		)
+	}
+
+	/// Return the unclaimed installments of the expired vesting of the tip with the given `hash`
+	/// to the treasury.
+	///
+	/// The `vesting_account` is reaped once it holds less than the existential deposit, so only
+	/// as much as it holds is returned.
+	fn expire_tip_vesting(hash: T::Hash, schedule: TipVestingScheduleOf<T, I>) {
+		let vesting_account = Self::vesting_account();
+		let unclaimed = schedule
+			.beneficiaries
+			.iter()
+			.fold(Zero::zero(), |unclaimed: BalanceOf<T, I>, (_, total, claimed)| {
+				unclaimed.saturating_add(total.saturating_sub(*claimed))
+			})
+			.min(T::Currency::free_balance(&vesting_account));
+		if let Err(e) =
+			T::Currency::transfer(&vesting_account, &Self::account_id(), unclaimed, AllowDeath)
+		{
+			defensive!(e, "the unclaimed installments are held by the vesting account");
+		}
+		TipVestingSchedules::<T, I>::remove(&hash);
+		Self::deposit_event(Event::TipVestingExpired { tip_hash: hash, unclaimed });
+	}
+
+	/// Returns the part of `total`, which is vested `elapsed` blocks into the vesting `period`.
+	///
+	/// An equal installment is vested every block and the rounding remainder is vested at the end
+	/// of the period.
+	fn vested_amount(
+		total: BalanceOf<T, I>,
+		elapsed: BlockNumberFor<T>,
+		period: BlockNumberFor<T>,
+	) -> BalanceOf<T, I> {
+		if elapsed >= period {
+			return total
+		}
+
+		let elapsed: u32 = elapsed.unique_saturated_into();
+		let period: u32 = period.unique_saturated_into();
+		(total / period.into()).saturating_mul(elapsed.into())
 	}
 
 	pub fn migrate_retract_tip_for_tip_new(module: &[u8], item: &[u8]) {
@@ -620,7 +1897,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			};
 			let new_tip = OpenTip {
 				reason: old_tip.reason,
-				who: old_tip.who,
+				beneficiaries: Self::single_beneficiary(old_tip.who),
 				finder,
 				deposit,
 				closes: old_tip.closes,
@@ -639,6 +1916,9 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	/// 1. The number of entries in `Tips` should be equal to `Reasons`.
 	/// 2. Reasons exists for each Tip[`OpenTip.reason`].
 	/// 3. If `OpenTip.finders_fee` is true, then OpenTip.deposit should be greater than zero.
+	/// 4. Only open tips are matched by the treasury.
+	/// 5. Only open tips have a vesting period.
+	/// 6. Only open tips have a beneficiary on another chain.
 	#[cfg(any(feature = "try-runtime", test))]
 	pub fn do_try_state() -> Result<(), TryRuntimeError> {
 		let reasons = Reasons::<T, I>::iter_keys().collect::<Vec<_>>();
@@ -666,6 +1946,18 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				TryRuntimeError::Other("no reason for this tip")
 			);
 		}
+
+		for tip in MatchedTips::<T, I>::iter_keys() {
+			ensure!(tips.contains(&tip), TryRuntimeError::Other("matched tip is not open"));
+		}
+
+		for tip in TipVestingPeriods::<T, I>::iter_keys() {
+			ensure!(tips.contains(&tip), TryRuntimeError::Other("vested tip is not open"));
+		}
+
+		for tip in CrossChainBeneficiaries::<T, I>::iter_keys() {
+			ensure!(tips.contains(&tip), TryRuntimeError::Other("cross-chain tip is not open"));
+		}
 		Ok(())
 	}
 }
@@ -22,5 +22,10 @@
 /// own storage identifier.
 pub mod v4;
 
+/// Version 5.
+///
+/// Open tips may have several beneficiaries instead of a single one.
+pub mod v5;
+
 /// A migration that unreserves all funds held in the context of this pallet.
 pub mod unreserve_deposits;
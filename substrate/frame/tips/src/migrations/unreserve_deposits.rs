@@ -22,10 +22,14 @@ use core::iter::Sum;
 use frame_support::{
 	pallet_prelude::OptionQuery,
 	storage_alias,
-	traits::{Currency, LockableCurrency, OnRuntimeUpgrade, ReservableCurrency},
+	traits::{
+		Currency, LockableCurrency, OnRuntimeUpgrade, ReservableCurrency, StorageVersion,
+		STORAGE_VERSION_STORAGE_KEY_POSTFIX,
+	},
 	weights::RuntimeDbWeight,
 	Parameter, Twox64Concat,
 };
+use sp_core::ConstU32;
 use sp_runtime::{traits::Zero, Saturating};
 use sp_std::collections::btree_map::BTreeMap;
 
@@ -64,6 +68,9 @@ pub trait UnlockConfig<I>: 'static {
 
 /// An open tipping "motion". Retains all details of a tip including information on the finder
 /// and the members who have voted.
+///
+/// The bound of the beneficiaries only limits the decoding, so the widest one is used to read
+/// any stored tip.
 #[storage_alias(dynamic)]
 type Tips<T: UnlockConfig<I>, I: 'static> = StorageMap<
 	<T as UnlockConfig<I>>::PalletName,
@@ -74,6 +81,22 @@ type Tips<T: UnlockConfig<I>, I: 'static> = StorageMap<
 		BalanceOf<T, I>,
 		<T as UnlockConfig<I>>::BlockNumber,
 		<T as UnlockConfig<I>>::Hash,
+		ConstU32<{ u32::MAX }>,
+	>,
+	OptionQuery,
+>;
+
+/// The open tips, stored by the pallet before the `v5` migration.
+#[storage_alias(dynamic)]
+type OldTips<T: UnlockConfig<I>, I: 'static> = StorageMap<
+	<T as UnlockConfig<I>>::PalletName,
+	Twox64Concat,
+	<T as UnlockConfig<I>>::Hash,
+	super::v5::OldOpenTip<
+		<T as UnlockConfig<I>>::AccountId,
+		BalanceOf<T, I>,
+		<T as UnlockConfig<I>>::BlockNumber,
+		<T as UnlockConfig<I>>::Hash,
 	>,
 	OptionQuery,
 >;
@@ -88,6 +111,27 @@ type Tips<T: UnlockConfig<I>, I: 'static> = StorageMap<
 pub struct UnreserveDeposits<T: UnlockConfig<I>, I: 'static>(sp_std::marker::PhantomData<(T, I)>);
 
 impl<T: UnlockConfig<I>, I: 'static> UnreserveDeposits<T, I> {
+	/// Returns the storage version of the pallet, stored on chain.
+	fn on_chain_storage_version() -> StorageVersion {
+		use sp_core::Get;
+
+		let key = frame_support::storage::storage_prefix(
+			T::PalletName::get().as_bytes(),
+			STORAGE_VERSION_STORAGE_KEY_POSTFIX,
+		);
+		frame_support::storage::unhashed::get_or_default(&key)
+	}
+
+	/// Returns the finder and the deposit of each open tip, reading the tips in the format of the
+	/// on chain storage version.
+	fn finder_deposits() -> sp_std::vec::Vec<(T::AccountId, BalanceOf<T, I>)> {
+		if Self::on_chain_storage_version() < StorageVersion::new(5) {
+			OldTips::<T, I>::iter_values().map(|tip| (tip.finder, tip.deposit)).collect()
+		} else {
+			Tips::<T, I>::iter_values().map(|tip| (tip.finder, tip.deposit)).collect()
+		}
+	}
+
 	/// Calculates and returns the total amount reserved by each account by this pallet from open
 	/// tips.
 	///
@@ -100,18 +144,19 @@ impl<T: UnlockConfig<I>, I: 'static> UnreserveDeposits<T, I> {
 		use sp_core::Get;
 
 		let mut tips_len = 0;
-		let account_deposits: BTreeMap<T::AccountId, BalanceOf<T, I>> = Tips::<T, I>::iter()
-			.map(|(_hash, open_tip)| open_tip)
-			.fold(BTreeMap::new(), |mut acc, tip| {
+		let account_deposits: BTreeMap<T::AccountId, BalanceOf<T, I>> = Self::finder_deposits()
+			.into_iter()
+			.fold(BTreeMap::new(), |mut acc, (finder, deposit)| {
 				// Count the total number of tips
 				tips_len.saturating_inc();
 
 				// Add the balance to the account's existing deposit in the accumulator
-				acc.entry(tip.finder).or_insert(Zero::zero()).saturating_accrue(tip.deposit);
+				acc.entry(finder).or_insert(Zero::zero()).saturating_accrue(deposit);
 				acc
 			});
 
-		(account_deposits, T::DbWeight::get().reads(tips_len))
+		// One more read for the storage version.
+		(account_deposits, T::DbWeight::get().reads(tips_len.saturating_add(1)))
 	}
 }
 
@@ -256,6 +301,32 @@ mod test {
 
 	#[test]
 	fn unreserve_all_funds_works() {
+		unreserve_all_funds_of_tips_stored_with(|| {});
+	}
+
+	#[test]
+	fn unreserve_all_funds_works_before_v5() {
+		unreserve_all_funds_of_tips_stored_with(|| {
+			// Store the tips in the format used before `v5`
+			frame_support::traits::StorageVersion::new(4).put::<Tips>();
+			for (hash, tip) in crate::Tips::<Test>::iter() {
+				frame_support::storage::unhashed::put(
+					&crate::Tips::<Test>::hashed_key_for(hash),
+					&super::super::v5::OldOpenTip {
+						reason: tip.reason,
+						who: tip.beneficiaries[0].0,
+						finder: tip.finder,
+						deposit: tip.deposit,
+						closes: tip.closes,
+						tips: tip.tips,
+						finders_fee: tip.finders_fee,
+					},
+				);
+			}
+		});
+	}
+
+	fn unreserve_all_funds_of_tips_stored_with(store_tips: impl FnOnce()) {
 		let tipper_0 = 0;
 		let tipper_1 = 1;
 		let tipper_0_initial_reserved = 0;
@@ -285,21 +356,18 @@ mod test {
 				tip_1_reason.clone(),
 				recipient
 			));
+			store_tips();
 
 			// Verify the expected amount is reserved
 			assert_eq!(
 				<Test as pallet_treasury::Config>::Currency::reserved_balance(&tipper_0),
 				tipper_0_initial_reserved +
-					<Test as crate::Config>::TipReportDepositBase::get() +
-					<Test as crate::Config>::DataDepositPerByte::get() *
-						tip_0_reason.len() as u64
+					crate::Pallet::<Test, ()>::report_deposit(tip_0_reason.len() as u32)
 			);
 			assert_eq!(
 				<Test as pallet_treasury::Config>::Currency::reserved_balance(&tipper_1),
 				tipper_1_initial_reserved +
-					<Test as crate::Config>::TipReportDepositBase::get() +
-					<Test as crate::Config>::DataDepositPerByte::get() *
-						tip_1_reason.len() as u64
+					crate::Pallet::<Test, ()>::report_deposit(tip_1_reason.len() as u32)
 			);
 
 			// Execute the migration
@@ -0,0 +1,140 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{BalanceOf, Config, OpenTip, Pallet, Tips, LOG_TARGET};
+use codec::{Decode, Encode};
+use frame_support::{
+	traits::{Defensive, Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+	BoundedVec, Parameter,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_runtime::{traits::Zero, Percent, RuntimeDebug};
+use sp_std::{marker::PhantomData, vec, vec::Vec};
+
+#[cfg(feature = "try-runtime")]
+use sp_runtime::TryRuntimeError;
+
+/// An open tip, stored before the `v5` migration.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub struct OldOpenTip<
+	AccountId: Parameter,
+	Balance: Parameter,
+	BlockNumber: Parameter,
+	Hash: Parameter,
+> {
+	/// The hash of the reason for the tip.
+	pub reason: Hash,
+	/// The account to be tipped.
+	pub who: AccountId,
+	/// The account who began this tip.
+	pub finder: AccountId,
+	/// The amount held on deposit for this tip.
+	pub deposit: Balance,
+	/// The block number at which this tip will close if `Some`.
+	pub closes: Option<BlockNumber>,
+	/// The members who have voted for this tip. Sorted by AccountId.
+	pub tips: Vec<(AccountId, Balance)>,
+	/// Whether this tip should result in the finder taking a fee.
+	pub finders_fee: bool,
+}
+
+/// Migrate the storage from V4 to V5.
+///
+/// Replaces the single beneficiary of every open tip with the list of beneficiaries, containing
+/// the former beneficiary with the 100% share.
+///
+/// The migration only runs if the on-chain storage version is 4. If `MaxBeneficiaries` is zero,
+/// the open tips can't be migrated: the migration logs an error and leaves both the tips and the
+/// storage version untouched, so it may be retried once the configuration is fixed.
+pub struct MigrateV4ToV5<T, I = ()>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for MigrateV4ToV5<T, I> {
+	fn on_runtime_upgrade() -> Weight {
+		let on_chain_version = Pallet::<T, I>::on_chain_storage_version();
+		if on_chain_version != 4 {
+			log::info!(
+				target: LOG_TARGET,
+				"Storage migration v5 for tips skipped, on-chain storage version is {:?}.",
+				on_chain_version,
+			);
+			return T::DbWeight::get().reads(1)
+		}
+
+		if T::MaxBeneficiaries::get().is_zero() {
+			log::error!(
+				target: LOG_TARGET,
+				"Storage migration v5 for tips failed: `MaxBeneficiaries` is zero, open tips \
+				can't be migrated. The storage version is left at 4.",
+			);
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut count = 0u64;
+		Tips::<T, I>::translate::<
+			OldOpenTip<T::AccountId, BalanceOf<T, I>, BlockNumberFor<T>, T::Hash>,
+			_,
+		>(|_, old| {
+			count += 1;
+			let beneficiaries = BoundedVec::try_from(vec![(old.who, Percent::one())])
+				.defensive_proof("`MaxBeneficiaries` is checked to be non-zero above; qed")
+				.ok()?;
+			Some(OpenTip {
+				reason: old.reason,
+				beneficiaries,
+				finder: old.finder,
+				deposit: old.deposit,
+				closes: old.closes,
+				tips: old.tips,
+				finders_fee: old.finders_fee,
+			})
+		});
+		StorageVersion::new(5).put::<Pallet<T, I>>();
+
+		log::info!(
+			target: LOG_TARGET,
+			"Storage migration v5 for tips finished. Migrated {} open tips.",
+			count,
+		);
+
+		T::DbWeight::get().reads_writes(count + 1, count + 1)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+		frame_support::ensure!(
+			!T::MaxBeneficiaries::get().is_zero(),
+			"`MaxBeneficiaries` is zero, open tips can't be migrated to v5"
+		);
+		Ok((Tips::<T, I>::iter_keys().count() as u32).encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+		let tip_count: u32 = Decode::decode(&mut &state[..]).map_err(|_| {
+			"the state parameter should be something that was generated by pre_upgrade"
+		})?;
+		frame_support::ensure!(
+			Pallet::<T, I>::on_chain_storage_version() >= 5,
+			"the storage version should be at least 5 after the migration"
+		);
+		frame_support::ensure!(
+			Tips::<T, I>::iter_values().count() as u32 == tip_count,
+			"every open tip should still be decodable after the migration"
+		);
+		Ok(())
+	}
+}
@@ -22,16 +22,16 @@
 use sp_core::H256;
 use sp_runtime::{
 	traits::{BadOrigin, BlakeTwo256, IdentityLookup},
-	BuildStorage, Perbill, Permill,
+	BuildStorage, Perbill, Permill, TokenError,
 };
 use sp_storage::Storage;
 
 use frame_support::{
-	assert_noop, assert_ok, derive_impl, parameter_types,
+	assert_noop, assert_ok, bounded_vec, derive_impl, parameter_types,
 	storage::StoragePrefixedMap,
 	traits::{
-		tokens::{PayFromAccount, UnityAssetBalanceConversion},
-		ConstU32, ConstU64, IntegrityTest, SortedMembers, StorageVersion,
+		tokens::{PayFromAccount, PaymentStatus, UnityAssetBalanceConversion},
+		ConstU32, ConstU64, Hooks, IntegrityTest, SortedMembers, StorageVersion,
 	},
 	PalletId,
 };
@@ -55,6 +55,7 @@ frame_support::construct_runtime!(
 
 parameter_types! {
 	pub const AvailableBlockRatio: Perbill = Perbill::one();
+	pub static ExistentialDeposit: u64 = 1;
 }
 
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
@@ -72,7 +73,7 @@ impl pallet_balances::Config for Test {
 	type Balance = u64;
 	type RuntimeEvent = RuntimeEvent;
 	type DustRemoval = ();
-	type ExistentialDeposit = ConstU64<1>;
+	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = System;
 	type WeightInfo = ();
 	type FreezeIdentifier = ();
@@ -167,9 +168,46 @@ impl pallet_treasury::Config<Instance1> for Test {
 	type BenchmarkHelper = ();
 }
 
+parameter_types! {
+	pub static CrossChainPayments: Vec<(u128, u64)> = vec![];
+	pub static CrossChainPayFails: bool = false;
+}
+
+/// Records the payments to the beneficiaries on other chains instead of sending them.
+pub struct TestCrossChainPay;
+impl Pay for TestCrossChainPay {
+	type Beneficiary = u128;
+	type Balance = u64;
+	type Id = u64;
+	type AssetKind = ();
+	type Error = ();
+
+	fn pay(
+		who: &Self::Beneficiary,
+		_: Self::AssetKind,
+		amount: Self::Balance,
+	) -> Result<Self::Id, Self::Error> {
+		if CrossChainPayFails::get() {
+			return Err(())
+		}
+		CrossChainPayments::mutate(|payments| payments.push((*who, amount)));
+		Ok(CrossChainPayments::get().len() as u64)
+	}
+	fn check_payment(_: Self::Id) -> PaymentStatus {
+		PaymentStatus::InProgress
+	}
+	#[cfg(feature = "runtime-benchmarks")]
+	fn ensure_successful(_: &Self::Beneficiary, _: Self::AssetKind, _: Self::Balance) {}
+	#[cfg(feature = "runtime-benchmarks")]
+	fn ensure_concluded(_: Self::Id) {}
+}
+
 parameter_types! {
 	pub const TipFindersFee: Percent = Percent::from_percent(20);
 	pub static TipReportDepositBase: u64 = 1;
+	pub static MaxTipAmountPerPeriod: u64 = 10_000_000;
+	pub static DataDepositPerByte: u64 = 1;
+	pub static TipperCooldown: u64 = 0;
 }
 impl Config for Test {
 	type MaximumReasonLength = ConstU32<16384>;
@@ -177,8 +215,22 @@ impl Config for Test {
 	type TipCountdown = ConstU64<1>;
 	type TipFindersFee = TipFindersFee;
 	type TipReportDepositBase = TipReportDepositBase;
-	type DataDepositPerByte = ConstU64<1>;
+	type DataDeposit = LinearDataDepositCurve<DataDepositPerByte>;
 	type MaxTipAmount = ConstU64<10_000_000>;
+	type MaxBeneficiaries = ConstU32<3>;
+	type MaxRecurringTips = ConstU32<2>;
+	type TipperCooldown = TipperCooldown;
+	type MaxVotesPerBatch = ConstU32<20>;
+	type VestingClaimPeriod = ConstU64<5>;
+	type MaxVestingExpiriesPerBlock = ConstU32<1>;
+	type MatchOrigin = frame_system::EnsureRoot<u128>;
+	type CrossChainBeneficiary = u128;
+	type CrossChainPaymentError = ();
+	type CrossChainPaymaster = TestCrossChainPay;
+	type CrossChainAssetKind = ();
+	type MaxTipAmountPerPeriod = MaxTipAmountPerPeriod;
+	type MaxDeferredTips = ConstU32<1>;
+	type MaxDeferredClosuresPerBlock = ConstU32<1>;
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
 }
@@ -189,8 +241,22 @@ impl Config<Instance1> for Test {
 	type TipCountdown = ConstU64<1>;
 	type TipFindersFee = TipFindersFee;
 	type TipReportDepositBase = TipReportDepositBase;
-	type DataDepositPerByte = ConstU64<1>;
+	type DataDeposit = LinearDataDepositCurve<DataDepositPerByte>;
 	type MaxTipAmount = ConstU64<10_000_000>;
+	type MaxBeneficiaries = ConstU32<3>;
+	type MaxRecurringTips = ConstU32<2>;
+	type TipperCooldown = TipperCooldown;
+	type MaxVotesPerBatch = ConstU32<20>;
+	type VestingClaimPeriod = ConstU64<5>;
+	type MaxVestingExpiriesPerBlock = ConstU32<1>;
+	type MatchOrigin = frame_system::EnsureRoot<u128>;
+	type CrossChainBeneficiary = u128;
+	type CrossChainPaymentError = ();
+	type CrossChainPaymaster = TestCrossChainPay;
+	type CrossChainAssetKind = ();
+	type MaxTipAmountPerPeriod = MaxTipAmountPerPeriod;
+	type MaxDeferredTips = ConstU32<1>;
+	type MaxDeferredClosuresPerBlock = ConstU32<1>;
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
 }
@@ -238,6 +304,19 @@ fn tip_hash() -> H256 {
 	BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 3u128))
 }
 
+fn beneficiaries(shares: &[(u128, u8)]) -> BoundedVec<(u128, Percent), ConstU32<3>> {
+	shares
+		.iter()
+		.map(|(who, share)| (*who, Percent::from_percent(*share)))
+		.collect::<Vec<_>>()
+		.try_into()
+		.unwrap()
+}
+
+fn split_tip_hash(shares: &[(u128, u8)]) -> H256 {
+	BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), beneficiaries(shares)))
+}
+
 #[test]
 fn tip_new_cannot_be_used_twice() {
 	build_and_execute(|| {
@@ -448,6 +527,1018 @@ fn tip_changing_works() {
 	});
 }
 
+#[test]
+fn tip_new_split_two_way_works() {
+	build_and_execute(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		let shares = [(3, 50), (4, 50)];
+		assert_ok!(Tips::tip_new_split(
+			RuntimeOrigin::signed(0),
+			b"awesome.dot".to_vec(),
+			beneficiaries(&shares)
+		));
+		assert_eq!(Balances::reserved_balance(0), 12);
+
+		let h = split_tip_hash(&shares);
+		assert_eq!(last_event(), TipEvent::NewTip { tip_hash: h });
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(10), h, 10));
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(11), h, 10));
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(12), h, 10));
+		System::set_block_number(2);
+		assert_ok!(Tips::close_tip(RuntimeOrigin::signed(100), h.into()));
+
+		// finder's fee is taken from the total amount, the rest is split
+		assert_eq!(Balances::reserved_balance(0), 0);
+		assert_eq!(Balances::free_balance(0), 102);
+		assert_eq!(Balances::free_balance(3), 4);
+		assert_eq!(Balances::free_balance(4), 4);
+		assert_eq!(last_event(), TipEvent::TipClosed { tip_hash: h, who: 4, payout: 4 });
+	});
+}
+
+#[test]
+fn tip_new_split_three_way_works() {
+	build_and_execute(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 1001);
+		let shares = [(3, 50), (4, 30), (5, 20)];
+		assert_ok!(Tips::tip_new_split(
+			RuntimeOrigin::signed(0),
+			b"awesome.dot".to_vec(),
+			beneficiaries(&shares)
+		));
+
+		let h = split_tip_hash(&shares);
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(10), h, 100));
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(11), h, 100));
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(12), h, 100));
+		System::set_block_number(2);
+		assert_ok!(Tips::close_tip(RuntimeOrigin::signed(100), h.into()));
+
+		assert_eq!(Balances::free_balance(0), 120);
+		assert_eq!(Balances::free_balance(3), 40);
+		assert_eq!(Balances::free_balance(4), 24);
+		assert_eq!(Balances::free_balance(5), 16);
+	});
+}
+
+#[test]
+fn tip_new_split_gives_rounding_remainder_to_first_beneficiary() {
+	build_and_execute(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		let shares = [(3, 34), (4, 33), (5, 33)];
+		assert_ok!(Tips::tip_new_split(
+			RuntimeOrigin::signed(0),
+			b"awesome.dot".to_vec(),
+			beneficiaries(&shares)
+		));
+
+		let h = split_tip_hash(&shares);
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(10), h, 10));
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(11), h, 10));
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(12), h, 10));
+		System::set_block_number(2);
+		assert_ok!(Tips::close_tip(RuntimeOrigin::signed(100), h.into()));
+
+		// 8 is left after the finder's fee; every share rounds down to 2
+		assert_eq!(Balances::free_balance(0), 102);
+		assert_eq!(Balances::free_balance(3), 4);
+		assert_eq!(Balances::free_balance(4), 2);
+		assert_eq!(Balances::free_balance(5), 2);
+	});
+}
+
+#[test]
+fn tip_new_split_requires_shares_to_sum_up_to_100() {
+	build_and_execute(|| {
+		assert_noop!(
+			Tips::tip_new_split(
+				RuntimeOrigin::signed(0),
+				b"awesome.dot".to_vec(),
+				beneficiaries(&[(3, 60), (4, 30)])
+			),
+			Error::<Test>::InvalidSplit
+		);
+		assert_noop!(
+			Tips::tip_new_split(
+				RuntimeOrigin::signed(0),
+				b"awesome.dot".to_vec(),
+				beneficiaries(&[(3, 60), (4, 50)])
+			),
+			Error::<Test>::InvalidSplit
+		);
+		assert_noop!(
+			Tips::tip_new_split(
+				RuntimeOrigin::signed(0),
+				b"awesome.dot".to_vec(),
+				beneficiaries(&[])
+			),
+			Error::<Test>::InvalidSplit
+		);
+	});
+}
+
+#[test]
+fn test_migration_v5() {
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade};
+
+	new_test_ext().execute_with(|| {
+		let reason = BlakeTwo256::hash(b"reason");
+		let hash = BlakeTwo256::hash_of(&(reason, 10u128));
+		let old_tip = crate::migrations::v5::OldOpenTip::<u128, u64, u64, H256> {
+			reason,
+			who: 10,
+			finder: 20,
+			deposit: 30,
+			closes: Some(13),
+			tips: vec![(40, 50), (60, 70)],
+			finders_fee: true,
+		};
+		frame_support::storage::unhashed::put(
+			&pallet_tips::Tips::<Test>::hashed_key_for(hash),
+			&old_tip,
+		);
+		StorageVersion::new(4).put::<Tips>();
+
+		#[cfg(feature = "try-runtime")]
+		let state = crate::migrations::v5::MigrateV4ToV5::<Test>::pre_upgrade().unwrap();
+		crate::migrations::v5::MigrateV4ToV5::<Test>::on_runtime_upgrade();
+		#[cfg(feature = "try-runtime")]
+		crate::migrations::v5::MigrateV4ToV5::<Test>::post_upgrade(state).unwrap();
+
+		assert_eq!(
+			pallet_tips::Tips::<Test>::get(hash),
+			Some(OpenTip {
+				reason,
+				beneficiaries: beneficiaries(&[(10, 100)]),
+				finder: 20,
+				deposit: 30,
+				closes: Some(13),
+				tips: vec![(40, 50), (60, 70)],
+				finders_fee: true,
+			})
+		);
+		assert_eq!(Tips::on_chain_storage_version(), 5);
+	});
+}
+
+fn recurring_tip_hash() -> H256 {
+	BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"infra.dot"), 3u128))
+}
+
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		Tips::on_initialize(System::block_number());
+	}
+}
+
+fn tip_and_close(h: H256) {
+	assert_ok!(Tips::tip(RuntimeOrigin::signed(10), h, 10));
+	assert_ok!(Tips::tip(RuntimeOrigin::signed(11), h, 10));
+	assert_ok!(Tips::tip(RuntimeOrigin::signed(12), h, 10));
+	run_to_block(System::block_number() + 1);
+	assert_ok!(Tips::close_tip(RuntimeOrigin::signed(100), h.into()));
+}
+
+#[test]
+fn recurring_tip_is_renewed() {
+	build_and_execute(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_ok!(Tips::schedule_recurring_tip(
+			RuntimeOrigin::signed(10),
+			b"infra.dot".to_vec(),
+			3,
+			5,
+			2
+		));
+		let h = recurring_tip_hash();
+		assert_eq!(last_event(), TipEvent::RecurringTipScheduled { tip_hash: h });
+		assert!(pallet_tips::Tips::<Test>::contains_key(h));
+
+		tip_and_close(h);
+		assert_eq!(Balances::free_balance(3), 10);
+		assert!(!pallet_tips::Tips::<Test>::contains_key(h));
+
+		// tip is re-opened after the interval
+		run_to_block(5);
+		assert!(!pallet_tips::Tips::<Test>::contains_key(h));
+		run_to_block(6);
+		assert_eq!(last_event(), TipEvent::NewTip { tip_hash: h });
+		assert!(pallet_tips::Tips::<Test>::contains_key(h));
+
+		tip_and_close(h);
+		assert_eq!(Balances::free_balance(3), 20);
+	});
+}
+
+#[test]
+fn recurring_tip_waits_for_previous_tip_to_close() {
+	build_and_execute(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_ok!(Tips::schedule_recurring_tip(
+			RuntimeOrigin::signed(10),
+			b"infra.dot".to_vec(),
+			3,
+			5,
+			1
+		));
+		let h = recurring_tip_hash();
+
+		// previous tip is still open, so the renewal is postponed
+		assert_eq!(pallet_tips::RecurringTipRenewals::<Test>::get(6).into_inner(), vec![h]);
+		run_to_block(6);
+		assert!(!pallet_tips::RecurringTipRenewals::<Test>::contains_key(6));
+		assert_eq!(pallet_tips::RecurringTipRenewals::<Test>::get(11).into_inner(), vec![h]);
+		assert_eq!(pallet_tips::RecurringTips::<Test>::get(h).unwrap().next_renewal, 11);
+		assert_eq!(pallet_tips::RecurringTips::<Test>::get(h).unwrap().remaining_renewals, 1);
+
+		tip_and_close(h);
+		run_to_block(11);
+		assert!(pallet_tips::Tips::<Test>::contains_key(h));
+	});
+}
+
+#[test]
+fn recurring_tip_can_be_cancelled() {
+	build_and_execute(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_ok!(Tips::schedule_recurring_tip(
+			RuntimeOrigin::signed(10),
+			b"infra.dot".to_vec(),
+			3,
+			5,
+			2
+		));
+		let h = recurring_tip_hash();
+		tip_and_close(h);
+
+		assert_noop!(
+			Tips::cancel_recurring_tip(RuntimeOrigin::signed(11), h),
+			Error::<Test>::NotRecurringTipOwner
+		);
+		run_to_block(4);
+		assert_ok!(Tips::cancel_recurring_tip(RuntimeOrigin::signed(10), h));
+		assert_eq!(last_event(), TipEvent::RecurringTipCancelled { tip_hash: h });
+		assert!(!pallet_tips::RecurringTipRenewals::<Test>::contains_key(6));
+		assert_noop!(
+			Tips::cancel_recurring_tip(RuntimeOrigin::signed(10), h),
+			Error::<Test>::UnknownRecurringTip
+		);
+
+		run_to_block(20);
+		assert!(!pallet_tips::Tips::<Test>::contains_key(h));
+	});
+}
+
+#[test]
+fn recurring_tip_is_exhausted() {
+	build_and_execute(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_ok!(Tips::schedule_recurring_tip(
+			RuntimeOrigin::signed(10),
+			b"infra.dot".to_vec(),
+			3,
+			5,
+			2
+		));
+		let h = recurring_tip_hash();
+
+		tip_and_close(h);
+		run_to_block(6);
+		tip_and_close(h);
+		run_to_block(11);
+		assert_eq!(last_event(), TipEvent::RecurringTipExhausted { tip_hash: h });
+		assert!(!pallet_tips::RecurringTips::<Test>::contains_key(h));
+		tip_and_close(h);
+		assert_eq!(Balances::free_balance(3), 30);
+
+		run_to_block(30);
+		assert!(!pallet_tips::Tips::<Test>::contains_key(h));
+	});
+}
+
+#[test]
+fn recurring_tip_renewal_spills_over_to_next_block() {
+	build_and_execute(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		let full = BoundedVec::truncate_from(vec![H256::repeat_byte(1), H256::repeat_byte(2)]);
+		pallet_tips::RecurringTipRenewals::<Test>::insert(6, full.clone());
+		pallet_tips::RecurringTipRenewals::<Test>::insert(7, full);
+
+		// none of the next `MaxRecurringTips` blocks has room for the renewal
+		assert_noop!(
+			Tips::schedule_recurring_tip(RuntimeOrigin::signed(10), b"infra.dot".to_vec(), 3, 5, 2),
+			Error::<Test>::TooManyRecurringTips
+		);
+
+		pallet_tips::RecurringTipRenewals::<Test>::remove(7);
+		assert_ok!(Tips::schedule_recurring_tip(
+			RuntimeOrigin::signed(10),
+			b"infra.dot".to_vec(),
+			3,
+			5,
+			2
+		));
+		let h = recurring_tip_hash();
+		assert_eq!(pallet_tips::RecurringTipRenewals::<Test>::get(7).into_inner(), vec![h]);
+		assert_eq!(pallet_tips::RecurringTips::<Test>::get(h).unwrap().next_renewal, 7);
+
+		tip_and_close(h);
+		run_to_block(7);
+		assert_eq!(last_event(), TipEvent::NewTip { tip_hash: h });
+		assert_eq!(pallet_tips::RecurringTips::<Test>::get(h).unwrap().next_renewal, 12);
+	});
+}
+
+#[test]
+fn recurring_tip_cannot_be_scheduled_twice() {
+	build_and_execute(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_ok!(Tips::schedule_recurring_tip(
+			RuntimeOrigin::signed(10),
+			b"infra.dot".to_vec(),
+			3,
+			5,
+			2
+		));
+		assert_noop!(
+			Tips::schedule_recurring_tip(RuntimeOrigin::signed(11), b"infra.dot".to_vec(), 3, 5, 2),
+			Error::<Test>::AlreadyKnown
+		);
+
+		// still known after the tip has been closed
+		tip_and_close(recurring_tip_hash());
+		assert_noop!(
+			Tips::schedule_recurring_tip(RuntimeOrigin::signed(11), b"infra.dot".to_vec(), 3, 5, 2),
+			Error::<Test>::AlreadyKnown
+		);
+	});
+}
+
+#[test]
+fn schedule_recurring_tip_checks_arguments() {
+	build_and_execute(|| {
+		assert_noop!(
+			Tips::schedule_recurring_tip(RuntimeOrigin::signed(9), b"infra.dot".to_vec(), 3, 5, 2),
+			BadOrigin
+		);
+		assert_noop!(
+			Tips::schedule_recurring_tip(RuntimeOrigin::signed(10), b"infra.dot".to_vec(), 3, 0, 2),
+			Error::<Test>::ZeroInterval
+		);
+
+		assert_ok!(Tips::schedule_recurring_tip(RuntimeOrigin::signed(10), b"a".to_vec(), 3, 5, 2));
+		assert_ok!(Tips::schedule_recurring_tip(RuntimeOrigin::signed(10), b"b".to_vec(), 3, 5, 2));
+		assert_noop!(
+			Tips::schedule_recurring_tip(RuntimeOrigin::signed(10), b"c".to_vec(), 3, 5, 2),
+			Error::<Test>::TooManyRecurringTips
+		);
+	});
+}
+
+fn open_and_tip(reason: &[u8], who: u128) -> H256 {
+	assert_ok!(Tips::tip_new(RuntimeOrigin::signed(10), reason.to_vec(), who, 10));
+	let h = BlakeTwo256::hash_of(&(BlakeTwo256::hash(reason), who));
+	assert_ok!(Tips::tip(RuntimeOrigin::signed(11), h, 10));
+	assert_ok!(Tips::tip(RuntimeOrigin::signed(12), h, 10));
+	h
+}
+
+#[test]
+fn tip_payouts_are_limited_per_period() {
+	build_and_execute(|| {
+		MaxTipAmountPerPeriod::set(15);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		let h1 = open_and_tip(b"awesome.dot", 3);
+		let h2 = open_and_tip(b"great.dot", 4);
+
+		run_to_block(2);
+		assert_ok!(Tips::close_tip(RuntimeOrigin::signed(100), h1));
+		assert_eq!(Balances::free_balance(3), 10);
+		assert_eq!(pallet_tips::TipPayoutsThisPeriod::<Test>::get(), 10);
+
+		// the second tip would exceed the limit
+		assert_ok!(Tips::close_tip(RuntimeOrigin::signed(100), h2));
+		assert_eq!(last_event(), TipEvent::TipClosureDeferred { tip_hash: h2 });
+		assert_eq!(Balances::free_balance(4), 0);
+		assert!(pallet_tips::Tips::<Test>::contains_key(h2));
+		assert_eq!(pallet_tips::DeferredTipClosures::<Test>::get().into_inner(), vec![h2]);
+
+		// closing it again doesn't help
+		assert_ok!(Tips::close_tip(RuntimeOrigin::signed(100), h2));
+		assert_eq!(pallet_tips::DeferredTipClosures::<Test>::get().into_inner(), vec![h2]);
+		run_to_block(3);
+		assert_eq!(Balances::free_balance(4), 0);
+	});
+}
+
+#[test]
+fn deferred_tip_is_closed_in_next_period() {
+	build_and_execute(|| {
+		MaxTipAmountPerPeriod::set(15);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		let h1 = open_and_tip(b"awesome.dot", 3);
+		let h2 = open_and_tip(b"great.dot", 4);
+
+		run_to_block(2);
+		assert_ok!(Tips::close_tip(RuntimeOrigin::signed(100), h1));
+		assert_ok!(Tips::close_tip(RuntimeOrigin::signed(100), h2));
+		assert_eq!(Balances::free_balance(4), 0);
+
+		run_to_block(4);
+		assert_eq!(last_event(), TipEvent::TipClosed { tip_hash: h2, who: 4, payout: 10 });
+		assert_eq!(Balances::free_balance(4), 10);
+		assert!(!pallet_tips::Tips::<Test>::contains_key(h2));
+		assert!(pallet_tips::DeferredTipClosures::<Test>::get().is_empty());
+		assert_eq!(pallet_tips::TipPayoutsThisPeriod::<Test>::get(), 10);
+	});
+}
+
+#[test]
+fn retracted_deferred_tip_is_skipped() {
+	build_and_execute(|| {
+		MaxTipAmountPerPeriod::set(15);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		let h1 = open_and_tip(b"awesome.dot", 3);
+		assert_ok!(Tips::report_awesome(RuntimeOrigin::signed(0), b"great.dot".to_vec(), 4));
+		let h2 = BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"great.dot"), 4u128));
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(10), h2, 10));
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(11), h2, 10));
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(12), h2, 10));
+
+		run_to_block(2);
+		assert_ok!(Tips::close_tip(RuntimeOrigin::signed(100), h1));
+		assert_ok!(Tips::close_tip(RuntimeOrigin::signed(100), h2));
+		assert_eq!(pallet_tips::DeferredTipClosures::<Test>::get().into_inner(), vec![h2]);
+
+		run_to_block(3);
+		assert_ok!(Tips::retract_tip(RuntimeOrigin::signed(0), h2));
+		assert_eq!(Balances::reserved_balance(0), 0);
+
+		run_to_block(4);
+		assert_eq!(Balances::free_balance(4), 0);
+		assert_eq!(Balances::free_balance(0), 100);
+		assert!(pallet_tips::DeferredTipClosures::<Test>::get().is_empty());
+		assert_eq!(pallet_tips::TipPayoutsThisPeriod::<Test>::get(), 0);
+	});
+}
+
+#[test]
+fn deferred_tip_is_dropped_if_tippers_have_left() {
+	build_and_execute(|| {
+		MaxTipAmountPerPeriod::set(15);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		let h1 = open_and_tip(b"awesome.dot", 3);
+		assert_ok!(Tips::report_awesome(RuntimeOrigin::signed(0), b"great.dot".to_vec(), 4));
+		let h2 = BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"great.dot"), 4u128));
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(10), h2, 10));
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(11), h2, 10));
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(12), h2, 10));
+
+		run_to_block(2);
+		assert_ok!(Tips::close_tip(RuntimeOrigin::signed(100), h1));
+		assert_ok!(Tips::close_tip(RuntimeOrigin::signed(100), h2));
+		assert_eq!(pallet_tips::DeferredTipClosures::<Test>::get().into_inner(), vec![h2]);
+		assert_ne!(Balances::reserved_balance(0), 0);
+
+		// all tippers of the deferred tip leave before the next spend period
+		TenToFourteenTestValue::set(vec![13, 14]);
+
+		run_to_block(4);
+		assert_eq!(last_event(), TipEvent::TipDropped { tip_hash: h2 });
+		assert!(!pallet_tips::Tips::<Test>::contains_key(h2));
+		assert_eq!(Balances::free_balance(4), 0);
+		assert_eq!(Balances::reserved_balance(0), 0);
+		assert_eq!(Balances::free_balance(0), 100);
+		assert!(pallet_tips::DeferredTipClosures::<Test>::get().is_empty());
+		assert_eq!(pallet_tips::DeferredClosuresToRetry::<Test>::get(), 0);
+		assert_eq!(pallet_tips::TipPayoutsThisPeriod::<Test>::get(), 0);
+	});
+}
+
+#[test]
+fn tip_payout_is_capped_at_period_limit() {
+	build_and_execute(|| {
+		MaxTipAmountPerPeriod::set(8);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		let h = open_and_tip(b"awesome.dot", 3);
+
+		// the tip is paid out, instead of being deferred forever
+		run_to_block(2);
+		assert_ok!(Tips::close_tip(RuntimeOrigin::signed(100), h));
+		assert_eq!(last_event(), TipEvent::TipClosed { tip_hash: h, who: 3, payout: 8 });
+		assert_eq!(Balances::free_balance(3), 8);
+		assert_eq!(pallet_tips::TipPayoutsThisPeriod::<Test>::get(), 8);
+	});
+}
+
+#[test]
+fn number_of_deferred_tips_is_limited() {
+	build_and_execute(|| {
+		MaxTipAmountPerPeriod::set(15);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		let h1 = open_and_tip(b"awesome.dot", 3);
+		let h2 = open_and_tip(b"great.dot", 4);
+		let h3 = open_and_tip(b"superb.dot", 5);
+
+		run_to_block(2);
+		assert_ok!(Tips::close_tip(RuntimeOrigin::signed(100), h1));
+		assert_ok!(Tips::close_tip(RuntimeOrigin::signed(100), h2));
+		assert_noop!(
+			Tips::close_tip(RuntimeOrigin::signed(100), h3),
+			Error::<Test>::TooManyDeferredTips
+		);
+		assert_eq!(pallet_tips::DeferredTipClosures::<Test>::get().into_inner(), vec![h2]);
+	});
+}
+
+#[test]
+fn tipper_cooldown_prevents_rapid_retipping() {
+	build_and_execute(|| {
+		TipperCooldown::set(3);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		// the initial tip is not subject to the cooldown
+		assert_ok!(Tips::tip_new(RuntimeOrigin::signed(10), b"awesome.dot".to_vec(), 3, 10));
+		let h = tip_hash();
+		assert_eq!(pallet_tips::LastTipBlock::<Test>::get(h, 10), Some(1));
+
+		System::set_block_number(3);
+		assert_noop!(
+			Tips::tip(RuntimeOrigin::signed(10), h, 20),
+			Error::<Test>::CooldownNotElapsed
+		);
+		// other tippers are not affected
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(11), h, 10));
+
+		System::set_block_number(4);
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(10), h, 20));
+		assert_eq!(pallet_tips::LastTipBlock::<Test>::get(h, 10), Some(4));
+
+		// the cooldown restarts with every vote
+		System::set_block_number(6);
+		assert_noop!(
+			Tips::tip(RuntimeOrigin::signed(10), h, 10),
+			Error::<Test>::CooldownNotElapsed
+		);
+	});
+}
+
+#[test]
+fn tipper_cooldown_is_cleared_on_retraction_and_slash() {
+	build_and_execute(|| {
+		TipperCooldown::set(3);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_ok!(Tips::tip_new(RuntimeOrigin::signed(10), b"awesome.dot".to_vec(), 3, 10));
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(11), tip_hash(), 10));
+		assert_ok!(Tips::retract_tip(RuntimeOrigin::signed(10), tip_hash()));
+		assert_eq!(pallet_tips::LastTipBlock::<Test>::iter_prefix(tip_hash()).count(), 0);
+
+		assert_ok!(Tips::tip_new(RuntimeOrigin::signed(10), b"awesome.dot".to_vec(), 3, 20));
+		assert_ok!(Tips::slash_tip(RuntimeOrigin::root(), tip_hash()));
+		assert_eq!(pallet_tips::LastTipBlock::<Test>::iter_prefix(tip_hash()).count(), 0);
+	});
+}
+
+#[test]
+fn tipper_cooldown_is_cleared_on_close() {
+	build_and_execute(|| {
+		TipperCooldown::set(3);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		let h = open_and_tip(b"awesome.dot", 3);
+		assert_eq!(pallet_tips::LastTipBlock::<Test>::iter_prefix(h).count(), 3);
+
+		System::set_block_number(2);
+		assert_ok!(Tips::close_tip(RuntimeOrigin::signed(100), h));
+		assert_eq!(Balances::free_balance(3), 10);
+		assert_eq!(pallet_tips::LastTipBlock::<Test>::iter_prefix(h).count(), 0);
+	});
+}
+
+#[test]
+fn zero_tipper_cooldown_disables_the_cooldown() {
+	build_and_execute(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_ok!(Tips::tip_new(RuntimeOrigin::signed(10), b"awesome.dot".to_vec(), 3, 10));
+		let h = tip_hash();
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(10), h, 20));
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(10), h, 30));
+		assert_eq!(pallet_tips::LastTipBlock::<Test>::iter_prefix(h).count(), 0);
+	});
+}
+
+fn tip_hash_of(reason: &[u8], who: u128) -> H256 {
+	BlakeTwo256::hash_of(&(BlakeTwo256::hash(reason), who))
+}
+
+#[test]
+fn batch_tip_works() {
+	build_and_execute(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_ok!(Tips::tip_new(RuntimeOrigin::signed(10), b"awesome.dot".to_vec(), 3, 10));
+		assert_ok!(Tips::tip_new(RuntimeOrigin::signed(10), b"great.dot".to_vec(), 4, 10));
+		let h1 = tip_hash_of(b"awesome.dot", 3);
+		let h2 = tip_hash_of(b"great.dot", 4);
+
+		assert_noop!(
+			Tips::batch_tip(RuntimeOrigin::signed(9), bounded_vec![(h1, 10), (h2, 10)]),
+			BadOrigin
+		);
+		assert_ok!(Tips::batch_tip(RuntimeOrigin::signed(11), bounded_vec![(h1, 10), (h2, 20)]));
+		assert_ok!(Tips::batch_tip(RuntimeOrigin::signed(12), bounded_vec![(h1, 10), (h2, 30)]));
+		assert_eq!(Tips::tips(h2).unwrap().tips, vec![(10, 10), (11, 20), (12, 30)]);
+		assert_eq!(last_event(), TipEvent::TipClosing { tip_hash: h2 });
+
+		System::set_block_number(2);
+		assert_ok!(Tips::close_tip(RuntimeOrigin::signed(100), h1));
+		assert_ok!(Tips::close_tip(RuntimeOrigin::signed(100), h2));
+		assert_eq!(Balances::free_balance(3), 10);
+		assert_eq!(Balances::free_balance(4), 20);
+	});
+}
+
+#[test]
+fn batch_tip_is_atomic() {
+	build_and_execute(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_ok!(Tips::tip_new(RuntimeOrigin::signed(10), b"awesome.dot".to_vec(), 3, 10));
+		let h = tip_hash_of(b"awesome.dot", 3);
+
+		assert_noop!(
+			Tips::batch_tip(
+				RuntimeOrigin::signed(11),
+				bounded_vec![(h, 10), (tip_hash_of(b"unknown.dot", 3), 10)]
+			),
+			Error::<Test>::UnknownTip
+		);
+		assert_noop!(
+			Tips::batch_tip(RuntimeOrigin::signed(11), bounded_vec![(h, 10), (h, 10_000_001)]),
+			Error::<Test>::MaxTipAmountExceeded
+		);
+		assert_eq!(Tips::tips(h).unwrap().tips, vec![(10, 10)]);
+	});
+}
+
+#[test]
+fn batch_tip_is_limited_to_max_votes_per_batch() {
+	build_and_execute(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		let hashes: Vec<_> = (0..21u128)
+			.map(|who| {
+				let reason = who.encode();
+				assert_ok!(Tips::tip_new(RuntimeOrigin::signed(10), reason.clone(), who, 10));
+				tip_hash_of(&reason, who)
+			})
+			.collect();
+		let votes: Vec<_> = hashes.into_iter().map(|h| (h, 10)).collect();
+
+		assert!(TipVotesOf::<Test>::try_from(votes.clone()).is_err());
+		let votes = TipVotesOf::<Test>::truncate_from(votes);
+		assert_eq!(votes.len(), 20);
+		assert_ok!(Tips::batch_tip(RuntimeOrigin::signed(11), votes.clone()));
+		for (h, _) in votes {
+			assert_eq!(Tips::tips(h).unwrap().tips, vec![(10, 10), (11, 10)]);
+		}
+	});
+}
+
+#[test]
+fn tip_match_adds_to_payout() {
+	build_and_execute(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		let h = open_and_tip(b"awesome.dot", 3);
+
+		assert_noop!(Tips::tip_match(RuntimeOrigin::signed(10), h, 25), BadOrigin);
+		assert_noop!(
+			Tips::tip_match(RuntimeOrigin::root(), tip_hash_of(b"unknown.dot", 3), 25),
+			Error::<Test>::UnknownTip
+		);
+		assert_ok!(Tips::tip_match(RuntimeOrigin::root(), h, 25));
+		assert_eq!(last_event(), TipEvent::TipMatched { tip_hash: h, amount: 25 });
+
+		System::set_block_number(2);
+		assert_ok!(Tips::close_tip(RuntimeOrigin::signed(100), h));
+		assert_eq!(Balances::free_balance(3), 35);
+		assert_eq!(Treasury::pot(), 65);
+		assert_eq!(pallet_tips::MatchedTips::<Test>::get(h), None);
+	});
+}
+
+#[test]
+fn tip_match_is_capped_at_treasury_balance() {
+	build_and_execute(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		let h = open_and_tip(b"awesome.dot", 3);
+		assert_ok!(Tips::tip_match(RuntimeOrigin::root(), h, 200));
+
+		System::set_block_number(2);
+		assert_ok!(Tips::close_tip(RuntimeOrigin::signed(100), h));
+		assert_eq!(Balances::free_balance(3), 100);
+		assert_eq!(Treasury::pot(), 0);
+	});
+}
+
+#[test]
+fn cancel_tip_match_works() {
+	build_and_execute(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		let h = open_and_tip(b"awesome.dot", 3);
+		assert_noop!(Tips::cancel_tip_match(RuntimeOrigin::root(), h), Error::<Test>::NotMatched);
+
+		assert_ok!(Tips::tip_match(RuntimeOrigin::root(), h, 25));
+		assert_noop!(Tips::cancel_tip_match(RuntimeOrigin::signed(10), h), BadOrigin);
+		assert_ok!(Tips::cancel_tip_match(RuntimeOrigin::root(), h));
+		assert_eq!(last_event(), TipEvent::TipMatchCancelled { tip_hash: h });
+
+		System::set_block_number(2);
+		assert_ok!(Tips::close_tip(RuntimeOrigin::signed(100), h));
+		assert_eq!(Balances::free_balance(3), 10);
+		assert_eq!(Treasury::pot(), 90);
+	});
+}
+
+#[test]
+fn tip_match_is_dropped_when_tip_is_retracted() {
+	build_and_execute(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_ok!(Tips::report_awesome(RuntimeOrigin::signed(0), b"awesome.dot".to_vec(), 3));
+		let h = tip_hash();
+		assert_ok!(Tips::tip_match(RuntimeOrigin::root(), h, 25));
+
+		assert_ok!(Tips::retract_tip(RuntimeOrigin::signed(0), h));
+		assert_eq!(pallet_tips::MatchedTips::<Test>::get(h), None);
+		System::assert_has_event(TipEvent::TipMatchCancelled { tip_hash: h }.into());
+
+		// the match doesn't survive re-opening of the same tip
+		assert_ok!(Tips::report_awesome(RuntimeOrigin::signed(0), b"awesome.dot".to_vec(), 3));
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(10), h, 10));
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(11), h, 10));
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(12), h, 10));
+		System::set_block_number(2);
+		assert_ok!(Tips::close_tip(RuntimeOrigin::signed(100), h));
+		assert_eq!(Treasury::pot(), 90);
+	});
+}
+
+fn close_vested_tip(vesting_blocks: u64) -> H256 {
+	Balances::make_free_balance_be(&Treasury::account_id(), 101);
+	let h = open_and_tip(b"awesome.dot", 3);
+	System::set_block_number(2);
+	assert_ok!(Tips::close_tip_vested(RuntimeOrigin::signed(100), h, vesting_blocks));
+	h
+}
+
+#[test]
+fn tip_vesting_pays_out_in_installments() {
+	build_and_execute(|| {
+		let h = close_vested_tip(4);
+		assert_eq!(last_event(), TipEvent::TipVestingStarted { tip_hash: h, until: 6 });
+		assert_eq!(Balances::free_balance(3), 0);
+		assert_eq!(Balances::free_balance(Tips::vesting_account()), 10);
+		assert_eq!(Treasury::pot(), 90);
+
+		// nothing is vested at the block the tip is closed
+		assert_noop!(
+			Tips::claim_tip_vesting(RuntimeOrigin::signed(100), h),
+			Error::<Test>::NothingVested
+		);
+
+		System::set_block_number(3);
+		assert_ok!(Tips::claim_tip_vesting(RuntimeOrigin::signed(100), h));
+		assert_eq!(Balances::free_balance(3), 2);
+		assert_eq!(last_event(), TipEvent::TipVestingClaimed { tip_hash: h, who: 3, amount: 2 });
+
+		// installments, which haven't been claimed in time, are claimed at once
+		System::set_block_number(5);
+		assert_ok!(Tips::claim_tip_vesting(RuntimeOrigin::signed(3), h));
+		assert_eq!(Balances::free_balance(3), 6);
+		assert_noop!(
+			Tips::claim_tip_vesting(RuntimeOrigin::signed(3), h),
+			Error::<Test>::NothingVested
+		);
+
+		// the rounding remainder is paid with the last installment
+		System::set_block_number(6);
+		assert_ok!(Tips::claim_tip_vesting(RuntimeOrigin::signed(3), h));
+		assert_eq!(Balances::free_balance(3), 10);
+		assert_eq!(Balances::free_balance(Tips::vesting_account()), 0);
+		assert_eq!(pallet_tips::TipVestingSchedules::<Test>::get(h), None);
+	});
+}
+
+#[test]
+fn tip_vesting_is_paid_at_once_after_the_period() {
+	build_and_execute(|| {
+		let h = close_vested_tip(4);
+
+		System::set_block_number(11);
+		assert_ok!(Tips::claim_tip_vesting(RuntimeOrigin::signed(100), h));
+		assert_eq!(Balances::free_balance(3), 10);
+		assert_noop!(
+			Tips::claim_tip_vesting(RuntimeOrigin::signed(100), h),
+			Error::<Test>::UnknownTipVesting
+		);
+	});
+}
+
+#[test]
+fn unclaimed_tip_vesting_is_returned_to_treasury() {
+	build_and_execute(|| {
+		let h = close_vested_tip(4);
+		System::set_block_number(3);
+		assert_ok!(Tips::claim_tip_vesting(RuntimeOrigin::signed(100), h));
+
+		// the installments must be claimed within `VestingClaimPeriod` after the vesting ends
+		System::set_block_number(12);
+		assert_ok!(Tips::claim_tip_vesting(RuntimeOrigin::signed(100), h));
+		assert_eq!(last_event(), TipEvent::TipVestingExpired { tip_hash: h, unclaimed: 8 });
+		assert_eq!(Balances::free_balance(3), 2);
+		assert_eq!(Treasury::pot(), 98);
+		assert_eq!(pallet_tips::TipVestingSchedules::<Test>::get(h), None);
+	});
+}
+
+#[test]
+fn expired_tip_vesting_is_returned_to_treasury_automatically() {
+	build_and_execute(|| {
+		let h = close_vested_tip(4);
+		assert_eq!(Treasury::pot(), 90);
+
+		// nobody claims the installments, they are returned at the end of the claim period
+		Tips::on_initialize(11);
+		assert_eq!(pallet_tips::TipVestingSchedules::<Test>::get(h).map(|s| s.period), Some(4));
+		Tips::on_initialize(12);
+		assert_eq!(last_event(), TipEvent::TipVestingExpired { tip_hash: h, unclaimed: 10 });
+		assert_eq!(Balances::free_balance(3), 0);
+		assert_eq!(Treasury::pot(), 100);
+		assert_eq!(pallet_tips::TipVestingSchedules::<Test>::get(h), None);
+		assert!(pallet_tips::TipVestingExpiries::<Test>::get(12).is_empty());
+	});
+}
+
+#[test]
+fn tip_vesting_claim_skips_beneficiaries_that_cant_be_paid() {
+	build_and_execute(|| {
+		ExistentialDeposit::set(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		// the vesting account is kept alive by the payouts of other tips
+		Balances::make_free_balance_be(&Tips::vesting_account(), 2);
+		let shares = [(3, 80), (4, 20)];
+		assert_ok!(Tips::tip_new_split(
+			RuntimeOrigin::signed(0),
+			b"awesome.dot".to_vec(),
+			beneficiaries(&shares)
+		));
+		let h = split_tip_hash(&shares);
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(10), h, 10));
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(11), h, 10));
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(12), h, 10));
+		System::set_block_number(2);
+		assert_ok!(Tips::close_tip_vested(RuntimeOrigin::signed(100), h, 4));
+
+		// 3 is paid 7 and 4 is paid 1, which is below the existential deposit
+		System::set_block_number(6);
+		assert_ok!(Tips::claim_tip_vesting(RuntimeOrigin::signed(100), h));
+		assert_eq!(Balances::free_balance(3), 7);
+		assert_eq!(Balances::free_balance(4), 0);
+		assert_eq!(
+			last_event(),
+			TipEvent::TipVestingClaimFailed {
+				tip_hash: h,
+				who: 4,
+				amount: 1,
+				error: TokenError::BelowMinimum.into(),
+			}
+		);
+
+		// the dust is returned to the treasury once the vesting expires
+		let pot = Treasury::pot();
+		Tips::on_initialize(12);
+		assert_eq!(last_event(), TipEvent::TipVestingExpired { tip_hash: h, unclaimed: 1 });
+		assert_eq!(Treasury::pot(), pot + 1);
+		assert_eq!(pallet_tips::TipVestingSchedules::<Test>::get(h), None);
+	});
+}
+
+#[test]
+fn zero_tip_vesting_pays_out_immediately() {
+	build_and_execute(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		let h = open_and_tip(b"awesome.dot", 3);
+
+		System::set_block_number(2);
+		assert_ok!(Tips::close_tip_vested(RuntimeOrigin::signed(100), h, 0));
+		assert_eq!(Balances::free_balance(3), 10);
+		assert_eq!(pallet_tips::TipVestingSchedules::<Test>::get(h), None);
+	});
+}
+
+#[test]
+fn tip_vesting_requires_previous_vesting_to_finish() {
+	build_and_execute(|| {
+		let h = close_vested_tip(4);
+
+		// the same tip is reported and tipped again
+		let h2 = open_and_tip(b"awesome.dot", 3);
+		assert_eq!(h, h2);
+		System::set_block_number(3);
+		assert_noop!(
+			Tips::close_tip_vested(RuntimeOrigin::signed(100), h, 4),
+			Error::<Test>::TipVestingInProgress
+		);
+
+		// the payout may still be made immediately
+		assert_ok!(Tips::close_tip(RuntimeOrigin::signed(100), h));
+		assert_eq!(Balances::free_balance(3), 10);
+		assert_eq!(pallet_tips::TipVestingSchedules::<Test>::get(h).map(|s| s.period), Some(4));
+	});
+}
+
+fn report_cross_chain_and_tip(beneficiary: u128) -> H256 {
+	assert_ok!(Tips::report_awesome_cross_chain(
+		RuntimeOrigin::signed(0),
+		b"awesome.dot".to_vec(),
+		Box::new(beneficiary)
+	));
+	let h = tip_hash_of(b"awesome.dot", beneficiary);
+	assert_ok!(Tips::tip(RuntimeOrigin::signed(10), h, 10));
+	assert_ok!(Tips::tip(RuntimeOrigin::signed(11), h, 10));
+	assert_ok!(Tips::tip(RuntimeOrigin::signed(12), h, 10));
+	h
+}
+
+#[test]
+fn cross_chain_tip_is_paid_with_cross_chain_paymaster() {
+	build_and_execute(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		let h = report_cross_chain_and_tip(300);
+		assert_eq!(Balances::reserved_balance(0), 12);
+		assert_eq!(pallet_tips::CrossChainBeneficiaries::<Test>::get(h), Some(300));
+		assert!(Tips::tips(h).unwrap().beneficiaries.is_empty());
+
+		// cross-chain tips are never vested
+		System::set_block_number(2);
+		assert_ok!(Tips::close_tip_vested(RuntimeOrigin::signed(100), h, 4));
+		assert_eq!(
+			last_event(),
+			TipEvent::CrossChainPayoutSent { tip_hash: h, payout: 8, payment_id: 1 }
+		);
+		assert_eq!(CrossChainPayments::get(), vec![(300, 8)]);
+		assert_eq!(Balances::free_balance(300), 0);
+
+		// the finder's fee is paid locally
+		assert_eq!(Balances::reserved_balance(0), 0);
+		assert_eq!(Balances::free_balance(0), 102);
+		assert_eq!(pallet_tips::CrossChainBeneficiaries::<Test>::get(h), None);
+		assert_eq!(pallet_tips::TipVestingSchedules::<Test>::get(h), None);
+	});
+}
+
+#[test]
+fn failed_cross_chain_payout_is_reported() {
+	build_and_execute(|| {
+		CrossChainPayFails::set(true);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		let h = report_cross_chain_and_tip(300);
+
+		System::set_block_number(2);
+		assert_ok!(Tips::close_tip(RuntimeOrigin::signed(100), h));
+		assert_eq!(
+			last_event(),
+			TipEvent::CrossChainPayoutFailed { tip_hash: h, payout: 8, reason: () }
+		);
+		assert_eq!(CrossChainPayments::get(), vec![]);
+		assert_eq!(Treasury::pot(), 98);
+		// only the finder's fee is counted
+		assert_eq!(pallet_tips::TipPayoutsThisPeriod::<Test>::get(), 2);
+		assert_eq!(pallet_tips::CrossChainBeneficiaries::<Test>::get(h), None);
+	});
+}
+
+#[test]
+fn cross_chain_beneficiary_is_removed_when_tip_is_retracted() {
+	build_and_execute(|| {
+		assert_ok!(Tips::report_awesome_cross_chain(
+			RuntimeOrigin::signed(0),
+			b"awesome.dot".to_vec(),
+			Box::new(300)
+		));
+		assert_noop!(
+			Tips::report_awesome_cross_chain(
+				RuntimeOrigin::signed(1),
+				b"awesome.dot".to_vec(),
+				Box::new(300)
+			),
+			Error::<Test>::AlreadyKnown
+		);
+
+		let h = tip_hash_of(b"awesome.dot", 300);
+		assert_ok!(Tips::retract_tip(RuntimeOrigin::signed(0), h));
+		assert_eq!(pallet_tips::CrossChainBeneficiaries::<Test>::get(h), None);
+		assert_eq!(Balances::reserved_balance(0), 0);
+	});
+}
+
 #[test]
 fn test_last_reward_migration() {
 	let mut s = Storage::default();
@@ -512,7 +1603,7 @@ fn test_last_reward_migration() {
 			pallet_tips::Tips::<Test>::get(hash1),
 			Some(OpenTip {
 				reason: reason1,
-				who: 10,
+				beneficiaries: beneficiaries(&[(10, 100)]),
 				finder: 20,
 				deposit: 30,
 				closes: Some(13),
@@ -526,7 +1617,7 @@ fn test_last_reward_migration() {
 			pallet_tips::Tips::<Test>::get(hash2),
 			Some(OpenTip {
 				reason: reason2,
-				who: 20,
+				beneficiaries: beneficiaries(&[(20, 100)]),
 				finder: Default::default(),
 				deposit: 0,
 				closes: Some(13),
@@ -542,9 +1633,9 @@ fn test_migration_v4() {
 	let reason1 = BlakeTwo256::hash(b"reason1");
 	let hash1 = BlakeTwo256::hash_of(&(reason1, 10u64));
 
-	let tip = OpenTip::<u128, u64, u64, H256> {
+	let tip = OpenTip::<u128, u64, u64, H256, ConstU32<3>> {
 		reason: reason1,
-		who: 10,
+		beneficiaries: beneficiaries(&[(10, 100)]),
 		finder: 20,
 		deposit: 30,
 		closes: Some(13),
@@ -659,9 +1750,9 @@ fn equal_entries_invariant() {
 		let reason1 = BlakeTwo256::hash(b"reason1");
 		let hash1 = BlakeTwo256::hash_of(&(reason1, 10u64));
 
-		let tip = OpenTip::<u128, u64, u64, H256> {
+		let tip = OpenTip::<u128, u64, u64, H256, ConstU32<3>> {
 			reason: reason1,
-			who: 10,
+			beneficiaries: beneficiaries(&[(10, 100)]),
 			finder: 20,
 			deposit: 30,
 			closes: Some(13),
@@ -723,6 +1814,39 @@ fn reasons_invariant() {
 	})
 }
 
+#[test]
+fn quadratic_data_deposit_grows_quadratically() {
+	type Curve = QuadraticDataDepositCurve<ConstU64<2>, ConstU64<1>>;
+
+	assert_eq!(Curve::deposit_for_length(0), 0);
+	assert_eq!(Curve::deposit_for_length(1), 3);
+	assert_eq!(Curve::deposit_for_length(10), 120);
+	assert_eq!(Curve::deposit_for_length(100), 10_200);
+	// doubling the length roughly quadruples the deposit
+	assert_eq!(Curve::deposit_for_length(200), 40_400);
+	assert_eq!(Curve::deposit_for_length(u32::MAX), u64::MAX);
+}
+
+#[test]
+fn report_deposit_uses_data_deposit_curve() {
+	build_and_execute(|| {
+		DataDepositPerByte::set(3);
+		assert_eq!(Tips::report_deposit(11), 1 + 3 * 11);
+
+		assert_ok!(Tips::report_awesome(RuntimeOrigin::signed(0), b"awesome.dot".to_vec(), 3));
+		assert_eq!(Balances::reserved_balance(0), 1 + 3 * 11);
+	});
+}
+
+#[test]
+#[should_panic = "`DataDeposit` should not be zero"]
+fn zero_data_deposit_prohibited() {
+	new_test_ext().execute_with(|| {
+		DataDepositPerByte::set(0);
+		Tips::integrity_test();
+	});
+}
+
 #[test]
 #[should_panic = "`TipReportDepositBase` should not be zero"]
 fn zero_base_deposit_prohibited() {
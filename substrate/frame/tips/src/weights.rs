@@ -23,6 +23,21 @@
 //! HOSTNAME: `runner-e8ezs4ez-project-145-concurrent-0`, CPU: `Intel(R) Xeon(R) CPU @ 2.60GHz`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
 
+// The weights of the following calls have not been generated by the benchmark CLI yet. They are
+// estimated from the weights of similar calls and the storage accesses of their benchmarks and
+// must be replaced by the output of the command below:
+// - `tip_new_split`
+// - `schedule_recurring_tip`
+// - `cancel_recurring_tip`
+// - `renew_recurring_tips`
+// - `tip_match`
+// - `cancel_tip_match`
+// - `retract_tip`
+// - `close_tip`
+// - `close_tip_vested`
+// - `claim_tip_vesting`
+// - `report_awesome_cross_chain`
+
 // Executed Command:
 // ./target/production/substrate
 // benchmark
@@ -57,7 +72,16 @@ pub trait WeightInfo {
 	fn tip_new(r: u32, t: u32, ) -> Weight;
 	fn tip(t: u32, ) -> Weight;
 	fn close_tip(t: u32, ) -> Weight;
+	fn close_tip_vested(t: u32, ) -> Weight;
 	fn slash_tip(t: u32, ) -> Weight;
+	fn tip_new_split(r: u32, b: u32, ) -> Weight;
+	fn schedule_recurring_tip(r: u32, t: u32, ) -> Weight;
+	fn cancel_recurring_tip() -> Weight;
+	fn renew_recurring_tips(n: u32, ) -> Weight;
+	fn tip_match() -> Weight;
+	fn cancel_tip_match() -> Weight;
+	fn claim_tip_vesting(b: u32, ) -> Weight;
+	fn report_awesome_cross_chain(r: u32, ) -> Weight;
 }
 
 /// Weights for pallet_tips using the Substrate node and recommended hardware.
@@ -84,13 +108,12 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Storage: Tips Reasons (r:0 w:1)
 	/// Proof Skipped: Tips Reasons (max_values: None, max_size: None, mode: Measured)
 	fn retract_tip() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `221`
-		//  Estimated: `3686`
-		// Minimum execution time: 28_522_000 picoseconds.
-		Weight::from_parts(29_323_000, 3686)
-			.saturating_add(T::DbWeight::get().reads(1_u64))
-			.saturating_add(T::DbWeight::get().writes(2_u64))
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		// Estimated as the previous output of `retract_tip` plus dropping the tip match and the
+		// votes of all 13 tippers.
+		Weight::from_parts(35_000_000, 3686)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(18_u64))
 	}
 	/// Storage: Elections Members (r:1 w:0)
 	/// Proof Skipped: Elections Members (max_values: Some(1), max_size: None, mode: Measured)
@@ -141,15 +164,37 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof Skipped: Tips Reasons (max_values: None, max_size: None, mode: Measured)
 	/// The range of component `t` is `[1, 13]`.
 	fn close_tip(t: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `786 + t * (112 ±0)`
-		//  Estimated: `4242 + t * (112 ±0)`
-		// Minimum execution time: 62_059_000 picoseconds.
-		Weight::from_parts(64_604_554, 4242)
-			// Standard Error: 11_818
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		// Estimated as the previous output of `close_tip` plus the payouts to the finder and all 10
+		// beneficiaries, the matched amount and the tipper votes.
+		Weight::from_parts(265_000_000, 30272)
 			.saturating_add(Weight::from_parts(116_297, 0).saturating_mul(t.into()))
-			.saturating_add(T::DbWeight::get().reads(3_u64))
-			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().reads(17_u64))
+			.saturating_add(T::DbWeight::get().writes(17_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(t.into())))
+			.saturating_add(Weight::from_parts(0, 112).saturating_mul(t.into()))
+	}
+	/// Storage: Tips Tips (r:1 w:1)
+	/// Proof Skipped: Tips Tips (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Elections Members (r:1 w:0)
+	/// Proof Skipped: Elections Members (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Tips TipVestingSchedules (r:2 w:1)
+	/// Proof Skipped: Tips TipVestingSchedules (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tips TipVestingPeriods (r:1 w:1)
+	/// Proof Skipped: Tips TipVestingPeriods (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Tips Reasons (r:0 w:1)
+	/// Proof Skipped: Tips Reasons (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `t` is `[1, 13]`.
+	fn close_tip_vested(t: u32, ) -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		// Estimated as `close_tip` plus starting the vesting of the payout.
+		Weight::from_parts(275_000_000, 30272)
+			.saturating_add(Weight::from_parts(116_297, 0).saturating_mul(t.into()))
+			.saturating_add(T::DbWeight::get().reads(20_u64))
+			.saturating_add(T::DbWeight::get().writes(19_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(t.into())))
 			.saturating_add(Weight::from_parts(0, 112).saturating_mul(t.into()))
 	}
 	/// Storage: Tips Tips (r:1 w:1)
@@ -168,6 +213,118 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
+	/// Storage: Tips Reasons (r:1 w:1)
+	/// Proof Skipped: Tips Reasons (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tips Tips (r:1 w:1)
+	/// Proof Skipped: Tips Tips (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `r` is `[0, 300]`.
+	/// The range of component `b` is `[1, 10]`.
+	fn tip_new_split(r: u32, b: u32, ) -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		// Estimated as `report_awesome` plus encoding and hashing of beneficiaries.
+		Weight::from_parts(30_722_650, 3469)
+			.saturating_add(Weight::from_parts(2_601, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(150_000, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Elections Members (r:1 w:0)
+	/// Proof Skipped: Elections Members (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Tips Reasons (r:1 w:1)
+	/// Proof Skipped: Tips Reasons (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tips RecurringTips (r:1 w:1)
+	/// Proof Skipped: Tips RecurringTips (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tips CounterForRecurringTips (r:1 w:1)
+	/// Proof: Tips CounterForRecurringTips (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tips Tips (r:0 w:1)
+	/// Proof Skipped: Tips Tips (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `r` is `[0, 300]`.
+	/// The range of component `t` is `[1, 13]`.
+	fn schedule_recurring_tip(r: u32, t: u32, ) -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		// Estimated as `tip_new` plus the recurring tip bookkeeping.
+		Weight::from_parts(22_000_000, 3991)
+			.saturating_add(Weight::from_parts(3_000, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(60_000, 0).saturating_mul(t.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: Tips RecurringTips (r:1 w:1)
+	/// Proof Skipped: Tips RecurringTips (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tips CounterForRecurringTips (r:1 w:1)
+	/// Proof: Tips CounterForRecurringTips (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn cancel_recurring_tip() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(15_000_000, 3686)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Tips CounterForRecurringTips (r:1 w:0)
+	/// Proof: Tips CounterForRecurringTips (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tips RecurringTips (r:n w:n)
+	/// Proof Skipped: Tips RecurringTips (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tips Tips (r:n w:n)
+	/// Proof Skipped: Tips Tips (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tips Reasons (r:n w:n)
+	/// Proof Skipped: Tips Reasons (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[0, 10]`.
+	fn renew_recurring_tips(n: u32, ) -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(3_000_000, 1489)
+			.saturating_add(Weight::from_parts(25_000_000, 2500).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Tips Tips (r:1 w:0)
+	/// Proof Skipped: Tips Tips (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tips MatchedTips (r:0 w:1)
+	/// Proof Skipped: Tips MatchedTips (max_values: None, max_size: None, mode: Measured)
+	fn tip_match() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(12_000_000, 3469)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Tips MatchedTips (r:1 w:1)
+	/// Proof Skipped: Tips MatchedTips (max_values: None, max_size: None, mode: Measured)
+	fn cancel_tip_match() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(11_000_000, 3469)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Tips TipVestingSchedules (r:1 w:1)
+	/// Proof Skipped: Tips TipVestingSchedules (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: System Account (r:b w:b)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// The range of component `b` is `[1, 10]`.
+	fn claim_tip_vesting(b: u32, ) -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(20_000_000, 3593)
+			.saturating_add(Weight::from_parts(30_000_000, 2603).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(b.into())))
+	}
+	/// Storage: Tips Reasons (r:1 w:1)
+	/// Proof Skipped: Tips Reasons (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tips Tips (r:1 w:1)
+	/// Proof Skipped: Tips Tips (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tips CrossChainBeneficiaries (r:0 w:1)
+	/// Proof Skipped: Tips CrossChainBeneficiaries (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `r` is `[0, 300]`.
+	fn report_awesome_cross_chain(r: u32, ) -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		// Estimated as `report_awesome` plus the beneficiary on another chain.
+		Weight::from_parts(33_000_000, 3469)
+			.saturating_add(Weight::from_parts(2_601, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -193,13 +350,12 @@ impl WeightInfo for () {
 	/// Storage: Tips Reasons (r:0 w:1)
 	/// Proof Skipped: Tips Reasons (max_values: None, max_size: None, mode: Measured)
 	fn retract_tip() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `221`
-		//  Estimated: `3686`
-		// Minimum execution time: 28_522_000 picoseconds.
-		Weight::from_parts(29_323_000, 3686)
-			.saturating_add(RocksDbWeight::get().reads(1_u64))
-			.saturating_add(RocksDbWeight::get().writes(2_u64))
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		// Estimated as the previous output of `retract_tip` plus dropping the tip match and the
+		// votes of all 13 tippers.
+		Weight::from_parts(35_000_000, 3686)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(18_u64))
 	}
 	/// Storage: Elections Members (r:1 w:0)
 	/// Proof Skipped: Elections Members (max_values: Some(1), max_size: None, mode: Measured)
@@ -250,15 +406,37 @@ impl WeightInfo for () {
 	/// Proof Skipped: Tips Reasons (max_values: None, max_size: None, mode: Measured)
 	/// The range of component `t` is `[1, 13]`.
 	fn close_tip(t: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `786 + t * (112 ±0)`
-		//  Estimated: `4242 + t * (112 ±0)`
-		// Minimum execution time: 62_059_000 picoseconds.
-		Weight::from_parts(64_604_554, 4242)
-			// Standard Error: 11_818
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		// Estimated as the previous output of `close_tip` plus the payouts to the finder and all 10
+		// beneficiaries, the matched amount and the tipper votes.
+		Weight::from_parts(265_000_000, 30272)
 			.saturating_add(Weight::from_parts(116_297, 0).saturating_mul(t.into()))
-			.saturating_add(RocksDbWeight::get().reads(3_u64))
-			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().reads(17_u64))
+			.saturating_add(RocksDbWeight::get().writes(17_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(t.into())))
+			.saturating_add(Weight::from_parts(0, 112).saturating_mul(t.into()))
+	}
+	/// Storage: Tips Tips (r:1 w:1)
+	/// Proof Skipped: Tips Tips (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Elections Members (r:1 w:0)
+	/// Proof Skipped: Elections Members (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Tips TipVestingSchedules (r:2 w:1)
+	/// Proof Skipped: Tips TipVestingSchedules (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tips TipVestingPeriods (r:1 w:1)
+	/// Proof Skipped: Tips TipVestingPeriods (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Tips Reasons (r:0 w:1)
+	/// Proof Skipped: Tips Reasons (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `t` is `[1, 13]`.
+	fn close_tip_vested(t: u32, ) -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		// Estimated as `close_tip` plus starting the vesting of the payout.
+		Weight::from_parts(275_000_000, 30272)
+			.saturating_add(Weight::from_parts(116_297, 0).saturating_mul(t.into()))
+			.saturating_add(RocksDbWeight::get().reads(20_u64))
+			.saturating_add(RocksDbWeight::get().writes(19_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(t.into())))
 			.saturating_add(Weight::from_parts(0, 112).saturating_mul(t.into()))
 	}
 	/// Storage: Tips Tips (r:1 w:1)
@@ -277,4 +455,116 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
+	/// Storage: Tips Reasons (r:1 w:1)
+	/// Proof Skipped: Tips Reasons (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tips Tips (r:1 w:1)
+	/// Proof Skipped: Tips Tips (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `r` is `[0, 300]`.
+	/// The range of component `b` is `[1, 10]`.
+	fn tip_new_split(r: u32, b: u32, ) -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		// Estimated as `report_awesome` plus encoding and hashing of beneficiaries.
+		Weight::from_parts(30_722_650, 3469)
+			.saturating_add(Weight::from_parts(2_601, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(150_000, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Elections Members (r:1 w:0)
+	/// Proof Skipped: Elections Members (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Tips Reasons (r:1 w:1)
+	/// Proof Skipped: Tips Reasons (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tips RecurringTips (r:1 w:1)
+	/// Proof Skipped: Tips RecurringTips (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tips CounterForRecurringTips (r:1 w:1)
+	/// Proof: Tips CounterForRecurringTips (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tips Tips (r:0 w:1)
+	/// Proof Skipped: Tips Tips (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `r` is `[0, 300]`.
+	/// The range of component `t` is `[1, 13]`.
+	fn schedule_recurring_tip(r: u32, t: u32, ) -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		// Estimated as `tip_new` plus the recurring tip bookkeeping.
+		Weight::from_parts(22_000_000, 3991)
+			.saturating_add(Weight::from_parts(3_000, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(60_000, 0).saturating_mul(t.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: Tips RecurringTips (r:1 w:1)
+	/// Proof Skipped: Tips RecurringTips (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tips CounterForRecurringTips (r:1 w:1)
+	/// Proof: Tips CounterForRecurringTips (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn cancel_recurring_tip() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(15_000_000, 3686)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Tips CounterForRecurringTips (r:1 w:0)
+	/// Proof: Tips CounterForRecurringTips (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tips RecurringTips (r:n w:n)
+	/// Proof Skipped: Tips RecurringTips (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tips Tips (r:n w:n)
+	/// Proof Skipped: Tips Tips (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tips Reasons (r:n w:n)
+	/// Proof Skipped: Tips Reasons (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[0, 10]`.
+	fn renew_recurring_tips(n: u32, ) -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(3_000_000, 1489)
+			.saturating_add(Weight::from_parts(25_000_000, 2500).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Tips Tips (r:1 w:0)
+	/// Proof Skipped: Tips Tips (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tips MatchedTips (r:0 w:1)
+	/// Proof Skipped: Tips MatchedTips (max_values: None, max_size: None, mode: Measured)
+	fn tip_match() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(12_000_000, 3469)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Tips MatchedTips (r:1 w:1)
+	/// Proof Skipped: Tips MatchedTips (max_values: None, max_size: None, mode: Measured)
+	fn cancel_tip_match() -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(11_000_000, 3469)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Tips TipVestingSchedules (r:1 w:1)
+	/// Proof Skipped: Tips TipVestingSchedules (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: System Account (r:b w:b)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// The range of component `b` is `[1, 10]`.
+	fn claim_tip_vesting(b: u32, ) -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		Weight::from_parts(20_000_000, 3593)
+			.saturating_add(Weight::from_parts(30_000_000, 2603).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(b.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(b.into())))
+	}
+	/// Storage: Tips Reasons (r:1 w:1)
+	/// Proof Skipped: Tips Reasons (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tips Tips (r:1 w:1)
+	/// Proof Skipped: Tips Tips (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tips CrossChainBeneficiaries (r:0 w:1)
+	/// Proof Skipped: Tips CrossChainBeneficiaries (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `r` is `[0, 300]`.
+	fn report_awesome_cross_chain(r: u32, ) -> Weight {
+		// Not generated by the benchmark CLI yet, see the note at the top of the file.
+		// Estimated as `report_awesome` plus the beneficiary on another chain.
+		Weight::from_parts(33_000_000, 3469)
+			.saturating_add(Weight::from_parts(2_601, 0).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
 }